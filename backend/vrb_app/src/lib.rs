@@ -1,5 +1,6 @@
-use std::env;
+use std::{env, sync::Arc, time::Duration};
 
+use actix::SystemService;
 use actix_cors::Cors;
 use actix_multipart::form::tempfile::TempFileConfig;
 use actix_web::{App, HttpServer, http, middleware, web};
@@ -11,20 +12,33 @@ use utoipa_rapidoc::RapiDoc;
 use utoipa_redoc::{Redoc, Servable};
 use utoipa_swagger_ui::SwaggerUi;
 use vrb_authent::{
-    self, config_jwt, user_authent_controller, user_orm, user_recovery_controller, user_recovery_orm, user_registr_controller,
-    user_registr_orm,
+    self, attempt_limiter::AttemptLimiter, config_jwt, user_admin_controller, user_authent_controller, user_email_change_controller,
+    user_email_change_orm, user_nickname_controller, user_nickname_orm, user_orm, user_recovery_controller, user_recovery_orm,
+    user_registr_controller, user_registr_orm, user_totp_controller, user_totp_orm,
+};
+use vrb_chats::{
+    chat_message::SetFanOut, chat_message_controller, chat_message_orm, chat_ws_controller, chat_ws_fan_out::impls::ChatFanOutPg,
+    chat_ws_server::ChatWsServer, config_chat,
 };
-use vrb_chats::{chat_message_controller, chat_message_orm, chat_ws_controller};
 use vrb_common::env_var;
 use vrb_dbase::dbase;
 use vrb_profiles::{config_prfl, profile_controller, profile_orm};
-use vrb_streams::{config_strm, stream_controller, stream_orm};
+use vrb_streams::{
+    config_strm, stream_controller, stream_orm, stream_series_controller, stream_series_orm,
+    stream_series_scheduler::{SetStreamSeriesOrm, StreamSeriesScheduler},
+};
 #[cfg(not(feature = "mockdata"))]
 use vrb_tools::send_email::mailer::impls::MailerApp;
 #[cfg(feature = "mockdata")]
 use vrb_tools::send_email::mailer::tests::MailerApp;
 use vrb_tools::ssl_acceptor;
 use vrb_tools::{config_app, send_email::config_smtp};
+use vrb_webhooks::{
+    config_hook,
+    webhook_controller,
+    webhook_dispatcher::{SetWebhookOrm, WebhookDispatcher},
+    webhook_orm,
+};
 
 pub(crate) mod static_controller;
 pub mod swagger_docs;
@@ -72,13 +86,35 @@ pub async fn server_run() -> std::io::Result<()> {
     // Execute all unapplied migrations for a given migration source
     dbase::run_migration(&mut pool.get().unwrap());
 
+    // Fan-out of chat events between several processes of the server.
+    let config_chat = config_chat::ConfigChat::init_by_env();
+    if config_chat.chat_fan_out == config_chat::FAN_OUT_PG {
+        app_log(&format!("Chat fan-out: PostgreSQL channel \"{}\".", &config_chat.chat_fan_out_channel));
+        let fan_out = Box::new(ChatFanOutPg::new(&db_url, &config_chat.chat_fan_out_channel));
+        let poll = Duration::from_millis(config_chat.chat_fan_out_poll);
+        let sync = Duration::from_secs(config_chat.chat_fan_out_sync);
+        ChatWsServer::from_registry().do_send(SetFanOut(fan_out, poll, sync));
+    }
+
+    // Sending events to the webhooks of users.
+    let config_hook = config_hook::ConfigHook::init_by_env();
+    let webhook_orm = Arc::new(webhook_orm::get_webhook_orm_app(pool.clone()));
+    WebhookDispatcher::from_registry().do_send(SetWebhookOrm(webhook_orm, config_hook));
+
+    // Creating the streams of the recurring series within the horizon.
+    let stream_series_orm = Arc::new(stream_series_orm::get_stream_series_orm_app(pool.clone()));
+    StreamSeriesScheduler::from_registry().do_send(SetStreamSeriesOrm(stream_series_orm, config_strm));
+
+    // The tracker of failed attempts is shared by all worker threads.
+    let attempt_limiter = web::Data::new(AttemptLimiter::from_config(&config_app));
+
     let config_app2 = config_app.clone();
     #[rustfmt::skip]
     let mut srv = HttpServer::new(move || {
         let cors = create_cors(config_app2.clone());
         App::new()
             .app_data(pool.clone())
-            .configure(configure_server(pool.clone()))
+            .configure(configure_server(pool.clone(), web::Data::clone(&attempt_limiter)))
             .wrap(cors)
             .wrap(middleware::Logger::default())
     });
@@ -102,13 +138,13 @@ pub async fn server_run() -> std::io::Result<()> {
     srv.run().await
 }
 
-pub fn configure_server(pool: dbase::DbPool) -> impl FnOnce(&mut web::ServiceConfig) {
+pub fn configure_server(pool: dbase::DbPool, attempt_limiter: web::Data<AttemptLimiter>) -> impl FnOnce(&mut web::ServiceConfig) {
     move |config: &mut web::ServiceConfig| {
         // Adding various configs.
         let config_app0 = config_app::ConfigApp::init_by_env();
         let temp_file_config0 = TempFileConfig::default().clone().directory(config_app0.app_dir_tmp.clone());
 
-        // used: user_recovery_controller, user_registr_controller, static_controller
+        // used: user_recovery_controller, user_registr_controller, user_totp_controller, static_controller
         let config_app = web::Data::new(config_app0);
         // used: user_authent_controller, user_recovery_controller, user_registr_controller
         let config_jwt = web::Data::new(config_jwt::ConfigJwt::init_by_env());
@@ -122,22 +158,34 @@ pub fn configure_server(pool: dbase::DbPool) -> impl FnOnce(&mut web::ServiceCon
         let config_smtp = web::Data::new(config_smtp0.clone());
         // used: profile_controller
         let config_prfl = web::Data::new(config_prfl::ConfigPrfl::init_by_env());
+        // used: chat_ws_controller
+        let config_chat = web::Data::new(config_chat::ConfigChat::init_by_env());
 
         // Adding various entities.
-        // used: user_recovery_controller, user_registr_controller
+        // used: user_recovery_controller, user_registr_controller, user_email_change_controller
         let mailer = web::Data::new(MailerApp::new(config_smtp0));
         // Create "UserOrmApp".
         let user_orm = web::Data::new(user_orm::get_user_orm_app(pool.clone()));
-        // used: user_registr_controller
+        // used: user_registr_controller, user_email_change_controller, user_nickname_controller
         let user_registr_orm = web::Data::new(user_registr_orm::get_user_registr_orm_app(pool.clone()));
         // used: user_recovery_controller
         let user_recovery_orm = web::Data::new(user_recovery_orm::get_user_recovery_orm_app(pool.clone()));
+        // used: user_authent_controller, user_totp_controller
+        let user_totp_orm = web::Data::new(user_totp_orm::get_user_totp_orm_app(pool.clone()));
+        // used: user_email_change_controller
+        let user_email_change_orm = web::Data::new(user_email_change_orm::get_user_email_change_orm_app(pool.clone()));
+        // used: user_nickname_controller, user_registr_controller, user_authent_controller
+        let user_nickname_orm = web::Data::new(user_nickname_orm::get_user_nickname_orm_app(pool.clone()));
         // used: stream_controller, profile_controller
         let stream_orm = web::Data::new(stream_orm::get_stream_orm_app(pool.clone()));
+        // used: stream_series_controller
+        let stream_series_orm = web::Data::new(stream_series_orm::get_stream_series_orm_app(pool.clone()));
         // used: profile_controller
         let profile_orm = web::Data::new(profile_orm::get_profile_orm_app(pool.clone()));
         // used: chat_message_controller, chat_ws_controller
         let chat_message_orm = web::Data::new(chat_message_orm::get_chat_message_orm_app(pool.clone()));
+        // used: webhook_controller
+        let webhook_orm = web::Data::new(webhook_orm::get_webhook_orm_app(pool.clone()));
 
         // Make instance variable of ApiDoc so all worker threads gets the same instance.
         let openapi = swagger_docs::ApiDoc::openapi();
@@ -149,13 +197,22 @@ pub fn configure_server(pool: dbase::DbPool) -> impl FnOnce(&mut web::ServiceCon
             .app_data(web::Data::clone(&config_strm))
             .app_data(web::Data::clone(&config_smtp))
             .app_data(web::Data::clone(&config_prfl))
+            .app_data(web::Data::clone(&config_chat))
             .app_data(web::Data::clone(&mailer))
+            // used: user_authent_controller, user_recovery_controller, user_registr_controller, user_totp_controller,
+            // user_email_change_controller
+            .app_data(web::Data::clone(&attempt_limiter))
             .app_data(web::Data::clone(&user_orm))
             .app_data(web::Data::clone(&user_registr_orm))
             .app_data(web::Data::clone(&user_recovery_orm))
+            .app_data(web::Data::clone(&user_totp_orm))
+            .app_data(web::Data::clone(&user_email_change_orm))
+            .app_data(web::Data::clone(&user_nickname_orm))
             .app_data(web::Data::clone(&stream_orm))
+            .app_data(web::Data::clone(&stream_series_orm))
             .app_data(web::Data::clone(&profile_orm))
             .app_data(web::Data::clone(&chat_message_orm))
+            .app_data(web::Data::clone(&webhook_orm))
             // Add documentation service "Redoc" and "RapiDoc".
             .service(Redoc::with_url("/redoc", openapi.clone()))
             .service(RapiDoc::new("/api-docs/openapi.json").path("/rapidoc"))
@@ -165,11 +222,17 @@ pub fn configure_server(pool: dbase::DbPool) -> impl FnOnce(&mut web::ServiceCon
             .configure(user_recovery_controller::configure())
             .configure(user_registr_controller::configure())
             .configure(user_authent_controller::configure())
+            .configure(user_totp_controller::configure())
+            .configure(user_email_change_controller::configure())
+            .configure(user_nickname_controller::configure())
+            .configure(user_admin_controller::configure())
             .configure(stream_controller::configure())
+            .configure(stream_series_controller::configure())
             .configure(profile_controller::configure())
             .configure(static_controller::configure())
             .configure(chat_message_controller::configure())
-            .configure(chat_ws_controller::configure());
+            .configure(chat_ws_controller::configure())
+            .configure(webhook_controller::configure());
     }
 }
 
@@ -3,22 +3,28 @@ use utoipa::{
     openapi::security::{/*ApiKey, ApiKeyValue,*/ HttpAuthScheme, HttpBuilder, SecurityScheme},
 };
 use vrb_authent::{
-    user_authent_controller, user_authent_models, user_recovery_controller, user_recovery_models, user_registr_controller,
-    user_registr_models,
+    user_admin_controller, user_admin_models, user_authent_controller, user_authent_models, user_email_change_controller,
+    user_email_change_models, user_nickname_controller, user_nickname_models, user_recovery_controller, user_recovery_models,
+    user_registr_controller, user_registr_models, user_totp_controller, user_totp_models,
 };
 use vrb_chats::{chat_event_ws, chat_message_controller, chat_message_models, chat_ws_controller};
 use vrb_common::api_error;
-use vrb_dbase::{enm_stream_state, enm_user_role};
+use vrb_dbase::{enm_delivery_status, enm_stream_state, enm_user_role};
 use vrb_profiles::{profile_controller, profile_models};
-use vrb_streams::{stream_controller, stream_models};
+use vrb_streams::{stream_controller, stream_models, stream_series_controller, stream_series_models};
+use vrb_webhooks::{webhook_controller, webhook_models};
 
 #[derive(OpenApi)]
 #[openapi(
     paths(
         user_authent_controller::users_uniqueness,
         user_authent_controller::login,
+        user_authent_controller::login_totp,
         user_authent_controller::logout,
         user_authent_controller::update_token,
+        user_authent_controller::get_sessions,
+        user_authent_controller::delete_session,
+        user_authent_controller::delete_sessions,
         //
         user_registr_controller::registration,
         user_registr_controller::confirm_registration,
@@ -28,6 +34,24 @@ use vrb_streams::{stream_controller, stream_models};
         user_recovery_controller::confirm_recovery,
         user_recovery_controller::recovery_clear_for_expired,
         //
+        user_totp_controller::post_totp,
+        user_totp_controller::get_totp_qr_code,
+        user_totp_controller::put_totp,
+        user_totp_controller::delete_totp,
+        //
+        user_email_change_controller::email_change,
+        user_email_change_controller::confirm_email_change,
+        user_email_change_controller::email_change_clear_for_expired,
+        //
+        user_nickname_controller::nickname_change,
+        user_nickname_controller::get_nickname_history,
+        //
+        user_admin_controller::get_users,
+        user_admin_controller::put_user_role,
+        user_admin_controller::put_user_suspend,
+        user_admin_controller::put_user_unsuspend,
+        user_admin_controller::delete_user_sessions,
+        //
         profile_controller::get_profile_by_id,
         profile_controller::get_profile_mini_by_id,
         profile_controller::get_profile_config,
@@ -46,17 +70,38 @@ use vrb_streams::{stream_controller, stream_models};
         stream_controller::put_toggle_state,
         stream_controller::put_stream,
         stream_controller::delete_stream,
+        stream_controller::get_stream_moderators,
+        stream_controller::post_stream_moderator,
+        stream_controller::delete_stream_moderator,
+        stream_controller::get_stream_timeline,
+        //
+        stream_series_controller::get_stream_series,
+        stream_series_controller::post_stream_series,
+        stream_series_controller::get_stream_series_streams,
+        stream_series_controller::put_stream_series_stream,
+        stream_series_controller::delete_stream_series,
         //
         chat_message_controller::get_chat_message,
         chat_message_controller::post_chat_message,
         chat_message_controller::put_chat_message,
         chat_message_controller::delete_chat_message,
+        chat_message_controller::get_chat_message_search,
+        chat_message_controller::get_chat_message_export,
+        chat_message_controller::get_chat_message_logs,
+        chat_message_controller::get_chat_mentions,
+        chat_message_controller::put_chat_mentions_read,
         chat_message_controller::get_blocked_users_names,
         chat_message_controller::get_blocked_users,
         chat_message_controller::post_blocked_user,
         chat_message_controller::delete_blocked_user,
         //
         chat_ws_controller::get_ws_chat,
+        //
+        webhook_controller::get_webhooks,
+        webhook_controller::post_webhook,
+        webhook_controller::put_webhook,
+        webhook_controller::delete_webhook,
+        webhook_controller::get_webhook_deliveries,
     ),
     components(
         schemas(
@@ -70,6 +115,8 @@ use vrb_streams::{stream_controller, stream_models};
             user_authent_models::LoginResponseDto,          // ::login
             user_authent_models::UserTokenDto,              // ::update_token
             user_authent_models::UserTokenResponseDto,      // ::update_token
+            user_authent_models::SessionDto,                // ::get_sessions, ::delete_session
+            user_authent_models::DeleteSessionsResponseDto, // ::delete_sessions
             // user_registr_controller
             user_registr_models::RegistrUserDto,                         // ::registration
             user_registr_models::RegistrUserResponseDto,                 // ::registration
@@ -80,6 +127,25 @@ use vrb_streams::{stream_controller, stream_models};
             user_recovery_models::RecoveryUserResponseDto,            // ::recovery
             user_recovery_models::ConfirmRecoveryUserResponseDto,     // ::confirm_recovery
             user_recovery_models::RecoveryClearForExpiredResponseDto, // ::recovery_clear_for_expired
+            // user_totp_controller
+            user_totp_models::TotpSetupResponseDto,      // ::post_totp
+            user_totp_models::TotpCodeDto,               // ::put_totp, ::delete_totp
+            user_totp_models::TotpRecoveryCodesDto,      // ::put_totp
+            user_totp_models::LoginChallengeResponseDto, // user_authent_controller::login
+            user_totp_models::LoginTotpDto,              // user_authent_controller::login_totp
+            // user_email_change_controller
+            user_email_change_models::EmailChangeDto,                        // ::email_change
+            user_email_change_models::EmailChangeResponseDto,                // ::email_change
+            user_email_change_models::ConfirmEmailChangeResponseDto,         // ::confirm_email_change
+            user_email_change_models::EmailChangeClearForExpiredResponseDto, // ::email_change_clear_for_expired
+            // user_nickname_controller
+            user_nickname_models::NicknameDto,        // ::nickname_change
+            user_nickname_models::NicknameHistoryDto, // ::nickname_change, ::get_nickname_history
+            // user_admin_controller
+            user_admin_models::SearchUserDto,     // ::get_users
+            user_admin_models::UserAdminDto,      // ::get_users, ::put_user_role, ::put_user_suspend, ::put_user_unsuspend
+            user_admin_models::UserAdminPageDto,  // ::get_users
+            user_admin_models::ModifyUserRoleDto, // ::put_user_role
 
             // profile_controller
             // ::get_profile_by_id, ::get_profile_current, ::put_profile, ::put_profile_new_password,
@@ -103,6 +169,17 @@ use vrb_streams::{stream_controller, stream_models};
             stream_models::CreateStreamInfoDto,   // ::post_stream
             stream_models::ModifyStreamInfoDto,   // ::put_stream
             stream_models::ToggleStreamStateDto,  // ::put_toggle_state
+            stream_models::StreamModeratorDto,    // ::get_stream_moderators
+            stream_models::CreateStreamModeratorDto, // ::post_stream_moderator
+            stream_models::StreamTimelineDto,     // ::get_stream_timeline
+            stream_models::StreamStateLogDto,     // ::get_stream_timeline
+
+            // stream_series_controller
+            // ::get_stream_series, ::post_stream_series, ::delete_stream_series
+            stream_series_models::StreamSeriesDto,
+            stream_series_models::CreateStreamSeriesDto, // ::post_stream_series
+            stream_series_models::ModifyStreamSeriesDto, // ::put_stream_series_stream
+            stream_series_models::SeriesScope,           // ::put_stream_series_stream
 
             // chat_message_controller
             // ::get_chat_message, ::post_chat_message, ::put_chat_message, ::delete_chat_message
@@ -110,36 +187,67 @@ use vrb_streams::{stream_controller, stream_models};
             chat_message_models::SearchChatMessageDto, // ::get_chat_message
             chat_message_models::CreateChatMessageDto, // ::post_chat_message
             chat_message_models::ModifyChatMessageDto, // ::put_chat_message
+            chat_message_models::SearchChatTextDto,    // ::get_chat_message_search
+            chat_message_models::ChatMessageFoundDto,  // ::get_chat_message_search
+            chat_message_models::ChatMessageFoundPageDto, // ::get_chat_message_search
+            chat_message_models::ExportChatMessagesDto, // ::get_chat_message_export
+            chat_message_models::TranscriptFormat,     // ::get_chat_message_export
+            chat_message_models::ChatTranscriptItemDto, // ::get_chat_message_export
+            chat_message_models::ChatMessageLogDto,    // ::get_chat_message_logs
+            chat_message_models::ChatMentionDto,       // ::get_chat_mentions, ::put_chat_mentions_read
+            chat_message_models::ReadChatMentionsDto,  // ::put_chat_mentions_read
             chat_message_models::BlockedUserDto,       // ::get_blocked_users, ::post_blocked_user, ::delete_blocked_user
             chat_message_models::CreateBlockedUserDto, // ::post_blocked_user
             chat_message_models::DeleteBlockedUserDto, // ::delete_blocked_user
 
             // chat_ws_controller
+            chat_event_ws::BacklogEWS, // ::get_ws_chat
             chat_event_ws::BlockEWS,   // ::get_ws_chat
             chat_event_ws::CountEWS,   // ::get_ws_chat
             chat_event_ws::EchoEWS,    // ::get_ws_chat
             chat_event_ws::ErrEWS,     // ::get_ws_chat
             chat_event_ws::JoinEWS,    // ::get_ws_chat
             chat_event_ws::LeaveEWS,   // ::get_ws_chat
+            chat_event_ws::MentionEWS, // ::get_ws_chat
             chat_event_ws::MsgEWS,     // ::get_ws_chat
             chat_event_ws::MsgCutEWS,  // ::get_ws_chat
             chat_event_ws::MsgPutEWS,  // ::get_ws_chat
             chat_event_ws::MsgRmvEWS,  // ::get_ws_chat
             chat_event_ws::NameEWS,    // ::get_ws_chat
+            chat_event_ws::PinEWS,     // ::get_ws_chat
             chat_event_ws::PrmBoolEWS, // ::get_ws_chat
             chat_event_ws::PrmIntEWS,  // ::get_ws_chat
             chat_event_ws::PrmStrEWS,  // ::get_ws_chat
+            chat_event_ws::SlowModeEWS, // ::get_ws_chat
+            chat_event_ws::StreamEWS,  // ::get_ws_chat
             chat_event_ws::UnblockEWS, // ::get_ws_chat
+            chat_event_ws::UnpinEWS,   // ::get_ws_chat
+
+            // webhook_controller
+            // ::get_webhooks, ::post_webhook, ::put_webhook, ::delete_webhook
+            webhook_models::WebhookDto,
+            webhook_models::WebhookEvent,
+            webhook_models::WebhookPayload,
+            webhook_models::CreateWebhookDto,   // ::post_webhook
+            webhook_models::ModifyWebhookDto,   // ::put_webhook
+            webhook_models::WebhookDeliveryDto, // ::get_webhook_deliveries
+            enm_delivery_status::DeliveryStatus, // ::get_webhook_deliveries
         )
     ),
     tags(
         (name = "user_authent_controller", description = "User authorization management (Endpoints)."),
         (name = "user_registr_controller", description = "User registration management (Endpoints)."),
         (name = "user_recovery_controller", description = "Manage user password recovery (endpoints)."),
+        (name = "user_totp_controller", description = "Manage two-factor authentication (TOTP) of the user (Endpoints)."),
+        (name = "user_email_change_controller", description = "Manage the change of the user's email (Endpoints)."),
+        (name = "user_nickname_controller", description = "Manage the change of the user's nickname (Endpoints)."),
+        (name = "user_admin_controller", description = "User management by the administrator (Endpoints)."),
         (name = "profile_controller", description = "Managing user profile information (Endpoints)."),
         (name = "stream_controller", description = "Stream management. (Endpoints)."),
+        (name = "stream_series_controller", description = "Manage the recurring series of streams (Endpoints)."),
         (name = "chat_message_controller", description = "Managing data for chat work (endpoints)."),
         (name = "chat_ws_controller", description = "Manage messages in chat (Endpoints)."),
+        (name = "webhook_controller", description = "Manage the webhooks of stream and chat events (Endpoints)."),
     ),
     modifiers(&SecurityAddon)
 )]
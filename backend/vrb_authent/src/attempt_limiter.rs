@@ -0,0 +1,160 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use actix_web::http::StatusCode;
+use chrono::{DateTime, Duration, Utc};
+use log::error;
+use vrb_common::{
+    api_error::{ApiError, code_to_str},
+    err,
+};
+use vrb_tools::config_app::ConfigApp;
+
+// The name of the error parameter with the number of seconds until the lock is released.
+pub const PARAM_RETRY_AFTER: &str = "retryAfter";
+// The maximum exponent for calculating the lockout duration (to avoid overflow).
+const LOCKOUT_POW_MAX: u32 = 16;
+
+// Key prefixes for the different checked actions.
+pub const KEY_LOGIN: &str = "login";
+pub const KEY_LOGIN_IP: &str = "login_ip";
+pub const KEY_RECOVERY: &str = "recovery";
+pub const KEY_RECOVERY_IP: &str = "recovery_ip";
+pub const KEY_REGISTR: &str = "registr";
+pub const KEY_REGISTR_IP: &str = "registr_ip";
+pub const KEY_EMAIL_CHANGE: &str = "email_change";
+pub const KEY_TOTP: &str = "totp";
+
+/// Create a key for the attempt limiter from the prefix and the value.
+pub fn get_key(prefix: &str, value: &str) -> String {
+    format!("{}:{}", prefix, value.to_lowercase())
+}
+
+#[derive(Debug, Clone)]
+struct AttemptEntry {
+    // Number of failed attempts.
+    count: u32,
+    // Time of the last failed attempt.
+    last_at: DateTime<Utc>,
+    // Time until which attempts are blocked.
+    locked_until: Option<DateTime<Utc>>,
+}
+
+/// Tracker of failed attempts (by nickname, email, IP address).
+///
+/// After "max_attempts" failed attempts, the key is locked for "lockout" seconds.
+/// Each subsequent failed attempt doubles the lock duration (but not more than "lockout_max" seconds).
+/// The counter is reset if there were no failed attempts during the "window" seconds.
+#[derive(Debug)]
+pub struct AttemptLimiter {
+    // Number of failed attempts before blocking. (The value 0 disables the limiter.)
+    pub max_attempts: u32,
+    // Period (in seconds) during which failed attempts are counted.
+    pub window: i64,
+    // Initial lock duration (in seconds).
+    pub lockout: i64,
+    // Maximum lock duration (in seconds).
+    pub lockout_max: i64,
+    entries: Mutex<HashMap<String, AttemptEntry>>,
+}
+
+impl AttemptLimiter {
+    pub fn new(max_attempts: u32, window: i64, lockout: i64, lockout_max: i64) -> Self {
+        AttemptLimiter {
+            max_attempts,
+            window,
+            lockout,
+            lockout_max: lockout_max.max(lockout),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+    /// Create a limiter with the parameters from the application configuration.
+    pub fn from_config(config_app: &ConfigApp) -> Self {
+        #[rustfmt::skip]
+        let (max_attempts, window, lockout, lockout_max) = (
+            config_app.app_attempt_max, config_app.app_attempt_window, config_app.app_attempt_lockout, config_app.app_attempt_lockout_max,
+        );
+        AttemptLimiter::new(max_attempts, window, lockout, lockout_max)
+    }
+    /// Get the number of seconds until the lock is released (the maximum value among the keys).
+    pub fn check(&self, keys: &[String]) -> Option<i64> {
+        if self.max_attempts == 0 {
+            return None;
+        }
+        let now = Utc::now();
+        let entries = self.entries.lock().unwrap();
+        keys.iter()
+            .filter_map(|key| entries.get(key).and_then(|entry| entry.locked_until))
+            .filter(|locked_until| *locked_until > now)
+            .map(|locked_until| (locked_until - now).num_seconds() + 1)
+            .max()
+    }
+    /// Register a failed attempt for each of the keys.
+    pub fn add_failure(&self, keys: &[String]) {
+        if self.max_attempts == 0 {
+            return;
+        }
+        let now = Utc::now();
+        let mut entries = self.entries.lock().unwrap();
+        // Remove entries that are no longer relevant.
+        entries.retain(|_, entry| !self.is_expired(entry, now));
+
+        for key in keys.iter() {
+            let entry = entries.entry(key.clone()).or_insert(AttemptEntry {
+                count: 0,
+                last_at: now,
+                locked_until: None,
+            });
+            entry.count += 1;
+            entry.last_at = now;
+            if entry.count >= self.max_attempts {
+                // Each subsequent failed attempt doubles the lock duration.
+                let pow = (entry.count - self.max_attempts).min(LOCKOUT_POW_MAX);
+                let lockout = self.lockout.saturating_mul(2_i64.pow(pow)).min(self.lockout_max);
+                entry.locked_until = Some(now + Duration::seconds(lockout));
+            }
+        }
+    }
+    /// Reset the counters of failed attempts for the keys.
+    pub fn reset(&self, keys: &[String]) {
+        let mut entries = self.entries.lock().unwrap();
+        for key in keys.iter() {
+            entries.remove(key);
+        }
+    }
+    /// Checks whether the entry can be deleted (the lock has expired and there were no attempts during the "window").
+    fn is_expired(&self, entry: &AttemptEntry, now: DateTime<Utc>) -> bool {
+        let is_unlocked = entry.locked_until.map(|locked_until| locked_until <= now).unwrap_or(true);
+        let last_at = entry.locked_until.map_or(entry.last_at, |locked_until| locked_until.max(entry.last_at));
+        is_unlocked && (now - last_at).num_seconds() >= self.window
+    }
+}
+
+/// If at least one of the keys is locked, then return an error 429("TooManyRequests", "too_many_attempts") with "retryAfter".
+pub fn is_too_many_attempts(attempt_limiter: &AttemptLimiter, keys: &[String]) -> Result<(), ApiError> {
+    if let Some(retry_after) = attempt_limiter.check(keys) {
+        let msg = format!("{}: {}", PARAM_RETRY_AFTER, retry_after);
+        error!("{}-{}; {}", code_to_str(StatusCode::TOO_MANY_REQUESTS), err::MSG_TOO_MANY_ATTEMPTS, &msg);
+        let mut api_error = ApiError::new(429, err::MSG_TOO_MANY_ATTEMPTS); // 429
+        api_error.add_param(PARAM_RETRY_AFTER.into(), &retry_after);
+        return Err(api_error);
+    }
+    Ok(())
+}
+
+#[cfg(any(test, feature = "mockdata"))]
+pub mod tests {
+    use actix_web::web;
+    use vrb_tools::config_app;
+
+    use crate::attempt_limiter::AttemptLimiter;
+
+    pub fn get_attempt_limiter() -> AttemptLimiter {
+        AttemptLimiter::from_config(&config_app::get_test_config())
+    }
+    pub fn cfg_attempt_limiter(attempt_limiter: AttemptLimiter) -> impl FnOnce(&mut web::ServiceConfig) {
+        move |config: &mut web::ServiceConfig| {
+            let data_attempt_limiter = web::Data::new(attempt_limiter);
+            config.app_data(web::Data::clone(&data_attempt_limiter));
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use std::{rc::Rc, time::Instant as tm};
 
 use actix_web::{FromRequest, HttpMessage, dev, error, http::StatusCode, web};
+use chrono::Utc;
 use futures_util::{
     FutureExt,
     future::{LocalBoxFuture, Ready, ready},
@@ -25,6 +26,12 @@ use crate::{
 
 // 500 Internal Server Error - Authentication: The entity "user" was not received from the request.
 pub const MSG_USER_NOT_RECEIVED_FROM_REQUEST: &str = "user_not_received_from_request";
+// 500 Internal Server Error - Authentication: The entity "session" was not received from the request.
+pub const MSG_SESSION_NOT_RECEIVED_FROM_REQUEST: &str = "session_not_received_from_request";
+// 403 Forbidden - The user account is suspended by the administrator.
+pub const MSG_USER_SUSPENDED: &str = "user_suspended";
+// The minimum interval (in seconds) between updates of the "last_seen_at" value of the session.
+pub const SESSION_LAST_SEEN_INTERVAL: i64 = 60;
 
 pub struct Authenticated(User);
 
@@ -50,6 +57,31 @@ impl std::ops::Deref for Authenticated {
     }
 }
 
+/// The session (device) through which the current user is authenticated.
+pub struct AuthenticatedSession(Session);
+
+impl FromRequest for AuthenticatedSession {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &actix_web::HttpRequest, _payload: &mut dev::Payload) -> Self::Future {
+        let value = req.extensions().get::<Session>().cloned();
+        let result = match value {
+            Some(session) => Ok(AuthenticatedSession(session)),
+            None => Err(error::ErrorInternalServerError(ApiError::new(500, MSG_SESSION_NOT_RECEIVED_FROM_REQUEST))),
+        };
+        ready(result)
+    }
+}
+
+impl std::ops::Deref for AuthenticatedSession {
+    type Target = Session;
+    /// Implement the deref method to access the inner "Session" value of AuthenticatedSession.
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
 pub struct RequireAuth {
     pub allowed_roles: Rc<Vec<UserRole>>,
 }
@@ -153,17 +185,25 @@ where
             let user_orm = req.app_data::<web::Data<UserOrmApp>>().unwrap().get_ref();
 
             // Token verification:
-            // 1. Search for a session by "id" from the token;
-            let opt_session = user_orm.get_session_by_id(user_id).map_err(|e| {
+            // 1. Search for the sessions (devices) of the user by "id" from the token;
+            let sessions = user_orm.get_sessions_by_user_id(user_id).map_err(|e| {
                 error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
                 return ApiError::create(507, err::MSG_DATABASE, &e); // 507
             })?;
-            // If the session does not exist, return error 406("NotAcceptable", "session_not_found; user_id: {}").
-            let session = is_session_not_found(opt_session, user_id)?;
-            // 2. Compare "num_token" from session with "num_token" from token;
-            // To block hacking, the session contains a numeric value "num_token".
-            // If session.num_token is not equal to token.num_token,return error401(c)("Unauthorized","unacceptable_token_num; user_id: {}")
-            let _ = is_unacceptable_token_num(&session, num_token, user_id)?;
+            // If there are no sessions, return error 406("NotAcceptable", "session_not_found; user_id: {}").
+            let sessions = is_session_not_found(sessions, user_id)?;
+            // 2. Find the session with the "num_token" from the token;
+            // To block hacking, each session contains a numeric value "num_token".
+            // If no session has token.num_token, return error401(c)("Unauthorized","unacceptable_token_num; user_id: {}")
+            let mut session = is_unacceptable_token_num(&sessions, num_token, user_id)?;
+            // Mark the session as active (no more than once per interval).
+            if (Utc::now() - session.last_seen_at).num_seconds() >= SESSION_LAST_SEEN_INTERVAL {
+                let opt_session = user_orm.modify_session(session.id, None).map_err(|e| {
+                    error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+                    ApiError::create(507, err::MSG_DATABASE, &e) // 507
+                })?;
+                session = opt_session.unwrap_or(session);
+            }
             // 3. If everything is correct, then search for the user by "user_id" from the token;
             let opt_user = user_orm.get_user_by_id(user_id, false).map_err(|e| {
                 error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
@@ -171,6 +211,8 @@ where
             })?;
             // If the user is not present, return error401(d)("Unauthorized", "unacceptable_token_id; user_id: {}").
             let user = is_unacceptable_token_id(opt_user, user_id)?;
+            // If the user account is suspended, return error403("Forbidden", "user_suspended; user_id: {}").
+            let user = is_user_suspended(user)?;
 
             if let Some(timer) = timer {
                 info!("authentication() time: {}", format!("{:.2?}", timer.elapsed()));
@@ -180,6 +222,7 @@ where
             if allowed_roles.contains(&user.role) {
                 // Insert user information into request extensions.
                 req.extensions_mut().insert::<User>(user);
+                req.extensions_mut().insert::<Session>(session);
                 // Call the wrapped service to handle the request
                 let res = srv.call(req).await?;
                 Ok(res)
@@ -193,26 +236,27 @@ where
     }
 }
 
-/// If the session is missing, then return an error406("NotAcceptable", "session_not_found; user_id: {}").
-pub fn is_session_not_found(opt_session: Option<Session>, user_id: i32) -> Result<Session, ApiError> {
-    let session = opt_session.ok_or_else(|| {
+/// If the sessions are missing, then return an error406("NotAcceptable", "session_not_found; user_id: {}").
+pub fn is_session_not_found(sessions: Vec<Session>, user_id: i32) -> Result<Vec<Session>, ApiError> {
+    if sessions.is_empty() {
         // There is no session for this user.
         let msg = format!("user_id: {}", user_id);
         error!("{}-{}; {}", code_to_str(StatusCode::NOT_ACCEPTABLE), err::MSG_SESSION_NOT_FOUND, &msg);
-        ApiError::create(406, err::MSG_SESSION_NOT_FOUND, &msg) // 406
-    })?;
-    Ok(session)
+        return Err(ApiError::create(406, err::MSG_SESSION_NOT_FOUND, &msg)); // 406
+    }
+    Ok(sessions)
 }
-pub fn is_unacceptable_token_num(session: &Session, num_token: i32, user_id: i32) -> Result<(), ApiError> {
+/// If there is no session with the specified "num_token", then return an error401(c)("Unauthorized", "unacceptable_token_num; user_id: {}").
+pub fn is_unacceptable_token_num(sessions: &[Session], num_token: i32, user_id: i32) -> Result<Session, ApiError> {
     // Each session contains an additional numeric value.
     // Compare an additional numeric value from the session and from the token.
-    if session.num_token.is_none() || session.num_token.unwrap() != num_token {
+    let opt_session = sessions.iter().find(|session| session.num_token == num_token);
+    opt_session.cloned().ok_or_else(|| {
         // If they do not match, then this is an error.
         let msg = format!("user_id: {}", user_id);
         error!("{}-{}; {}", code_to_str(StatusCode::UNAUTHORIZED), err::MSG_UNACCEPTABLE_TOKEN_NUM, &msg); // 401(c)
-        return Err(ApiError::create(401, err::MSG_UNACCEPTABLE_TOKEN_NUM, &msg));
-    }
-    Ok(())
+        ApiError::create(401, err::MSG_UNACCEPTABLE_TOKEN_NUM, &msg)
+    })
 }
 /// If the user is missing, then return an error401(d)("Unauthorized", "unacceptable_token_id; user_id: {}").
 pub fn is_unacceptable_token_id(opt_user: Option<User>, user_id: i32) -> Result<User, ApiError> {
@@ -223,3 +267,12 @@ pub fn is_unacceptable_token_id(opt_user: Option<User>, user_id: i32) -> Result<
     })?;
     Ok(user)
 }
+/// If the user account is suspended, then return an error403("Forbidden", "user_suspended; user_id: {}").
+pub fn is_user_suspended(user: User) -> Result<User, ApiError> {
+    if user.suspended_at.is_some() {
+        let msg = format!("user_id: {}", user.id);
+        error!("{}-{}; {}", code_to_str(StatusCode::FORBIDDEN), MSG_USER_SUSPENDED, &msg);
+        return Err(ApiError::create(403, MSG_USER_SUSPENDED, &msg)); // 403
+    }
+    Ok(user)
+}
@@ -7,6 +7,7 @@ mod tests {
         http::{StatusCode, header},
         test,
     };
+    use chrono::Utc;
     use serde_json;
     use vrb_common::{
         api_error::{ApiError, code_to_str},
@@ -15,7 +16,7 @@ mod tests {
     use vrb_tools::{token_coding, token_data};
 
     use crate::{
-        authentication::RequireAuth,
+        authentication::{MSG_USER_SUSPENDED, RequireAuth},
         config_jwt,
         user_models::Session,
         user_orm::tests::{ADMIN, USER, USER1_ID, UserOrmTest},
@@ -127,7 +128,7 @@ mod tests {
         let data_u = UserOrmTest::users(&[USER]);
         let config_jwt = config_jwt::tests::get_config();
         let user1_id = data_u.0.get(0).unwrap().id;
-        let num_token1 = data_u.1.get(0).unwrap().num_token.unwrap();
+        let num_token1 = data_u.1.get(0).unwrap().num_token;
         let jwt_secret: &[u8] = config_jwt.jwt_secret.as_bytes();
         let token1 = token_coding::encode_token(user1_id, num_token1, &jwt_secret, -config_jwt.jwt_access).unwrap();
         #[rustfmt::skip]
@@ -172,7 +173,7 @@ mod tests {
     async fn test_authentication_middelware_valid_token_non_existent_user() {
         let mut data_u = UserOrmTest::users(&[USER]);
         let user2_id = USER1_ID + 1;
-        data_u.1 = vec![Session::new(user2_id, Some(config_jwt::tests::get_num_token(user2_id)))];
+        data_u.1 = vec![Session::new(1, user2_id, config_jwt::tests::get_num_token(user2_id))];
         let token2 = config_jwt::tests::get_token(user2_id);
         #[rustfmt::skip]
         let app = test::init_service(
@@ -194,7 +195,7 @@ mod tests {
     async fn test_authentication_middelware_valid_token_non_existent_num() {
         let mut data_u = UserOrmTest::users(&[USER]);
         let user2_id = USER1_ID + 1;
-        data_u.1 = vec![Session::new(user2_id, Some(config_jwt::tests::get_num_token(USER1_ID)))];
+        data_u.1 = vec![Session::new(1, user2_id, config_jwt::tests::get_num_token(USER1_ID))];
         let token2 = config_jwt::tests::get_token(user2_id);
         #[rustfmt::skip]
         let app = test::init_service(
@@ -233,4 +234,26 @@ mod tests {
         assert_eq!(api_err.code, code_to_str(StatusCode::FORBIDDEN));
         assert_eq!(api_err.message, err::MSG_ACCESS_DENIED);
     }
+    #[actix_web::test]
+    async fn test_authentication_middleware_user_suspended() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let mut data_u = UserOrmTest::users(&[USER]);
+        data_u.0.get_mut(0).unwrap().suspended_at = Some(Utc::now());
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(handler_with_auth)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        let req = test::TestRequest::get().insert_header(header_auth(&token1)).to_request();
+        let result = test::try_call_service(&app, req).await.err();
+        let err = result.expect(MSG_ERROR_WAS_EXPECTED);
+
+        let actual_status = err.as_response_error().status_code();
+        assert_eq!(actual_status, StatusCode::FORBIDDEN); // 403
+
+        let api_err: ApiError = serde_json::from_str(&err.to_string()).expect(MSG_FAILED_TO_DESER);
+        assert_eq!(api_err.code, code_to_str(StatusCode::FORBIDDEN));
+        assert_eq!(api_err.message, format!("{}; user_id: {}", MSG_USER_SUSPENDED, USER1_ID));
+    }
 }
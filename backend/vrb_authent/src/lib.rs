@@ -1,10 +1,22 @@
+pub mod attempt_limiter;
 pub mod authentication;
 pub mod authentication_test;
 pub mod config_jwt;
+pub mod user_admin_controller;
+pub mod user_admin_models;
+pub mod user_admin_test;
 pub mod user_authent_controller;
 pub mod user_authent_models;
 pub mod user_authent_test;
+pub mod user_email_change_controller;
+pub mod user_email_change_models;
+pub mod user_email_change_orm;
+pub mod user_email_change_test;
 pub mod user_models;
+pub mod user_nickname_controller;
+pub mod user_nickname_models;
+pub mod user_nickname_orm;
+pub mod user_nickname_test;
 pub mod user_orm;
 pub mod user_recovery_controller;
 pub mod user_recovery_models;
@@ -13,4 +25,8 @@ pub mod user_recovery_test;
 pub mod user_registr_controller;
 pub mod user_registr_models;
 pub mod user_registr_orm;
-pub mod user_registr_test;
\ No newline at end of file
+pub mod user_registr_test;
+pub mod user_totp_controller;
+pub mod user_totp_models;
+pub mod user_totp_orm;
+pub mod user_totp_test;
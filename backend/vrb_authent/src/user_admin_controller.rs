@@ -0,0 +1,508 @@
+use std::{ops::Deref, time::Instant as tm};
+
+use actix_web::{HttpRequest, HttpResponse, delete, get, http::StatusCode, put, web};
+use chrono::Utc;
+use log::{Level::Info, error, info, log_enabled};
+use utoipa;
+use vrb_common::{
+    api_error::{ApiError, code_to_str},
+    err, parser,
+    validators::{Validator, msg_validation},
+};
+use vrb_dbase::enm_user_role::UserRole;
+
+#[cfg(not(all(test, feature = "mockdata")))]
+use crate::user_orm::impls::UserOrmApp;
+#[cfg(all(test, feature = "mockdata"))]
+use crate::user_orm::tests::UserOrmApp;
+use crate::{
+    authentication::{Authenticated, RequireAuth},
+    user_admin_models::{self, ModifyUserRoleDto, SearchUser, SearchUserDto, UserAdminDto, UserAdminPageDto},
+    user_authent_models::DeleteSessionsResponseDto,
+    user_models::{ModifyUser, User},
+    user_orm::UserOrm,
+};
+
+// 409 Conflict - The administrator cannot perform this action on their own account.
+pub const MSG_ACTION_ON_OWN_ACCOUNT: &str = "action_on_own_account";
+
+pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
+    |config: &mut web::ServiceConfig| {
+        config
+            // GET /api/admin/users
+            .service(get_users)
+            // PUT /api/admin/users/{id}/role
+            .service(put_user_role)
+            // PUT /api/admin/users/{id}/suspend
+            .service(put_user_suspend)
+            // PUT /api/admin/users/{id}/unsuspend
+            .service(put_user_unsuspend)
+            // DELETE /api/admin/users/{id}/sessions
+            .service(delete_user_sessions);
+    }
+}
+
+/// Get the user ID from the request path. If it is not a number, then return an error 416.
+fn get_path_user_id(request: &HttpRequest) -> Result<i32, ApiError> {
+    let id_str = request.match_info().query("id").to_string();
+    parser::parse_i32(&id_str).map_err(|e| {
+        let msg = &format!("`{}` - {}", "id", &e);
+        #[rustfmt::skip]
+        error!("{}-{}; {}", code_to_str(StatusCode::RANGE_NOT_SATISFIABLE), err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg);
+        ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg) // 416
+    })
+}
+
+/// If the action is performed on the administrator's own account, then return an error 409.
+fn is_own_account(curr_user_id: i32, user_id: i32) -> Result<(), ApiError> {
+    if curr_user_id == user_id {
+        let msg = format!("user_id: {}", user_id);
+        error!("{}-{}; {}", code_to_str(StatusCode::CONFLICT), MSG_ACTION_ON_OWN_ACCOUNT, &msg);
+        return Err(ApiError::create(409, MSG_ACTION_ON_OWN_ACCOUNT, &msg)); // 409
+    }
+    Ok(())
+}
+
+/// If the user is missing, then return an error 404.
+fn is_user_not_found(opt_user: Option<User>, user_id: i32) -> Result<User, ApiError> {
+    opt_user.ok_or_else(|| {
+        let msg = format!("user_id: {}", user_id);
+        error!("{}-{}; {}", code_to_str(StatusCode::NOT_FOUND), err::MSG_USER_NOT_FOUND, &msg);
+        ApiError::create(404, err::MSG_USER_NOT_FOUND, &msg) // 404
+    })
+}
+
+/// get_users
+///
+/// Search for users by the specified parameters (paginated).
+///
+/// Request structure:
+/// ```text
+/// {
+///   nickname?: String,           // optional
+///   email?: String,              // optional
+///   role?: String,               // optional
+///   createdFrom?: DateTime<Utc>, // optional
+///   createdTo?: DateTime<Utc>,   // optional
+///   page?: number,               // optional
+///   limit?: number,              // optional
+/// }
+/// Where:
+/// "nickname" - part of the user's nickname (case insensitive);
+/// "email" - part of the user's email (case insensitive);
+/// "role" - user role ("admin", "moderator", "user");
+/// "createdFrom" - users created at or after the specified date and time (in Utc-format);
+/// "createdTo" - users created before the specified date and time (in Utc-format);
+/// "page" - page number, stratified from 1 (1 by default);
+/// "limit" - number of records on the page (10 by default);
+/// ```
+/// One could call with following curl.
+/// ```text
+/// curl -i -X GET http://localhost:8080/api/admin/users?nickname=oliver&page=1
+/// ```
+/// Could be called with all fields with the next curl.
+/// ```text
+/// curl -i -X GET http://localhost:8080/api/admin/users?nickname=oliver&email=gmail&role=user \
+///     &createdFrom=2030-01-01T00:00:00.000Z&createdTo=2030-02-01T00:00:00.000Z&page=1&limit=10
+/// ```
+/// Response structure:
+/// ```text
+/// {
+///   list: [UserAdminDto],
+///   limit: number,
+///   count: number,
+///   page: number,
+///   pages: number,
+/// }
+/// Where:
+/// "list"  - array of users;
+/// "limit" - number of records on the page;
+/// "count" - total number of records;
+/// "page"  - current page number (stratified from 1);
+/// "pages" - total pages with a given number of records on the page;
+/// ```
+/// Return found data with users (`UserAdminPageDto`) with status 200.
+///
+/// The "admin" role is required.
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "Result of the user search.", body = UserAdminPageDto),
+        (status = 401, description = "An authorization token is required.", body = ApiError,
+            example = json!(ApiError::new(401, err::MSG_MISSING_TOKEN))),
+        (status = 403, description = "Access denied: insufficient user rights.", body = ApiError,
+            example = json!(ApiError::new(403, err::MSG_ACCESS_DENIED))),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[rustfmt::skip]
+#[get("/api/admin/users", wrap = "RequireAuth::allowed_roles(RequireAuth::admin_role())")]
+pub async fn get_users(
+    user_orm: web::Data<UserOrmApp>,
+    query_params: web::Query<SearchUserDto>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+    // Get search parameters.
+    let search_user_dto: SearchUserDto = query_params.into_inner();
+
+    let page: u32 = search_user_dto.page.unwrap_or(user_admin_models::SEARCH_USER_PAGE).max(1);
+    let limit: u32 = search_user_dto.limit.unwrap_or(user_admin_models::SEARCH_USER_LIMIT).max(1);
+    let mut search_user = SearchUser::convert(search_user_dto);
+    search_user.page = Some(page);
+    search_user.limit = Some(limit);
+
+    let (count, users) = web::block(move || {
+        // Find for an entity (user) by SearchUser.
+        let res_data = user_orm.find_users_by_pages(search_user).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_data
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })??;
+
+    let list: Vec<UserAdminDto> = users.into_iter().map(UserAdminDto::from).collect();
+    let pages: u32 = count / limit + if (count % limit) > 0 { 1 } else { 0 };
+    let result = UserAdminPageDto { list, limit, count, page, pages };
+
+    if let Some(timer) = timer {
+        info!("get_users() time: {}", format!("{:.2?}", timer.elapsed()));
+    }
+    Ok(HttpResponse::Ok().json(result)) // 200
+}
+
+/// put_user_role
+///
+/// Change the role of the user with the specified ID.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X PUT http://localhost:8080/api/admin/users/1/role \
+/// -d '{"role": "moderator"}' \
+/// -H 'Content-Type: application/json'
+/// ```
+///
+/// The administrator cannot change their own role.
+///
+/// Return the modified user (`UserAdminDto`) with status 200.
+///
+/// The "admin" role is required.
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "The user with the modified role.", body = UserAdminDto,
+            example = json!(UserAdminDto::from(User::new(1, "james_smith", "james_smith@gmail.com", "", UserRole::Moderator)))),
+        (status = 401, description = "An authorization token is required.", body = ApiError,
+            example = json!(ApiError::new(401, err::MSG_MISSING_TOKEN))),
+        (status = 403, description = "Access denied: insufficient user rights.", body = ApiError,
+            example = json!(ApiError::new(403, err::MSG_ACCESS_DENIED))),
+        (status = 404, description = "User not found.", body = ApiError,
+            example = json!(ApiError::create(404, err::MSG_USER_NOT_FOUND, "user_id: 123"))),
+        (status = 409, description = "The administrator cannot change their own role.", body = ApiError,
+            example = json!(ApiError::create(409, MSG_ACTION_ON_OWN_ACCOUNT, "user_id: 123"))),
+        (status = 416, description = "Error parsing input parameter. `curl -i -X PUT http://localhost:8080/api/admin/users/2a/role`",
+            body = ApiError, example = json!(ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED
+                , "`id` - invalid digit found in string (2a)"))),
+        (status = 417, body = [ApiError],
+            description = "Validation error. `curl -i -X PUT http://localhost:8080/api/admin/users/1/role -d '{\"role\": \"guest\" }'`",
+            example = json!(ApiError::validations((ModifyUserRoleDto { role: "guest".to_string() }).validate().err().unwrap()))),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    params(("id", description = "Unique user ID.")),
+    security(("bearer_auth" = [])),
+)]
+#[rustfmt::skip]
+#[put("/api/admin/users/{id}/role", wrap = "RequireAuth::allowed_roles(RequireAuth::admin_role())")]
+pub async fn put_user_role(
+    authenticated: Authenticated,
+    user_orm: web::Data<UserOrmApp>,
+    request: HttpRequest,
+    json_body: web::Json<ModifyUserRoleDto>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+    let user_id = get_path_user_id(&request)?;
+
+    // Checking the validity of the data model.
+    let validation_res = json_body.validate();
+    if let Err(validation_errors) = validation_res {
+        error!("{}-{}", code_to_str(StatusCode::EXPECTATION_FAILED), msg_validation(&validation_errors)); // 417
+        return Ok(ApiError::to_response(&ApiError::validations(validation_errors)));
+    }
+    let role = UserRole::try_from(json_body.into_inner().role.as_str()).ok();
+
+    // The administrator cannot change their own role.
+    let curr_user_id = authenticated.deref().id;
+    is_own_account(curr_user_id, user_id)?;
+
+    let opt_user = web::block(move || {
+        // Modify the role of the entity (user).
+        let modify_user = ModifyUser::new(None, None, None, role);
+        let opt_user = user_orm.modify_user(user_id, modify_user).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        opt_user
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })??;
+
+    let user = is_user_not_found(opt_user, user_id)?;
+
+    if let Some(timer) = timer {
+        info!("put_user_role() time: {}", format!("{:.2?}", timer.elapsed()));
+    }
+    Ok(HttpResponse::Ok().json(UserAdminDto::from(user))) // 200
+}
+
+/// put_user_suspend
+///
+/// Suspend the account of the user with the specified ID.
+///
+/// All sessions of the user are closed. Requests of a suspended user are rejected with error 403,
+/// and they cannot log in until the account is unsuspended.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X PUT http://localhost:8080/api/admin/users/1/suspend
+/// ```
+///
+/// The administrator cannot suspend their own account.
+///
+/// Return the suspended user (`UserAdminDto`) with status 200.
+///
+/// The "admin" role is required.
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "The suspended user.", body = UserAdminDto,
+            example = json!(UserAdminPageDto::create_users(2).pop().unwrap())),
+        (status = 401, description = "An authorization token is required.", body = ApiError,
+            example = json!(ApiError::new(401, err::MSG_MISSING_TOKEN))),
+        (status = 403, description = "Access denied: insufficient user rights.", body = ApiError,
+            example = json!(ApiError::new(403, err::MSG_ACCESS_DENIED))),
+        (status = 404, description = "User not found.", body = ApiError,
+            example = json!(ApiError::create(404, err::MSG_USER_NOT_FOUND, "user_id: 123"))),
+        (status = 409, description = "The administrator cannot suspend their own account.", body = ApiError,
+            example = json!(ApiError::create(409, MSG_ACTION_ON_OWN_ACCOUNT, "user_id: 123"))),
+        (status = 416, description = "Error parsing input parameter. `curl -i -X PUT http://localhost:8080/api/admin/users/2a/suspend`",
+            body = ApiError, example = json!(ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED
+                , "`id` - invalid digit found in string (2a)"))),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    params(("id", description = "Unique user ID.")),
+    security(("bearer_auth" = [])),
+)]
+#[rustfmt::skip]
+#[put("/api/admin/users/{id}/suspend", wrap = "RequireAuth::allowed_roles(RequireAuth::admin_role())")]
+pub async fn put_user_suspend(
+    authenticated: Authenticated,
+    user_orm: web::Data<UserOrmApp>,
+    request: HttpRequest,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+    let user_id = get_path_user_id(&request)?;
+
+    // The administrator cannot suspend their own account.
+    let curr_user_id = authenticated.deref().id;
+    is_own_account(curr_user_id, user_id)?;
+
+    let opt_user = web::block(move || {
+        // Suspend the account of the entity (user).
+        let opt_user = user_orm.modify_user_suspended(user_id, Some(Utc::now())).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        })?;
+        if opt_user.is_some() {
+            // Close all sessions of the user.
+            user_orm.delete_sessions(user_id, None).map_err(|e| {
+                error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+                ApiError::create(507, err::MSG_DATABASE, &e) // 507
+            })?;
+        }
+        Ok::<Option<User>, ApiError>(opt_user)
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })??;
+
+    let user = is_user_not_found(opt_user, user_id)?;
+
+    if let Some(timer) = timer {
+        info!("put_user_suspend() time: {}", format!("{:.2?}", timer.elapsed()));
+    }
+    Ok(HttpResponse::Ok().json(UserAdminDto::from(user))) // 200
+}
+
+/// put_user_unsuspend
+///
+/// Unsuspend the account of the user with the specified ID.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X PUT http://localhost:8080/api/admin/users/1/unsuspend
+/// ```
+///
+/// Return the unsuspended user (`UserAdminDto`) with status 200.
+///
+/// The "admin" role is required.
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "The unsuspended user.", body = UserAdminDto,
+            example = json!(UserAdminDto::from(User::new(1, "james_smith", "james_smith@gmail.com", "", UserRole::User)))),
+        (status = 401, description = "An authorization token is required.", body = ApiError,
+            example = json!(ApiError::new(401, err::MSG_MISSING_TOKEN))),
+        (status = 403, description = "Access denied: insufficient user rights.", body = ApiError,
+            example = json!(ApiError::new(403, err::MSG_ACCESS_DENIED))),
+        (status = 404, description = "User not found.", body = ApiError,
+            example = json!(ApiError::create(404, err::MSG_USER_NOT_FOUND, "user_id: 123"))),
+        (status = 416, description = "Error parsing input parameter. `curl -i -X PUT http://localhost:8080/api/admin/users/2a/unsuspend`",
+            body = ApiError, example = json!(ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED
+                , "`id` - invalid digit found in string (2a)"))),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    params(("id", description = "Unique user ID.")),
+    security(("bearer_auth" = [])),
+)]
+#[rustfmt::skip]
+#[put("/api/admin/users/{id}/unsuspend", wrap = "RequireAuth::allowed_roles(RequireAuth::admin_role())")]
+pub async fn put_user_unsuspend(
+    user_orm: web::Data<UserOrmApp>,
+    request: HttpRequest,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+    let user_id = get_path_user_id(&request)?;
+
+    let opt_user = web::block(move || {
+        // Unsuspend the account of the entity (user).
+        let opt_user = user_orm.modify_user_suspended(user_id, None).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        opt_user
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })??;
+
+    let user = is_user_not_found(opt_user, user_id)?;
+
+    if let Some(timer) = timer {
+        info!("put_user_unsuspend() time: {}", format!("{:.2?}", timer.elapsed()));
+    }
+    Ok(HttpResponse::Ok().json(UserAdminDto::from(user))) // 200
+}
+
+/// delete_user_sessions
+///
+/// Force logout of the user with the specified ID (close all sessions of the user on all devices).
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X DELETE http://localhost:8080/api/admin/users/1/sessions
+/// ```
+///
+/// Return the number of closed sessions (`DeleteSessionsResponseDto`) with status 200.
+///
+/// The "admin" role is required.
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "The number of closed sessions.", body = DeleteSessionsResponseDto,
+            example = json!(DeleteSessionsResponseDto { count: 2 })),
+        (status = 401, description = "An authorization token is required.", body = ApiError,
+            example = json!(ApiError::new(401, err::MSG_MISSING_TOKEN))),
+        (status = 403, description = "Access denied: insufficient user rights.", body = ApiError,
+            example = json!(ApiError::new(403, err::MSG_ACCESS_DENIED))),
+        (status = 404, description = "User not found.", body = ApiError,
+            example = json!(ApiError::create(404, err::MSG_USER_NOT_FOUND, "user_id: 123"))),
+        (status = 416, description = "Error parsing input parameter. `curl -i -X DELETE http://localhost:8080/api/admin/users/2a/sessions`",
+            body = ApiError, example = json!(ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED
+                , "`id` - invalid digit found in string (2a)"))),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    params(("id", description = "Unique user ID.")),
+    security(("bearer_auth" = [])),
+)]
+#[rustfmt::skip]
+#[delete("/api/admin/users/{id}/sessions", wrap = "RequireAuth::allowed_roles(RequireAuth::admin_role())")]
+pub async fn delete_user_sessions(
+    user_orm: web::Data<UserOrmApp>,
+    request: HttpRequest,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+    let user_id = get_path_user_id(&request)?;
+
+    let (opt_user, count) = web::block(move || {
+        // Get the user by ID.
+        let opt_user = user_orm.get_user_by_id(user_id, false).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        })?;
+        if opt_user.is_none() {
+            return Ok((None, 0));
+        }
+        // Close all sessions of the user.
+        let count = user_orm.delete_sessions(user_id, None).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        })?;
+        Ok::<(Option<User>, usize), ApiError>((opt_user, count))
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })??;
+
+    let _ = is_user_not_found(opt_user, user_id)?;
+
+    if let Some(timer) = timer {
+        info!("delete_user_sessions() time: {}", format!("{:.2?}", timer.elapsed()));
+    }
+    Ok(HttpResponse::Ok().json(DeleteSessionsResponseDto { count })) // 200
+}
+
+#[cfg(all(test, feature = "mockdata"))]
+pub mod tests {
+
+    use actix_web::http;
+    use vrb_tools::token_data::BEARER;
+
+    pub fn header_auth(token: &str) -> (http::header::HeaderName, http::header::HeaderValue) {
+        let header_value = http::header::HeaderValue::from_str(&format!("{}{}", BEARER, token)).unwrap();
+        (http::header::AUTHORIZATION, header_value)
+    }
+}
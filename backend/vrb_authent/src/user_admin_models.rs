@@ -0,0 +1,149 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use vrb_common::{
+    serial_datetime_option,
+    validators::{ValidationError, Validator},
+};
+use vrb_dbase::enm_user_role::UserRole;
+
+use crate::user_models::{self, User};
+
+// ** Section: "SearchUser" **
+
+pub const SEARCH_USER_PAGE: u32 = 1;
+pub const SEARCH_USER_LIMIT: u32 = 10;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SearchUser {
+    // Part of the nickname (case insensitive).
+    pub nickname: Option<String>,
+    // Part of the email (case insensitive).
+    pub email: Option<String>,
+    pub role: Option<UserRole>,
+    // Users with a "created_at" greater than or equal to the specified one.
+    pub created_from: Option<DateTime<Utc>>,
+    // Users with a "created_at" less than the specified one.
+    pub created_to: Option<DateTime<Utc>>,
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+impl SearchUser {
+    pub fn convert(search_user: SearchUserDto) -> Self {
+        SearchUser {
+            nickname: search_user.nickname.filter(|v| v.len() > 0).map(|v| v.to_lowercase()),
+            email: search_user.email.filter(|v| v.len() > 0).map(|v| v.to_lowercase()),
+            role: search_user.role.clone(),
+            created_from: search_user.created_from.clone(),
+            created_to: search_user.created_to.clone(),
+            page: search_user.page.clone(),
+            limit: search_user.limit.clone(),
+        }
+    }
+}
+
+// * SearchUserDto *
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchUserDto {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<UserRole>,
+    // Users with a "created_at" greater than or equal to the specified one.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "serial_datetime_option")]
+    pub created_from: Option<DateTime<Utc>>,
+    // Users with a "created_at" less than the specified one.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "serial_datetime_option")]
+    pub created_to: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+// * UserAdminDto *
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UserAdminDto {
+    pub id: i32,
+    pub nickname: String,
+    pub email: String,
+    pub role: UserRole,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    // Date and time the account was suspended (if it is suspended).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suspended_at: Option<DateTime<Utc>>,
+}
+
+impl From<User> for UserAdminDto {
+    fn from(user: User) -> Self {
+        UserAdminDto {
+            id: user.id,
+            nickname: user.nickname,
+            email: user.email,
+            role: user.role,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+            suspended_at: user.suspended_at,
+        }
+    }
+}
+
+// * UserAdminPageDto *
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UserAdminPageDto {
+    #[schema(example = json!(Self::create_users(2)))]
+    pub list: Vec<UserAdminDto>,
+    #[schema(example = 10)]
+    pub limit: u32,
+    #[schema(example = 2)]
+    pub count: u32,
+    #[schema(example = 1)]
+    pub page: u32,
+    #[schema(example = 1)]
+    pub pages: u32,
+}
+
+impl UserAdminPageDto {
+    pub fn create_users(amount: i32) -> Vec<UserAdminDto> {
+        let mut result: Vec<UserAdminDto> = Vec::new();
+        for idx in 1..=amount {
+            let nickname = format!("user_{}", idx);
+            let mut user = User::new(idx, &nickname, &format!("{}@gmail.com", nickname), "", UserRole::User);
+            if idx % 2 == 0 {
+                user.suspended_at = Some(Utc::now() - Duration::days(1));
+            }
+            result.push(UserAdminDto::from(user));
+        }
+        result
+    }
+}
+
+// ** Used: in "user_admin_controller::put_user_role(). **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ModifyUserRoleDto {
+    // New user role ("admin", "moderator", "user").
+    pub role: String,
+}
+
+impl Validator for ModifyUserRoleDto {
+    // Check the model against the required conditions.
+    fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors: Vec<Option<ValidationError>> = vec![];
+
+        errors.push(user_models::validate_role(&self.role).err());
+
+        self.filter_errors(errors)
+    }
+}
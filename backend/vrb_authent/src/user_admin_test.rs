@@ -0,0 +1,491 @@
+#[cfg(all(test, feature = "mockdata"))]
+mod tests {
+    use actix_web::{
+        App, body, dev,
+        http::StatusCode,
+        http::header::{CONTENT_TYPE, HeaderValue},
+        test,
+    };
+    use chrono::{Duration, SecondsFormat, Utc};
+    use vrb_common::{
+        api_error::{ApiError, code_to_str},
+        err,
+    };
+    use vrb_dbase::enm_user_role::UserRole;
+
+    use crate::{
+        config_jwt,
+        user_admin_controller::{
+            MSG_ACTION_ON_OWN_ACCOUNT, delete_user_sessions, get_users, put_user_role, put_user_suspend, put_user_unsuspend,
+            tests as AdminCtrlTest,
+        },
+        user_admin_models::{ModifyUserRoleDto, SEARCH_USER_LIMIT, UserAdminDto, UserAdminPageDto},
+        user_authent_models::DeleteSessionsResponseDto,
+        user_models::{self, Session},
+        user_orm::tests::{ADMIN, USER, USER1_ID, USER2_ID, USER3_ID, USER4_ID, UserOrmTest},
+    };
+
+    const MSG_ERROR_WAS_EXPECTED: &str = "Service call succeeded, but an error was expected.";
+    const MSG_FAILED_TO_DESER: &str = "Failed to deserialize JSON string";
+    const MSG_FAILED_DESER: &str = "Failed to deserialize response from JSON.";
+
+    // ** get_users **
+
+    #[actix_web::test]
+    async fn test_get_users_not_admin() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER, USER]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_users)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri("/api/admin/users")
+            .insert_header(AdminCtrlTest::header_auth(&token1))
+            .to_request();
+        let result = test::try_call_service(&app, req).await.err();
+        let err = result.expect(MSG_ERROR_WAS_EXPECTED);
+
+        let actual_status = err.as_response_error().status_code();
+        assert_eq!(actual_status, StatusCode::FORBIDDEN); // 403
+
+        let api_err: ApiError = serde_json::from_str(&err.to_string()).expect(MSG_FAILED_TO_DESER);
+        assert_eq!(api_err.code, code_to_str(StatusCode::FORBIDDEN));
+        assert_eq!(api_err.message, err::MSG_ACCESS_DENIED);
+    }
+    #[actix_web::test]
+    async fn test_get_users_without_params() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN, USER, USER, USER]);
+        let user_vec: Vec<UserAdminDto> = data_u.0.iter().map(|user| UserAdminDto::from(user.clone())).collect();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_users)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri("/api/admin/users")
+            .insert_header(AdminCtrlTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: UserAdminPageDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        let ids: Vec<i32> = response.list.iter().map(|user| user.id).collect();
+        assert_eq!(ids, vec![USER1_ID, USER2_ID, USER3_ID, USER4_ID]);
+        assert_eq!(response.list[1].nickname, user_vec[1].nickname);
+        assert_eq!(response.list[1].email, user_vec[1].email);
+        assert_eq!(response.list[1].role, user_vec[1].role);
+        assert_eq!(response.limit, SEARCH_USER_LIMIT);
+        assert_eq!(response.count, 4);
+        assert_eq!(response.page, 1);
+        assert_eq!(response.pages, 1);
+    }
+    #[actix_web::test]
+    async fn test_get_users_by_nickname_and_email() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN, USER, USER, USER]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_users)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        // "t_b" - only "robert_brown"; "@gmail" - all users.
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri("/api/admin/users?nickname=T_B&email=%40GMAIL")
+            .insert_header(AdminCtrlTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: UserAdminPageDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        let ids: Vec<i32> = response.list.iter().map(|user| user.id).collect();
+        assert_eq!(ids, vec![USER2_ID]);
+        assert_eq!(response.count, 1);
+        assert_eq!(response.pages, 1);
+    }
+    #[actix_web::test]
+    async fn test_get_users_by_role() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let mut data_u = UserOrmTest::users(&[ADMIN, USER, USER, USER]);
+        data_u.0.get_mut(2).unwrap().role = UserRole::Moderator;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_users)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri("/api/admin/users?role=moderator")
+            .insert_header(AdminCtrlTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: UserAdminPageDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        let ids: Vec<i32> = response.list.iter().map(|user| user.id).collect();
+        assert_eq!(ids, vec![USER3_ID]);
+        assert_eq!(response.list[0].role, UserRole::Moderator);
+        assert_eq!(response.count, 1);
+    }
+    #[actix_web::test]
+    async fn test_get_users_by_created_date() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let mut data_u = UserOrmTest::users(&[ADMIN, USER, USER, USER]);
+        let now = Utc::now();
+        for (idx, user) in data_u.0.iter_mut().enumerate() {
+            user.created_at = now - Duration::days(10 * i64::try_from(idx).unwrap());
+        }
+        // created_at: user1 - now, user2 - 10 days ago, user3 - 20 days ago, user4 - 30 days ago.
+        let created_from = (now - Duration::days(25)).to_rfc3339_opts(SecondsFormat::Millis, true);
+        let created_to = (now - Duration::days(5)).to_rfc3339_opts(SecondsFormat::Millis, true);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_users)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/admin/users?createdFrom={}&createdTo={}", created_from, created_to))
+            .insert_header(AdminCtrlTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: UserAdminPageDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        let ids: Vec<i32> = response.list.iter().map(|user| user.id).collect();
+        assert_eq!(ids, vec![USER2_ID, USER3_ID]);
+        assert_eq!(response.count, 2);
+    }
+    #[actix_web::test]
+    async fn test_get_users_page2() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN, USER, USER, USER]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_users)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri("/api/admin/users?page=2&limit=3")
+            .insert_header(AdminCtrlTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: UserAdminPageDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        let ids: Vec<i32> = response.list.iter().map(|user| user.id).collect();
+        assert_eq!(ids, vec![USER4_ID]);
+        assert_eq!(response.limit, 3);
+        assert_eq!(response.count, 4);
+        assert_eq!(response.page, 2);
+        assert_eq!(response.pages, 2);
+    }
+
+    // ** put_user_role **
+
+    #[actix_web::test]
+    async fn test_put_user_role_invalid_id() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN, USER]);
+        let user_id_bad = format!("{}a", USER2_ID);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_user_role)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/admin/users/{}/role", user_id_bad))
+            .insert_header(AdminCtrlTest::header_auth(&token1))
+            .set_json(ModifyUserRoleDto { role: "moderator".to_string() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::RANGE_NOT_SATISFIABLE); // 416
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::RANGE_NOT_SATISFIABLE));
+        #[rustfmt::skip]
+        let msg = format!("{}; `id` - invalid digit found in string ({})", err::MSG_PARSING_TYPE_NOT_SUPPORTED, user_id_bad);
+        assert_eq!(app_err.message, msg);
+    }
+    #[actix_web::test]
+    async fn test_put_user_role_invalid_dto_role() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN, USER]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_user_role)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/admin/users/{}/role", USER2_ID))
+            .insert_header(AdminCtrlTest::header_auth(&token1))
+            .set_json(ModifyUserRoleDto { role: "guest".to_string() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err_vec: Vec<ApiError> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err_vec.len(), 1);
+        assert_eq!(app_err_vec[0].code, code_to_str(StatusCode::EXPECTATION_FAILED));
+        assert_eq!(app_err_vec[0].message, user_models::MSG_USER_ROLE_INVALID_VALUE);
+    }
+    #[actix_web::test]
+    async fn test_put_user_role_own_account() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN, USER]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_user_role)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/admin/users/{}/role", USER1_ID))
+            .insert_header(AdminCtrlTest::header_auth(&token1))
+            .set_json(ModifyUserRoleDto { role: "user".to_string() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT); // 409
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::CONFLICT));
+        assert_eq!(app_err.message, format!("{}; user_id: {}", MSG_ACTION_ON_OWN_ACCOUNT, USER1_ID));
+    }
+    #[actix_web::test]
+    async fn test_put_user_role_user_not_exist() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN, USER]);
+        let user_id = USER4_ID + 1;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_user_role)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/admin/users/{}/role", user_id))
+            .insert_header(AdminCtrlTest::header_auth(&token1))
+            .set_json(ModifyUserRoleDto { role: "moderator".to_string() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND); // 404
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::NOT_FOUND));
+        assert_eq!(app_err.message, format!("{}; user_id: {}", err::MSG_USER_NOT_FOUND, user_id));
+    }
+    #[actix_web::test]
+    async fn test_put_user_role_valid_data() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN, USER]);
+        let user2 = data_u.0.get(1).unwrap().clone();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_user_role)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/admin/users/{}/role", USER2_ID))
+            .insert_header(AdminCtrlTest::header_auth(&token1))
+            .set_json(ModifyUserRoleDto { role: "Moderator".to_string() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: UserAdminDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.id, user2.id);
+        assert_eq!(response.nickname, user2.nickname);
+        assert_eq!(response.role, UserRole::Moderator);
+        assert_eq!(response.suspended_at, None);
+    }
+
+    // ** put_user_suspend **
+
+    #[actix_web::test]
+    async fn test_put_user_suspend_own_account() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN, USER]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_user_suspend)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/admin/users/{}/suspend", USER1_ID))
+            .insert_header(AdminCtrlTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT); // 409
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::CONFLICT));
+        assert_eq!(app_err.message, format!("{}; user_id: {}", MSG_ACTION_ON_OWN_ACCOUNT, USER1_ID));
+    }
+    #[actix_web::test]
+    async fn test_put_user_suspend_user_not_exist() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN, USER]);
+        let user_id = USER4_ID + 1;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_user_suspend)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/admin/users/{}/suspend", user_id))
+            .insert_header(AdminCtrlTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND); // 404
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::NOT_FOUND));
+        assert_eq!(app_err.message, format!("{}; user_id: {}", err::MSG_USER_NOT_FOUND, user_id));
+    }
+    #[actix_web::test]
+    async fn test_put_user_suspend_valid_data() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN, USER]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_user_suspend)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        let now = Utc::now();
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/admin/users/{}/suspend", USER2_ID))
+            .insert_header(AdminCtrlTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: UserAdminDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.id, USER2_ID);
+        let suspended_at = response.suspended_at.expect("suspended_at must be specified");
+        assert!(now <= suspended_at && suspended_at <= Utc::now());
+    }
+
+    // ** put_user_unsuspend **
+
+    #[actix_web::test]
+    async fn test_put_user_unsuspend_user_not_exist() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN, USER]);
+        let user_id = USER4_ID + 1;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_user_unsuspend)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/admin/users/{}/unsuspend", user_id))
+            .insert_header(AdminCtrlTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND); // 404
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::NOT_FOUND));
+        assert_eq!(app_err.message, format!("{}; user_id: {}", err::MSG_USER_NOT_FOUND, user_id));
+    }
+    #[actix_web::test]
+    async fn test_put_user_unsuspend_valid_data() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let mut data_u = UserOrmTest::users(&[ADMIN, USER]);
+        data_u.0.get_mut(1).unwrap().suspended_at = Some(Utc::now() - Duration::days(1));
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_user_unsuspend)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/admin/users/{}/unsuspend", USER2_ID))
+            .insert_header(AdminCtrlTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: UserAdminDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.id, USER2_ID);
+        assert_eq!(response.suspended_at, None);
+    }
+
+    // ** delete_user_sessions **
+
+    #[actix_web::test]
+    async fn test_delete_user_sessions_user_not_exist() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN, USER]);
+        let user_id = USER4_ID + 1;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(delete_user_sessions)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::delete().uri(&format!("/api/admin/users/{}/sessions", user_id))
+            .insert_header(AdminCtrlTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND); // 404
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::NOT_FOUND));
+        assert_eq!(app_err.message, format!("{}; user_id: {}", err::MSG_USER_NOT_FOUND, user_id));
+    }
+    #[actix_web::test]
+    async fn test_delete_user_sessions_valid_data() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let mut data_u = UserOrmTest::users(&[ADMIN, USER]);
+        data_u.1.push(Session::new(2, USER2_ID, config_jwt::tests::get_num_token(USER2_ID)));
+        data_u.1.push(Session::new(3, USER2_ID, config_jwt::tests::get_num_token(USER2_ID) + 1));
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(delete_user_sessions)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::delete().uri(&format!("/api/admin/users/{}/sessions", USER2_ID))
+            .insert_header(AdminCtrlTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: DeleteSessionsResponseDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.count, 2);
+    }
+}
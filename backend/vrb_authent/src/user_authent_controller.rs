@@ -1,10 +1,10 @@
 use std::{borrow::Cow, ops::Deref, time::Instant as tm};
 
 use actix_web::{
-    HttpResponse,
+    HttpRequest, HttpResponse,
     cookie::{Cookie, SameSite, time::Duration as ActixWebDuration},
-    get,
-    http::StatusCode,
+    delete, get,
+    http::{StatusCode, header::USER_AGENT},
     post, web,
 };
 use log::{Level::Info, error, info, log_enabled};
@@ -12,26 +12,38 @@ use serde_json::json;
 use utoipa;
 use vrb_common::{
     api_error::{ApiError, code_to_str},
-    err,
+    err, parser,
     validators::{Validator, msg_validation},
 };
 use vrb_dbase::enm_user_role::UserRole;
 use vrb_tools::{hash_tools, token_coding, token_data::TOKEN_NAME};
 
 use crate::{
-    authentication::{Authenticated, RequireAuth},
+    attempt_limiter::{self, AttemptLimiter, is_too_many_attempts},
+    authentication::{self, Authenticated, AuthenticatedSession, RequireAuth, is_session_not_found, is_user_suspended},
     config_jwt,
     user_authent_models::{
-        LoginDto, LoginResponseDto, LoginUserProfileDto, UserTokenDto, UserTokenResponseDto, UserUniquenessDto, UserUniquenessResponseDto,
+        DeleteSessionsDto, DeleteSessionsResponseDto, LoginDto, LoginResponseDto, LoginUserProfileDto, SessionDto, UserTokenDto,
+        UserTokenResponseDto, UserUniquenessDto, UserUniquenessResponseDto,
     },
-    user_models::User,
+    user_models::{CreateSession, Session, User},
+    user_nickname_orm::UserNicknameOrm,
     user_orm::UserOrm,
     user_registr_orm::UserRegistrOrm,
+    user_totp_controller::{CHALLENGE_DURATION, verify_totp_code},
+    user_totp_models::{LoginChallengeResponseDto, LoginTotpDto},
+    user_totp_orm::UserTotpOrm,
 };
 #[cfg(not(all(test, feature = "mockdata")))]
-use crate::{user_orm::impls::UserOrmApp, user_registr_orm::impls::UserRegistrOrmApp};
+use crate::{
+    user_nickname_orm::impls::UserNicknameOrmApp, user_orm::impls::UserOrmApp, user_registr_orm::impls::UserRegistrOrmApp,
+    user_totp_orm::impls::UserTotpOrmApp,
+};
 #[cfg(all(test, feature = "mockdata"))]
-use crate::{user_orm::tests::UserOrmApp, user_registr_orm::tests::UserRegistrOrmApp};
+use crate::{
+    user_nickname_orm::tests::UserNicknameOrmApp, user_orm::tests::UserOrmApp, user_registr_orm::tests::UserRegistrOrmApp,
+    user_totp_orm::tests::UserTotpOrmApp,
+};
 
 const PASSWORD1: &str = "$argon2id$v=19$m=19456,t=2,p=1$sUU7bgDw7XH4z8SzvgXjkA$izpWfsHPJeXEhD90cRxxR/no7gyRz/DiANxe5Ckt53I";
 const TOKEN1: &str = "6lqN0k3-SB_OXGzOJYUr2GwYwAEmlJWFMpOwiYrT04_WQMRQs3PAlb7WHFExilHzFrbNSTsdGzmBzFMwFD2rVXgiQtoK4fON634zV9rjMswSd7FW7eHh3PmoVxUVtID1j6TWck_wJy0TdO2rcnLZIfu2jbMzk6myQCl_5u05Ii9YvtXOI8-a0fhMRveIcM8udUGatXT5HRnGAzjDQuhDZ-94DonA0rvn2DK3D9h-baU=";
@@ -45,10 +57,28 @@ pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
             .service(users_uniqueness)
             // POST /api/login
             .service(login)
+            // POST /api/login/totp
+            .service(login_totp)
             // POST /api/logout
             .service(logout)
             // POST /api/token
-            .service(update_token);
+            .service(update_token)
+            // GET /api/sessions
+            .service(get_sessions)
+            // DELETE /api/sessions/{id}
+            .service(delete_session)
+            // DELETE /api/sessions
+            .service(delete_sessions);
+    }
+}
+
+/// Generate a new "num_token" value that is not used by other sessions of the user.
+fn generate_num_token_for(sessions: &[Session]) -> i32 {
+    loop {
+        let num_token = token_coding::generate_num_token();
+        if !sessions.iter().any(|session| session.num_token == num_token) {
+            return num_token;
+        }
     }
 }
 
@@ -68,7 +98,7 @@ pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
 /// ```
 ///
 /// Returns the result of the user data uniqueness check (`UserUniquenessResponseDto`) with status 200.
-/// If the value is already in use, then `{"uniqueness":false}`.
+/// If the value is already in use (or the nickname is reserved after a change by another user), then `{"uniqueness":false}`.
 /// If the value is not yet used, then `{"uniqueness":true}`.
 ///
 #[utoipa::path(
@@ -94,6 +124,7 @@ pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
 pub async fn users_uniqueness(
     user_orm: web::Data<UserOrmApp>,
     user_registr_orm: web::Data<UserRegistrOrmApp>,
+    user_nickname_orm: web::Data<UserNicknameOrmApp>,
     query_params: web::Query<UserUniquenessDto>,
 ) -> actix_web::Result<HttpResponse, ApiError> {
     let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
@@ -112,6 +143,7 @@ pub async fn users_uniqueness(
 
     let user_orm2 = user_orm.get_ref().clone();
     let user_registr_orm2 = user_registr_orm.get_ref().clone();
+    let user_nickname_orm2 = user_nickname_orm.get_ref().clone();
 
     let opt_search = web::block(move || {
         let mut res_search: Option<(bool, bool)> = None;
@@ -137,6 +169,16 @@ pub async fn users_uniqueness(
                 res_search = Some((nickname == user_registr.nickname, email == user_registr.email));
             }
         }
+        if res_search.is_none() {
+            let opt_reserved = user_nickname_orm2
+                .find_reserved_nickname(&nickname)
+                .map_err(|e| ApiError::create(507, err::MSG_DATABASE, &e)) // 507
+                .ok()?;
+            // If the nickname is reserved in the "user_nickname_history" table, then exit.
+            if opt_reserved.is_some() {
+                res_search = Some((true, false));
+            }
+        }
         res_search
     })
     .await
@@ -170,22 +212,30 @@ fn get_login_user_profile() -> LoginUserProfileDto {
 ///
 /// User authentication to enter an authorized state.
 ///
-/// Open a session for the current user.
+/// Open a new session (device) for the current user. Sessions opened on other devices remain active.
+/// The optional "device" field sets the label of the device in the list of sessions.
+///
+/// Failed attempts are counted by nickname (email) and by IP address. After too many failed attempts,
+/// login is temporarily blocked (error 429 with the "retryAfter" parameter, the number of seconds until unlocking).
 ///
 /// One could call with following curl.
 /// ```text
 /// curl -i -X POST http://localhost:8080/api/login \
-/// -d '{"nickname": "user01", "password": "password"}' \
+/// -d '{"nickname": "user01", "password": "password", "device": "Laptop"}' \
 /// -H 'Content-Type: application/json'
 /// ```
 ///
 /// Returns (`LoginResponseDto`) the current user data (`LoginUserProfileDto`) and the open session token (`UserTokenResponseDto`)
 /// with status 200.
 ///
+/// If the user has enabled two-factor authentication (TOTP), then the session is not opened yet.
+/// Returns the challenge token (`LoginChallengeResponseDto`) with status 202, which must be confirmed
+/// with a one-time code (`POST /api/login/totp`).
+///
 #[utoipa::path(
     request_body(content = LoginDto,
         description = "Credentials to log in to your account `LoginDto`",
-        example = json!(LoginDto { nickname: "james_miller".to_owned(), password: PASSWORD1.to_owned() })
+        example = json!(LoginDto { nickname: "james_miller".to_owned(), password: PASSWORD1.to_owned(), device: None })
     ),
     responses(
         ( status = 200, description = "The current user's profile and the open session token.",
@@ -195,18 +245,24 @@ fn get_login_user_profile() -> LoginUserProfileDto {
                 token_user_response_dto: UserTokenResponseDto { access_token: TOKEN2.to_owned(), refresh_token: TOKEN3.to_owned() },
             })
         ),
+        (status = 202, description = "Two-factor authentication is enabled. A one-time code is required.",
+            body = LoginChallengeResponseDto,
+            example = json!(LoginChallengeResponseDto { challenge_token: TOKEN1.to_owned() })
+        ),
         (status = 401, description = "The nickname or password is incorrect.", body = ApiError, examples(
             ("Nickname" = (summary = "Nickname is incorrect", description = "The nickname is incorrect.",
                 value = json!(ApiError::new(401, err::MSG_WRONG_NICKNAME_EMAIL)))),
             ("Password" = (summary = "Password is incorrect", description = "The password is incorrect.",
                 value = json!(ApiError::new(401, err::MSG_PASSWORD_INCORRECT))))
         )),
+        (status = 403, description = "The user account is suspended.", body = ApiError,
+            example = json!(ApiError::create(403, authentication::MSG_USER_SUSPENDED, "user_id: 123"))),
         (status = 417, body = [ApiError], description =
             "Validation error. `curl -i -X POST http://localhost:8080/api/login -d '{ \"nickname\": \"us\", \"password\": \"pas\" }'`",
             example = json!(ApiError::validations(
-                (LoginDto { nickname: "us".to_string(), password: "pas".to_string() }).validate().err().unwrap()) )),
-        ( status = 406, description = "Error session not found.", body = ApiError,
-            example = json!(ApiError::create(406, err::MSG_SESSION_NOT_FOUND, "user_id: 1"))),
+                (LoginDto { nickname: "us".to_string(), password: "pas".to_string(), device: None }).validate().err().unwrap()) )),
+        (status = 429, description = "Too many failed attempts. Login is temporarily blocked.", body = ApiError,
+            example = json!(ApiError::new(429, err::MSG_TOO_MANY_ATTEMPTS).add_param(attempt_limiter::PARAM_RETRY_AFTER.into(), &60))),
         (status = 409, description = "Error when comparing password hashes.", body = ApiError,
             example = json!(ApiError::create(409, err::MSG_INVALID_HASH, "Parameter is empty."))),
         ( status = 422, description = "Token encoding error.", body = ApiError,
@@ -219,8 +275,11 @@ fn get_login_user_profile() -> LoginUserProfileDto {
 )]
 #[post("/api/login")]
 pub async fn login(
+    request: HttpRequest,
     config_jwt: web::Data<config_jwt::ConfigJwt>,
+    attempt_limiter: web::Data<AttemptLimiter>,
     user_orm: web::Data<UserOrmApp>,
+    user_totp_orm: web::Data<UserTotpOrmApp>,
     json_body: web::Json<LoginDto>,
 ) -> actix_web::Result<HttpResponse, ApiError> {
     let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
@@ -236,6 +295,20 @@ pub async fn login(
     let nickname = login_dto.nickname.clone();
     let email = login_dto.nickname.clone();
     let password = login_dto.password.clone();
+    let device = login_dto.device.clone().filter(|v| v.len() > 0);
+    #[rustfmt::skip]
+    let user_agent = request.headers().get(USER_AGENT).and_then(|v| v.to_str().ok()).map(|v| v.to_owned());
+    let ip_address = request.connection_info().realip_remote_addr().map(|v| v.to_owned());
+
+    // Failed attempts are counted by nickname (email) and by IP address.
+    let key_login = attempt_limiter::get_key(attempt_limiter::KEY_LOGIN, &login_dto.nickname);
+    let mut attempt_keys = vec![key_login.clone()];
+    if let Some(ip_address) = &ip_address {
+        attempt_keys.push(attempt_limiter::get_key(attempt_limiter::KEY_LOGIN_IP, ip_address));
+    }
+    // If there were too many failed attempts, then return error 429.
+    is_too_many_attempts(&attempt_limiter, &attempt_keys)?;
+
     let user_orm2 = user_orm.get_ref().clone();
 
     let opt_user_pwd = web::block(move || {
@@ -255,6 +328,7 @@ pub async fn login(
     })??;
 
     let user_pwd = opt_user_pwd.ok_or_else(|| {
+        attempt_limiter.add_failure(&attempt_keys);
         error!("{}-{}", code_to_str(StatusCode::UNAUTHORIZED), err::MSG_WRONG_NICKNAME_EMAIL);
         ApiError::new(401, err::MSG_WRONG_NICKNAME_EMAIL) // 401(f)
     })?;
@@ -266,12 +340,84 @@ pub async fn login(
     })?;
 
     if !password_matches {
+        attempt_limiter.add_failure(&attempt_keys);
         error!("{}-{}", code_to_str(StatusCode::UNAUTHORIZED), err::MSG_PASSWORD_INCORRECT);
         return Err(ApiError::new(401, err::MSG_PASSWORD_INCORRECT)); // 401(g)
     }
+    // After successful login, the counter of failed attempts for the nickname (email) is reset.
+    attempt_limiter.reset(&[key_login]);
+    // If the user account is suspended, then error 403.
+    let user_pwd = is_user_suspended(user_pwd)?;
+
+    let user_totp_orm2 = user_totp_orm.get_ref().clone();
+    let user_id = user_pwd.id;
+
+    let opt_user_totp = web::block(move || {
+        // Get the two-factor authentication settings (TOTP) of the user.
+        let res_user_totp = user_totp_orm2.get_user_totp(user_id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_user_totp
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })??;
+
+    // If two-factor authentication is enabled, then tokens are issued only after the one-time code is checked.
+    if opt_user_totp.map(|user_totp| user_totp.is_enabled).unwrap_or(false) {
+        let jwt_secret: &[u8] = config_jwt.jwt_secret.as_bytes();
+        let num_token = token_coding::generate_num_token();
+        #[rustfmt::skip]
+        let challenge_token = token_coding::encode_challenge_token(user_id, num_token, jwt_secret, CHALLENGE_DURATION)
+            .map_err(|e| {
+                error!("{}-{}; {}", code_to_str(StatusCode::UNPROCESSABLE_ENTITY), err::MSG_JSON_WEB_TOKEN_ENCODE, &e);
+                ApiError::create(422, err::MSG_JSON_WEB_TOKEN_ENCODE, &e) // 422
+            })?;
+        if let Some(timer) = timer {
+            info!("login() time: {}", format!("{:.2?}", timer.elapsed()));
+        }
+        return Ok(HttpResponse::Accepted().json(LoginChallengeResponseDto { challenge_token })); // 202
+    }
 
-    let num_token = token_coding::generate_num_token();
     let config_jwt = config_jwt.get_ref().clone();
+    let response = open_session(user_pwd, device, user_agent, ip_address, config_jwt, user_orm).await?;
+
+    if let Some(timer) = timer {
+        info!("login() time: {}", format!("{:.2?}", timer.elapsed()));
+    }
+    Ok(response) // 200
+}
+
+/// Open a new session (device) for the user. Return the user data and the session tokens (and the token cookie).
+async fn open_session(
+    user_pwd: User,
+    device: Option<String>,
+    user_agent: Option<String>,
+    ip_address: Option<String>,
+    config_jwt: config_jwt::ConfigJwt,
+    user_orm: web::Data<UserOrmApp>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let user_orm2 = user_orm.get_ref().clone();
+    let user_id = user_pwd.id;
+
+    let sessions = web::block(move || {
+        // Get the sessions already opened by the user (on other devices).
+        let res_sessions = user_orm2.get_sessions_by_user_id(user_id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_sessions
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })??;
+
+    let num_token = generate_num_token_for(&sessions);
     let jwt_secret: &[u8] = config_jwt.jwt_secret.as_bytes();
 
     // Packing two parameters (user_id, num_token) into access_token.
@@ -280,20 +426,16 @@ pub async fn login(
         ApiError::create(422, err::MSG_JSON_WEB_TOKEN_ENCODE, &e) // 422
     })?;
 
-    // Packing two parameters (user_id, num_token) into refresh_token.
-    let refresh_token = token_coding::encode_token(user_pwd.id, num_token, jwt_secret, config_jwt.jwt_refresh).map_err(|e| {
-        error!("{}-{}; {}", code_to_str(StatusCode::UNPROCESSABLE_ENTITY), err::MSG_JSON_WEB_TOKEN_ENCODE, &e);
-        ApiError::create(422, err::MSG_JSON_WEB_TOKEN_ENCODE, &e) // 422
-    })?;
+    let create_session = CreateSession::new(user_id, num_token, device, user_agent, ip_address);
 
     let res_session_profile = web::block(move || {
-        // Modify the entity (session) with new data. Result <Option<Session>>.
-        let res_session = user_orm.modify_session(user_pwd.id, Some(num_token)).map_err(|e| {
+        // Add a new entity (session) for the device. Result <Session>.
+        let res_session = user_orm.create_session(create_session).map_err(|e| {
             error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
             ApiError::create(507, err::MSG_DATABASE, &e) // 507
         });
 
-        let res_profile = user_orm.get_profile_by_id(user_pwd.id).map_err(|e| {
+        let res_profile = user_orm.get_profile_by_id(user_id).map_err(|e| {
             error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
             ApiError::create(507, err::MSG_DATABASE, &e) // 507
         });
@@ -306,12 +448,15 @@ pub async fn login(
         ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
     })?;
 
-    let opt_session = res_session_profile.0?;
-    if opt_session.is_none() {
-        let msg = format!("user_id: {}", user_pwd.id);
-        error!("{}-{}; {}", code_to_str(StatusCode::NOT_ACCEPTABLE), err::MSG_SESSION_NOT_FOUND, &msg);
-        return Err(ApiError::create(406, err::MSG_SESSION_NOT_FOUND, &msg)); // 406
-    }
+    let session = res_session_profile.0?;
+
+    // Packing three parameters (user_id, num_token, session_id) into a one-time refresh_token.
+    #[rustfmt::skip]
+    let refresh_token = token_coding::encode_refresh_token(user_id, num_token, session.id, jwt_secret, config_jwt.jwt_refresh)
+        .map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::UNPROCESSABLE_ENTITY), err::MSG_JSON_WEB_TOKEN_ENCODE, &e);
+            ApiError::create(422, err::MSG_JSON_WEB_TOKEN_ENCODE, &e) // 422
+        })?;
 
     let opt_profile = res_session_profile.1?;
     if opt_profile.is_none() {
@@ -346,17 +491,182 @@ pub async fn login(
         .same_site(SameSite::Strict)
         .finish();
 
+    Ok(HttpResponse::Ok().cookie(cookie).json(login_response_dto)) // 200
+}
+
+/// login_totp
+///
+/// The second step of login for users with enabled two-factor authentication (TOTP).
+///
+/// The challenge token received from `POST /api/login` is confirmed with a one-time code from the authenticator
+/// application or with one of the recovery codes (each recovery code can be used only once).
+/// After that, a new session (device) is opened for the user.
+///
+/// Failed attempts are counted by user. After too many failed attempts, the check is temporarily blocked
+/// (error 429 with the "retryAfter" parameter).
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X POST http://localhost:8080/api/login/totp \
+/// -d '{"challengeToken": "challenge_token", "code": "123456", "device": "Laptop"}' \
+/// -H 'Content-Type: application/json'
+/// ```
+///
+/// Returns (`LoginResponseDto`) the current user data (`LoginUserProfileDto`) and the open session token (`UserTokenResponseDto`)
+/// with status 200.
+///
+#[utoipa::path(
+    request_body(content = LoginTotpDto,
+        description = "The challenge token and the one-time code (or recovery code) `LoginTotpDto`",
+        example = json!(LoginTotpDto { challenge_token: TOKEN1.to_owned(), code: "123456".to_owned(), device: None })
+    ),
+    responses(
+        ( status = 200, description = "The current user's profile and the open session token.",
+            body = LoginResponseDto,
+            example = json!(LoginResponseDto {
+                user_profile_dto: get_login_user_profile(),
+                token_user_response_dto: UserTokenResponseDto { access_token: TOKEN2.to_owned(), refresh_token: TOKEN3.to_owned() },
+            })
+        ),
+        (status = 401, description = "The challenge token or the one-time code is incorrect.", body = ApiError, examples(
+            ("Token" = (summary = "Token is invalid or expired", description = "The challenge token is invalid or expired.",
+                value = json!(ApiError::create(401, err::MSG_INVALID_OR_EXPIRED_TOKEN, "ExpiredSignature")))),
+            ("Code" = (summary = "Code is incorrect", description = "The one-time code (recovery code) is incorrect.",
+                value = json!(ApiError::new(401, err::MSG_TOTP_CODE_INCORRECT))))
+        )),
+        (status = 403, description = "The user account is suspended.", body = ApiError,
+            example = json!(ApiError::create(403, authentication::MSG_USER_SUSPENDED, "user_id: 123"))),
+        (status = 409, description = "Error when comparing hashes of recovery codes.", body = ApiError,
+            example = json!(ApiError::create(409, err::MSG_INVALID_HASH, "Parameter is empty."))),
+        (status = 417, body = [ApiError], description =
+            "Validation error. `curl -i -X POST http://localhost:8080/api/login/totp -d '{ \"challengeToken\": \"token\", \"code\": \"\" }'`",
+            example = json!(ApiError::validations(
+                (LoginTotpDto { challenge_token: TOKEN1.to_owned(), code: "".to_owned(), device: None }).validate().err().unwrap()) )),
+        (status = 422, description = "Token encoding error.", body = ApiError,
+            example = json!(ApiError::create(422, err::MSG_JSON_WEB_TOKEN_ENCODE, "InvalidKeyFormat"))),
+        (status = 429, description = "Too many failed attempts. The check is temporarily blocked.", body = ApiError,
+            example = json!(ApiError::new(429, err::MSG_TOO_MANY_ATTEMPTS).add_param(attempt_limiter::PARAM_RETRY_AFTER.into(), &60))),
+        (status = 506, description = "Blocking error.", body = ApiError, 
+            example = json!(ApiError::new(506, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError, 
+            example = json!(ApiError::new(507, "Error while querying the database."))),
+    ),
+)]
+#[post("/api/login/totp")]
+pub async fn login_totp(
+    request: HttpRequest,
+    config_jwt: web::Data<config_jwt::ConfigJwt>,
+    attempt_limiter: web::Data<AttemptLimiter>,
+    user_orm: web::Data<UserOrmApp>,
+    user_totp_orm: web::Data<UserTotpOrmApp>,
+    json_body: web::Json<LoginTotpDto>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+    // Checking the validity of the data model.
+    let validation_res = json_body.validate();
+    if let Err(validation_errors) = validation_res {
+        error!("{}-{}", code_to_str(StatusCode::EXPECTATION_FAILED), msg_validation(&validation_errors)); // 417
+        return Ok(ApiError::to_response(&ApiError::validations(validation_errors)));
+    }
+
+    let login_totp_dto: LoginTotpDto = json_body.into_inner();
+    let device = login_totp_dto.device.clone().filter(|v| v.len() > 0);
+    #[rustfmt::skip]
+    let user_agent = request.headers().get(USER_AGENT).and_then(|v| v.to_str().ok()).map(|v| v.to_owned());
+    let ip_address = request.connection_info().realip_remote_addr().map(|v| v.to_owned());
+    let jwt_secret: &[u8] = config_jwt.jwt_secret.as_bytes();
+
+    // Get user ID from the challenge token.
+    let (user_id, _) = token_coding::decode_challenge_token(&login_totp_dto.challenge_token, jwt_secret).map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::UNAUTHORIZED), err::MSG_INVALID_OR_EXPIRED_TOKEN, &e);
+        ApiError::create(401, err::MSG_INVALID_OR_EXPIRED_TOKEN, &e) // 401
+    })?;
+
+    // Failed attempts are counted by user.
+    let attempt_keys = vec![attempt_limiter::get_key(attempt_limiter::KEY_TOTP, &user_id.to_string())];
+    // If there were too many failed attempts, then return error 429.
+    is_too_many_attempts(&attempt_limiter, &attempt_keys)?;
+
+    let user_orm2 = user_orm.get_ref().clone();
+    let user_totp_orm2 = user_totp_orm.get_ref().clone();
+
+    let (res_user, res_user_totp) = web::block(move || {
+        // Get the user by ID.
+        let res_user = user_orm2.get_user_by_id(user_id, false).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        // Get the two-factor authentication settings (TOTP) of the user.
+        let res_user_totp = user_totp_orm2.get_user_totp(user_id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        (res_user, res_user_totp)
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })?;
+
+    let msg = format!("user_id: {}", user_id);
+    let user = res_user?.ok_or_else(|| {
+        error!("{}-{}; {}", code_to_str(StatusCode::UNAUTHORIZED), err::MSG_UNACCEPTABLE_TOKEN_ID, &msg);
+        ApiError::create(401, err::MSG_UNACCEPTABLE_TOKEN_ID, &msg) // 401(d)
+    })?;
+    // If the user account is suspended, then error 403.
+    let user = is_user_suspended(user)?;
+    // If two-factor authentication was disabled after the challenge token was issued, then the token is no longer valid.
+    let user_totp = res_user_totp?.filter(|user_totp| user_totp.is_enabled).ok_or_else(|| {
+        error!("{}-{}; {}", code_to_str(StatusCode::UNAUTHORIZED), err::MSG_INVALID_OR_EXPIRED_TOKEN, &msg);
+        ApiError::create(401, err::MSG_INVALID_OR_EXPIRED_TOKEN, &msg) // 401
+    })?;
+
+    let Some(modify_user_totp) = verify_totp_code(&user_totp, &login_totp_dto.code)? else {
+        attempt_limiter.add_failure(&attempt_keys);
+        error!("{}-{}", code_to_str(StatusCode::UNAUTHORIZED), err::MSG_TOTP_CODE_INCORRECT);
+        return Err(ApiError::new(401, err::MSG_TOTP_CODE_INCORRECT)); // 401
+    };
+    attempt_limiter.reset(&attempt_keys);
+
+    let old_last_step = user_totp.last_step;
+    let old_recovery_codes = user_totp.recovery_codes.clone();
+    let opt_user_totp = web::block(move || {
+        // Save the used time period (or the remaining recovery codes), if the code has not been used by another request.
+        #[rustfmt::skip]
+        let res_user_totp = user_totp_orm.use_user_totp_code(user_id, old_last_step, old_recovery_codes, modify_user_totp)
+        .map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_user_totp
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })??;
+    // The same code was accepted by a parallel request (or the settings were changed), so it cannot be used again.
+    if opt_user_totp.is_none() {
+        error!("{}-{}", code_to_str(StatusCode::UNAUTHORIZED), err::MSG_TOTP_CODE_INCORRECT);
+        return Err(ApiError::new(401, err::MSG_TOTP_CODE_INCORRECT)); // 401
+    }
+
+    let config_jwt = config_jwt.get_ref().clone();
+    let response = open_session(user, device, user_agent, ip_address, config_jwt, user_orm).await?;
+
     if let Some(timer) = timer {
-        info!("login() time: {}", format!("{:.2?}", timer.elapsed()));
+        info!("login_totp() time: {}", format!("{:.2?}", timer.elapsed()));
     }
-    Ok(HttpResponse::Ok().cookie(cookie).json(login_response_dto)) // 200
+    Ok(response) // 200
 }
 
 /// logout
 ///
 /// Exit from the authorized state.
 ///
-/// Close the session for the current user.
+/// Close the session (device) of the current request. Sessions on other devices remain active.
 ///
 /// One could call with following curl.
 /// ```text
@@ -382,16 +692,22 @@ pub async fn login(
     security(("bearer_auth" = []))
 )]
 #[post("/api/logout", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
-pub async fn logout(authenticated: Authenticated, user_orm: web::Data<UserOrmApp>) -> actix_web::Result<HttpResponse, ApiError> {
+pub async fn logout(
+    authenticated: Authenticated,
+    authenticated_session: AuthenticatedSession,
+    user_orm: web::Data<UserOrmApp>,
+) -> actix_web::Result<HttpResponse, ApiError> {
     let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
 
     // Get user ID.
     let user = authenticated.deref().clone();
+    // Get the session ID of the current request.
+    let session_id = authenticated_session.id;
 
-    // Clear "num_token" value.
+    // Delete the session of the current device.
     let opt_session = web::block(move || {
-        // Modify the entity (session) with new data. Result <Option<Session>>.
-        let res_session = user_orm.modify_session(user.id, None).map_err(|e| {
+        // Delete the entity (session). Result <Option<Session>>.
+        let res_session = user_orm.delete_session(user.id, session_id).map_err(|e| {
             error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
             ApiError::create(507, err::MSG_DATABASE, &e) // 507
         });
@@ -428,6 +744,9 @@ pub async fn logout(authenticated: Authenticated, user_orm: web::Data<UserOrmApp
 ///
 /// When a token has expired, it can be refreshed using "refresh_token".
 ///
+/// The refresh token is one-time: each call returns a new pair of tokens and the previous refresh token becomes invalid.
+/// If an already used refresh token is presented again, the session (device) it belongs to is revoked.
+///
 /// One could call with following curl.
 /// ```text
 /// curl -i -X POST http://localhost:8080/api/token \
@@ -450,9 +769,9 @@ pub async fn logout(authenticated: Authenticated, user_orm: web::Data<UserOrmApp
             ("Token" = (summary = "Token is invalid or expired",
                 description = "The token is invalid or expired.",
                 value = json!(ApiError::create(401, err::MSG_INVALID_OR_EXPIRED_TOKEN, "InvalidToken")))),
-            ("Token_number" = (summary = "Token number is incorrect", 
-                description = "The specified token number is incorrect.",
-                value = json!(ApiError::create(401, err::MSG_UNACCEPTABLE_TOKEN_NUM, "user_id: 1"))))
+            ("Token_reused" = (summary = "Refresh token has already been used",
+                description = "The refresh token has already been used. The session is revoked.",
+                value = json!(ApiError::create(401, err::MSG_REFRESH_TOKEN_REUSED, "user_id: 1"))))
         )),
         (status = 406, description = "Error session not found.", body = ApiError,
             example = json!(ApiError::create(406, err::MSG_SESSION_NOT_FOUND, "user_id: 1"))),
@@ -477,21 +796,21 @@ pub async fn update_token(
     let token = token_user_dto.token;
     let jwt_secret: &[u8] = config_jwt.jwt_secret.as_bytes();
 
-    // Get user ID.
-    let (user_id, num_token) = token_coding::decode_token(&token, jwt_secret).map_err(|e| {
+    // Get user ID, the numeric value and the session ID (token family) from the refresh token.
+    let (user_id, num_token, session_id) = token_coding::decode_refresh_token(&token, jwt_secret).map_err(|e| {
         error!("{}-{}; {}", code_to_str(StatusCode::UNAUTHORIZED), err::MSG_INVALID_OR_EXPIRED_TOKEN, &e);
         ApiError::create(401, err::MSG_INVALID_OR_EXPIRED_TOKEN, &e) // 401
     })?;
 
     let user_orm2 = user_orm.get_ref().clone();
 
-    let opt_session = web::block(move || {
-        // Find a session for a given user.
-        let existing_session = user_orm2.get_session_by_id(user_id).map_err(|e| {
+    let sessions = web::block(move || {
+        // Find the sessions (devices) for a given user.
+        let existing_sessions = user_orm2.get_sessions_by_user_id(user_id).map_err(|e| {
             error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
             ApiError::create(507, err::MSG_DATABASE, &e) // 507
         });
-        existing_session
+        existing_sessions
     })
     .await
     .map_err(|e| {
@@ -499,48 +818,45 @@ pub async fn update_token(
         ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
     })??;
 
-    let session = opt_session.ok_or_else(|| {
-        // There is no session for this user.
-        let msg = format!("user_id: {}", user_id);
-        error!("{}-{}; {}", code_to_str(StatusCode::NOT_ACCEPTABLE), err::MSG_SESSION_NOT_FOUND, &msg);
-        ApiError::create(406, err::MSG_SESSION_NOT_FOUND, &msg) // 406
-    })?;
-
-    // Each session contains an additional numeric value.
-    let session_num_token = session.num_token.unwrap_or(0);
-    // Compare an additional numeric value from the session and from the token.
-    if session_num_token != num_token {
-        // If they do not match, then this is an error.
-        let msg = format!("user_id: {}", user_id);
-        error!("{}-{}; {}", code_to_str(StatusCode::UNAUTHORIZED), err::MSG_UNACCEPTABLE_TOKEN_NUM, &msg); // 401
-        return Err(ApiError::create(401, err::MSG_UNACCEPTABLE_TOKEN_NUM, &msg));
-    }
+    // Find the session (token family) from the refresh token. If it is missing (closed or revoked), then error 406.
+    let opt_session = sessions.iter().find(|session| session.id == session_id).cloned();
+    let session = is_session_not_found(opt_session.into_iter().collect(), user_id)?.remove(0);
 
-    let num_token = token_coding::generate_num_token();
+    let num_token_new = generate_num_token_for(&sessions);
     let config_jwt = config_jwt.get_ref().clone();
     let jwt_secret: &[u8] = config_jwt.jwt_secret.as_bytes();
 
     // Pack two parameters (user.id, num_token) into a access_token.
-    let access_token = token_coding::encode_token(user_id, num_token, jwt_secret, config_jwt.jwt_access).map_err(|e| {
+    let access_token = token_coding::encode_token(user_id, num_token_new, jwt_secret, config_jwt.jwt_access).map_err(|e| {
         error!("{}-{}; {}", code_to_str(StatusCode::UNPROCESSABLE_ENTITY), err::MSG_JSON_WEB_TOKEN_ENCODE, &e);
         ApiError::create(422, err::MSG_JSON_WEB_TOKEN_ENCODE, &e) // 422
     })?;
 
-    // Pack two parameters (user.id, num_token) into a access_token.
-    let refresh_token = token_coding::encode_token(user_id, num_token, jwt_secret, config_jwt.jwt_refresh).map_err(|e| {
-        error!("{}-{}; {}", code_to_str(StatusCode::UNPROCESSABLE_ENTITY), err::MSG_JSON_WEB_TOKEN_ENCODE, &e);
-        ApiError::create(422, err::MSG_JSON_WEB_TOKEN_ENCODE, &e) // 422
-    })?;
+    // Pack three parameters (user.id, num_token, session.id) into a one-time refresh_token.
+    #[rustfmt::skip]
+    let refresh_token = token_coding::encode_refresh_token(user_id, num_token_new, session.id, jwt_secret, config_jwt.jwt_refresh)
+        .map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::UNPROCESSABLE_ENTITY), err::MSG_JSON_WEB_TOKEN_ENCODE, &e);
+            ApiError::create(422, err::MSG_JSON_WEB_TOKEN_ENCODE, &e) // 422
+        })?;
 
     let opt_session = web::block(move || {
-        // Find a session for a given user.
-        #[rustfmt::skip]
-        let existing_session = user_orm.modify_session(user_id, Some(num_token))
-        .map_err(|e| {
+        // The refresh token is one-time: its numeric value must match the current value of the session.
+        // The numeric value is replaced only if it has not been changed by another request (atomically).
+        let mut res_session = Ok(None);
+        if session.num_token == num_token {
+            res_session = user_orm.rotate_session(session.id, num_token, num_token_new);
+        }
+        if let Ok(None) = res_session {
+            // The refresh token has already been used (reuse detected), so the whole session (token family) is revoked.
+            let msg = format!("user_id: {}, session_id: {}", user_id, session.id);
+            error!("{}-{}; {}", code_to_str(StatusCode::UNAUTHORIZED), err::MSG_REFRESH_TOKEN_REUSED, &msg);
+            res_session = user_orm.delete_session(user_id, session.id).map(|_| None);
+        }
+        res_session.map_err(|e| {
             error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
             ApiError::create(507, err::MSG_DATABASE, &e) // 507
-        });
-        existing_session
+        })
     })
     .await
     .map_err(|e| {
@@ -549,10 +865,9 @@ pub async fn update_token(
     })??;
 
     if opt_session.is_none() {
-        // There is no session for this user.
+        // The session has been revoked due to reuse of the refresh token.
         let msg = format!("user_id: {}", user_id);
-        error!("{}-{}; {}", code_to_str(StatusCode::NOT_ACCEPTABLE), err::MSG_SESSION_NOT_FOUND, &msg); // 406
-        return Err(ApiError::create(406, err::MSG_SESSION_NOT_FOUND, &msg));
+        return Err(ApiError::create(401, err::MSG_REFRESH_TOKEN_REUSED, &msg)); // 401(e)
     }
 
     let token_user_response_dto = UserTokenResponseDto {
@@ -572,6 +887,228 @@ pub async fn update_token(
     Ok(HttpResponse::Ok().cookie(cookie).json(token_user_response_dto)) // 200
 }
 
+// ** Section: sessions **
+
+fn get_session_dto(id: i32, device: &str, is_current: bool) -> SessionDto {
+    let mut session = Session::new(id, 1100, 1234);
+    session.device = Some(device.to_owned());
+    session.user_agent = Some("Mozilla/5.0 (X11; Linux x86_64)".to_owned());
+    session.ip_address = Some("127.0.0.1".to_owned());
+    SessionDto::new(session, if is_current { id } else { 0 })
+}
+
+/// get_sessions
+///
+/// Get a list of the open sessions (devices) of the current user.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X GET http://localhost:8080/api/sessions
+/// ```
+///
+/// Return the list of sessions (`Vec<SessionDto>`) with status 200.
+/// The session of the current request is marked with `"isCurrent": true`.
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "List of open sessions of the current user.", body = Vec<SessionDto>,
+            example = json!(vec![get_session_dto(1, "Laptop", true), get_session_dto(2, "Phone", false)])),
+        (status = 401, description = "An authorization token is required.", body = ApiError,
+            example = json!(ApiError::new(401, err::MSG_MISSING_TOKEN))),
+        (status = 506, description = "Blocking error.", body = ApiError, 
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError, 
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/api/sessions", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn get_sessions(
+    authenticated: Authenticated,
+    authenticated_session: AuthenticatedSession,
+    user_orm: web::Data<UserOrmApp>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+    // Get user ID.
+    let user_id = authenticated.deref().id;
+    // Get the session ID of the current request.
+    let session_id = authenticated_session.id;
+
+    let sessions = web::block(move || {
+        // Get a list of entities (session) of the user.
+        let res_sessions = user_orm.get_sessions_by_user_id(user_id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_sessions
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })??;
+
+    let session_dto_list: Vec<SessionDto> = sessions.into_iter().map(|session| SessionDto::new(session, session_id)).collect();
+
+    if let Some(timer) = timer {
+        info!("get_sessions() time: {}", format!("{:.2?}", timer.elapsed()));
+    }
+    Ok(HttpResponse::Ok().json(session_dto_list)) // 200
+}
+
+/// delete_session
+///
+/// Close (revoke) the specified session (device) of the current user.
+///
+/// The tokens issued for this session stop working immediately.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X DELETE http://localhost:8080/api/sessions/1
+/// ```
+///
+/// Return the closed session (`SessionDto`) with status 200 or 204 (no content) if the session is not found.
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "The closed session.", body = SessionDto,
+            example = json!(get_session_dto(2, "Phone", false))),
+        (status = 204, description = "The specified session was not found."),
+        (status = 401, description = "An authorization token is required.", body = ApiError,
+            example = json!(ApiError::new(401, err::MSG_MISSING_TOKEN))),
+        (status = 416, description = "Error parsing input parameter. `curl -i -X DELETE http://localhost:8080/api/sessions/2a`",
+            body = ApiError, example = json!(ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED,
+                "`id` - invalid digit found in string"))),
+        (status = 506, description = "Blocking error.", body = ApiError, 
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError, 
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    params(("id", description = "Unique session ID.")),
+    security(("bearer_auth" = []))
+)]
+#[rustfmt::skip]
+#[delete("/api/sessions/{id}", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn delete_session(
+    authenticated: Authenticated,
+    authenticated_session: AuthenticatedSession,
+    user_orm: web::Data<UserOrmApp>,
+    request: actix_web::HttpRequest,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+    // Get data from request.
+    let id_str = request.match_info().query("id").to_string();
+    let id = parser::parse_i32(&id_str).map_err(|e| {
+        let msg = &format!("`{}` - {}", "id", &e);
+        error!("{}-{}; {}", code_to_str(StatusCode::RANGE_NOT_SATISFIABLE), err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg);
+        ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg) // 416
+    })?;
+    // Get user ID.
+    let user_id = authenticated.deref().id;
+    // Get the session ID of the current request.
+    let session_id = authenticated_session.id;
+
+    let opt_session = web::block(move || {
+        // Delete the entity (session) of the user. Result <Option<Session>>.
+        let res_session = user_orm.delete_session(user_id, id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_session
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })??;
+
+    if let Some(timer) = timer {
+        info!("delete_session() time: {}", format!("{:.2?}", timer.elapsed()));
+    }
+    if let Some(session) = opt_session {
+        Ok(HttpResponse::Ok().json(SessionDto::new(session, session_id))) // 200
+    } else {
+        Ok(HttpResponse::NoContent().finish()) // 204
+    }
+}
+
+/// delete_sessions
+///
+/// Close (revoke) all sessions (devices) of the current user.
+///
+/// By default, the session of the current request remains open ("sign out of all other devices").
+/// To close it as well, specify `isCurrent=true`.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X DELETE http://localhost:8080/api/sessions?isCurrent=true
+/// ```
+///
+/// Return the number of closed sessions (`DeleteSessionsResponseDto`) with status 200.
+///
+#[utoipa::path(
+    params(("isCurrent" = Option<bool>, Query, description = "Also close the session of the current request (default false).")),
+    responses(
+        (status = 200, description = "The number of closed sessions.", body = DeleteSessionsResponseDto,
+            example = json!(DeleteSessionsResponseDto { count: 2 })),
+        (status = 401, description = "An authorization token is required.", body = ApiError,
+            example = json!(ApiError::new(401, err::MSG_MISSING_TOKEN))),
+        (status = 506, description = "Blocking error.", body = ApiError, 
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError, 
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/api/sessions", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn delete_sessions(
+    authenticated: Authenticated,
+    authenticated_session: AuthenticatedSession,
+    user_orm: web::Data<UserOrmApp>,
+    query_params: web::Query<DeleteSessionsDto>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+    // Get user ID.
+    let user_id = authenticated.deref().id;
+    // Get search parameters.
+    let is_current = query_params.into_inner().is_current.unwrap_or(false);
+    // Get the session ID of the current request.
+    let except_id = if is_current { None } else { Some(authenticated_session.id) };
+
+    let count = web::block(move || {
+        // Delete the entities (session) of the user.
+        let res_count = user_orm.delete_sessions(user_id, except_id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_count
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })??;
+
+    let mut response = HttpResponse::Ok();
+    if is_current {
+        // If a cookie has expired, the browser will delete the existing cookie.
+        let cookie = Cookie::build(TOKEN_NAME, "")
+            .path("/")
+            .max_age(ActixWebDuration::new(-1, 0))
+            .http_only(true)
+            .finish();
+        response.cookie(cookie);
+    }
+
+    if let Some(timer) = timer {
+        info!("delete_sessions() time: {}", format!("{:.2?}", timer.elapsed()));
+    }
+    Ok(response.json(DeleteSessionsResponseDto { count })) // 200
+}
+
 #[cfg(all(test, feature = "mockdata"))]
 pub mod tests {
 
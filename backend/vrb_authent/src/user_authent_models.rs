@@ -8,7 +8,7 @@ use vrb_common::{
 };
 use vrb_dbase::enm_user_role::UserRole;
 
-use crate::user_models::{self, User};
+use crate::user_models::{self, Session, User};
 
 pub fn validate_nickname_or_email(value: &str) -> Result<(), ValidationError> {
     if value.contains("@") {
@@ -53,6 +53,9 @@ impl UserUniquenessResponseDto {
 pub struct LoginDto {
     pub nickname: String,
     pub password: String,
+    // Device label (for the list of sessions).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>, // max_len=255
 }
 
 impl Validator for LoginDto {
@@ -62,6 +65,9 @@ impl Validator for LoginDto {
 
         errors.push(validate_nickname_or_email(&self.nickname).err());
         errors.push(user_models::validate_password(&self.password).err());
+        if let Some(device) = &self.device {
+            errors.push(user_models::validate_device(device).err());
+        }
 
         self.filter_errors(errors)
     }
@@ -130,4 +136,55 @@ pub struct UserTokenResponseDto {
     pub refresh_token: String,
 }
 
+// ** Section: "Sessions" **
+
+// ** Used: in "user_authent_controller::get_sessions(), delete_session()". **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDto {
+    pub id: i32,
+    pub device: Option<String>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    #[serde(with = "serial_datetime")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "serial_datetime")]
+    pub last_seen_at: DateTime<Utc>,
+    // Indicates the session of the current request.
+    pub is_current: bool,
+}
+
+impl SessionDto {
+    pub fn new(session: Session, current_id: i32) -> Self {
+        SessionDto {
+            id: session.id,
+            device: session.device,
+            user_agent: session.user_agent,
+            ip_address: session.ip_address,
+            created_at: session.created_at,
+            last_seen_at: session.last_seen_at,
+            is_current: session.id == current_id,
+        }
+    }
+}
+
+// ** Used: in "user_authent_controller::delete_sessions()". **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteSessionsDto {
+    // Also close the session of the current request (default false).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_current: Option<bool>,
+}
+
+// ** Used: in "user_authent_controller::delete_sessions()". **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteSessionsResponseDto {
+    pub count: usize,
+}
+
 // ** - **
@@ -14,17 +14,26 @@ mod tests {
         api_error::{ApiError, code_to_str},
         err,
     };
-    use vrb_tools::{hash_tools, token_coding, token_data::TOKEN_NAME};
+    use vrb_tools::{hash_tools, token_coding, token_data::TOKEN_NAME, totp_tools};
 
     use crate::{
+        attempt_limiter,
+        authentication::MSG_USER_SUSPENDED,
         config_jwt,
-        user_authent_controller::{login, logout, tests as AthCtTest, update_token, users_uniqueness},
+        user_authent_controller::{
+            delete_session, delete_sessions, get_sessions, login, login_totp, logout, tests as AthCtTest, update_token, users_uniqueness,
+        },
         user_authent_models::{
-            LoginDto, LoginResponseDto, LoginUserProfileDto, UserTokenDto, UserTokenResponseDto, UserUniquenessResponseDto,
+            DeleteSessionsResponseDto, LoginDto, LoginResponseDto, LoginUserProfileDto, SessionDto, UserTokenDto, UserTokenResponseDto,
+            UserUniquenessResponseDto,
         },
         user_models::{self, Session, UserMock},
+        user_nickname_orm::tests::UserNicknameOrmTest,
         user_orm::tests::{USER, USER1_ID, UserOrmTest},
         user_registr_orm::tests::UserRegistrOrmTest,
+        user_totp_controller::CHALLENGE_DURATION,
+        user_totp_models::{self, LoginChallengeResponseDto, LoginTotpDto},
+        user_totp_orm::tests::{TOTP_SECRET, UserTotpOrmTest},
     };
 
     const MSG_ERROR_WAS_EXPECTED: &str = "Service call succeeded, but an error was expected.";
@@ -43,6 +52,7 @@ mod tests {
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::get().uri("/api/users_uniqueness")
@@ -68,6 +78,7 @@ mod tests {
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::get().uri("/api/users_uniqueness?nickname=")
@@ -94,6 +105,7 @@ mod tests {
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::get().uri("/api/users_uniqueness?email=")
@@ -121,6 +133,7 @@ mod tests {
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::get().uri(&format!("/api/users_uniqueness?nickname={}", nickname))
@@ -145,6 +158,7 @@ mod tests {
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::get().uri(&format!("/api/users_uniqueness?email={}", email))
@@ -170,6 +184,7 @@ mod tests {
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(registr))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::get().uri(&format!("/api/users_uniqueness?nickname={}", nickname))
@@ -195,6 +210,7 @@ mod tests {
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(registr))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::get().uri(&format!("/api/users_uniqueness?email={}", email))
@@ -210,6 +226,33 @@ mod tests {
         assert_eq!(response1_dto, response2_dto);
     }
     #[actix_web::test]
+    async fn test_users_uniqueness_by_nickname_reserved() {
+        let data_u = UserOrmTest::users(&[USER]);
+        let user1 = data_u.0.get(0).unwrap().clone();
+        let nickname = "old_nickname".to_string();
+        let history = UserNicknameOrmTest::nickname_history(user1.id, &nickname, &user1.nickname, 60, 600);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(users_uniqueness)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![history]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/users_uniqueness?nickname={}", nickname))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response2_dto: UserUniquenessResponseDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        let response1_dto = UserUniquenessResponseDto::new(false);
+        assert_eq!(response1_dto, response2_dto);
+    }
+    #[actix_web::test]
     async fn test_users_uniqueness_by_new_nickname() {
         let data_u = UserOrmTest::users(&[USER]);
         let nickname = format!("a{}", data_u.0.get(0).unwrap().nickname.clone());
@@ -219,6 +262,7 @@ mod tests {
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::get().uri(&format!("/api/users_uniqueness?nickname={}", nickname))
@@ -243,6 +287,7 @@ mod tests {
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::get().uri(&format!("/api/users_uniqueness?email={}", email))
@@ -266,8 +311,10 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
         ).await;
         let req = test::TestRequest::post().uri("/api/login").to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
@@ -285,8 +332,10 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
         ).await;
         let req = test::TestRequest::post().uri("/api/login").set_json(json!({})).to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
@@ -303,12 +352,14 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/login")
-            .set_json(LoginDto { nickname: "".to_string(), password: "passwordD1T1".to_string() })
+            .set_json(LoginDto { nickname: "".to_string(), password: "passwordD1T1".to_string(), device: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
@@ -325,12 +376,14 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/login")
-            .set_json(LoginDto { nickname: UserMock::nickname_min(), password: "passwordD1T1".to_string() })
+            .set_json(LoginDto { nickname: UserMock::nickname_min(), password: "passwordD1T1".to_string(), device: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
@@ -347,12 +400,14 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/login")
-            .set_json(LoginDto { nickname: UserMock::nickname_max(), password: "passwordD1T1".to_string() })
+            .set_json(LoginDto { nickname: UserMock::nickname_max(), password: "passwordD1T1".to_string(), device: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
@@ -369,12 +424,14 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/login")
-            .set_json(LoginDto { nickname: UserMock::nickname_wrong(), password: "passwordD1T1".to_string() })
+            .set_json(LoginDto { nickname: UserMock::nickname_wrong(), password: "passwordD1T1".to_string(), device: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
@@ -391,12 +448,14 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/login")
-            .set_json(LoginDto { nickname: UserMock::email_min(), password: "passwordD1T1".to_string() })
+            .set_json(LoginDto { nickname: UserMock::email_min(), password: "passwordD1T1".to_string(), device: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
@@ -413,12 +472,14 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/login")
-            .set_json(LoginDto { nickname: UserMock::email_max(), password: "passwordD1T1".to_string() })
+            .set_json(LoginDto { nickname: UserMock::email_max(), password: "passwordD1T1".to_string(), device: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
@@ -435,12 +496,14 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/login")
-            .set_json(LoginDto { nickname: UserMock::email_wrong(), password: "passwordD1T1".to_string() })
+            .set_json(LoginDto { nickname: UserMock::email_wrong(), password: "passwordD1T1".to_string(), device: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
@@ -457,12 +520,14 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/login")
-            .set_json(LoginDto { nickname: "James_Smith".to_string(), password: "".to_string() })
+            .set_json(LoginDto { nickname: "James_Smith".to_string(), password: "".to_string(), device: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
@@ -479,12 +544,14 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/login")
-            .set_json(LoginDto { nickname: "James_Smith".to_string(), password: UserMock::password_min() })
+            .set_json(LoginDto { nickname: "James_Smith".to_string(), password: UserMock::password_min(), device: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
@@ -501,12 +568,14 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/login")
-            .set_json(LoginDto { nickname: "James_Smith".to_string(), password: UserMock::password_max() })
+            .set_json(LoginDto { nickname: "James_Smith".to_string(), password: UserMock::password_max(), device: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
@@ -523,12 +592,14 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/login")
-            .set_json(LoginDto { nickname: "James_Smith".to_string(), password: UserMock::password_wrong() })
+            .set_json(LoginDto { nickname: "James_Smith".to_string(), password: UserMock::password_wrong(), device: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
@@ -546,12 +617,14 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/login")
-            .set_json(LoginDto { nickname: format!("a{}", nickname), password: "passwordD1T1".to_string() })
+            .set_json(LoginDto { nickname: format!("a{}", nickname), password: "passwordD1T1".to_string(), device: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::UNAUTHORIZED); // 401(f)
@@ -569,12 +642,14 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/login")
-            .set_json(LoginDto { nickname: format!("a{}", email), password: "passwordD1T1".to_string() })
+            .set_json(LoginDto { nickname: format!("a{}", email), password: "passwordD1T1".to_string(), device: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::UNAUTHORIZED); // 401(f)
@@ -595,12 +670,14 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/login")
-            .set_json(LoginDto { nickname: nickname.to_string(), password: password.to_string() })
+            .set_json(LoginDto { nickname: nickname.to_string(), password: password.to_string(), device: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::CONFLICT); // 409
@@ -622,12 +699,14 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/login")
-            .set_json(LoginDto { nickname: nickname.to_string(), password: format!("{}b", password) })
+            .set_json(LoginDto { nickname: nickname.to_string(), password: format!("{}b", password), device: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::UNAUTHORIZED); // 401(g)
@@ -639,6 +718,81 @@ mod tests {
         assert_eq!(app_err.message, err::MSG_PASSWORD_INCORRECT);
     }
     #[actix_web::test]
+    async fn test_login_if_too_many_attempts() {
+        let mut data_u = UserOrmTest::users(&[USER]);
+        let user1 = data_u.0.get_mut(0).unwrap();
+        let nickname = "Robert_Brown".to_string();
+        let password = "passwdR2B2";
+        user1.nickname = nickname.clone().to_lowercase();
+        user1.password = hash_tools::encode_hash(password).unwrap(); // hashed
+        let attempt_limiter = attempt_limiter::tests::get_attempt_limiter();
+        let max_attempts = attempt_limiter.max_attempts;
+        let lockout = attempt_limiter.lockout;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
+        ).await;
+        for _ in 0..max_attempts {
+            #[rustfmt::skip]
+            let req = test::TestRequest::post().uri("/api/login")
+                .set_json(LoginDto { nickname: nickname.to_string(), password: format!("{}b", password), device: None })
+                .to_request();
+            let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::UNAUTHORIZED); // 401(g)
+        }
+        // Even the correct password is rejected while the lock is in effect.
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/login")
+            .set_json(LoginDto { nickname: nickname.to_string(), password: password.to_string(), device: None })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS); // 429
+
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::TOO_MANY_REQUESTS));
+        assert_eq!(app_err.message, err::MSG_TOO_MANY_ATTEMPTS);
+        let retry_after = app_err.params.get(attempt_limiter::PARAM_RETRY_AFTER).unwrap().as_i64().unwrap();
+        assert!(0 < retry_after && retry_after <= lockout + 1);
+    }
+    #[actix_web::test]
+    async fn test_login_valid_credentials_resets_attempts() {
+        let mut data_u = UserOrmTest::users(&[USER]);
+        let user1 = data_u.0.get_mut(0).unwrap();
+        let nickname = "Robert_Brown".to_string();
+        let password = "passwdR2B2";
+        user1.nickname = nickname.clone().to_lowercase();
+        user1.password = hash_tools::encode_hash(password).unwrap(); // hashed
+        let attempt_limiter = attempt_limiter::tests::get_attempt_limiter();
+        let max_attempts = attempt_limiter.max_attempts;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
+        ).await;
+        for idx in 0..(max_attempts * 2 - 2) {
+            // Every "max_attempts - 1" failed attempts are followed by a successful login.
+            #[rustfmt::skip]
+            let value = if (idx + 1) % max_attempts == 0 { password.to_string() } else { format!("{}b", password) };
+            #[rustfmt::skip]
+            let req = test::TestRequest::post().uri("/api/login")
+                .set_json(LoginDto { nickname: nickname.to_string(), password: value.clone(), device: None })
+                .to_request();
+            let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+            #[rustfmt::skip]
+            let status = if value == password { StatusCode::OK } else { StatusCode::UNAUTHORIZED };
+            assert_eq!(resp.status(), status);
+        }
+    }
+    #[actix_web::test]
     async fn test_login_err_jsonwebtoken_encode() {
         let mut data_u = UserOrmTest::users(&[USER]);
         let nickname = "Robert_Brown".to_string();
@@ -651,12 +805,14 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/login")
-            .set_json(LoginDto { nickname: nickname.to_string(), password: password.to_string() })
+            .set_json(LoginDto { nickname: nickname.to_string(), password: password.to_string(), device: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY); // 422
@@ -671,31 +827,82 @@ mod tests {
     async fn test_login_if_session_not_exist() {
         let mut data_u = UserOrmTest::users(&[USER]);
         let user1 = data_u.0.last_mut().unwrap();
-        // Change ID, reset connection with session.
         let nickname = user1.nickname.clone();
         let password = "passwdR2B2";
-        let user1_id = user1.id;
         user1.password = hash_tools::encode_hash(password).unwrap(); // hashed
-        let session1 = data_u.1.get_mut(0).unwrap();
-        session1.user_id = session1.user_id + 1;
+        // The user has no open sessions (devices).
+        data_u.1 = vec![];
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/login")
-            .set_json(LoginDto { nickname: nickname.to_string(), password: password.to_string() })
+            .set_json(LoginDto { nickname: nickname.to_string(), password: password.to_string(), device: Some("Laptop".to_owned()) })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
-        assert_eq!(resp.status(), StatusCode::NOT_ACCEPTABLE); // 406
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let login_resp: LoginResponseDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert!(login_resp.token_user_response_dto.access_token.len() > 0);
+    }
+    #[actix_web::test]
+    async fn test_login_invalid_dto_device_max() {
+        let data_u = UserOrmTest::users(&[]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/login")
+            .set_json(LoginDto { nickname: "James_Smith".to_string(), password: "passwordD1T1".to_string(), device: Some(UserMock::device_max()) })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
 
         assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err_vec: Vec<ApiError> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        #[rustfmt::skip]
+        AthCtTest::check_app_err(app_err_vec, &code_to_str(StatusCode::EXPECTATION_FAILED), &[user_models::MSG_DEVICE_MAX_LENGTH]);
+    }
+    #[actix_web::test]
+    async fn test_login_if_user_suspended() {
+        let mut data_u = UserOrmTest::users(&[USER]);
+        let user1 = data_u.0.get_mut(0).unwrap();
+        let nickname = user1.nickname.clone();
+        let password = "passwdR2B2";
+        user1.password = hash_tools::encode_hash(password).unwrap(); // hashed
+        user1.suspended_at = Some(Utc::now());
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/login")
+            .set_json(LoginDto { nickname: nickname.to_string(), password: password.to_string(), device: None })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN); // 403
+        assert!(resp.response().cookies().find(|cookie| cookie.name() == TOKEN_NAME).is_none());
+
         let body = body::to_bytes(resp.into_body()).await.unwrap();
         let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
-        assert_eq!(app_err.code, code_to_str(StatusCode::NOT_ACCEPTABLE));
-        assert_eq!(app_err.message, format!("{}; user_id: {}", err::MSG_SESSION_NOT_FOUND, user1_id));
+        assert_eq!(app_err.code, code_to_str(StatusCode::FORBIDDEN));
+        assert_eq!(app_err.message, format!("{}; user_id: {}", MSG_USER_SUSPENDED, USER1_ID));
     }
     #[actix_web::test]
     async fn test_login_valid_credentials() {
@@ -716,12 +923,14 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/login")
-            .set_json(LoginDto { nickname: nickname.to_string(), password: password.to_string() })
+            .set_json(LoginDto { nickname: nickname.to_string(), password: password.to_string(), device: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::OK); // 200
@@ -769,6 +978,295 @@ mod tests {
         assert_eq!(res_updated_at, user_profile_ser.updated_at.to_rfc3339_opts(SecondsFormat::Secs, true));
     }
 
+    // ** login (two-factor authentication) **
+
+    fn get_challenge_token(user_id: i32) -> String {
+        let config_jwt = config_jwt::tests::get_config();
+        let jwt_secret: &[u8] = config_jwt.jwt_secret.as_bytes();
+        token_coding::encode_challenge_token(user_id, 1234, jwt_secret, CHALLENGE_DURATION).unwrap()
+    }
+    fn get_totp_code() -> String {
+        let time_step = totp_tools::get_time_step(Utc::now().timestamp());
+        totp_tools::generate_code(TOTP_SECRET, time_step).unwrap()
+    }
+
+    #[actix_web::test]
+    async fn test_login_if_totp_enabled() {
+        let mut data_u = UserOrmTest::users(&[USER]);
+        let user1 = data_u.0.get_mut(0).unwrap();
+        let nickname = user1.nickname.clone();
+        let password = "passwdR2B2";
+        user1.password = hash_tools::encode_hash(password).unwrap(); // hashed
+        let user_totp1 = UserTotpOrmTest::user_totp(user1.id, true, &[]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![user_totp1]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/login")
+            .set_json(LoginDto { nickname: nickname.to_string(), password: password.to_string(), device: None })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::ACCEPTED); // 202
+        // The session is not opened yet, so there is no token cookie.
+        assert!(resp.response().cookies().find(|cookie| cookie.name() == TOKEN_NAME).is_none());
+
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let challenge_resp: LoginChallengeResponseDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        let config_jwt = config_jwt::tests::get_config();
+        let jwt_secret: &[u8] = config_jwt.jwt_secret.as_bytes();
+        let (user_id, _) = token_coding::decode_challenge_token(&challenge_resp.challenge_token, jwt_secret).unwrap();
+        assert_eq!(user_id, USER1_ID);
+        // The challenge token cannot be used as an access token.
+        assert!(token_coding::decode_token(&challenge_resp.challenge_token, jwt_secret).is_err());
+    }
+    #[actix_web::test]
+    async fn test_login_if_totp_not_confirmed() {
+        let mut data_u = UserOrmTest::users(&[USER]);
+        let user1 = data_u.0.get_mut(0).unwrap();
+        let nickname = user1.nickname.clone();
+        let password = "passwdR2B2";
+        user1.password = hash_tools::encode_hash(password).unwrap(); // hashed
+        let user_totp1 = UserTotpOrmTest::user_totp(user1.id, false, &[]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(login)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![user_totp1]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/login")
+            .set_json(LoginDto { nickname: nickname.to_string(), password: password.to_string(), device: None })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+    }
+
+    // ** login_totp **
+
+    #[actix_web::test]
+    async fn test_login_totp_invalid_dto_code_empty() {
+        let data_u = UserOrmTest::users(&[USER]);
+        let user_totp1 = UserTotpOrmTest::user_totp(USER1_ID, true, &[]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(login_totp)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![user_totp1]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/login/totp")
+            .set_json(LoginTotpDto { challenge_token: get_challenge_token(USER1_ID), code: "".to_owned(), device: None })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
+
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err_vec: Vec<ApiError> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        #[rustfmt::skip]
+        AthCtTest::check_app_err(app_err_vec, &code_to_str(StatusCode::EXPECTATION_FAILED), &[user_totp_models::MSG_CODE_REQUIRED]);
+    }
+    #[actix_web::test]
+    async fn test_login_totp_invalid_challenge_token() {
+        let data_u = UserOrmTest::users(&[USER]);
+        let user_totp1 = UserTotpOrmTest::user_totp(USER1_ID, true, &[]);
+        // An access token cannot be used as a challenge token.
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(login_totp)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![user_totp1]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/login/totp")
+            .set_json(LoginTotpDto { challenge_token: token1, code: get_totp_code(), device: None })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED); // 401
+
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::UNAUTHORIZED));
+        assert!(app_err.message.starts_with(err::MSG_INVALID_OR_EXPIRED_TOKEN));
+    }
+    #[actix_web::test]
+    async fn test_login_totp_if_totp_disabled() {
+        let data_u = UserOrmTest::users(&[USER]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(login_totp)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/login/totp")
+            .set_json(LoginTotpDto { challenge_token: get_challenge_token(USER1_ID), code: get_totp_code(), device: None })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED); // 401
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.message, format!("{}; user_id: {}", err::MSG_INVALID_OR_EXPIRED_TOKEN, USER1_ID));
+    }
+    #[actix_web::test]
+    async fn test_login_totp_code_incorrect() {
+        let data_u = UserOrmTest::users(&[USER]);
+        let user_totp1 = UserTotpOrmTest::user_totp(USER1_ID, true, &[]);
+        let code = format!("{:06}", (get_totp_code().parse::<u32>().unwrap() + 500_000) % 1_000_000);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(login_totp)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![user_totp1]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/login/totp")
+            .set_json(LoginTotpDto { challenge_token: get_challenge_token(USER1_ID), code, device: None })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED); // 401
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::UNAUTHORIZED));
+        assert_eq!(app_err.message, err::MSG_TOTP_CODE_INCORRECT);
+    }
+    #[actix_web::test]
+    async fn test_login_totp_code_already_used() {
+        let data_u = UserOrmTest::users(&[USER]);
+        let mut user_totp1 = UserTotpOrmTest::user_totp(USER1_ID, true, &[]);
+        // The code of the current time period has already been used.
+        user_totp1.last_step = Some(totp_tools::get_time_step(Utc::now().timestamp()) + totp_tools::TOTP_SKEW);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(login_totp)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![user_totp1]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/login/totp")
+            .set_json(LoginTotpDto { challenge_token: get_challenge_token(USER1_ID), code: get_totp_code(), device: None })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED); // 401
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.message, err::MSG_TOTP_CODE_INCORRECT);
+    }
+    #[actix_web::test]
+    async fn test_login_totp_if_too_many_attempts() {
+        let data_u = UserOrmTest::users(&[USER]);
+        let user_totp1 = UserTotpOrmTest::user_totp(USER1_ID, true, &[]);
+        let code = format!("{:06}", (get_totp_code().parse::<u32>().unwrap() + 500_000) % 1_000_000);
+        let attempt_limiter = attempt_limiter::tests::get_attempt_limiter();
+        let max_attempts = attempt_limiter.max_attempts;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(login_totp)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![user_totp1]))
+        ).await;
+        for _ in 0..max_attempts {
+            #[rustfmt::skip]
+            let req = test::TestRequest::post().uri("/api/login/totp")
+                .set_json(LoginTotpDto { challenge_token: get_challenge_token(USER1_ID), code: code.clone(), device: None })
+                .to_request();
+            let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::UNAUTHORIZED); // 401
+        }
+        // Even the correct code is rejected while the lock is in effect.
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/login/totp")
+            .set_json(LoginTotpDto { challenge_token: get_challenge_token(USER1_ID), code: get_totp_code(), device: None })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS); // 429
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.message, err::MSG_TOO_MANY_ATTEMPTS);
+    }
+    #[actix_web::test]
+    async fn test_login_totp_valid_code() {
+        let data_u = UserOrmTest::users(&[USER]);
+        let user1 = data_u.0.get(0).unwrap().clone();
+        let user_totp1 = UserTotpOrmTest::user_totp(USER1_ID, true, &[]);
+        let jwt_access = config_jwt::tests::get_config().jwt_access;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(login_totp)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![user_totp1]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/login/totp")
+            .set_json(LoginTotpDto { challenge_token: get_challenge_token(USER1_ID), code: get_totp_code(), device: None })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+        let token_cookie_opt = resp.response().cookies().find(|cookie| cookie.name() == TOKEN_NAME);
+        assert!(token_cookie_opt.is_some());
+        assert_eq!(token_cookie_opt.unwrap().max_age(), Some(ActixWebDuration::new(jwt_access, 0)));
+
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let login_resp: LoginResponseDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert!(!login_resp.token_user_response_dto.access_token.is_empty());
+        assert!(!login_resp.token_user_response_dto.refresh_token.is_empty());
+        assert_eq!(login_resp.user_profile_dto.id, user1.id);
+        assert_eq!(login_resp.user_profile_dto.nickname, user1.nickname);
+    }
+    #[actix_web::test]
+    async fn test_login_totp_valid_recovery_code() {
+        let data_u = UserOrmTest::users(&[USER]);
+        let recovery_code = "k7fh2qpx9m";
+        let recovery_codes = vec![
+            hash_tools::encode_hash("w3nzr8tc4d").unwrap(),
+            hash_tools::encode_hash(recovery_code).unwrap(),
+        ];
+        let user_totp1 = UserTotpOrmTest::user_totp(USER1_ID, true, &recovery_codes);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(login_totp)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![user_totp1]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/login/totp")
+            .set_json(LoginTotpDto { challenge_token: get_challenge_token(USER1_ID), code: recovery_code.to_owned(), device: None })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+    }
+
     // ** logout **
 
     #[actix_web::test]
@@ -843,7 +1341,7 @@ mod tests {
     async fn test_logout_valid_token_non_existent_user() {
         let mut data_u = UserOrmTest::users(&[USER]);
         let user2_id = USER1_ID + 1;
-        data_u.1 = vec![Session::new(user2_id, Some(config_jwt::tests::get_num_token(user2_id)))];
+        data_u.1 = vec![Session::new(1, user2_id, config_jwt::tests::get_num_token(user2_id))];
         let token2 = config_jwt::tests::get_token(user2_id);
         #[rustfmt::skip]
         let app = test::init_service(
@@ -868,7 +1366,7 @@ mod tests {
     async fn test_logout_valid_token_non_existent_num() {
         let mut data_u = UserOrmTest::users(&[USER]);
         let user2_id = USER1_ID + 1;
-        data_u.1 = vec![Session::new(user2_id, Some(config_jwt::tests::get_num_token(USER1_ID)))];
+        data_u.1 = vec![Session::new(1, user2_id, config_jwt::tests::get_num_token(USER1_ID))];
         let token2 = config_jwt::tests::get_token(user2_id);
         #[rustfmt::skip]
         let app = test::init_service(
@@ -1025,8 +1523,10 @@ mod tests {
         let config_jwt = config_jwt::tests::get_config();
         let jwt_secret = config_jwt.jwt_secret.as_bytes();
         let user_id_bad = data_u.0.get(0).unwrap().id + 1;
-        let num_token = data_u.1.get(0).unwrap().num_token.unwrap();
-        let token_bad = token_coding::encode_token(user_id_bad, num_token, &jwt_secret, config_jwt.jwt_access).unwrap();
+        let session = data_u.1.get(0).unwrap().clone();
+        #[rustfmt::skip]
+        let token_bad = token_coding::encode_refresh_token(user_id_bad, session.num_token, session.id, &jwt_secret, config_jwt.jwt_refresh)
+            .unwrap();
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(update_token)
@@ -1054,8 +1554,10 @@ mod tests {
         let user1_id = data_u.0.get(0).unwrap().id;
         let config_jwt = config_jwt::tests::get_config();
         let jwt_secret = config_jwt.jwt_secret.as_bytes();
-        let num_token = data_u.1.get(0).unwrap().num_token.unwrap();
-        let token_bad = token_coding::encode_token(user1_id, num_token + 1, &jwt_secret, config_jwt.jwt_access).unwrap();
+        let session = data_u.1.get(0).unwrap().clone();
+        #[rustfmt::skip]
+        let token_bad = token_coding::encode_refresh_token(user1_id, session.num_token + 1, session.id, &jwt_secret, config_jwt.jwt_refresh)
+            .unwrap();
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(update_token)
@@ -1074,7 +1576,36 @@ mod tests {
         let body = body::to_bytes(resp.into_body()).await.unwrap();
         let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
         assert_eq!(app_err.code, code_to_str(StatusCode::UNAUTHORIZED));
-        assert_eq!(app_err.message, format!("{}; user_id: {}", err::MSG_UNACCEPTABLE_TOKEN_NUM, user1_id));
+        assert_eq!(app_err.message, format!("{}; user_id: {}", err::MSG_REFRESH_TOKEN_REUSED, user1_id));
+    }
+    #[actix_web::test]
+    async fn test_update_token_access_token() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let user1_id = data_u.0.get(0).unwrap().id;
+        let config_jwt = config_jwt::tests::get_config();
+        let jwt_secret = config_jwt.jwt_secret.as_bytes();
+        let num_token = data_u.1.get(0).unwrap().num_token;
+        let token_access = token_coding::encode_token(user1_id, num_token, &jwt_secret, config_jwt.jwt_refresh).unwrap();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(update_token)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/token")
+            .insert_header(AthCtTest::header_auth(&token1))
+            .set_json(UserTokenDto { token: token_access })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED); // 401
+
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::UNAUTHORIZED));
+        assert!(app_err.message.starts_with(err::MSG_INVALID_OR_EXPIRED_TOKEN));
     }
     #[actix_web::test]
     async fn test_update_token_valid_dto_token() {
@@ -1084,8 +1615,10 @@ mod tests {
         let config_jwt = config_jwt::tests::get_config();
         let jwt_access = config_jwt.jwt_access;
         let jwt_secret = config_jwt.jwt_secret.as_bytes();
-        let num_token = data_u.1.get(0).unwrap().num_token.unwrap();
-        let token_refresh = token_coding::encode_token(user1_id, num_token, &jwt_secret, config_jwt.jwt_refresh).unwrap();
+        let session = data_u.1.get(0).unwrap().clone();
+        #[rustfmt::skip]
+        let token_refresh = token_coding::encode_refresh_token(user1_id, session.num_token, session.id, &jwt_secret, config_jwt.jwt_refresh)
+            .unwrap();
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(update_token)
@@ -1121,4 +1654,161 @@ mod tests {
         assert!(refresh_token.len() > 0);
         assert_eq!(token_value, access_token);
     }
+
+    // ** get_sessions **
+
+    #[actix_web::test]
+    async fn test_get_sessions_valid_token() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let mut data_u = UserOrmTest::users(&[USER]);
+        let session1 = data_u.1.get(0).unwrap().clone();
+        let mut session2 = Session::new(session1.id + 1, USER1_ID, session1.num_token + 1);
+        session2.device = Some("Phone".to_owned());
+        data_u.1.push(session2.clone());
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_sessions)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri("/api/sessions")
+            .insert_header(AthCtTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let session_dto_res: Vec<SessionDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        let json1 = serde_json::json!(vec![
+            SessionDto::new(session1.clone(), session1.id),
+            SessionDto::new(session2, session1.id)
+        ]);
+        let session_dto_ser: Vec<SessionDto> = serde_json::from_value(json1).expect(MSG_FAILED_DESER);
+        assert_eq!(session_dto_res, session_dto_ser);
+        assert_eq!(session_dto_res.iter().filter(|v| v.is_current).count(), 1);
+    }
+
+    // ** delete_session **
+
+    #[actix_web::test]
+    async fn test_delete_session_invalid_id() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(delete_session)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::delete().uri("/api/sessions/2a")
+            .insert_header(AthCtTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::RANGE_NOT_SATISFIABLE); // 416
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::RANGE_NOT_SATISFIABLE));
+        assert!(app_err.message.starts_with(err::MSG_PARSING_TYPE_NOT_SUPPORTED));
+    }
+    #[actix_web::test]
+    async fn test_delete_session_non_existent_id() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let session_id = data_u.1.get(0).unwrap().id + 1;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(delete_session)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::delete().uri(&format!("/api/sessions/{}", session_id))
+            .insert_header(AthCtTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT); // 204
+    }
+    #[actix_web::test]
+    async fn test_delete_session_existent_id() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let mut data_u = UserOrmTest::users(&[USER]);
+        let session1 = data_u.1.get(0).unwrap().clone();
+        let session2 = Session::new(session1.id + 1, USER1_ID, session1.num_token + 1);
+        data_u.1.push(session2.clone());
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(delete_session)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::delete().uri(&format!("/api/sessions/{}", session2.id))
+            .insert_header(AthCtTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let session_dto_res: SessionDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(session_dto_res.id, session2.id);
+        assert_eq!(session_dto_res.is_current, false);
+    }
+
+    // ** delete_sessions **
+
+    #[actix_web::test]
+    async fn test_delete_sessions_except_current() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let mut data_u = UserOrmTest::users(&[USER]);
+        let session1 = data_u.1.get(0).unwrap().clone();
+        data_u.1.push(Session::new(session1.id + 1, USER1_ID, session1.num_token + 1));
+        data_u.1.push(Session::new(session1.id + 2, USER1_ID, session1.num_token + 2));
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(delete_sessions)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::delete().uri("/api/sessions")
+            .insert_header(AthCtTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+        let token_cookie_opt = resp.response().cookies().find(|cookie| cookie.name() == TOKEN_NAME);
+        assert!(token_cookie_opt.is_none());
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response_dto: DeleteSessionsResponseDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response_dto.count, 2);
+    }
+    #[actix_web::test]
+    async fn test_delete_sessions_with_current() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let mut data_u = UserOrmTest::users(&[USER]);
+        let session1 = data_u.1.get(0).unwrap().clone();
+        data_u.1.push(Session::new(session1.id + 1, USER1_ID, session1.num_token + 1));
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(delete_sessions)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::delete().uri("/api/sessions?isCurrent=true")
+            .insert_header(AthCtTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+        let token_cookie_opt = resp.response().cookies().find(|cookie| cookie.name() == TOKEN_NAME);
+        assert_eq!(token_cookie_opt.unwrap().value(), "");
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response_dto: DeleteSessionsResponseDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response_dto.count, 2);
+    }
 }
@@ -0,0 +1,529 @@
+use std::{ops::Deref, time::Instant as tm};
+
+use actix_web::{HttpResponse, get, http::StatusCode, post, put, web};
+use chrono::{Duration, Utc};
+use log::{Level::Info, error, info, log_enabled};
+use utoipa;
+use vrb_common::{
+    api_error::{ApiError, code_to_str},
+    err,
+    validators::{Validator, msg_validation},
+};
+#[cfg(not(all(test, feature = "mockdata")))]
+use vrb_tools::send_email::mailer::impls::MailerApp;
+#[cfg(all(test, feature = "mockdata"))]
+use vrb_tools::send_email::mailer::tests::MailerApp;
+use vrb_tools::{
+    config_app,
+    send_email::{config_smtp, mailer::Mailer},
+    token_coding,
+};
+
+#[cfg(not(all(test, feature = "mockdata")))]
+use crate::user_email_change_orm::impls::UserEmailChangeOrmApp;
+#[cfg(all(test, feature = "mockdata"))]
+use crate::user_email_change_orm::tests::UserEmailChangeOrmApp;
+#[cfg(not(all(test, feature = "mockdata")))]
+use crate::user_orm::impls::UserOrmApp;
+#[cfg(all(test, feature = "mockdata"))]
+use crate::user_orm::tests::UserOrmApp;
+#[cfg(not(all(test, feature = "mockdata")))]
+use crate::user_registr_orm::impls::UserRegistrOrmApp;
+#[cfg(all(test, feature = "mockdata"))]
+use crate::user_registr_orm::tests::UserRegistrOrmApp;
+use crate::{
+    attempt_limiter::{self, AttemptLimiter, is_too_many_attempts},
+    authentication::{Authenticated, RequireAuth},
+    config_jwt,
+    user_email_change_models::{
+        ConfirmEmailChangeResponseDto, CreateUserEmailChange, EmailChangeClearForExpiredResponseDto, EmailChangeDto, EmailChangeResponseDto,
+    },
+    user_email_change_orm::UserEmailChangeOrm,
+    user_models::ModifyUser,
+    user_orm::UserOrm,
+    user_registr_orm::UserRegistrOrm,
+};
+
+// 404 Not Found - Email change record not found.
+pub const MSG_EMAIL_CHANGE_NOT_FOUND: &str = "email_change_not_found";
+// 404 Not Found - User not found.
+pub const MSG_USER_NOT_FOUND: &str = "user_not_found";
+
+pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
+    |config: &mut web::ServiceConfig| {
+        config
+            // POST /api/email_change
+            .service(email_change)
+            // PUT /api/email_change/{email_change_token}
+            .service(confirm_email_change)
+            // GET /api/email_change/clear_for_expired
+            .service(email_change_clear_for_expired);
+    }
+}
+
+/// email_change
+///
+/// Send an email confirming the change of the current user's email to the new address.
+///
+/// The email of the user is changed only after confirmation (`PUT /api/email_change/{email_change_token}`).
+/// A repeated request replaces the previous one (the previous confirmation link becomes invalid).
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X POST http://localhost:8080/api/email_change \
+/// -d '{"email": "user_new@email"}' \
+/// -H 'Content-Type: application/json'
+/// ```
+///
+/// The number of requests is limited for each user. If the limit is exceeded,
+/// the request is temporarily blocked (error 429 with the "retryAfter" parameter, the number of seconds until unlocking).
+///
+/// Return the parameters of the email change request (`EmailChangeResponseDto`) with status 201.
+///
+#[utoipa::path(
+    responses(
+        (status = 201, description = "Parameters of the email change request.", body = EmailChangeResponseDto,
+            example = json!(EmailChangeResponseDto {
+                id: 27, new_email: "james_miller@gmail.us".to_owned(), final_date: Utc::now() + Duration::minutes(15) })
+        ),
+        (status = 401, description = "An authorization token is required.", body = ApiError,
+            example = json!(ApiError::new(401, err::MSG_MISSING_TOKEN))),
+        (status = 409, description = "Error: email is already in use.", body = ApiError,
+            example = json!(ApiError::new(409, err::MSG_EMAIL_ALREADY_USE))),
+        (status = 417, body = [ApiError],
+            description = "Validation error. `curl -i -X POST http://localhost:8080/api/email_change -d '{\"email\": \"us_email\" }'`",
+            example = json!(ApiError::validations((EmailChangeDto { email: "us_email".to_string() }).validate().err().unwrap()))),
+        (status = 422, description = "Token encoding error.", body = ApiError,
+            example = json!(ApiError::create(422, err::MSG_JSON_WEB_TOKEN_ENCODE, "InvalidKeyFormat"))),
+        (status = 429, description = "Too many requests. Email change is temporarily blocked.", body = ApiError,
+            example = json!(ApiError::new(429, err::MSG_TOO_MANY_ATTEMPTS).add_param(attempt_limiter::PARAM_RETRY_AFTER.into(), &60))),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+        (status = 510, description = "Error sending email.", body = ApiError,
+            example = json!(ApiError::create(510, err::MSG_ERROR_SENDING_EMAIL, "The mail server is overloaded."))),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/api/email_change", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn email_change(
+    authenticated: Authenticated,
+    config_app: web::Data<config_app::ConfigApp>,
+    attempt_limiter: web::Data<AttemptLimiter>,
+    config_jwt: web::Data<config_jwt::ConfigJwt>,
+    mailer: web::Data<MailerApp>,
+    config_smtp: web::Data<config_smtp::ConfigSmtp>,
+    user_orm: web::Data<UserOrmApp>,
+    user_registr_orm: web::Data<UserRegistrOrmApp>,
+    user_email_change_orm: web::Data<UserEmailChangeOrmApp>,
+    json_body: web::Json<EmailChangeDto>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+    // Checking the validity of the data model.
+    let validation_res = json_body.validate();
+    if let Err(validation_errors) = validation_res {
+        error!("{}-{}", code_to_str(StatusCode::EXPECTATION_FAILED), msg_validation(&validation_errors)); // 417
+        return Ok(ApiError::to_response(&ApiError::validations(validation_errors)));
+    }
+    let mut email_change_dto: EmailChangeDto = json_body.into_inner();
+    email_change_dto.email = email_change_dto.email.to_lowercase();
+    let new_email = email_change_dto.email.clone();
+
+    // Get current user details.
+    let user = authenticated.deref().clone();
+    let user_id = user.id;
+
+    // Requests are counted by user (each request can send a letter).
+    let attempt_keys = vec![attempt_limiter::get_key(attempt_limiter::KEY_EMAIL_CHANGE, &user_id.to_string())];
+    // If there were too many requests, then return error 429.
+    is_too_many_attempts(&attempt_limiter, &attempt_keys)?;
+    attempt_limiter.add_failure(&attempt_keys);
+
+    let new_email2 = new_email.clone();
+    let user_email_change_orm2 = user_email_change_orm.clone();
+    let is_email_used = web::block(move || {
+        // Search for "email" in the "users" table.
+        let opt_user = user_orm.find_user_by_nickname_or_email(None, Some(&new_email2), false).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        })?;
+        if opt_user.is_some() {
+            return Ok(true);
+        }
+        // Search for "email" in the "user_registration" table.
+        let opt_user_registr = user_registr_orm
+            .find_user_registr_by_nickname_or_email(None, Some(&new_email2))
+            .map_err(|e| {
+                error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+                ApiError::create(507, err::MSG_DATABASE, &e) // 507
+            })?;
+        if opt_user_registr.is_some() {
+            return Ok(true);
+        }
+        // Search for "email" in the requests of other users in the "user_email_change" table.
+        let opt_user_email_change = user_email_change_orm2.find_user_email_change_by_new_email(&new_email2).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        })?;
+        Ok(opt_user_email_change.map(|item| item.user_id != user_id).unwrap_or(false))
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })??;
+
+    // Since the specified "email" is not unique, return an error.
+    if is_email_used {
+        error!("{}-{}", code_to_str(StatusCode::CONFLICT), err::MSG_EMAIL_ALREADY_USE);
+        return Err(ApiError::new(409, err::MSG_EMAIL_ALREADY_USE)); // 409
+    }
+
+    // The waiting time for confirmation is the same as for registration (in seconds).
+    let app_email_change_duration: i64 = config_app.app_registr_duration.try_into().unwrap();
+    let final_date_utc = Utc::now() + Duration::seconds(app_email_change_duration);
+
+    // The number of the token is stored in the request, so that only the last token sent can confirm it.
+    let num_token = token_coding::generate_num_token();
+
+    let create_user_email_change = CreateUserEmailChange {
+        user_id,
+        new_email: new_email.clone(),
+        final_date: final_date_utc,
+        num_token,
+    };
+    // Create a new entity (user_email_change).
+    let user_email_change = web::block(move || {
+        #[rustfmt::skip]
+        let user_email_change = user_email_change_orm.create_user_email_change(create_user_email_change)
+        .map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        user_email_change
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })??;
+
+    let config_jwt = config_jwt.get_ref().clone();
+    let jwt_secret: &[u8] = config_jwt.jwt_secret.as_bytes();
+
+    // Pack two parameters (user_email_change.id, num_token) into a email_change_token.
+    #[rustfmt::skip]
+    let email_change_token = token_coding::encode_email_change_token(
+        user_email_change.id, user_email_change.num_token, jwt_secret, app_email_change_duration)
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::UNPROCESSABLE_ENTITY), err::MSG_JSON_WEB_TOKEN_ENCODE, &e);
+        ApiError::create(422, err::MSG_JSON_WEB_TOKEN_ENCODE, &e) // 422
+    })?;
+
+    let config_smtp = config_smtp.get_ref().clone();
+    let path_template = config_smtp.smtp_path_template;
+    // Prepare a letter to the new address confirming this change.
+    let domain = &config_app.app_domain;
+    let subject = format!("Email change in {}", &config_app.app_name);
+    let nickname = user.nickname.clone();
+    let receiver = user_email_change.new_email.clone();
+    let target = email_change_token;
+    let email_change_duration = app_email_change_duration / 60; // Convert from seconds to minutes.
+    #[rustfmt::skip]
+    let result = mailer.send_email_change_code(
+        &path_template, &receiver, &domain, &subject, &nickname, &target, email_change_duration);
+
+    if let Err(e) = result {
+        error!("{}-{}; {}", code_to_str(StatusCode::NOT_EXTENDED), err::MSG_ERROR_SENDING_EMAIL, &e);
+        return Err(ApiError::create(510, err::MSG_ERROR_SENDING_EMAIL, &e)); // 510
+    }
+
+    let email_change_response_dto = EmailChangeResponseDto {
+        id: user_email_change.id,
+        new_email: user_email_change.new_email,
+        final_date: user_email_change.final_date,
+    };
+    if let Some(timer) = timer {
+        info!("email_change() time: {}", format!("{:.2?}", timer.elapsed()));
+    }
+    Ok(HttpResponse::Created().json(email_change_response_dto)) // 201
+}
+
+/// confirm_email_change
+///
+/// Confirmation of the change of the user's email.
+///
+/// The user's email is replaced with the new address, and a notice of the change is sent to the old address.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X PUT http://localhost:8080/api/email_change/email_change_token1234
+/// ```
+///
+/// Returns data about the user whose email was changed (`ConfirmEmailChangeResponseDto`), with status 200.
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "Information about the user whose email was changed.", body = ConfirmEmailChangeResponseDto,
+            example = json!(ConfirmEmailChangeResponseDto { id: 120, nickname: "james_miller".to_owned()
+                , email: "james_miller@gmail.us".to_owned(), created_at: Utc::now() - Duration::days(20)
+                , updated_at: Utc::now() })
+        ),
+        (status = 401, description = "The token is invalid or expired.", body = ApiError,
+            example = json!(ApiError::create(401, err::MSG_INVALID_OR_EXPIRED_TOKEN, "InvalidToken"))),
+        (status = 404, description = "Error: record not found.", body = ApiError, examples(
+            ("email_change" = (summary = "email_change_not_found",
+                description = "An record to change the user's email was not found.",
+                value = json!(ApiError::create(404, MSG_EMAIL_CHANGE_NOT_FOUND, "user_email_change_id: 1234")))),
+            ("user" = (summary = "user_not_found",
+                description = "User not found.",
+                value = json!(ApiError::create(404, MSG_USER_NOT_FOUND, "user_id: 123"))))
+        )),
+        (status = 409, description = "Error: email is already in use.", body = ApiError,
+            example = json!(ApiError::new(409, err::MSG_EMAIL_ALREADY_USE))),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    params(("email_change_token", description = "Email change token.")),
+)]
+#[put("/api/email_change/{email_change_token}")]
+pub async fn confirm_email_change(
+    request: actix_web::HttpRequest,
+    config_app: web::Data<config_app::ConfigApp>,
+    config_jwt: web::Data<config_jwt::ConfigJwt>,
+    mailer: web::Data<MailerApp>,
+    config_smtp: web::Data<config_smtp::ConfigSmtp>,
+    user_orm: web::Data<UserOrmApp>,
+    user_email_change_orm: web::Data<UserEmailChangeOrmApp>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+    let email_change_token = request.match_info().query("email_change_token").to_string();
+
+    let config_jwt = config_jwt.get_ref().clone();
+    let jwt_secret: &[u8] = config_jwt.jwt_secret.as_bytes();
+
+    // Check the signature and expiration date on the received "email_change_token".
+    let dual_token = token_coding::decode_email_change_token(&email_change_token, jwt_secret).map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::UNAUTHORIZED), err::MSG_INVALID_OR_EXPIRED_TOKEN, &e);
+        ApiError::create(401, err::MSG_INVALID_OR_EXPIRED_TOKEN, &e) // 401
+    })?;
+
+    // Get "user_email_change ID" and "num_token" from "email_change_token".
+    let (user_email_change_id, num_token) = dual_token;
+
+    let user_email_change_orm2 = user_email_change_orm.clone();
+    // Find a record with the specified ID in the "user_email_change" table.
+    let opt_user_email_change = web::block(move || {
+        let user_email_change = user_email_change_orm2.get_user_email_change_by_id(user_email_change_id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        user_email_change
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })??;
+
+    let user_email_change_orm2 = user_email_change_orm.clone();
+    // Delete entries in the "user_email_change" table, that are already expired.
+    let _ = web::block(move || user_email_change_orm2.delete_inactive_final_date(None)).await;
+
+    // If no such entry exists (or it has already expired), then exit with code 404.
+    let user_email_change = opt_user_email_change.filter(|item| item.final_date > Utc::now()).ok_or_else(|| {
+        let msg = format!("user_email_change_id: {}", user_email_change_id);
+        error!("{}-{}; {}", code_to_str(StatusCode::NOT_FOUND), MSG_EMAIL_CHANGE_NOT_FOUND, &msg);
+        ApiError::create(404, MSG_EMAIL_CHANGE_NOT_FOUND, &msg) // 404
+    })?;
+    // The token must belong to this request (and not to a previous request with the same ID).
+    if user_email_change.num_token != num_token {
+        let msg = format!("user_email_change_id: {}; {}", user_email_change_id, token_coding::CD_UNALLOWABLE_TOKEN);
+        error!("{}-{}; {}", code_to_str(StatusCode::UNAUTHORIZED), err::MSG_INVALID_OR_EXPIRED_TOKEN, &msg);
+        return Err(ApiError::create(401, err::MSG_INVALID_OR_EXPIRED_TOKEN, &msg)); // 401
+    }
+    let user_id = user_email_change.user_id;
+    let new_email = user_email_change.new_email.clone();
+
+    let user_orm2 = user_orm.clone();
+    let new_email2 = new_email.clone();
+    let (opt_user, is_email_used) = web::block(move || {
+        // Find user by id.
+        let opt_user = user_orm2.get_user_by_id(user_id, false).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        })?;
+        // The new email could have been used by another user while waiting for confirmation.
+        let opt_user2 = user_orm2.find_user_by_nickname_or_email(None, Some(&new_email2), false).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        })?;
+        Ok::<(_, bool), ApiError>((opt_user, opt_user2.is_some()))
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })??;
+
+    // If no such entry exists, then exit with code 404.
+    let user = opt_user.ok_or_else(|| {
+        let msg = format!("user_id: {}", user_id);
+        error!("{}-{}; {}", code_to_str(StatusCode::NOT_FOUND), MSG_USER_NOT_FOUND, &msg);
+        ApiError::create(404, MSG_USER_NOT_FOUND, &msg) // 404
+    })?;
+    if is_email_used {
+        error!("{}-{}", code_to_str(StatusCode::CONFLICT), err::MSG_EMAIL_ALREADY_USE);
+        return Err(ApiError::new(409, err::MSG_EMAIL_ALREADY_USE)); // 409
+    }
+    let old_email = user.email.clone();
+
+    // Create a model to update the "email" field in the user profile.
+    let modify_user = ModifyUser::new(None, Some(new_email.clone()), None, None);
+    // Update the email for the user profile.
+    let opt_user = web::block(move || {
+        let opt_user1 = user_orm.modify_user(user_id, modify_user).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        opt_user1
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })??;
+
+    let user = opt_user.ok_or_else(|| {
+        let msg = format!("user_id: {}", user_id);
+        error!("{}-{}; {}", code_to_str(StatusCode::NOT_FOUND), MSG_USER_NOT_FOUND, &msg);
+        ApiError::create(404, MSG_USER_NOT_FOUND, &msg) // 404
+    })?;
+
+    // Delete the processed record in the "user_email_change" table.
+    let _ = web::block(move || user_email_change_orm.delete_user_email_change(user_email_change_id)).await;
+
+    let config_smtp = config_smtp.get_ref().clone();
+    let path_template = config_smtp.smtp_path_template;
+    // Prepare a letter to the old address with a notice of the change.
+    let domain = &config_app.app_domain;
+    let subject = format!("Email changed in {}", &config_app.app_name);
+    #[rustfmt::skip]
+    let result = mailer.send_email_change_notice(
+        &path_template, &old_email, &domain, &subject, &user.nickname, &user.email);
+    // The email has already been changed, so an error sending the notice does not cancel the change.
+    if let Err(e) = result {
+        error!("{}-{}; {}", code_to_str(StatusCode::NOT_EXTENDED), err::MSG_ERROR_SENDING_EMAIL, &e);
+    }
+
+    let response_dto = ConfirmEmailChangeResponseDto {
+        id: user.id,
+        nickname: user.nickname,
+        email: user.email,
+        created_at: user.created_at,
+        updated_at: user.updated_at,
+    };
+    if let Some(timer) = timer {
+        info!("confirm_email_change() time: {}", format!("{:.2?}", timer.elapsed()));
+    }
+    Ok(HttpResponse::Ok().json(response_dto)) // 200
+}
+
+/// email_change_clear_for_expired
+///
+/// Clean up expired requests to change the user's email.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X GET http://localhost:8080/api/email_change/clear_for_expired
+/// ```
+///
+/// Returns the number (of expired) records deleted (`EmailChangeClearForExpiredResponseDto`) with status 200.
+///
+/// The "admin" role is required.
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "The number of deleted expired email change records.",
+            body = EmailChangeClearForExpiredResponseDto,
+            example = json!(EmailChangeClearForExpiredResponseDto { count_inactive_email_change: 3 })
+        ),
+        (status = 401, description = "An authorization token is required.", body = ApiError,
+            example = json!(ApiError::new(401, err::MSG_MISSING_TOKEN))),
+        (status = 403, description = "Access denied: insufficient user rights.", body = ApiError,
+            example = json!(ApiError::new(403, err::MSG_ACCESS_DENIED))),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[rustfmt::skip]
+#[get("/api/email_change/clear_for_expired", wrap = "RequireAuth::allowed_roles(RequireAuth::admin_role())")]
+pub async fn email_change_clear_for_expired(
+    user_email_change_orm: web::Data<UserEmailChangeOrmApp>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+    // Delete entries in the "user_email_change" table, that are already expired.
+    let count_inactive_email_change_res =
+        web::block(move || user_email_change_orm.delete_inactive_final_date(None)
+        .map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        })
+        ).await
+        .map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+            ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+        })?;
+
+    let count_inactive_email_change = count_inactive_email_change_res.unwrap_or(0);
+
+    let clear_for_expired_response_dto = EmailChangeClearForExpiredResponseDto {
+        count_inactive_email_change,
+    };
+    if let Some(timer) = timer {
+        info!("email_change_clear_for_expired() time: {}", format!("{:.2?}", timer.elapsed()));
+    }
+    Ok(HttpResponse::Ok().json(clear_for_expired_response_dto)) // 200
+}
+
+#[cfg(all(test, feature = "mockdata"))]
+pub mod tests {
+
+    use actix_web::{http, web};
+    use vrb_tools::{
+        config_app,
+        send_email::{config_smtp, mailer::tests::MailerApp},
+        token_data::BEARER,
+    };
+
+    pub fn header_auth(token: &str) -> (http::header::HeaderName, http::header::HeaderValue) {
+        let header_value = http::header::HeaderValue::from_str(&format!("{}{}", BEARER, token)).unwrap();
+        (http::header::AUTHORIZATION, header_value)
+    }
+
+    pub fn cfg_config_app(config_app: config_app::ConfigApp) -> impl FnOnce(&mut web::ServiceConfig) {
+        move |config: &mut web::ServiceConfig| {
+            let data_config_app = web::Data::new(config_app);
+            config.app_data(web::Data::clone(&data_config_app));
+        }
+    }
+
+    pub fn cfg_mailer(config_smtp: config_smtp::ConfigSmtp) -> impl FnOnce(&mut web::ServiceConfig) {
+        move |config: &mut web::ServiceConfig| {
+            let data_config_smtp = web::Data::new(config_smtp.clone());
+            config.app_data(web::Data::clone(&data_config_smtp));
+
+            let data_mailer = web::Data::new(MailerApp::new(config_smtp));
+            config.app_data(web::Data::clone(&data_mailer));
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use vrb_common::validators::{ValidationError, Validator};
+use vrb_dbase::schema;
+
+use crate::user_models;
+
+// ** Section: "UserEmailChange" **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Queryable, Selectable)]
+#[diesel(table_name = schema::user_email_change)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UserEmailChange {
+    pub id: i32,
+    pub user_id: i32,
+    pub new_email: String,
+    pub final_date: DateTime<Utc>,
+    pub num_token: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, AsChangeset, Insertable)]
+#[diesel(table_name = schema::user_email_change)]
+pub struct CreateUserEmailChange {
+    pub user_id: i32,
+    pub new_email: String,
+    pub final_date: DateTime<Utc>,
+    pub num_token: i32,
+}
+
+// ** Used: in "user_email_change_controller::email_change(). **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailChangeDto {
+    // New email address.
+    pub email: String,
+}
+
+impl Validator for EmailChangeDto {
+    // Check the model against the required conditions.
+    fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors: Vec<Option<ValidationError>> = vec![];
+
+        errors.push(user_models::validate_email(&self.email).err());
+
+        self.filter_errors(errors)
+    }
+}
+
+// ** Used: in "user_email_change_controller::email_change(). **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailChangeResponseDto {
+    pub id: i32,
+    // New email address (waiting for confirmation).
+    pub new_email: String,
+    // Date and time until which the change can be confirmed.
+    pub final_date: DateTime<Utc>,
+}
+
+// ** Used: in "user_email_change_controller::confirm_email_change(). **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmEmailChangeResponseDto {
+    pub id: i32,
+    pub nickname: String,
+    pub email: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ** Model Dto: "EmailChangeClearForExpiredResponseDto". Used: in "user_email_change_controller::email_change_clear_for_expired(). **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailChangeClearForExpiredResponseDto {
+    pub count_inactive_email_change: usize,
+}
@@ -0,0 +1,297 @@
+use vrb_dbase::dbase::DbPool;
+
+use crate::user_email_change_models::{CreateUserEmailChange, UserEmailChange};
+
+pub const DURATION_IN_DAYS: u16 = 90;
+
+pub trait UserEmailChangeOrm {
+    /// Get an entity (user_email_change) by ID.
+    fn get_user_email_change_by_id(&self, id: i32) -> Result<Option<UserEmailChange>, String>;
+    /// Find for an entity (user_email_change) by new email (where final_date > now).
+    fn find_user_email_change_by_new_email(&self, new_email: &str) -> Result<Option<UserEmailChange>, String>;
+    /// Add a new entity (user_email_change), the previous request of this user is deleted.
+    fn create_user_email_change(&self, create_user_email_change: CreateUserEmailChange) -> Result<UserEmailChange, String>;
+    /// Delete an entity (user_email_change).
+    fn delete_user_email_change(&self, id: i32) -> Result<usize, String>;
+    /// Delete all entities (user_email_change) with an inactive "final_date".
+    fn delete_inactive_final_date(&self, duration_in_days: Option<u16>) -> Result<usize, String>;
+}
+
+#[cfg(not(all(test, feature = "mockdata")))]
+pub fn get_user_email_change_orm_app(pool: DbPool) -> impls::UserEmailChangeOrmApp {
+    impls::UserEmailChangeOrmApp::new(pool)
+}
+#[cfg(all(test, feature = "mockdata"))]
+pub fn get_user_email_change_orm_app(_: DbPool) -> tests::UserEmailChangeOrmApp {
+    tests::UserEmailChangeOrmApp::new()
+}
+
+#[cfg(not(all(test, feature = "mockdata")))]
+pub mod impls {
+    use std::time::Instant as tm;
+
+    use chrono::{Duration, Utc};
+    use diesel::{self, prelude::*};
+    use log::{Level::Info, info, log_enabled};
+    use vrb_dbase::{
+        dbase,
+        schema::{self, user_email_change::dsl},
+    };
+
+    use super::*;
+
+    pub const CONN_POOL: &str = "ConnectionPool";
+
+    #[derive(Debug, Clone)]
+    pub struct UserEmailChangeOrmApp {
+        pub pool: dbase::DbPool,
+    }
+
+    impl UserEmailChangeOrmApp {
+        pub fn new(pool: dbase::DbPool) -> Self {
+            UserEmailChangeOrmApp { pool }
+        }
+        pub fn get_conn(&self) -> Result<dbase::DbPooledConnection, String> {
+            (&self.pool).get().map_err(|e| format!("{CONN_POOL}: {}", e.to_string()))
+        }
+    }
+
+    impl UserEmailChangeOrm for UserEmailChangeOrmApp {
+        /// Get an entity (user_email_change) by ID.
+        fn get_user_email_change_by_id(&self, id: i32) -> Result<Option<UserEmailChange>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+            // Run query using Diesel to find the entry by id and return it.
+            let result = schema::user_email_change::table
+                .filter(dsl::id.eq(id))
+                .first::<UserEmailChange>(&mut conn)
+                .optional()
+                .map_err(|e| format!("get_user_email_change_by_id: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("get_user_email_change_by_id() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(result)
+        }
+
+        /// Find for an entity (user_email_change) by new email (where final_date > now).
+        fn find_user_email_change_by_new_email(&self, new_email: &str) -> Result<Option<UserEmailChange>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            let new_email2 = new_email.to_lowercase();
+            if new_email2.len() == 0 {
+                return Ok(None);
+            }
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+            let now = Utc::now();
+            // Run query using Diesel to find the entry by new_email and return it (where final_date > now).
+            let result = schema::user_email_change::table
+                .filter(dsl::new_email.eq(new_email2).and(dsl::final_date.gt(now)))
+                .first::<UserEmailChange>(&mut conn)
+                .optional()
+                .map_err(|e| format!("find_user_email_change_by_new_email: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                #[rustfmt::skip]
+                info!("find_user_email_change_by_new_email() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(result)
+        }
+
+        /// Add a new entity (user_email_change), the previous request of this user is deleted.
+        fn create_user_email_change(&self, create_user_email_change: CreateUserEmailChange) -> Result<UserEmailChange, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            let mut create_user_email_change2 = create_user_email_change.clone();
+            create_user_email_change2.new_email = create_user_email_change2.new_email.to_lowercase();
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+            // The new request gets a new ID, so the token of the previous request is no longer valid.
+            let user_email_change = conn
+                .transaction::<_, diesel::result::Error, _>(|conn| {
+                    // Run query using Diesel to delete the previous request of this user.
+                    diesel::delete(schema::user_email_change::table.filter(dsl::user_id.eq(create_user_email_change2.user_id)))
+                        .execute(conn)?;
+                    // Run query using Diesel to add a new entry (user_email_change).
+                    diesel::insert_into(schema::user_email_change::table)
+                        .values(create_user_email_change2)
+                        .returning(UserEmailChange::as_returning())
+                        .get_result(conn)
+                })
+                .map_err(|e| format!("create_user_email_change: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("create_user_email_change() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(user_email_change)
+        }
+
+        /// Delete an entity (user_email_change).
+        fn delete_user_email_change(&self, id: i32) -> Result<usize, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+            // Run query using Diesel to delete a entry (user_email_change).
+            let count: usize = diesel::delete(dsl::user_email_change.find(id))
+                .execute(&mut conn)
+                .map_err(|e| format!("delete_user_email_change: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("delete_user_email_change() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(count)
+        }
+
+        /// Delete all entities (user_email_change) with an inactive "final_date".
+        fn delete_inactive_final_date(&self, duration_in_days: Option<u16>) -> Result<usize, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            let now = Utc::now();
+            let duration = duration_in_days.unwrap_or(DURATION_IN_DAYS.into());
+            let start_day_time = now - Duration::days(duration.into());
+            let end_day_time = now.clone();
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+            // Run query using Diesel to delete a entry (user_email_change).
+            #[rustfmt::skip]
+            let count: usize = diesel::delete(
+                schema::user_email_change::table
+                .filter(dsl::final_date.gt(start_day_time).and(dsl::final_date.lt(end_day_time))),
+            )
+            .execute(&mut conn)
+            .map_err(|e| format!("delete_inactive_final_date: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                #[rustfmt::skip]
+                info!("delete_inactive_final_date() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(count)
+        }
+    }
+}
+
+#[cfg(any(test, feature = "mockdata"))]
+pub mod tests {
+    use actix_web::web;
+    use chrono::{DateTime, Duration, Utc};
+
+    use crate::user_email_change_models::{CreateUserEmailChange, UserEmailChange};
+
+    use super::{DURATION_IN_DAYS, UserEmailChangeOrm};
+
+    pub const USER_EMAIL_CHANGE_ID: i32 = 1400;
+    pub const USER_EMAIL_CHANGE_NUM_TOKEN: i32 = 1450;
+
+    #[derive(Debug, Clone)]
+    pub struct UserEmailChangeOrmApp {
+        pub user_email_change_vec: Vec<UserEmailChange>,
+    }
+
+    impl UserEmailChangeOrmApp {
+        /// Create a new instance.
+        pub fn new() -> Self {
+            UserEmailChangeOrmApp {
+                user_email_change_vec: Vec::new(),
+            }
+        }
+        /// Create a new instance with the specified list of entities (user_email_change).
+        pub fn create(user_email_change_list: &[UserEmailChange]) -> Self {
+            let mut user_email_change_vec: Vec<UserEmailChange> = Vec::new();
+            for (idx, user_email_change) in user_email_change_list.iter().enumerate() {
+                let id = USER_EMAIL_CHANGE_ID + i32::try_from(idx).unwrap();
+                #[rustfmt::skip]
+                user_email_change_vec.push(Self::new_user_email_change(id, user_email_change.user_id,
+                    &user_email_change.new_email, user_email_change.num_token, user_email_change.final_date));
+            }
+            UserEmailChangeOrmApp { user_email_change_vec }
+        }
+        /// Create a new entity instance.
+        #[rustfmt::skip]
+        pub fn new_user_email_change(
+            id: i32, user_id: i32, new_email: &str, num_token: i32, final_date: DateTime<Utc>,
+        ) -> UserEmailChange {
+            UserEmailChange {
+                id,
+                user_id,
+                new_email: new_email.to_lowercase(),
+                final_date,
+                num_token,
+            }
+        }
+    }
+
+    impl UserEmailChangeOrm for UserEmailChangeOrmApp {
+        /// Get an entity (user_email_change) by ID.
+        fn get_user_email_change_by_id(&self, id: i32) -> Result<Option<UserEmailChange>, String> {
+            let result = self.user_email_change_vec.iter().find(|item| item.id == id).cloned();
+            Ok(result)
+        }
+
+        /// Find for an entity (user_email_change) by new email (where final_date > now).
+        fn find_user_email_change_by_new_email(&self, new_email: &str) -> Result<Option<UserEmailChange>, String> {
+            let new_email2 = new_email.to_lowercase();
+            if new_email2.len() == 0 {
+                return Ok(None);
+            }
+            let now = Utc::now();
+            #[rustfmt::skip]
+            let result = self.user_email_change_vec.iter()
+                .find(|item| item.final_date > now && item.new_email == new_email2)
+                .cloned();
+            Ok(result)
+        }
+
+        /// Add a new entity (user_email_change), the previous request of this user is deleted.
+        fn create_user_email_change(&self, create_user_email_change: CreateUserEmailChange) -> Result<UserEmailChange, String> {
+            let user_id = create_user_email_change.user_id;
+            let new_email = create_user_email_change.new_email.clone();
+            let num_token = create_user_email_change.num_token;
+            let final_date = create_user_email_change.final_date.clone();
+
+            // The new request gets a new ID (the previous request of this user is deleted).
+            let id = USER_EMAIL_CHANGE_ID + i32::try_from(self.user_email_change_vec.len()).unwrap();
+            Ok(Self::new_user_email_change(id, user_id, &new_email, num_token, final_date))
+        }
+
+        /// Delete an entity (user_email_change).
+        fn delete_user_email_change(&self, id: i32) -> Result<usize, String> {
+            let count = self.user_email_change_vec.iter().filter(|item| item.id == id).count();
+            Ok(count)
+        }
+
+        /// Delete all entities (user_email_change) with an inactive "final_date".
+        fn delete_inactive_final_date(&self, duration_in_days: Option<u16>) -> Result<usize, String> {
+            let now = Utc::now();
+            let duration = duration_in_days.unwrap_or(DURATION_IN_DAYS.into());
+            let start_day_time = now - Duration::days(duration.into());
+            let end_day_time = now.clone();
+
+            #[rustfmt::skip]
+            let result = self.user_email_change_vec.iter()
+                .filter(|item| item.final_date > start_day_time && item.final_date < end_day_time)
+                .count();
+            Ok(result)
+        }
+    }
+
+    pub struct UserEmailChangeOrmTest {}
+
+    impl UserEmailChangeOrmTest {
+        /// Create a list with one entity (user_email_change) that expires after the specified number of seconds.
+        pub fn email_changes(user_id: i32, new_email: &str, duration: i64) -> Vec<UserEmailChange> {
+            let final_date = Utc::now() + Duration::seconds(duration);
+            let num_token = USER_EMAIL_CHANGE_NUM_TOKEN;
+            #[rustfmt::skip]
+            let user_email_change = UserEmailChangeOrmApp::new_user_email_change(
+                USER_EMAIL_CHANGE_ID, user_id, new_email, num_token, final_date);
+            UserEmailChangeOrmApp::create(&[user_email_change]).user_email_change_vec
+        }
+        pub fn cfg_email_change_orm(email_change_vec: Vec<UserEmailChange>) -> impl FnOnce(&mut web::ServiceConfig) {
+            move |config: &mut web::ServiceConfig| {
+                let data_user_email_change_orm = web::Data::new(UserEmailChangeOrmApp::create(&email_change_vec));
+                config.app_data(web::Data::clone(&data_user_email_change_orm));
+            }
+        }
+    }
+}
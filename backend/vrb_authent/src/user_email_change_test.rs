@@ -0,0 +1,621 @@
+#[cfg(all(test, feature = "mockdata"))]
+mod tests {
+    use actix_web::{
+        App, body, dev,
+        http::StatusCode,
+        http::header::{CONTENT_TYPE, HeaderValue},
+        test,
+    };
+    use chrono::{Duration, SecondsFormat, Utc};
+    use vrb_common::{
+        api_error::{ApiError, code_to_str},
+        consts, env_var, err,
+    };
+    use vrb_tools::{config_app, send_email::config_smtp, token_coding};
+
+    use crate::{
+        attempt_limiter, config_jwt,
+        user_email_change_controller::{
+            MSG_EMAIL_CHANGE_NOT_FOUND, MSG_USER_NOT_FOUND, confirm_email_change, email_change, email_change_clear_for_expired,
+            tests as EmailChangeCtrlTest,
+        },
+        user_email_change_models::{
+            ConfirmEmailChangeResponseDto, EmailChangeClearForExpiredResponseDto, EmailChangeDto, EmailChangeResponseDto,
+        },
+        user_email_change_orm::tests::{USER_EMAIL_CHANGE_ID, USER_EMAIL_CHANGE_NUM_TOKEN, UserEmailChangeOrmTest},
+        user_models,
+        user_orm::tests::{ADMIN, USER, USER1_ID, USER2_ID, UserOrmTest},
+        user_registr_orm::tests::UserRegistrOrmTest,
+    };
+
+    const TEST_PATH_TEMPLATE: &str = "../templates";
+    const NEW_EMAIL: &str = "oliver_taylor_new@gmail.com";
+    const MSG_ERROR_WAS_EXPECTED: &str = "Service call succeeded, but an error was expected.";
+    const MSG_FAILED_TO_DESER: &str = "Failed to deserialize JSON string";
+    const MSG_FAILED_DESER: &str = "Failed to deserialize response from JSON.";
+
+    fn get_email_change_token(user_email_change_id: i32, duration: i64) -> String {
+        let num_token = USER_EMAIL_CHANGE_NUM_TOKEN;
+        let config_jwt = config_jwt::tests::get_config();
+        let jwt_secret: &[u8] = config_jwt.jwt_secret.as_bytes();
+        token_coding::encode_email_change_token(user_email_change_id, num_token, jwt_secret, duration).unwrap()
+    }
+    fn get_duration() -> i64 {
+        config_app::get_test_config().app_registr_duration.try_into().unwrap()
+    }
+
+    // ** email_change **
+
+    #[actix_web::test]
+    async fn test_email_change_missing_token() {
+        env_var::env_set_var(consts::SMTP_PATH_TEMPLATE, TEST_PATH_TEMPLATE);
+        let data_u = UserOrmTest::users(&[USER]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(email_change)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(EmailChangeCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(EmailChangeCtrlTest::cfg_mailer(config_smtp::get_test_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserEmailChangeOrmTest::cfg_email_change_orm(vec![]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/email_change")
+            .set_json(EmailChangeDto { email: NEW_EMAIL.to_string() })
+            .to_request();
+        let result = test::try_call_service(&app, req).await.err();
+        let err = result.expect(MSG_ERROR_WAS_EXPECTED);
+
+        let actual_status = err.as_response_error().status_code();
+        assert_eq!(actual_status, StatusCode::UNAUTHORIZED); // 401(a)
+
+        let api_err: ApiError = serde_json::from_str(&err.to_string()).expect(MSG_FAILED_TO_DESER);
+        assert_eq!(api_err.code, code_to_str(StatusCode::UNAUTHORIZED));
+        assert_eq!(api_err.message, err::MSG_MISSING_TOKEN);
+    }
+    #[actix_web::test]
+    async fn test_email_change_invalid_dto_email_empty() {
+        env_var::env_set_var(consts::SMTP_PATH_TEMPLATE, TEST_PATH_TEMPLATE);
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(email_change)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(EmailChangeCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(EmailChangeCtrlTest::cfg_mailer(config_smtp::get_test_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserEmailChangeOrmTest::cfg_email_change_orm(vec![]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/email_change")
+            .insert_header(EmailChangeCtrlTest::header_auth(&token1))
+            .set_json(EmailChangeDto { email: "".to_string() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
+
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err_vec: Vec<ApiError> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err_vec.len(), 1);
+        assert_eq!(app_err_vec[0].code, code_to_str(StatusCode::EXPECTATION_FAILED));
+        assert_eq!(app_err_vec[0].message, user_models::MSG_EMAIL_REQUIRED);
+    }
+    #[actix_web::test]
+    async fn test_email_change_email_used_by_user() {
+        env_var::env_set_var(consts::SMTP_PATH_TEMPLATE, TEST_PATH_TEMPLATE);
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER, USER]);
+        let user2_email = data_u.0.get(1).unwrap().email.clone();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(email_change)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(EmailChangeCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(EmailChangeCtrlTest::cfg_mailer(config_smtp::get_test_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserEmailChangeOrmTest::cfg_email_change_orm(vec![]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/email_change")
+            .insert_header(EmailChangeCtrlTest::header_auth(&token1))
+            .set_json(EmailChangeDto { email: user2_email.to_uppercase() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT); // 409
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::CONFLICT));
+        assert_eq!(app_err.message, err::MSG_EMAIL_ALREADY_USE);
+    }
+    #[actix_web::test]
+    async fn test_email_change_email_used_in_registration() {
+        env_var::env_set_var(consts::SMTP_PATH_TEMPLATE, TEST_PATH_TEMPLATE);
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let registrs = UserRegistrOrmTest::registrs(true);
+        let registr_email = registrs.get(0).unwrap().email.clone();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(email_change)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(EmailChangeCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(EmailChangeCtrlTest::cfg_mailer(config_smtp::get_test_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserRegistrOrmTest::cfg_registr_orm(registrs))
+                .configure(UserEmailChangeOrmTest::cfg_email_change_orm(vec![]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/email_change")
+            .insert_header(EmailChangeCtrlTest::header_auth(&token1))
+            .set_json(EmailChangeDto { email: registr_email })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT); // 409
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::CONFLICT));
+        assert_eq!(app_err.message, err::MSG_EMAIL_ALREADY_USE);
+    }
+    #[actix_web::test]
+    async fn test_email_change_email_requested_by_another_user() {
+        env_var::env_set_var(consts::SMTP_PATH_TEMPLATE, TEST_PATH_TEMPLATE);
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER, USER]);
+        let email_changes = UserEmailChangeOrmTest::email_changes(USER2_ID, NEW_EMAIL, get_duration());
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(email_change)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(EmailChangeCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(EmailChangeCtrlTest::cfg_mailer(config_smtp::get_test_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserEmailChangeOrmTest::cfg_email_change_orm(email_changes))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/email_change")
+            .insert_header(EmailChangeCtrlTest::header_auth(&token1))
+            .set_json(EmailChangeDto { email: NEW_EMAIL.to_string() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT); // 409
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::CONFLICT));
+        assert_eq!(app_err.message, err::MSG_EMAIL_ALREADY_USE);
+    }
+    #[actix_web::test]
+    async fn test_email_change_repeated_request() {
+        env_var::env_set_var(consts::SMTP_PATH_TEMPLATE, TEST_PATH_TEMPLATE);
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        // The previous request of this user is replaced with a new one (with a new ID).
+        let email_changes = UserEmailChangeOrmTest::email_changes(USER1_ID, NEW_EMAIL, get_duration());
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(email_change)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(EmailChangeCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(EmailChangeCtrlTest::cfg_mailer(config_smtp::get_test_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserEmailChangeOrmTest::cfg_email_change_orm(email_changes))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/email_change")
+            .insert_header(EmailChangeCtrlTest::header_auth(&token1))
+            .set_json(EmailChangeDto { email: NEW_EMAIL.to_string() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED); // 201
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response_dto: EmailChangeResponseDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_ne!(response_dto.id, USER_EMAIL_CHANGE_ID);
+        assert_eq!(response_dto.new_email, NEW_EMAIL);
+    }
+    #[actix_web::test]
+    async fn test_email_change_if_too_many_attempts() {
+        env_var::env_set_var(consts::SMTP_PATH_TEMPLATE, TEST_PATH_TEMPLATE);
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let attempt_limiter = attempt_limiter::tests::get_attempt_limiter();
+        let max_attempts = attempt_limiter.max_attempts;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(email_change)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter))
+                .configure(EmailChangeCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(EmailChangeCtrlTest::cfg_mailer(config_smtp::get_test_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserEmailChangeOrmTest::cfg_email_change_orm(vec![]))
+        ).await;
+        for idx in 0..max_attempts {
+            #[rustfmt::skip]
+            let req = test::TestRequest::post().uri("/api/email_change")
+                .insert_header(EmailChangeCtrlTest::header_auth(&token1))
+                .set_json(EmailChangeDto { email: format!("new{}_{}", idx, NEW_EMAIL) })
+                .to_request();
+            let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::CREATED); // 201
+        }
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/email_change")
+            .insert_header(EmailChangeCtrlTest::header_auth(&token1))
+            .set_json(EmailChangeDto { email: NEW_EMAIL.to_string() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS); // 429
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::TOO_MANY_REQUESTS));
+        assert_eq!(app_err.message, err::MSG_TOO_MANY_ATTEMPTS);
+        assert!(app_err.params.get(attempt_limiter::PARAM_RETRY_AFTER).is_some());
+    }
+    #[actix_web::test]
+    async fn test_email_change_success() {
+        env_var::env_set_var(consts::SMTP_PATH_TEMPLATE, TEST_PATH_TEMPLATE);
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let user1_email = data_u.0.get(0).unwrap().email.clone();
+        let final_date = Utc::now() + Duration::seconds(get_duration());
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(email_change)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(EmailChangeCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(EmailChangeCtrlTest::cfg_mailer(config_smtp::get_test_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserEmailChangeOrmTest::cfg_email_change_orm(vec![]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/email_change")
+            .insert_header(EmailChangeCtrlTest::header_auth(&token1))
+            .set_json(EmailChangeDto { email: NEW_EMAIL.to_uppercase() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED); // 201
+
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response_dto: EmailChangeResponseDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response_dto.id, USER_EMAIL_CHANGE_ID);
+        assert_eq!(response_dto.new_email, NEW_EMAIL);
+        assert_ne!(response_dto.new_email, user1_email);
+        #[rustfmt::skip]
+        assert_eq!(response_dto.final_date.to_rfc3339_opts(SecondsFormat::Secs, true), final_date.to_rfc3339_opts(SecondsFormat::Secs, true));
+    }
+
+    // ** confirm_email_change **
+
+    #[actix_web::test]
+    async fn test_confirm_email_change_invalid_token() {
+        env_var::env_set_var(consts::SMTP_PATH_TEMPLATE, TEST_PATH_TEMPLATE);
+        let data_u = UserOrmTest::users(&[USER]);
+        let email_changes = UserEmailChangeOrmTest::email_changes(USER1_ID, NEW_EMAIL, get_duration());
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(confirm_email_change)
+                .configure(EmailChangeCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(EmailChangeCtrlTest::cfg_mailer(config_smtp::get_test_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserEmailChangeOrmTest::cfg_email_change_orm(email_changes))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/email_change/{}", "invalid_email_change_token"))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED); // 401
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::UNAUTHORIZED));
+        assert!(app_err.message.starts_with(err::MSG_INVALID_OR_EXPIRED_TOKEN));
+    }
+    #[actix_web::test]
+    async fn test_confirm_email_change_token_has_expired() {
+        env_var::env_set_var(consts::SMTP_PATH_TEMPLATE, TEST_PATH_TEMPLATE);
+        let data_u = UserOrmTest::users(&[USER]);
+        let email_changes = UserEmailChangeOrmTest::email_changes(USER1_ID, NEW_EMAIL, get_duration());
+        let email_change_token = get_email_change_token(USER_EMAIL_CHANGE_ID, -get_duration());
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(confirm_email_change)
+                .configure(EmailChangeCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(EmailChangeCtrlTest::cfg_mailer(config_smtp::get_test_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserEmailChangeOrmTest::cfg_email_change_orm(email_changes))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/email_change/{}", email_change_token))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED); // 401
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::UNAUTHORIZED));
+        assert_eq!(app_err.message, format!("{}; {}", err::MSG_INVALID_OR_EXPIRED_TOKEN, "ExpiredSignature"));
+    }
+    #[actix_web::test]
+    async fn test_confirm_email_change_access_token() {
+        env_var::env_set_var(consts::SMTP_PATH_TEMPLATE, TEST_PATH_TEMPLATE);
+        let data_u = UserOrmTest::users(&[USER]);
+        let email_changes = UserEmailChangeOrmTest::email_changes(USER1_ID, NEW_EMAIL, get_duration());
+        let config_jwt = config_jwt::tests::get_config();
+        let jwt_secret: &[u8] = config_jwt.jwt_secret.as_bytes();
+        // An access token with the same parameters cannot be used to confirm the change.
+        #[rustfmt::skip]
+        let access_token = token_coding::encode_token(
+            USER_EMAIL_CHANGE_ID, USER_EMAIL_CHANGE_NUM_TOKEN, jwt_secret, get_duration()).unwrap();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(confirm_email_change)
+                .configure(EmailChangeCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(EmailChangeCtrlTest::cfg_mailer(config_smtp::get_test_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserEmailChangeOrmTest::cfg_email_change_orm(email_changes))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/email_change/{}", access_token))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED); // 401
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::UNAUTHORIZED));
+        #[rustfmt::skip]
+        assert_eq!(app_err.message, format!("{}; {}", err::MSG_INVALID_OR_EXPIRED_TOKEN, token_coding::CD_UNALLOWABLE_TOKEN_KIND));
+    }
+    #[actix_web::test]
+    async fn test_confirm_email_change_invalid_num_token() {
+        env_var::env_set_var(consts::SMTP_PATH_TEMPLATE, TEST_PATH_TEMPLATE);
+        let data_u = UserOrmTest::users(&[USER]);
+        let email_changes = UserEmailChangeOrmTest::email_changes(USER1_ID, NEW_EMAIL, get_duration());
+        let config_jwt = config_jwt::tests::get_config();
+        let jwt_secret: &[u8] = config_jwt.jwt_secret.as_bytes();
+        // The token of another (previous) request with the same ID.
+        #[rustfmt::skip]
+        let email_change_token = token_coding::encode_email_change_token(
+            USER_EMAIL_CHANGE_ID, USER_EMAIL_CHANGE_NUM_TOKEN + 1, jwt_secret, get_duration()).unwrap();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(confirm_email_change)
+                .configure(EmailChangeCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(EmailChangeCtrlTest::cfg_mailer(config_smtp::get_test_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserEmailChangeOrmTest::cfg_email_change_orm(email_changes))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/email_change/{}", email_change_token))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED); // 401
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::UNAUTHORIZED));
+        #[rustfmt::skip]
+        let msg = format!("user_email_change_id: {}; {}", USER_EMAIL_CHANGE_ID, token_coding::CD_UNALLOWABLE_TOKEN);
+        assert_eq!(app_err.message, format!("{}; {}", err::MSG_INVALID_OR_EXPIRED_TOKEN, msg));
+    }
+    #[actix_web::test]
+    async fn test_confirm_email_change_no_exists_in_email_change() {
+        env_var::env_set_var(consts::SMTP_PATH_TEMPLATE, TEST_PATH_TEMPLATE);
+        let data_u = UserOrmTest::users(&[USER]);
+        let email_changes = UserEmailChangeOrmTest::email_changes(USER1_ID, NEW_EMAIL, get_duration());
+        let email_change_id = USER_EMAIL_CHANGE_ID + 1;
+        let email_change_token = get_email_change_token(email_change_id, get_duration());
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(confirm_email_change)
+                .configure(EmailChangeCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(EmailChangeCtrlTest::cfg_mailer(config_smtp::get_test_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserEmailChangeOrmTest::cfg_email_change_orm(email_changes))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/email_change/{}", email_change_token))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND); // 404
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::NOT_FOUND));
+        #[rustfmt::skip]
+        assert_eq!(app_err.message, format!("{}; user_email_change_id: {}", MSG_EMAIL_CHANGE_NOT_FOUND, email_change_id));
+    }
+    #[actix_web::test]
+    async fn test_confirm_email_change_final_date_has_expired() {
+        env_var::env_set_var(consts::SMTP_PATH_TEMPLATE, TEST_PATH_TEMPLATE);
+        let data_u = UserOrmTest::users(&[USER]);
+        let email_changes = UserEmailChangeOrmTest::email_changes(USER1_ID, NEW_EMAIL, -1);
+        let email_change_token = get_email_change_token(USER_EMAIL_CHANGE_ID, get_duration());
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(confirm_email_change)
+                .configure(EmailChangeCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(EmailChangeCtrlTest::cfg_mailer(config_smtp::get_test_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserEmailChangeOrmTest::cfg_email_change_orm(email_changes))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/email_change/{}", email_change_token))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND); // 404
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::NOT_FOUND));
+        #[rustfmt::skip]
+        assert_eq!(app_err.message, format!("{}; user_email_change_id: {}", MSG_EMAIL_CHANGE_NOT_FOUND, USER_EMAIL_CHANGE_ID));
+    }
+    #[actix_web::test]
+    async fn test_confirm_email_change_no_exists_in_user() {
+        env_var::env_set_var(consts::SMTP_PATH_TEMPLATE, TEST_PATH_TEMPLATE);
+        let data_u = UserOrmTest::users(&[USER]);
+        let user_id = USER1_ID + 10;
+        let email_changes = UserEmailChangeOrmTest::email_changes(user_id, NEW_EMAIL, get_duration());
+        let email_change_token = get_email_change_token(USER_EMAIL_CHANGE_ID, get_duration());
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(confirm_email_change)
+                .configure(EmailChangeCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(EmailChangeCtrlTest::cfg_mailer(config_smtp::get_test_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserEmailChangeOrmTest::cfg_email_change_orm(email_changes))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/email_change/{}", email_change_token))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND); // 404
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::NOT_FOUND));
+        assert_eq!(app_err.message, format!("{}; user_id: {}", MSG_USER_NOT_FOUND, user_id));
+    }
+    #[actix_web::test]
+    async fn test_confirm_email_change_email_used_by_user() {
+        env_var::env_set_var(consts::SMTP_PATH_TEMPLATE, TEST_PATH_TEMPLATE);
+        let data_u = UserOrmTest::users(&[USER, USER]);
+        // While waiting for confirmation, the new email was taken by another user.
+        let user2_email = data_u.0.get(1).unwrap().email.clone();
+        let email_changes = UserEmailChangeOrmTest::email_changes(USER1_ID, &user2_email, get_duration());
+        let email_change_token = get_email_change_token(USER_EMAIL_CHANGE_ID, get_duration());
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(confirm_email_change)
+                .configure(EmailChangeCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(EmailChangeCtrlTest::cfg_mailer(config_smtp::get_test_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserEmailChangeOrmTest::cfg_email_change_orm(email_changes))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/email_change/{}", email_change_token))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT); // 409
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::CONFLICT));
+        assert_eq!(app_err.message, err::MSG_EMAIL_ALREADY_USE);
+    }
+    #[actix_web::test]
+    async fn test_confirm_email_change_success() {
+        env_var::env_set_var(consts::SMTP_PATH_TEMPLATE, TEST_PATH_TEMPLATE);
+        let data_u = UserOrmTest::users(&[USER]);
+        let user1 = data_u.0.get(0).unwrap().clone();
+        let email_changes = UserEmailChangeOrmTest::email_changes(USER1_ID, NEW_EMAIL, get_duration());
+        let email_change_token = get_email_change_token(USER_EMAIL_CHANGE_ID, get_duration());
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(confirm_email_change)
+                .configure(EmailChangeCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(EmailChangeCtrlTest::cfg_mailer(config_smtp::get_test_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserEmailChangeOrmTest::cfg_email_change_orm(email_changes))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/email_change/{}", email_change_token))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response_dto: ConfirmEmailChangeResponseDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response_dto.id, user1.id);
+        assert_eq!(response_dto.nickname, user1.nickname);
+        assert_eq!(response_dto.email, NEW_EMAIL);
+        assert_eq!(response_dto.created_at, user1.created_at);
+        let now_str = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+        assert_eq!(response_dto.updated_at.to_rfc3339_opts(SecondsFormat::Secs, true), now_str);
+    }
+
+    // ** email_change_clear_for_expired **
+
+    #[actix_web::test]
+    async fn test_email_change_clear_for_expired_not_admin() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let email_changes = UserEmailChangeOrmTest::email_changes(USER1_ID, NEW_EMAIL, -get_duration());
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(email_change_clear_for_expired)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserEmailChangeOrmTest::cfg_email_change_orm(email_changes))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri("/api/email_change/clear_for_expired")
+            .insert_header(EmailChangeCtrlTest::header_auth(&token1))
+            .to_request();
+        let result = test::try_call_service(&app, req).await.err();
+        let err = result.expect(MSG_ERROR_WAS_EXPECTED);
+
+        let actual_status = err.as_response_error().status_code();
+        assert_eq!(actual_status, StatusCode::FORBIDDEN); // 403
+
+        let api_err: ApiError = serde_json::from_str(&err.to_string()).expect(MSG_FAILED_TO_DESER);
+        assert_eq!(api_err.code, code_to_str(StatusCode::FORBIDDEN));
+        assert_eq!(api_err.message, err::MSG_ACCESS_DENIED);
+    }
+    #[actix_web::test]
+    async fn test_email_change_clear_for_expired() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN]);
+        let email_changes = UserEmailChangeOrmTest::email_changes(USER1_ID, NEW_EMAIL, -get_duration());
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(email_change_clear_for_expired)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserEmailChangeOrmTest::cfg_email_change_orm(email_changes))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri("/api/email_change/clear_for_expired")
+            .insert_header(EmailChangeCtrlTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response_dto: EmailChangeClearForExpiredResponseDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response_dto.count_inactive_email_change, 1);
+    }
+}
@@ -124,6 +124,7 @@ pub struct User {
     pub role: UserRole,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub suspended_at: Option<DateTime<Utc>>, // Nullable (if specified, the account is suspended)
 }
 
 impl User {
@@ -137,6 +138,7 @@ impl User {
             role,
             created_at: now.clone(),
             updated_at: now.clone(),
+            suspended_at: None,
         }
     }
 }
@@ -185,19 +187,74 @@ impl ModifyUser {
     }
 }
 
-// ** Model: "Session". **
+// ** Section: "Session.device" **
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Queryable, Selectable, Insertable, AsChangeset)]
+pub const DEVICE_MAX: u8 = 255;
+pub const MSG_DEVICE_MAX_LENGTH: &str = "device:max_length";
+pub const USER_AGENT_MAX: usize = 255;
+pub const IP_ADDRESS_MAX: usize = 64;
+
+// MAX=255
+pub fn validate_device(value: &str) -> Result<(), ValidationError> {
+    ValidationChecks::max_length(value, DEVICE_MAX.into(), MSG_DEVICE_MAX_LENGTH)?;
+    Ok(())
+}
+
+// ** Model: "Session". (One entry per device.) **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Queryable, Selectable)]
 #[diesel(table_name = schema::sessions)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct Session {
+    pub id: i32,
     pub user_id: i32,
-    pub num_token: Option<i32>,
+    pub num_token: i32,
+    pub device: Option<String>,     // max_len=255 Nullable
+    pub user_agent: Option<String>, // max_len=255 Nullable
+    pub ip_address: Option<String>, // max_len=64 Nullable
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
 }
 
 impl Session {
-    pub fn new(user_id: i32, num_token: Option<i32>) -> Self {
-        Session { user_id, num_token }
+    pub fn new(id: i32, user_id: i32, num_token: i32) -> Self {
+        let now = Utc::now();
+        Session {
+            id,
+            user_id,
+            num_token,
+            device: None,
+            user_agent: None,
+            ip_address: None,
+            created_at: now.clone(),
+            last_seen_at: now.clone(),
+        }
+    }
+}
+
+// ** Used: UserOrm::create_session() **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Insertable)]
+#[diesel(table_name = schema::sessions)]
+pub struct CreateSession {
+    pub user_id: i32,
+    pub num_token: i32,
+    pub device: Option<String>,     // max_len=255 Nullable
+    pub user_agent: Option<String>, // max_len=255 Nullable
+    pub ip_address: Option<String>, // max_len=64 Nullable
+}
+
+impl CreateSession {
+    pub fn new(user_id: i32, num_token: i32, device: Option<String>, user_agent: Option<String>, ip_address: Option<String>) -> Self {
+        // Values that do not fit into the table columns are truncated.
+        let cut = |value: String, max: usize| value.chars().take(max).collect::<String>();
+        CreateSession {
+            user_id,
+            num_token,
+            device: device.map(|v| cut(v, DEVICE_MAX.into())),
+            user_agent: user_agent.map(|v| cut(v, USER_AGENT_MAX)),
+            ip_address: ip_address.map(|v| cut(v, IP_ADDRESS_MAX)),
+        }
     }
 }
 
@@ -238,6 +295,9 @@ pub struct UserMock {}
 
 #[cfg(any(test, feature = "mockdata"))]
 impl UserMock {
+    pub fn device_max() -> String {
+        (0..(usize::from(DEVICE_MAX) + 1)).map(|_| 'a').collect()
+    }
     pub fn nickname_min() -> String {
         (0..(NICKNAME_MIN - 1)).map(|_| 'a').collect()
     }
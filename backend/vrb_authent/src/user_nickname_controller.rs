@@ -0,0 +1,300 @@
+use std::{ops::Deref, time::Instant as tm};
+
+use actix_web::{HttpResponse, get, http::StatusCode, put, web};
+use chrono::{Duration, Utc};
+use log::{Level::Info, error, info, log_enabled};
+use utoipa;
+use vrb_common::{
+    api_error::{ApiError, code_to_str},
+    err, parser,
+    validators::{Validator, msg_validation},
+};
+use vrb_tools::config_app;
+
+#[cfg(not(all(test, feature = "mockdata")))]
+use crate::user_nickname_orm::impls::UserNicknameOrmApp;
+#[cfg(all(test, feature = "mockdata"))]
+use crate::user_nickname_orm::tests::UserNicknameOrmApp;
+#[cfg(not(all(test, feature = "mockdata")))]
+use crate::user_orm::impls::UserOrmApp;
+#[cfg(all(test, feature = "mockdata"))]
+use crate::user_orm::tests::UserOrmApp;
+#[cfg(not(all(test, feature = "mockdata")))]
+use crate::user_registr_orm::impls::UserRegistrOrmApp;
+#[cfg(all(test, feature = "mockdata"))]
+use crate::user_registr_orm::tests::UserRegistrOrmApp;
+use crate::{
+    attempt_limiter,
+    authentication::{Authenticated, RequireAuth},
+    user_nickname_models::{CreateUserNicknameHistory, NicknameDto, NicknameHistoryDto},
+    user_nickname_orm::UserNicknameOrm,
+    user_orm::UserOrm,
+    user_registr_orm::UserRegistrOrm,
+};
+
+// 429 Too Many Requests - The nickname can be changed again only after the cooldown period.
+pub const MSG_NICKNAME_CHANGE_TOO_EARLY: &str = "nickname_change_too_early";
+
+pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
+    |config: &mut web::ServiceConfig| {
+        config
+            // PUT /api/nickname
+            .service(nickname_change)
+            // GET /api/nickname_history/{id}
+            .service(get_nickname_history);
+    }
+}
+
+/// nickname_change
+///
+/// Change the nickname of the current user.
+///
+/// The new nickname must not be used by other users, by pending registrations,
+/// and must not be reserved (the old nickname of another user remains reserved for "APP_NICKNAME_RESERVE" seconds).
+/// The user can take back their own reserved nickname.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X PUT http://localhost:8080/api/nickname \
+/// -d '{"nickname": "user_new"}' \
+/// -H 'Content-Type: application/json'
+/// ```
+///
+/// If the cooldown is enabled ("APP_NICKNAME_COOLDOWN" > 0) and the previous change was made recently,
+/// then error 429 is returned with the "retryAfter" parameter (the number of seconds until the change is allowed).
+///
+/// Return the entry about the nickname change (`NicknameHistoryDto`) with status 200.
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "Information about the nickname change.", body = NicknameHistoryDto,
+            example = json!(NicknameHistoryDto { id: 31, user_id: 120, old_nickname: "james_miller".to_owned()
+                , new_nickname: "james_smith".to_owned(), changed_at: Utc::now(), reserved_until: Utc::now() + Duration::days(30) })
+        ),
+        (status = 401, description = "An authorization token is required.", body = ApiError,
+            example = json!(ApiError::new(401, err::MSG_MISSING_TOKEN))),
+        (status = 404, description = "User not found.", body = ApiError,
+            example = json!(ApiError::create(404, err::MSG_USER_NOT_FOUND, "user_id: 123"))),
+        (status = 409, description = "Error: nickname is already in use (or reserved).", body = ApiError,
+            example = json!(ApiError::new(409, err::MSG_NICKNAME_ALREADY_USE))),
+        (status = 417, body = [ApiError],
+            description = "Validation error. `curl -i -X PUT http://localhost:8080/api/nickname -d '{\"nickname\": \"us\" }'`",
+            example = json!(ApiError::validations((NicknameDto { nickname: "us".to_string() }).validate().err().unwrap()))),
+        (status = 429, description = "The nickname was changed recently, the next change is not yet allowed.", body = ApiError,
+            example = json!(ApiError::new(429, MSG_NICKNAME_CHANGE_TOO_EARLY).add_param(attempt_limiter::PARAM_RETRY_AFTER.into(), &3600))),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[put("/api/nickname", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn nickname_change(
+    authenticated: Authenticated,
+    config_app: web::Data<config_app::ConfigApp>,
+    user_orm: web::Data<UserOrmApp>,
+    user_registr_orm: web::Data<UserRegistrOrmApp>,
+    user_nickname_orm: web::Data<UserNicknameOrmApp>,
+    json_body: web::Json<NicknameDto>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+    // Checking the validity of the data model.
+    let validation_res = json_body.validate();
+    if let Err(validation_errors) = validation_res {
+        error!("{}-{}", code_to_str(StatusCode::EXPECTATION_FAILED), msg_validation(&validation_errors)); // 417
+        return Ok(ApiError::to_response(&ApiError::validations(validation_errors)));
+    }
+    let mut nickname_dto: NicknameDto = json_body.into_inner();
+    nickname_dto.nickname = nickname_dto.nickname.to_lowercase();
+    let new_nickname = nickname_dto.nickname.clone();
+
+    // Get current user details.
+    let user = authenticated.deref().clone();
+    let user_id = user.id;
+
+    let new_nickname2 = new_nickname.clone();
+    let user_nickname_orm2 = user_nickname_orm.clone();
+    let (opt_last_change, is_nickname_used) = web::block(move || {
+        // Get the date of the last nickname change of the user.
+        let history = user_nickname_orm2.get_nickname_history_by_user_id(user_id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        })?;
+        let opt_last_change = history.first().map(|item| item.changed_at);
+        // Search for "nickname" in the "users" table.
+        let opt_user = user_orm.find_user_by_nickname_or_email(Some(&new_nickname2), None, false).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        })?;
+        if opt_user.is_some() {
+            return Ok((opt_last_change, true));
+        }
+        // Search for "nickname" in the "user_registration" table.
+        let opt_user_registr = user_registr_orm
+            .find_user_registr_by_nickname_or_email(Some(&new_nickname2), None)
+            .map_err(|e| {
+                error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+                ApiError::create(507, err::MSG_DATABASE, &e) // 507
+            })?;
+        if opt_user_registr.is_some() {
+            return Ok((opt_last_change, true));
+        }
+        // Search for "nickname" among the reserved nicknames of other users in the "user_nickname_history" table.
+        let opt_reserved = user_nickname_orm2.find_reserved_nickname(&new_nickname2).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        })?;
+        let is_reserved = opt_reserved.map(|item| item.user_id != user_id).unwrap_or(false);
+        Ok::<(_, bool), ApiError>((opt_last_change, is_reserved))
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })??;
+
+    let now = Utc::now();
+    // If the cooldown is enabled, check the time since the last nickname change.
+    let app_nickname_cooldown = config_app.app_nickname_cooldown;
+    if let Some(last_change) = opt_last_change.filter(|_| app_nickname_cooldown > 0) {
+        let retry_after = (last_change + Duration::seconds(app_nickname_cooldown) - now).num_seconds();
+        if retry_after > 0 {
+            let msg = format!("{}: {}", attempt_limiter::PARAM_RETRY_AFTER, retry_after);
+            error!("{}-{}; {}", code_to_str(StatusCode::TOO_MANY_REQUESTS), MSG_NICKNAME_CHANGE_TOO_EARLY, &msg);
+            let mut api_error = ApiError::new(429, MSG_NICKNAME_CHANGE_TOO_EARLY); // 429
+            api_error.add_param(attempt_limiter::PARAM_RETRY_AFTER.into(), &retry_after);
+            return Err(api_error);
+        }
+    }
+
+    // Since the specified "nickname" is not unique, return an error.
+    if is_nickname_used {
+        error!("{}-{}", code_to_str(StatusCode::CONFLICT), err::MSG_NICKNAME_ALREADY_USE);
+        return Err(ApiError::new(409, err::MSG_NICKNAME_ALREADY_USE)); // 409
+    }
+
+    // The old nickname remains reserved for the user for the specified period (in seconds).
+    let reserved_until = now + Duration::seconds(config_app.app_nickname_reserve);
+    let create_history = CreateUserNicknameHistory {
+        user_id,
+        old_nickname: user.nickname.clone(),
+        new_nickname,
+        reserved_until,
+    };
+    // Change the nickname and add an entry to the history.
+    let opt_user_nickname_history = web::block(move || {
+        let opt_user_nickname_history = user_nickname_orm.modify_nickname(create_history).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        opt_user_nickname_history
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })??;
+
+    // If the user (with the old nickname) no longer exists, then exit with code 404.
+    let user_nickname_history = opt_user_nickname_history.ok_or_else(|| {
+        let msg = format!("user_id: {}", user_id);
+        error!("{}-{}; {}", code_to_str(StatusCode::NOT_FOUND), err::MSG_USER_NOT_FOUND, &msg);
+        ApiError::create(404, err::MSG_USER_NOT_FOUND, &msg) // 404
+    })?;
+
+    let response_dto = NicknameHistoryDto::from(user_nickname_history);
+    if let Some(timer) = timer {
+        info!("nickname_change() time: {}", format!("{:.2?}", timer.elapsed()));
+    }
+    Ok(HttpResponse::Ok().json(response_dto)) // 200
+}
+
+/// get_nickname_history
+///
+/// Get the history of nickname changes of the user with the specified ID (the most recent changes first).
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X GET http://localhost:8080/api/nickname_history/1
+/// ```
+///
+/// Returns a list of nickname changes (`Vec<NicknameHistoryDto>`) with status 200.
+///
+/// The "admin" role is required.
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "List of nickname changes of the user.", body = [NicknameHistoryDto],
+            example = json!([NicknameHistoryDto { id: 31, user_id: 120, old_nickname: "james_miller".to_owned()
+                , new_nickname: "james_smith".to_owned(), changed_at: Utc::now(), reserved_until: Utc::now() + Duration::days(30) }])
+        ),
+        (status = 401, description = "An authorization token is required.", body = ApiError,
+            example = json!(ApiError::new(401, err::MSG_MISSING_TOKEN))),
+        (status = 403, description = "Access denied: insufficient user rights.", body = ApiError,
+            example = json!(ApiError::new(403, err::MSG_ACCESS_DENIED))),
+        (status = 416, description = "Error parsing input parameter. `curl -i -X GET http://localhost:8080/api/nickname_history/2a`",
+            body = ApiError, example = json!(ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED
+                , "`id` - invalid digit found in string (2a)"))),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    params(("id", description = "Unique user ID.")),
+    security(("bearer_auth" = [])),
+)]
+#[rustfmt::skip]
+#[get("/api/nickname_history/{id}", wrap = "RequireAuth::allowed_roles(RequireAuth::admin_role())")]
+pub async fn get_nickname_history(
+    user_nickname_orm: web::Data<UserNicknameOrmApp>,
+    request: actix_web::HttpRequest,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+    let id_str = request.match_info().query("id").to_string();
+    let user_id = parser::parse_i32(&id_str).map_err(|e| {
+        let msg = &format!("`{}` - {}", "id", &e);
+        error!("{}-{}; {}", code_to_str(StatusCode::RANGE_NOT_SATISFIABLE), err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg);
+        ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg) // 416
+    })?;
+
+    let history = web::block(move || {
+        let history = user_nickname_orm.get_nickname_history_by_user_id(user_id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        history
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })??;
+
+    let response_dto: Vec<NicknameHistoryDto> = history.into_iter().map(NicknameHistoryDto::from).collect();
+    if let Some(timer) = timer {
+        info!("get_nickname_history() time: {}", format!("{:.2?}", timer.elapsed()));
+    }
+    Ok(HttpResponse::Ok().json(response_dto)) // 200
+}
+
+#[cfg(all(test, feature = "mockdata"))]
+pub mod tests {
+
+    use actix_web::{http, web};
+    use vrb_tools::{config_app, token_data::BEARER};
+
+    pub fn header_auth(token: &str) -> (http::header::HeaderName, http::header::HeaderValue) {
+        let header_value = http::header::HeaderValue::from_str(&format!("{}{}", BEARER, token)).unwrap();
+        (http::header::AUTHORIZATION, header_value)
+    }
+
+    pub fn cfg_config_app(config_app: config_app::ConfigApp) -> impl FnOnce(&mut web::ServiceConfig) {
+        move |config: &mut web::ServiceConfig| {
+            let data_config_app = web::Data::new(config_app);
+            config.app_data(web::Data::clone(&data_config_app));
+        }
+    }
+}
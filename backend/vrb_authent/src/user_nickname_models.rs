@@ -0,0 +1,78 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use vrb_common::validators::{ValidationError, Validator};
+use vrb_dbase::schema;
+
+use crate::user_models;
+
+// ** Section: "UserNicknameHistory" **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Queryable, Selectable)]
+#[diesel(table_name = schema::user_nickname_history)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UserNicknameHistory {
+    pub id: i32,
+    pub user_id: i32,
+    pub old_nickname: String,
+    pub new_nickname: String,
+    pub changed_at: DateTime<Utc>,
+    pub reserved_until: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name = schema::user_nickname_history)]
+pub struct CreateUserNicknameHistory {
+    pub user_id: i32,
+    pub old_nickname: String,
+    pub new_nickname: String,
+    pub reserved_until: DateTime<Utc>,
+}
+
+// ** Used: in "user_nickname_controller::nickname_change(). **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NicknameDto {
+    // New nickname.
+    pub nickname: String,
+}
+
+impl Validator for NicknameDto {
+    // Check the model against the required conditions.
+    fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors: Vec<Option<ValidationError>> = vec![];
+
+        errors.push(user_models::validate_nickname(&self.nickname).err());
+
+        self.filter_errors(errors)
+    }
+}
+
+// ** Used: in "user_nickname_controller::nickname_change(), get_nickname_history(). **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NicknameHistoryDto {
+    pub id: i32,
+    pub user_id: i32,
+    pub old_nickname: String,
+    pub new_nickname: String,
+    pub changed_at: DateTime<Utc>,
+    // Date and time until which the old nickname is reserved for the user.
+    pub reserved_until: DateTime<Utc>,
+}
+
+impl From<UserNicknameHistory> for NicknameHistoryDto {
+    fn from(user_nickname_history: UserNicknameHistory) -> Self {
+        NicknameHistoryDto {
+            id: user_nickname_history.id,
+            user_id: user_nickname_history.user_id,
+            old_nickname: user_nickname_history.old_nickname,
+            new_nickname: user_nickname_history.new_nickname,
+            changed_at: user_nickname_history.changed_at,
+            reserved_until: user_nickname_history.reserved_until,
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use vrb_dbase::dbase::DbPool;
+
+use crate::user_nickname_models::{CreateUserNicknameHistory, UserNicknameHistory};
+
+pub trait UserNicknameOrm {
+    /// Get a list of entities (user_nickname_history) of the user (the most recent changes first).
+    fn get_nickname_history_by_user_id(&self, user_id: i32) -> Result<Vec<UserNicknameHistory>, String>;
+    /// Find for an entity (user_nickname_history) in which the old nickname is still reserved (where reserved_until > now).
+    fn find_reserved_nickname(&self, nickname: &str) -> Result<Option<UserNicknameHistory>, String>;
+    /// Change the user's nickname (only if it still has the old value) and add an entry (user_nickname_history).
+    fn modify_nickname(&self, create_history: CreateUserNicknameHistory) -> Result<Option<UserNicknameHistory>, String>;
+}
+
+#[cfg(not(all(test, feature = "mockdata")))]
+pub fn get_user_nickname_orm_app(pool: DbPool) -> impls::UserNicknameOrmApp {
+    impls::UserNicknameOrmApp::new(pool)
+}
+#[cfg(all(test, feature = "mockdata"))]
+pub fn get_user_nickname_orm_app(_: DbPool) -> tests::UserNicknameOrmApp {
+    tests::UserNicknameOrmApp::new()
+}
+
+#[cfg(not(all(test, feature = "mockdata")))]
+pub mod impls {
+    use std::time::Instant as tm;
+
+    use chrono::Utc;
+    use diesel::{self, prelude::*};
+    use log::{Level::Info, info, log_enabled};
+    use vrb_dbase::{
+        dbase,
+        schema::{self, user_nickname_history::dsl},
+    };
+
+    use super::*;
+
+    pub const CONN_POOL: &str = "ConnectionPool";
+
+    #[derive(Debug, Clone)]
+    pub struct UserNicknameOrmApp {
+        pub pool: dbase::DbPool,
+    }
+
+    impl UserNicknameOrmApp {
+        pub fn new(pool: dbase::DbPool) -> Self {
+            UserNicknameOrmApp { pool }
+        }
+        pub fn get_conn(&self) -> Result<dbase::DbPooledConnection, String> {
+            (&self.pool).get().map_err(|e| format!("{CONN_POOL}: {}", e.to_string()))
+        }
+    }
+
+    impl UserNicknameOrm for UserNicknameOrmApp {
+        /// Get a list of entities (user_nickname_history) of the user (the most recent changes first).
+        fn get_nickname_history_by_user_id(&self, user_id: i32) -> Result<Vec<UserNicknameHistory>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+            // Run query using Diesel to find all entries of the user.
+            let result: Vec<UserNicknameHistory> = schema::user_nickname_history::table
+                .filter(dsl::user_id.eq(user_id))
+                .order_by((dsl::changed_at.desc(), dsl::id.desc()))
+                .select(UserNicknameHistory::as_select())
+                .load(&mut conn)
+                .map_err(|e| format!("get_nickname_history_by_user_id: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                #[rustfmt::skip]
+                info!("get_nickname_history_by_user_id() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(result)
+        }
+
+        /// Find for an entity (user_nickname_history) in which the old nickname is still reserved (where reserved_until > now).
+        fn find_reserved_nickname(&self, nickname: &str) -> Result<Option<UserNicknameHistory>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            let nickname2 = nickname.to_lowercase();
+            if nickname2.len() == 0 {
+                return Ok(None);
+            }
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+            let now = Utc::now();
+            // Run query using Diesel to find the entry by old_nickname and return it (where reserved_until > now).
+            let result = schema::user_nickname_history::table
+                .filter(dsl::old_nickname.eq(nickname2).and(dsl::reserved_until.gt(now)))
+                .order_by(dsl::reserved_until.desc())
+                .first::<UserNicknameHistory>(&mut conn)
+                .optional()
+                .map_err(|e| format!("find_reserved_nickname: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("find_reserved_nickname() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(result)
+        }
+
+        /// Change the user's nickname (only if it still has the old value) and add an entry (user_nickname_history).
+        fn modify_nickname(&self, create_history: CreateUserNicknameHistory) -> Result<Option<UserNicknameHistory>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            let mut create_history2 = create_history.clone();
+            create_history2.old_nickname = create_history2.old_nickname.to_lowercase();
+            create_history2.new_nickname = create_history2.new_nickname.to_lowercase();
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+            let result = conn
+                .transaction::<_, diesel::result::Error, _>(|conn| {
+                    // Run query using Diesel to change the nickname of the user (if it has not been changed).
+                    let count = diesel::update(
+                        schema::users::table
+                            .filter(schema::users::dsl::id.eq(create_history2.user_id))
+                            .filter(schema::users::dsl::nickname.eq(&create_history2.old_nickname)),
+                    )
+                    .set(schema::users::dsl::nickname.eq(&create_history2.new_nickname))
+                    .execute(conn)?;
+                    if count == 0 {
+                        return Ok(None);
+                    }
+                    // Run query using Diesel to add a new entry (user_nickname_history).
+                    diesel::insert_into(schema::user_nickname_history::table)
+                        .values(create_history2)
+                        .returning(UserNicknameHistory::as_returning())
+                        .get_result(conn)
+                        .map(Some)
+                })
+                .map_err(|e| format!("modify_nickname: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("modify_nickname() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(result)
+        }
+    }
+}
+
+#[cfg(any(test, feature = "mockdata"))]
+pub mod tests {
+    use actix_web::web;
+    use chrono::{DateTime, Duration, Utc};
+
+    use crate::user_nickname_models::{CreateUserNicknameHistory, UserNicknameHistory};
+
+    use super::UserNicknameOrm;
+
+    pub const USER_NICKNAME_HISTORY_ID: i32 = 1500;
+
+    #[derive(Debug, Clone)]
+    pub struct UserNicknameOrmApp {
+        pub nickname_history_vec: Vec<UserNicknameHistory>,
+    }
+
+    impl UserNicknameOrmApp {
+        /// Create a new instance.
+        pub fn new() -> Self {
+            UserNicknameOrmApp {
+                nickname_history_vec: Vec::new(),
+            }
+        }
+        /// Create a new instance with the specified list of entities (user_nickname_history).
+        pub fn create(nickname_history_list: &[UserNicknameHistory]) -> Self {
+            let mut nickname_history_vec: Vec<UserNicknameHistory> = Vec::new();
+            for (idx, item) in nickname_history_list.iter().enumerate() {
+                let id = USER_NICKNAME_HISTORY_ID + i32::try_from(idx).unwrap();
+                #[rustfmt::skip]
+                nickname_history_vec.push(Self::new_nickname_history(
+                    id, item.user_id, &item.old_nickname, &item.new_nickname, item.changed_at, item.reserved_until));
+            }
+            UserNicknameOrmApp { nickname_history_vec }
+        }
+        /// Create a new entity instance.
+        #[rustfmt::skip]
+        pub fn new_nickname_history(
+            id: i32, user_id: i32, old_nickname: &str, new_nickname: &str, changed_at: DateTime<Utc>, reserved_until: DateTime<Utc>,
+        ) -> UserNicknameHistory {
+            UserNicknameHistory {
+                id,
+                user_id,
+                old_nickname: old_nickname.to_lowercase(),
+                new_nickname: new_nickname.to_lowercase(),
+                changed_at,
+                reserved_until,
+            }
+        }
+    }
+
+    impl UserNicknameOrm for UserNicknameOrmApp {
+        /// Get a list of entities (user_nickname_history) of the user (the most recent changes first).
+        fn get_nickname_history_by_user_id(&self, user_id: i32) -> Result<Vec<UserNicknameHistory>, String> {
+            #[rustfmt::skip]
+            let mut result: Vec<UserNicknameHistory> = self.nickname_history_vec.iter()
+                .filter(|item| item.user_id == user_id)
+                .cloned()
+                .collect();
+            result.sort_by(|a, b| b.changed_at.cmp(&a.changed_at).then(b.id.cmp(&a.id)));
+            Ok(result)
+        }
+
+        /// Find for an entity (user_nickname_history) in which the old nickname is still reserved (where reserved_until > now).
+        fn find_reserved_nickname(&self, nickname: &str) -> Result<Option<UserNicknameHistory>, String> {
+            let nickname2 = nickname.to_lowercase();
+            if nickname2.len() == 0 {
+                return Ok(None);
+            }
+            let now = Utc::now();
+            #[rustfmt::skip]
+            let result = self.nickname_history_vec.iter()
+                .find(|item| item.reserved_until > now && item.old_nickname == nickname2)
+                .cloned();
+            Ok(result)
+        }
+
+        /// Change the user's nickname (only if it still has the old value) and add an entry (user_nickname_history).
+        fn modify_nickname(&self, create_history: CreateUserNicknameHistory) -> Result<Option<UserNicknameHistory>, String> {
+            let id = USER_NICKNAME_HISTORY_ID + i32::try_from(self.nickname_history_vec.len()).unwrap();
+            #[rustfmt::skip]
+            let result = Self::new_nickname_history(id, create_history.user_id, &create_history.old_nickname,
+                &create_history.new_nickname, Utc::now(), create_history.reserved_until);
+            Ok(Some(result))
+        }
+    }
+
+    pub struct UserNicknameOrmTest {}
+
+    impl UserNicknameOrmTest {
+        /// Create an entity (user_nickname_history) that was changed the specified number of seconds ago
+        /// and whose old nickname is reserved for the specified number of seconds from now.
+        pub fn nickname_history(user_id: i32, old_nickname: &str, new_nickname: &str, ago: i64, reserve: i64) -> UserNicknameHistory {
+            let now = Utc::now();
+            let changed_at = now - Duration::seconds(ago);
+            let reserved_until = now + Duration::seconds(reserve);
+            UserNicknameOrmApp::new_nickname_history(
+                USER_NICKNAME_HISTORY_ID, user_id, old_nickname, new_nickname, changed_at, reserved_until,
+            )
+        }
+        pub fn cfg_nickname_orm(nickname_history_vec: Vec<UserNicknameHistory>) -> impl FnOnce(&mut web::ServiceConfig) {
+            move |config: &mut web::ServiceConfig| {
+                let data_user_nickname_orm = web::Data::new(UserNicknameOrmApp::create(&nickname_history_vec));
+                config.app_data(web::Data::clone(&data_user_nickname_orm));
+            }
+        }
+    }
+}
@@ -0,0 +1,435 @@
+#[cfg(all(test, feature = "mockdata"))]
+mod tests {
+    use actix_web::{
+        App, body, dev,
+        http::StatusCode,
+        http::header::{CONTENT_TYPE, HeaderValue},
+        test,
+    };
+    use chrono::{Duration, SecondsFormat, Utc};
+    use vrb_common::{
+        api_error::{ApiError, code_to_str},
+        err,
+    };
+    use vrb_tools::config_app;
+
+    use crate::{
+        attempt_limiter, config_jwt, user_models,
+        user_nickname_controller::{MSG_NICKNAME_CHANGE_TOO_EARLY, get_nickname_history, nickname_change, tests as NicknameCtrlTest},
+        user_nickname_models::{NicknameDto, NicknameHistoryDto},
+        user_nickname_orm::tests::{USER_NICKNAME_HISTORY_ID, UserNicknameOrmTest},
+        user_orm::tests::{ADMIN, USER, USER1_ID, USER2_ID, UserOrmTest},
+        user_registr_orm::tests::UserRegistrOrmTest,
+    };
+
+    const NEW_NICKNAME: &str = "oliver_smith";
+    const MSG_ERROR_WAS_EXPECTED: &str = "Service call succeeded, but an error was expected.";
+    const MSG_FAILED_TO_DESER: &str = "Failed to deserialize JSON string";
+    const MSG_FAILED_DESER: &str = "Failed to deserialize response from JSON.";
+
+    fn get_cooldown() -> i64 {
+        config_app::get_test_config().app_nickname_cooldown
+    }
+
+    // ** nickname_change **
+
+    #[actix_web::test]
+    async fn test_nickname_change_missing_token() {
+        let data_u = UserOrmTest::users(&[USER]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(nickname_change)
+                .configure(NicknameCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri("/api/nickname")
+            .set_json(NicknameDto { nickname: NEW_NICKNAME.to_string() })
+            .to_request();
+        let result = test::try_call_service(&app, req).await.err();
+        let err = result.expect(MSG_ERROR_WAS_EXPECTED);
+
+        let actual_status = err.as_response_error().status_code();
+        assert_eq!(actual_status, StatusCode::UNAUTHORIZED); // 401(a)
+
+        let api_err: ApiError = serde_json::from_str(&err.to_string()).expect(MSG_FAILED_TO_DESER);
+        assert_eq!(api_err.code, code_to_str(StatusCode::UNAUTHORIZED));
+        assert_eq!(api_err.message, err::MSG_MISSING_TOKEN);
+    }
+    #[actix_web::test]
+    async fn test_nickname_change_invalid_dto_nickname_empty() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(nickname_change)
+                .configure(NicknameCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri("/api/nickname")
+            .insert_header(NicknameCtrlTest::header_auth(&token1))
+            .set_json(NicknameDto { nickname: "".to_string() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
+
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err_vec: Vec<ApiError> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err_vec.len(), 1);
+        assert_eq!(app_err_vec[0].code, code_to_str(StatusCode::EXPECTATION_FAILED));
+        assert_eq!(app_err_vec[0].message, user_models::MSG_NICKNAME_REQUIRED);
+    }
+    #[actix_web::test]
+    async fn test_nickname_change_nickname_used_by_user() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER, USER]);
+        let user2_nickname = data_u.0.get(1).unwrap().nickname.clone();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(nickname_change)
+                .configure(NicknameCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri("/api/nickname")
+            .insert_header(NicknameCtrlTest::header_auth(&token1))
+            .set_json(NicknameDto { nickname: user2_nickname.to_uppercase() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT); // 409
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::CONFLICT));
+        assert_eq!(app_err.message, err::MSG_NICKNAME_ALREADY_USE);
+    }
+    #[actix_web::test]
+    async fn test_nickname_change_nickname_used_in_registration() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let registrs = UserRegistrOrmTest::registrs(true);
+        let registr_nickname = registrs.get(0).unwrap().nickname.clone();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(nickname_change)
+                .configure(NicknameCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserRegistrOrmTest::cfg_registr_orm(registrs))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri("/api/nickname")
+            .insert_header(NicknameCtrlTest::header_auth(&token1))
+            .set_json(NicknameDto { nickname: registr_nickname })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT); // 409
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::CONFLICT));
+        assert_eq!(app_err.message, err::MSG_NICKNAME_ALREADY_USE);
+    }
+    #[actix_web::test]
+    async fn test_nickname_change_nickname_reserved_by_another_user() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER, USER]);
+        let user2_nickname = data_u.0.get(1).unwrap().nickname.clone();
+        let history = UserNicknameOrmTest::nickname_history(USER2_ID, NEW_NICKNAME, &user2_nickname, get_cooldown() * 2, 600);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(nickname_change)
+                .configure(NicknameCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![history]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri("/api/nickname")
+            .insert_header(NicknameCtrlTest::header_auth(&token1))
+            .set_json(NicknameDto { nickname: NEW_NICKNAME.to_string() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT); // 409
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::CONFLICT));
+        assert_eq!(app_err.message, err::MSG_NICKNAME_ALREADY_USE);
+    }
+    #[actix_web::test]
+    async fn test_nickname_change_nickname_reservation_has_expired() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER, USER]);
+        let user2_nickname = data_u.0.get(1).unwrap().nickname.clone();
+        let history = UserNicknameOrmTest::nickname_history(USER2_ID, NEW_NICKNAME, &user2_nickname, get_cooldown() * 2, -1);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(nickname_change)
+                .configure(NicknameCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![history]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri("/api/nickname")
+            .insert_header(NicknameCtrlTest::header_auth(&token1))
+            .set_json(NicknameDto { nickname: NEW_NICKNAME.to_string() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response_dto: NicknameHistoryDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response_dto.user_id, USER1_ID);
+        assert_eq!(response_dto.new_nickname, NEW_NICKNAME);
+    }
+    #[actix_web::test]
+    async fn test_nickname_change_own_reserved_nickname() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let user1_nickname = data_u.0.get(0).unwrap().nickname.clone();
+        // The user takes back their own old nickname, which is still reserved.
+        let history = UserNicknameOrmTest::nickname_history(USER1_ID, NEW_NICKNAME, &user1_nickname, get_cooldown() * 2, 600);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(nickname_change)
+                .configure(NicknameCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![history]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri("/api/nickname")
+            .insert_header(NicknameCtrlTest::header_auth(&token1))
+            .set_json(NicknameDto { nickname: NEW_NICKNAME.to_string() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response_dto: NicknameHistoryDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response_dto.user_id, USER1_ID);
+        assert_eq!(response_dto.old_nickname, user1_nickname);
+        assert_eq!(response_dto.new_nickname, NEW_NICKNAME);
+    }
+    #[actix_web::test]
+    async fn test_nickname_change_cooldown_has_not_expired() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let user1_nickname = data_u.0.get(0).unwrap().nickname.clone();
+        // The previous change was made half the cooldown ago.
+        let ago = get_cooldown() / 2;
+        let history = UserNicknameOrmTest::nickname_history(USER1_ID, "oliver_prev", &user1_nickname, ago, 600);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(nickname_change)
+                .configure(NicknameCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![history]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri("/api/nickname")
+            .insert_header(NicknameCtrlTest::header_auth(&token1))
+            .set_json(NicknameDto { nickname: NEW_NICKNAME.to_string() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS); // 429
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::TOO_MANY_REQUESTS));
+        assert_eq!(app_err.message, MSG_NICKNAME_CHANGE_TOO_EARLY);
+        let retry_after = app_err
+            .params
+            .get(attempt_limiter::PARAM_RETRY_AFTER)
+            .and_then(|v| v.as_i64())
+            .unwrap_or(0);
+        assert!(retry_after > 0 && retry_after <= get_cooldown() - ago);
+    }
+    #[actix_web::test]
+    async fn test_nickname_change_cooldown_disabled() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let user1_nickname = data_u.0.get(0).unwrap().nickname.clone();
+        let history = UserNicknameOrmTest::nickname_history(USER1_ID, "oliver_prev", &user1_nickname, 1, 600);
+        let mut config_app = config_app::get_test_config();
+        config_app.app_nickname_cooldown = 0;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(nickname_change)
+                .configure(NicknameCtrlTest::cfg_config_app(config_app))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![history]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri("/api/nickname")
+            .insert_header(NicknameCtrlTest::header_auth(&token1))
+            .set_json(NicknameDto { nickname: NEW_NICKNAME.to_string() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+    }
+    #[actix_web::test]
+    async fn test_nickname_change_success() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let user1_nickname = data_u.0.get(0).unwrap().nickname.clone();
+        let config_app = config_app::get_test_config();
+        let app_nickname_reserve = config_app.app_nickname_reserve;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(nickname_change)
+                .configure(NicknameCtrlTest::cfg_config_app(config_app))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri("/api/nickname")
+            .insert_header(NicknameCtrlTest::header_auth(&token1))
+            .set_json(NicknameDto { nickname: NEW_NICKNAME.to_uppercase() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response_dto: NicknameHistoryDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response_dto.id, USER_NICKNAME_HISTORY_ID);
+        assert_eq!(response_dto.user_id, USER1_ID);
+        assert_eq!(response_dto.old_nickname, user1_nickname);
+        assert_eq!(response_dto.new_nickname, NEW_NICKNAME);
+        let now = Utc::now();
+        let reserved_until_str = (now + Duration::seconds(app_nickname_reserve)).to_rfc3339_opts(SecondsFormat::Secs, true);
+        assert_eq!(response_dto.reserved_until.to_rfc3339_opts(SecondsFormat::Secs, true), reserved_until_str);
+        let now_str = now.to_rfc3339_opts(SecondsFormat::Secs, true);
+        assert_eq!(response_dto.changed_at.to_rfc3339_opts(SecondsFormat::Secs, true), now_str);
+    }
+
+    // ** get_nickname_history **
+
+    #[actix_web::test]
+    async fn test_get_nickname_history_not_admin() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER, USER]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_nickname_history)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/nickname_history/{}", USER2_ID))
+            .insert_header(NicknameCtrlTest::header_auth(&token1))
+            .to_request();
+        let result = test::try_call_service(&app, req).await.err();
+        let err = result.expect(MSG_ERROR_WAS_EXPECTED);
+
+        let actual_status = err.as_response_error().status_code();
+        assert_eq!(actual_status, StatusCode::FORBIDDEN); // 403
+
+        let api_err: ApiError = serde_json::from_str(&err.to_string()).expect(MSG_FAILED_TO_DESER);
+        assert_eq!(api_err.code, code_to_str(StatusCode::FORBIDDEN));
+        assert_eq!(api_err.message, err::MSG_ACCESS_DENIED);
+    }
+    #[actix_web::test]
+    async fn test_get_nickname_history_invalid_id() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN, USER]);
+        let user_id_bad = format!("{}a", USER2_ID);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_nickname_history)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/nickname_history/{}", user_id_bad))
+            .insert_header(NicknameCtrlTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::RANGE_NOT_SATISFIABLE); // 416
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::RANGE_NOT_SATISFIABLE));
+        assert!(app_err.message.starts_with(err::MSG_PARSING_TYPE_NOT_SUPPORTED));
+    }
+    #[actix_web::test]
+    async fn test_get_nickname_history_valid_id() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN, USER]);
+        let user2_nickname = data_u.0.get(1).unwrap().nickname.clone();
+        let history1 = UserNicknameOrmTest::nickname_history(USER2_ID, "robert_first", "robert_second", 7200, -3600);
+        let history2 = UserNicknameOrmTest::nickname_history(USER2_ID, "robert_second", &user2_nickname, 60, 600);
+        let history3 = UserNicknameOrmTest::nickname_history(USER1_ID, "oliver_prev", "oliver_taylor", 60, 600);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_nickname_history)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![history1, history2, history3]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/nickname_history/{}", USER2_ID))
+            .insert_header(NicknameCtrlTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response_dto: Vec<NicknameHistoryDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response_dto.len(), 2);
+        // The most recent changes first.
+        assert_eq!(response_dto[0].old_nickname, "robert_second");
+        assert_eq!(response_dto[0].new_nickname, user2_nickname);
+        assert_eq!(response_dto[1].old_nickname, "robert_first");
+        assert_eq!(response_dto[1].new_nickname, "robert_second");
+    }
+    #[actix_web::test]
+    async fn test_get_nickname_history_no_history() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN, USER]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_nickname_history)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/nickname_history/{}", USER2_ID))
+            .insert_header(NicknameCtrlTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response_dto: Vec<NicknameHistoryDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response_dto.len(), 0);
+    }
+}
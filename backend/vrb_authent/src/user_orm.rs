@@ -1,17 +1,31 @@
+use chrono::{DateTime, Utc};
 use vrb_dbase::dbase::DbPool;
 
-use crate::user_models::{CreateUser, ModifyUser, Profile, Session, User};
+use crate::user_admin_models::SearchUser;
+use crate::user_models::{CreateSession, CreateUser, ModifyUser, Profile, Session, User};
 
 pub trait UserOrm {
     /// Get an entity (user) by ID.
     fn get_user_by_id(&self, id: i32, is_password: bool) -> Result<Option<User>, String>;
 
-    /// Get an entity (session) by ID.
-    fn get_session_by_id(&self, user_id: i32) -> Result<Option<Session>, String>;
+    /// Get a list of entities (session) of the user (one entry per device).
+    fn get_sessions_by_user_id(&self, user_id: i32) -> Result<Vec<Session>, String>;
 
-    /// Modify the entity (session).
-    fn modify_session(&self, user_id: i32, num_token: Option<i32>) -> Result<Option<Session>, String>;
-    // There is no need to delete the entity (session), since it is deleted cascade when deleting an entry in the users table.
+    /// Add a new entry (session).
+    fn create_session(&self, create_session: CreateSession) -> Result<Session, String>;
+
+    /// Modify the entity (session). Updates "last_seen_at" and, if specified, "num_token".
+    fn modify_session(&self, id: i32, num_token: Option<i32>) -> Result<Option<Session>, String>;
+
+    /// Replace "num_token" of the entity (session) only if it still has the specified old value.
+    fn rotate_session(&self, id: i32, old_num_token: i32, num_token: i32) -> Result<Option<Session>, String>;
+
+    /// Delete an entity (session) of the user.
+    fn delete_session(&self, user_id: i32, id: i32) -> Result<Option<Session>, String>;
+
+    /// Delete all entities (session) of the user, except the specified one.
+    fn delete_sessions(&self, user_id: i32, except_id: Option<i32>) -> Result<usize, String>;
+    // Sessions are also deleted cascade when deleting an entry in the users table.
 
     /// Find for an entity (user) by nickname or email.
     #[rustfmt::skip]
@@ -30,6 +44,12 @@ pub trait UserOrm {
 
     /// Get an entity (profile) by USER_ID.
     fn get_profile_by_id(&self, user_id: i32) -> Result<Option<Profile>, String>;
+
+    /// Find for an entity (user) by SearchUser.
+    fn find_users_by_pages(&self, search_user: SearchUser) -> Result<(u32, Vec<User>), String>;
+
+    /// Suspend (if "suspended_at" is specified) or unsuspend the account of the entity (user).
+    fn modify_user_suspended(&self, id: i32, suspended_at: Option<DateTime<Utc>>) -> Result<Option<User>, String>;
 }
 
 #[cfg(not(all(test, feature = "mockdata")))]
@@ -45,11 +65,16 @@ pub fn get_user_orm_app(_: DbPool) -> tests::UserOrmApp {
 pub mod impls {
     use std::time::Instant as tm;
 
+    use chrono::{DateTime, Utc};
     use diesel::{self, prelude::*, sql_types};
     use log::{Level::Info, info, log_enabled};
-    use vrb_dbase::{dbase, schema};
+    use vrb_dbase::{
+        dbase,
+        schema::{self, users::dsl as users_dsl},
+    };
 
-    use crate::user_models::{CreateUser, ModifyUser, Profile, Session, User};
+    use crate::user_admin_models::{self, SearchUser};
+    use crate::user_models::{CreateSession, CreateUser, ModifyUser, Profile, Session, User};
     use crate::user_orm::UserOrm;
 
     pub const CONN_POOL: &str = "ConnectionPool";
@@ -92,36 +117,63 @@ pub mod impls {
             Ok(opt_user)
         }
 
-        /// Get an entity (session) by ID.
-        fn get_session_by_id(&self, user_id: i32) -> Result<Option<Session>, String> {
+        /// Get a list of entities (session) of the user (one entry per device).
+        fn get_sessions_by_user_id(&self, user_id: i32) -> Result<Vec<Session>, String> {
             let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
             // Get a connection from the P2D2 pool.
             let mut conn = self.get_conn()?;
-            // Run query using Diesel to find user by id and return it.
-            let opt_session: Option<Session> = schema::sessions::table
+            // Run query using Diesel to find all sessions of the user.
+            let sessions: Vec<Session> = schema::sessions::table
                 .filter(schema::sessions::dsl::user_id.eq(user_id))
-                .first::<Session>(&mut conn)
-                .optional()
-                .map_err(|e| format!("get_session_by_id: {}", e.to_string()))?;
+                .order_by(schema::sessions::dsl::id.asc())
+                .select(Session::as_select())
+                .load(&mut conn)
+                .map_err(|e| format!("get_sessions_by_user_id: {}", e.to_string()))?;
 
             if let Some(timer) = timer {
-                info!("get_session_by_id() time: {}", format!("{:.2?}", timer.elapsed()));
+                info!("get_sessions_by_user_id() time: {}", format!("{:.2?}", timer.elapsed()));
             }
-            Ok(opt_session)
+            Ok(sessions)
         }
 
-        /// Perform a full or partial change to a session record.
-        fn modify_session(&self, user_id: i32, num_token: Option<i32>) -> Result<Option<Session>, String> {
+        /// Add a new entry (session).
+        fn create_session(&self, create_session: CreateSession) -> Result<Session, String> {
             let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
             // Get a connection from the P2D2 pool.
             let mut conn = self.get_conn()?;
-            // Run query using Diesel to full or partially modify the session entry.
-            let result = diesel::update(schema::sessions::dsl::sessions.find(user_id))
-                .set(schema::sessions::dsl::num_token.eq(num_token))
+            // Run query using Diesel to add a new session entry.
+            let session: Session = diesel::insert_into(schema::sessions::table)
+                .values(create_session)
                 .returning(Session::as_returning())
                 .get_result(&mut conn)
-                .optional()
-                .map_err(|e| format!("modify_session: {}", e.to_string()))?;
+                .map_err(|e| format!("create_session: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("create_session() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(session)
+        }
+
+        /// Modify the entity (session). Updates "last_seen_at" and, if specified, "num_token".
+        fn modify_session(&self, id: i32, num_token: Option<i32>) -> Result<Option<Session>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+            let now = Utc::now();
+            let query = diesel::update(schema::sessions::dsl::sessions.find(id));
+            // Run query using Diesel to full or partially modify the session entry.
+            let result = match num_token {
+                Some(num_token) => query
+                    .set((schema::sessions::dsl::num_token.eq(num_token), schema::sessions::dsl::last_seen_at.eq(now)))
+                    .returning(Session::as_returning())
+                    .get_result(&mut conn),
+                None => query
+                    .set(schema::sessions::dsl::last_seen_at.eq(now))
+                    .returning(Session::as_returning())
+                    .get_result(&mut conn),
+            }
+            .optional()
+            .map_err(|e| format!("modify_session: {}", e.to_string()))?;
 
             if let Some(timer) = timer {
                 info!("modify_session() time: {}", format!("{:.2?}", timer.elapsed()));
@@ -129,6 +181,74 @@ pub mod impls {
             Ok(result)
         }
 
+        /// Replace "num_token" of the entity (session) only if it still has the specified old value.
+        fn rotate_session(&self, id: i32, old_num_token: i32, num_token: i32) -> Result<Option<Session>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+            // Run query using Diesel to modify the session entry (if "num_token" has not been changed).
+            let result = diesel::update(
+                schema::sessions::table
+                    .filter(schema::sessions::dsl::id.eq(id))
+                    .filter(schema::sessions::dsl::num_token.eq(old_num_token)),
+            )
+            .set((
+                schema::sessions::dsl::num_token.eq(num_token),
+                schema::sessions::dsl::last_seen_at.eq(Utc::now()),
+            ))
+            .returning(Session::as_returning())
+            .get_result(&mut conn)
+            .optional()
+            .map_err(|e| format!("rotate_session: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("rotate_session() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(result)
+        }
+
+        /// Delete an entity (session) of the user.
+        fn delete_session(&self, user_id: i32, id: i32) -> Result<Option<Session>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+            // Run query using Diesel to delete a entry (session).
+            let result = diesel::delete(
+                schema::sessions::table
+                    .filter(schema::sessions::dsl::id.eq(id))
+                    .filter(schema::sessions::dsl::user_id.eq(user_id)),
+            )
+            .returning(Session::as_returning())
+            .get_result(&mut conn)
+            .optional()
+            .map_err(|e| format!("delete_session: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("delete_session() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(result)
+        }
+
+        /// Delete all entities (session) of the user, except the specified one.
+        fn delete_sessions(&self, user_id: i32, except_id: Option<i32>) -> Result<usize, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+            // Run query using Diesel to delete entries (session).
+            let count = diesel::delete(
+                schema::sessions::table
+                    .filter(schema::sessions::dsl::user_id.eq(user_id))
+                    .filter(schema::sessions::dsl::id.ne(except_id.unwrap_or(-1))),
+            )
+            .execute(&mut conn)
+            .map_err(|e| format!("delete_sessions: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("delete_sessions() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(count)
+        }
+
         /// Find for an entity (user) by nickname or email.
         fn find_user_by_nickname_or_email(
             &self,
@@ -256,19 +376,103 @@ pub mod impls {
             }
             Ok(opt_profile)
         }
+
+        /// Find for an entity (user) by SearchUser.
+        fn find_users_by_pages(&self, search_user: SearchUser) -> Result<(u32, Vec<User>), String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            let page: u32 = search_user.page.unwrap_or(user_admin_models::SEARCH_USER_PAGE).max(1);
+            let limit: u32 = search_user.limit.unwrap_or(user_admin_models::SEARCH_USER_LIMIT);
+            let offset: u32 = (page - 1) * limit;
+            // Special characters of the "LIKE" pattern are escaped.
+            let like = |value: &str| format!("%{}%", value.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"));
+            let nickname = search_user.nickname.map(|v| like(&v.to_lowercase()));
+            let email = search_user.email.map(|v| like(&v.to_lowercase()));
+
+            // Build a query to find a list of "users".
+            let mut query_list = schema::users::table.into_boxed();
+            // Create a query to get the number of elements in the list of "users".
+            let mut query_count = schema::users::table.into_boxed();
+
+            if let Some(nickname) = nickname {
+                query_list = query_list.filter(users_dsl::nickname.ilike(nickname.clone()));
+                query_count = query_count.filter(users_dsl::nickname.ilike(nickname));
+            }
+            if let Some(email) = email {
+                query_list = query_list.filter(users_dsl::email.ilike(email.clone()));
+                query_count = query_count.filter(users_dsl::email.ilike(email));
+            }
+            if let Some(role) = search_user.role {
+                query_list = query_list.filter(users_dsl::role.eq(role));
+                query_count = query_count.filter(users_dsl::role.eq(role));
+            }
+            if let Some(created_from) = search_user.created_from {
+                // created_at >= created_from
+                query_list = query_list.filter(users_dsl::created_at.ge(created_from));
+                query_count = query_count.filter(users_dsl::created_at.ge(created_from));
+            }
+            if let Some(created_to) = search_user.created_to {
+                // created_at < created_to
+                query_list = query_list.filter(users_dsl::created_at.lt(created_to));
+                query_count = query_count.filter(users_dsl::created_at.lt(created_to));
+            }
+
+            let amount: i64 = query_count
+                .count()
+                .get_result::<i64>(&mut conn)
+                .map_err(|e| format!("find_users_by_pages: (query_count) {}", e.to_string()))?;
+            let count: u32 = amount.try_into().unwrap();
+
+            let users: Vec<User> = query_list
+                .select(User::as_select())
+                .order_by(users_dsl::id.asc())
+                .offset(offset.into())
+                .limit(limit.into())
+                .load(&mut conn)
+                .map_err(|e| format!("find_users_by_pages: (query_list) {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("find_users_by_pages() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok((count, users))
+        }
+
+        /// Suspend (if "suspended_at" is specified) or unsuspend the account of the entity (user).
+        fn modify_user_suspended(&self, id: i32, suspended_at: Option<DateTime<Utc>>) -> Result<Option<User>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+            // Run query using Diesel to modify the "suspended_at" value of the user entry.
+            let opt_user: Option<User> = diesel::update(users_dsl::users.find(id))
+                .set(users_dsl::suspended_at.eq(suspended_at))
+                .returning(User::as_returning())
+                .get_result(&mut conn)
+                .optional()
+                .map_err(|e| format!("modify_user_suspended: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("modify_user_suspended() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(opt_user)
+        }
     }
 }
 
 #[cfg(any(test, feature = "mockdata"))]
 pub mod tests {
     use actix_web::web;
-    use chrono::Utc;
+    use chrono::{DateTime, Utc};
     use vrb_common::profile::{PROFILE_LOCALE_DEF, PROFILE_THEME_DARK, PROFILE_THEME_LIGHT_DEF};
     use vrb_dbase::enm_user_role::UserRole;
 
     use crate::{
         config_jwt,
-        user_models::{CreateUser, ModifyUser, Profile, Session, User},
+        user_admin_models::{self, SearchUser},
+        user_models::{CreateSession, CreateUser, ModifyUser, Profile, Session, User},
         user_orm::UserOrm,
     };
 
@@ -326,28 +530,71 @@ pub mod tests {
             Ok(result)
         }
 
-        /// Get an entity (session) by ID.
-        fn get_session_by_id(&self, user_id: i32) -> Result<Option<Session>, String> {
-            let opt_session: Option<Session> = self
+        /// Get a list of entities (session) of the user (one entry per device).
+        fn get_sessions_by_user_id(&self, user_id: i32) -> Result<Vec<Session>, String> {
+            let sessions: Vec<Session> = self.session_vec.iter().filter(|session| session.user_id == user_id).cloned().collect();
+
+            Ok(sessions)
+        }
+
+        /// Add a new entry (session).
+        fn create_session(&self, create_session: CreateSession) -> Result<Session, String> {
+            let id = self.session_vec.iter().map(|session| session.id).max().unwrap_or(0) + 1;
+            let mut session = Session::new(id, create_session.user_id, create_session.num_token);
+            session.device = create_session.device;
+            session.user_agent = create_session.user_agent;
+            session.ip_address = create_session.ip_address;
+
+            Ok(session)
+        }
+
+        /// Modify the entity (session). Updates "last_seen_at" and, if specified, "num_token".
+        fn modify_session(&self, id: i32, num_token: Option<i32>) -> Result<Option<Session>, String> {
+            let opt_session = self.session_vec.iter().find(|session| session.id == id).map(|session| {
+                let mut res_session = session.clone();
+                if let Some(num_token) = num_token {
+                    res_session.num_token = num_token;
+                }
+                res_session.last_seen_at = Utc::now();
+                res_session
+            });
+
+            Ok(opt_session)
+        }
+
+        /// Replace "num_token" of the entity (session) only if it still has the specified old value.
+        fn rotate_session(&self, id: i32, old_num_token: i32, num_token: i32) -> Result<Option<Session>, String> {
+            let opt_session = self
                 .session_vec
                 .iter()
-                .find(|session| session.user_id == user_id)
-                .map(|session| session.clone());
+                .find(|session| session.id == id && session.num_token == old_num_token);
+            let opt_session = opt_session.map(|session| {
+                let mut res_session = session.clone();
+                res_session.num_token = num_token;
+                res_session.last_seen_at = Utc::now();
+                res_session
+            });
 
             Ok(opt_session)
         }
 
-        /// Modify the entity (session).
-        fn modify_session(&self, user_id: i32, num_token: Option<i32>) -> Result<Option<Session>, String> {
-            let opt_session: Option<Session> = self.get_session_by_id(user_id)?;
-            if opt_session.is_none() {
-                return Ok(None);
-            }
-            let mut res_session = opt_session.unwrap();
-            let new_session = Session { user_id, num_token };
-            res_session.num_token = new_session.num_token;
+        /// Delete an entity (session) of the user.
+        fn delete_session(&self, user_id: i32, id: i32) -> Result<Option<Session>, String> {
+            let opt_session = self.session_vec.iter().find(|session| session.id == id && session.user_id == user_id);
+
+            Ok(opt_session.cloned())
+        }
 
-            Ok(Some(res_session))
+        /// Delete all entities (session) of the user, except the specified one.
+        fn delete_sessions(&self, user_id: i32, except_id: Option<i32>) -> Result<usize, String> {
+            let except_id = except_id.unwrap_or(-1);
+            let count = self
+                .session_vec
+                .iter()
+                .filter(|session| session.user_id == user_id && session.id != except_id)
+                .count();
+
+            Ok(count)
         }
 
         /// Find for an entity (user) by nickname or email.
@@ -446,6 +693,44 @@ pub mod tests {
 
             Ok(opt_profile)
         }
+
+        /// Find for an entity (user) by SearchUser.
+        fn find_users_by_pages(&self, search_user: SearchUser) -> Result<(u32, Vec<User>), String> {
+            let nickname = search_user.nickname.map(|v| v.to_lowercase());
+            let email = search_user.email.map(|v| v.to_lowercase());
+
+            let mut users: Vec<User> = self
+                .user_vec
+                .iter()
+                .filter(|user| nickname.as_ref().map(|v| user.nickname.contains(v)).unwrap_or(true))
+                .filter(|user| email.as_ref().map(|v| user.email.contains(v)).unwrap_or(true))
+                .filter(|user| search_user.role.map(|v| user.role == v).unwrap_or(true))
+                .filter(|user| search_user.created_from.map(|v| user.created_at >= v).unwrap_or(true))
+                .filter(|user| search_user.created_to.map(|v| user.created_at < v).unwrap_or(true))
+                .cloned()
+                .collect();
+            users.sort_by(|a, b| a.id.cmp(&b.id));
+
+            let count: u32 = users.len().try_into().unwrap();
+            let page = search_user.page.unwrap_or(user_admin_models::SEARCH_USER_PAGE).max(1);
+            let limit = search_user.limit.unwrap_or(user_admin_models::SEARCH_USER_LIMIT);
+            let offset: usize = ((page - 1) * limit).try_into().unwrap();
+            let list: Vec<User> = users.into_iter().skip(offset).take(limit.try_into().unwrap()).collect();
+
+            Ok((count, list))
+        }
+
+        /// Suspend (if "suspended_at" is specified) or unsuspend the account of the entity (user).
+        fn modify_user_suspended(&self, id: i32, suspended_at: Option<DateTime<Utc>>) -> Result<Option<User>, String> {
+            let opt_user = self.user_vec.iter().find(|user| user.id == id).map(|user| {
+                let mut res_user = user.clone();
+                res_user.suspended_at = suspended_at;
+                res_user.updated_at = Utc::now();
+                res_user
+            });
+
+            Ok(opt_user)
+        }
     }
 
     pub struct UserOrmTest {}
@@ -465,12 +750,11 @@ pub mod tests {
 
                 let user = User::new(user_id, &nickname, &format!("{}@gmail.com", nickname), "", role);
                 user_vec.push(user);
-                let num_token = if user_id == USER1_ID {
-                    Some(config_jwt::tests::get_num_token(user_id))
-                } else {
-                    None
-                };
-                session_vec.push(Session { user_id, num_token });
+                // Only the first user has an open session.
+                if user_id == USER1_ID {
+                    let session_id = i32::try_from(session_vec.len()).unwrap() + 1;
+                    session_vec.push(Session::new(session_id, user_id, config_jwt::tests::get_num_token(user_id)));
+                }
             }
             (user_vec, session_vec)
         }
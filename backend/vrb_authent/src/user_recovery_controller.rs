@@ -1,6 +1,6 @@
 use std::time::Instant as tm;
 
-use actix_web::{HttpResponse, get, http::StatusCode, post, put, web};
+use actix_web::{HttpRequest, HttpResponse, get, http::StatusCode, post, put, web};
 use chrono::{Duration, Utc};
 use log::{Level::Info, error, info, log_enabled};
 use utoipa;
@@ -30,6 +30,7 @@ use crate::user_recovery_orm::impls::UserRecoveryOrmApp;
 use crate::user_recovery_orm::tests::UserRecoveryOrmApp;
 
 use crate::{
+    attempt_limiter::{self, AttemptLimiter, is_too_many_attempts},
     authentication::RequireAuth,
     config_jwt,
     user_models::ModifyUser,
@@ -73,6 +74,9 @@ pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
 /// -d '{"email": "user@email"}' \
 /// -H 'Content-Type: application/json'
 /// ```
+/// The number of requests is limited by email and by IP address. If the limit is exceeded,
+/// the request is temporarily blocked (error 429 with the "retryAfter" parameter, the number of seconds until unlocking).
+///
 /// Return new user registration parameters (`RecoveryUserResponseDto`) with status 201.
 ///
 #[utoipa::path(
@@ -88,6 +92,8 @@ pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
             example = json!(ApiError::validations((RecoveryUserDto { email: "us_email".to_string() }).validate().err().unwrap()))),
         (status = 422, description = "Token encoding error.", body = ApiError,
             example = json!(ApiError::create(422, err::MSG_JSON_WEB_TOKEN_ENCODE, "InvalidKeyFormat"))),
+        (status = 429, description = "Too many requests. Password recovery is temporarily blocked.", body = ApiError,
+            example = json!(ApiError::new(429, err::MSG_TOO_MANY_ATTEMPTS).add_param(attempt_limiter::PARAM_RETRY_AFTER.into(), &60))),
         (status = 506, description = "Blocking error.", body = ApiError, 
             example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
         (status = 507, description = "Database error.", body = ApiError, 
@@ -98,7 +104,9 @@ pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
 )]
 #[post("/api/recovery")]
 pub async fn recovery(
+    request: HttpRequest,
     config_app: web::Data<config_app::ConfigApp>,
+    attempt_limiter: web::Data<AttemptLimiter>,
     config_jwt: web::Data<config_jwt::ConfigJwt>,
     mailer: web::Data<MailerApp>,
     config_smtp: web::Data<config_smtp::ConfigSmtp>,
@@ -118,6 +126,15 @@ pub async fn recovery(
     recovery_profile_dto.email = recovery_profile_dto.email.to_lowercase();
     let email = recovery_profile_dto.email.clone();
 
+    // Requests are counted by email and by IP address (each request can send a letter).
+    let mut attempt_keys = vec![attempt_limiter::get_key(attempt_limiter::KEY_RECOVERY, &email)];
+    if let Some(ip_address) = request.connection_info().realip_remote_addr() {
+        attempt_keys.push(attempt_limiter::get_key(attempt_limiter::KEY_RECOVERY_IP, ip_address));
+    }
+    // If there were too many requests, then return error 429.
+    is_too_many_attempts(&attempt_limiter, &attempt_keys)?;
+    attempt_limiter.add_failure(&attempt_keys);
+
     // Find in the "user" table an entry by email.
     let opt_user = web::block(move || {
         let existing_user = user_orm.find_user_by_nickname_or_email(None, Some(&email), false).map_err(|e| {
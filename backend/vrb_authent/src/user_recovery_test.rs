@@ -14,7 +14,7 @@ mod tests {
     use vrb_tools::{config_app, send_email::config_smtp, token_coding};
 
     use crate::{
-        config_jwt,
+        attempt_limiter, config_jwt,
         user_models::{self, UserMock},
         user_orm::tests::{UserOrmTest, ADMIN, USER, USER1_ID},
         user_recovery_controller::{
@@ -39,6 +39,7 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(recovery)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRecoveryCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRecoveryCtrlTest::cfg_mailer(config_smtp::get_test_config()))
@@ -65,6 +66,7 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(recovery)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRecoveryCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRecoveryCtrlTest::cfg_mailer(config_smtp::get_test_config()))
@@ -90,6 +92,7 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(recovery)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRecoveryCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRecoveryCtrlTest::cfg_mailer(config_smtp::get_test_config()))
@@ -117,6 +120,7 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(recovery)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRecoveryCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRecoveryCtrlTest::cfg_mailer(config_smtp::get_test_config()))
@@ -144,6 +148,7 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(recovery)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRecoveryCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRecoveryCtrlTest::cfg_mailer(config_smtp::get_test_config()))
@@ -171,6 +176,7 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(recovery)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRecoveryCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRecoveryCtrlTest::cfg_mailer(config_smtp::get_test_config()))
@@ -199,6 +205,7 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(recovery)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRecoveryCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRecoveryCtrlTest::cfg_mailer(config_smtp::get_test_config()))
@@ -230,6 +237,7 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(recovery)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRecoveryCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRecoveryCtrlTest::cfg_mailer(config_smtp::get_test_config()))
@@ -269,6 +277,7 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(recovery)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRecoveryCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRecoveryCtrlTest::cfg_mailer(config_smtp::get_test_config()))
@@ -298,6 +307,47 @@ mod tests {
         assert_eq!(user_recovery1_id, user_recovery_id);
     }
     #[actix_web::test]
+    async fn test_recovery_if_too_many_attempts() {
+        env_var::env_set_var(consts::SMTP_PATH_TEMPLATE, TEST_PATH_TEMPLATE);
+        let data_u = UserOrmTest::users(&[USER]);
+        let user1_id = data_u.0.get(0).unwrap().id;
+        let user1_email = data_u.0.get(0).unwrap().email.clone();
+        let recoveries = UserRecoveryOrmTest::recoveries(Some(user1_id));
+        let attempt_limiter = attempt_limiter::tests::get_attempt_limiter();
+        let max_attempts = attempt_limiter.max_attempts;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(recovery)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter))
+                .configure(UserRecoveryCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserRecoveryCtrlTest::cfg_mailer(config_smtp::get_test_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserRecoveryOrmTest::cfg_recovery_orm(recoveries))
+        ).await;
+        for _ in 0..max_attempts {
+            #[rustfmt::skip]
+            let req = test::TestRequest::post().uri("/api/recovery")
+                .set_json(RecoveryUserDto { email: user1_email.to_string() })
+                .to_request();
+            let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::CREATED); // 201
+        }
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/recovery")
+            .set_json(RecoveryUserDto { email: user1_email.to_uppercase() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS); // 429
+
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::TOO_MANY_REQUESTS));
+        assert_eq!(app_err.message, err::MSG_TOO_MANY_ATTEMPTS);
+        assert!(app_err.params.get(attempt_limiter::PARAM_RETRY_AFTER).is_some());
+    }
+    #[actix_web::test]
     async fn test_recovery_err_jsonwebtoken_encode() {
         env_var::env_set_var(consts::SMTP_PATH_TEMPLATE, TEST_PATH_TEMPLATE);
         let data_u = UserOrmTest::users(&[USER]);
@@ -307,6 +357,7 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(recovery)
+            .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
             .configure(UserRecoveryCtrlTest::cfg_config_app(config_app::get_test_config()))
             .configure(config_jwt::tests::cfg_config_jwt(config_jwt))
             .configure(UserRecoveryCtrlTest::cfg_mailer(config_smtp::get_test_config()))
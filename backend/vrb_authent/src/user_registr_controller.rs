@@ -1,6 +1,6 @@
 use std::{borrow::Cow, time::Instant as tm};
 
-use actix_web::{HttpResponse, get, http::StatusCode, post, put, web};
+use actix_web::{HttpRequest, HttpResponse, get, http::StatusCode, post, put, web};
 use chrono::{Duration, Utc};
 use log::{Level::Info, error, info, log_enabled};
 use utoipa;
@@ -19,6 +19,10 @@ use vrb_tools::{
     token_coding,
 };
 
+#[cfg(not(all(test, feature = "mockdata")))]
+use crate::user_nickname_orm::impls::UserNicknameOrmApp;
+#[cfg(all(test, feature = "mockdata"))]
+use crate::user_nickname_orm::tests::UserNicknameOrmApp;
 #[cfg(not(all(test, feature = "mockdata")))]
 use crate::user_orm::impls::UserOrmApp;
 #[cfg(all(test, feature = "mockdata"))]
@@ -28,9 +32,11 @@ use crate::user_registr_orm::impls::UserRegistrOrmApp;
 #[cfg(all(test, feature = "mockdata"))]
 use crate::user_registr_orm::tests::UserRegistrOrmApp;
 use crate::{
+    attempt_limiter::{self, AttemptLimiter, is_too_many_attempts},
     authentication::RequireAuth,
     config_jwt,
     user_models::CreateUser,
+    user_nickname_orm::UserNicknameOrm,
     user_orm::UserOrm,
     user_registr_models::{
         ConfirmRegistrUserResponseDto, CreateUserRegistr, RegistrUserDto, RegistrUserResponseDto, RegistrationClearForExpiredResponseDto,
@@ -69,6 +75,9 @@ pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
 /// -H 'Content-Type: application/json'
 /// ```
 ///
+/// The number of requests is limited by email and by IP address. If the limit is exceeded,
+/// the request is temporarily blocked (error 429 with the "retryAfter" parameter, the number of seconds until unlocking).
+///
 /// Return new user registration parameters (`RegistrUserResponseDto`) with status 201.
 ///
 #[utoipa::path(
@@ -91,6 +100,8 @@ pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
                     .validate().err().unwrap()) )),
         (status = 422, description = "Token encoding error.", body = ApiError,
             example = json!(ApiError::create(422, err::MSG_JSON_WEB_TOKEN_ENCODE, "InvalidKeyFormat"))),
+        (status = 429, description = "Too many requests. Registration is temporarily blocked.", body = ApiError,
+            example = json!(ApiError::new(429, err::MSG_TOO_MANY_ATTEMPTS).add_param(attempt_limiter::PARAM_RETRY_AFTER.into(), &60))),
         (status = 500, description = "Error while calculating the password hash.", body = ApiError, 
             example = json!(ApiError::create(500, err::MSG_ERROR_HASHING_PASSWORD, "Parameter is empty."))),
         (status = 506, description = "Blocking error.", body = ApiError, 
@@ -103,12 +114,15 @@ pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
 )]
 #[post("/api/registration")]
 pub async fn registration(
+    request: HttpRequest,
     config_app: web::Data<config_app::ConfigApp>,
+    attempt_limiter: web::Data<AttemptLimiter>,
     config_jwt: web::Data<config_jwt::ConfigJwt>,
     mailer: web::Data<MailerApp>,
     config_smtp: web::Data<config_smtp::ConfigSmtp>,
     user_orm: web::Data<UserOrmApp>,
     user_registr_orm: web::Data<UserRegistrOrmApp>,
+    user_nickname_orm: web::Data<UserNicknameOrmApp>,
     json_body: web::Json<RegistrUserDto>,
 ) -> actix_web::Result<HttpResponse, ApiError> {
     let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
@@ -124,6 +138,15 @@ pub async fn registration(
     registr_user_dto.nickname = registr_user_dto.nickname.to_lowercase();
     registr_user_dto.email = registr_user_dto.email.to_lowercase();
 
+    // Requests are counted by email and by IP address (each request can send a letter).
+    let mut attempt_keys = vec![attempt_limiter::get_key(attempt_limiter::KEY_REGISTR, &registr_user_dto.email)];
+    if let Some(ip_address) = request.connection_info().realip_remote_addr() {
+        attempt_keys.push(attempt_limiter::get_key(attempt_limiter::KEY_REGISTR_IP, ip_address));
+    }
+    // If there were too many requests, then return error 429.
+    is_too_many_attempts(&attempt_limiter, &attempt_keys)?;
+    attempt_limiter.add_failure(&attempt_keys);
+
     let password = registr_user_dto.password.clone();
     let password_hashed = hash_tools::encode_hash(&password).map_err(|e| {
         error!("{}-{}; {}", code_to_str(StatusCode::INTERNAL_SERVER_ERROR), err::MSG_ERROR_HASHING_PASSWORD, &e);
@@ -142,6 +165,7 @@ pub async fn registration(
 
     let user_orm2 = user_orm.get_ref().clone();
     let user_registr_orm2 = user_registr_orm.get_ref().clone();
+    let user_nickname_orm2 = user_nickname_orm.get_ref().clone();
 
     let opt_search = web::block(move || {
         let mut res_search: Option<(bool, bool)> = None;
@@ -167,6 +191,16 @@ pub async fn registration(
                 res_search = Some((nickname == user_registr.nickname, email == user_registr.email));
             }
         }
+        if res_search.is_none() {
+            let opt_reserved = user_nickname_orm2
+                .find_reserved_nickname(&nickname)
+                .map_err(|e| ApiError::create(507, err::MSG_DATABASE, &e)) // 507
+                .ok()?;
+            // If the nickname is reserved in the "user_nickname_history" table, then exit.
+            if opt_reserved.is_some() {
+                res_search = Some((true, false));
+            }
+        }
         res_search
     })
     .await
@@ -14,8 +14,9 @@ mod tests {
     use vrb_tools::{config_app, send_email::config_smtp, token_coding};
 
     use crate::{
-        config_jwt,
+        attempt_limiter, config_jwt,
         user_models::{self, UserMock},
+        user_nickname_orm::tests::UserNicknameOrmTest,
         user_orm::tests::{ADMIN, USER, USER1_ID, UserOrmTest},
         user_registr_controller::{
             MSG_REGISTR_NOT_FOUND, confirm_registration, registration, registration_clear_for_expired, tests as UserRegistrCtrlTest,
@@ -38,11 +39,13 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(registration)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRegistrCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRegistrCtrlTest::cfg_mailer(config_smtp::get_test_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/registration").to_request();
@@ -61,11 +64,13 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(registration)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRegistrCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRegistrCtrlTest::cfg_mailer(config_smtp::get_test_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/registration").set_json(json!({}))
@@ -85,11 +90,13 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(registration)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRegistrCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRegistrCtrlTest::cfg_mailer(config_smtp::get_test_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/registration")
@@ -116,11 +123,13 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(registration)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRegistrCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRegistrCtrlTest::cfg_mailer(config_smtp::get_test_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/registration")
@@ -147,11 +156,13 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(registration)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRegistrCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRegistrCtrlTest::cfg_mailer(config_smtp::get_test_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/registration")
@@ -178,11 +189,13 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(registration)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRegistrCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRegistrCtrlTest::cfg_mailer(config_smtp::get_test_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/registration")
@@ -209,11 +222,13 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(registration)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRegistrCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRegistrCtrlTest::cfg_mailer(config_smtp::get_test_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/registration")
@@ -240,11 +255,13 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(registration)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRegistrCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRegistrCtrlTest::cfg_mailer(config_smtp::get_test_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/registration")
@@ -271,11 +288,13 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(registration)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRegistrCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRegistrCtrlTest::cfg_mailer(config_smtp::get_test_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/registration")
@@ -302,11 +321,13 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(registration)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRegistrCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRegistrCtrlTest::cfg_mailer(config_smtp::get_test_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/registration")
@@ -333,11 +354,13 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(registration)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRegistrCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRegistrCtrlTest::cfg_mailer(config_smtp::get_test_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/registration")
@@ -364,11 +387,13 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(registration)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRegistrCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRegistrCtrlTest::cfg_mailer(config_smtp::get_test_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/registration")
@@ -395,11 +420,13 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(registration)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRegistrCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRegistrCtrlTest::cfg_mailer(config_smtp::get_test_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/registration")
@@ -426,11 +453,13 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(registration)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRegistrCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRegistrCtrlTest::cfg_mailer(config_smtp::get_test_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/registration")
@@ -459,11 +488,13 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(registration)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRegistrCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRegistrCtrlTest::cfg_mailer(config_smtp::get_test_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/registration")
@@ -490,11 +521,13 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(registration)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRegistrCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRegistrCtrlTest::cfg_mailer(config_smtp::get_test_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/registration")
@@ -522,11 +555,13 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(registration)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRegistrCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRegistrCtrlTest::cfg_mailer(config_smtp::get_test_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(registrs))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/registration")
@@ -554,11 +589,13 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(registration)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRegistrCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRegistrCtrlTest::cfg_mailer(config_smtp::get_test_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(registrs))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/registration")
@@ -577,6 +614,86 @@ mod tests {
         assert_eq!(app_err.message, err::MSG_EMAIL_ALREADY_USE);
     }
     #[actix_web::test]
+    async fn test_registration_if_nickname_reserved() {
+        env_var::env_set_var(consts::SMTP_PATH_TEMPLATE, TEST_PATH_TEMPLATE);
+        let data_u = UserOrmTest::users(&[USER]);
+        let user1 = data_u.0.get(0).unwrap().clone();
+        let nickname1 = "old_nickname".to_string();
+        let history = UserNicknameOrmTest::nickname_history(user1.id, &nickname1, &user1.nickname, 60, 600);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(registration)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(UserRegistrCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserRegistrCtrlTest::cfg_mailer(config_smtp::get_test_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![history]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/registration")
+            .set_json(RegistrUserDto {
+                nickname: nickname1, email: "old_nickname@gmail.com".to_string(), password: "passwordD2T2".to_string(),
+            })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT); // 409
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::CONFLICT));
+        assert_eq!(app_err.message, err::MSG_NICKNAME_ALREADY_USE);
+    }
+    #[actix_web::test]
+    async fn test_registration_if_too_many_attempts() {
+        env_var::env_set_var(consts::SMTP_PATH_TEMPLATE, TEST_PATH_TEMPLATE);
+        let registrs = UserRegistrOrmTest::registrs(true);
+        let user_registr1 = registrs.get(0).unwrap().clone();
+        let data_u = UserOrmTest::users(&[USER]);
+        let attempt_limiter = attempt_limiter::tests::get_attempt_limiter();
+        let max_attempts = attempt_limiter.max_attempts;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(registration)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter))
+                .configure(UserRegistrCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserRegistrCtrlTest::cfg_mailer(config_smtp::get_test_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
+        ).await;
+        let registr_user_dto = RegistrUserDto {
+            nickname: user_registr1.nickname.clone(),
+            email: user_registr1.email.clone(),
+            password: user_registr1.password.clone(),
+        };
+        for _ in 0..max_attempts {
+            #[rustfmt::skip]
+            let req = test::TestRequest::post().uri("/api/registration")
+                .set_json(registr_user_dto.clone())
+                .to_request();
+            let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::CREATED); // 201
+        }
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/registration")
+            .set_json(registr_user_dto)
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS); // 429
+
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::TOO_MANY_REQUESTS));
+        assert_eq!(app_err.message, err::MSG_TOO_MANY_ATTEMPTS);
+        assert!(app_err.params.get(attempt_limiter::PARAM_RETRY_AFTER).is_some());
+    }
+    #[actix_web::test]
     async fn test_registration_err_jsonwebtoken_encode() {
         env_var::env_set_var(consts::SMTP_PATH_TEMPLATE, TEST_PATH_TEMPLATE);
         let data_u = UserOrmTest::users(&[USER]);
@@ -586,11 +703,13 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(registration)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRegistrCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt))
                 .configure(UserRegistrCtrlTest::cfg_mailer(config_smtp::get_test_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/registration")
@@ -617,11 +736,13 @@ mod tests {
         #[rustfmt::skip]
         let app = test::init_service(
             App::new().service(registration)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
                 .configure(UserRegistrCtrlTest::cfg_config_app(config_app::get_test_config()))
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
                 .configure(UserRegistrCtrlTest::cfg_mailer(config_smtp::get_test_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(UserRegistrOrmTest::cfg_registr_orm(UserRegistrOrmTest::registrs(false)))
+                .configure(UserNicknameOrmTest::cfg_nickname_orm(vec![]))
         ).await;
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/registration")
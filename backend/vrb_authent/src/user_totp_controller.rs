@@ -0,0 +1,499 @@
+use std::{ops::Deref, time::Instant as tm};
+
+use actix_web::{HttpResponse, delete, get, http::StatusCode, post, put, web};
+use chrono::Utc;
+use log::{Level::Info, error, info, log_enabled};
+use utoipa;
+use vrb_common::{
+    api_error::{ApiError, code_to_str},
+    err,
+    validators::{Validator, msg_validation},
+};
+use vrb_tools::{config_app, hash_tools, totp_tools};
+
+#[cfg(not(all(test, feature = "mockdata")))]
+use crate::user_totp_orm::impls::UserTotpOrmApp;
+#[cfg(all(test, feature = "mockdata"))]
+use crate::user_totp_orm::tests::UserTotpOrmApp;
+use crate::{
+    attempt_limiter::{self, AttemptLimiter, is_too_many_attempts},
+    authentication::{Authenticated, RequireAuth},
+    user_totp_models::{CreateUserTotp, ModifyUserTotp, TotpCodeDto, TotpRecoveryCodesDto, TotpSetupResponseDto, UserTotp},
+    user_totp_orm::UserTotpOrm,
+};
+
+// The lifetime of the challenge token (the second step of login) in seconds.
+pub const CHALLENGE_DURATION: i64 = 300;
+
+const SECRET1: &str = "JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP";
+
+pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
+    |config: &mut web::ServiceConfig| {
+        config
+            // POST /api/totp
+            .service(post_totp)
+            // GET /api/totp/qr_code
+            .service(get_totp_qr_code)
+            // PUT /api/totp
+            .service(put_totp)
+            // DELETE /api/totp
+            .service(delete_totp);
+    }
+}
+
+/// Check the one-time code (TOTP) or one of the recovery codes.
+///
+/// Returns the changes for the entity (user_totp) if the code is correct:
+/// for a one-time code, the number of the used time period (the code cannot be used again);
+/// for a recovery code, the list of remaining recovery codes.
+pub fn verify_totp_code(user_totp: &UserTotp, code: &str) -> Result<Option<ModifyUserTotp>, ApiError> {
+    let code = code.trim();
+    let is_totp_code = code.len() == totp_tools::TOTP_DIGITS as usize && code.chars().all(|ch| ch.is_ascii_digit());
+    if is_totp_code {
+        let timestamp = Utc::now().timestamp();
+        let opt_step = totp_tools::verify_code(&user_totp.secret, code, timestamp).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::CONFLICT), err::MSG_INVALID_HASH, &e);
+            ApiError::create(409, err::MSG_INVALID_HASH, &e) // 409
+        })?;
+        // A code that has already been used (or an earlier one) is not accepted again.
+        let result = opt_step.filter(|step| user_totp.last_step.map(|last_step| *step > last_step).unwrap_or(true));
+        return Ok(result.map(|step| ModifyUserTotp {
+            last_step: Some(Some(step)),
+            ..Default::default()
+        }));
+    }
+    for (idx, code_hash) in user_totp.recovery_codes.iter().enumerate() {
+        let is_match = hash_tools::compare_hash(code, code_hash).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::CONFLICT), err::MSG_INVALID_HASH, &e);
+            ApiError::create(409, err::MSG_INVALID_HASH, &e) // 409
+        })?;
+        if is_match {
+            // The recovery code is one-time, so it is removed from the list.
+            let mut recovery_codes = user_totp.recovery_codes.clone();
+            recovery_codes.remove(idx);
+            return Ok(Some(ModifyUserTotp {
+                recovery_codes: Some(recovery_codes),
+                ..Default::default()
+            }));
+        }
+    }
+    Ok(None)
+}
+
+/// post_totp
+///
+/// Start setting up two-factor authentication (TOTP, RFC 6238) for the current user.
+///
+/// A new secret key is generated. It must be added to the authenticator application
+/// (manually or by scanning the QR code `GET /api/totp/qr_code`), and then confirmed with a one-time code `PUT /api/totp`.
+/// Until confirmation, two-factor authentication is not used at login.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X POST http://localhost:8080/api/totp
+/// ```
+///
+/// Return the secret key and the key URI (`TotpSetupResponseDto`) with status 201.
+///
+#[utoipa::path(
+    responses(
+        (status = 201, description = "The secret key and the key URI for the authenticator application.",
+            body = TotpSetupResponseDto,
+            example = json!(TotpSetupResponseDto {
+                secret: SECRET1.to_owned(),
+                otpauth_uri: totp_tools::get_otpauth_uri("verbena", "james_miller", SECRET1),
+            })
+        ),
+        (status = 401, description = "An authorization token is required.", body = ApiError,
+            example = json!(ApiError::new(401, err::MSG_MISSING_TOKEN))),
+        (status = 409, description = "Two-factor authentication is already enabled.", body = ApiError,
+            example = json!(ApiError::create(409, err::MSG_TOTP_ALREADY_ENABLED, "user_id: 1"))),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/api/totp", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn post_totp(
+    authenticated: Authenticated,
+    config_app: web::Data<config_app::ConfigApp>,
+    user_totp_orm: web::Data<UserTotpOrmApp>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+    // Get current user details.
+    let user = authenticated.deref().clone();
+    let user_id = user.id;
+    let secret = totp_tools::generate_secret();
+    let create_user_totp = CreateUserTotp { user_id, secret };
+
+    let res_user_totp = web::block(move || {
+        let opt_user_totp = user_totp_orm.get_user_totp(user_id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        })?;
+        // Re-enrollment is possible only after disabling two-factor authentication.
+        if opt_user_totp.map(|user_totp| user_totp.is_enabled).unwrap_or(false) {
+            let msg = format!("user_id: {}", user_id);
+            error!("{}-{}; {}", code_to_str(StatusCode::CONFLICT), err::MSG_TOTP_ALREADY_ENABLED, &msg);
+            return Err(ApiError::create(409, err::MSG_TOTP_ALREADY_ENABLED, &msg)); // 409
+        }
+        // Add a new entity (user_totp) or replace the secret key of the pending one.
+        user_totp_orm.create_user_totp(create_user_totp).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        })
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })?;
+
+    let user_totp = res_user_totp?;
+    let otpauth_uri = totp_tools::get_otpauth_uri(&config_app.app_name, &user.nickname, &user_totp.secret);
+    let totp_setup_response_dto = TotpSetupResponseDto {
+        secret: user_totp.secret,
+        otpauth_uri,
+    };
+
+    if let Some(timer) = timer {
+        info!("post_totp() time: {}", format!("{:.2?}", timer.elapsed()));
+    }
+    Ok(HttpResponse::Created().json(totp_setup_response_dto)) // 201
+}
+
+/// get_totp_qr_code
+///
+/// Get the QR code (PNG image) with the key URI of the pending two-factor authentication setup.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X GET http://localhost:8080/api/totp/qr_code --output qr_code.png
+/// ```
+///
+/// Return the QR code image ("image/png") with status 200.
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "The QR code image with the key URI.", content_type = "image/png", body = Vec<u8>),
+        (status = 401, description = "An authorization token is required.", body = ApiError,
+            example = json!(ApiError::new(401, err::MSG_MISSING_TOKEN))),
+        (status = 404, description = "Two-factor authentication has not been set up.", body = ApiError,
+            example = json!(ApiError::create(404, err::MSG_TOTP_NOT_FOUND, "user_id: 1"))),
+        (status = 409, description = "Two-factor authentication is already enabled.", body = ApiError,
+            example = json!(ApiError::create(409, err::MSG_TOTP_ALREADY_ENABLED, "user_id: 1"))),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+        (status = 510, description = "Error while creating the QR code image.", body = ApiError,
+            example = json!(ApiError::create(510, err::MSG_ERROR_CONVERT_FILE, "data too long"))),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/api/totp/qr_code", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn get_totp_qr_code(
+    authenticated: Authenticated,
+    config_app: web::Data<config_app::ConfigApp>,
+    user_totp_orm: web::Data<UserTotpOrmApp>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+    // Get current user details.
+    let user = authenticated.deref().clone();
+    let user_id = user.id;
+
+    let user_totp = get_pending_user_totp(user_totp_orm, user_id).await?;
+
+    let otpauth_uri = totp_tools::get_otpauth_uri(&config_app.app_name, &user.nickname, &user_totp.secret);
+    let buffer = totp_tools::encode_qr_code_png(&otpauth_uri).map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::NOT_EXTENDED), err::MSG_ERROR_CONVERT_FILE, &e);
+        ApiError::create(510, err::MSG_ERROR_CONVERT_FILE, &e) // 510
+    })?;
+
+    if let Some(timer) = timer {
+        info!("get_totp_qr_code() time: {}", format!("{:.2?}", timer.elapsed()));
+    }
+    Ok(HttpResponse::Ok().content_type("image/png").body(buffer)) // 200
+}
+
+/// Get the entity (user_totp) whose setup has not yet been confirmed.
+async fn get_pending_user_totp(user_totp_orm: web::Data<UserTotpOrmApp>, user_id: i32) -> Result<UserTotp, ApiError> {
+    let opt_user_totp = web::block(move || {
+        let res_user_totp = user_totp_orm.get_user_totp(user_id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_user_totp
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })??;
+
+    let msg = format!("user_id: {}", user_id);
+    let user_totp = opt_user_totp.ok_or_else(|| {
+        error!("{}-{}; {}", code_to_str(StatusCode::NOT_FOUND), err::MSG_TOTP_NOT_FOUND, &msg);
+        ApiError::create(404, err::MSG_TOTP_NOT_FOUND, &msg) // 404
+    })?;
+    if user_totp.is_enabled {
+        error!("{}-{}; {}", code_to_str(StatusCode::CONFLICT), err::MSG_TOTP_ALREADY_ENABLED, &msg);
+        return Err(ApiError::create(409, err::MSG_TOTP_ALREADY_ENABLED, &msg)); // 409
+    }
+    Ok(user_totp)
+}
+
+/// put_totp
+///
+/// Confirm the setup of two-factor authentication with a one-time code from the authenticator application.
+///
+/// After confirmation, login requires a one-time code (`POST /api/login/totp`).
+/// A list of one-time recovery codes is returned. They are shown only once and can be used instead of a one-time code.
+///
+/// Failed attempts are counted by user. After too many failed attempts, the check is temporarily blocked (error 429).
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X PUT http://localhost:8080/api/totp \
+/// -d '{"code": "123456"}' \
+/// -H 'Content-Type: application/json'
+/// ```
+///
+/// Return the list of recovery codes (`TotpRecoveryCodesDto`) with status 200.
+///
+#[utoipa::path(
+    request_body(content = TotpCodeDto,
+        description = "One-time code from the authenticator application `TotpCodeDto`",
+        example = json!(TotpCodeDto { code: "123456".to_owned() })
+    ),
+    responses(
+        (status = 200, description = "The list of one-time recovery codes.", body = TotpRecoveryCodesDto,
+            example = json!(TotpRecoveryCodesDto { recovery_codes: vec!["k7fh2qpx9m".to_owned(), "w3nzr8tc4d".to_owned()] })),
+        (status = 401, description = "The one-time code is incorrect.", body = ApiError,
+            example = json!(ApiError::new(401, err::MSG_TOTP_CODE_INCORRECT))),
+        (status = 404, description = "Two-factor authentication has not been set up.", body = ApiError,
+            example = json!(ApiError::create(404, err::MSG_TOTP_NOT_FOUND, "user_id: 1"))),
+        (status = 409, description = "Two-factor authentication is already enabled.", body = ApiError,
+            example = json!(ApiError::create(409, err::MSG_TOTP_ALREADY_ENABLED, "user_id: 1"))),
+        (status = 417, body = [ApiError], description =
+            "Validation error. `curl -i -X PUT http://localhost:8080/api/totp -d '{ \"code\": \"\" }'`",
+            example = json!(ApiError::validations((TotpCodeDto { code: "".to_owned() }).validate().err().unwrap()))),
+        (status = 429, description = "Too many failed attempts. The check is temporarily blocked.", body = ApiError,
+            example = json!(ApiError::new(429, err::MSG_TOO_MANY_ATTEMPTS).add_param(attempt_limiter::PARAM_RETRY_AFTER.into(), &60))),
+        (status = 500, description = "Error while calculating the hash of recovery codes.", body = ApiError,
+            example = json!(ApiError::create(500, err::MSG_ERROR_HASHING_PASSWORD, "Parameter is empty."))),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[put("/api/totp", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn put_totp(
+    authenticated: Authenticated,
+    attempt_limiter: web::Data<AttemptLimiter>,
+    user_totp_orm: web::Data<UserTotpOrmApp>,
+    json_body: web::Json<TotpCodeDto>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+    // Checking the validity of the data model.
+    let validation_res = json_body.validate();
+    if let Err(validation_errors) = validation_res {
+        error!("{}-{}", code_to_str(StatusCode::EXPECTATION_FAILED), msg_validation(&validation_errors)); // 417
+        return Ok(ApiError::to_response(&ApiError::validations(validation_errors)));
+    }
+    let totp_code_dto: TotpCodeDto = json_body.into_inner();
+    // Get current user details.
+    let user_id = authenticated.deref().id;
+
+    // Failed attempts are counted by user.
+    let attempt_keys = vec![attempt_limiter::get_key(attempt_limiter::KEY_TOTP, &user_id.to_string())];
+    // If there were too many failed attempts, then return error 429.
+    is_too_many_attempts(&attempt_limiter, &attempt_keys)?;
+
+    let user_totp = get_pending_user_totp(user_totp_orm.clone(), user_id).await?;
+
+    let opt_modify_user_totp = verify_totp_code(&user_totp, &totp_code_dto.code)?;
+    let Some(mut modify_user_totp) = opt_modify_user_totp else {
+        attempt_limiter.add_failure(&attempt_keys);
+        error!("{}-{}", code_to_str(StatusCode::UNAUTHORIZED), err::MSG_TOTP_CODE_INCORRECT);
+        return Err(ApiError::new(401, err::MSG_TOTP_CODE_INCORRECT)); // 401
+    };
+    attempt_limiter.reset(&attempt_keys);
+
+    // Only the hashes of the recovery codes are stored.
+    let recovery_codes = totp_tools::generate_recovery_codes();
+    let mut recovery_code_hashes: Vec<String> = Vec::new();
+    for recovery_code in recovery_codes.iter() {
+        let code_hash = hash_tools::encode_hash(recovery_code).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INTERNAL_SERVER_ERROR), err::MSG_ERROR_HASHING_PASSWORD, &e);
+            ApiError::create(500, err::MSG_ERROR_HASHING_PASSWORD, &e) // 500
+        })?;
+        recovery_code_hashes.push(code_hash);
+    }
+    modify_user_totp.is_enabled = Some(true);
+    modify_user_totp.recovery_codes = Some(recovery_code_hashes);
+
+    let opt_user_totp = web::block(move || {
+        // Enable two-factor authentication. Result <Option<UserTotp>>.
+        let res_user_totp = user_totp_orm.modify_user_totp(user_id, modify_user_totp).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_user_totp
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })??;
+
+    if opt_user_totp.is_none() {
+        let msg = format!("user_id: {}", user_id);
+        error!("{}-{}; {}", code_to_str(StatusCode::NOT_FOUND), err::MSG_TOTP_NOT_FOUND, &msg);
+        return Err(ApiError::create(404, err::MSG_TOTP_NOT_FOUND, &msg)); // 404
+    }
+
+    if let Some(timer) = timer {
+        info!("put_totp() time: {}", format!("{:.2?}", timer.elapsed()));
+    }
+    Ok(HttpResponse::Ok().json(TotpRecoveryCodesDto { recovery_codes })) // 200
+}
+
+/// delete_totp
+///
+/// Disable two-factor authentication for the current user.
+///
+/// Confirmation is required with a one-time code from the authenticator application or with one of the recovery codes.
+///
+/// Failed attempts are counted by user. After too many failed attempts, the check is temporarily blocked (error 429).
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X DELETE http://localhost:8080/api/totp \
+/// -d '{"code": "123456"}' \
+/// -H 'Content-Type: application/json'
+/// ```
+///
+/// Return the response with status 200.
+///
+#[utoipa::path(
+    request_body(content = TotpCodeDto,
+        description = "One-time code from the authenticator application or a recovery code `TotpCodeDto`",
+        example = json!(TotpCodeDto { code: "123456".to_owned() })
+    ),
+    responses(
+        (status = 200, description = "Two-factor authentication is disabled."),
+        (status = 401, description = "The one-time code (recovery code) is incorrect.", body = ApiError,
+            example = json!(ApiError::new(401, err::MSG_TOTP_CODE_INCORRECT))),
+        (status = 404, description = "Two-factor authentication is not enabled.", body = ApiError,
+            example = json!(ApiError::create(404, err::MSG_TOTP_NOT_FOUND, "user_id: 1"))),
+        (status = 409, description = "Error when comparing hashes of recovery codes.", body = ApiError,
+            example = json!(ApiError::create(409, err::MSG_INVALID_HASH, "Parameter is empty."))),
+        (status = 417, body = [ApiError], description =
+            "Validation error. `curl -i -X DELETE http://localhost:8080/api/totp -d '{ \"code\": \"\" }'`",
+            example = json!(ApiError::validations((TotpCodeDto { code: "".to_owned() }).validate().err().unwrap()))),
+        (status = 429, description = "Too many failed attempts. The check is temporarily blocked.", body = ApiError,
+            example = json!(ApiError::new(429, err::MSG_TOO_MANY_ATTEMPTS).add_param(attempt_limiter::PARAM_RETRY_AFTER.into(), &60))),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/api/totp", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn delete_totp(
+    authenticated: Authenticated,
+    attempt_limiter: web::Data<AttemptLimiter>,
+    user_totp_orm: web::Data<UserTotpOrmApp>,
+    json_body: web::Json<TotpCodeDto>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+    // Checking the validity of the data model.
+    let validation_res = json_body.validate();
+    if let Err(validation_errors) = validation_res {
+        error!("{}-{}", code_to_str(StatusCode::EXPECTATION_FAILED), msg_validation(&validation_errors)); // 417
+        return Ok(ApiError::to_response(&ApiError::validations(validation_errors)));
+    }
+    let totp_code_dto: TotpCodeDto = json_body.into_inner();
+    // Get current user details.
+    let user_id = authenticated.deref().id;
+
+    // Failed attempts are counted by user.
+    let attempt_keys = vec![attempt_limiter::get_key(attempt_limiter::KEY_TOTP, &user_id.to_string())];
+    // If there were too many failed attempts, then return error 429.
+    is_too_many_attempts(&attempt_limiter, &attempt_keys)?;
+
+    let user_totp_orm2 = user_totp_orm.get_ref().clone();
+    let opt_user_totp = web::block(move || {
+        let res_user_totp = user_totp_orm2.get_user_totp(user_id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_user_totp
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })??;
+
+    let Some(user_totp) = opt_user_totp.filter(|user_totp| user_totp.is_enabled) else {
+        let msg = format!("user_id: {}", user_id);
+        error!("{}-{}; {}", code_to_str(StatusCode::NOT_FOUND), err::MSG_TOTP_NOT_FOUND, &msg);
+        return Err(ApiError::create(404, err::MSG_TOTP_NOT_FOUND, &msg)); // 404
+    };
+
+    if verify_totp_code(&user_totp, &totp_code_dto.code)?.is_none() {
+        attempt_limiter.add_failure(&attempt_keys);
+        error!("{}-{}", code_to_str(StatusCode::UNAUTHORIZED), err::MSG_TOTP_CODE_INCORRECT);
+        return Err(ApiError::new(401, err::MSG_TOTP_CODE_INCORRECT)); // 401
+    }
+    attempt_limiter.reset(&attempt_keys);
+
+    web::block(move || {
+        // Delete the entity (user_totp).
+        let res_count = user_totp_orm.delete_user_totp(user_id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_count
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })??;
+
+    if let Some(timer) = timer {
+        info!("delete_totp() time: {}", format!("{:.2?}", timer.elapsed()));
+    }
+    Ok(HttpResponse::Ok().body(())) // 200
+}
+
+#[cfg(all(test, feature = "mockdata"))]
+pub mod tests {
+
+    use actix_web::{http, web};
+    use vrb_tools::{config_app, token_data::BEARER};
+
+    pub fn header_auth(token: &str) -> (http::header::HeaderName, http::header::HeaderValue) {
+        let header_value = http::header::HeaderValue::from_str(&format!("{}{}", BEARER, token)).unwrap();
+        (http::header::AUTHORIZATION, header_value)
+    }
+
+    pub fn cfg_config_app(config_app: config_app::ConfigApp) -> impl FnOnce(&mut web::ServiceConfig) {
+        move |config: &mut web::ServiceConfig| {
+            let data_config_app = web::Data::new(config_app);
+            config.app_data(web::Data::clone(&data_config_app));
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use vrb_common::validators::{ValidationChecks, ValidationError, Validator};
+use vrb_dbase::schema;
+
+use crate::user_models;
+
+// ** Section: "UserTotp.code" **
+
+pub const CODE_MAX: u8 = 32;
+pub const MSG_CODE_REQUIRED: &str = "code:required";
+pub const MSG_CODE_MAX_LENGTH: &str = "code:max_length";
+
+// MAX=32 (one-time code or recovery code)
+pub fn validate_code(value: &str) -> Result<(), ValidationError> {
+    ValidationChecks::required(value, MSG_CODE_REQUIRED)?;
+    ValidationChecks::max_length(value, CODE_MAX.into(), MSG_CODE_MAX_LENGTH)?;
+    Ok(())
+}
+
+// ** Section: "UserTotp" **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Queryable, Selectable)]
+#[diesel(table_name = schema::user_totp)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UserTotp {
+    pub user_id: i32,
+    pub secret: String, // max_len=64 (base32)
+    pub is_enabled: bool,
+    pub recovery_codes: Vec<String>, // hashes of recovery codes
+    pub last_step: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl UserTotp {
+    pub fn new(user_id: i32, secret: &str, is_enabled: bool) -> UserTotp {
+        let now = Utc::now();
+        UserTotp {
+            user_id,
+            secret: secret.to_owned(),
+            is_enabled,
+            recovery_codes: vec![],
+            last_step: None,
+            created_at: now.clone(),
+            updated_at: now.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Insertable)]
+#[diesel(table_name = schema::user_totp)]
+pub struct CreateUserTotp {
+    pub user_id: i32,
+    pub secret: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, AsChangeset)]
+#[diesel(table_name = schema::user_totp)]
+pub struct ModifyUserTotp {
+    pub is_enabled: Option<bool>,
+    pub recovery_codes: Option<Vec<String>>,
+    pub last_step: Option<Option<i64>>,
+}
+
+// ** Used: in "user_totp_controller::post_totp(). **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpSetupResponseDto {
+    // Secret key (base32) for manual entry into the authenticator application.
+    pub secret: String,
+    // Key URI ("otpauth://totp/...") for the authenticator application.
+    pub otpauth_uri: String,
+}
+
+// ** Used: in "user_totp_controller::put_totp(), delete_totp(). **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpCodeDto {
+    // One-time code (TOTP) or recovery code.
+    pub code: String,
+}
+
+impl Validator for TotpCodeDto {
+    // Check the model against the required conditions.
+    fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors: Vec<Option<ValidationError>> = vec![];
+
+        errors.push(validate_code(&self.code).err());
+
+        self.filter_errors(errors)
+    }
+}
+
+// ** Used: in "user_totp_controller::put_totp(). **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpRecoveryCodesDto {
+    // One-time recovery codes (shown only once).
+    pub recovery_codes: Vec<String>,
+}
+
+// ** Used: in "user_authent_controller::login(). **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginChallengeResponseDto {
+    // Token for the second step of login (confirmation with a one-time code).
+    pub challenge_token: String,
+}
+
+// ** Used: in "user_authent_controller::login_totp(). **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginTotpDto {
+    pub challenge_token: String,
+    // One-time code (TOTP) or recovery code.
+    pub code: String,
+    // Device label (for the list of sessions).
+    #[serde(default)]
+    pub device: Option<String>,
+}
+
+impl Validator for LoginTotpDto {
+    // Check the model against the required conditions.
+    fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors: Vec<Option<ValidationError>> = vec![];
+
+        errors.push(validate_code(&self.code).err());
+        if let Some(device) = &self.device {
+            errors.push(user_models::validate_device(device).err());
+        }
+
+        self.filter_errors(errors)
+    }
+}
@@ -0,0 +1,278 @@
+use vrb_dbase::dbase::DbPool;
+
+use crate::user_totp_models::{CreateUserTotp, ModifyUserTotp, UserTotp};
+
+pub trait UserTotpOrm {
+    /// Get an entity (user_totp) by user ID.
+    fn get_user_totp(&self, user_id: i32) -> Result<Option<UserTotp>, String>;
+    /// Add a new entity (user_totp) or replace the secret key of an existing one (the entity becomes disabled).
+    fn create_user_totp(&self, create_user_totp: CreateUserTotp) -> Result<UserTotp, String>;
+    /// Modify an entity (user_totp).
+    fn modify_user_totp(&self, user_id: i32, modify_user_totp: ModifyUserTotp) -> Result<Option<UserTotp>, String>;
+    /// Modify an enabled entity (user_totp) if its "last_step" and "recovery_codes" have not been changed.
+    #[rustfmt::skip]
+    fn use_user_totp_code(
+        &self, user_id: i32, old_last_step: Option<i64>, old_recovery_codes: Vec<String>, modify_user_totp: ModifyUserTotp,
+    ) -> Result<Option<UserTotp>, String>;
+    /// Delete an entity (user_totp).
+    fn delete_user_totp(&self, user_id: i32) -> Result<usize, String>;
+}
+
+#[cfg(not(all(test, feature = "mockdata")))]
+pub fn get_user_totp_orm_app(pool: DbPool) -> impls::UserTotpOrmApp {
+    impls::UserTotpOrmApp::new(pool)
+}
+#[cfg(all(test, feature = "mockdata"))]
+pub fn get_user_totp_orm_app(_: DbPool) -> tests::UserTotpOrmApp {
+    tests::UserTotpOrmApp::new()
+}
+
+#[cfg(not(all(test, feature = "mockdata")))]
+pub mod impls {
+    use std::time::Instant as tm;
+
+    use diesel::{self, prelude::*, upsert::excluded};
+    use log::{Level::Info, info, log_enabled};
+    use vrb_dbase::{
+        dbase,
+        schema::{self, user_totp::dsl},
+    };
+
+    use super::*;
+
+    pub const CONN_POOL: &str = "ConnectionPool";
+
+    #[derive(Debug, Clone)]
+    pub struct UserTotpOrmApp {
+        pub pool: dbase::DbPool,
+    }
+
+    impl UserTotpOrmApp {
+        pub fn new(pool: dbase::DbPool) -> Self {
+            UserTotpOrmApp { pool }
+        }
+        pub fn get_conn(&self) -> Result<dbase::DbPooledConnection, String> {
+            (&self.pool).get().map_err(|e| format!("{CONN_POOL}: {}", e.to_string()))
+        }
+    }
+
+    impl UserTotpOrm for UserTotpOrmApp {
+        /// Get an entity (user_totp) by user ID.
+        fn get_user_totp(&self, user_id: i32) -> Result<Option<UserTotp>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+            // Run query using Diesel to find the entry by user_id and return it.
+            let result = schema::user_totp::table
+                .filter(dsl::user_id.eq(user_id))
+                .first::<UserTotp>(&mut conn)
+                .optional()
+                .map_err(|e| format!("get_user_totp: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("get_user_totp() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(result)
+        }
+
+        /// Add a new entity (user_totp) or replace the secret key of an existing one (the entity becomes disabled).
+        fn create_user_totp(&self, create_user_totp: CreateUserTotp) -> Result<UserTotp, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+            let empty_codes: Vec<String> = vec![];
+            // Run query using Diesel to add a new entry or to reset an existing one.
+            let user_totp: UserTotp = diesel::insert_into(schema::user_totp::table)
+                .values(create_user_totp)
+                .on_conflict(dsl::user_id)
+                .do_update()
+                .set((
+                    dsl::secret.eq(excluded(dsl::secret)),
+                    dsl::is_enabled.eq(false),
+                    dsl::recovery_codes.eq(empty_codes),
+                    dsl::last_step.eq(None::<i64>),
+                ))
+                .returning(UserTotp::as_returning())
+                .get_result(&mut conn)
+                .map_err(|e| format!("create_user_totp: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("create_user_totp() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(user_totp)
+        }
+
+        /// Modify an entity (user_totp).
+        fn modify_user_totp(&self, user_id: i32, modify_user_totp: ModifyUserTotp) -> Result<Option<UserTotp>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+            // Run query using Diesel to partially modify the entry.
+            let result = diesel::update(dsl::user_totp.find(user_id))
+                .set(&modify_user_totp)
+                .returning(UserTotp::as_returning())
+                .get_result(&mut conn)
+                .optional()
+                .map_err(|e| format!("modify_user_totp: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("modify_user_totp() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(result)
+        }
+
+        /// Modify an enabled entity (user_totp) if its "last_step" and "recovery_codes" have not been changed.
+        #[rustfmt::skip]
+        fn use_user_totp_code(
+            &self, user_id: i32, old_last_step: Option<i64>, old_recovery_codes: Vec<String>, modify_user_totp: ModifyUserTotp,
+        ) -> Result<Option<UserTotp>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+            // Run query using Diesel to modify the entry (if the code has not already been used by another request).
+            let result = diesel::update(
+                dsl::user_totp
+                    .filter(dsl::user_id.eq(user_id))
+                    .filter(dsl::is_enabled.eq(true))
+                    .filter(dsl::last_step.is_not_distinct_from(old_last_step))
+                    .filter(dsl::recovery_codes.eq(old_recovery_codes)),
+            )
+            .set(&modify_user_totp)
+            .returning(UserTotp::as_returning())
+            .get_result(&mut conn)
+            .optional()
+            .map_err(|e| format!("use_user_totp_code: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("use_user_totp_code() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(result)
+        }
+
+        /// Delete an entity (user_totp).
+        fn delete_user_totp(&self, user_id: i32) -> Result<usize, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+            // Run query using Diesel to delete the entry (user_totp).
+            let count: usize = diesel::delete(dsl::user_totp.find(user_id))
+                .execute(&mut conn)
+                .map_err(|e| format!("delete_user_totp: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("delete_user_totp() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(count)
+        }
+    }
+}
+
+#[cfg(any(test, feature = "mockdata"))]
+pub mod tests {
+    use actix_web::web;
+    use chrono::Utc;
+
+    use crate::user_totp_models::{CreateUserTotp, ModifyUserTotp, UserTotp};
+
+    use super::UserTotpOrm;
+
+    pub const TOTP_SECRET: &str = "JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP";
+
+    #[derive(Debug, Clone)]
+    pub struct UserTotpOrmApp {
+        pub user_totp_vec: Vec<UserTotp>,
+    }
+
+    impl UserTotpOrmApp {
+        /// Create a new instance.
+        pub fn new() -> Self {
+            UserTotpOrmApp { user_totp_vec: Vec::new() }
+        }
+        /// Create a new instance with the specified list of entities (user_totp).
+        pub fn create(user_totp_vec: &[UserTotp]) -> Self {
+            UserTotpOrmApp {
+                user_totp_vec: user_totp_vec.to_vec(),
+            }
+        }
+    }
+
+    impl UserTotpOrm for UserTotpOrmApp {
+        /// Get an entity (user_totp) by user ID.
+        fn get_user_totp(&self, user_id: i32) -> Result<Option<UserTotp>, String> {
+            let result = self.user_totp_vec.iter().find(|user_totp| user_totp.user_id == user_id).cloned();
+            Ok(result)
+        }
+
+        /// Add a new entity (user_totp) or replace the secret key of an existing one (the entity becomes disabled).
+        fn create_user_totp(&self, create_user_totp: CreateUserTotp) -> Result<UserTotp, String> {
+            let opt_user_totp = self.get_user_totp(create_user_totp.user_id)?;
+            let user_totp = match opt_user_totp {
+                Some(mut user_totp) => {
+                    user_totp.secret = create_user_totp.secret;
+                    user_totp.is_enabled = false;
+                    user_totp.recovery_codes = vec![];
+                    user_totp.last_step = None;
+                    user_totp.updated_at = Utc::now();
+                    user_totp
+                }
+                None => UserTotp::new(create_user_totp.user_id, &create_user_totp.secret, false),
+            };
+            Ok(user_totp)
+        }
+
+        /// Modify an entity (user_totp).
+        fn modify_user_totp(&self, user_id: i32, modify_user_totp: ModifyUserTotp) -> Result<Option<UserTotp>, String> {
+            let opt_user_totp = self.get_user_totp(user_id)?.map(|mut user_totp| {
+                if let Some(is_enabled) = modify_user_totp.is_enabled {
+                    user_totp.is_enabled = is_enabled;
+                }
+                if let Some(recovery_codes) = modify_user_totp.recovery_codes {
+                    user_totp.recovery_codes = recovery_codes;
+                }
+                if let Some(last_step) = modify_user_totp.last_step {
+                    user_totp.last_step = last_step;
+                }
+                user_totp.updated_at = Utc::now();
+                user_totp
+            });
+            Ok(opt_user_totp)
+        }
+
+        /// Modify an enabled entity (user_totp) if its "last_step" and "recovery_codes" have not been changed.
+        #[rustfmt::skip]
+        fn use_user_totp_code(
+            &self, user_id: i32, old_last_step: Option<i64>, old_recovery_codes: Vec<String>, modify_user_totp: ModifyUserTotp,
+        ) -> Result<Option<UserTotp>, String> {
+            let is_unchanged = self.get_user_totp(user_id)?
+                .map(|user_totp| user_totp.is_enabled && user_totp.last_step == old_last_step && user_totp.recovery_codes == old_recovery_codes)
+                .unwrap_or(false);
+            if !is_unchanged {
+                return Ok(None);
+            }
+            self.modify_user_totp(user_id, modify_user_totp)
+        }
+
+        /// Delete an entity (user_totp).
+        fn delete_user_totp(&self, user_id: i32) -> Result<usize, String> {
+            let count = self.user_totp_vec.iter().filter(|user_totp| user_totp.user_id == user_id).count();
+            Ok(count)
+        }
+    }
+
+    pub struct UserTotpOrmTest {}
+
+    impl UserTotpOrmTest {
+        /// Create an entity (user_totp) with the test secret key.
+        pub fn user_totp(user_id: i32, is_enabled: bool, recovery_codes: &[String]) -> UserTotp {
+            let mut user_totp = UserTotp::new(user_id, TOTP_SECRET, is_enabled);
+            user_totp.recovery_codes = recovery_codes.to_vec();
+            user_totp
+        }
+        pub fn cfg_user_totp_orm(user_totp_vec: Vec<UserTotp>) -> impl FnOnce(&mut web::ServiceConfig) {
+            move |config: &mut web::ServiceConfig| {
+                let data_user_totp_orm = web::Data::new(UserTotpOrmApp::create(&user_totp_vec));
+                config.app_data(web::Data::clone(&data_user_totp_orm));
+            }
+        }
+    }
+}
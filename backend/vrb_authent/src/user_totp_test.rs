@@ -0,0 +1,365 @@
+#[cfg(all(test, feature = "mockdata"))]
+mod tests {
+    use actix_web::{
+        App, body, dev,
+        http::StatusCode,
+        http::header::{CONTENT_TYPE, HeaderValue},
+        test,
+    };
+    use chrono::Utc;
+    use vrb_common::{
+        api_error::{ApiError, code_to_str},
+        err,
+    };
+    use vrb_tools::{config_app, hash_tools, totp_tools};
+
+    use crate::{
+        attempt_limiter, config_jwt,
+        user_orm::tests::{USER, USER1_ID, UserOrmTest},
+        user_totp_controller::{delete_totp, get_totp_qr_code, post_totp, put_totp, tests as TotpCtrlTest},
+        user_totp_models::{self, TotpCodeDto, TotpRecoveryCodesDto, TotpSetupResponseDto},
+        user_totp_orm::tests::{TOTP_SECRET, UserTotpOrmTest},
+    };
+
+    const MSG_ERROR_WAS_EXPECTED: &str = "Service call succeeded, but an error was expected.";
+    const MSG_FAILED_TO_DESER: &str = "Failed to deserialize JSON string";
+    const MSG_FAILED_DESER: &str = "Failed to deserialize response from JSON.";
+
+    fn get_totp_code() -> String {
+        let time_step = totp_tools::get_time_step(Utc::now().timestamp());
+        totp_tools::generate_code(TOTP_SECRET, time_step).unwrap()
+    }
+    fn get_wrong_totp_code() -> String {
+        format!("{:06}", (get_totp_code().parse::<u32>().unwrap() + 500_000) % 1_000_000)
+    }
+
+    // ** post_totp **
+
+    #[actix_web::test]
+    async fn test_post_totp_missing_token() {
+        let data_u = UserOrmTest::users(&[USER]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_totp)
+                .configure(TotpCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
+        ).await;
+        let req = test::TestRequest::post().uri("/api/totp").to_request();
+        let result = test::try_call_service(&app, req).await.err();
+        let err = result.expect(MSG_ERROR_WAS_EXPECTED);
+
+        let actual_status = err.as_response_error().status_code();
+        assert_eq!(actual_status, StatusCode::UNAUTHORIZED); // 401(a)
+
+        let api_err: ApiError = serde_json::from_str(&err.to_string()).expect(MSG_FAILED_TO_DESER);
+        assert_eq!(api_err.code, code_to_str(StatusCode::UNAUTHORIZED));
+        assert_eq!(api_err.message, err::MSG_MISSING_TOKEN);
+    }
+    #[actix_web::test]
+    async fn test_post_totp_already_enabled() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let user_totp1 = UserTotpOrmTest::user_totp(USER1_ID, true, &[]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_totp)
+                .configure(TotpCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![user_totp1]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/totp")
+            .insert_header(TotpCtrlTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT); // 409
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::CONFLICT));
+        assert_eq!(app_err.message, format!("{}; user_id: {}", err::MSG_TOTP_ALREADY_ENABLED, USER1_ID));
+    }
+    #[actix_web::test]
+    async fn test_post_totp_valid_token() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let nickname = data_u.0.get(0).unwrap().nickname.clone();
+        let config_app = config_app::get_test_config();
+        let app_name = config_app.app_name.clone();
+        // The pending setup is replaced with a new secret key.
+        let user_totp1 = UserTotpOrmTest::user_totp(USER1_ID, false, &[]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_totp)
+                .configure(TotpCtrlTest::cfg_config_app(config_app))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![user_totp1]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/totp")
+            .insert_header(TotpCtrlTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED); // 201
+
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let setup_resp: TotpSetupResponseDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_ne!(setup_resp.secret, TOTP_SECRET);
+        assert_eq!(totp_tools::base32_decode(&setup_resp.secret).unwrap().len(), totp_tools::SECRET_LENGTH);
+        #[rustfmt::skip]
+        assert_eq!(setup_resp.otpauth_uri, totp_tools::get_otpauth_uri(&app_name, &nickname, &setup_resp.secret));
+    }
+
+    // ** get_totp_qr_code **
+
+    #[actix_web::test]
+    async fn test_get_totp_qr_code_not_found() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_totp_qr_code)
+                .configure(TotpCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri("/api/totp/qr_code")
+            .insert_header(TotpCtrlTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND); // 404
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.message, format!("{}; user_id: {}", err::MSG_TOTP_NOT_FOUND, USER1_ID));
+    }
+    #[actix_web::test]
+    async fn test_get_totp_qr_code_valid_token() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let user_totp1 = UserTotpOrmTest::user_totp(USER1_ID, false, &[]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_totp_qr_code)
+                .configure(TotpCtrlTest::cfg_config_app(config_app::get_test_config()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![user_totp1]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri("/api/totp/qr_code")
+            .insert_header(TotpCtrlTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("image/png"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        // PNG file signature.
+        assert!(body.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]));
+    }
+
+    // ** put_totp **
+
+    #[actix_web::test]
+    async fn test_put_totp_invalid_dto_code_empty() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let user_totp1 = UserTotpOrmTest::user_totp(USER1_ID, false, &[]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_totp)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![user_totp1]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri("/api/totp")
+            .insert_header(TotpCtrlTest::header_auth(&token1))
+            .set_json(TotpCodeDto { code: "".to_owned() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err_vec: Vec<ApiError> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err_vec.len(), 1);
+        assert_eq!(app_err_vec[0].message, user_totp_models::MSG_CODE_REQUIRED);
+    }
+    #[actix_web::test]
+    async fn test_put_totp_not_found() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_totp)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri("/api/totp")
+            .insert_header(TotpCtrlTest::header_auth(&token1))
+            .set_json(TotpCodeDto { code: get_totp_code() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND); // 404
+    }
+    #[actix_web::test]
+    async fn test_put_totp_code_incorrect() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let user_totp1 = UserTotpOrmTest::user_totp(USER1_ID, false, &[]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_totp)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![user_totp1]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri("/api/totp")
+            .insert_header(TotpCtrlTest::header_auth(&token1))
+            .set_json(TotpCodeDto { code: get_wrong_totp_code() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED); // 401
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.message, err::MSG_TOTP_CODE_INCORRECT);
+    }
+    #[actix_web::test]
+    async fn test_put_totp_valid_code() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let user_totp1 = UserTotpOrmTest::user_totp(USER1_ID, false, &[]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_totp)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![user_totp1]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri("/api/totp")
+            .insert_header(TotpCtrlTest::header_auth(&token1))
+            .set_json(TotpCodeDto { code: get_totp_code() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let codes_resp: TotpRecoveryCodesDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(codes_resp.recovery_codes.len(), totp_tools::RECOVERY_CODE_COUNT);
+    }
+
+    // ** delete_totp **
+
+    #[actix_web::test]
+    async fn test_delete_totp_not_enabled() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let user_totp1 = UserTotpOrmTest::user_totp(USER1_ID, false, &[]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(delete_totp)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![user_totp1]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::delete().uri("/api/totp")
+            .insert_header(TotpCtrlTest::header_auth(&token1))
+            .set_json(TotpCodeDto { code: get_totp_code() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND); // 404
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.message, format!("{}; user_id: {}", err::MSG_TOTP_NOT_FOUND, USER1_ID));
+    }
+    #[actix_web::test]
+    async fn test_delete_totp_code_incorrect() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let recovery_codes = vec![hash_tools::encode_hash("k7fh2qpx9m").unwrap()];
+        let user_totp1 = UserTotpOrmTest::user_totp(USER1_ID, true, &recovery_codes);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(delete_totp)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![user_totp1]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::delete().uri("/api/totp")
+            .insert_header(TotpCtrlTest::header_auth(&token1))
+            .set_json(TotpCodeDto { code: "w3nzr8tc4d".to_owned() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED); // 401
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.message, err::MSG_TOTP_CODE_INCORRECT);
+    }
+    #[actix_web::test]
+    async fn test_delete_totp_valid_code() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let user_totp1 = UserTotpOrmTest::user_totp(USER1_ID, true, &[]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(delete_totp)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![user_totp1]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::delete().uri("/api/totp")
+            .insert_header(TotpCtrlTest::header_auth(&token1))
+            .set_json(TotpCodeDto { code: get_totp_code() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+    }
+    #[actix_web::test]
+    async fn test_delete_totp_valid_recovery_code() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let recovery_codes = vec![hash_tools::encode_hash("k7fh2qpx9m").unwrap()];
+        let user_totp1 = UserTotpOrmTest::user_totp(USER1_ID, true, &recovery_codes);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(delete_totp)
+                .configure(attempt_limiter::tests::cfg_attempt_limiter(attempt_limiter::tests::get_attempt_limiter()))
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(UserTotpOrmTest::cfg_user_totp_orm(vec![user_totp1]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::delete().uri("/api/totp")
+            .insert_header(TotpCtrlTest::header_auth(&token1))
+            .set_json(TotpCodeDto { code: "k7fh2qpx9m".to_owned() })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+    }
+}
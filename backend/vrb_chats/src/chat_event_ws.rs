@@ -4,8 +4,9 @@ use chrono::SecondsFormat;
 use serde::{Deserialize, Serialize};
 use serde_json;
 use utoipa::ToSchema;
+use vrb_dbase::enm_stream_state::StreamState;
 
-use crate::chat_message_models::ChatMessage;
+use crate::chat_message_models::{ChatMessage, ChatMessageReactionDelta, ChatPin, reply_snippet};
 
 pub const MISSING_STARTING_CURLY_BRACE: &str = "Serialization: missing \"{\".";
 pub const MISSING_ENDING_CURLY_BRACE: &str = "Serialization: missing \"}\".";
@@ -17,41 +18,55 @@ pub const SERIALIZATION: &str = "Serialization: ";
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub enum EWSType {
+    Backlog,
     Block,
     Count,
     Echo,
     Err,
     Join,
     Leave,
+    Mention,
     Msg,
     MsgCut,
     MsgPut,
     MsgRmv,
     Name,
+    Pin,
     PrmBool,
     PrmInt,
     PrmStr,
+    Reaction,
+    SlowMode,
+    Stream,
     Unblock,
+    Unpin,
 }
 
 impl EWSType {
     pub fn iterator() -> Iter<'static, EWSType> {
-        static LIST: [EWSType; 15] = [
+        static LIST: [EWSType; 22] = [
+            EWSType::Backlog,
             EWSType::Block,
             EWSType::Count,
             EWSType::Echo,
             EWSType::Err,
             EWSType::Join,
             EWSType::Leave,
+            EWSType::Mention,
             EWSType::Msg,
             EWSType::MsgCut,
             EWSType::MsgPut,
             EWSType::MsgRmv,
             EWSType::Name,
+            EWSType::Pin,
             EWSType::PrmBool,
             EWSType::PrmInt,
             EWSType::PrmStr,
+            EWSType::Reaction,
+            EWSType::SlowMode,
+            EWSType::Stream,
             EWSType::Unblock,
+            EWSType::Unpin,
         ];
         LIST.iter()
     }
@@ -173,12 +188,28 @@ fn parse_json_to_i32(json_value: &serde_json::Value) -> Result<i32, &'static str
 
 // ** **
 
+// ** Send the last messages of the room to the client as a single batch (in ascending order of date). **
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BacklogEWS {
+    pub backlog: Vec<MsgEWS>,
+}
+
+impl BacklogEWS {
+    pub fn new(chat_messages: Vec<ChatMessage>) -> Self {
+        let backlog = chat_messages.into_iter().map(MsgEWS::from).collect();
+        BacklogEWS { backlog }
+    }
+}
+
 // ** Block clients in a room by name. **
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BlockEWS {
     pub block: String,
     pub is_in_chat: bool, // The user is in chat now.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_until: Option<String>, // DateTime<Utc> The end of a timed block.
 }
 
 // ** Count of clients in the room. **
@@ -226,6 +257,10 @@ pub struct JoinEWS {
     pub is_owner: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub is_blocked: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_moderator: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pins: Option<Vec<PinEWS>>, // Pinned messages and announcements of the room.
 }
 
 // ** Leave the client from the chat room. **
@@ -237,6 +272,29 @@ pub struct LeaveEWS {
     pub count: usize,
 }
 
+// ** Send a notification about the mention to all sessions of the mentioned user. **
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MentionEWS {
+    pub mention: i32,   // Chat message ID.
+    pub stream_id: i32, // Chat (stream) ID.
+    pub member: String, // The nickname of the author of the message.
+    pub msg: String,
+    pub date: String, // DateTime<Utc>
+}
+
+impl From<ChatMessage> for MentionEWS {
+    fn from(chat_message: ChatMessage) -> Self {
+        MentionEWS {
+            mention: chat_message.id,
+            stream_id: chat_message.stream_id,
+            member: chat_message.user_name.clone(),
+            msg: chat_message.msg.unwrap_or_default(),
+            date: chat_message.date_created.to_rfc3339_opts(SecondsFormat::Millis, true),
+        }
+    }
+}
+
 // ** Send a text message to all clients in the room. **
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -247,6 +305,14 @@ pub struct MsgEWS {
     pub date: String,             // DateTime<Utc>
     pub date_edt: Option<String>, // DateTime<Utc>
     pub date_rmv: Option<String>, // DateTime<Utc>
+    #[serde(default)]
+    pub cnt_edt: i32, // The number of entries in the edit history.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<i32>, // ID of the parent message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_member: Option<String>, // Nickname of the author of the parent message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_msg: Option<String>, // Fragment of the text of the parent message.
 }
 
 impl From<ChatMessage> for MsgEWS {
@@ -258,10 +324,24 @@ impl From<ChatMessage> for MsgEWS {
             date: chat_message.date_created.to_rfc3339_opts(SecondsFormat::Millis, true),
             date_edt: chat_message.date_changed.map(|v| v.to_rfc3339_opts(SecondsFormat::Millis, true)),
             date_rmv: chat_message.date_removed.map(|v| v.to_rfc3339_opts(SecondsFormat::Millis, true)),
+            cnt_edt: chat_message.cnt_logs,
+            reply_to: chat_message.reply_to,
+            reply_member: chat_message.reply_user_name.clone(),
+            reply_msg: chat_message.reply_msg.map(|v| reply_snippet(&v)),
         }
     }
 }
 
+impl MsgEWS {
+    /// Get the message ID from the text of the "msg" event (for other events - None).
+    pub fn get_id(event: &str) -> Option<i32> {
+        if !event.starts_with("{\"msg\":") {
+            return None;
+        }
+        serde_json::from_str::<MsgEWS>(event).ok().map(|v| v.id)
+    }
+}
+
 // ** Send a message about deleting text to all chat members. **
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -291,6 +371,30 @@ pub struct NameEWS {
     pub name: String, // user_name
 }
 
+// ** Send a pinned message (or an announcement) to all chat members. **
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PinEWS {
+    pub pin: String,    // Text of the message (or of the announcement).
+    pub id: i32,        // Pin ID.
+    pub member: String, // Nickname of the author of the message (or of the announcement).
+    pub date: String,   // DateTime<Utc>
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub msg_id: Option<i32>, // ID of the pinned message. (None - the pin is an announcement.)
+}
+
+impl From<ChatPin> for PinEWS {
+    fn from(chat_pin: ChatPin) -> Self {
+        PinEWS {
+            pin: chat_pin.msg.clone(),
+            id: chat_pin.id,
+            member: chat_pin.user_name.clone(),
+            date: chat_pin.date_created.to_rfc3339_opts(SecondsFormat::Millis, true),
+            msg_id: chat_pin.chat_message_id,
+        }
+    }
+}
+
 // ** Send a parameter with the name and type boolean. **
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -321,6 +425,52 @@ pub struct PrmStrEWS {
     pub is_owner: Option<bool>, // Indicates that the chat was sent by the owner.
 }
 
+// ** Send the change in the number of reactions to the message to all chat members. **
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReactionEWS {
+    pub reaction: String, // Emoji of the reaction.
+    pub id: i32,          // Chat message ID.
+    pub count: i32,       // The number of reactions with this emoji to the message.
+    pub delta: i32,       // 1 - the reaction has been added, -1 - the reaction has been removed.
+    pub member: String,   // Nickname of the user who changed the reaction.
+}
+
+impl ReactionEWS {
+    #[rustfmt::skip]
+    pub fn new(reaction_delta: ChatMessageReactionDelta, member: String) -> Self {
+        ReactionEWS {
+            reaction: reaction_delta.emoji, id: reaction_delta.chat_message_id, count: reaction_delta.cnt,
+            delta: reaction_delta.delta, member,
+        }
+    }
+}
+
+// ** Set the slow mode of the room (the minimum interval between messages of one user in seconds). **
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SlowModeEWS {
+    pub slow_mode: u32, // 0 - slow mode is disabled.
+}
+
+// ** Send the state of the stream to all members of its chat room. **
+// The metadata (title, description, tags) is not sent, so that the event always fits into the fan-out payload;
+// clients get it with "GET /api/streams/{id}".
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamEWS {
+    pub stream: StreamState, // The state of the stream.
+    pub id: i32,             // Stream ID (chat room ID).
+    pub live: bool,          // The stream is "live" ("preparing", "started", "paused").
+    pub starttime: String,   // DateTime<Utc>
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started: Option<String>, // DateTime<Utc>
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paused: Option<String>, // DateTime<Utc>
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stopped: Option<String>, // DateTime<Utc>
+}
+
 // ** Unblock clients in a room by name. **
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -329,6 +479,13 @@ pub struct UnblockEWS {
     pub is_in_chat: bool, // The user is in chat now.
 }
 
+// ** Send a message about unpinning to all chat members. **
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UnpinEWS {
+    pub unpin: i32, // Pin ID.
+}
+
 // ** **
 
 #[cfg(test)]
@@ -355,4 +512,22 @@ mod tests {
             assert_eq!(res, Some(et.clone()), "EWSType={:?}", et);
         }
     }
+
+    // ** MsgEWS **
+
+    #[test]
+    fn test_msgews_get_id() {
+        #[rustfmt::skip]
+        let msg_ews = MsgEWS {
+            msg: "text".to_string(), id: 12, member: "Oliver_Taylor".to_string(), date: "2030-01-02T10:00:00.000Z".to_string(),
+            date_edt: None, date_rmv: None, cnt_edt: 0, reply_to: None, reply_member: None, reply_msg: None,
+        };
+        assert_eq!(MsgEWS::get_id(&serde_json::to_string(&msg_ews).unwrap()), Some(12));
+        let msg_cut_ews = MsgCutEWS {
+            msg_cut: "".to_string(),
+            id: 12,
+        };
+        assert_eq!(MsgEWS::get_id(&serde_json::to_string(&msg_cut_ews).unwrap()), None);
+        assert_eq!(MsgEWS::get_id("{\"msg\": \"text\"}"), None);
+    }
 }
@@ -1,30 +1,38 @@
+use std::time::Duration;
+
 use actix::prelude::*;
 use actix_web_actors::ws::CloseReason;
+use chrono::{DateTime, Utc};
+
+use crate::chat_ws_fan_out::{ChatFanOut, FanOutEvent};
 
 // ** Blocking client in a room by name. (Session -> Server) **
 #[derive(Debug, Clone, Message)]
 #[rtype(result = "bool")] // is_in_chat
 pub struct BlockClient(
-    pub i32,    // room_id
-    pub String, // client_name
-    pub bool,   // is_block
+    pub i32,                   // room_id
+    pub String,                // client_name
+    pub bool,                  // is_block
+    pub Option<DateTime<Utc>>, // block_until (None - the block is permanent)
 );
 
 // ** Send a block to the client in the room. (Server -> Session) **
 #[derive(Debug, Clone, Message)]
 #[rtype(result = "()")]
 pub struct BlockSsn(
-    pub bool, // is_block
-    pub bool, // is_in_chat
+    pub bool,                  // is_block
+    pub bool,                  // is_in_chat
+    pub Option<DateTime<Utc>>, // block_until (None - the block is permanent)
 );
 
 // ** Blocking a user by their nickname. (Session -> Server) **
 #[derive(Debug, Clone, Message)]
 #[rtype(result = "bool")] // is_in_chat
 pub struct BlockUser(
-    pub i32,    // owner_id
-    pub String, // client_name
-    pub bool,   // is_block
+    pub i32,                   // owner_id
+    pub String,                // client_name
+    pub bool,                  // is_block
+    pub Option<DateTime<Utc>>, // block_until (None - the block is permanent)
 );
 
 // ** Send a chat message to all clients in the room. (Server -> Session) **
@@ -34,6 +42,22 @@ pub struct ChatMsgSsn(
     pub String, // message
 );
 
+// ** Check the slow mode of the room before sending a message. (Session -> Server) **
+#[derive(Debug, Clone, Message)]
+#[rtype(result = "u32")] // The number of seconds to wait before sending (0 - the message can be sent).
+pub struct CheckSlowMode(
+    pub i32, // room_id
+    pub i32, // user_id
+);
+
+// ** Cancel the slow mode check if the message was not created. (Session -> Server) **
+#[derive(Debug, Clone, Message)]
+#[rtype(result = "()")]
+pub struct CancelSlowMode(
+    pub i32, // room_id
+    pub i32, // user_id
+);
+
 // ** Commands that have one handler. (Session -> Server) **
 #[derive(Debug, Clone, Message)]
 #[rtype(result = "()")]
@@ -69,6 +93,21 @@ pub struct LeaveRoom(
     pub String, // client_name
 );
 
+// ** Events of other processes of the server received by the fan-out. (Fan-out -> Server) **
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct ReceiveFanOut(
+    pub Vec<(String, FanOutEvent)>, // Vec<(node_id, event)>
+);
+
+// ** Send a notification about the mention to all sessions of the mentioned users. (Server -> Session) **
+#[derive(Debug, Clone, Message)]
+#[rtype(result = "()")]
+pub struct SendMention(
+    pub Vec<String>, // client_names - nicknames of the mentioned users
+    pub String,      // message
+);
+
 // ** Send a text message to all clients in the room. (Server -> Session) **
 #[derive(Debug, Clone, Message)]
 #[rtype(result = "()")]
@@ -76,3 +115,20 @@ pub struct SendMessage(
     pub i32,    // room_id
     pub String, // message
 );
+
+// ** Set the fan-out of events to other processes of the server. (App -> Server) **
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct SetFanOut(
+    pub Box<dyn ChatFanOut>, // fan_out
+    pub Duration,            // poll - interval for receiving events from other processes
+    pub Duration,            // sync - interval for sending the number of room members to other processes
+);
+
+// ** Set the slow mode of the room. (Session -> Server) **
+#[derive(Debug, Clone, Message)]
+#[rtype(result = "()")]
+pub struct SlowMode(
+    pub i32, // room_id
+    pub u32, // slow_mode - the minimum interval between messages of one user in seconds (0 - disabled)
+);
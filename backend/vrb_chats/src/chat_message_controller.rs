@@ -4,12 +4,13 @@ use std::{borrow::Cow, collections::HashMap, ops::Deref, time::Instant as tm};
 use actix::SystemService;
 use actix_web::{
     HttpResponse, delete, get,
-    http::StatusCode,
+    http::{StatusCode, header::CONTENT_DISPOSITION},
     post, put,
-    web::{self, Query},
+    web::{self, Bytes, Query},
 };
 use chrono::{DateTime, Duration, TimeZone, Utc};
 // use futures_util::FutureExt;
+use futures_util::stream;
 use log::{Level::Info, error, info, log_enabled};
 use serde_json::json;
 use utoipa;
@@ -20,19 +21,25 @@ use vrb_common::{
     validators::{Validator, msg_validation},
 };
 use vrb_dbase::enm_user_role::UserRole;
+use vrb_webhooks::{
+    webhook_dispatcher::{FireWebhookEvent, WebhookDispatcher},
+    webhook_models::WebhookEvent,
+};
 
 #[cfg(not(all(test, feature = "mockdata")))]
 use crate::chat_message_orm::impls::ChatMessageOrmApp;
 #[cfg(all(test, feature = "mockdata"))]
 use crate::chat_message_orm::tests::ChatMessageOrmApp;
 use crate::{
-    chat_message::BlockUser, chat_message_models::{
-        BlockedUser, BlockedUserDto, BlockedUserMini, BlockedUserMiniDto, ChatMessage, ChatMessageDto, CreateBlockedUser, CreateBlockedUserDto, CreateChatMessage, CreateChatMessageDto, DeleteBlockedUser, DeleteBlockedUserDto, MESSAGE_MAX, ModifyChatMessage, ModifyChatMessageDto, SearchChatMessage, SearchChatMessageDto, SortingBlockedUsersDto
-    }, chat_message_orm::ChatMessageOrm, chat_ws_server::ChatWsServer
+    chat_event_ws::MentionEWS, chat_message::{BlockUser, SendMention}, chat_message_models::{
+        self, BlockedUser, BlockedUserDto, BlockedUserMini, BlockedUserMiniDto, ChatMention, ChatMentionDto, ChatMessage, ChatMessageDto, ChatMessageFound, ChatMessageFoundDto, ChatMessageFoundPageDto, ChatMessageLog, ChatMessageLogDto, ChatMessageReactionDto, ChatStreamTimes, ChatTranscriptItemDto, CreateBlockedUser, CreateBlockedUserDto, CreateChatMessage, CreateChatMessageDto, DeleteBlockedUser, DeleteBlockedUserDto, EXPORT_CHAT_PAGE_SIZE, ExportChatMessagesDto, MESSAGE_MAX, ModifyChatMessage, ModifyChatMessageDto, ReadChatMentionsDto, SearchChatMessage, SearchChatMessageDto, SearchChatText, SearchChatTextDto, SearchChatTranscript, SortingBlockedUsersDto, TRANSCRIPT_CSV_HEADER, TranscriptFormat
+    }, chat_message_orm::ChatMessageOrm, chat_ws_assistant::ChatWsAssistant, chat_ws_server::ChatWsServer
 };
 
 // 403 Access denied - insufficient user rights.
 pub const MSG_MODIFY_ANOTHER_USERS_CHAT_MESSAGE: &str = "modify_another_users_chat_message";
+// 403 Access denied - insufficient user rights.
+pub const MSG_VIEW_ANOTHER_USERS_CHAT_MESSAGE_LOGS: &str = "view_another_users_chat_message_logs";
 
 pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
     |config: &mut web::ServiceConfig| {
@@ -45,6 +52,16 @@ pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
             .service(put_chat_message)
             // DELETE /api/chat_messages/{id}
             .service(delete_chat_message)
+            // GET /api/chat_messages/search
+            .service(get_chat_message_search)
+            // GET /api/chat_messages/export
+            .service(get_chat_message_export)
+            // GET /api/chat_messages/{id}/logs
+            .service(get_chat_message_logs)
+            // GET /api/chat_mentions
+            .service(get_chat_mentions)
+            // PUT /api/chat_mentions/read
+            .service(put_chat_mentions_read)
             // GET /api/blocked_users/nicknames/
             .service(get_blocked_users_names)
             // GET /api/blocked_users
@@ -78,7 +95,15 @@ fn get_ch_msgs(start: u16, finish: u16) -> Vec<ChatMessageDto> {
             msg: format!("Demo message {}", idx),
             date_edt: None,
             date_rmv: None,
+            cnt_edt: 0,
+            reply_to: None,
+            reply_member: None,
+            reply_msg: None,
+            reactions: vec![],
         });
+        if idx == 1 {
+            result.last_mut().unwrap().reactions.push(ChatMessageReactionDto::new("👍", 2));
+        }
 
         current = current + Duration::minutes(dlt_minutes);
         if !is_asc && idx == 0 {
@@ -103,12 +128,16 @@ fn get_ch_msgs(start: u16, finish: u16) -> Vec<ChatMessageDto> {
 ///   minDate?: DateTime<Utc>,  // optional - minimum end date for chat message selection; 
 ///   maxDate?: DateTime<Utc>,  // optional - maximum end date of selection of chat messages;
 ///   limit?: number,           // optional - number of records on the page (20 by default);
+///   threadId?: number,        // optional - ID of the root message of the thread;
 /// }
 /// ```
 /// 
 /// For "minDate" the result is strictly greater than the specified date.
 /// For "maxDate" the result is strictly less than the specified date.
 /// 
+/// If "threadId" is specified, then only the root message of the thread and all replies to it
+/// (including replies to replies) are returned.
+/// 
 /// It is recommended to enter the date and time in ISO8601 format.
 /// ```text
 /// var d1 = new Date();
@@ -133,6 +162,11 @@ fn get_ch_msgs(start: u16, finish: u16) -> Vec<ChatMessageDto> {
 /// ```text
 /// curl -i -X GET http://localhost:8080/api/chat_messages?streamId=1&isSortDes=true&minDate=2020-07-01T11:10:00.000Z&limit=20
 /// ```
+///
+/// Or you could call with the next curl (get one thread).
+/// ```text
+/// curl -i -X GET http://localhost:8080/api/chat_messages?streamId=1&threadId=201
+/// ```
 /// Returns the found list of chat messages (Vec<`ChatMessageDto`>) with status 200.
 ///
 /// The structure is returned:
@@ -145,6 +179,16 @@ fn get_ch_msgs(start: u16, finish: u16) -> Vec<ChatMessageDto> {
 ///     msg: String,              // required - chat message text;
 ///     dateEdt?: DateTime<Utc>,  // optional - the date the chat message text was last edited;
 ///     dateRmv?: DateTime<Utc>,  // optional - date the chat message was deleted;
+///     cntEdt: Number,           // required - the number of entries in the edit history;
+///     replyTo?: Number,         // optional - ID of the message being replied to;
+///     replyMember?: String,     // optional - nickname of the author of the message being replied to;
+///     replyMsg?: String,        // optional - fragment of the text of the message being replied to;
+///     reactions?: [             // optional - reactions to the message (if any);
+///       {
+///         emoji: String,        // required - emoji of the reaction;
+///         count: Number,        // required - the number of users who added this reaction;
+///       }
+///     ],
 ///   }
 /// ]
 /// ```
@@ -204,14 +248,20 @@ pub async fn get_chat_message(
     let chat_message_orm2 = chat_message_orm.get_ref().clone();
 
     let res_data = web::block(move || {
-        // Find for an entity (stream event) by SearchStreamEvent.
-        let res_data =
-        chat_message_orm2.filter_chat_messages(search_chat_message)
-        .map_err(|e| {
+        let err_database = |e: String| {
             error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
             ApiError::create(507, err::MSG_DATABASE, &e) // 507
-        });
-        res_data
+        };
+        // Find for an entity (stream event) by SearchStreamEvent.
+        let chat_messages = chat_message_orm2.filter_chat_messages(search_chat_message).map_err(err_database)?;
+        // Get the number of reactions for the found messages.
+        let chat_message_ids: Vec<i32> = chat_messages.iter().map(|v| v.id).collect();
+        let reactions = if !chat_message_ids.is_empty() {
+            chat_message_orm2.filter_chat_message_reactions(&chat_message_ids).map_err(err_database)?
+        } else {
+            vec![]
+        };
+        Ok((chat_messages, reactions))
     })
     .await
     .map_err(|e| {
@@ -219,9 +269,16 @@ pub async fn get_chat_message(
         ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
     })?;
 
-    let chat_messages = match res_data { Ok(v) => v, Err(e) => return Err(e) };
+    let (chat_messages, reactions) = match res_data { Ok(v) => v, Err(e) => return Err(e) };
     let chat_message_dto_list: Vec<ChatMessageDto> = chat_messages.iter()
-        .map(|ch_msg| ChatMessageDto::from(ch_msg.clone()))
+        .map(|ch_msg| {
+            let mut chat_message_dto = ChatMessageDto::from(ch_msg.clone());
+            chat_message_dto.reactions = reactions.iter()
+                .filter(|v| v.chat_message_id == ch_msg.id)
+                .map(|v| ChatMessageReactionDto::from(v.clone()))
+                .collect();
+            chat_message_dto
+        })
         .collect();
 
     if let Some(timer) = timer {
@@ -230,6 +287,409 @@ pub async fn get_chat_message(
     Ok(HttpResponse::Ok().json(chat_message_dto_list)) // 200
 }
 
+/// get_chat_message_search
+///
+/// Search for chat messages by text and (or) by author (page by page).
+///
+/// Request structure:
+/// ```text
+/// {
+///   text?: string,            // optional - search text (words are searched regardless of case);
+///   member?: string,          // optional - nickname of the author of the message;
+///   streamId?: number,        // optional - chat ID (Stream ID), search in one stream;
+///   ownerId?: number,         // optional - stream owner ID, search in all streams of the owner;
+///   minDate?: DateTime<Utc>,  // optional - minimum date of the chat message;
+///   maxDate?: DateTime<Utc>,  // optional - maximum date of the chat message;
+///   page?: number,            // optional - page number (1 by default);
+///   limit?: number,           // optional - number of records on the page (20 by default, maximum 100);
+/// }
+/// ```
+///
+/// One of the parameters "text", "member" must be present.
+/// If neither "streamId" nor "ownerId" is specified, the search is carried out in all streams of the current user.
+///
+/// The search is carried out only in the streams that the current user owns or in which the user is a chat moderator.
+/// A user with moderator or administrator rights can search in any stream.
+/// Deleted messages are not searched.
+///
+/// The text supports the syntax of web search engines: "quoted text" - a phrase, "or" - one of the words, "-word" - exclude the word.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X GET http://localhost:8080/api/chat_messages/search?streamId=1&text=hello
+/// ```
+///
+/// Or you could call with the next curl (all streams of the owner, messages of one author).
+/// ```text
+/// curl -i -X GET http://localhost:8080/api/chat_messages/search?ownerId=3&member=ava_wilson&page=2&limit=10
+/// ```
+///
+/// Returns the found page of chat messages (`ChatMessageFoundPageDto`) with status 200.
+/// The messages are sorted by date, starting with the newest.
+///
+/// The structure is returned:
+/// ```text
+/// {
+///   list: [
+///     {
+///       id: Number,               // required - chat message ID;
+///       streamId: Number,         // required - chat ID (Stream ID);
+///       member: String,           // required - nickname of the chat message user;
+///       msg: String,              // required - chat message text;
+///       highlight: String,        // required - chat message text (HTML-escaped) with matches highlighted by the "<mark>" tag;
+///       date: DateTime<Utc>,      // required - date of the chat message;
+///       dateEdt?: DateTime<Utc>,  // optional - the date the chat message text was last edited;
+///     }
+///   ],
+///   limit: Number,                // required - number of records on the page;
+///   count: Number,                // required - total number of found messages;
+///   page: Number,                 // required - page number;
+///   pages: Number,                // required - total number of pages;
+/// }
+/// ```
+///
+/// Date and time are transmitted in ISO8601 format ("2020-01-20T20:10:57.000Z").
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "The result is a page of found chat messages.", body = ChatMessageFoundPageDto,
+            examples(
+            ("1_messages_found" = (summary = "messages found", description = "Messages containing the word \"hello\".
+                `curl -i -X GET http://localhost:8080/api/chat_messages/search?streamId=1&text=hello`",
+                value = json!(ChatMessageFoundPageDto {
+                    list: vec![
+                        ChatMessageFoundDto::from(ChatMessageFound::new(205, 1, 37, "emma_johnson", "Hello everyone",
+                            "<mark>Hello</mark> everyone", Utc::now() + Duration::minutes(-10))),
+                        ChatMessageFoundDto::from(ChatMessageFound::new(201, 1, 42, "ava_wilson", "hello, how are you?",
+                            "<mark>hello</mark>, how are you?", Utc::now() + Duration::minutes(-30))),
+                    ],
+                    limit: 20, count: 2, page: 1, pages: 1 })
+            )),
+            ("2_messages_not_found" = (summary = "messages not found", description = "No messages were found.",
+                value = json!(ChatMessageFoundPageDto { list: vec![], limit: 20, count: 0, page: 1, pages: 0 })
+            )) ),
+        ),
+        (status = 401, description = "An authorization token is required.", body = ApiError,
+            example = json!(ApiError::new(401, err::MSG_MISSING_TOKEN))),
+        (status = 417, body = [ApiError], description =
+            "Validation error. `curl -i -X GET http://localhost:8080/api/chat_messages/search?streamId=1`",
+            example = json!(ApiError::validations(
+                (SearchChatTextDto { text: None, member: None, stream_id: Some(1), owner_id: None, min_date: None, max_date: None,
+                    page: None, limit: None }).validate().err().unwrap()) )),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    security(("bearer_auth" = [])),
+)]
+// GET /api/chat_messages/search
+#[rustfmt::skip]
+#[get("/api/chat_messages/search", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn get_chat_message_search(
+    authenticated: Authenticated,
+    chat_message_orm: web::Data<ChatMessageOrmApp>,
+    query_params: web::Query<SearchChatTextDto>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+    // Get current user details.
+    let user = authenticated.deref();
+
+    // Get search parameters.
+    let search_chat_text_dto: SearchChatTextDto = query_params.into_inner();
+
+    // Checking the validity of the data model.
+    let validation_res = search_chat_text_dto.validate();
+    if let Err(validation_errors) = validation_res {
+        error!("{}-{}", code_to_str(StatusCode::EXPECTATION_FAILED), msg_validation(&validation_errors));
+        return Ok(ApiError::to_response(&ApiError::validations(validation_errors))); // 417
+    }
+
+    // A user with chat moderation rights can search in any stream.
+    let viewer_id = if ChatWsAssistant::is_moderator(&user.role) { None } else { Some(user.id) };
+    let search_chat_text = SearchChatText::convert(search_chat_text_dto, user.id, viewer_id);
+    let page = search_chat_text.page;
+    let limit = search_chat_text.limit;
+
+    let chat_message_orm2 = chat_message_orm.get_ref().clone();
+    let res_data = web::block(move || {
+        // Search for chat messages by the specified parameters.
+        let res_data1 = chat_message_orm2
+            .search_chat_messages(search_chat_text)
+            .map_err(|e| {
+                error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+                ApiError::create(507, err::MSG_DATABASE, &e) // 507
+            });
+        res_data1
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })?;
+
+    let (count, chat_messages) = res_data?;
+    let list: Vec<ChatMessageFoundDto> = chat_messages.into_iter().map(ChatMessageFoundDto::from).collect();
+    let pages: u32 = count / limit + if (count % limit) > 0 { 1 } else { 0 };
+
+    let result = ChatMessageFoundPageDto { list, limit, count, page, pages };
+
+    if let Some(timer) = timer {
+        info!("get_chat_message_search() time: {}", format!("{:.2?}", timer.elapsed()));
+    }
+    Ok(HttpResponse::Ok().json(result)) // 200
+}
+
+/// get_chat_message_export
+///
+/// Download the transcript of the stream chat in JSON, CSV or text format.
+///
+/// Request structure:
+/// ```text
+/// {
+///   streamId: number,       // required - chat ID (Stream ID);
+///   format?: string,        // optional - transcript format: "json" (default), "csv", "txt";
+/// }
+/// ```
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X GET http://localhost:8080/api/chat_messages/export?streamId=1&format=csv
+/// ```
+///
+/// The transcript can be downloaded by the owner of the stream, its chat moderators,
+/// as well as by a user with moderator or administrator rights.
+///
+/// The transcript contains all messages of the chat (except deleted ones), sorted by date, starting with the oldest.
+/// The transcript is transmitted in parts, the messages are read from the database page by page.
+/// The time of a message ("time") is given relative to the beginning of the stream (`streams.started`)
+/// in the format "HH:MM:SS", excluding the time when the stream was paused.
+/// If the stream has not started yet, the time is given relative to its scheduled start time.
+/// A message sent before the beginning of the stream has a negative time ("-00:01:05").
+///
+/// Format "json" returns an array of structures:
+/// ```text
+/// [
+///   {
+///     id: Number,               // required - chat message ID;
+///     time: String,             // required - time relative to the beginning of the stream ("HH:MM:SS");
+///     member: String,           // required - nickname of the chat message user;
+///     msg: String,              // required - chat message text;
+///     date: DateTime<Utc>,      // required - date of the chat message;
+///     dateEdt?: DateTime<Utc>,  // optional - the date the chat message text was last edited;
+///   }
+/// ]
+/// ```
+///
+/// Format "csv" returns a table with a header: "id,time,date,member,msg".
+///
+/// Format "txt" returns lines: "[HH:MM:SS] member: msg".
+///
+/// Date and time are transmitted in ISO8601 format ("2020-01-20T20:10:57.000Z").
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "The transcript of the stream chat (the format depends on the \"format\" parameter).",
+            body = Vec<ChatTranscriptItemDto>,
+            example = json!([
+                ChatTranscriptItemDto::new(
+                    ChatMessage::new(1, 1, 37, "emma_johnson".to_owned(), Some("Hello everyone".to_owned()),
+                        Utc::now() + Duration::seconds(-125), None, None, 0),
+                    &ChatStreamTimes::new(1, 37, "stopped", Utc::now() + Duration::minutes(-30),
+                        Some(Utc::now() + Duration::minutes(-15)), None, Some(Utc::now()))),
+                ChatTranscriptItemDto::new(
+                    ChatMessage::new(2, 1, 42, "ava_wilson".to_owned(), Some("hello, how are you?".to_owned()),
+                        Utc::now() + Duration::seconds(-64), None, None, 0),
+                    &ChatStreamTimes::new(1, 37, "stopped", Utc::now() + Duration::minutes(-30),
+                        Some(Utc::now() + Duration::minutes(-15)), None, Some(Utc::now()))),
+            ])
+        ),
+        (status = 401, description = "An authorization token is required.", body = ApiError,
+            example = json!(ApiError::new(401, err::MSG_MISSING_TOKEN))),
+        (status = 403, description = "Access denied: insufficient user rights.", body = ApiError,
+            example = json!(ApiError::create(403, err::MSG_STREAM_OWNER_RIGHTS_MISSING, "stream_id: 1, user_id: 42"))),
+        (status = 404, description = "The stream was not found.", body = ApiError,
+            example = json!(ApiError::create(404, err::MSG_STREAM_NOT_FOUND, "stream_id: 1"))),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    security(("bearer_auth" = [])),
+)]
+// GET /api/chat_messages/export
+#[rustfmt::skip]
+#[get("/api/chat_messages/export", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn get_chat_message_export(
+    authenticated: Authenticated,
+    chat_message_orm: web::Data<ChatMessageOrmApp>,
+    query_params: web::Query<ExportChatMessagesDto>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+    // Get current user details.
+    let user = authenticated.deref();
+
+    // Get export parameters.
+    let export_chat_messages_dto: ExportChatMessagesDto = query_params.into_inner();
+    let stream_id = export_chat_messages_dto.stream_id;
+    let format = export_chat_messages_dto.format.unwrap_or(TranscriptFormat::Json);
+
+    // A user with chat moderation rights can download the transcript of any stream.
+    let is_moderator = ChatWsAssistant::is_moderator(&user.role);
+    let user_id = user.id;
+
+    let chat_message_orm2 = chat_message_orm.get_ref().clone();
+    let res_stream_times = web::block(move || {
+        // Get the times of the stream (and the rights of the user to its chat).
+        let res_stream_times1 = chat_message_orm2
+            .get_chat_stream_times(stream_id, Some(user_id))
+            .map_err(|e| {
+                error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+                ApiError::create(507, err::MSG_DATABASE, &e) // 507
+            });
+        res_stream_times1
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })?;
+
+    let Some(stream_times) = res_stream_times? else {
+        let msg = format!("stream_id: {}", stream_id);
+        error!("{}-{}; {}", code_to_str(StatusCode::NOT_FOUND), err::MSG_STREAM_NOT_FOUND, &msg);
+        return Err(ApiError::create(404, err::MSG_STREAM_NOT_FOUND, &msg)); // 404
+    };
+    if !is_moderator && stream_times.stream_owner != user_id && !stream_times.is_moderator {
+        let msg = format!("stream_id: {}, user_id: {}", stream_id, user_id);
+        error!("{}-{}; {}", code_to_str(StatusCode::FORBIDDEN), err::MSG_STREAM_OWNER_RIGHTS_MISSING, &msg);
+        return Err(ApiError::create(403, err::MSG_STREAM_OWNER_RIGHTS_MISSING, &msg)); // 403
+    }
+
+    // The transcript includes the messages sent before the start of the download.
+    let transcript_export = TranscriptExport::new(chat_message_orm.get_ref().clone(), stream_times, format, Utc::now());
+    let body = stream::unfold(transcript_export, |mut transcript_export| async move {
+        let res_part = transcript_export.next_part().await.transpose()?;
+        Some((res_part.map(Bytes::from), transcript_export))
+    });
+    let file_name = format!("chat_{}.{}", stream_id, format.extension());
+
+    if let Some(timer) = timer {
+        info!("get_chat_message_export() time: {}", format!("{:.2?}", timer.elapsed()));
+    }
+    Ok(HttpResponse::Ok() // 200
+        .content_type(format.content_type())
+        .insert_header((CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", file_name)))
+        .streaming(body))
+}
+
+// The stages of the transfer of the chat transcript.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TranscriptStage {
+    Head,
+    Body,
+    Tail,
+    Done,
+}
+
+// The chat transcript, which is transmitted in parts (the messages are read from the database page by page).
+struct TranscriptExport {
+    chat_message_orm: ChatMessageOrmApp,
+    stream_times: ChatStreamTimes,
+    format: TranscriptFormat,
+    max_date: DateTime<Utc>,
+    last_key: Option<(DateTime<Utc>, i32)>, // The (date_created, id) of the last transmitted message.
+    is_empty: bool,                         // No message has been transmitted yet.
+    stage: TranscriptStage,
+}
+
+impl TranscriptExport {
+    fn new(chat_message_orm: ChatMessageOrmApp, stream_times: ChatStreamTimes, format: TranscriptFormat, max_date: DateTime<Utc>) -> Self {
+        TranscriptExport {
+            chat_message_orm,
+            stream_times,
+            format,
+            max_date,
+            last_key: None,
+            is_empty: true,
+            stage: TranscriptStage::Head,
+        }
+    }
+    // Get the next part of the transcript (None - the transcript has been transmitted completely).
+    async fn next_part(&mut self) -> Result<Option<String>, ApiError> {
+        let part = match self.stage {
+            TranscriptStage::Head => {
+                self.stage = TranscriptStage::Body;
+                match self.format {
+                    TranscriptFormat::Json => "[".to_owned(),
+                    TranscriptFormat::Csv => TRANSCRIPT_CSV_HEADER.to_owned(),
+                    TranscriptFormat::Txt => "".to_owned(),
+                }
+            }
+            TranscriptStage::Body => self.next_page().await.inspect_err(|_| self.stage = TranscriptStage::Done)?,
+            TranscriptStage::Tail => {
+                self.stage = TranscriptStage::Done;
+                match self.format {
+                    TranscriptFormat::Json => "]".to_owned(),
+                    _ => "".to_owned(),
+                }
+            }
+            TranscriptStage::Done => return Ok(None),
+        };
+        Ok(Some(part))
+    }
+    // Get the next page of chat messages in the transcript format.
+    async fn next_page(&mut self) -> Result<String, ApiError> {
+        // The page starts after the last transmitted message (messages can have the same date, so the ID is also used).
+        #[rustfmt::skip]
+        let search_chat_transcript = SearchChatTranscript::new(
+            self.stream_times.stream_id, self.last_key, Some(self.max_date), Some(EXPORT_CHAT_PAGE_SIZE));
+
+        let chat_message_orm2 = self.chat_message_orm.clone();
+        let res_chat_messages = web::block(move || {
+            // Get the next page of entities (chat_messages) for the transcript.
+            let res_chat_messages1 = chat_message_orm2.filter_chat_transcript(search_chat_transcript).map_err(|e| {
+                error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+                ApiError::create(507, err::MSG_DATABASE, &e) // 507
+            });
+            res_chat_messages1
+        })
+        .await
+        .map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+            ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+        })?;
+        let chat_messages: Vec<ChatMessage> = res_chat_messages?;
+
+        let is_last_page = chat_messages.len() < EXPORT_CHAT_PAGE_SIZE;
+        let mut result = String::new();
+        for chat_message in chat_messages {
+            self.last_key = Some((chat_message.date_created, chat_message.id));
+            // Deleted messages are not included in the transcript.
+            if chat_message.date_removed.is_some() {
+                continue;
+            }
+            let item = ChatTranscriptItemDto::new(chat_message, &self.stream_times);
+            match self.format {
+                TranscriptFormat::Json => {
+                    result.push_str(if self.is_empty { "\n" } else { ",\n" });
+                    result.push_str(&serde_json::to_string(&item).unwrap_or_default());
+                }
+                TranscriptFormat::Csv => result.push_str(&item.to_csv()),
+                TranscriptFormat::Txt => result.push_str(&item.to_txt()),
+            }
+            self.is_empty = false;
+        }
+        if is_last_page {
+            self.stage = TranscriptStage::Tail;
+            if self.format == TranscriptFormat::Json && !self.is_empty {
+                result.push('\n');
+            }
+        }
+        Ok(result)
+    }
+}
+
 /// post_chat_message
 /// 
 /// Create a new message in the chat.
@@ -239,21 +699,36 @@ pub async fn get_chat_message(
 /// {
 ///   streamId: Number,   // required - stream identifier;
 ///   msg: String,        // required - text of the new message;
+///   replyTo?: Number,   // optional - ID of the message being replied to;
 /// }
 /// ```
 /// 
 /// The minimum length of a new message is 1 character. 
 /// The maximum length of a new message is 255 characters.
 /// 
+/// The message being replied to must be in the same chat and must not be deleted.
+/// If the message being replied to is later deleted, the reply keeps its "replyTo" and "replyMember",
+/// but "replyMsg" is no longer returned.
+/// 
 /// One could call with following curl.
 /// ```text
 /// curl -i -X POST http://localhost:8080/api/chat_messages \
 /// -d '{"streamId": 123, "msg": "mesage1"}' \
 /// -H 'Content-Type: application/json'
 /// ```
+///
+/// Or you could call with the next curl (reply to the message).
+/// ```text
+/// curl -i -X POST http://localhost:8080/api/chat_messages \
+/// -d '{"streamId": 123, "msg": "mesage2", "replyTo": 201}' \
+/// -H 'Content-Type: application/json'
+/// ```
 /// Returns the new message entity (`ChatMessageDto`) with status 200.
 /// The new message is received by all active users of the chat in real time.
 /// 
+/// If the message contains mentions of users ("@nickname"), then each mentioned user receives
+/// a "mention" event in all of their open chat sessions (users who blocked the author are not notified).
+/// 
 /// The structure is returned:
 /// ```text
 /// {
@@ -263,6 +738,10 @@ pub async fn get_chat_message(
 ///   msg: String,              // required - chat message text;
 ///   dateEdt?: DateTime<Utc>,  // optional - the date the chat message text was last edited;
 ///   dateRmv?: DateTime<Utc>,  // optional - date the chat message was deleted;
+///   cntEdt: Number,           // required - the number of entries in the edit history;
+///   replyTo?: Number,         // optional - ID of the message being replied to;
+///   replyMember?: String,     // optional - nickname of the author of the message being replied to;
+///   replyMsg?: String,        // optional - fragment of the text of the message being replied to;
 /// }
 /// ```
 /// 
@@ -272,7 +751,7 @@ pub async fn get_chat_message(
     responses(
         (status = 201, description = "The result of the request is a new chat message.", body = ChatMessageDto,
             example = json!(ChatMessageDto::from(
-            ChatMessage::new(123, 98, 37, "emma_johnson".to_string(), Some("message1".to_string()), Utc::now(), None, None) )) ),
+            ChatMessage::new(123, 98, 37, "emma_johnson".to_string(), Some("message1".to_string()), Utc::now(), None, None, 0) )) ),
         (status = 401, description = "An authorization token is required.", body = ApiError,
             example = json!(ApiError::new(401, err::MSG_MISSING_TOKEN))),
         (status = 406, description = "Error session not found.", body = ApiError,
@@ -282,7 +761,7 @@ pub async fn get_chat_message(
             "Validation error. `curl -i -X POST http://localhost:8080/api/chat_messages 
             -d '{ \"streamId\": 123, \"msg\": \"\" }' -H 'Content-Type: application/json'`",
             example = json!(ApiError::validations(
-                (CreateChatMessageDto { stream_id: 123, msg: "".to_string() }).validate().err().unwrap()) )),
+                (CreateChatMessageDto { stream_id: 123, msg: "".to_string(), reply_to: None }).validate().err().unwrap()) )),
         (status = 506, description = "Blocking error.", body = ApiError, 
             example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
         (status = 507, description = "Database error.", body = ApiError, 
@@ -313,17 +792,30 @@ pub async fn post_chat_message(
 
     let stream_id = create_chat_message_dto.stream_id;
     let msg = create_chat_message_dto.msg.clone();
+    let reply_to = create_chat_message_dto.reply_to;
     
-    let create_chat_message = CreateChatMessage::new(stream_id, user.id, &msg);
+    let create_chat_message = CreateChatMessage::new(stream_id, user.id, &msg, reply_to);
+    // Get the nicknames of the users mentioned in the message ("@nickname").
+    let nicknames = chat_message_models::parse_mentions(&msg);
+    let user_id = user.id;
 
     let chat_message_orm2 = chat_message_orm.get_ref().clone();
     let res_chat_message = web::block(move || {
-        // Add a new entity (stream).
-        let res_chat_message1 = chat_message_orm2.create_chat_message(create_chat_message).map_err(|e| {
+        let err_database = |e: String| {
             error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
             ApiError::create(507, err::MSG_DATABASE, &e) // 507
-        });
-        res_chat_message1
+        };
+        // Add a new entity (stream).
+        let opt_chat_message = chat_message_orm2.create_chat_message(create_chat_message).map_err(err_database)?;
+        let mut user_names: Vec<String> = Vec::new();
+        if let (Some(chat_message), false) = (&opt_chat_message, nicknames.is_empty()) {
+            // Add mentions of users to the message (blocked users cannot mention the user).
+            let mentioned_users = chat_message_orm2
+                .create_chat_message_mentions(chat_message.id, user_id, &nicknames)
+                .map_err(err_database)?;
+            user_names = mentioned_users.into_iter().map(|v| v.nickname).collect();
+        }
+        Ok((opt_chat_message, user_names))
     })
     .await
     .map_err(|e| {
@@ -331,16 +823,30 @@ pub async fn post_chat_message(
         ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
     })?;
 
-    let opt_chat_message_dto = res_chat_message?.map(|v| ChatMessageDto::from(v));
+    let (opt_chat_message, user_names) = res_chat_message?;
+    if let (Some(chat_message), false) = (&opt_chat_message, user_names.is_empty()) {
+        // Send a notification to all sessions of the mentioned users (in any room).
+        let mention_ews = MentionEWS::from(chat_message.clone());
+        ChatWsServer::from_registry().do_send(SendMention(user_names, serde_json::to_string(&mention_ews).unwrap()));
+    }
+    let opt_chat_message_dto = opt_chat_message.map(|v| ChatMessageDto::from(v));
     
     if let Some(timer) = timer {
         info!("post_chat_message() time: {}", format!("{:.2?}", timer.elapsed()));
     }
     if let Some(chat_message_dto) = opt_chat_message_dto {
+        // Notify the webhooks of the owner of the stream (if they are subscribed to chat messages).
+        let data = serde_json::to_value(&chat_message_dto).unwrap();
+        WebhookDispatcher::from_registry().do_send(FireWebhookEvent::for_stream(stream_id, WebhookEvent::ChatMessage, data));
         Ok(HttpResponse::Created().json(chat_message_dto)) // 201
     } else {
-        let json = serde_json::json!({ "stream_id": stream_id, "msg": &msg });
-        let msg = format!("stream_id: {}, msg: \"{}\"",  stream_id, &msg);
+        let mut json = serde_json::json!({ "stream_id": stream_id, "msg": &msg });
+        let mut msg = format!("stream_id: {}, msg: \"{}\"",  stream_id, &msg);
+        if let Some(reply_to) = reply_to {
+            // The message being replied to was not found in this chat (or it was deleted).
+            json["reply_to"] = serde_json::json!(reply_to);
+            msg = format!("{}, reply_to: {}", msg, reply_to);
+        }
         error!("{}-{}; {}", code_to_str(StatusCode::NOT_ACCEPTABLE), err::MSG_PARAMETER_UNACCEPTABLE, &msg);
         Err(ApiError::create(406, err::MSG_PARAMETER_UNACCEPTABLE, &msg) // 406
             .add_param(Cow::Borrowed("invalidParams"), &json))
@@ -381,6 +887,7 @@ fn message_max() -> String {
 ///   msg: String,              // required - chat message text;
 ///   dateEdt?: DateTime<Utc>,  // optional - the date the chat message text was last edited;
 ///   dateRmv?: DateTime<Utc>,  // optional - date the chat message was deleted;
+///   cntEdt: Number,           // required - the number of entries in the edit history;
 /// }
 /// ```
 /// 
@@ -403,13 +910,13 @@ fn message_max() -> String {
                 description = "Update the current user's message. `curl -i -X PUT http://localhost:8080/api/chat_messages/123 
                 -d '{\"msg\": \"mesage2\"} -H 'Content-Type: application/json'`",
                 value = json!(ChatMessageDto::from(ChatMessage::new(123, 98, 37, "emma_johnson".to_string()
-                    , Some("message2".to_string()), Utc::now() + Duration::minutes(-10), Some(Utc::now()), None) ) )
+                    , Some("message2".to_string()), Utc::now() + Duration::minutes(-10), Some(Utc::now()), None, 1) ) )
             )),
             ("msg_some_other_user" = (summary = "Message of some other user. (Admin)",
                 description = "Update another user's message. `curl -i -X PUT http://localhost:8080/api/chat_messages/123?userId=30 
                 -d '{\"msg\": \"mesage2\"} -H 'Content-Type: application/json'`",
                 value = json!(ChatMessageDto::from(ChatMessage::new(123, 98, 30, "robert_brown".to_string()
-                    , Some("message2".to_string()), Utc::now() + Duration::minutes(-10), Some(Utc::now()), None) ) )
+                    , Some("message2".to_string()), Utc::now() + Duration::minutes(-10), Some(Utc::now()), None, 1) ) )
             )) ),
         ),
         (status = 401, description = "An authorization token is required.", body = ApiError,
@@ -495,7 +1002,7 @@ pub async fn put_chat_message(
     let res_chat_message = web::block(move || {
         // Add a new entity (stream).
         let res_chat_message1 = chat_message_orm2
-            .modify_chat_message(id, user_id.clone(), modify_chat_message)
+            .modify_chat_message(id, Some(user_id), modify_chat_message)
             .map_err(|e| {
                 error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
                 ApiError::create(507, err::MSG_DATABASE, &e)
@@ -546,12 +1053,13 @@ pub async fn put_chat_message(
 ///   msg: String,              // required - chat message text;
 ///   dateEdt?: DateTime<Utc>,  // optional - the date the chat message text was last edited;
 ///   dateRmv?: DateTime<Utc>,  // optional - date the chat message was deleted;
+///   cntEdt: Number,           // required - the number of entries in the edit history;
 /// }
 /// ```
 /// 
 /// Date and time are transmitted in ISO8601 format ("2020-01-20T20:10:57.000Z").
 /// 
-/// A user with administrator rights can delete messages from other chat users.
+/// A user with administrator or moderator rights can delete messages from other chat users.
 /// 
 /// One could call with following curl.
 /// ```text
@@ -565,12 +1073,12 @@ pub async fn put_chat_message(
             ("msg_current_user" = (summary = "Message of the current user.",
                 description = "Delete the current user's message. `curl -i -X DELETE http://localhost:8080/api/chat_messages/123`",
                 value = json!(ChatMessageDto::from(ChatMessage::new(123, 98, 37, "emma_johnson".to_string()
-                    , None, Utc::now() + Duration::minutes(-10), None, Some(Utc::now())) ) )
+                    , None, Utc::now() + Duration::minutes(-10), None, Some(Utc::now()), 1) ) )
             )),
-            ("msg_some_other_user" = (summary = "Message of some other user. (Admin)",
+            ("msg_some_other_user" = (summary = "Message of some other user. (Admin, Moderator)",
                 description = "Delete another user's message. `curl -i -X DELETE http://localhost:8080/api/chat_messages/123?userId=30`",
                 value = json!(ChatMessageDto::from(ChatMessage::new(123, 98, 30, "robert_brown".to_string()
-                    , None, Utc::now() + Duration::minutes(-10), None, Some(Utc::now())) ) )
+                    , None, Utc::now() + Duration::minutes(-10), None, Some(Utc::now()), 1) ) )
             )) ),
         ),
         (status = 401, description = "An authorization token is required.", body = ApiError,
@@ -586,7 +1094,7 @@ pub async fn put_chat_message(
                 value = json!(ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED
                     , "`id` - invalid digit found in string (123a)"))    
             )),
-            ("msg_some_other_user" = (summary = "Message of some other user. (Admin)", 
+            ("msg_some_other_user" = (summary = "Message of some other user. (Admin, Moderator)", 
                 description = "Error parsing input parameter. `curl -i -X DELETE http://localhost:8080/api/chat_messages/123?userId=30a`",
                 value = json!(ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED
                     , "`userId` - invalid digit found in string (30a)"))
@@ -621,7 +1129,8 @@ pub async fn delete_chat_message(
         ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg) // 416
     })?;
     
-    if user.role == UserRole::Admin {
+    // Check if the user has chat moderation rights.
+    if ChatWsAssistant::is_moderator(&user.role) {
         let query_params = Query::<HashMap<String, String>>::from_query(request.query_string()).unwrap();
         let user_id1 = query_params.get("userId").map(|v| v.clone()).unwrap_or("".to_string());
         if user_id1.len() > 0 {
@@ -637,7 +1146,7 @@ pub async fn delete_chat_message(
     let res_chat_message = web::block(move || {
         // Add a new entity (stream).
         let res_chat_message1 = chat_message_orm2
-            .delete_chat_message(id, user_id)
+            .delete_chat_message(id, Some(user_id))
             .map_err(|e| {
                 error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
                 ApiError::create(507, err::MSG_DATABASE, &e) // 507
@@ -667,6 +1176,348 @@ pub async fn delete_chat_message(
     }
 }
 
+/// get_chat_message_logs
+///
+/// Get the edit history of the chat message with the specified ID.
+/// This method is available to the author of the message, the stream owner and the chat moderators of the stream,
+/// as well as to users with moderator or administrator rights.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X GET http://localhost:8080/api/chat_messages/123/logs
+/// ```
+///
+/// Returns the list of previous versions of the message text (Vec<`ChatMessageLogDto`>) with status 200.
+/// The list is sorted in the order in which the changes were made.
+/// If the message is not found, status 404 is returned.
+///
+/// The structure is returned:
+/// ```text
+/// [
+///   {
+///     id: Number,                // required - record ID;
+///     chatMessageId: Number,     // required - chat message ID;
+///     oldMsg: String,            // required - the message text before the change;
+///     dateUpdate: DateTime<Utc>, // required - date and time of the change;
+///   }
+/// ]
+/// ```
+///
+/// Date and time are transmitted in ISO8601 format ("2020-01-20T20:10:57.000Z").
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "The edit history of the chat message.", body = Vec<ChatMessageLogDto>,
+            examples(
+            ("1_message_was_edited" = (summary = "message was edited", description = "The message text has been changed.",
+                value = json!([
+                    ChatMessageLogDto::from(ChatMessageLog::new(1, 123, "message1", Utc::now() + Duration::minutes(-20))),
+                    ChatMessageLogDto::from(ChatMessageLog::new(2, 123, "message2", Utc::now() + Duration::minutes(-10))),
+                ])
+            )),
+            ("2_message_was_not_edited" = (summary = "message was not edited",
+                description = "The message text has not been changed.", value = json!([])
+            )) ),
+        ),
+        (status = 401, description = "An authorization token is required.", body = ApiError,
+            example = json!(ApiError::new(401, err::MSG_MISSING_TOKEN))),
+        (status = 403, description = "The user is not the author of the message, the stream owner or a chat moderator.",
+            body = ApiError, example = json!(ApiError::create(403, MSG_VIEW_ANOTHER_USERS_CHAT_MESSAGE_LOGS, "id: 123, user_id: 37"))),
+        (status = 404, description = "The chat message was not found.", body = ApiError,
+            example = json!(ApiError::create(404, err::MSG_CHAT_MESSAGE_NOT_FOUND, "id: 123"))),
+        (status = 416, description = "Error parsing input parameter.", body = ApiError,
+            example = json!(ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED
+                , "`id` - invalid digit found in string (123a)"))),
+        (status = 506, description = "Blocking error.", body = ApiError, 
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError, 
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    params(("id", description = "Unique chat message ID.")),
+    security(("bearer_auth" = [])),
+)]
+// GET /api/chat_messages/{id}/logs
+#[rustfmt::skip]
+#[get("/api/chat_messages/{id}/logs", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn get_chat_message_logs(
+    authenticated: Authenticated,
+    chat_message_orm: web::Data<ChatMessageOrmApp>,
+    request: actix_web::HttpRequest,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+    // Get current user details.
+    let user = authenticated.deref();
+
+    // Get data from request.
+    let id_str = request.match_info().query("id").to_string();
+    let id = parser::parse_i32(&id_str).map_err(|e| {
+        let msg = &format!("`id` - {}", &e);
+        error!("{}-{}; {}", code_to_str(StatusCode::RANGE_NOT_SATISFIABLE), err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg);
+        ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg) // 416
+    })?;
+
+    // A user with chat moderation rights can view the history of any message.
+    let is_moderator = ChatWsAssistant::is_moderator(&user.role);
+    let user_id = user.id;
+
+    let chat_message_orm2 = chat_message_orm.get_ref().clone();
+    let res_chat_message_logs = web::block(move || {
+        let err_database = |e: String| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        };
+        // Get the chat message to check its existence, the author and the stream.
+        let Some(chat_message) = chat_message_orm2.get_chat_message(id).map_err(err_database)? else {
+            let msg = format!("id: {}", id);
+            error!("{}-{}; {}", code_to_str(StatusCode::NOT_FOUND), err::MSG_CHAT_MESSAGE_NOT_FOUND, &msg);
+            return Err(ApiError::create(404, err::MSG_CHAT_MESSAGE_NOT_FOUND, &msg)); // 404
+        };
+        if !is_moderator && chat_message.user_id != user_id {
+            // Check if the user is the owner of the stream or its chat moderator.
+            let opt_chat_access = chat_message_orm2
+                .get_chat_access(chat_message.stream_id, Some(user_id))
+                .map_err(err_database)?;
+            let is_access = opt_chat_access.map(|v| v.stream_owner == user_id || v.is_moderator).unwrap_or(false);
+            if !is_access {
+                return Ok(None);
+            }
+        }
+        // Get a list of "chat_message_log" for the specified "chat_message_id".
+        let res_chat_message_logs1 = chat_message_orm2
+            .get_chat_message_logs(id)
+            .map(Some)
+            .map_err(err_database);
+        res_chat_message_logs1
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })?;
+
+    let Some(chat_message_logs) = res_chat_message_logs? else {
+        let msg = format!("id: {}, user_id: {}", id, user_id);
+        error!("{}-{}; {}", code_to_str(StatusCode::FORBIDDEN), MSG_VIEW_ANOTHER_USERS_CHAT_MESSAGE_LOGS, &msg);
+        return Err(ApiError::create(403, MSG_VIEW_ANOTHER_USERS_CHAT_MESSAGE_LOGS, &msg)); // 403
+    };
+    let chat_message_log_dto_list: Vec<ChatMessageLogDto> = chat_message_logs.iter()
+        .map(|ch_msg_log| ChatMessageLogDto::from(ch_msg_log.clone()))
+        .collect();
+
+    if let Some(timer) = timer {
+        info!("get_chat_message_logs() time: {}", format!("{:.2?}", timer.elapsed()));
+    }
+    Ok(HttpResponse::Ok().json(chat_message_log_dto_list)) // 200
+}
+
+// ** Section: ChatMentions **
+
+/// get_chat_mentions
+///
+/// Get a list of unread mentions of the current user in chat messages.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X GET http://localhost:8080/api/chat_mentions
+/// ```
+///
+/// Returns the list of unread mentions (Vec<`ChatMentionDto`>) with status 200.
+/// The list is sorted in the order in which the mentions were made.
+/// Mentions in deleted messages are not included in the list.
+///
+/// The structure is returned:
+/// ```text
+/// [
+///   {
+///     id: Number,                // required - mention ID;
+///     chatMessageId: Number,     // required - chat message ID;
+///     streamId: Number,          // required - chat (stream) ID;
+///     member: String,            // required - nickname of the author of the message;
+///     msg: String,               // required - chat message text;
+///     date: DateTime<Utc>,       // required - date and time of the mention;
+///   }
+/// ]
+/// ```
+///
+/// Date and time are transmitted in ISO8601 format ("2020-01-20T20:10:57.000Z").
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "The list of unread mentions of the current user.", body = Vec<ChatMentionDto>,
+            examples(
+            ("1_mentions_present" = (summary = "mentions are present", description = "There are unread mentions.",
+                value = json!([
+                    ChatMentionDto::from(ChatMention::new(1, 123, 98, "emma_johnson", Some("Hi @ava_wilson".into())
+                        , Utc::now() + Duration::minutes(-20))),
+                    ChatMentionDto::from(ChatMention::new(2, 131, 98, "mary_williams", Some("@ava_wilson look".into())
+                        , Utc::now() + Duration::minutes(-10))),
+                ])
+            )),
+            ("2_mentions_absent" = (summary = "mentions are absent", description = "There are no unread mentions.",
+                value = json!([])
+            )) ),
+        ),
+        (status = 401, description = "An authorization token is required.", body = ApiError,
+            example = json!(ApiError::new(401, err::MSG_MISSING_TOKEN))),
+        (status = 506, description = "Blocking error.", body = ApiError, 
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError, 
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    security(("bearer_auth" = [])),
+)]
+// GET /api/chat_mentions
+#[rustfmt::skip]
+#[get("/api/chat_mentions", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn get_chat_mentions(
+    authenticated: Authenticated,
+    chat_message_orm: web::Data<ChatMessageOrmApp>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+    // Get current user details.
+    let user = authenticated.deref();
+    let user_id = user.id;
+
+    let chat_message_orm2 = chat_message_orm.get_ref().clone();
+    let res_chat_mentions = web::block(move || {
+        // Get a list of unread mentions of the user.
+        let res_chat_mentions1 = chat_message_orm2
+            .filter_chat_mentions(user_id)
+            .map_err(|e| {
+                error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+                ApiError::create(507, err::MSG_DATABASE, &e) // 507
+            });
+        res_chat_mentions1
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })?;
+
+    let chat_mention_vec = res_chat_mentions?;
+    let chat_mention_dto_vec: Vec<ChatMentionDto> = chat_mention_vec.into_iter().map(ChatMentionDto::from).collect();
+
+    if let Some(timer) = timer {
+        info!("get_chat_mentions() time: {}", format!("{:.2?}", timer.elapsed()));
+    }
+    Ok(HttpResponse::Ok().json(chat_mention_dto_vec)) // 200
+}
+
+/// put_chat_mentions_read
+///
+/// Mark the mentions of the current user as read.
+///
+/// Request structure:
+/// ```text
+/// {
+///   ids?: Number[],  // optional - list of mention IDs (absent - all unread mentions);
+/// }
+/// ```
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X PUT http://localhost:8080/api/chat_mentions/read \
+/// -d '{"ids": [1, 2]}' \
+/// -H 'Content-Type: application/json'
+/// ```
+/// Mark the specified mentions as read.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X PUT http://localhost:8080/api/chat_mentions/read \
+/// -d '{}' \
+/// -H 'Content-Type: application/json'
+/// ```
+/// Mark all unread mentions as read.
+///
+/// Returns the list of mentions that have been marked as read (Vec<`ChatMentionDto`>) with status 200.
+/// Mentions of other users and mentions that have already been read are ignored.
+///
+/// The structure is returned:
+/// ```text
+/// [
+///   {
+///     id: Number,                // required - mention ID;
+///     chatMessageId: Number,     // required - chat message ID;
+///     streamId: Number,          // required - chat (stream) ID;
+///     member: String,            // required - nickname of the author of the message;
+///     msg: String,               // required - chat message text;
+///     date: DateTime<Utc>,       // required - date and time of the mention;
+///   }
+/// ]
+/// ```
+///
+/// Date and time are transmitted in ISO8601 format ("2020-01-20T20:10:57.000Z").
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "The list of mentions that have been marked as read.", body = Vec<ChatMentionDto>,
+            examples(
+            ("1_mentions_were_read" = (summary = "mentions were read", description = "The mentions were marked as read.",
+                value = json!([
+                    ChatMentionDto::from(ChatMention::new(1, 123, 98, "emma_johnson", Some("Hi @ava_wilson".into())
+                        , Utc::now() + Duration::minutes(-20))),
+                ])
+            )),
+            ("2_mentions_were_not_read" = (summary = "mentions were not read",
+                description = "There are no unread mentions with the specified IDs.", value = json!([])
+            )) ),
+        ),
+        (status = 401, description = "An authorization token is required.", body = ApiError,
+            example = json!(ApiError::new(401, err::MSG_MISSING_TOKEN))),
+        (status = 506, description = "Blocking error.", body = ApiError, 
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError, 
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    request_body(content = ReadChatMentionsDto,
+        description = "List of mention IDs to mark as read (absent - all unread mentions).",
+        example = json!(ReadChatMentionsDto { ids: Some(vec![1, 2]) })
+    ),
+    security(("bearer_auth" = [])),
+)]
+// PUT /api/chat_mentions/read
+#[rustfmt::skip]
+#[put("/api/chat_mentions/read", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn put_chat_mentions_read(
+    authenticated: Authenticated,
+    chat_message_orm: web::Data<ChatMessageOrmApp>,
+    json_body: web::Json<ReadChatMentionsDto>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+    // Get current user details.
+    let user = authenticated.deref();
+    let user_id = user.id;
+
+    let opt_ids: Option<Vec<i32>> = json_body.into_inner().ids;
+
+    let chat_message_orm2 = chat_message_orm.get_ref().clone();
+    let res_chat_mentions = web::block(move || {
+        // Mark the mentions of the user as read.
+        let res_chat_mentions1 = chat_message_orm2
+            .read_chat_mentions(user_id, opt_ids)
+            .map_err(|e| {
+                error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+                ApiError::create(507, err::MSG_DATABASE, &e) // 507
+            });
+        res_chat_mentions1
+    })
+    .await
+    .map_err(|e| {
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })?;
+
+    let chat_mention_vec = res_chat_mentions?;
+    let chat_mention_dto_vec: Vec<ChatMentionDto> = chat_mention_vec.into_iter().map(ChatMentionDto::from).collect();
+
+    if let Some(timer) = timer {
+        info!("put_chat_mentions_read() time: {}", format!("{:.2?}", timer.elapsed()));
+    }
+    Ok(HttpResponse::Ok().json(chat_mention_dto_vec)) // 200
+}
+
 // ** Section: BlockedUsers **
 
 
@@ -766,6 +1617,7 @@ pub async fn get_blocked_users_names(
 ///     nickname: String,          // required - nickname of the blocked user;
 ///     email: String,             // required - email of the blocked user;
 ///     blockDate: DateTime<Utc>,  // required - date and time the user was blocked;
+///     blockUntil?: DateTime<Utc>,// optional - date and time the block ends (absent - the block is permanent);
 ///     avatar?: String,           // optional - avatar of the blocked user;
 ///   }
 /// ]
@@ -778,9 +1630,9 @@ pub async fn get_blocked_users_names(
             ("1_blocked_users_present" = (summary = "blocked users are present", description = "There are no blocked users.",
                 value = json!([
                     BlockedUserDto::from(BlockedUser::new(1, 42, "mary_williams".into(), "mary_williams@email.uk".into()
-                        , Utc::now() + Duration::minutes(-30), None) ),
+                        , Utc::now() + Duration::minutes(-30), Some(Utc::now() + Duration::minutes(30)), None) ),
                     BlockedUserDto::from(BlockedUser::new(1, 48, "ava_wilson".into(), "ava_wilson@email.uk".into()
-                        , Utc::now() + Duration::minutes(-145), None) ),
+                        , Utc::now() + Duration::minutes(-145), None, None) ),
                 ])
             )),
             ("2_blocked_users_absent" = (summary = "blocked users are absent", description = "There are no blocked users.",
@@ -849,11 +1701,15 @@ pub async fn get_blocked_users(
 /// {
 ///   blockedId?: Number,        // optional - user id to block;
 ///   blockedNickname?: String,  // optional - "nickname" of the user to block;
+///   blockDuration?: Number,    // optional - block duration in seconds (absent - the block is permanent);
 /// }
 /// ```
 /// 
 /// One of the parameters "blockedId", "blockedNickname" must be present.
 /// 
+/// If the user is already blocked, the end of the block is replaced with the new one.
+/// When a timed block ends, the user is unblocked automatically.
+/// 
 /// Returns the blocked user record (`BlockedUserMiniDto`) with status 200 or 204 (no content) if the user is not found.
 /// 
 /// The structure is returned:
@@ -863,6 +1719,7 @@ pub async fn get_blocked_users(
 ///   userId: Number,            // required - blocked user ID;
 ///   nickname: String,          // required - nickname of the blocked user;
 ///   blockDate: DateTime<Utc>,  // required - date and time the user was blocked;
+///   blockUntil?: DateTime<Utc>,// optional - date and time the block ends;
 /// }
 /// ```
 /// 
@@ -882,6 +1739,23 @@ pub async fn get_blocked_users(
 /// ```
 /// Add user to the blocked list by user "nickname".
 /// 
+/// One could call with following curl.
+/// ```text
+/// curl -i -X POST http://localhost:8080/api/blocked_users/ \
+/// -d '{"blockedNickname": "mary_williams", "blockDuration": 600}' \
+/// -H 'Content-Type: application/json'
+/// ```
+/// Add user to the blocked list for 10 minutes.
+/// 
+/// A user with moderator or administrator rights can add a user to the blocked list of any stream owner.
+/// 
+/// One could call with following curl.
+/// ```text
+/// curl -i -X POST http://localhost:8080/api/blocked_users?ownerId=3 \
+/// -d '{"blockedNickname": "mary_williams"}' \
+/// -H 'Content-Type: application/json'
+/// ```
+/// 
 #[utoipa::path(
     responses(
         (status = 201, description = "Add user to blocked list.", body = BlockedUserMiniDto,
@@ -891,24 +1765,34 @@ pub async fn get_blocked_users(
                 `curl -i -X POST http://localhost:8080/api/blocked_users 
                 -d '{\"blockedId\": 42} -H 'Content-Type: application/json'`",
                 value = json!(BlockedUserMiniDto::from(BlockedUserMini::new(1, 42, "mary_williams".to_string()
-                    , Utc::now() + Duration::minutes(-30)))
+                    , Utc::now() + Duration::minutes(-30), None))
             ))),
             ("2_add_by_nickname" = (summary = "Add user by user \"nickname\"", 
                 description = "Add user to the blocked list by user \"nickname\".
                 `curl -i -X POST http://localhost:8080/api/blocked_users 
                 -d '{\"blockedNickname\": \"mary_williams\"} -H 'Content-Type: application/json'`",
                 value = json!(BlockedUserMiniDto::from(BlockedUserMini::new(1, 42, "mary_williams".to_string()
-                , Utc::now() + Duration::minutes(-30)))
+                , Utc::now() + Duration::minutes(-30), None))
+            ))),
+            ("3_add_for_a_while" = (summary = "Add user for a while", 
+                description = "Add user to the blocked list for 10 minutes.
+                `curl -i -X POST http://localhost:8080/api/blocked_users 
+                -d '{\"blockedNickname\": \"mary_williams\", \"blockDuration\": 600} -H 'Content-Type: application/json'`",
+                value = json!(BlockedUserMiniDto::from(BlockedUserMini::new(1, 42, "mary_williams".to_string()
+                , Utc::now(), Some(Utc::now() + Duration::minutes(10))))
             )))),
         ),
         (status = 204, description = "The user with the specified ID was not found."),
         (status = 401, description = "An authorization token is required.", body = ApiError,
             example = json!(ApiError::new(403, err::MSG_MISSING_TOKEN))),
+        (status = 416, description = "Error parsing input parameter. (Admin, Moderator)", body = ApiError,
+            example = json!(ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED
+                , "`ownerId` - invalid digit found in string (3a)"))),
         (status = 417, body = [ApiError],
             description = "Validation error. `curl -i -X POST http://localhost:8080/api/blocked_users 
             -d '{} -H 'Content-Type: application/json'`",
             example = json!(ApiError::validations(
-                (CreateBlockedUserDto { blocked_id: None, blocked_nickname: None }).validate().err().unwrap() ) )
+                (CreateBlockedUserDto { blocked_id: None, blocked_nickname: None, block_duration: None }).validate().err().unwrap() ) )
         ),
         (status = 506, description = "Blocking error.", body = ApiError, 
             example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
@@ -923,12 +1807,26 @@ pub async fn get_blocked_users(
 pub async fn post_blocked_user(
     authenticated: Authenticated,
     chat_message_orm: web::Data<ChatMessageOrmApp>,
+    request: actix_web::HttpRequest,
     json_body: web::Json<CreateBlockedUserDto>,
 ) -> actix_web::Result<HttpResponse, ApiError> {
     let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
     // Get current user details.
     let user = authenticated.deref();
-    let user_id = user.id;
+
+    let mut owner_id = user.id;
+    // A user with chat moderation rights can change the list of blocked users of any stream owner.
+    if ChatWsAssistant::is_moderator(&user.role) {
+        let query_params = Query::<HashMap<String, String>>::from_query(request.query_string()).unwrap();
+        let owner_id_str = query_params.get("ownerId").map(|v| v.clone()).unwrap_or("".to_string());
+        if owner_id_str.len() > 0 {
+            owner_id = parser::parse_i32(&owner_id_str).map_err(|e| {
+                let msg = &format!("`ownerId` - {}", &e);
+                error!("{}-{}; {}", code_to_str(StatusCode::RANGE_NOT_SATISFIABLE), err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg);
+                ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg) // 416
+            })?;
+        }
+    }
 
     // Checking the validity of the data model.
     let validation_res = json_body.validate();
@@ -940,8 +1838,10 @@ pub async fn post_blocked_user(
     let create_blocked_user_dto: CreateBlockedUserDto = json_body.into_inner();
     let blocked_id = create_blocked_user_dto.blocked_id;
     let blocked_nickname = create_blocked_user_dto.blocked_nickname.clone();
+    // Get the date and time the block ends (if the block is timed).
+    let block_until = create_blocked_user_dto.block_duration.map(|v| Utc::now() + Duration::seconds(v.into()));
 
-    let create_blocked_user = CreateBlockedUser::new(user_id, blocked_id, blocked_nickname);
+    let create_blocked_user = CreateBlockedUser::new(owner_id, blocked_id, blocked_nickname, block_until);
 
     let chat_message_orm2 = chat_message_orm.get_ref().clone();
     let res_blocked_user = web::block(move || {
@@ -960,6 +1860,13 @@ pub async fn post_blocked_user(
 
     let opt_blocked_user_mini_dto = res_blocked_user?.map(|v| BlockedUserMiniDto::from(v));
 
+    if let Some(blocked_user_dto) = opt_blocked_user_mini_dto.clone() {
+        let blocked_name = blocked_user_dto.nickname.clone();
+        let is_block = true;
+        let block_user = BlockUser(owner_id, blocked_name, is_block, blocked_user_dto.block_until.clone());
+        let _ = ChatWsServer::from_registry().send(block_user).await;
+    }
+
     if let Some(timer) = timer {
         info!("post_blocked_user() time: {}", format!("{:.2?}", timer.elapsed()));
     }
@@ -1011,6 +1918,15 @@ pub async fn post_blocked_user(
 /// ```
 /// Remove user from blocked list by user "nickname".
 /// 
+/// A user with moderator or administrator rights can remove a user from the blocked list of any stream owner.
+/// 
+/// One could call with following curl.
+/// ```text
+/// curl -i -X DELETE http://localhost:8080/api/blocked_users?ownerId=3 \
+/// -d '{"blockedNickname": "mary_williams"}' \
+/// -H 'Content-Type: application/json'
+/// ```
+/// 
 #[utoipa::path(
     responses(
         (status = 200, description = "Remove user from blocked list.", body = BlockedUserMiniDto,
@@ -1020,19 +1936,22 @@ pub async fn post_blocked_user(
                 `curl -i -X DELETE http://localhost:8080/api/blocked_users 
                 -d '{\"blockedId\": 42} -H 'Content-Type: application/json'`",
                 value = json!(BlockedUserMiniDto::from(BlockedUserMini::new(1, 42, "mary_williams".to_string()
-                    , Utc::now() + Duration::minutes(-30)))
+                    , Utc::now() + Duration::minutes(-30), None))
             ))),
             ("2_remove_by_nickname" = (summary = "Remove user by user \"nickname\"", 
                 description = "Remove user from the blocked list by user \"nickname\".
                 `curl -i -X DELETE http://localhost:8080/api/blocked_users 
                 -d '{\"blockedNickname\": \"mary_williams\"} -H 'Content-Type: application/json'`",
                 value = json!(BlockedUserMiniDto::from(BlockedUserMini::new(1, 42, "mary_williams".to_string()
-                , Utc::now() + Duration::minutes(-30)))
+                , Utc::now() + Duration::minutes(-30), None))
             )))),
         ),
         (status = 204, description = "The user with the specified ID was not found."),
         (status = 401, description = "An authorization token is required.", body = ApiError,
             example = json!(ApiError::new(401, err::MSG_MISSING_TOKEN))),
+        (status = 416, description = "Error parsing input parameter. (Admin, Moderator)", body = ApiError,
+            example = json!(ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED
+                , "`ownerId` - invalid digit found in string (3a)"))),
         (status = 417, body = [ApiError],
             description = "Validation error. `curl -i -X POST http://localhost:8080/api/blocked_users 
             -d '{} -H 'Content-Type: application/json'`",
@@ -1052,12 +1971,26 @@ pub async fn post_blocked_user(
 pub async fn delete_blocked_user(
     authenticated: Authenticated,
     chat_message_orm: web::Data<ChatMessageOrmApp>,
+    request: actix_web::HttpRequest,
     json_body: web::Json<DeleteBlockedUserDto>,
 ) -> actix_web::Result<HttpResponse, ApiError> {
     let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
     // Get current user details.
     let user = authenticated.deref();
-    let user_id = user.id;
+
+    let mut owner_id = user.id;
+    // A user with chat moderation rights can change the list of blocked users of any stream owner.
+    if ChatWsAssistant::is_moderator(&user.role) {
+        let query_params = Query::<HashMap<String, String>>::from_query(request.query_string()).unwrap();
+        let owner_id_str = query_params.get("ownerId").map(|v| v.clone()).unwrap_or("".to_string());
+        if owner_id_str.len() > 0 {
+            owner_id = parser::parse_i32(&owner_id_str).map_err(|e| {
+                let msg = &format!("`ownerId` - {}", &e);
+                error!("{}-{}; {}", code_to_str(StatusCode::RANGE_NOT_SATISFIABLE), err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg);
+                ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg) // 416
+            })?;
+        }
+    }
 
     // Checking the validity of the data model.
     let validation_res = json_body.validate();
@@ -1070,7 +2003,7 @@ pub async fn delete_blocked_user(
     let blocked_id = delete_blocked_user_dto.blocked_id;
     let blocked_nickname = delete_blocked_user_dto.blocked_nickname.clone();
 
-    let delete_blocked_user = DeleteBlockedUser::new(user_id, blocked_id, blocked_nickname);
+    let delete_blocked_user = DeleteBlockedUser::new(owner_id, blocked_id, blocked_nickname);
 
     let chat_message_orm2 = chat_message_orm.get_ref().clone();
     let res_blocked_user = web::block(move || {
@@ -1090,10 +2023,9 @@ pub async fn delete_blocked_user(
     let opt_blocked_user_mini_dto = res_blocked_user?.map(|v| BlockedUserMiniDto::from(v));
 
     if let Some(blocked_user_dto) = opt_blocked_user_mini_dto.clone() {
-        let owner_id = user.id;
         let blocked_name = blocked_user_dto.nickname.clone();
         let is_block = false;
-        let block_user = BlockUser(owner_id, blocked_name, is_block);
+        let block_user = BlockUser(owner_id, blocked_name, is_block, None);
         let _ = ChatWsServer::from_registry().send(block_user).await;
     }
 
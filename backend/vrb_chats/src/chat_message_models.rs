@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -6,7 +6,7 @@ use vrb_common::{
     serial_datetime, serial_datetime_option,
     validators::{ValidationChecks, ValidationError, Validator},
 };
-use vrb_dbase::schema;
+use vrb_dbase::{enm_stream_state::StreamState, schema};
 
 // ** Models: "CreateChatMessage", "ModifyChatMessage". **
 
@@ -22,6 +22,139 @@ pub fn validate_message(value: &str) -> Result<(), ValidationError> {
     Ok(())
 }
 
+// ** Models: "ChatMessageDto", "MsgEWS". **
+
+pub const REPLY_MSG_MAX: usize = 64;
+
+// Get a short fragment of the text of the parent message (the quote), no more than REPLY_MSG_MAX characters.
+pub fn reply_snippet(msg: &str) -> String {
+    if msg.chars().count() <= REPLY_MSG_MAX {
+        return msg.to_string();
+    }
+    let snippet: String = msg.chars().take(REPLY_MSG_MAX).collect();
+    format!("{}...", snippet.trim_end())
+}
+
+// ** Models: "MentionedUser", "MentionEWS". **
+
+pub const MENTIONS_MAX: usize = 10;
+
+// Get a list of nicknames mentioned in the message ("@nickname"), no more than MENTIONS_MAX (without repetitions).
+pub fn parse_mentions(msg: &str) -> Vec<String> {
+    let is_word_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let chars: Vec<char> = msg.chars().collect();
+    let mut result: Vec<String> = Vec::new();
+    let mut idx = 0;
+    while idx < chars.len() && result.len() < MENTIONS_MAX {
+        // The mention must be at the beginning of the text or after a character that is not part of a word.
+        if chars[idx] != '@' || (idx > 0 && is_word_char(chars[idx - 1])) {
+            idx += 1;
+            continue;
+        }
+        let start = idx + 1;
+        let mut end = start;
+        while end < chars.len() && is_word_char(chars[end]) {
+            end += 1;
+        }
+        let nickname: String = chars[start..end].iter().collect();
+        // The nickname must match the pattern: /^[a-zA-Z]+[\w]+$/
+        let is_nickname = nickname.len() > 1 && nickname.starts_with(|c: char| c.is_ascii_alphabetic());
+        if is_nickname && !result.contains(&nickname) {
+            result.push(nickname);
+        }
+        idx = end;
+    }
+    result
+}
+
+// ** Model: "ModifyChatMessageReaction". **
+
+pub const REACTION_MIN: u8 = 1;
+pub const MSG_REACTION_MIN_LENGTH: &str = "reaction:min_length";
+pub const REACTION_MAX: u8 = 32;
+pub const MSG_REACTION_MAX_LENGTH: &str = "reaction:max_length";
+pub const MSG_REACTION_NOT_EMOJI: &str = "reaction:not_emoji";
+// The maximum number of different reactions of one user to one message.
+pub const REACTIONS_PER_USER_MAX: u8 = 3;
+
+// MIN=1, MAX=32, a single emoji.
+pub fn validate_reaction(value: &str) -> Result<(), ValidationError> {
+    ValidationChecks::min_length(value, REACTION_MIN.into(), MSG_REACTION_MIN_LENGTH)?;
+    ValidationChecks::max_length(value, REACTION_MAX.into(), MSG_REACTION_MAX_LENGTH)?;
+    if !is_emoji(value) {
+        return Err(ValidationError::new(MSG_REACTION_NOT_EMOJI));
+    }
+    Ok(())
+}
+
+// Check that the character is a pictographic emoji (the main blocks of "Extended_Pictographic").
+fn is_pictographic(c: char) -> bool {
+    #[rustfmt::skip]
+    let result = matches!(c,
+        '\u{00A9}' | '\u{00AE}' | '\u{203C}' | '\u{2049}' | '\u{2122}' | '\u{2139}' | '\u{2194}'..='\u{2199}'
+        | '\u{21A9}'..='\u{21AA}' | '\u{231A}'..='\u{231B}' | '\u{2328}' | '\u{23CF}' | '\u{23E9}'..='\u{23F3}'
+        | '\u{23F8}'..='\u{23FA}' | '\u{24C2}' | '\u{25AA}'..='\u{25AB}' | '\u{25B6}' | '\u{25C0}' | '\u{25FB}'..='\u{25FE}'
+        | '\u{2600}'..='\u{27BF}' | '\u{2934}'..='\u{2935}' | '\u{2B05}'..='\u{2B07}' | '\u{2B1B}'..='\u{2B1C}' | '\u{2B50}'
+        | '\u{2B55}' | '\u{3030}' | '\u{303D}' | '\u{3297}' | '\u{3299}' | '\u{1F000}'..='\u{1F1E5}'
+        | '\u{1F200}'..='\u{1F3FA}' | '\u{1F400}'..='\u{1FAFF}');
+    result
+}
+
+// Check that the value is a single emoji (one grapheme): a pictographic character with a skin tone or tags,
+// a sequence of such characters joined by ZWJ, a flag (a pair of regional indicators) or a keycap ("1️⃣").
+pub fn is_emoji(value: &str) -> bool {
+    const ZWJ: char = '\u{200D}';
+    const VS16: char = '\u{FE0F}';
+    const KEYCAP: char = '\u{20E3}';
+    let is_regional = |c: char| ('\u{1F1E6}'..='\u{1F1FF}').contains(&c);
+    let is_skin_tone = |c: char| ('\u{1F3FB}'..='\u{1F3FF}').contains(&c);
+    let is_tag = |c: char| ('\u{E0020}'..='\u{E007E}').contains(&c);
+
+    let chars: Vec<char> = value.chars().collect();
+    // The flag of a country.
+    if chars.len() == 2 && chars.iter().all(|c| is_regional(*c)) {
+        return true;
+    }
+    // The keycap: "0".."9", "#" or "*", then (optionally) VS16 and the combining keycap.
+    if let [first, rest @ ..] = chars.as_slice() {
+        if (first.is_ascii_digit() || *first == '#' || *first == '*') && (rest == [KEYCAP] || rest == [VS16, KEYCAP]) {
+            return true;
+        }
+    }
+    // The sequence of pictographic characters joined by ZWJ.
+    let mut idx = 0;
+    loop {
+        let Some(c) = chars.get(idx) else {
+            return false;
+        };
+        if !is_pictographic(*c) {
+            return false;
+        }
+        idx += 1;
+        if chars.get(idx) == Some(&VS16) {
+            idx += 1;
+        }
+        if chars.get(idx).map(|c| is_skin_tone(*c)).unwrap_or(false) {
+            idx += 1;
+        }
+        // The tags of the subdivision flag ("🏴󠁧󠁢󠁳󠁣󠁴󠁿") end with the cancel tag.
+        if chars.get(idx).map(|c| is_tag(*c)).unwrap_or(false) {
+            while chars.get(idx).map(|c| is_tag(*c)).unwrap_or(false) {
+                idx += 1;
+            }
+            if chars.get(idx) != Some(&'\u{E007F}') {
+                return false;
+            }
+            idx += 1;
+        }
+        match chars.get(idx) {
+            None => return true,
+            Some(&ZWJ) => idx += 1,
+            Some(_) => return false,
+        }
+    }
+}
+
 // ** Models: "CreateBlockedUser", "DeleteBlockedUser". **
 
 pub const BLOCKED_NICKNAME_MIN: u8 = 3;
@@ -29,6 +162,10 @@ pub const BLOCKED_NICKNAME_MAX: u8 = 64;
 pub const MSG_BLOCKED_NICKNAME_MIN_LENGTH: &str = "blocked_nickname:min_length";
 pub const MSG_BLOCKED_NICKNAME_MAX_LENGTH: &str = "blocked_nickname:max_length";
 pub const MSG_BLOCKED_ONE_OPTIONAL_MUST_PRESENT: &str = "blocked_oneOptionalMustPresent";
+pub const BLOCK_DURATION_MIN: u32 = 1; // 1 second
+pub const BLOCK_DURATION_MAX: u32 = 31_622_400; // 366 days
+pub const MSG_BLOCK_DURATION_MIN_AMOUNT: &str = "block_duration:min_amount";
+pub const MSG_BLOCK_DURATION_MAX_AMOUNT: &str = "block_duration:max_amount";
 
 // MIN=3, MAX=64
 pub fn validate_blocked_nickname(value: &str) -> Result<(), ValidationError> {
@@ -37,6 +174,14 @@ pub fn validate_blocked_nickname(value: &str) -> Result<(), ValidationError> {
     Ok(())
 }
 
+// MIN=1, MAX=31_622_400 (in seconds)
+pub fn validate_block_duration(value: i32) -> Result<(), ValidationError> {
+    let amount = usize::try_from(value).unwrap_or_default();
+    ValidationChecks::min_amount(amount, BLOCK_DURATION_MIN as usize, MSG_BLOCK_DURATION_MIN_AMOUNT)?;
+    ValidationChecks::max_amount(amount, BLOCK_DURATION_MAX as usize, MSG_BLOCK_DURATION_MAX_AMOUNT)?;
+    Ok(())
+}
+
 // * * * * Section: models for "ChatMessageOrm". * * * *
 
 // ** Model: "ChatMessage". Used to return "chat_message" data. **
@@ -55,6 +200,16 @@ pub struct ChatMessage {
     pub date_created: DateTime<Utc>,
     pub date_changed: Option<DateTime<Utc>>,
     pub date_removed: Option<DateTime<Utc>>,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    #[diesel(column_name = "cnt_logs")]
+    pub cnt_logs: i32, // The number of entries in the edit history.
+    pub reply_to: Option<i32>, // ID of the parent message (the message being replied to).
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    #[diesel(column_name = "reply_user_name")]
+    pub reply_user_name: Option<String>, // Nickname of the author of the parent message.
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    #[diesel(column_name = "reply_msg")]
+    pub reply_msg: Option<String>, // Text of the parent message (None - if the parent message was deleted).
 }
 
 impl ChatMessage {
@@ -67,6 +222,7 @@ impl ChatMessage {
         date_created: DateTime<Utc>,
         date_changed: Option<DateTime<Utc>>,
         date_removed: Option<DateTime<Utc>>,
+        cnt_logs: i32,
     ) -> ChatMessage {
         ChatMessage {
             id,
@@ -77,6 +233,10 @@ impl ChatMessage {
             date_created,
             date_changed,
             date_removed,
+            cnt_logs,
+            reply_to: None,
+            reply_user_name: None,
+            reply_msg: None,
         }
     }
 }
@@ -95,6 +255,16 @@ pub struct ChatMessageDto {
     #[rustfmt::skip]
     #[serde(default, with = "serial_datetime_option", skip_serializing_if = "Option::is_none")]
     pub date_rmv: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub cnt_edt: i32, // The number of entries in the edit history.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<i32>, // ID of the parent message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_member: Option<String>, // Nickname of the author of the parent message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_msg: Option<String>, // Fragment of the text of the parent message.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reactions: Vec<ChatMessageReactionDto>, // The number of reactions of each emoji.
 }
 
 impl From<ChatMessage> for ChatMessageDto {
@@ -106,10 +276,176 @@ impl From<ChatMessage> for ChatMessageDto {
             msg: chat_message.msg.unwrap_or("".to_owned()),
             date_edt: chat_message.date_changed.clone(),
             date_rmv: chat_message.date_removed.clone(),
+            cnt_edt: chat_message.cnt_logs,
+            reply_to: chat_message.reply_to,
+            reply_member: chat_message.reply_user_name.clone(),
+            reply_msg: chat_message.reply_msg.map(|v| reply_snippet(&v)),
+            reactions: vec![],
         }
     }
 }
 
+// ** Model: "ChatMessageReaction". Used to return the number of reactions (of one emoji) to the message. **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, QueryableByName)]
+#[diesel(table_name = schema::chat_message_reactions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ChatMessageReaction {
+    pub chat_message_id: i32,
+    pub emoji: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    #[diesel(column_name = "cnt")]
+    pub cnt: i32, // The number of reactions (of this emoji).
+}
+
+impl ChatMessageReaction {
+    pub fn new(chat_message_id: i32, emoji: &str, cnt: i32) -> ChatMessageReaction {
+        ChatMessageReaction {
+            chat_message_id,
+            emoji: emoji.to_string(),
+            cnt,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatMessageReactionDto {
+    pub emoji: String,
+    pub count: i32,
+}
+
+impl ChatMessageReactionDto {
+    pub fn new(emoji: &str, count: i32) -> ChatMessageReactionDto {
+        ChatMessageReactionDto {
+            emoji: emoji.to_owned(),
+            count,
+        }
+    }
+}
+
+impl From<ChatMessageReaction> for ChatMessageReactionDto {
+    fn from(chat_message_reaction: ChatMessageReaction) -> Self {
+        ChatMessageReactionDto {
+            emoji: chat_message_reaction.emoji.clone(),
+            count: chat_message_reaction.cnt,
+        }
+    }
+}
+
+// ** Model: "ChatMessageReactionDelta". Used to return the change in the number of reactions to the message. **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, QueryableByName)]
+#[diesel(table_name = schema::chat_message_reactions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ChatMessageReactionDelta {
+    pub chat_message_id: i32,
+    pub emoji: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    #[diesel(column_name = "cnt")]
+    pub cnt: i32, // The number of reactions (of this emoji).
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    #[diesel(column_name = "delta")]
+    pub delta: i32, // The change in the number of reactions (1 - added, 0 - unchanged, -1 - removed).
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    #[diesel(column_name = "is_limit")]
+    pub is_limit: bool, // The reaction was not added, the user already has the maximum number of reactions to the message.
+}
+
+impl ChatMessageReactionDelta {
+    pub fn new(chat_message_id: i32, emoji: &str, cnt: i32, delta: i32) -> ChatMessageReactionDelta {
+        ChatMessageReactionDelta {
+            chat_message_id,
+            emoji: emoji.to_string(),
+            cnt,
+            delta,
+            is_limit: false,
+        }
+    }
+}
+
+// ** Model: "MentionedUser". Used to return the user mentioned in the message. **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, QueryableByName)]
+#[diesel(table_name = schema::chat_message_mentions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MentionedUser {
+    pub user_id: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    #[diesel(column_name = "nickname")]
+    pub nickname: String,
+}
+
+impl MentionedUser {
+    pub fn new(user_id: i32, nickname: &str) -> MentionedUser {
+        MentionedUser {
+            user_id,
+            nickname: nickname.to_string(),
+        }
+    }
+}
+
+// ** Model: "ChatMention". Used to return a mention of the user in the chat message. **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, QueryableByName)]
+#[diesel(table_name = schema::chat_message_mentions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ChatMention {
+    pub id: i32,
+    pub chat_message_id: i32,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    #[diesel(column_name = "stream_id")]
+    pub stream_id: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    #[diesel(column_name = "user_name")]
+    pub user_name: String, // Nickname of the author of the message.
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    #[diesel(column_name = "msg")]
+    pub msg: Option<String>,
+    pub date_created: DateTime<Utc>, // Date of the chat message.
+}
+
+impl ChatMention {
+    #[rustfmt::skip]
+    pub fn new(id: i32, chat_message_id: i32, stream_id: i32, user_name: &str, msg: Option<String>, date_created: DateTime<Utc>) -> Self {
+        ChatMention { id, chat_message_id, stream_id, user_name: user_name.to_string(), msg, date_created }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatMentionDto {
+    pub id: i32,
+    pub chat_message_id: i32,
+    pub stream_id: i32,
+    pub member: String,
+    pub msg: String,
+    #[serde(with = "serial_datetime")]
+    pub date: DateTime<Utc>,
+}
+
+impl From<ChatMention> for ChatMentionDto {
+    fn from(chat_mention: ChatMention) -> Self {
+        ChatMentionDto {
+            id: chat_mention.id,
+            chat_message_id: chat_mention.chat_message_id,
+            stream_id: chat_mention.stream_id,
+            member: chat_mention.user_name.clone(),
+            msg: chat_mention.msg.unwrap_or_default(),
+            date: chat_mention.date_created.clone(),
+        }
+    }
+}
+
+// ** Model Dto: "ReadChatMentionsDto". Used: in "chat_controller::put_chat_mentions_read()" **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadChatMentionsDto {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ids: Option<Vec<i32>>, // None - all unread mentions.
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, QueryableByName)]
 #[diesel(table_name = schema::chat_message_logs)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
@@ -131,21 +467,44 @@ impl ChatMessageLog {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatMessageLogDto {
+    pub id: i32,
+    pub chat_message_id: i32,
+    pub old_msg: String,
+    #[serde(with = "serial_datetime")]
+    pub date_update: DateTime<Utc>,
+}
+
+impl From<ChatMessageLog> for ChatMessageLogDto {
+    fn from(chat_message_log: ChatMessageLog) -> Self {
+        ChatMessageLogDto {
+            id: chat_message_log.id,
+            chat_message_id: chat_message_log.chat_message_id,
+            old_msg: chat_message_log.old_msg.clone(),
+            date_update: chat_message_log.date_update.clone(),
+        }
+    }
+}
+
 // ** Model: "CreateChatMessage". Used: ChatMessageOrm::create_chat_message() **
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct CreateChatMessage {
     pub stream_id: i32,
     pub user_id: i32,
-    pub msg: String, // min_len=1 max_len=255 Nullable
+    pub msg: String,           // min_len=1 max_len=255 Nullable
+    pub reply_to: Option<i32>, // ID of the parent message (the message being replied to).
 }
 
 impl CreateChatMessage {
-    pub fn new(stream_id: i32, user_id: i32, msg: &str) -> CreateChatMessage {
+    pub fn new(stream_id: i32, user_id: i32, msg: &str, reply_to: Option<i32>) -> CreateChatMessage {
         CreateChatMessage {
             stream_id,
             user_id,
             msg: msg.to_owned(),
+            reply_to,
         }
     }
 }
@@ -168,6 +527,8 @@ impl Validator for CreateChatMessage {
 pub struct CreateChatMessageDto {
     pub stream_id: i32,
     pub msg: String, // min_len=1 max_len=255 Nullable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<i32>, // ID of the parent message (the message being replied to).
 }
 
 impl Validator for CreateChatMessageDto {
@@ -181,6 +542,35 @@ impl Validator for CreateChatMessageDto {
     }
 }
 
+// ** Model: "ModifyChatMessageReaction". Used: ChatMessageOrm::modify_chat_message_reaction() **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ModifyChatMessageReaction {
+    pub chat_message_id: i32,
+    pub stream_id: i32, // The message must be in the chat of this stream.
+    pub user_id: i32,
+    pub emoji: String, // min_len=1 max_len=32, a single emoji.
+    pub is_add: bool,  // true - add a reaction, false - remove a reaction.
+}
+
+impl ModifyChatMessageReaction {
+    #[rustfmt::skip]
+    pub fn new(chat_message_id: i32, stream_id: i32, user_id: i32, emoji: &str, is_add: bool) -> ModifyChatMessageReaction {
+        ModifyChatMessageReaction { chat_message_id, stream_id, user_id, emoji: emoji.to_owned(), is_add }
+    }
+}
+
+impl Validator for ModifyChatMessageReaction {
+    // Check the model against the required conditions.
+    fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors: Vec<Option<ValidationError>> = vec![];
+
+        errors.push(validate_reaction(&self.emoji).err());
+
+        self.filter_errors(errors)
+    }
+}
+
 // ** Model: "ModifyChatMessage". Used: ChatMessageOrm::modify_chat_message() **
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -238,6 +628,7 @@ pub struct SearchChatMessage {
     pub min_date_created: Option<DateTime<Utc>>,
     pub max_date_created: Option<DateTime<Utc>>,
     pub limit: Option<usize>,
+    pub thread_id: Option<i32>, // ID of the root message of the thread (the root message and all replies to it).
 }
 
 impl SearchChatMessage {
@@ -247,6 +638,7 @@ impl SearchChatMessage {
         min_date_created: Option<DateTime<Utc>>,
         max_date_created: Option<DateTime<Utc>>,
         limit: Option<usize>,
+        thread_id: Option<i32>,
     ) -> SearchChatMessage {
         SearchChatMessage {
             stream_id,
@@ -254,6 +646,7 @@ impl SearchChatMessage {
             min_date_created,
             max_date_created,
             limit,
+            thread_id,
         }
     }
 }
@@ -270,6 +663,7 @@ impl SearchChatMessage {
             min_date_created: search_chat_message.min_date.clone(),
             max_date_created: search_chat_message.max_date.clone(),
             limit,
+            thread_id: search_chat_message.thread_id,
         }
     }
 }
@@ -289,6 +683,452 @@ pub struct SearchChatMessageDto {
     pub max_date: Option<DateTime<Utc>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub limit: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<i32>,
+}
+
+// ** Model: "SearchChatText". Used: ChatMessageOrm::search_chat_messages() **
+
+pub const SEARCH_CHAT_TEXT_PAGE: u32 = 1;
+pub const SEARCH_CHAT_TEXT_LIMIT: u32 = 20;
+pub const SEARCH_TEXT_MIN: u16 = 2;
+pub const MSG_SEARCH_TEXT_MIN_LENGTH: &str = "search_text:min_length";
+pub const SEARCH_TEXT_MAX: u16 = 255;
+pub const MSG_SEARCH_TEXT_MAX_LENGTH: &str = "search_text:max_length";
+pub const MSG_SEARCH_ONE_OPTIONAL_MUST_PRESENT: &str = "search_oneOptionalMustPresent";
+pub const SEARCH_PAGE_MIN: u32 = 1;
+pub const MSG_SEARCH_PAGE_MIN_AMOUNT: &str = "search_page:min_amount";
+pub const SEARCH_LIMIT_MIN: u32 = 1;
+pub const SEARCH_LIMIT_MAX: u32 = 100;
+pub const MSG_SEARCH_LIMIT_MIN_AMOUNT: &str = "search_limit:min_amount";
+pub const MSG_SEARCH_LIMIT_MAX_AMOUNT: &str = "search_limit:max_amount";
+
+// MIN=2, MAX=255
+pub fn validate_search_text(value: &str) -> Result<(), ValidationError> {
+    ValidationChecks::min_length(value, SEARCH_TEXT_MIN.into(), MSG_SEARCH_TEXT_MIN_LENGTH)?;
+    ValidationChecks::max_length(value, SEARCH_TEXT_MAX.into(), MSG_SEARCH_TEXT_MAX_LENGTH)?;
+    Ok(())
+}
+
+// MIN=1
+pub fn validate_search_page(value: u32) -> Result<(), ValidationError> {
+    ValidationChecks::min_amount(value as usize, SEARCH_PAGE_MIN as usize, MSG_SEARCH_PAGE_MIN_AMOUNT)?;
+    Ok(())
+}
+
+// MIN=1, MAX=100
+pub fn validate_search_limit(value: u32) -> Result<(), ValidationError> {
+    ValidationChecks::min_amount(value as usize, SEARCH_LIMIT_MIN as usize, MSG_SEARCH_LIMIT_MIN_AMOUNT)?;
+    ValidationChecks::max_amount(value as usize, SEARCH_LIMIT_MAX as usize, MSG_SEARCH_LIMIT_MAX_AMOUNT)?;
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SearchChatText {
+    pub text: Option<String>,
+    pub member: Option<String>, // Nickname of the author of the message.
+    pub stream_id: Option<i32>, // Search in one stream.
+    pub owner_id: Option<i32>,  // Search in all streams of the owner.
+    pub viewer_id: Option<i32>, // Search only in streams that the user owns or moderates (None - in all streams).
+    pub min_date_created: Option<DateTime<Utc>>,
+    pub max_date_created: Option<DateTime<Utc>>,
+    pub page: u32,
+    pub limit: u32,
+}
+
+impl SearchChatText {
+    // If no stream is specified, the search is carried out in all streams of the current user.
+    pub fn convert(search_chat_text: SearchChatTextDto, user_id: i32, viewer_id: Option<i32>) -> Self {
+        let mut owner_id = search_chat_text.owner_id;
+        if search_chat_text.stream_id.is_none() && owner_id.is_none() {
+            owner_id = Some(user_id);
+        }
+        SearchChatText {
+            text: search_chat_text.text.clone(),
+            member: search_chat_text.member.clone(),
+            stream_id: search_chat_text.stream_id,
+            owner_id,
+            viewer_id,
+            min_date_created: search_chat_text.min_date,
+            max_date_created: search_chat_text.max_date,
+            page: search_chat_text.page.unwrap_or(SEARCH_CHAT_TEXT_PAGE),
+            limit: search_chat_text.limit.unwrap_or(SEARCH_CHAT_TEXT_LIMIT),
+        }
+    }
+}
+
+// * SearchChatTextDto *
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchChatTextDto {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub member: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream_id: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner_id: Option<i32>,
+    #[serde(default, with = "serial_datetime_option", skip_serializing_if = "Option::is_none")]
+    pub min_date: Option<DateTime<Utc>>,
+    #[serde(default, with = "serial_datetime_option", skip_serializing_if = "Option::is_none")]
+    pub max_date: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+impl Validator for SearchChatTextDto {
+    // Check the model against the required conditions.
+    fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors: Vec<Option<ValidationError>> = vec![];
+
+        if let Some(text) = &self.text {
+            errors.push(validate_search_text(text).err());
+        }
+        if self.text.is_none() && self.member.is_none() {
+            let fields = "text, member";
+            let msg = MSG_SEARCH_ONE_OPTIONAL_MUST_PRESENT;
+            errors.push(ValidationChecks::one_optional_fields_must_present(&[false, false], fields, msg).err());
+        }
+        if let Some(page) = self.page {
+            errors.push(validate_search_page(page).err());
+        }
+        if let Some(limit) = self.limit {
+            errors.push(validate_search_limit(limit).err());
+        }
+
+        self.filter_errors(errors)
+    }
+}
+
+// ** Model: "ChatMessageFound". Used to return the found "chat_message" with highlighted matches. **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, QueryableByName)]
+#[diesel(table_name = schema::chat_messages)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ChatMessageFound {
+    pub id: i32,
+    pub stream_id: i32,
+    pub user_id: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    #[diesel(column_name = "user_name")]
+    pub user_name: String,
+    pub msg: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    #[diesel(column_name = "highlight")]
+    pub highlight: Option<String>, // The text of the message (HTML-escaped) in which the matches are highlighted with the "<mark>" tag.
+    pub date_created: DateTime<Utc>,
+    pub date_changed: Option<DateTime<Utc>>,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    #[diesel(column_name = "cnt_total")]
+    pub cnt_total: i32, // The total number of found messages (on all pages).
+}
+
+impl ChatMessageFound {
+    #[rustfmt::skip]
+    pub fn new(id: i32, stream_id: i32, user_id: i32, user_name: &str, msg: &str, highlight: &str, date_created: DateTime<Utc>) -> Self {
+        ChatMessageFound {
+            id,
+            stream_id,
+            user_id,
+            user_name: user_name.to_string(),
+            msg: Some(msg.to_string()),
+            highlight: Some(highlight.to_string()),
+            date_created,
+            date_changed: None,
+            cnt_total: 0,
+        }
+    }
+}
+
+// * ChatMessageFoundDto *
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatMessageFoundDto {
+    pub id: i32,
+    pub stream_id: i32,
+    pub member: String,
+    pub msg: String,
+    pub highlight: String,
+    #[serde(with = "serial_datetime")]
+    pub date: DateTime<Utc>,
+    #[serde(default, with = "serial_datetime_option", skip_serializing_if = "Option::is_none")]
+    pub date_edt: Option<DateTime<Utc>>,
+}
+
+impl From<ChatMessageFound> for ChatMessageFoundDto {
+    fn from(chat_message: ChatMessageFound) -> Self {
+        let msg = chat_message.msg.unwrap_or_default();
+        ChatMessageFoundDto {
+            id: chat_message.id,
+            stream_id: chat_message.stream_id,
+            member: chat_message.user_name.clone(),
+            highlight: chat_message.highlight.unwrap_or(msg.clone()),
+            msg,
+            date: chat_message.date_created,
+            date_edt: chat_message.date_changed,
+        }
+    }
+}
+
+// * ChatMessageFoundPageDto *
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatMessageFoundPageDto {
+    pub list: Vec<ChatMessageFoundDto>,
+    #[schema(example = 20)]
+    pub limit: u32,
+    #[schema(example = 2)]
+    pub count: u32,
+    #[schema(example = 1)]
+    pub page: u32,
+    #[schema(example = 1)]
+    pub pages: u32,
+}
+
+// ** Section: Chat transcript export. Used: ChatMessageOrm::get_chat_stream_times(), filter_chat_transcript() **
+
+// The number of messages requested from the database at one time during the export.
+pub const EXPORT_CHAT_PAGE_SIZE: usize = 200;
+// The header of the transcript in CSV format.
+pub const TRANSCRIPT_CSV_HEADER: &str = "id,time,date,member,msg\r\n";
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptFormat {
+    Json, // default
+    Csv,
+    Txt,
+}
+
+impl TranscriptFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            TranscriptFormat::Json => "application/json",
+            TranscriptFormat::Csv => "text/csv; charset=utf-8",
+            TranscriptFormat::Txt => "text/plain; charset=utf-8",
+        }
+    }
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TranscriptFormat::Json => "json",
+            TranscriptFormat::Csv => "csv",
+            TranscriptFormat::Txt => "txt",
+        }
+    }
+}
+
+// * SearchChatTranscript *
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SearchChatTranscript {
+    pub stream_id: i32,
+    pub after: Option<(DateTime<Utc>, i32)>, // (date_created, id) of the last message of the previous page.
+    pub max_date_created: Option<DateTime<Utc>>,
+    pub limit: Option<usize>,
+}
+
+impl SearchChatTranscript {
+    #[rustfmt::skip]
+    pub fn new(
+        stream_id: i32, after: Option<(DateTime<Utc>, i32)>, max_date_created: Option<DateTime<Utc>>, limit: Option<usize>,
+    ) -> Self {
+        SearchChatTranscript { stream_id, after, max_date_created, limit }
+    }
+}
+
+// * ExportChatMessagesDto *
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportChatMessagesDto {
+    pub stream_id: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<TranscriptFormat>,
+}
+
+// * ChatStreamTimes *
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, QueryableByName)]
+pub struct ChatStreamTimes {
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub stream_id: i32,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub stream_owner: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub stream_state: String,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub is_moderator: bool, // The user is the chat moderator of this stream.
+    #[diesel(sql_type = diesel::sql_types::Timestamptz)]
+    pub starttime: DateTime<Utc>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Timestamptz>)]
+    pub started: Option<DateTime<Utc>>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Timestamptz>)]
+    pub paused: Option<DateTime<Utc>>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Timestamptz>)]
+    pub stopped: Option<DateTime<Utc>>,
+    // The beginnings of the pauses of the stream (according to the history of the stream states).
+    #[diesel(sql_type = diesel::sql_types::Array<diesel::sql_types::Timestamptz>)]
+    pub paused_starts: Vec<DateTime<Utc>>,
+    // The ends of the pauses of the stream (the end of the current pause is None).
+    #[diesel(sql_type = diesel::sql_types::Array<diesel::sql_types::Nullable<diesel::sql_types::Timestamptz>>)]
+    pub paused_ends: Vec<Option<DateTime<Utc>>>,
+}
+
+impl ChatStreamTimes {
+    #[rustfmt::skip]
+    pub fn new(
+        stream_id: i32, stream_owner: i32, stream_state: &str, starttime: DateTime<Utc>, started: Option<DateTime<Utc>>,
+        paused: Option<DateTime<Utc>>, stopped: Option<DateTime<Utc>>,
+    ) -> ChatStreamTimes {
+        ChatStreamTimes {
+            stream_id,
+            stream_owner,
+            stream_state: stream_state.to_string(),
+            is_moderator: false,
+            starttime,
+            started,
+            paused,
+            stopped,
+            paused_starts: vec![],
+            paused_ends: vec![],
+        }
+    }
+    /// The beginning of the stream (if the stream has not started yet, then its scheduled start time).
+    pub fn beginning(&self) -> DateTime<Utc> {
+        self.started.unwrap_or(self.starttime)
+    }
+    /// Get the periods (start, end) when the stream was paused. The current pause has no end.
+    pub fn paused_periods(&self) -> Vec<(DateTime<Utc>, Option<DateTime<Utc>>)> {
+        if !self.paused_starts.is_empty() {
+            // The periods of all pauses are taken from the history of the stream states.
+            let paused_ends = self.paused_ends.iter().cloned().chain(std::iter::repeat(None));
+            return self.paused_starts.iter().cloned().zip(paused_ends).collect();
+        }
+        // There is no history of the stream states, and the "streams" table stores only the beginning of the last pause.
+        // Therefore, the period is known only for the current pause.
+        match self.paused {
+            Some(paused) if self.stream_state == StreamState::Paused.to_string() => vec![(paused, None)],
+            _ => vec![],
+        }
+    }
+    /// Get the time of the message relative to the beginning of the stream, excluding the time of pauses.
+    pub fn offset(&self, date: DateTime<Utc>) -> Duration {
+        let beginning = self.beginning();
+        let mut offset = date - beginning;
+        for (start, end) in self.paused_periods() {
+            let start = start.max(beginning);
+            let end = end.unwrap_or(date).min(date);
+            if start < end {
+                offset -= end - start;
+            }
+        }
+        offset
+    }
+}
+
+// Get the relative time in the format "HH:MM:SS" (a negative value is preceded by "-").
+pub fn format_transcript_time(offset: Duration) -> String {
+    let seconds = offset.num_seconds();
+    let sign = if seconds < 0 { "-" } else { "" };
+    let seconds = seconds.abs();
+    format!("{}{:02}:{:02}:{:02}", sign, seconds / 3600, (seconds % 3600) / 60, seconds % 60)
+}
+
+// Get the value of the CSV field (a value with a separator, quotes or a line break is enclosed in quotes).
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// * ChatTranscriptItemDto *
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatTranscriptItemDto {
+    pub id: i32,
+    pub time: String, // Time relative to the beginning of the stream ("HH:MM:SS").
+    pub member: String,
+    pub msg: String,
+    #[serde(with = "serial_datetime")]
+    pub date: DateTime<Utc>,
+    #[serde(default, with = "serial_datetime_option", skip_serializing_if = "Option::is_none")]
+    pub date_edt: Option<DateTime<Utc>>,
+}
+
+impl ChatTranscriptItemDto {
+    pub fn new(chat_message: ChatMessage, stream_times: &ChatStreamTimes) -> Self {
+        ChatTranscriptItemDto {
+            id: chat_message.id,
+            time: format_transcript_time(stream_times.offset(chat_message.date_created)),
+            member: chat_message.user_name,
+            msg: chat_message.msg.unwrap_or_default(),
+            date: chat_message.date_created,
+            date_edt: chat_message.date_changed,
+        }
+    }
+    /// Get a record of the transcript in CSV format.
+    pub fn to_csv(&self) -> String {
+        let date = self.date.to_rfc3339_opts(SecondsFormat::Millis, true);
+        #[rustfmt::skip]
+        let fields = [self.id.to_string(), self.time.clone(), date, csv_field(&self.member), csv_field(&self.msg)];
+        format!("{}\r\n", fields.join(","))
+    }
+    /// Get a line of the transcript in text format ("[HH:MM:SS] member: msg").
+    pub fn to_txt(&self) -> String {
+        // The continuation lines of the message are indented.
+        format!("[{}] {}: {}\n", self.time, self.member, self.msg.replace('\n', "\n    "))
+    }
+}
+
+// ** Model: "ChatPin". Used to return a pinned message or announcement of the chat. **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, QueryableByName)]
+#[diesel(table_name = schema::chat_pins)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ChatPin {
+    pub id: i32,
+    pub stream_id: i32,
+    pub chat_message_id: Option<i32>, // ID of the pinned message. (None - the pin is an announcement.)
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    #[diesel(column_name = "user_name")]
+    pub user_name: String, // Nickname of the author of the message (or of the announcement).
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    #[diesel(column_name = "msg")]
+    pub msg: String, // Text of the message (or of the announcement).
+    pub date_created: DateTime<Utc>,  // Date of the pin.
+}
+
+impl ChatPin {
+    #[rustfmt::skip]
+    pub fn new(id: i32, stream_id: i32, chat_message_id: Option<i32>, user_name: &str, msg: &str, date_created: DateTime<Utc>) -> Self {
+        ChatPin { id, stream_id, chat_message_id, user_name: user_name.to_string(), msg: msg.to_string(), date_created }
+    }
+}
+
+// ** Model: "CreateChatPin". Used: ChatMessageOrm::create_chat_pin() **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CreateChatPin {
+    pub stream_id: i32,
+    pub user_id: i32,                 // The user who makes the pin.
+    pub chat_message_id: Option<i32>, // ID of the message being pinned.
+    pub msg: Option<String>,          // Text of the announcement. (It is used if "chat_message_id" is None.)
+}
+
+impl CreateChatPin {
+    #[rustfmt::skip]
+    pub fn new(stream_id: i32, user_id: i32, chat_message_id: Option<i32>, msg: Option<String>) -> CreateChatPin {
+        CreateChatPin { stream_id, user_id, chat_message_id, msg }
+    }
 }
 
 // ** Model: "ChatAccess". Used: ChatMessageOrm::get_chat_access() **
@@ -307,15 +1147,26 @@ pub struct ChatAccess {
     #[diesel(sql_type = diesel::sql_types::Bool)]
     #[diesel(column_name = "is_blocked")]
     pub is_blocked: bool,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    #[diesel(column_name = "is_moderator")]
+    pub is_moderator: bool,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Timestamptz>)]
+    #[diesel(column_name = "block_until")]
+    pub block_until: Option<DateTime<Utc>>, // The end of a timed block (None - the block is permanent).
 }
 
 impl ChatAccess {
-    pub fn new(stream_id: i32, stream_owner: i32, stream_state: String, is_blocked: bool) -> ChatAccess {
+    #[rustfmt::skip]
+    pub fn new(
+        stream_id: i32, stream_owner: i32, stream_state: String, is_blocked: bool, is_moderator: bool, block_until: Option<DateTime<Utc>>,
+    ) -> ChatAccess {
         ChatAccess {
             stream_id,
             stream_owner,
             stream_state,
             is_blocked,
+            is_moderator,
+            block_until,
         }
     }
 }
@@ -362,15 +1213,18 @@ pub struct BlockedUserMini {
     #[diesel(column_name = "nickname")]
     pub nickname: String,
     pub block_date: DateTime<Utc>,
+    pub block_until: Option<DateTime<Utc>>,
 }
 
 impl BlockedUserMini {
-    pub fn new(id: i32, user_id: i32, nickname: String, block_date: DateTime<Utc>) -> Self {
+    #[rustfmt::skip]
+    pub fn new(id: i32, user_id: i32, nickname: String, block_date: DateTime<Utc>, block_until: Option<DateTime<Utc>>) -> Self {
         BlockedUserMini {
             id,
             user_id,
             nickname: nickname.clone(),
             block_date: block_date.clone(),
+            block_until: block_until.clone(),
         }
     }
 }
@@ -383,6 +1237,8 @@ pub struct BlockedUserMiniDto {
     pub nickname: String,
     #[serde(with = "serial_datetime")]
     pub block_date: DateTime<Utc>,
+    #[serde(default, with = "serial_datetime_option", skip_serializing_if = "Option::is_none")]
+    pub block_until: Option<DateTime<Utc>>,
 }
 
 impl From<BlockedUserMini> for BlockedUserMiniDto {
@@ -392,6 +1248,7 @@ impl From<BlockedUserMini> for BlockedUserMiniDto {
             user_id: blocked_user.user_id,
             nickname: blocked_user.nickname.clone(),
             block_date: blocked_user.block_date.clone(),
+            block_until: blocked_user.block_until.clone(),
         }
     }
 }
@@ -411,19 +1268,25 @@ pub struct BlockedUser {
     #[diesel(column_name = "email")]
     pub email: String,
     pub block_date: DateTime<Utc>,
+    pub block_until: Option<DateTime<Utc>>,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
     #[diesel(column_name = "avatar")]
     pub avatar: Option<String>,
 }
 
 impl BlockedUser {
-    pub fn new(id: i32, user_id: i32, nickname: String, email: String, block_date: DateTime<Utc>, avatar: Option<String>) -> Self {
+    #[rustfmt::skip]
+    pub fn new(
+        id: i32, user_id: i32, nickname: String, email: String, block_date: DateTime<Utc>, block_until: Option<DateTime<Utc>>,
+        avatar: Option<String>,
+    ) -> Self {
         BlockedUser {
             id,
             user_id,
             nickname,
             email,
             block_date,
+            block_until,
             avatar,
         }
     }
@@ -438,6 +1301,8 @@ pub struct BlockedUserDto {
     pub email: String,
     #[serde(with = "serial_datetime")]
     pub block_date: DateTime<Utc>,
+    #[serde(default, with = "serial_datetime_option", skip_serializing_if = "Option::is_none")]
+    pub block_until: Option<DateTime<Utc>>,
     pub avatar: Option<String>,
 }
 
@@ -449,6 +1314,7 @@ impl From<BlockedUser> for BlockedUserDto {
             nickname: blocked_user.nickname.clone(),
             email: blocked_user.email,
             block_date: blocked_user.block_date.clone(),
+            block_until: blocked_user.block_until.clone(),
             avatar: blocked_user.avatar.clone(),
         }
     }
@@ -470,14 +1336,19 @@ pub struct CreateBlockedUser {
     pub owner_id: i32,
     pub blocked_id: Option<i32>,
     pub blocked_nickname: Option<String>,
+    pub block_until: Option<DateTime<Utc>>, // None - the block is permanent.
 }
 
 impl CreateBlockedUser {
-    pub fn new(owner_id: i32, blocked_id: Option<i32>, blocked_nickname: Option<String>) -> CreateBlockedUser {
+    #[rustfmt::skip]
+    pub fn new(
+        owner_id: i32, blocked_id: Option<i32>, blocked_nickname: Option<String>, block_until: Option<DateTime<Utc>>,
+    ) -> CreateBlockedUser {
         CreateBlockedUser {
             owner_id,
             blocked_id,
             blocked_nickname,
+            block_until,
         }
     }
 }
@@ -507,6 +1378,9 @@ impl Validator for CreateBlockedUser {
 pub struct CreateBlockedUserDto {
     pub blocked_id: Option<i32>,
     pub blocked_nickname: Option<String>,
+    // Block duration in seconds. (If absent, the block is permanent.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_duration: Option<i32>,
 }
 
 impl Validator for CreateBlockedUserDto {
@@ -517,6 +1391,9 @@ impl Validator for CreateBlockedUserDto {
         if let Some(nickname_val) = &self.blocked_nickname {
             errors.push(validate_blocked_nickname(nickname_val).err());
         }
+        if let Some(block_duration) = self.block_duration {
+            errors.push(validate_block_duration(block_duration).err());
+        }
         if self.blocked_nickname.is_none() && self.blocked_id.is_none() {
             let fields = "blocked_id, blocked_nickname";
             let msg = MSG_BLOCKED_ONE_OPTIONAL_MUST_PRESENT;
@@ -614,3 +1491,44 @@ impl ChatMessageMock {
         (0..(BLOCKED_NICKNAME_MAX + 1)).map(|_| 'a').collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ** is_emoji **
+
+    #[test]
+    fn test_is_emoji_valid() {
+        // A pictographic character, with VS16 and with a skin tone.
+        assert!(is_emoji("👍"));
+        assert!(is_emoji("❤️"));
+        assert!(is_emoji("👍🏽"));
+        // The sequences joined by ZWJ.
+        assert!(is_emoji("👨‍👩‍👧‍👦"));
+        assert!(is_emoji("🏳️‍🌈"));
+        assert!(is_emoji("👩🏽‍💻"));
+        // The flags and the keycap.
+        assert!(is_emoji("🇺🇦"));
+        assert!(is_emoji("🏴\u{E0067}\u{E0062}\u{E0073}\u{E0063}\u{E0074}\u{E007F}"));
+        assert!(is_emoji("1️⃣"));
+        assert!(is_emoji("#\u{20E3}"));
+    }
+    #[test]
+    fn test_is_emoji_invalid() {
+        assert!(!is_emoji(""));
+        assert!(!is_emoji("a"));
+        assert!(!is_emoji("1"));
+        assert!(!is_emoji("<b>"));
+        // Several emoji or an emoji with a text.
+        assert!(!is_emoji("👍👍"));
+        assert!(!is_emoji("👍 "));
+        assert!(!is_emoji("👍a"));
+        assert!(!is_emoji("🇺🇦🇺"));
+        // The incomplete sequences.
+        assert!(!is_emoji("👨\u{200D}"));
+        assert!(!is_emoji("\u{200D}👨"));
+        assert!(!is_emoji("\u{1F3FB}"));
+        assert!(!is_emoji("🏴\u{E0067}\u{E0062}"));
+    }
+}
@@ -1,28 +1,68 @@
 use vrb_dbase::dbase::DbPool;
 
 use crate::chat_message_models::{
-    BlockedName, BlockedUser, BlockedUserMini, ChatAccess, ChatMessage, ChatMessageLog, CreateBlockedUser, CreateChatMessage, DeleteBlockedUser, ModifyChatMessage, SearchChatMessage
+    BlockedName, BlockedUser, BlockedUserMini, ChatAccess, ChatMention, ChatMessage, ChatMessageFound, ChatMessageLog, ChatMessageReaction, ChatMessageReactionDelta, ChatPin, ChatStreamTimes, CreateBlockedUser, CreateChatMessage, CreateChatPin, DeleteBlockedUser, MentionedUser, ModifyChatMessage, ModifyChatMessageReaction, SearchChatMessage, SearchChatText, SearchChatTranscript
 };
 
 pub trait ChatMessageOrm {
     /// Get a list of "chat_message_log" for the specified "chat_message_id".
     fn get_chat_message_logs(&self, chat_message_id: i32) -> Result<Vec<ChatMessageLog>, String>;
 
+    /// Get an entity (chat_message) by ID.
+    fn get_chat_message(&self, id: i32) -> Result<Option<ChatMessage>, String>;
+
     /// Filter entities (chat_messages) by specified parameters.
     fn filter_chat_messages(&self, search_chat_message: SearchChatMessage) -> Result<Vec<ChatMessage>, String>;
 
+    /// Search for entities (chat_messages) by text and (or) by author, page by page.
+    /// Returns the total number of found messages and the messages of the specified page.
+    fn search_chat_messages(&self, search_chat_text: SearchChatText) -> Result<(u32, Vec<ChatMessageFound>), String>;
+
     /// Add a new entry (chat_message).
     fn create_chat_message(&self, create_chat_message: CreateChatMessage) -> Result<Option<ChatMessage>, String>;
 
     /// Modify an entity (chat_message).
-    fn modify_chat_message(&self, id: i32, user_id: i32, modify_chat_message: ModifyChatMessage) -> Result<Option<ChatMessage>, String>;
+    /// If "opt_user_id" is not specified, the message is changed regardless of its author.
+    fn modify_chat_message(&self, id: i32, opt_user_id: Option<i32>, modify_chat_message: ModifyChatMessage) -> Result<Option<ChatMessage>, String>;
 
     /// Delete an entity (chat_message).
-    fn delete_chat_message(&self, id: i32, user_id: i32) -> Result<Option<ChatMessage>, String>;
+    /// If "opt_user_id" is not specified, the message is deleted regardless of its author.
+    fn delete_chat_message(&self, id: i32, opt_user_id: Option<i32>) -> Result<Option<ChatMessage>, String>;
+
+    /// Get the number of reactions (of each emoji) for the specified messages.
+    fn filter_chat_message_reactions(&self, chat_message_ids: &[i32]) -> Result<Vec<ChatMessageReaction>, String>;
+
+    /// Add or remove the user's reaction (chat_message_reaction).
+    /// The user can add no more than REACTIONS_PER_USER_MAX different reactions to the message.
+    fn modify_chat_message_reaction(&self, modify: ModifyChatMessageReaction) -> Result<Option<ChatMessageReactionDelta>, String>;
+
+    /// Add mentions of users (by nickname) to the message of the specified author.
+    fn create_chat_message_mentions(&self, id: i32, user_id: i32, nicknames: &[String]) -> Result<Vec<MentionedUser>, String>;
+
+    /// Get a list of unread mentions of the user.
+    fn filter_chat_mentions(&self, user_id: i32) -> Result<Vec<ChatMention>, String>;
+
+    /// Mark the user's mentions as read. (If "opt_ids" is None, then all unread mentions.)
+    fn read_chat_mentions(&self, user_id: i32, opt_ids: Option<Vec<i32>>) -> Result<Vec<ChatMention>, String>;
+
+    /// Get a list of pins (pinned messages and announcements) of the chat.
+    fn filter_chat_pins(&self, stream_id: i32) -> Result<Vec<ChatPin>, String>;
+
+    /// Add a new entry (chat_pin). If the message is already pinned, the existing pin is returned.
+    fn create_chat_pin(&self, create_chat_pin: CreateChatPin) -> Result<Option<ChatPin>, String>;
+
+    /// Delete an entity (chat_pin).
+    fn delete_chat_pin(&self, id: i32, stream_id: i32) -> Result<Option<ChatPin>, String>;
 
     /// Get chat access information. (ChatAccess)
     fn get_chat_access(&self, stream_id: i32, opt_user_id: Option<i32>) -> Result<Option<ChatAccess>, String>;
 
+    /// Get the times of the stream (the chat) to calculate the time of messages relative to its beginning. (ChatStreamTimes)
+    fn get_chat_stream_times(&self, stream_id: i32, opt_user_id: Option<i32>) -> Result<Option<ChatStreamTimes>, String>;
+
+    /// Get the next page of entities (chat_messages) for the transcript, sorted by "date_created" and "id".
+    fn filter_chat_transcript(&self, search_chat_transcript: SearchChatTranscript) -> Result<Vec<ChatMessage>, String>;
+
     /// Get a list of blocked users (nickname only).
     fn get_blocked_nicknames(&self, owner_id: i32) -> Result<Vec<BlockedName>, String>;
 
@@ -56,7 +96,7 @@ pub mod impls {
 
     use crate::{
         chat_message_models::{
-            BlockedName, BlockedUser, BlockedUserMini, ChatAccess, ChatMessage, ChatMessageLog, CreateBlockedUser, CreateChatMessage, DeleteBlockedUser, ModifyChatMessage, SearchChatMessage
+            BlockedName, BlockedUser, BlockedUserMini, ChatAccess, ChatMention, ChatMessage, ChatMessageFound, ChatMessageLog, ChatMessageReaction, ChatMessageReactionDelta, ChatPin, ChatStreamTimes, CreateBlockedUser, CreateChatMessage, CreateChatPin, DeleteBlockedUser, MentionedUser, ModifyChatMessage, ModifyChatMessageReaction, REACTIONS_PER_USER_MAX, SearchChatMessage, SearchChatText, SearchChatTranscript
         },
         chat_message_orm::ChatMessageOrm,
     };
@@ -94,6 +134,26 @@ pub mod impls {
             Ok(list)
         }
 
+        /// Get an entity (chat_message) by ID.
+        fn get_chat_message(&self, id: i32) -> Result<Option<ChatMessage>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            let query = diesel::sql_query("select * from get_chat_message($1);").bind::<sql_types::Integer, _>(id); // $1
+
+            // Run a query using Diesel to find the entity by ID and return it.
+            let opt_chat_message = query
+                .get_result::<ChatMessage>(&mut conn)
+                .optional()
+                .map_err(|e| format!("get_chat_message: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("get_chat_message() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(opt_chat_message)
+        }
+
         /// Filter entities (chat_messages) by specified parameters.
         fn filter_chat_messages(&self, flt_chat_msg: SearchChatMessage) -> Result<Vec<ChatMessage>, String> {
             let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
@@ -101,12 +161,13 @@ pub mod impls {
             let mut conn = self.get_conn()?;
             let opt_limit = flt_chat_msg.limit.map(|v| i32::try_from(v).unwrap());
 
-            let query = diesel::sql_query("select * from filter_chat_messages($1,$2,$3,$4,$5);")
+            let query = diesel::sql_query("select * from filter_chat_messages($1,$2,$3,$4,$5,$6);")
                 .bind::<sql_types::Integer, _>(flt_chat_msg.stream_id) //$1
                 .bind::<sql_types::Nullable<sql_types::Bool>, _>(flt_chat_msg.is_sort_des) // $2
                 .bind::<sql_types::Nullable<sql_types::Timestamptz>, _>(flt_chat_msg.min_date_created) // $3
                 .bind::<sql_types::Nullable<sql_types::Timestamptz>, _>(flt_chat_msg.max_date_created) // $4
-                .bind::<sql_types::Nullable<sql_types::Integer>, _>(opt_limit); // $5
+                .bind::<sql_types::Nullable<sql_types::Integer>, _>(opt_limit) // $5
+                .bind::<sql_types::Nullable<sql_types::Integer>, _>(flt_chat_msg.thread_id); // $6
 
             // Run a query using Diesel to find a list of entities (ChatMessage) based on the given parameters.
             let chat_messages: Vec<ChatMessage> = query
@@ -121,6 +182,48 @@ pub mod impls {
             Ok(chat_messages)
         }
 
+        /// Search for entities (chat_messages) by text and (or) by author, page by page.
+        /// Returns the total number of found messages and the messages of the specified page.
+        fn search_chat_messages(&self, search_chat_text: SearchChatText) -> Result<(u32, Vec<ChatMessageFound>), String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+            let page = i32::try_from(search_chat_text.page.max(1)).unwrap_or(1);
+            let limit = i32::try_from(search_chat_text.limit).unwrap_or(i32::MAX);
+            let offset = (page - 1).saturating_mul(limit);
+
+            let search_query = |rec_offset: i32, rec_limit: i32| {
+                diesel::sql_query("select * from search_chat_messages($1,$2,$3,$4,$5,$6,$7,$8,$9);")
+                    .bind::<sql_types::Nullable<sql_types::Text>, _>(search_chat_text.text.clone()) // $1
+                    .bind::<sql_types::Nullable<sql_types::Text>, _>(search_chat_text.member.clone()) // $2
+                    .bind::<sql_types::Nullable<sql_types::Integer>, _>(search_chat_text.stream_id) // $3
+                    .bind::<sql_types::Nullable<sql_types::Integer>, _>(search_chat_text.owner_id) // $4
+                    .bind::<sql_types::Nullable<sql_types::Integer>, _>(search_chat_text.viewer_id) // $5
+                    .bind::<sql_types::Nullable<sql_types::Timestamptz>, _>(search_chat_text.min_date_created) // $6
+                    .bind::<sql_types::Nullable<sql_types::Timestamptz>, _>(search_chat_text.max_date_created) // $7
+                    .bind::<sql_types::Integer, _>(rec_offset) // $8
+                    .bind::<sql_types::Integer, _>(rec_limit) // $9
+            };
+            // Run a query using Diesel to find a list of entities (ChatMessageFound) based on the given parameters.
+            let chat_messages: Vec<ChatMessageFound> = search_query(offset, limit)
+                .load(&mut conn)
+                .map_err(|e| format!("search_chat_messages: {}", e.to_string()))?;
+
+            let mut count = chat_messages.first().map(|v| v.cnt_total).unwrap_or(0);
+            // If the page is outside the found list, then the total number is obtained separately.
+            if chat_messages.is_empty() && offset > 0 {
+                let chat_messages2: Vec<ChatMessageFound> = search_query(0, 1)
+                    .load(&mut conn)
+                    .map_err(|e| format!("search_chat_messages: (count) {}", e.to_string()))?;
+                count = chat_messages2.first().map(|v| v.cnt_total).unwrap_or(0);
+            }
+
+            if let Some(timer) = timer {
+                info!("search_chat_messages() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok((u32::try_from(count).unwrap_or(0), chat_messages))
+        }
+
         /// Add a new entry (chat_message).
         fn create_chat_message(&self, create_chat_message: CreateChatMessage) -> Result<Option<ChatMessage>, String> {
             let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
@@ -134,10 +237,11 @@ pub mod impls {
             // Get a connection from the P2D2 pool.
             let mut conn = self.get_conn()?;
 
-            let query = diesel::sql_query("select * from create_chat_message($1,$2,$3);")
+            let query = diesel::sql_query("select * from create_chat_message($1,$2,$3,$4);")
                 .bind::<sql_types::Integer, _>(create_chat_message.stream_id) // $1
                 .bind::<sql_types::Integer, _>(create_chat_message.user_id) // $2
-                .bind::<sql_types::Text, _>(create_chat_message.msg); // $3
+                .bind::<sql_types::Text, _>(create_chat_message.msg) // $3
+                .bind::<sql_types::Nullable<sql_types::Integer>, _>(create_chat_message.reply_to); // $4
 
             // Run a query with Diesel to create a new user and return it.
             let opt_chat_message = query
@@ -155,7 +259,7 @@ pub mod impls {
         fn modify_chat_message(
             &self,
             id: i32,
-            user_id: i32,
+            opt_user_id: Option<i32>,
             modify_chat_message: ModifyChatMessage,
         ) -> Result<Option<ChatMessage>, String> {
             let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
@@ -171,7 +275,7 @@ pub mod impls {
 
             let query = diesel::sql_query("select * from modify_chat_message($1,$2,$3);")
                 .bind::<sql_types::Integer, _>(id) // $1
-                .bind::<sql_types::Integer, _>(user_id) // $2
+                .bind::<sql_types::Nullable<sql_types::Integer>, _>(opt_user_id) // $2
                 .bind::<sql_types::Text, _>(modify_chat_message.msg); // $3
 
             // Run a query with Diesel to modify the entity and return it.
@@ -187,14 +291,14 @@ pub mod impls {
         }
 
         /// Delete an entity (chat_message).
-        fn delete_chat_message(&self, id: i32, user_id: i32) -> Result<Option<ChatMessage>, String> {
+        fn delete_chat_message(&self, id: i32, opt_user_id: Option<i32>) -> Result<Option<ChatMessage>, String> {
             let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
             // Get a connection from the P2D2 pool.
             let mut conn = self.get_conn()?;
 
             let query = diesel::sql_query("select * from delete_chat_message($1,$2);")
                 .bind::<sql_types::Integer, _>(id)
-                .bind::<sql_types::Integer, _>(user_id); // $2
+                .bind::<sql_types::Nullable<sql_types::Integer>, _>(opt_user_id); // $2
 
             // Run a query using Diesel to delete the entity by ID and return it.
             let opt_chat_message = query
@@ -208,6 +312,190 @@ pub mod impls {
             Ok(opt_chat_message)
         }
 
+        /// Get the number of reactions (of each emoji) for the specified messages.
+        fn filter_chat_message_reactions(&self, chat_message_ids: &[i32]) -> Result<Vec<ChatMessageReaction>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            #[rustfmt::skip]
+            let query = diesel::sql_query("select * from filter_chat_message_reactions($1);")
+                .bind::<sql_types::Array<sql_types::Integer>, _>(chat_message_ids.to_vec()); // $1
+
+            // Run a query using Diesel to find a list of entities (ChatMessageReaction).
+            let list: Vec<ChatMessageReaction> = query
+                .load(&mut conn)
+                .map_err(|e| format!("filter_chat_message_reactions: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("filter_chat_message_reactions() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(list)
+        }
+
+        /// Add or remove the user's reaction (chat_message_reaction).
+        fn modify_chat_message_reaction(&self, modify: ModifyChatMessageReaction) -> Result<Option<ChatMessageReactionDelta>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            let validation_res = modify.validate();
+            if let Err(validation_errors) = validation_res {
+                let buff: Vec<String> = validation_errors.into_iter().map(|v| v.message.to_string()).collect();
+                return Err(buff.join("','"));
+            }
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            let max_count: i32 = REACTIONS_PER_USER_MAX.into();
+            let query = diesel::sql_query("select * from modify_chat_message_reaction($1,$2,$3,$4,$5,$6);")
+                .bind::<sql_types::Integer, _>(modify.chat_message_id) // $1
+                .bind::<sql_types::Integer, _>(modify.stream_id) // $2
+                .bind::<sql_types::Integer, _>(modify.user_id) // $3
+                .bind::<sql_types::Text, _>(modify.emoji) // $4
+                .bind::<sql_types::Bool, _>(modify.is_add) // $5
+                .bind::<sql_types::Integer, _>(max_count); // $6
+
+            // Run a query with Diesel to add or remove the reaction and return the change.
+            let opt_reaction_delta = query
+                .get_result::<ChatMessageReactionDelta>(&mut conn)
+                .optional()
+                .map_err(|e| format!("modify_chat_message_reaction: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("modify_chat_message_reaction() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(opt_reaction_delta)
+        }
+
+        /// Add mentions of users (by nickname) to the message of the specified author.
+        fn create_chat_message_mentions(&self, id: i32, user_id: i32, nicknames: &[String]) -> Result<Vec<MentionedUser>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            let query = diesel::sql_query("select * from create_chat_message_mentions($1,$2,$3);")
+                .bind::<sql_types::Integer, _>(id) // $1
+                .bind::<sql_types::Integer, _>(user_id) // $2
+                .bind::<sql_types::Array<sql_types::Text>, _>(nicknames.to_vec()); // $3
+
+            // Run a query using Diesel to add mentions and get a list of the mentioned users.
+            let list: Vec<MentionedUser> = query
+                .load(&mut conn)
+                .map_err(|e| format!("create_chat_message_mentions: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("create_chat_message_mentions() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(list)
+        }
+
+        /// Get a list of unread mentions of the user.
+        fn filter_chat_mentions(&self, user_id: i32) -> Result<Vec<ChatMention>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            #[rustfmt::skip]
+            let query = diesel::sql_query("select * from filter_chat_message_mentions($1);")
+                .bind::<sql_types::Integer, _>(user_id); // $1
+
+            // Run a query using Diesel to find a list of entities (ChatMention).
+            let list: Vec<ChatMention> = query
+                .load(&mut conn)
+                .map_err(|e| format!("filter_chat_message_mentions: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("filter_chat_mentions() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(list)
+        }
+
+        /// Mark the user's mentions as read. (If "opt_ids" is None, then all unread mentions.)
+        fn read_chat_mentions(&self, user_id: i32, opt_ids: Option<Vec<i32>>) -> Result<Vec<ChatMention>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            let query = diesel::sql_query("select * from read_chat_message_mentions($1,$2);")
+                .bind::<sql_types::Integer, _>(user_id) // $1
+                .bind::<sql_types::Nullable<sql_types::Array<sql_types::Integer>>, _>(opt_ids); // $2
+
+            // Run a query using Diesel to mark mentions as read.
+            let list: Vec<ChatMention> = query
+                .load(&mut conn)
+                .map_err(|e| format!("read_chat_message_mentions: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("read_chat_mentions() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(list)
+        }
+
+        /// Get a list of pins (pinned messages and announcements) of the chat.
+        fn filter_chat_pins(&self, stream_id: i32) -> Result<Vec<ChatPin>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            #[rustfmt::skip]
+            let query = diesel::sql_query("select * from filter_chat_pins($1);")
+                .bind::<sql_types::Integer, _>(stream_id); // $1
+
+            // Run a query using Diesel to find a list of entities (ChatPin).
+            let list: Vec<ChatPin> = query.load(&mut conn).map_err(|e| format!("filter_chat_pins: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("filter_chat_pins() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(list)
+        }
+
+        /// Add a new entry (chat_pin). If the message is already pinned, the existing pin is returned.
+        fn create_chat_pin(&self, create_chat_pin: CreateChatPin) -> Result<Option<ChatPin>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            let query = diesel::sql_query("select * from create_chat_pin($1,$2,$3,$4);")
+                .bind::<sql_types::Integer, _>(create_chat_pin.stream_id) // $1
+                .bind::<sql_types::Integer, _>(create_chat_pin.user_id) // $2
+                .bind::<sql_types::Nullable<sql_types::Integer>, _>(create_chat_pin.chat_message_id) // $3
+                .bind::<sql_types::Nullable<sql_types::Text>, _>(create_chat_pin.msg); // $4
+
+            // Run a query using Diesel to add a new entry (chat_pin).
+            let opt_chat_pin = query
+                .get_result::<ChatPin>(&mut conn)
+                .optional()
+                .map_err(|e| format!("create_chat_pin: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("create_chat_pin() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(opt_chat_pin)
+        }
+
+        /// Delete an entity (chat_pin).
+        fn delete_chat_pin(&self, id: i32, stream_id: i32) -> Result<Option<ChatPin>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            let query = diesel::sql_query("select * from delete_chat_pin($1,$2);")
+                .bind::<sql_types::Integer, _>(id) // $1
+                .bind::<sql_types::Integer, _>(stream_id); // $2
+
+            // Run a query using Diesel to delete an entity (chat_pin).
+            let opt_chat_pin = query
+                .get_result::<ChatPin>(&mut conn)
+                .optional()
+                .map_err(|e| format!("delete_chat_pin: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("delete_chat_pin() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(opt_chat_pin)
+        }
+
         /// Get chat access information. (ChatAccess)
         fn get_chat_access(&self, stream_id: i32, opt_user_id: Option<i32>) -> Result<Option<ChatAccess>, String> {
             let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
@@ -229,6 +517,53 @@ pub mod impls {
             Ok(opt_chat_access)
         }
 
+        /// Get the times of the stream (the chat) to calculate the time of messages relative to its beginning. (ChatStreamTimes)
+        fn get_chat_stream_times(&self, stream_id: i32, opt_user_id: Option<i32>) -> Result<Option<ChatStreamTimes>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            let query = diesel::sql_query("select * from get_chat_stream_times($1,$2);")
+                .bind::<sql_types::Integer, _>(stream_id) // $1
+                .bind::<sql_types::Nullable<sql_types::Integer>, _>(opt_user_id); // $2
+
+            let opt_chat_stream_times = query
+                .get_result::<ChatStreamTimes>(&mut conn)
+                .optional()
+                .map_err(|e| format!("get_chat_stream_times: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("get_chat_stream_times() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(opt_chat_stream_times)
+        }
+
+        /// Get the next page of entities (chat_messages) for the transcript, sorted by "date_created" and "id".
+        fn filter_chat_transcript(&self, search_chat_transcript: SearchChatTranscript) -> Result<Vec<ChatMessage>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+            let opt_after_date = search_chat_transcript.after.map(|(date_created, _id)| date_created);
+            let opt_after_id = search_chat_transcript.after.map(|(_date_created, id)| id);
+            let opt_limit = search_chat_transcript.limit.map(|v| i32::try_from(v).unwrap());
+
+            let query = diesel::sql_query("select * from filter_chat_transcript($1,$2,$3,$4,$5);")
+                .bind::<sql_types::Integer, _>(search_chat_transcript.stream_id) // $1
+                .bind::<sql_types::Nullable<sql_types::Timestamptz>, _>(opt_after_date) // $2
+                .bind::<sql_types::Nullable<sql_types::Integer>, _>(opt_after_id) // $3
+                .bind::<sql_types::Nullable<sql_types::Timestamptz>, _>(search_chat_transcript.max_date_created) // $4
+                .bind::<sql_types::Nullable<sql_types::Integer>, _>(opt_limit); // $5
+
+            // Run a query using Diesel to find a list of entities (ChatMessage) based on the given parameters.
+            let chat_messages: Vec<ChatMessage> =
+                query.load(&mut conn).map_err(|e| format!("filter_chat_transcript: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("filter_chat_transcript() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(chat_messages)
+        }
+
         /// Get a list of blocked users (nickname only).
         fn get_blocked_nicknames(&self, owner_id: i32) -> Result<Vec<BlockedName>, String> {
             let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
@@ -288,10 +623,11 @@ pub mod impls {
             // Get a connection from the P2D2 pool.
             let mut conn = self.get_conn()?;
 
-            let query = diesel::sql_query("select * from create_blocked_user($1,$2,$3);")
+            let query = diesel::sql_query("select * from create_blocked_user($1,$2,$3,$4);")
                 .bind::<sql_types::Integer, _>(create_blocked_user.owner_id) // $1
                 .bind::<sql_types::Nullable<sql_types::Integer>, _>(create_blocked_user.blocked_id) // $2
-                .bind::<sql_types::Nullable<sql_types::Text>, _>(create_blocked_user.blocked_nickname); // $3
+                .bind::<sql_types::Nullable<sql_types::Text>, _>(create_blocked_user.blocked_nickname) // $3
+                .bind::<sql_types::Nullable<sql_types::Timestamptz>, _>(create_blocked_user.block_until); // $4
 
             // Run a query with Diesel to create a new user and return it.
             let opt_blocked_user_mini = query
@@ -346,10 +682,11 @@ pub mod tests {
     use chrono::{DateTime, Duration, SubsecRound, Timelike, Utc};
     use vrb_authent::user_orm::tests::{USER1_ID, USER1_NAME, USER2_ID, USER2_NAME, USER3_ID, USER3_NAME, USER4_ID, USER4_NAME};
     use vrb_common::validators::Validator;
+    use vrb_dbase::enm_stream_state::StreamState;
 
     use crate::{
         chat_message_models::{
-            BlockedName, BlockedUser, BlockedUserMini, ChatAccess, ChatMessage, ChatMessageLog, CreateBlockedUser, CreateChatMessage, DeleteBlockedUser, ModifyChatMessage, SearchChatMessage
+            BlockedName, BlockedUser, BlockedUserMini, ChatAccess, ChatMention, ChatMessage, ChatMessageFound, ChatMessageLog, ChatMessageReaction, ChatMessageReactionDelta, ChatPin, ChatStreamTimes, CreateBlockedUser, CreateChatMessage, CreateChatPin, DeleteBlockedUser, MentionedUser, ModifyChatMessage, ModifyChatMessageReaction, REACTIONS_PER_USER_MAX, SearchChatMessage, SearchChatText, SearchChatTranscript
         },
         chat_message_orm::ChatMessageOrm,
     };
@@ -358,6 +695,8 @@ pub mod tests {
     pub const CHAT_MESSAGE_ID: i32 = 1500;
     pub const CHAT_MESSAGE_LOG_ID: i32 = 1600;
     pub const BLOCKED_USER_ID: i32 = 1700;
+    pub const MENTION_ID: i32 = 1800;
+    pub const CHAT_PIN_ID: i32 = 1900;
 
     pub const STREAM1_ID: i32 = 1; // Owner user idx 0 (live: true)  1100 oliver_taylor
     pub const STREAM2_ID: i32 = 2; // Owner user idx 1 (live: true)  1101 robert_brown
@@ -378,6 +717,7 @@ pub mod tests {
         pub nickname: String,
         pub email: String,
         pub block_date: DateTime<Utc>,
+        pub block_until: Option<DateTime<Utc>>, // None - the block is permanent.
         pub avatar: String,    
     }
 
@@ -392,9 +732,14 @@ pub mod tests {
                 nickname,
                 email,
                 block_date,
+                block_until: None,
                 avatar,
             }
         }
+        /// Checking that the block has not expired yet.
+        pub fn is_active(&self) -> bool {
+            self.block_until.map(|block_until| block_until > Utc::now()).unwrap_or(true)
+        }
     }
 
     impl Into<BlockedUser> for BlockedData {
@@ -405,6 +750,7 @@ pub mod tests {
                 nickname: self.nickname.clone(),
                 email: self.email.clone(),
                 block_date: self.block_date.clone(),
+                block_until: self.block_until.clone(),
                 avatar: if self.avatar.len() > 0 { Some(self.avatar.clone()) } else { None },
             }
         }
@@ -417,16 +763,52 @@ pub mod tests {
                 user_id: self.user_id,
                 nickname: self.nickname.clone(),
                 block_date: self.block_date.clone(),
+                block_until: self.block_until.clone(),
             }
         }
     }
 
+    #[derive(Debug, Clone)]
+    pub struct ReactionData {
+        pub chat_message_id: i32,
+        pub user_id: i32,
+        pub emoji: String,
+    }
+
+    impl ReactionData {
+        pub fn new(chat_message_id: i32, user_id: i32, emoji: &str) -> Self {
+            ReactionData {
+                chat_message_id,
+                user_id,
+                emoji: emoji.to_string(),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct MentionData {
+        pub id: i32,
+        pub chat_message_id: i32,
+        pub user_id: i32,
+        pub date_read: Option<DateTime<Utc>>,
+    }
+
+    impl MentionData {
+        #[rustfmt::skip]
+        pub fn new(id: i32, chat_message_id: i32, user_id: i32, date_read: Option<DateTime<Utc>>) -> Self {
+            MentionData { id, chat_message_id, user_id, date_read }
+        }
+    }
+
     #[derive(Debug, Clone)]
     pub struct ChatMessageOrmApp {
         pub chat_message_vec: Vec<ChatMessage>,
         pub chat_message_log_map: HashMap<i32, Vec<ChatMessageLog>>,
         pub blocked_user_vec: Box<RefCell<Vec<BlockedData>>>,
         pub user_vec: Vec<UserMini>,
+        pub reaction_vec: Box<RefCell<Vec<ReactionData>>>,
+        pub mention_vec: Box<RefCell<Vec<MentionData>>>,
+        pub pin_vec: Box<RefCell<Vec<ChatPin>>>,
     }
 
     fn round_subsecs(d1: DateTime<Utc>) -> DateTime<Utc> {
@@ -441,6 +823,9 @@ pub mod tests {
                 chat_message_log_map: HashMap::new(),
                 blocked_user_vec: Box::new(RefCell::new(Vec::new())),
                 user_vec: Vec::new(),
+                reaction_vec: Box::new(RefCell::new(Vec::new())),
+                mention_vec: Box::new(RefCell::new(Vec::new())),
+                pin_vec: Box::new(RefCell::new(Vec::new())),
             }
         }
         /// Create a new instance with the specified ChatMessage list.
@@ -449,6 +834,9 @@ pub mod tests {
             chat_message_log_list: &[ChatMessageLog],
             blocked_user_list: &[BlockedData],
             users_list: &[UserMini],
+            reaction_list: &[ReactionData],
+            mention_list: &[MentionData],
+            pin_list: &[ChatPin],
         ) -> Self {
             let mut chat_message_vec: Vec<ChatMessage> = Vec::new();
             let mut chat_message_log_map: HashMap<i32, Vec<ChatMessageLog>> = HashMap::new();
@@ -471,7 +859,7 @@ pub mod tests {
             for (idx, chat_message) in chat_message_list.iter().enumerate() {
                 let delta: i32 = idx.try_into().unwrap();
                 let id = CHAT_MESSAGE_ID + delta;
-                let new_chat_message = ChatMessage::new(
+                let mut new_chat_message = ChatMessage::new(
                     id,
                     chat_message.stream_id,
                     chat_message.user_id,
@@ -480,8 +868,9 @@ pub mod tests {
                     round_subsecs(chat_message.date_created.clone()),
                     chat_message.date_changed.clone().map(|d| round_subsecs(d)),
                     chat_message.date_removed.clone().map(|d| round_subsecs(d)),
+                    0,
                 );
-                chat_message_vec.push(new_chat_message);
+                new_chat_message.reply_to = chat_message.reply_to;
 
                 if chat_message.date_changed.is_some() {
                     if let Some(ch_msg_lg_vec) = tmp_ch_msg_lg_map.get(&chat_message.id) {
@@ -498,15 +887,26 @@ pub mod tests {
                             ch_msg_log_list.push(new_ch_msg_log);
                         }
 
+                        new_chat_message.cnt_logs = ch_msg_log_list.len().try_into().unwrap();
                         chat_message_log_map.insert(id, ch_msg_log_list);
                     }
                 }
+                chat_message_vec.push(new_chat_message);
+            }
+            // Get the author and the text of the parent message (the text is absent if the parent message was deleted).
+            let parent_vec = chat_message_vec.clone();
+            for chat_message in chat_message_vec.iter_mut() {
+                let opt_parent = chat_message.reply_to.and_then(|reply_to| parent_vec.iter().find(|v| v.id == reply_to));
+                if let Some(parent) = opt_parent {
+                    chat_message.reply_user_name = Some(parent.user_name.clone());
+                    chat_message.reply_msg = if parent.date_removed.is_none() { parent.msg.clone() } else { None };
+                }
             }
 
             let mut blocked_user_vec: Vec<BlockedData> = Vec::new();
             for (idx, blocked_data) in blocked_user_list.iter().enumerate() {
                 let delta: i32 = idx.try_into().unwrap();
-                let new_blocked_user = BlockedData::new(
+                let mut new_blocked_user = BlockedData::new(
                     BLOCKED_USER_ID + delta,
                     blocked_data.owner_id,
                     blocked_data.user_id,
@@ -515,6 +915,7 @@ pub mod tests {
                     round_subsecs(blocked_data.block_date.clone()),
                     blocked_data.avatar.clone(),
                 );
+                new_blocked_user.block_until = blocked_data.block_until.map(|v| round_subsecs(v));
                 blocked_user_vec.push(new_blocked_user);
             }
             let user_vec: Vec<UserMini> = Vec::from(users_list);
@@ -524,12 +925,28 @@ pub mod tests {
                 chat_message_log_map,
                 blocked_user_vec: Box::new(RefCell::new(blocked_user_vec)),
                 user_vec,
+                reaction_vec: Box::new(RefCell::new(Vec::from(reaction_list))),
+                mention_vec: Box::new(RefCell::new(Vec::from(mention_list))),
+                pin_vec: Box::new(RefCell::new(Vec::from(pin_list))),
             }
         }
         #[rustfmt::skip]
         pub fn is_stream_id_exists(&self, opt_stream_id: Option<i32>) -> bool {
             if let Some(stream_id) = opt_stream_id { ChatMessageOrmTest::stream_ids().contains(&stream_id) } else { true }
         }
+        /// Checking that the message is the root of the thread or one of the replies (direct or nested) to it.
+        pub fn is_in_thread(&self, chat_message: &ChatMessage, thread_id: i32) -> bool {
+            let mut opt_ch_msg = Some(chat_message.clone());
+            while let Some(ch_msg) = opt_ch_msg {
+                if ch_msg.id == thread_id {
+                    return true;
+                }
+                opt_ch_msg = ch_msg
+                    .reply_to
+                    .and_then(|reply_to| self.chat_message_vec.iter().find(|v| v.id == reply_to).map(|v| v.clone()));
+            }
+            false
+        }
         pub fn find_user_by_id(&self, id: i32) -> Option<UserMini> {
             self.user_vec.iter().find(|v| v.id == id).map(|v| v.clone())
         }
@@ -541,9 +958,18 @@ pub mod tests {
     impl ChatMessageOrm for ChatMessageOrmApp {
         /// Get a list of "chat_message_log" for the specified "chat_message_id".
         fn get_chat_message_logs(&self, chat_message_id: i32) -> Result<Vec<ChatMessageLog>, String> {
-            let opt_chat_message = self.chat_message_vec.iter().find(|chat_msg| (*chat_msg).id == chat_message_id);
-            if let Some(_chat_message) = opt_chat_message {}
-            Ok(vec![])
+            let result = self.chat_message_log_map.get(&chat_message_id).map(|v| v.clone()).unwrap_or(vec![]);
+            Ok(result)
+        }
+
+        /// Get an entity (chat_message) by ID.
+        fn get_chat_message(&self, id: i32) -> Result<Option<ChatMessage>, String> {
+            let opt_chat_message = self
+                .chat_message_vec
+                .iter()
+                .find(|chat_msg| (*chat_msg).id == id)
+                .map(|chat_msg| chat_msg.clone());
+            Ok(opt_chat_message)
         }
 
         /// Add a new entry (chat_message).
@@ -554,12 +980,24 @@ pub mod tests {
             if create_chat_message.msg.len() == 0 || !is_stream_id_exists || !is_user_id_exists {
                 return Ok(None);
             }
+            // The parent message must be in the same chat and must not be deleted.
+            let mut opt_parent: Option<ChatMessage> = None;
+            if let Some(reply_to) = create_chat_message.reply_to {
+                opt_parent = self
+                    .chat_message_vec
+                    .iter()
+                    .find(|v| v.id == reply_to && v.stream_id == create_chat_message.stream_id && v.date_removed.is_none())
+                    .map(|v| v.clone());
+                if opt_parent.is_none() {
+                    return Ok(None);
+                }
+            }
 
             let idx: i32 = self.chat_message_vec.len().try_into().unwrap();
             let chat_message_id: i32 = CHAT_MESSAGE_ID + idx;
             let user_name = ChatMessageOrmTest::get_user_name(create_chat_message.user_id).clone();
 
-            let chat_message = ChatMessage::new(
+            let mut chat_message = ChatMessage::new(
                 chat_message_id,
                 create_chat_message.stream_id,
                 create_chat_message.user_id,
@@ -568,7 +1006,13 @@ pub mod tests {
                 Utc::now(),
                 None,
                 None,
+                0,
             );
+            if let Some(parent) = opt_parent {
+                chat_message.reply_to = Some(parent.id);
+                chat_message.reply_user_name = Some(parent.user_name.clone());
+                chat_message.reply_msg = parent.msg.clone();
+            }
 
             Ok(Some(chat_message))
         }
@@ -586,6 +1030,11 @@ pub mod tests {
                 if ch_msg.stream_id != stream_id {
                     is_add_value = false;
                 }
+                if is_add_value {
+                    if let Some(thread_id) = flt_chat_msg.thread_id {
+                        is_add_value = self.is_in_thread(ch_msg, thread_id);
+                    }
+                }
                 if is_add_value {
                     if let Some(min_date_created) = opt_min_date_created {
                         is_add_value = min_date_created < ch_msg.date_created;
@@ -624,17 +1073,91 @@ pub mod tests {
             Ok(result)
         }
 
+        /// Search for entities (chat_messages) by text and (or) by author, page by page.
+        /// Returns the total number of found messages and the messages of the specified page.
+        fn search_chat_messages(&self, search_chat_text: SearchChatText) -> Result<(u32, Vec<ChatMessageFound>), String> {
+            let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+            #[rustfmt::skip]
+            let words: Vec<String> = search_chat_text.text.clone().unwrap_or_default()
+                .split_whitespace().map(|v| v.to_lowercase()).collect();
+            let user_ids = ChatMessageOrmTest::user_ids();
+            let mut list: Vec<ChatMessageFound> = Vec::new();
+
+            for ch_msg in self.chat_message_vec.iter() {
+                let msg = ch_msg.msg.clone().unwrap_or_default();
+                let opt_idx = ChatMessageOrmTest::stream_ids().iter().position(|v| *v == ch_msg.stream_id);
+                let stream_owner = opt_idx.and_then(|idx| user_ids.get(idx).cloned()).unwrap_or_default();
+                let msg_words: Vec<String> = msg.split(|c: char| !is_word_char(c)).map(|v| v.to_lowercase()).collect();
+
+                let mut is_add_value = ch_msg.date_removed.is_none();
+                is_add_value = is_add_value && search_chat_text.stream_id.map(|v| v == ch_msg.stream_id).unwrap_or(true);
+                is_add_value = is_add_value && search_chat_text.owner_id.map(|v| v == stream_owner).unwrap_or(true);
+                if let (true, Some(viewer_id)) = (is_add_value, search_chat_text.viewer_id) {
+                    let is_moderator = ChatMessageOrmTest::stream_moderators().contains(&(ch_msg.stream_id, viewer_id));
+                    is_add_value = stream_owner == viewer_id || is_moderator;
+                }
+                is_add_value = is_add_value && words.iter().all(|word| msg_words.contains(word));
+                is_add_value = is_add_value && search_chat_text.member.as_ref().map(|v| *v == ch_msg.user_name).unwrap_or(true);
+                if let (true, Some(min_date_created)) = (is_add_value, search_chat_text.min_date_created) {
+                    is_add_value = min_date_created <= ch_msg.date_created;
+                }
+                if let (true, Some(max_date_created)) = (is_add_value, search_chat_text.max_date_created) {
+                    is_add_value = ch_msg.date_created <= max_date_created;
+                }
+                if !is_add_value {
+                    continue;
+                }
+                // The text is HTML-escaped, and the found words are highlighted with the "<mark>" tag.
+                let mut highlight = String::new();
+                let mut word = String::new();
+                for c in msg.chars().chain(std::iter::once(' ')) {
+                    if is_word_char(c) {
+                        word.push(c);
+                        continue;
+                    }
+                    if !word.is_empty() && words.contains(&word.to_lowercase()) {
+                        word = format!("<mark>{}</mark>", word);
+                    }
+                    highlight.push_str(&word);
+                    match c {
+                        '&' => highlight.push_str("&amp;"),
+                        '<' => highlight.push_str("&lt;"),
+                        '>' => highlight.push_str("&gt;"),
+                        '"' => highlight.push_str("&quot;"),
+                        '\'' => highlight.push_str("&#39;"),
+                        _ => highlight.push(c),
+                    }
+                    word.clear();
+                }
+                highlight.pop();
+                #[rustfmt::skip]
+                let mut chat_message_found = ChatMessageFound::new(
+                    ch_msg.id, ch_msg.stream_id, ch_msg.user_id, &ch_msg.user_name, &msg, &highlight, ch_msg.date_created);
+                chat_message_found.date_changed = ch_msg.date_changed.clone();
+                list.push(chat_message_found);
+            }
+            // Sort by creation date (newest first).
+            list.sort_by(|a, b| b.date_created.cmp(&a.date_created).then(b.id.cmp(&a.id)));
+
+            let count = u32::try_from(list.len()).unwrap();
+            let offset = usize::try_from((search_chat_text.page.max(1) - 1) * search_chat_text.limit).unwrap();
+            let limit = usize::try_from(search_chat_text.limit).unwrap();
+            let result: Vec<ChatMessageFound> = list.into_iter().skip(offset).take(limit).collect();
+
+            Ok((count, result))
+        }
+
         /// Modify an entity (chat_message).
         fn modify_chat_message(
             &self,
             id: i32,
-            user_id: i32,
+            opt_user_id: Option<i32>,
             modify_chat_message: ModifyChatMessage,
         ) -> Result<Option<ChatMessage>, String> {
             let opt_chat_message = self
                 .chat_message_vec
                 .iter()
-                .find(|chat_msg| (*chat_msg).id == id && (*chat_msg).user_id == user_id)
+                .find(|chat_msg| (*chat_msg).id == id && opt_user_id.map(|user_id| (*chat_msg).user_id == user_id).unwrap_or(true))
                 .map(|chat_msg| chat_msg.clone());
 
             if opt_chat_message.is_none() {
@@ -651,21 +1174,210 @@ pub mod tests {
                 chat_message1.date_removed = Some(date);
             }
             chat_message1.msg = Some(modify_chat_message.msg.clone());
+            // The previous text of the message is added to the edit history.
+            chat_message1.cnt_logs += 1;
 
             Ok(Some(chat_message1))
         }
 
         /// Delete an entity (chat_message).
-        fn delete_chat_message(&self, id: i32, user_id: i32) -> Result<Option<ChatMessage>, String> {
+        fn delete_chat_message(&self, id: i32, opt_user_id: Option<i32>) -> Result<Option<ChatMessage>, String> {
             let opt_chat_message = self
                 .chat_message_vec
                 .iter()
-                .find(|chat_msg| (*chat_msg).id == id && (*chat_msg).user_id == user_id)
+                .find(|chat_msg| (*chat_msg).id == id && opt_user_id.map(|user_id| (*chat_msg).user_id == user_id).unwrap_or(true))
                 .map(|chat_msg| chat_msg.clone());
 
             Ok(opt_chat_message)
         }
 
+        /// Get the number of reactions (of each emoji) for the specified messages.
+        fn filter_chat_message_reactions(&self, chat_message_ids: &[i32]) -> Result<Vec<ChatMessageReaction>, String> {
+            let vec = (*self.reaction_vec).borrow();
+            let mut result: Vec<ChatMessageReaction> = Vec::new();
+            // The order of emoji is the order in which they were first added.
+            for reaction in vec.iter().filter(|v| chat_message_ids.contains(&v.chat_message_id)) {
+                #[rustfmt::skip]
+                let opt_item = result.iter_mut()
+                    .find(|v| v.chat_message_id == reaction.chat_message_id && v.emoji.eq(&reaction.emoji));
+                if let Some(item) = opt_item {
+                    item.cnt += 1;
+                } else {
+                    result.push(ChatMessageReaction::new(reaction.chat_message_id, &reaction.emoji, 1));
+                }
+            }
+            result.sort_by(|a, b| a.chat_message_id.cmp(&b.chat_message_id));
+            Ok(result)
+        }
+
+        /// Add or remove the user's reaction (chat_message_reaction).
+        fn modify_chat_message_reaction(&self, modify: ModifyChatMessageReaction) -> Result<Option<ChatMessageReactionDelta>, String> {
+            let validation_res = modify.validate();
+            if let Err(validation_errors) = validation_res {
+                let buff: Vec<String> = validation_errors.into_iter().map(|v| v.message.to_string()).collect();
+                return Err(buff.join("','"));
+            }
+            let (chat_message_id, user_id, emoji) = (modify.chat_message_id, modify.user_id, modify.emoji);
+            // The message must be in the chat of this stream and must not be deleted.
+            #[rustfmt::skip]
+            let is_exist = self.chat_message_vec.iter()
+                .any(|v| v.id == chat_message_id && v.stream_id == modify.stream_id && v.date_removed.is_none());
+            if !is_exist {
+                return Ok(None);
+            }
+            let mut vec = (*self.reaction_vec).borrow_mut();
+            #[rustfmt::skip]
+            let opt_index = vec.iter()
+                .position(|v| v.chat_message_id == chat_message_id && v.user_id == user_id && v.emoji.eq(&emoji));
+            #[rustfmt::skip]
+            let user_count = vec.iter().filter(|v| v.chat_message_id == chat_message_id && v.user_id == user_id).count();
+            let is_limit = modify.is_add && opt_index.is_none() && usize::from(REACTIONS_PER_USER_MAX) <= user_count;
+            let mut delta = 0;
+            if modify.is_add && opt_index.is_none() && !is_limit {
+                vec.push(ReactionData::new(chat_message_id, user_id, &emoji));
+                delta = 1;
+            } else if let (false, Some(index)) = (modify.is_add, opt_index) {
+                vec.remove(index);
+                delta = -1;
+            }
+            #[rustfmt::skip]
+            let cnt: i32 = vec.iter()
+                .filter(|v| v.chat_message_id == chat_message_id && v.emoji.eq(&emoji)).count().try_into().unwrap();
+            let mut reaction_delta = ChatMessageReactionDelta::new(chat_message_id, &emoji, cnt, delta);
+            reaction_delta.is_limit = is_limit;
+
+            Ok(Some(reaction_delta))
+        }
+
+        /// Add mentions of users (by nickname) to the message of the specified author.
+        fn create_chat_message_mentions(&self, id: i32, user_id: i32, nicknames: &[String]) -> Result<Vec<MentionedUser>, String> {
+            let now = Utc::now();
+            // Users who have blocked the author are not mentioned.
+            #[rustfmt::skip]
+            let owner_ids: Vec<i32> = (*self.blocked_user_vec).borrow().iter()
+                .filter(|v| v.user_id == user_id && v.block_until.map(|until| now < until).unwrap_or(true))
+                .map(|v| v.owner_id).collect();
+            let mut mention_vec = (*self.mention_vec).borrow_mut();
+            let mut result: Vec<MentionedUser> = Vec::new();
+            for user_mini in self.user_vec.iter() {
+                let is_blocked = owner_ids.contains(&user_mini.id);
+                let is_mention = nicknames.contains(&user_mini.name) && user_mini.id != user_id && !is_blocked;
+                let is_exist = mention_vec.iter().any(|v| v.chat_message_id == id && v.user_id == user_mini.id);
+                if !is_mention || is_exist {
+                    continue;
+                }
+                let mention_id = mention_vec.last().map(|v| v.id + 1).unwrap_or(MENTION_ID);
+                mention_vec.push(MentionData::new(mention_id, id, user_mini.id, None));
+                result.push(MentionedUser::new(user_mini.id, &user_mini.name));
+            }
+            result.sort_by(|a, b| a.nickname.cmp(&b.nickname));
+            Ok(result)
+        }
+
+        /// Get a list of unread mentions of the user.
+        fn filter_chat_mentions(&self, user_id: i32) -> Result<Vec<ChatMention>, String> {
+            let mut result: Vec<ChatMention> = Vec::new();
+            for mention in (*self.mention_vec).borrow().iter() {
+                if mention.user_id != user_id || mention.date_read.is_some() {
+                    continue;
+                }
+                #[rustfmt::skip]
+                let opt_chat_message = self.chat_message_vec.iter()
+                    .find(|v| v.id == mention.chat_message_id && v.date_removed.is_none());
+                if let Some(ch_msg) = opt_chat_message {
+                    #[rustfmt::skip]
+                    result.push(ChatMention::new(
+                        mention.id, ch_msg.id, ch_msg.stream_id, &ch_msg.user_name, ch_msg.msg.clone(), ch_msg.date_created));
+                }
+            }
+            Ok(result)
+        }
+
+        /// Mark the user's mentions as read. (If "opt_ids" is None, then all unread mentions.)
+        fn read_chat_mentions(&self, user_id: i32, opt_ids: Option<Vec<i32>>) -> Result<Vec<ChatMention>, String> {
+            let mut result: Vec<ChatMention> = Vec::new();
+            let now = Utc::now();
+            for mention in (*self.mention_vec).borrow_mut().iter_mut() {
+                let is_id = opt_ids.as_ref().map(|ids| ids.contains(&mention.id)).unwrap_or(true);
+                if mention.user_id != user_id || mention.date_read.is_some() || !is_id {
+                    continue;
+                }
+                mention.date_read = Some(now);
+                if let Some(ch_msg) = self.chat_message_vec.iter().find(|v| v.id == mention.chat_message_id) {
+                    #[rustfmt::skip]
+                    result.push(ChatMention::new(
+                        mention.id, ch_msg.id, ch_msg.stream_id, &ch_msg.user_name, ch_msg.msg.clone(), ch_msg.date_created));
+                }
+            }
+            Ok(result)
+        }
+
+        /// Get a list of pins (pinned messages and announcements) of the chat.
+        fn filter_chat_pins(&self, stream_id: i32) -> Result<Vec<ChatPin>, String> {
+            let mut result: Vec<ChatPin> = Vec::new();
+            for chat_pin in (*self.pin_vec).borrow().iter() {
+                if chat_pin.stream_id != stream_id {
+                    continue;
+                }
+                // Pins of removed messages are not returned.
+                #[rustfmt::skip]
+                let is_removed = chat_pin.chat_message_id
+                    .map(|id| self.chat_message_vec.iter().all(|v| v.id != id || v.date_removed.is_some()))
+                    .unwrap_or(false);
+                if !is_removed {
+                    result.push(chat_pin.clone());
+                }
+            }
+            result.sort_by(|a, b| a.date_created.cmp(&b.date_created).then(a.id.cmp(&b.id)));
+            Ok(result)
+        }
+
+        /// Add a new entry (chat_pin). If the message is already pinned, the existing pin is returned.
+        fn create_chat_pin(&self, create_chat_pin: CreateChatPin) -> Result<Option<ChatPin>, String> {
+            let stream_id = create_chat_pin.stream_id;
+            if !self.is_stream_id_exists(Some(stream_id)) {
+                return Ok(None);
+            }
+            let (user_name, msg) = if let Some(chat_message_id) = create_chat_pin.chat_message_id {
+                // The pinned message must be in the specified stream and must not be removed.
+                #[rustfmt::skip]
+                let opt_chat_message = self.chat_message_vec.iter()
+                    .find(|v| v.id == chat_message_id && v.stream_id == stream_id && v.date_removed.is_none());
+                let Some(ch_msg) = opt_chat_message else {
+                    return Ok(None);
+                };
+                #[rustfmt::skip]
+                let opt_chat_pin = (*self.pin_vec).borrow().iter()
+                    .find(|v| v.chat_message_id == Some(chat_message_id)).map(|v| v.clone());
+                if opt_chat_pin.is_some() {
+                    return Ok(opt_chat_pin);
+                }
+                (ch_msg.user_name.clone(), ch_msg.msg.clone().unwrap_or_default())
+            } else {
+                let msg = create_chat_pin.msg.unwrap_or_default();
+                if msg.len() == 0 {
+                    return Ok(None);
+                }
+                let user_name = self.find_user_by_id(create_chat_pin.user_id).map(|v| v.name).unwrap_or_default();
+                (user_name, msg)
+            };
+            let mut pin_vec = (*self.pin_vec).borrow_mut();
+            let id = CHAT_PIN_ID + i32::try_from(pin_vec.len()).unwrap();
+            let chat_message_id = create_chat_pin.chat_message_id;
+            let chat_pin = ChatPin::new(id, stream_id, chat_message_id, &user_name, &msg, round_subsecs(Utc::now()));
+            pin_vec.push(chat_pin.clone());
+
+            Ok(Some(chat_pin))
+        }
+
+        /// Delete an entity (chat_pin).
+        fn delete_chat_pin(&self, id: i32, stream_id: i32) -> Result<Option<ChatPin>, String> {
+            let mut pin_vec = (*self.pin_vec).borrow_mut();
+            let opt_index = pin_vec.iter().position(|v| v.id == id && v.stream_id == stream_id);
+
+            Ok(opt_index.map(|index| pin_vec.remove(index)))
+        }
+
         /// Get chat access information. (ChatAccess)
         fn get_chat_access(&self, stream_id: i32, opt_user_id: Option<i32>) -> Result<Option<ChatAccess>, String> {
             let opt_idx_stream_id = ChatMessageOrmTest::stream_ids().iter().position(|v| *v == stream_id);
@@ -680,15 +1392,58 @@ pub mod tests {
             let stream_state: String = if stream_id == stream2_id { "stopped".to_owned() } else { "preparing".to_owned() };
 
             let mut is_blocked = false;
+            let mut is_moderator = false;
+            let mut block_until: Option<DateTime<Utc>> = None;
             if let Some(user_id) = opt_user_id {
                 let opt_idx_user_id = ChatMessageOrmTest::user_ids().iter().position(|v| *v == user_id);
                 if opt_idx_user_id.is_some() {
-                    is_blocked = (*self.blocked_user_vec).borrow().iter()
-                        .find(|v| v.owner_id == stream_owner && v.user_id == user_id).is_some();
+                    let vec = (*self.blocked_user_vec).borrow();
+                    // An expired block is not taken into account.
+                    let opt_blocked_data = vec.iter()
+                        .find(|v| v.owner_id == stream_owner && v.user_id == user_id && v.is_active());
+                    is_blocked = opt_blocked_data.is_some();
+                    block_until = opt_blocked_data.and_then(|v| v.block_until.clone());
+                    is_moderator = ChatMessageOrmTest::stream_moderators().contains(&(stream_id, user_id));
                 }
             }
              
-            Ok(Some(ChatAccess::new(stream_id, stream_owner, stream_state, is_blocked)))
+            Ok(Some(ChatAccess::new(stream_id, stream_owner, stream_state, is_blocked, is_moderator, block_until)))
+        }
+
+        /// Get the times of the stream (the chat) to calculate the time of messages relative to its beginning. (ChatStreamTimes)
+        fn get_chat_stream_times(&self, stream_id: i32, opt_user_id: Option<i32>) -> Result<Option<ChatStreamTimes>, String> {
+            let Some(chat_access) = self.get_chat_access(stream_id, opt_user_id)? else {
+                return Ok(None);
+            };
+            // The scheduled start time of the stream is the date of its first message.
+            #[rustfmt::skip]
+            let starttime = self.chat_message_vec.iter().filter(|v| v.stream_id == stream_id)
+                .map(|v| v.date_created).min().unwrap_or(Utc::now());
+            let is_stopped = chat_access.stream_state == StreamState::Stopped.to_string();
+            let started = if is_stopped { Some(starttime) } else { None };
+            let stopped = if is_stopped { Some(Utc::now()) } else { None };
+            #[rustfmt::skip]
+            let mut chat_stream_times = ChatStreamTimes::new(
+                stream_id, chat_access.stream_owner, &chat_access.stream_state, starttime, started, None, stopped);
+            chat_stream_times.is_moderator = chat_access.is_moderator;
+
+            Ok(Some(chat_stream_times))
+        }
+
+        /// Get the next page of entities (chat_messages) for the transcript, sorted by "date_created" and "id".
+        fn filter_chat_transcript(&self, search_chat_transcript: SearchChatTranscript) -> Result<Vec<ChatMessage>, String> {
+            let stream_id = search_chat_transcript.stream_id;
+            let opt_after = search_chat_transcript.after;
+            let max_date_created = search_chat_transcript.max_date_created.unwrap_or(Utc::now());
+            #[rustfmt::skip]
+            let mut list: Vec<ChatMessage> = self.chat_message_vec.iter()
+                .filter(|v| v.stream_id == stream_id && v.date_created < max_date_created)
+                .filter(|v| opt_after.map(|after| (v.date_created, v.id) > after).unwrap_or(true))
+                .cloned()
+                .collect();
+            list.sort_by_key(|v| (v.date_created, v.id));
+            list.truncate(search_chat_transcript.limit.unwrap_or(20));
+            Ok(list)
         }
 
         /// Get a list of blocked users (nickname only).
@@ -696,7 +1451,7 @@ pub mod tests {
             let vec = (*self.blocked_user_vec).borrow();
             #[rustfmt::skip]
             let result: Vec<BlockedName> = vec.iter()
-                .filter(|v| (*v).owner_id == owner_id)
+                .filter(|v| (*v).owner_id == owner_id && v.is_active())
                 .map(|v| BlockedName::new(v.id, v.user_id, v.nickname.clone()))
                 .collect();
             Ok(result)
@@ -707,7 +1462,7 @@ pub mod tests {
             let vec = (*self.blocked_user_vec).borrow();
             #[rustfmt::skip]
             let mut result: Vec<BlockedUser> = vec.iter()
-                .filter(|v| (*v).owner_id == owner_id).map(|v| v.clone().into()).collect();
+                .filter(|v| (*v).owner_id == owner_id && v.is_active()).map(|v| v.clone().into()).collect();
             
             result.sort_by(|a, b| {
                 let mut result = if sort_column == "email" {
@@ -748,23 +1503,29 @@ pub mod tests {
             }
             let mut result: Option<BlockedUserMini> = None;
             let mut vec = (*self.blocked_user_vec).borrow_mut();
+            let block_until = create_blocked_user.block_until.map(|v| round_subsecs(v));
             if let Some(user_mini) = opt_user_mini {
                 let opt_blocked_data = vec
-                    .iter()
+                    .iter_mut()
                     .find(|v| {
                         (*v).owner_id == create_blocked_user.owner_id
                             && (*v).user_id == user_mini.id
                             && (*v).nickname.eq(&user_mini.name)
-                    })
-                    .map(|v| v.clone());
+                    });
 
                 if let Some(blocked_data) = opt_blocked_data {
-                    result = Some(blocked_data.into());
+                    // An expired block is considered a new block.
+                    if !blocked_data.is_active() {
+                        blocked_data.block_date = round_subsecs(Utc::now());
+                    }
+                    // The expiration date of the existing block is replaced with the new one.
+                    blocked_data.block_until = block_until;
+                    result = Some(blocked_data.clone().into());
                 } else {
                     let cnt = vec.len();
                     let idx: i32 = cnt.try_into().unwrap();
                     let email = ChatMessageOrmTest::get_user_email(user_mini.id);
-                    let blocked_data = BlockedData::new(
+                    let mut blocked_data = BlockedData::new(
                         BLOCKED_USER_ID + idx,
                         create_blocked_user.owner_id,
                         user_mini.id,
@@ -773,6 +1534,7 @@ pub mod tests {
                         round_subsecs(Utc::now()),
                         "".into(),
                     );
+                    blocked_data.block_until = block_until;
                     vec.push(blocked_data.clone());
                     result = Some(blocked_data.into());
                 }
@@ -828,6 +1590,12 @@ pub mod tests {
                 4, // Owner user idx 3  blocked      1103 ava_wilson
             ]
         }
+        /// List of pairs (stream_id, user_id) of moderators appointed by the stream owner.
+        pub fn stream_moderators() -> Vec<(i32, i32)> {
+            vec![
+                (2, USER3_ID), // Stream 2 (owner 1101 robert_brown), moderator 1102 mary_williams
+            ]
+        }
         pub fn get_user_name(user_id: i32) -> String {
             match user_id {
                 USER1_ID => USER1_NAME,
@@ -884,7 +1652,10 @@ pub mod tests {
         fn get_user_mini() -> Vec<UserMini> {
             Self::user_ids().iter().map(|v| UserMini {id: *v, name: Self::get_user_name(*v)}).collect()
         }
-        pub fn chat_messages(count_msg: i32) -> (Vec<ChatMessage>, Vec<ChatMessageLog>, Vec<BlockedData>, Vec<UserMini>) {
+        #[rustfmt::skip]
+        pub fn chat_messages(
+            count_msg: i32,
+        ) -> (Vec<ChatMessage>, Vec<ChatMessageLog>, Vec<BlockedData>, Vec<UserMini>, Vec<ReactionData>, Vec<MentionData>, Vec<ChatPin>) {
             let mut chat_message_list: Vec<ChatMessage> = Vec::new();
             let chat_message_log_list: Vec<ChatMessageLog> = Vec::new();
 
@@ -895,7 +1666,7 @@ pub mod tests {
                 let msg = Some(format!("msg1{}{}", (if idx < 10 { "0" } else { "" }), idx + 1));
                 let user_name = Self::get_user_name(user_id);
 
-                let ch_msg = ChatMessage::new(idx, stream_id, user_id, user_name, msg, date_created, None, None);
+                let ch_msg = ChatMessage::new(idx, stream_id, user_id, user_name, msg, date_created, None, None, 0);
                 chat_message_list.push(ch_msg);
                 date_created = date_created + Duration::minutes(1);
                 user_id = if user_id == USER4_ID { USER1_ID } else { user_id + 1 };
@@ -904,9 +1675,12 @@ pub mod tests {
             let blocked_user_list: Vec<BlockedData> = Self::get_blocked_user_vec();
             let user_mini_vec: Vec<UserMini> = Self::get_user_mini();
             let users_list = user_mini_vec.clone();
+            let reaction_list: Vec<ReactionData> = Vec::new();
+            let mention_list: Vec<MentionData> = Vec::new();
+            let pin_list: Vec<ChatPin> = Vec::new();
             #[rustfmt::skip]
             let chat_message_orm = ChatMessageOrmApp::create(
-                &chat_message_list, &chat_message_log_list, &blocked_user_list, &users_list);
+                &chat_message_list, &chat_message_log_list, &blocked_user_list, &users_list, &reaction_list, &mention_list, &pin_list);
 
             let mut chat_message_log_vec: Vec<ChatMessageLog> = Vec::new();
             for (_key, value_vec) in chat_message_orm.chat_message_log_map.iter() {
@@ -916,14 +1690,20 @@ pub mod tests {
             }
             let blocked_user_vec = (*chat_message_orm.blocked_user_vec).borrow().clone();
             let chat_message_vec = chat_message_orm.chat_message_vec.clone();
+            let reaction_vec = (*chat_message_orm.reaction_vec).borrow().clone();
+            let mention_vec = (*chat_message_orm.mention_vec).borrow().clone();
+            let pin_vec = (*chat_message_orm.pin_vec).borrow().clone();
 
-            (chat_message_vec, chat_message_log_vec, blocked_user_vec, user_mini_vec)
+            (chat_message_vec, chat_message_log_vec, blocked_user_vec, user_mini_vec, reaction_vec, mention_vec, pin_vec)
         }
+        #[rustfmt::skip]
         pub fn cfg_chat_message_orm(
-            data_cm: (Vec<ChatMessage>, Vec<ChatMessageLog>, Vec<BlockedData>, Vec<UserMini>),
+            data_cm: (Vec<ChatMessage>, Vec<ChatMessageLog>, Vec<BlockedData>, Vec<UserMini>, Vec<ReactionData>, Vec<MentionData>, Vec<ChatPin>),
         ) -> impl FnOnce(&mut web::ServiceConfig) {
             move |config: &mut web::ServiceConfig| {
-                let data_chat_message_orm = web::Data::new(ChatMessageOrmApp::create(&data_cm.0, &data_cm.1, &data_cm.2, &data_cm.3));
+                #[rustfmt::skip]
+                let chat_message_orm = ChatMessageOrmApp::create(&data_cm.0, &data_cm.1, &data_cm.2, &data_cm.3, &data_cm.4, &data_cm.5, &data_cm.6);
+                let data_chat_message_orm = web::Data::new(chat_message_orm);
                 config.app_data(web::Data::clone(&data_chat_message_orm));
             }
         }
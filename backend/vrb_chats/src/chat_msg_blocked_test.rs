@@ -8,16 +8,18 @@ mod tests {
         http::header::{CONTENT_TYPE, HeaderValue},
         test,
     };
-    use chrono::{SecondsFormat, Utc};
+    use chrono::{Duration, SecondsFormat, Utc};
     use serde_json::{self, json};
     use vrb_authent::{
         config_jwt,
+        user_models::Session,
         user_orm::tests::{USER, USER1_ID, USER4_ID, UserOrmTest},
     };
     use vrb_common::{
         api_error::{ApiError, code_to_str},
-        validators,
+        err, validators,
     };
+    use vrb_dbase::enm_user_role::UserRole;
 
     use crate::{
         chat_message_controller::{delete_blocked_user, get_blocked_users, get_blocked_users_names, post_blocked_user, tests as ChatMessageCtrlTest},
@@ -27,6 +29,7 @@ mod tests {
 
     const MSG_CONTENT_TYPE_ERROR: &str = "Content type error";
     const MSG_FAILED_DESER: &str = "Failed to deserialize response from JSON.";
+    const MSG_CASTING_TO_TYPE: &str = "invalid digit found in string";
 
     // ** get_blocked_users_names **
 
@@ -165,12 +168,44 @@ mod tests {
         assert_eq!(blocked_user_dto_res.len(), 0);
     }
     #[actix_web::test]
+    async fn test_get_blocked_users_without_expired_blocked_users() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
+        let user1_id = data_u.0.get(0).unwrap().id;
+        let mut data_cm = ChatMessageOrmTest::chat_messages(1);
+        // The blocking of user1 has expired.
+        let expired_id = data_cm.2.iter().find(|v| v.owner_id == user1_id).map(|v| v.id).unwrap();
+        for blocked in data_cm.2.iter_mut().filter(|v| v.id == expired_id) {
+            blocked.block_until = Some(Utc::now() - Duration::minutes(1));
+        }
+        #[rustfmt::skip]
+        let blocked_len = data_cm.2.iter().filter(|v| v.owner_id == user1_id && v.id != expired_id).count();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_blocked_users)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri("/api/blocked_users")
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let blocked_users_res: Vec<BlockedUserDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(blocked_users_res.len(), blocked_len);
+        assert!(blocked_users_res.iter().all(|v| v.id != expired_id));
+    }
+    #[actix_web::test]
     async fn test_get_blocked_users_without_sorting() {
         let token4 = config_jwt::tests::get_token(USER4_ID);
         let mut data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
         let user4_id = data_u.0.get(3).unwrap().id;
         // Add session (num_token) for user4.
-        data_u.1.get_mut(3).unwrap().num_token = Some(config_jwt::tests::get_num_token(user4_id));
+        data_u.1.push(Session::new(4, user4_id, config_jwt::tests::get_num_token(user4_id)));
         let data_cm = ChatMessageOrmTest::chat_messages(1);
         
         #[rustfmt::skip] // Filter data only for the user.
@@ -208,7 +243,7 @@ mod tests {
         let mut data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
         let user4_id = data_u.0.get(3).unwrap().id;
         // Add session (num_token) for user4.
-        data_u.1.get_mut(3).unwrap().num_token = Some(config_jwt::tests::get_num_token(user4_id));
+        data_u.1.push(Session::new(4, user4_id, config_jwt::tests::get_num_token(user4_id)));
         let data_cm = ChatMessageOrmTest::chat_messages(1);
         
         #[rustfmt::skip] // Filter data only for the user.
@@ -247,7 +282,7 @@ mod tests {
         let mut data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
         let user4_id = data_u.0.get(3).unwrap().id;
         // Add session (num_token) for user4.
-        data_u.1.get_mut(3).unwrap().num_token = Some(config_jwt::tests::get_num_token(user4_id));
+        data_u.1.push(Session::new(4, user4_id, config_jwt::tests::get_num_token(user4_id)));
         let data_cm = ChatMessageOrmTest::chat_messages(1);
         
         #[rustfmt::skip] // Filter data only for the user.
@@ -286,7 +321,7 @@ mod tests {
         let mut data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
         let user4_id = data_u.0.get(3).unwrap().id;
         // Add session (num_token) for user4.
-        data_u.1.get_mut(3).unwrap().num_token = Some(config_jwt::tests::get_num_token(user4_id));
+        data_u.1.push(Session::new(4, user4_id, config_jwt::tests::get_num_token(user4_id)));
         let data_cm = ChatMessageOrmTest::chat_messages(1);
         
         #[rustfmt::skip] // Filter data only for the user.
@@ -325,7 +360,7 @@ mod tests {
         let mut data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
         let user4_id = data_u.0.get(3).unwrap().id;
         // Add session (num_token) for user4.
-        data_u.1.get_mut(3).unwrap().num_token = Some(config_jwt::tests::get_num_token(user4_id));
+        data_u.1.push(Session::new(4, user4_id, config_jwt::tests::get_num_token(user4_id)));
         let data_cm = ChatMessageOrmTest::chat_messages(1);
         
         #[rustfmt::skip] // Filter data only for the user.
@@ -364,7 +399,7 @@ mod tests {
         let mut data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
         let user4_id = data_u.0.get(3).unwrap().id;
         // Add session (num_token) for user4.
-        data_u.1.get_mut(3).unwrap().num_token = Some(config_jwt::tests::get_num_token(user4_id));
+        data_u.1.push(Session::new(4, user4_id, config_jwt::tests::get_num_token(user4_id)));
         let data_cm = ChatMessageOrmTest::chat_messages(1);
         
         #[rustfmt::skip] // Filter data only for the user.
@@ -403,7 +438,7 @@ mod tests {
         let mut data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
         let user4_id = data_u.0.get(3).unwrap().id;
         // Add session (num_token) for user4.
-        data_u.1.get_mut(3).unwrap().num_token = Some(config_jwt::tests::get_num_token(user4_id));
+        data_u.1.push(Session::new(4, user4_id, config_jwt::tests::get_num_token(user4_id)));
         let data_cm = ChatMessageOrmTest::chat_messages(1);
         
         #[rustfmt::skip] // Filter data only for the user.
@@ -513,7 +548,7 @@ mod tests {
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/blocked_users")
             .insert_header(ChatMessageCtrlTest::header_auth(&token1))
-            .set_json(CreateBlockedUserDto { blocked_id: None, blocked_nickname })
+            .set_json(CreateBlockedUserDto { blocked_id: None, blocked_nickname, block_duration: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
@@ -546,7 +581,7 @@ mod tests {
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/blocked_users")
             .insert_header(ChatMessageCtrlTest::header_auth(&token1))
-            .set_json(CreateBlockedUserDto { blocked_id: None, blocked_nickname })
+            .set_json(CreateBlockedUserDto { blocked_id: None, blocked_nickname, block_duration: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
@@ -577,7 +612,7 @@ mod tests {
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/blocked_users")
             .insert_header(ChatMessageCtrlTest::header_auth(&token1))
-            .set_json(CreateBlockedUserDto { blocked_id: Some(user_id), blocked_nickname: None })
+            .set_json(CreateBlockedUserDto { blocked_id: Some(user_id), blocked_nickname: None, block_duration: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::NO_CONTENT); // 204
@@ -598,7 +633,7 @@ mod tests {
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/blocked_users")
             .insert_header(ChatMessageCtrlTest::header_auth(&token1))
-            .set_json(CreateBlockedUserDto { blocked_id: None, blocked_nickname: Some(nickname) })
+            .set_json(CreateBlockedUserDto { blocked_id: None, blocked_nickname: Some(nickname), block_duration: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::NO_CONTENT); // 204
@@ -621,7 +656,7 @@ mod tests {
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/blocked_users")
             .insert_header(ChatMessageCtrlTest::header_auth(&token1))
-            .set_json(CreateBlockedUserDto { blocked_id: Some(blocked_id), blocked_nickname: None })
+            .set_json(CreateBlockedUserDto { blocked_id: Some(blocked_id), blocked_nickname: None, block_duration: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
 
@@ -656,7 +691,7 @@ mod tests {
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/blocked_users")
             .insert_header(ChatMessageCtrlTest::header_auth(&token1))
-            .set_json(CreateBlockedUserDto { blocked_id: None, blocked_nickname: Some(blocked_nickname.clone()) })
+            .set_json(CreateBlockedUserDto { blocked_id: None, blocked_nickname: Some(blocked_nickname.clone()), block_duration: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
 
@@ -692,7 +727,7 @@ mod tests {
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/blocked_users")
             .insert_header(ChatMessageCtrlTest::header_auth(&token1))
-            .set_json(CreateBlockedUserDto { blocked_id: Some(blocked_id), blocked_nickname: None })
+            .set_json(CreateBlockedUserDto { blocked_id: Some(blocked_id), blocked_nickname: None, block_duration: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
 
@@ -728,7 +763,7 @@ mod tests {
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/blocked_users")
             .insert_header(ChatMessageCtrlTest::header_auth(&token1))
-            .set_json(CreateBlockedUserDto { blocked_id: None, blocked_nickname: Some(blocked_nickname.clone()) })
+            .set_json(CreateBlockedUserDto { blocked_id: None, blocked_nickname: Some(blocked_nickname.clone()), block_duration: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
 
@@ -746,6 +781,231 @@ mod tests {
         assert_eq!(blocked_user_mini_res.block_date.to_rfc3339_opts(SecondsFormat::Secs, true), now_date);
     }
 
+    #[actix_web::test]
+    async fn test_post_blocked_user_min_block_duration() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
+        let data_cm = ChatMessageOrmTest::chat_messages(1);
+        let blocked_id = data_u.0.get(1).unwrap().id;
+        let block_duration = chat_message_models::BLOCK_DURATION_MIN as i32 - 1;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_blocked_user)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/blocked_users")
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1))
+            .set_json(CreateBlockedUserDto { blocked_id: Some(blocked_id), blocked_nickname: None, block_duration: Some(block_duration) })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
+
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err_vec: Vec<ApiError> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        let app_err = app_err_vec.get(0).unwrap().clone();
+        #[rustfmt::skip]
+        ChatMessageCtrlTest::check_app_err(app_err_vec, &code_to_str(StatusCode::EXPECTATION_FAILED), &[chat_message_models::MSG_BLOCK_DURATION_MIN_AMOUNT]);
+        #[rustfmt::skip]
+        let json = serde_json::json!({ "actualAmount": block_duration, "requiredAmount": chat_message_models::BLOCK_DURATION_MIN });
+        assert_eq!(*app_err.params.get("minAmount").unwrap(), json);
+    }
+    #[actix_web::test]
+    async fn test_post_blocked_user_max_block_duration() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
+        let data_cm = ChatMessageOrmTest::chat_messages(1);
+        let blocked_id = data_u.0.get(1).unwrap().id;
+        let block_duration = chat_message_models::BLOCK_DURATION_MAX as i32 + 1;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_blocked_user)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/blocked_users")
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1))
+            .set_json(CreateBlockedUserDto { blocked_id: Some(blocked_id), blocked_nickname: None, block_duration: Some(block_duration) })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
+
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err_vec: Vec<ApiError> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        let app_err = app_err_vec.get(0).unwrap().clone();
+        #[rustfmt::skip]
+        ChatMessageCtrlTest::check_app_err(app_err_vec, &code_to_str(StatusCode::EXPECTATION_FAILED), &[chat_message_models::MSG_BLOCK_DURATION_MAX_AMOUNT]);
+        #[rustfmt::skip]
+        let json = serde_json::json!({ "actualAmount": block_duration, "requiredAmount": chat_message_models::BLOCK_DURATION_MAX });
+        assert_eq!(*app_err.params.get("maxAmount").unwrap(), json);
+    }
+    #[actix_web::test]
+    async fn test_post_blocked_user_by_new_blocked_id_with_block_duration() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
+        let data_cm = ChatMessageOrmTest::chat_messages(1);
+        let blocked_id = data_u.0.get(1).unwrap().id;
+        let blocked_last_id = data_cm.2.last().unwrap().id;
+        let block_duration: i32 = 600;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_blocked_user)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/blocked_users")
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1))
+            .set_json(CreateBlockedUserDto { blocked_id: Some(blocked_id), blocked_nickname: None, block_duration: Some(block_duration) })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), StatusCode::CREATED); // 201
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let blocked_user_mini_res: BlockedUserMiniDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(blocked_user_mini_res.id, blocked_last_id + 1);
+        assert_eq!(blocked_user_mini_res.user_id, blocked_id);
+        // DateTime.to_rfc3339_opts(SecondsFormat::Secs, true)   => "2018-01-26T18:30:09Z"
+        let block_until = (Utc::now() + Duration::seconds(block_duration.into())).to_rfc3339_opts(SecondsFormat::Secs, true);
+        #[rustfmt::skip]
+        assert_eq!(blocked_user_mini_res.block_until.unwrap().to_rfc3339_opts(SecondsFormat::Secs, true), block_until);
+    }
+    #[actix_web::test]
+    async fn test_post_blocked_user_by_old_blocked_id_with_block_duration() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
+        let data_cm = ChatMessageOrmTest::chat_messages(1);
+        let user_id = data_u.0.get(0).unwrap().id;
+        #[rustfmt::skip] // Find a user who is already blocked for user1.
+        let blocked = data_cm.2.iter().find(|v| v.owner_id == user_id).map(|v| v.clone()).unwrap();
+        let block_duration: i32 = 600;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_blocked_user)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/blocked_users")
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1))
+            .set_json(CreateBlockedUserDto { blocked_id: Some(blocked.user_id), blocked_nickname: None, block_duration: Some(block_duration) })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), StatusCode::CREATED); // 201
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let blocked_user_mini_res: BlockedUserMiniDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        // The existing blocking remains, only its expiration date changes.
+        assert_eq!(blocked_user_mini_res.id, blocked.id);
+        assert_eq!(blocked_user_mini_res.user_id, blocked.user_id);
+        #[rustfmt::skip]
+        assert_eq!(blocked_user_mini_res.block_date.to_rfc3339_opts(SecondsFormat::Secs, true), blocked.block_date.to_rfc3339_opts(SecondsFormat::Secs, true));
+        let block_until = (Utc::now() + Duration::seconds(block_duration.into())).to_rfc3339_opts(SecondsFormat::Secs, true);
+        #[rustfmt::skip]
+        assert_eq!(blocked_user_mini_res.block_until.unwrap().to_rfc3339_opts(SecondsFormat::Secs, true), block_until);
+    }
+    #[actix_web::test]
+    async fn test_post_blocked_user_moderator_invalid_owner_id() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let mut data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
+        data_u.0.get_mut(0).unwrap().role = UserRole::Moderator;
+        let owner_id_bad = format!("{}a", data_u.0.get(1).unwrap().id);
+        let blocked_id = data_u.0.get(3).unwrap().id;
+        let data_cm = ChatMessageOrmTest::chat_messages(1);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_blocked_user)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri(&format!("/api/blocked_users?ownerId={}", owner_id_bad))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1))
+            .set_json(CreateBlockedUserDto { blocked_id: Some(blocked_id), blocked_nickname: None, block_duration: None })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::RANGE_NOT_SATISFIABLE); // 416
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::RANGE_NOT_SATISFIABLE));
+        #[rustfmt::skip]
+        let msg = format!("{}; `{}` - {}", err::MSG_PARSING_TYPE_NOT_SUPPORTED, "ownerId", MSG_CASTING_TO_TYPE);
+        assert!(app_err.message.starts_with(&msg));
+    }
+    #[actix_web::test]
+    async fn test_post_blocked_user_moderator_by_owner_id() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let mut data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
+        data_u.0.get_mut(0).unwrap().role = UserRole::Moderator;
+        let owner_id = data_u.0.get(1).unwrap().id;
+        let blocked_id = data_u.0.get(3).unwrap().id;
+        let data_cm = ChatMessageOrmTest::chat_messages(1);
+        #[rustfmt::skip] // Find a user who is already blocked for user2.
+        let blocked = data_cm.2.iter().find(|v| v.owner_id == owner_id && v.user_id == blocked_id).map(|v| v.clone()).unwrap();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_blocked_user)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri(&format!("/api/blocked_users?ownerId={}", owner_id))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1))
+            .set_json(CreateBlockedUserDto { blocked_id: Some(blocked_id), blocked_nickname: None, block_duration: None })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED); // 201
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let blocked_user_mini_res: BlockedUserMiniDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(blocked_user_mini_res.id, blocked.id);
+        assert_eq!(blocked_user_mini_res.user_id, blocked.user_id);
+        assert_eq!(blocked_user_mini_res.nickname, blocked.nickname);
+    }
+    #[actix_web::test]
+    async fn test_post_blocked_user_user_by_owner_id() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
+        let user_id = data_u.0.get(0).unwrap().id;
+        let owner_id = data_u.0.get(1).unwrap().id;
+        let blocked_id = data_u.0.get(3).unwrap().id;
+        let data_cm = ChatMessageOrmTest::chat_messages(1);
+        #[rustfmt::skip] // The "ownerId" parameter is ignored, the user1 block list is used.
+        let blocked = data_cm.2.iter().find(|v| v.owner_id == user_id && v.user_id == blocked_id).map(|v| v.clone()).unwrap();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_blocked_user)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri(&format!("/api/blocked_users?ownerId={}", owner_id))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1))
+            .set_json(CreateBlockedUserDto { blocked_id: Some(blocked_id), blocked_nickname: None, block_duration: None })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED); // 201
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let blocked_user_mini_res: BlockedUserMiniDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(blocked_user_mini_res.id, blocked.id);
+        assert_eq!(blocked_user_mini_res.user_id, blocked.user_id);
+    }
+
     // ** delete_blocked_user **
 
     #[actix_web::test]
@@ -1025,4 +1285,35 @@ mod tests {
         #[rustfmt::skip]
         assert_eq!(blocked_user_mini_res.block_date.to_rfc3339_opts(SecondsFormat::Secs, true), now_date);
     }
+    #[actix_web::test]
+    async fn test_delete_blocked_user_moderator_by_owner_id() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let mut data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
+        data_u.0.get_mut(0).unwrap().role = UserRole::Moderator;
+        let owner_id = data_u.0.get(1).unwrap().id;
+        let blocked_id = data_u.0.get(3).unwrap().id;
+        let data_cm = ChatMessageOrmTest::chat_messages(1);
+        #[rustfmt::skip] // Find a user who is already blocked for user2.
+        let blocked = data_cm.2.iter().find(|v| v.owner_id == owner_id && v.user_id == blocked_id).map(|v| v.clone()).unwrap();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(delete_blocked_user)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::delete().uri(&format!("/api/blocked_users?ownerId={}", owner_id))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1))
+            .set_json(DeleteBlockedUserDto { blocked_id: Some(blocked_id), blocked_nickname: None })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let blocked_user_mini_res: BlockedUserMiniDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(blocked_user_mini_res.id, blocked.id);
+        assert_eq!(blocked_user_mini_res.user_id, blocked.user_id);
+        assert_eq!(blocked_user_mini_res.nickname, blocked.nickname);
+    }
 }
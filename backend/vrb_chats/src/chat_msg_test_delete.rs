@@ -15,6 +15,7 @@ mod tests {
         api_error::{ApiError, code_to_str},
         err,
     };
+    use vrb_dbase::enm_user_role::UserRole;
 
     use crate::{
         chat_message_controller::{delete_chat_message, tests as ChatMessageCtrlTest},
@@ -269,4 +270,62 @@ mod tests {
         assert_eq!(chat_message_dto_res.date_edt, ch_msg.date_changed);
         assert_eq!(chat_message_dto_res.date_rmv, ch_msg.date_removed);
     }
+    #[actix_web::test]
+    async fn test_delete_chat_message_moderator_msg_another_user_valid_data() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let mut data_u = UserOrmTest::users(&[USER]);
+        data_u.0.get_mut(0).unwrap().role = UserRole::Moderator;
+        let data_cm = ChatMessageOrmTest::chat_messages(2);
+        let user_id1 = data_u.0.get(0).unwrap().id;
+        let ch_msg = data_cm.0.iter().find(|v| v.user_id != user_id1).unwrap().clone();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(delete_chat_message)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::delete().uri(&format!("/api/chat_messages/{}?userId={}", ch_msg.id, ch_msg.user_id))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let chat_message_dto_res: ChatMessageDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(chat_message_dto_res.id, ch_msg.id);
+        assert_eq!(chat_message_dto_res.member, ch_msg.user_name);
+        assert_eq!(chat_message_dto_res.msg, ch_msg.msg.unwrap());
+    }
+    #[actix_web::test]
+    async fn test_delete_chat_message_user_msg_another_user_with_user_id() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let data_cm = ChatMessageOrmTest::chat_messages(2);
+        let user_id1 = data_u.0.get(0).unwrap().id;
+        let ch_msg = data_cm.0.iter().find(|v| v.user_id != user_id1).unwrap().clone();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(delete_chat_message)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::delete().uri(&format!("/api/chat_messages/{}?userId={}", ch_msg.id, ch_msg.user_id))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_ACCEPTABLE); // 406
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::NOT_ACCEPTABLE));
+        #[rustfmt::skip]
+        let message = format!("{}; id: {}, user_id: {}", err::MSG_PARAMETER_UNACCEPTABLE, ch_msg.id, user_id1);
+        assert_eq!(app_err.message, message);
+    }
 }
@@ -0,0 +1,339 @@
+#[cfg(all(test, feature = "mockdata"))]
+mod tests {
+    use actix_web::{
+        self, App, body, dev,
+        http::StatusCode,
+        http::header::{CONTENT_DISPOSITION, CONTENT_TYPE, HeaderValue},
+        test,
+    };
+    use chrono::{Duration, SecondsFormat, Utc};
+    use serde_json;
+    use vrb_authent::{
+        config_jwt,
+        user_models::Session,
+        user_orm::tests::{USER, USER1_ID, USER2_ID, USER3_ID, UserOrmTest},
+    };
+    use vrb_common::{
+        api_error::{ApiError, code_to_str},
+        err,
+    };
+    use vrb_dbase::enm_stream_state::StreamState;
+
+    use crate::{
+        chat_message_controller::{get_chat_message_export, tests as ChatMessageCtrlTest},
+        chat_message_models::{self, ChatStreamTimes, ChatTranscriptItemDto, EXPORT_CHAT_PAGE_SIZE, TRANSCRIPT_CSV_HEADER},
+        chat_message_orm::tests::ChatMessageOrmTest,
+    };
+
+    const MSG_FAILED_DESER: &str = "Failed to deserialize response from JSON.";
+
+    // ** ChatStreamTimes **
+
+    #[actix_web::test]
+    async fn test_chat_stream_times_offset_without_pause() {
+        let started = Utc::now() - Duration::hours(2);
+        let stream_state = StreamState::Stopped.to_string();
+        #[rustfmt::skip]
+        let stream_times = ChatStreamTimes::new(1, USER1_ID, &stream_state, started - Duration::minutes(10), Some(started), None, None);
+        let offset = stream_times.offset(started + Duration::seconds(3723));
+        assert_eq!(chat_message_models::format_transcript_time(offset), "01:02:03");
+        let offset = stream_times.offset(started - Duration::seconds(65));
+        assert_eq!(chat_message_models::format_transcript_time(offset), "-00:01:05");
+    }
+    #[actix_web::test]
+    async fn test_chat_stream_times_offset_without_start() {
+        let starttime = Utc::now() - Duration::hours(2);
+        let stream_state = StreamState::Waiting.to_string();
+        let stream_times = ChatStreamTimes::new(1, USER1_ID, &stream_state, starttime, None, None, None);
+        // The time is given relative to the scheduled start time.
+        let offset = stream_times.offset(starttime + Duration::seconds(59));
+        assert_eq!(chat_message_models::format_transcript_time(offset), "00:00:59");
+    }
+    #[actix_web::test]
+    async fn test_chat_stream_times_offset_with_pause() {
+        let started = Utc::now() - Duration::hours(2);
+        let paused = started + Duration::minutes(30);
+        let stream_state = StreamState::Paused.to_string();
+        let stream_times = ChatStreamTimes::new(1, USER1_ID, &stream_state, started, Some(started), Some(paused), None);
+        // Before the pause.
+        let offset = stream_times.offset(paused - Duration::minutes(1));
+        assert_eq!(chat_message_models::format_transcript_time(offset), "00:29:00");
+        // The time of the pause is not taken into account.
+        let offset = stream_times.offset(paused + Duration::minutes(20));
+        assert_eq!(chat_message_models::format_transcript_time(offset), "00:30:00");
+    }
+    #[actix_web::test]
+    async fn test_chat_stream_times_offset_after_resume() {
+        let started = Utc::now() - Duration::hours(2);
+        let paused = started + Duration::minutes(30);
+        let stream_state = StreamState::Started.to_string();
+        // There is no history of the stream states, the time of resumption is unknown, so the pause is not taken into account.
+        let stream_times = ChatStreamTimes::new(1, USER1_ID, &stream_state, started, Some(started), Some(paused), None);
+        let offset = stream_times.offset(paused + Duration::minutes(20));
+        assert_eq!(chat_message_models::format_transcript_time(offset), "00:50:00");
+    }
+    #[actix_web::test]
+    async fn test_chat_stream_times_offset_with_state_log() {
+        let started = Utc::now() - Duration::hours(2);
+        let paused1 = started + Duration::minutes(30);
+        let resumed1 = paused1 + Duration::minutes(10);
+        let paused2 = resumed1 + Duration::minutes(20);
+        let stream_state = StreamState::Paused.to_string();
+        let mut stream_times = ChatStreamTimes::new(1, USER1_ID, &stream_state, started, Some(started), Some(paused2), None);
+        // The periods of all pauses are taken from the history of the stream states.
+        stream_times.paused_starts = vec![paused1, paused2];
+        stream_times.paused_ends = vec![Some(resumed1), None];
+        assert_eq!(stream_times.paused_periods(), vec![(paused1, Some(resumed1)), (paused2, None)]);
+        // During the first pause.
+        let offset = stream_times.offset(paused1 + Duration::minutes(5));
+        assert_eq!(chat_message_models::format_transcript_time(offset), "00:30:00");
+        // After the first pause, its time is not taken into account.
+        let offset = stream_times.offset(resumed1 + Duration::minutes(5));
+        assert_eq!(chat_message_models::format_transcript_time(offset), "00:35:00");
+        // During the current pause, the time of both pauses is not taken into account.
+        let offset = stream_times.offset(paused2 + Duration::minutes(15));
+        assert_eq!(chat_message_models::format_transcript_time(offset), "00:50:00");
+    }
+
+    // ** ChatTranscriptItemDto **
+
+    #[actix_web::test]
+    async fn test_chat_transcript_item_to_csv() {
+        let date = Utc::now();
+        #[rustfmt::skip]
+        let item = ChatTranscriptItemDto {
+            id: 1, time: "00:01:02".to_owned(), member: "ava_wilson".to_owned(), msg: "Hi, \"all\"".to_owned(), date, date_edt: None,
+        };
+        let date_str = date.to_rfc3339_opts(SecondsFormat::Millis, true);
+        assert_eq!(item.to_csv(), format!("1,00:01:02,{},ava_wilson,\"Hi, \"\"all\"\"\"\r\n", date_str));
+    }
+    #[actix_web::test]
+    async fn test_chat_transcript_item_to_txt() {
+        #[rustfmt::skip]
+        let item = ChatTranscriptItemDto {
+            id: 1, time: "00:01:02".to_owned(), member: "ava_wilson".to_owned(), msg: "Hi\nall".to_owned(), date: Utc::now(), date_edt: None,
+        };
+        assert_eq!(item.to_txt(), "[00:01:02] ava_wilson: Hi\n    all\n");
+    }
+
+    // ** get_chat_message_export **
+
+    #[actix_web::test]
+    async fn test_get_chat_message_export_stream_not_found() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let data_cm = ChatMessageOrmTest::chat_messages(4);
+        let stream_id_wrong = ChatMessageOrmTest::stream_ids().iter().max().unwrap() + 1;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_export)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/chat_messages/export?streamId={}", stream_id_wrong))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND); // 404
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::NOT_FOUND));
+        assert_eq!(app_err.message, format!("{}; stream_id: {}", err::MSG_STREAM_NOT_FOUND, stream_id_wrong));
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_export_by_another_user() {
+        let token2 = config_jwt::tests::get_token(USER2_ID);
+        let mut data_u = UserOrmTest::users(&[USER, USER]);
+        // Add session (num_token) for user2.
+        data_u.1.push(Session::new(2, USER2_ID, config_jwt::tests::get_num_token(USER2_ID)));
+        let data_cm = ChatMessageOrmTest::chat_messages(4);
+        let stream_id = data_cm.0.get(0).unwrap().stream_id;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_export)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/chat_messages/export?streamId={}", stream_id))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token2)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN); // 403
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::FORBIDDEN));
+        #[rustfmt::skip]
+        assert_eq!(app_err.message, format!("{}; stream_id: {}, user_id: {}", err::MSG_STREAM_OWNER_RIGHTS_MISSING, stream_id, USER2_ID));
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_export_json() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let mut data_cm = ChatMessageOrmTest::chat_messages(4);
+        // The deleted message is not included in the transcript.
+        data_cm.0.get_mut(2).unwrap().date_removed = Some(Utc::now());
+        let stream_id = data_cm.0.get(0).unwrap().stream_id;
+        let ch_msg_vec: Vec<_> = data_cm.0.iter().filter(|v| v.date_removed.is_none()).cloned().collect();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_export)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/chat_messages/export?streamId={}", stream_id))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let content_disposition = format!("attachment; filename=\"chat_{}.json\"", stream_id);
+        assert_eq!(resp.headers().get(CONTENT_DISPOSITION).unwrap().to_str().unwrap(), content_disposition);
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: Vec<ChatTranscriptItemDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.len(), ch_msg_vec.len());
+        let ids: Vec<i32> = response.iter().map(|v| v.id).collect();
+        assert_eq!(ids, ch_msg_vec.iter().map(|v| v.id).collect::<Vec<i32>>());
+        // The time is given relative to the beginning of the stream (the first message).
+        let times: Vec<&str> = response.iter().map(|v| v.time.as_str()).collect();
+        assert_eq!(times, vec!["00:00:00", "00:01:00", "00:03:00"]);
+        assert_eq!(response[1].member, ch_msg_vec[1].user_name);
+        assert_eq!(response[1].msg, ch_msg_vec[1].msg.clone().unwrap());
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_export_json_empty() {
+        let token3 = config_jwt::tests::get_token(USER3_ID);
+        let mut data_u = UserOrmTest::users(&[USER, USER, USER]);
+        // Add session (num_token) for user3.
+        data_u.1.push(Session::new(3, USER3_ID, config_jwt::tests::get_num_token(USER3_ID)));
+        let data_cm = ChatMessageOrmTest::chat_messages(4);
+        let stream_id = ChatMessageOrmTest::stream_ids().get(2).unwrap().clone();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_export)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/chat_messages/export?streamId={}", stream_id))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token3)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        assert_eq!(String::from_utf8(body.to_vec()).unwrap(), "[]");
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_export_csv_by_stream_moderator() {
+        let token3 = config_jwt::tests::get_token(USER3_ID);
+        let mut data_u = UserOrmTest::users(&[USER, USER, USER]);
+        // Add session (num_token) for user3.
+        data_u.1.push(Session::new(3, USER3_ID, config_jwt::tests::get_num_token(USER3_ID)));
+        let mut data_cm = ChatMessageOrmTest::chat_messages(3);
+        // Messages in the stream of user2 (user3 is the chat moderator of this stream).
+        let stream_id = ChatMessageOrmTest::stream_ids().get(1).unwrap().clone();
+        for ch_msg in data_cm.0.iter_mut() {
+            ch_msg.stream_id = stream_id;
+        }
+        data_cm.0.get_mut(1).unwrap().msg = Some("Hi, all".to_owned());
+        let ch_msg_vec = data_cm.0.clone();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_export)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/chat_messages/export?streamId={}&format=csv", stream_id))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token3)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("text/csv; charset=utf-8"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response = String::from_utf8(body.to_vec()).unwrap();
+        let lines: Vec<&str> = response.split_terminator("\r\n").collect();
+        assert_eq!(lines.len(), ch_msg_vec.len() + 1);
+        assert_eq!(format!("{}\r\n", lines[0]), TRANSCRIPT_CSV_HEADER);
+        let ch_msg = ch_msg_vec.get(1).unwrap();
+        let date = ch_msg.date_created.to_rfc3339_opts(SecondsFormat::Millis, true);
+        assert_eq!(lines[2], format!("{},00:01:00,{},{},\"Hi, all\"", ch_msg.id, date, ch_msg.user_name));
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_export_txt_by_pages() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        // The transcript is read from the database in several pages.
+        let data_cm = ChatMessageOrmTest::chat_messages(i32::try_from(EXPORT_CHAT_PAGE_SIZE * 2 + 3).unwrap());
+        let stream_id = data_cm.0.get(0).unwrap().stream_id;
+        let ch_msg_vec = data_cm.0.clone();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_export)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/chat_messages/export?streamId={}&format=txt", stream_id))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("text/plain; charset=utf-8"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response = String::from_utf8(body.to_vec()).unwrap();
+        let lines: Vec<&str> = response.lines().collect();
+        assert_eq!(lines.len(), ch_msg_vec.len());
+        let ch_msg_last = ch_msg_vec.last().unwrap();
+        let time_last = chat_message_models::format_transcript_time(Duration::minutes(i64::try_from(ch_msg_vec.len() - 1).unwrap()));
+        #[rustfmt::skip]
+        assert_eq!(lines.last().unwrap().to_string(), format!("[{}] {}: {}", time_last, ch_msg_last.user_name, ch_msg_last.msg.clone().unwrap()));
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_export_txt_by_pages_with_same_date() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let mut data_cm = ChatMessageOrmTest::chat_messages(i32::try_from(EXPORT_CHAT_PAGE_SIZE * 2 + 3).unwrap());
+        // More messages than fit on one page have the same date.
+        let date_created = data_cm.0.get(0).unwrap().date_created;
+        for ch_msg in data_cm.0.iter_mut() {
+            ch_msg.date_created = date_created;
+        }
+        let stream_id = data_cm.0.get(0).unwrap().stream_id;
+        let ch_msg_vec = data_cm.0.clone();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_export)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/chat_messages/export?streamId={}&format=txt", stream_id))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response = String::from_utf8(body.to_vec()).unwrap();
+        let lines: Vec<&str> = response.lines().collect();
+        // All messages are transmitted once, in the order of their IDs.
+        assert_eq!(lines.len(), ch_msg_vec.len());
+        let mut ch_msg_sorted = ch_msg_vec.clone();
+        ch_msg_sorted.sort_by_key(|v| v.id);
+        for (line, ch_msg) in lines.iter().zip(ch_msg_sorted.iter()) {
+            assert!(line.ends_with(&format!("{}: {}", ch_msg.user_name, ch_msg.msg.clone().unwrap())));
+        }
+    }
+}
@@ -6,20 +6,29 @@ mod tests {
         http::header::{CONTENT_TYPE, HeaderValue},
         test,
     };
-    use chrono::SecondsFormat;
+    use chrono::{Duration, SecondsFormat, Utc};
     use serde_json;
     use vrb_authent::{
         config_jwt,
-        user_orm::tests::{USER, USER1_ID, UserOrmTest},
+        user_models::Session,
+        user_orm::tests::{ADMIN, USER, USER1_ID, USER2_ID, USER3_ID, USER4_ID, UserOrmTest},
     };
+    use vrb_common::{
+        api_error::{ApiError, code_to_str},
+        err,
+    };
+    use vrb_dbase::enm_user_role::UserRole;
 
     use crate::{
-        chat_message_controller::{get_chat_message, tests as ChatMessageCtrlTest},
-        chat_message_models::ChatMessageDto,
-        chat_message_orm::tests::ChatMessageOrmTest,
+        chat_message_controller::{
+            MSG_VIEW_ANOTHER_USERS_CHAT_MESSAGE_LOGS, get_chat_message, get_chat_message_logs, tests as ChatMessageCtrlTest,
+        },
+        chat_message_models::{ChatMessageDto, ChatMessageLog, ChatMessageLogDto, ChatMessageReactionDto},
+        chat_message_orm::tests::{ChatMessageOrmTest, ReactionData},
     };
 
     const MSG_FAILED_DESER: &str = "Failed to deserialize response from JSON.";
+    const MSG_CASTING_TO_TYPE: &str = "invalid digit found in string";
 
     // ** get_chat_message **
 
@@ -232,4 +241,388 @@ mod tests {
         assert_eq!(response.len(), ch_msg2_vec_ser.len());
         assert_eq!(response, ch_msg2_vec_ser);
     }
+    #[actix_web::test]
+    async fn test_get_chat_message_search_by_thread_id() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let mut data_cm = ChatMessageOrmTest::chat_messages(6);
+        let stream_id = data_cm.0.get(0).unwrap().stream_id.clone();
+        let ids: Vec<i32> = data_cm.0.iter().map(|ch_msg| ch_msg.id).collect();
+        // Thread: ids[1] -> ids[0], ids[3] -> ids[1], ids[4] -> ids[0]. ids[2] -> ids[5] (another thread).
+        data_cm.0.get_mut(1).unwrap().reply_to = Some(ids[0]);
+        data_cm.0.get_mut(3).unwrap().reply_to = Some(ids[1]);
+        data_cm.0.get_mut(4).unwrap().reply_to = Some(ids[0]);
+        data_cm.0.get_mut(2).unwrap().reply_to = Some(ids[5]);
+        let ch_msg_vec = data_cm.0.clone();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/chat_messages?streamId={}&threadId={}", stream_id, ids[0]))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: Vec<ChatMessageDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        let response_ids: Vec<i32> = response.iter().map(|ch_msg_dto| ch_msg_dto.id).collect();
+        assert_eq!(response_ids, vec![ids[0], ids[1], ids[3], ids[4]]);
+        assert_eq!(response[0].reply_to, None);
+        assert_eq!(response[0].reply_member, None);
+        assert_eq!(response[0].reply_msg, None);
+        for (ch_msg_dto, parent_idx) in response[1..].iter().zip([0, 1, 0]) {
+            let parent = ch_msg_vec.get(parent_idx).unwrap();
+            assert_eq!(ch_msg_dto.reply_to, Some(parent.id));
+            assert_eq!(ch_msg_dto.reply_member, Some(parent.user_name.clone()));
+            assert_eq!(ch_msg_dto.reply_msg, parent.msg.clone());
+        }
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_search_by_thread_id_with_removed_root() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let mut data_cm = ChatMessageOrmTest::chat_messages(3);
+        let stream_id = data_cm.0.get(0).unwrap().stream_id.clone();
+        let ch_msg1 = data_cm.0.get_mut(0).unwrap();
+        ch_msg1.msg = None;
+        ch_msg1.date_removed = Some(Utc::now());
+        let ch_msg1 = ch_msg1.clone();
+        data_cm.0.get_mut(1).unwrap().reply_to = Some(ch_msg1.id);
+        let ch_msg2_id = data_cm.0.get(1).unwrap().id;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/chat_messages?streamId={}&threadId={}", stream_id, ch_msg1.id))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: Vec<ChatMessageDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.len(), 2);
+        assert_eq!(response[0].id, ch_msg1.id);
+        assert!(response[0].date_rmv.is_some());
+        // The reply to the removed message keeps the link and the author, but not the text.
+        assert_eq!(response[1].id, ch_msg2_id);
+        assert_eq!(response[1].reply_to, Some(ch_msg1.id));
+        assert_eq!(response[1].reply_member, Some(ch_msg1.user_name.clone()));
+        assert_eq!(response[1].reply_msg, None);
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_search_with_reactions() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let mut data_cm = ChatMessageOrmTest::chat_messages(3);
+        let stream_id = data_cm.0.get(0).unwrap().stream_id.clone();
+        let ids: Vec<i32> = data_cm.0.iter().map(|ch_msg| ch_msg.id).collect();
+        data_cm.4.push(ReactionData::new(ids[0], USER1_ID, "👍"));
+        data_cm.4.push(ReactionData::new(ids[0], USER2_ID, "🔥"));
+        data_cm.4.push(ReactionData::new(ids[0], USER3_ID, "👍"));
+        data_cm.4.push(ReactionData::new(ids[2], USER2_ID, "👍"));
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/chat_messages?streamId={}", stream_id))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: Vec<ChatMessageDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.len(), 3);
+        let reactions1 = vec![ChatMessageReactionDto::new("👍", 2), ChatMessageReactionDto::new("🔥", 1)];
+        assert_eq!(response[0].reactions, reactions1);
+        assert_eq!(response[1].reactions, vec![]);
+        assert_eq!(response[2].reactions, vec![ChatMessageReactionDto::new("👍", 1)]);
+    }
+
+    // ** get_chat_message_logs **
+
+    #[actix_web::test]
+    async fn test_get_chat_message_logs_invalid_id() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let mut data_u = UserOrmTest::users(&[USER]);
+        data_u.0.get_mut(0).unwrap().role = UserRole::Moderator;
+        let data_cm = ChatMessageOrmTest::chat_messages(2);
+        let ch_msg_id_bad = format!("{}a", data_cm.0.last().unwrap().id);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_logs)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/chat_messages/{}/logs", ch_msg_id_bad))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::RANGE_NOT_SATISFIABLE); // 416
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::RANGE_NOT_SATISFIABLE));
+        #[rustfmt::skip]
+        let msg = format!("{}; `{}` - {}", err::MSG_PARSING_TYPE_NOT_SUPPORTED, "id", MSG_CASTING_TO_TYPE);
+        assert!(app_err.message.starts_with(&msg));
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_logs_user_without_access_rights() {
+        let token2 = config_jwt::tests::get_token(USER2_ID);
+        let mut data_u = UserOrmTest::users(&[USER, USER]);
+        // Add session (num_token) for user2.
+        data_u.1.push(Session::new(2, USER2_ID, config_jwt::tests::get_num_token(USER2_ID)));
+        let data_cm = ChatMessageOrmTest::chat_messages(4);
+        // The message of user3 in the stream of user1.
+        let ch_msg = data_cm.0.iter().find(|v| v.user_id == USER3_ID).unwrap().clone();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_logs)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/chat_messages/{}/logs", ch_msg.id))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token2)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN); // 403
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::FORBIDDEN));
+        #[rustfmt::skip]
+        assert_eq!(app_err.message, format!("{}; id: {}, user_id: {}", MSG_VIEW_ANOTHER_USERS_CHAT_MESSAGE_LOGS, ch_msg.id, USER2_ID));
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_logs_user_non_existent_message() {
+        let token2 = config_jwt::tests::get_token(USER2_ID);
+        let mut data_u = UserOrmTest::users(&[USER, USER]);
+        // Add session (num_token) for user2.
+        data_u.1.push(Session::new(2, USER2_ID, config_jwt::tests::get_num_token(USER2_ID)));
+        let data_cm = ChatMessageOrmTest::chat_messages(4);
+        let ch_msg_id = data_cm.0.last().unwrap().id + 1;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_logs)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/chat_messages/{}/logs", ch_msg_id))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token2)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND); // 404
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::NOT_FOUND));
+        assert_eq!(app_err.message, format!("{}; id: {}", err::MSG_CHAT_MESSAGE_NOT_FOUND, ch_msg_id));
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_logs_moderator_non_existent_message() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let mut data_u = UserOrmTest::users(&[USER]);
+        data_u.0.get_mut(0).unwrap().role = UserRole::Moderator;
+        let data_cm = ChatMessageOrmTest::chat_messages(2);
+        let ch_msg_id = data_cm.0.last().unwrap().id + 1;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_logs)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/chat_messages/{}/logs", ch_msg_id))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND); // 404
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.message, format!("{}; id: {}", err::MSG_CHAT_MESSAGE_NOT_FOUND, ch_msg_id));
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_logs_author_message_with_logs() {
+        let token2 = config_jwt::tests::get_token(USER2_ID);
+        let mut data_u = UserOrmTest::users(&[USER, USER]);
+        // Add session (num_token) for user2.
+        data_u.1.push(Session::new(2, USER2_ID, config_jwt::tests::get_num_token(USER2_ID)));
+        let mut data_cm = ChatMessageOrmTest::chat_messages(4);
+        // The message of user2 in the stream of user1.
+        let ch_msg = data_cm.0.iter_mut().find(|v| v.user_id == USER2_ID).unwrap();
+        let date_update = Utc::now() - Duration::minutes(1);
+        ch_msg.date_changed = Some(date_update.clone());
+        let ch_msg_id = ch_msg.id;
+        data_cm.1 = vec![ChatMessageLog::new(1, ch_msg_id, "old_msg1", date_update)];
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_logs)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/chat_messages/{}/logs", ch_msg_id))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token2)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: Vec<ChatMessageLogDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.len(), 1);
+        assert_eq!(response[0].chat_message_id, ch_msg_id);
+        assert_eq!(response[0].old_msg, "old_msg1");
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_logs_stream_owner_message_with_logs() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let mut data_cm = ChatMessageOrmTest::chat_messages(4);
+        // The message of user3 in the stream of user1.
+        let ch_msg = data_cm.0.iter_mut().find(|v| v.user_id == USER3_ID).unwrap();
+        let date_update = Utc::now() - Duration::minutes(1);
+        ch_msg.date_changed = Some(date_update.clone());
+        let ch_msg_id = ch_msg.id;
+        data_cm.1 = vec![ChatMessageLog::new(1, ch_msg_id, "old_msg1", date_update)];
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_logs)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/chat_messages/{}/logs", ch_msg_id))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: Vec<ChatMessageLogDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.len(), 1);
+        assert_eq!(response[0].chat_message_id, ch_msg_id);
+        assert_eq!(response[0].old_msg, "old_msg1");
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_logs_stream_moderator_message_with_logs() {
+        let token3 = config_jwt::tests::get_token(USER3_ID);
+        let mut data_u = UserOrmTest::users(&[USER, USER, USER]);
+        // Add session (num_token) for user3.
+        data_u.1.push(Session::new(3, USER3_ID, config_jwt::tests::get_num_token(USER3_ID)));
+        let mut data_cm = ChatMessageOrmTest::chat_messages(4);
+        // User3 is a chat moderator of stream 2 (the stream of user2).
+        let (stream2_id, moderator_id) = ChatMessageOrmTest::stream_moderators().get(0).unwrap().clone();
+        assert_eq!(moderator_id, USER3_ID);
+        // The message of user4 in the stream of user2.
+        let ch_msg = data_cm.0.iter_mut().find(|v| v.user_id == USER4_ID).unwrap();
+        ch_msg.stream_id = stream2_id;
+        let date_update = Utc::now() - Duration::minutes(1);
+        ch_msg.date_changed = Some(date_update.clone());
+        let ch_msg_id = ch_msg.id;
+        data_cm.1 = vec![ChatMessageLog::new(1, ch_msg_id, "old_msg1", date_update)];
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_logs)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/chat_messages/{}/logs", ch_msg_id))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token3)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: Vec<ChatMessageLogDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.len(), 1);
+        assert_eq!(response[0].chat_message_id, ch_msg_id);
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_logs_moderator_message_with_logs() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let mut data_u = UserOrmTest::users(&[USER]);
+        data_u.0.get_mut(0).unwrap().role = UserRole::Moderator;
+        let mut data_cm = ChatMessageOrmTest::chat_messages(2);
+        let ch_msg = data_cm.0.get_mut(1).unwrap();
+        let date_update = Utc::now() - Duration::minutes(1);
+        ch_msg.date_changed = Some(date_update.clone());
+        let ch_msg_id = ch_msg.id;
+        data_cm.1 = vec![
+            ChatMessageLog::new(1, ch_msg_id, "old_msg1", date_update - Duration::seconds(30)),
+            ChatMessageLog::new(2, ch_msg_id, "old_msg2", date_update),
+        ];
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_logs)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/chat_messages/{}/logs", ch_msg_id))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: Vec<ChatMessageLogDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.len(), 2);
+        assert_eq!(response[0].chat_message_id, ch_msg_id);
+        assert_eq!(response[0].old_msg, "old_msg1");
+        assert_eq!(response[1].chat_message_id, ch_msg_id);
+        assert_eq!(response[1].old_msg, "old_msg2");
+        let date_update_str = date_update.to_rfc3339_opts(SecondsFormat::Secs, true);
+        assert_eq!(response[1].date_update.to_rfc3339_opts(SecondsFormat::Secs, true), date_update_str);
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_logs_admin_message_without_logs() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN]);
+        let data_cm = ChatMessageOrmTest::chat_messages(2);
+        let ch_msg_id = data_cm.0.get(0).unwrap().id;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_logs)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/chat_messages/{}/logs", ch_msg_id))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: Vec<ChatMessageLogDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.len(), 0);
+    }
 }
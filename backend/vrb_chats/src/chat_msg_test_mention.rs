@@ -0,0 +1,234 @@
+#[cfg(all(test, feature = "mockdata"))]
+mod tests {
+    use actix_web::{
+        self, App, body, dev,
+        http::StatusCode,
+        http::header::{CONTENT_TYPE, HeaderValue},
+        test,
+    };
+    use chrono::{Duration, Utc};
+    use serde_json;
+    use vrb_authent::{
+        config_jwt,
+        user_models::Session,
+        user_orm::tests::{USER, USER1_ID, USER2_ID, USER3_ID, USER4_ID, UserOrmTest},
+    };
+
+    use crate::{
+        chat_message_controller::{get_chat_mentions, post_chat_message, put_chat_mentions_read, tests as ChatMessageCtrlTest},
+        chat_message_models::{
+            self, ChatMention, ChatMentionDto, ChatMessage, ChatMessageDto, CreateChatMessageDto, MENTIONS_MAX, ReadChatMentionsDto,
+        },
+        chat_message_orm::tests::{ChatMessageOrmTest, MENTION_ID, MentionData},
+    };
+
+    const MSG_FAILED_DESER: &str = "Failed to deserialize response from JSON.";
+
+    // Get the expected list of mentions (in the format of the response).
+    fn to_mention_dto_vec(data_cm: &(Vec<ChatMessage>, Vec<MentionData>), ids: &[i32]) -> Vec<ChatMentionDto> {
+        let mut result: Vec<ChatMentionDto> = Vec::new();
+        for mention in data_cm.1.iter().filter(|v| ids.contains(&v.id)) {
+            let ch_msg = data_cm.0.iter().find(|v| v.id == mention.chat_message_id).unwrap();
+            #[rustfmt::skip]
+            let chat_mention = ChatMention::new(
+                mention.id, ch_msg.id, ch_msg.stream_id, &ch_msg.user_name, ch_msg.msg.clone(), ch_msg.date_created);
+            result.push(ChatMentionDto::from(chat_mention));
+        }
+        let json = serde_json::json!(&result).to_string();
+        serde_json::from_slice(json.as_bytes()).expect(MSG_FAILED_DESER)
+    }
+
+    // ** parse_mentions **
+
+    #[actix_web::test]
+    async fn test_parse_mentions_valid() {
+        let nicknames = chat_message_models::parse_mentions("@oliver_taylor hi, @robert_brown and (@mary_williams)!");
+        assert_eq!(nicknames, vec!["oliver_taylor", "robert_brown", "mary_williams"]);
+    }
+    #[actix_web::test]
+    async fn test_parse_mentions_invalid() {
+        // There are no mentions in the e-mail, after "@" there must be a letter and at least two characters.
+        let nicknames = chat_message_models::parse_mentions("mail@oliver_taylor @ @1robert @m");
+        assert_eq!(nicknames, Vec::<String>::new());
+    }
+    #[actix_web::test]
+    async fn test_parse_mentions_without_repetitions() {
+        let nicknames = chat_message_models::parse_mentions("@robert_brown @robert_brown, @robert_brown.");
+        assert_eq!(nicknames, vec!["robert_brown"]);
+    }
+    #[actix_web::test]
+    async fn test_parse_mentions_max() {
+        let msg: String = (0..(MENTIONS_MAX + 2)).map(|i| format!("@user_{} ", i)).collect();
+        let nicknames = chat_message_models::parse_mentions(&msg);
+        assert_eq!(nicknames.len(), MENTIONS_MAX);
+        assert_eq!(nicknames.last().unwrap(), &format!("user_{}", MENTIONS_MAX - 1));
+    }
+
+    // ** get_chat_mentions **
+
+    #[actix_web::test]
+    async fn test_get_chat_mentions_absent() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let data_cm = ChatMessageOrmTest::chat_messages(4);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_mentions)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri("/api/chat_mentions")
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: Vec<ChatMentionDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.len(), 0);
+    }
+    #[actix_web::test]
+    async fn test_get_chat_mentions_unread() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let mut data_cm = ChatMessageOrmTest::chat_messages(4);
+        let ch_msg_ids: Vec<i32> = data_cm.0.iter().map(|v| v.id).collect();
+        // The message of user4 has been deleted.
+        data_cm.0.get_mut(3).unwrap().date_removed = Some(Utc::now());
+        #[rustfmt::skip]
+        let mention_vec = vec![
+            MentionData::new(MENTION_ID, ch_msg_ids[1], USER1_ID, None), // unread
+            MentionData::new(MENTION_ID + 1, ch_msg_ids[2], USER1_ID, Some(Utc::now() - Duration::minutes(1))), // read
+            MentionData::new(MENTION_ID + 2, ch_msg_ids[3], USER1_ID, None), // in the deleted message
+            MentionData::new(MENTION_ID + 3, ch_msg_ids[0], USER2_ID, None), // another user
+            MentionData::new(MENTION_ID + 4, ch_msg_ids[2], USER1_ID, None), // unread
+        ];
+        data_cm.5 = mention_vec;
+        let mention_dto_vec = to_mention_dto_vec(&(data_cm.0.clone(), data_cm.5.clone()), &[MENTION_ID, MENTION_ID + 4]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_mentions)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri("/api/chat_mentions")
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: Vec<ChatMentionDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.len(), mention_dto_vec.len());
+        assert_eq!(response, mention_dto_vec);
+        assert_eq!(response[0].member, ChatMessageOrmTest::get_user_name(USER2_ID));
+    }
+
+    // ** put_chat_mentions_read **
+
+    #[actix_web::test]
+    async fn test_put_chat_mentions_read_by_ids() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let mut data_cm = ChatMessageOrmTest::chat_messages(4);
+        let ch_msg_ids: Vec<i32> = data_cm.0.iter().map(|v| v.id).collect();
+        #[rustfmt::skip]
+        let mention_vec = vec![
+            MentionData::new(MENTION_ID, ch_msg_ids[1], USER1_ID, None),
+            MentionData::new(MENTION_ID + 1, ch_msg_ids[2], USER1_ID, None),
+            MentionData::new(MENTION_ID + 2, ch_msg_ids[0], USER2_ID, None), // another user
+        ];
+        data_cm.5 = mention_vec;
+        let mention_dto_vec = to_mention_dto_vec(&(data_cm.0.clone(), data_cm.5.clone()), &[MENTION_ID + 1]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_chat_mentions_read)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri("/api/chat_mentions/read")
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1))
+            .set_json(ReadChatMentionsDto { ids: Some(vec![MENTION_ID + 1, MENTION_ID + 2]) })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: Vec<ChatMentionDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response, mention_dto_vec);
+    }
+    #[actix_web::test]
+    async fn test_put_chat_mentions_read_all() {
+        let token2 = config_jwt::tests::get_token(USER2_ID);
+        let mut data_u = UserOrmTest::users(&[USER, USER]);
+        // Add session (num_token) for user2.
+        data_u.1.push(Session::new(2, USER2_ID, config_jwt::tests::get_num_token(USER2_ID)));
+        let mut data_cm = ChatMessageOrmTest::chat_messages(4);
+        let ch_msg_ids: Vec<i32> = data_cm.0.iter().map(|v| v.id).collect();
+        #[rustfmt::skip]
+        let mention_vec = vec![
+            MentionData::new(MENTION_ID, ch_msg_ids[0], USER2_ID, None),
+            MentionData::new(MENTION_ID + 1, ch_msg_ids[2], USER2_ID, Some(Utc::now() - Duration::minutes(1))), // read
+            MentionData::new(MENTION_ID + 2, ch_msg_ids[3], USER2_ID, None),
+            MentionData::new(MENTION_ID + 3, ch_msg_ids[1], USER1_ID, None), // another user
+        ];
+        data_cm.5 = mention_vec;
+        let mention_dto_vec = to_mention_dto_vec(&(data_cm.0.clone(), data_cm.5.clone()), &[MENTION_ID, MENTION_ID + 2]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_chat_mentions_read)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri("/api/chat_mentions/read")
+            .insert_header(ChatMessageCtrlTest::header_auth(&token2))
+            .set_json(ReadChatMentionsDto { ids: None })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: Vec<ChatMentionDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response, mention_dto_vec);
+    }
+
+    // ** post_chat_message **
+
+    #[actix_web::test]
+    async fn test_post_chat_message_with_mentions() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let data_cm = ChatMessageOrmTest::chat_messages(2);
+        let stream_id = data_cm.0.get(0).unwrap().stream_id.clone();
+        let user3_name = ChatMessageOrmTest::get_user_name(USER3_ID);
+        let user4_name = ChatMessageOrmTest::get_user_name(USER4_ID);
+        // User4 has blocked user1, so the mention of user4 is skipped (the message is still created).
+        let msg = format!("Hi @{} and @{}", user3_name, user4_name);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_chat_message)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/chat_messages")
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1))
+            .set_json(CreateChatMessageDto { stream_id, msg: msg.clone(), reply_to: None })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED); // 201
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let chat_message_dto_res: ChatMessageDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(chat_message_dto_res.msg, msg);
+    }
+}
@@ -19,7 +19,9 @@ mod tests {
 
     use crate::{
         chat_message_controller::{post_chat_message, put_chat_message, tests as ChatMessageCtrlTest},
-        chat_message_models::{self, ChatMessageDto, ChatMessageMock, CreateChatMessageDto, ModifyChatMessageDto},
+        chat_message_models::{
+            self, ChatMessageDto, ChatMessageMock, CreateChatMessageDto, ModifyChatMessageDto, REPLY_MSG_MAX, reply_snippet,
+        },
         chat_message_orm::tests::ChatMessageOrmTest,
     };
 
@@ -97,7 +99,7 @@ mod tests {
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/chat_messages")
             .insert_header(ChatMessageCtrlTest::header_auth(&token1))
-            .set_json(CreateChatMessageDto { stream_id, msg: ChatMessageMock::message_min() })
+            .set_json(CreateChatMessageDto { stream_id, msg: ChatMessageMock::message_min(), reply_to: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
@@ -125,7 +127,7 @@ mod tests {
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/chat_messages")
             .insert_header(ChatMessageCtrlTest::header_auth(&token1))
-            .set_json(CreateChatMessageDto { stream_id, msg: ChatMessageMock::message_max() })
+            .set_json(CreateChatMessageDto { stream_id, msg: ChatMessageMock::message_max(), reply_to: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
@@ -154,7 +156,7 @@ mod tests {
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/chat_messages")
             .insert_header(ChatMessageCtrlTest::header_auth(&token1))
-            .set_json(CreateChatMessageDto { stream_id: stream_id_wrong, msg: msg.clone() })
+            .set_json(CreateChatMessageDto { stream_id: stream_id_wrong, msg: msg.clone(), reply_to: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::NOT_ACCEPTABLE); // 406
@@ -190,7 +192,7 @@ mod tests {
         #[rustfmt::skip]
         let req = test::TestRequest::post().uri("/api/chat_messages")
             .insert_header(ChatMessageCtrlTest::header_auth(&token1))
-            .set_json(CreateChatMessageDto { stream_id, msg: msg.clone() })
+            .set_json(CreateChatMessageDto { stream_id, msg: msg.clone(), reply_to: None })
             .to_request();
         let resp: dev::ServiceResponse = test::call_service(&app, req).await;
         assert_eq!(resp.status(), StatusCode::CREATED); // 201
@@ -209,6 +211,120 @@ mod tests {
         assert_eq!(chat_message_dto_res.msg, msg);
         assert_eq!(chat_message_dto_res.date_edt, None);
         assert_eq!(chat_message_dto_res.date_rmv, None);
+        assert_eq!(chat_message_dto_res.reply_to, None);
+        assert_eq!(chat_message_dto_res.reply_member, None);
+        assert_eq!(chat_message_dto_res.reply_msg, None);
+    }
+    #[actix_web::test]
+    async fn test_post_chat_message_reply_to_non_existent_message() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let data_cm = ChatMessageOrmTest::chat_messages(2);
+        let stream_id = data_cm.0.get(0).unwrap().stream_id.clone();
+        let reply_to = data_cm.0.last().unwrap().id.clone() + 1;
+        let msg = ChatMessageMock::message_norm();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_chat_message)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/chat_messages")
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1))
+            .set_json(CreateChatMessageDto { stream_id, msg: msg.clone(), reply_to: Some(reply_to) })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_ACCEPTABLE); // 406
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::NOT_ACCEPTABLE));
+        #[rustfmt::skip]
+        let message = format!("{}; stream_id: {}, msg: \"{}\", reply_to: {}", err::MSG_PARAMETER_UNACCEPTABLE, stream_id, &msg, reply_to);
+        assert_eq!(app_err.message, message);
+        #[rustfmt::skip]
+        let json = serde_json::json!({ "stream_id": stream_id, "msg": &msg, "reply_to": reply_to });
+        assert_eq!(*app_err.params.get("invalidParams").unwrap(), json);
+    }
+    #[actix_web::test]
+    async fn test_post_chat_message_reply_to_removed_message() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let mut data_cm = ChatMessageOrmTest::chat_messages(2);
+        let ch_msg1 = data_cm.0.get_mut(0).unwrap();
+        ch_msg1.date_removed = Some(Utc::now());
+        ch_msg1.msg = None;
+        let stream_id = ch_msg1.stream_id.clone();
+        let reply_to = ch_msg1.id.clone();
+        let msg = ChatMessageMock::message_norm();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_chat_message)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/chat_messages")
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1))
+            .set_json(CreateChatMessageDto { stream_id, msg: msg.clone(), reply_to: Some(reply_to) })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_ACCEPTABLE); // 406
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::NOT_ACCEPTABLE));
+        #[rustfmt::skip]
+        let json = serde_json::json!({ "stream_id": stream_id, "msg": &msg, "reply_to": reply_to });
+        assert_eq!(*app_err.params.get("invalidParams").unwrap(), json);
+    }
+    #[actix_web::test]
+    async fn test_post_chat_message_reply_to_valid_data() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let mut data_cm = ChatMessageOrmTest::chat_messages(2);
+        let ch_msg2 = data_cm.0.get_mut(1).unwrap();
+        // The text of the parent message is longer than the fragment (quote).
+        let parent_msg: String = (0..(REPLY_MSG_MAX + 10)).map(|_| 'b').collect();
+        ch_msg2.msg = Some(parent_msg.clone());
+        let ch_msg2 = ch_msg2.clone();
+        let last_msg_id = data_cm.0.last().unwrap().id.clone();
+        let user1_name = data_u.0.get(0).unwrap().nickname.clone();
+        let msg = ChatMessageMock::message_norm();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_chat_message)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/chat_messages")
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1))
+            .set_json(CreateChatMessageDto { stream_id: ch_msg2.stream_id, msg: msg.clone(), reply_to: Some(ch_msg2.id) })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED); // 201
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let chat_message_dto_res: ChatMessageDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(chat_message_dto_res.id, last_msg_id + 1);
+        assert_eq!(chat_message_dto_res.member, user1_name);
+        assert_eq!(chat_message_dto_res.msg, msg);
+        assert_eq!(chat_message_dto_res.reply_to, Some(ch_msg2.id));
+        assert_eq!(chat_message_dto_res.reply_member, Some(ch_msg2.user_name.clone()));
+        assert_eq!(chat_message_dto_res.reply_msg, Some(reply_snippet(&parent_msg)));
+        let reply_msg = chat_message_dto_res.reply_msg.unwrap();
+        assert!(reply_msg.starts_with(&parent_msg[..REPLY_MSG_MAX]) && reply_msg.ends_with("..."));
     }
 
     // ** put_chat_message **
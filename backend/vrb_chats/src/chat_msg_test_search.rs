@@ -0,0 +1,287 @@
+#[cfg(all(test, feature = "mockdata"))]
+mod tests {
+    use actix_web::{
+        self, App, body, dev,
+        http::StatusCode,
+        http::header::{CONTENT_TYPE, HeaderValue},
+        test,
+    };
+    use chrono::Utc;
+    use serde_json;
+    use vrb_authent::{
+        config_jwt,
+        user_models::Session,
+        user_orm::tests::{USER, USER1_ID, USER2_ID, USER3_ID, USER4_ID, UserOrmTest},
+    };
+    use vrb_common::api_error::{ApiError, code_to_str};
+
+    use crate::{
+        chat_message_controller::{get_chat_message_search, tests as ChatMessageCtrlTest},
+        chat_message_models::{self, ChatMessageFoundPageDto},
+        chat_message_orm::tests::ChatMessageOrmTest,
+    };
+
+    const MSG_FAILED_DESER: &str = "Failed to deserialize response from JSON.";
+
+    // ** get_chat_message_search **
+
+    #[actix_web::test]
+    async fn test_get_chat_message_search_without_text_and_member() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let data_cm = ChatMessageOrmTest::chat_messages(4);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_search)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri("/api/chat_messages/search?streamId=1")
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err_vec: Vec<ApiError> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        #[rustfmt::skip]
+        ChatMessageCtrlTest::check_app_err(app_err_vec, &code_to_str(StatusCode::EXPECTATION_FAILED), &[chat_message_models::MSG_SEARCH_ONE_OPTIONAL_MUST_PRESENT]);
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_search_text_min() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let data_cm = ChatMessageOrmTest::chat_messages(4);
+        let text = "a".repeat((chat_message_models::SEARCH_TEXT_MIN - 1).into());
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_search)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/chat_messages/search?text={}", text))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err_vec: Vec<ApiError> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        #[rustfmt::skip]
+        ChatMessageCtrlTest::check_app_err(app_err_vec, &code_to_str(StatusCode::EXPECTATION_FAILED), &[chat_message_models::MSG_SEARCH_TEXT_MIN_LENGTH]);
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_search_limit_max() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let data_cm = ChatMessageOrmTest::chat_messages(4);
+        let limit = chat_message_models::SEARCH_LIMIT_MAX + 1;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_search)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/chat_messages/search?text=hello&limit={}", limit))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err_vec: Vec<ApiError> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        #[rustfmt::skip]
+        ChatMessageCtrlTest::check_app_err(app_err_vec, &code_to_str(StatusCode::EXPECTATION_FAILED), &[chat_message_models::MSG_SEARCH_LIMIT_MAX_AMOUNT]);
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_search_by_text() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let mut data_cm = ChatMessageOrmTest::chat_messages(4);
+        data_cm.0.get_mut(0).unwrap().msg = Some("Hello everyone".to_owned());
+        data_cm.0.get_mut(1).unwrap().msg = Some("Good evening".to_owned());
+        data_cm.0.get_mut(2).unwrap().msg = Some("hello, how are you?".to_owned());
+        // The deleted message is not searched.
+        data_cm.0.get_mut(3).unwrap().msg = Some("hello".to_owned());
+        data_cm.0.get_mut(3).unwrap().date_removed = Some(Utc::now());
+        let ch_msg_ids: Vec<i32> = data_cm.0.iter().map(|v| v.id).collect();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_search)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri("/api/chat_messages/search?streamId=1&text=HELLO")
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: ChatMessageFoundPageDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.count, 2);
+        assert_eq!(response.page, chat_message_models::SEARCH_CHAT_TEXT_PAGE);
+        assert_eq!(response.pages, 1);
+        assert_eq!(response.limit, chat_message_models::SEARCH_CHAT_TEXT_LIMIT);
+        // Messages are sorted starting with the newest.
+        let ids: Vec<i32> = response.list.iter().map(|v| v.id).collect();
+        assert_eq!(ids, vec![ch_msg_ids[2], ch_msg_ids[0]]);
+        assert_eq!(response.list[0].member, ChatMessageOrmTest::get_user_name(USER3_ID));
+        assert_eq!(response.list[0].msg, "hello, how are you?");
+        assert_eq!(response.list[0].highlight, "<mark>hello</mark>, how are you?");
+        assert_eq!(response.list[1].highlight, "<mark>Hello</mark> everyone");
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_search_by_text_with_markup() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let mut data_cm = ChatMessageOrmTest::chat_messages(1);
+        data_cm.0.get_mut(0).unwrap().msg = Some("hello <img src=x onerror=alert(1)> & \"bye\"".to_owned());
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_search)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri("/api/chat_messages/search?streamId=1&text=hello")
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: ChatMessageFoundPageDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.count, 1);
+        assert_eq!(response.list[0].msg, "hello <img src=x onerror=alert(1)> & \"bye\"");
+        // The markup of the message text is escaped, only the "<mark>" tag remains.
+        #[rustfmt::skip]
+        assert_eq!(response.list[0].highlight, "<mark>hello</mark> &lt;img src=x onerror=alert(1)&gt; &amp; &quot;bye&quot;");
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_search_by_member() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let data_cm = ChatMessageOrmTest::chat_messages(8);
+        let member = ChatMessageOrmTest::get_user_name(USER2_ID);
+        #[rustfmt::skip]
+        let ch_msg_ids: Vec<i32> = data_cm.0.iter().rev().filter(|v| v.user_id == USER2_ID).map(|v| v.id).collect();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_search)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/chat_messages/search?member={}", member))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: ChatMessageFoundPageDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.count, u32::try_from(ch_msg_ids.len()).unwrap());
+        let ids: Vec<i32> = response.list.iter().map(|v| v.id).collect();
+        assert_eq!(ids, ch_msg_ids);
+        assert!(response.list.iter().all(|v| v.member == member && v.msg == v.highlight));
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_search_by_page() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let mut data_cm = ChatMessageOrmTest::chat_messages(7);
+        for ch_msg in data_cm.0.iter_mut() {
+            ch_msg.msg = Some(format!("hello {}", ch_msg.id));
+        }
+        let ch_msg_ids: Vec<i32> = data_cm.0.iter().rev().map(|v| v.id).collect();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_search)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri("/api/chat_messages/search?text=hello&page=2&limit=3")
+            .insert_header(ChatMessageCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: ChatMessageFoundPageDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.count, 7);
+        assert_eq!(response.page, 2);
+        assert_eq!(response.pages, 3);
+        assert_eq!(response.limit, 3);
+        let ids: Vec<i32> = response.list.iter().map(|v| v.id).collect();
+        assert_eq!(ids, ch_msg_ids[3..6].to_vec());
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_search_in_another_users_stream() {
+        let token2 = config_jwt::tests::get_token(USER2_ID);
+        let mut data_u = UserOrmTest::users(&[USER, USER]);
+        // Add session (num_token) for user2.
+        data_u.1.push(Session::new(2, USER2_ID, config_jwt::tests::get_num_token(USER2_ID)));
+        let data_cm = ChatMessageOrmTest::chat_messages(4);
+        let member = ChatMessageOrmTest::get_user_name(USER2_ID);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_search)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        // User2 is neither the owner nor the moderator of the stream of user1.
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/chat_messages/search?streamId=1&member={}", member))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token2)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: ChatMessageFoundPageDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.count, 0);
+        assert_eq!(response.pages, 0);
+        assert_eq!(response.list.len(), 0);
+    }
+    #[actix_web::test]
+    async fn test_get_chat_message_search_by_stream_moderator() {
+        let token3 = config_jwt::tests::get_token(USER3_ID);
+        let mut data_u = UserOrmTest::users(&[USER, USER, USER]);
+        // Add session (num_token) for user3.
+        data_u.1.push(Session::new(3, USER3_ID, config_jwt::tests::get_num_token(USER3_ID)));
+        let mut data_cm = ChatMessageOrmTest::chat_messages(4);
+        // Messages in the stream of user2 (user3 is the chat moderator of this stream).
+        data_cm.0.get_mut(1).unwrap().stream_id = 2;
+        data_cm.0.get_mut(3).unwrap().stream_id = 2;
+        let ch_msg_ids: Vec<i32> = data_cm.0.iter().map(|v| v.id).collect();
+        let member = ChatMessageOrmTest::get_user_name(USER4_ID);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_chat_message_search)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/chat_messages/search?ownerId={}&member={}", USER2_ID, member))
+            .insert_header(ChatMessageCtrlTest::header_auth(&token3)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: ChatMessageFoundPageDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.count, 1);
+        assert_eq!(response.list[0].id, ch_msg_ids[3]);
+        assert_eq!(response.list[0].stream_id, 2);
+    }
+}
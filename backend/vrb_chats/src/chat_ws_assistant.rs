@@ -1,11 +1,12 @@
 use actix_web::http::StatusCode;
+use chrono::{DateTime, Utc};
 use log::error;
 #[cfg(not(all(test, feature = "mockdata")))]
 use vrb_authent::user_orm::impls::UserOrmApp;
 #[cfg(all(test, feature = "mockdata"))]
 use vrb_authent::user_orm::tests::UserOrmApp;
 use vrb_authent::{
-    authentication::{is_session_not_found, is_unacceptable_token_id, is_unacceptable_token_num},
+    authentication::{is_session_not_found, is_unacceptable_token_id, is_unacceptable_token_num, is_user_suspended},
     config_jwt,
     user_models::User,
     user_orm::UserOrm,
@@ -14,6 +15,7 @@ use vrb_common::{
     api_error::{ApiError, code_to_str},
     err,
 };
+use vrb_dbase::enm_user_role::UserRole;
 use vrb_tools::token_coding;
 
 #[cfg(not(all(test, feature = "mockdata")))]
@@ -22,7 +24,8 @@ use crate::chat_message_orm::impls::ChatMessageOrmApp;
 use crate::chat_message_orm::tests::ChatMessageOrmApp;
 use crate::{
     chat_message_models::{
-        BlockedUserMini, ChatAccess, ChatMessage, CreateBlockedUser, CreateChatMessage, DeleteBlockedUser, ModifyChatMessage
+        BlockedUserMini, ChatAccess, ChatMessage, ChatMessageReactionDelta, ChatPin, CreateBlockedUser, CreateChatMessage, CreateChatPin,
+        DeleteBlockedUser, MentionedUser, ModifyChatMessage, ModifyChatMessageReaction, SearchChatMessage,
     },
     chat_message_orm::ChatMessageOrm,
 };
@@ -37,24 +40,41 @@ pub struct ChatStream {
 // ** AssistantChatMsg **
 
 pub trait AssistantChatMsg {
-    /** Create a new user message in the chat. */
-    fn execute_create_chat_message(&self, stream_id: i32, user_id: i32, msg: &str) -> Result<Option<ChatMessage>, ApiError>;
-    /** Change a user's message in a chat. */
-    fn execute_modify_chat_message(&self, id: i32, user_id: i32, new_msg: &str) -> Result<Option<ChatMessage>, ApiError>;
-    /** Delete a user's message in a chat. */
-    fn execute_delete_chat_message(&self, id: i32, user_id: i32) -> Result<Option<ChatMessage>, ApiError>;
+    /** Create a new user message in the chat. (If "reply_to" is specified, then a reply to this message.) */
+    #[rustfmt::skip]
+    fn execute_create_chat_message(&self, stream_id: i32, user_id: i32, msg: &str, reply_to: Option<i32>) -> Result<Option<ChatMessage>, ApiError>;
+    /** Change a user's message in a chat. (If "opt_user_id" is None, then the message of any user.) */
+    #[rustfmt::skip]
+    fn execute_modify_chat_message(&self, id: i32, opt_user_id: Option<i32>, new_msg: &str) -> Result<Option<ChatMessage>, ApiError>;
+    /** Delete a user's message in a chat. (If "opt_user_id" is None, then the message of any user.) */
+    fn execute_delete_chat_message(&self, id: i32, opt_user_id: Option<i32>) -> Result<Option<ChatMessage>, ApiError>;
+    /** Add or remove the user's reaction to the message in the chat. */
+    #[rustfmt::skip]
+    fn execute_modify_chat_message_reaction(&self, id: i32, stream_id: i32, user_id: i32, emoji: &str, is_add: bool)
+        -> Result<Option<ChatMessageReactionDelta>, ApiError>;
+    /** Add mentions of users (by nickname) to the message. (Resolve nicknames to user IDs.) */
+    #[rustfmt::skip]
+    fn execute_create_chat_message_mentions(&self, id: i32, user_id: i32, nicknames: &[String])
+        -> Result<Vec<MentionedUser>, ApiError>;
+    /** Pin a message ("chat_message_id") or an announcement ("msg") in the chat. */
+    #[rustfmt::skip]
+    fn execute_create_chat_pin(&self, stream_id: i32, user_id: i32, chat_message_id: Option<i32>, msg: Option<String>)
+        -> Result<Option<ChatPin>, ApiError>;
+    /** Unpin a message or an announcement in the chat. */
+    fn execute_delete_chat_pin(&self, id: i32, stream_id: i32) -> Result<Option<ChatPin>, ApiError>;
 }
 
 // ** AssistantBlockUser **
 
 pub trait AssistantBlockUser {
-    /** Perform blocking/unblocking of a user. */
+    /** Perform blocking/unblocking of a user. (If "block_until" is None, then the block is permanent.) */
     fn execute_block_user(
         &self,
         is_block: bool,
         user_id: i32,
         blocked_id: Option<i32>,
         blocked_nickname: Option<String>,
+        block_until: Option<DateTime<Utc>>,
     ) -> Result<Option<BlockedUserMini>, ApiError>;
 }
 
@@ -77,6 +97,10 @@ impl ChatWsAssistant {
             user_orm,
         }
     }
+    /** Check if the user role gives chat moderation rights in any room. */
+    pub fn is_moderator(role: &UserRole) -> bool {
+        *role == UserRole::Moderator || *role == UserRole::Admin
+    }
     /** Decode the token. And unpack the two parameters from the token. */
     pub fn decode_and_verify_token(&self, token: &str) -> Result<(i32, i32), String> {
         let jwt_secret: &[u8] = self.config_jwt.jwt_secret.as_bytes();
@@ -88,17 +112,17 @@ impl ChatWsAssistant {
         let user_orm: UserOrmApp = self.user_orm.clone();
 
         // Token verification:
-        // 1. Search for a session by "id" from the token;
-        let opt_session = user_orm.get_session_by_id(user_id).map_err(|e| {
+        // 1. Search for the sessions (devices) of the user by "id" from the token;
+        let sessions = user_orm.get_sessions_by_user_id(user_id).map_err(|e| {
             error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
             return ApiError::create(507, err::MSG_DATABASE, &e); // 507
         })?;
-        // If the session does not exist, return error 406("NotAcceptable", "session_not_found; user_id: {}").
-        let session = is_session_not_found(opt_session, user_id)?;
-        // 2. Compare "num_token" from session with "num_token" from token;
-        // To block hacking, the session contains a numeric value "num_token".
-        // If session.num_token is not equal to token.num_token,return error401(c)("Unauthorized","unacceptable_token_num; user_id: {}")
-        let _ = is_unacceptable_token_num(&session, num_token, user_id)?;
+        // If there are no sessions, return error 406("NotAcceptable", "session_not_found; user_id: {}").
+        let sessions = is_session_not_found(sessions, user_id)?;
+        // 2. Find the session with the "num_token" from the token;
+        // To block hacking, each session contains a numeric value "num_token".
+        // If no session has token.num_token, return error401(c)("Unauthorized","unacceptable_token_num; user_id: {}")
+        let _ = is_unacceptable_token_num(&sessions, num_token, user_id)?;
         // 3. If everything is correct, then search for the user by "user_id" from the token;
         let opt_user = user_orm.get_user_by_id(user_id, false).map_err(|e| {
             error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
@@ -106,6 +130,8 @@ impl ChatWsAssistant {
         })?;
         // If the user is not present, return error401(d)("Unauthorized", "unacceptable_token_id; user_id: {}").
         let user = is_unacceptable_token_id(opt_user, user_id)?;
+        // If the user account is suspended, return error403("Forbidden", "user_suspended; user_id: {}").
+        let user = is_user_suspended(user)?;
         Ok(user)
     }
     /** Get chat access information. (ChatAccess) */
@@ -117,24 +143,51 @@ impl ChatWsAssistant {
             ApiError::create(507, err::MSG_DATABASE, &e) // 507
         })
     }
+    /** Get the last messages of the chat (in ascending order of date). */
+    pub async fn get_chat_backlog(&self, stream_id: i32, limit: usize) -> Result<Vec<ChatMessage>, ApiError> {
+        let chat_message_orm: ChatMessageOrmApp = self.chat_message_orm.clone();
+        // Get the last messages (in descending order of date).
+        let search_chat_message = SearchChatMessage::new(stream_id, Some(true), None, None, Some(limit), None);
+
+        let mut chat_messages = chat_message_orm.filter_chat_messages(search_chat_message).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        })?;
+        chat_messages.reverse();
+        // The text of the removed messages is not sent.
+        for chat_message in chat_messages.iter_mut().filter(|v| v.date_removed.is_some()) {
+            chat_message.msg = None;
+        }
+        Ok(chat_messages)
+    }
+    /** Get a list of pins (pinned messages and announcements) of the chat. */
+    pub async fn get_chat_pins(&self, stream_id: i32) -> Result<Vec<ChatPin>, ApiError> {
+        let chat_message_orm: ChatMessageOrmApp = self.chat_message_orm.clone();
+
+        chat_message_orm.filter_chat_pins(stream_id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        })
+    }
 }
 
 // ** AssistantBlockUser **
 
 impl AssistantBlockUser for ChatWsAssistant {
-    /** Perform blocking/unblocking of a user. */
+    /** Perform blocking/unblocking of a user. (If "block_until" is None, then the block is permanent.) */
     fn execute_block_user(
         &self,
         is_block: bool,
         user_id: i32,
         blocked_id: Option<i32>,
         blocked_nickname: Option<String>,
+        block_until: Option<DateTime<Utc>>,
     ) -> Result<Option<BlockedUserMini>, ApiError> {
         let chat_message_orm: ChatMessageOrmApp = self.chat_message_orm.clone();
         if is_block {
             // Add a new entry (blocked_user).
             chat_message_orm
-                .create_blocked_user(CreateBlockedUser::new(user_id, blocked_id, blocked_nickname))
+                .create_blocked_user(CreateBlockedUser::new(user_id, blocked_id, blocked_nickname, block_until))
                 .map_err(|e| {
                     error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
                     ApiError::create(507, err::MSG_DATABASE, &e) // 507
@@ -154,10 +207,11 @@ impl AssistantBlockUser for ChatWsAssistant {
 // ** AssistantChatMsg **
 
 impl AssistantChatMsg for ChatWsAssistant {
-    /** Create a new user message in the chat. */
-    fn execute_create_chat_message(&self, stream_id: i32, user_id: i32, msg: &str) -> Result<Option<ChatMessage>, ApiError> {
+    /** Create a new user message in the chat. (If "reply_to" is specified, then a reply to this message.) */
+    #[rustfmt::skip]
+    fn execute_create_chat_message(&self, stream_id: i32, user_id: i32, msg: &str, reply_to: Option<i32>) -> Result<Option<ChatMessage>, ApiError> {
         let chat_message_orm: ChatMessageOrmApp = self.chat_message_orm.clone();
-        let create_chat_message = CreateChatMessage::new(stream_id, user_id, msg);
+        let create_chat_message = CreateChatMessage::new(stream_id, user_id, msg, reply_to);
         // Add a new entity (stream).
         chat_message_orm.create_chat_message(create_chat_message).map_err(|e| {
             error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
@@ -165,22 +219,71 @@ impl AssistantChatMsg for ChatWsAssistant {
         })
     }
 
-    /** Change a user's message in a chat. */
-    fn execute_modify_chat_message(&self, id: i32, user_id: i32, new_msg: &str) -> Result<Option<ChatMessage>, ApiError> {
+    /** Change a user's message in a chat. (If "opt_user_id" is None, then the message of any user.) */
+    #[rustfmt::skip]
+    fn execute_modify_chat_message(&self, id: i32, opt_user_id: Option<i32>, new_msg: &str) -> Result<Option<ChatMessage>, ApiError> {
         let chat_message_orm: ChatMessageOrmApp = self.chat_message_orm.clone();
         let modify_chat_message = ModifyChatMessage::new(new_msg.to_owned());
         // Modify an entity (chat_message).
-        chat_message_orm.modify_chat_message(id, user_id, modify_chat_message).map_err(|e| {
+        chat_message_orm.modify_chat_message(id, opt_user_id, modify_chat_message).map_err(|e| {
             error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
             ApiError::create(507, err::MSG_DATABASE, &e) // 507
         })
     }
 
-    /** Delete a user's message in a chat. */
-    fn execute_delete_chat_message(&self, id: i32, user_id: i32) -> Result<Option<ChatMessage>, ApiError> {
+    /** Delete a user's message in a chat. (If "opt_user_id" is None, then the message of any user.) */
+    fn execute_delete_chat_message(&self, id: i32, opt_user_id: Option<i32>) -> Result<Option<ChatMessage>, ApiError> {
         let chat_message_orm: ChatMessageOrmApp = self.chat_message_orm.clone();
         // Add a new entity (stream).
-        chat_message_orm.delete_chat_message(id, user_id).map_err(|e| {
+        chat_message_orm.delete_chat_message(id, opt_user_id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        })
+    }
+
+    /** Add or remove the user's reaction to the message in the chat. */
+    #[rustfmt::skip]
+    fn execute_modify_chat_message_reaction(&self, id: i32, stream_id: i32, user_id: i32, emoji: &str, is_add: bool)
+        -> Result<Option<ChatMessageReactionDelta>, ApiError> {
+        let chat_message_orm: ChatMessageOrmApp = self.chat_message_orm.clone();
+        let modify_reaction = ModifyChatMessageReaction::new(id, stream_id, user_id, emoji, is_add);
+        // Add or remove an entity (chat_message_reaction).
+        chat_message_orm.modify_chat_message_reaction(modify_reaction).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        })
+    }
+
+    /** Add mentions of users (by nickname) to the message. (Resolve nicknames to user IDs.) */
+    #[rustfmt::skip]
+    fn execute_create_chat_message_mentions(&self, id: i32, user_id: i32, nicknames: &[String])
+        -> Result<Vec<MentionedUser>, ApiError> {
+        let chat_message_orm: ChatMessageOrmApp = self.chat_message_orm.clone();
+        // Add new entities (chat_message_mention).
+        chat_message_orm.create_chat_message_mentions(id, user_id, nicknames).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        })
+    }
+
+    /** Pin a message ("chat_message_id") or an announcement ("msg") in the chat. */
+    #[rustfmt::skip]
+    fn execute_create_chat_pin(&self, stream_id: i32, user_id: i32, chat_message_id: Option<i32>, msg: Option<String>)
+        -> Result<Option<ChatPin>, ApiError> {
+        let chat_message_orm: ChatMessageOrmApp = self.chat_message_orm.clone();
+        let create_chat_pin = CreateChatPin::new(stream_id, user_id, chat_message_id, msg);
+        // Add a new entity (chat_pin).
+        chat_message_orm.create_chat_pin(create_chat_pin).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        })
+    }
+
+    /** Unpin a message or an announcement in the chat. */
+    fn execute_delete_chat_pin(&self, id: i32, stream_id: i32) -> Result<Option<ChatPin>, ApiError> {
+        let chat_message_orm: ChatMessageOrmApp = self.chat_message_orm.clone();
+        // Delete an entity (chat_pin).
+        chat_message_orm.delete_chat_pin(id, stream_id).map_err(|e| {
             error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
             ApiError::create(507, err::MSG_DATABASE, &e) // 507
         })
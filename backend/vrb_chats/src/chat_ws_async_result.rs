@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 
 // * * * * Handler for asynchronous response to the "error" command. * * * *
 
@@ -10,9 +11,10 @@ pub struct AsyncResultError(
 // * * * * Handler for asynchronous response to the "BlockClient" event * * * *
 
 pub struct AsyncResultBlockClient(
-    pub i32,    // room_id
-    pub bool,   // is_block
-    pub String, // blocked_name
+    pub i32,                   // room_id
+    pub bool,                  // is_block
+    pub String,                // blocked_name
+    pub Option<DateTime<Utc>>, // block_until
 );
 
 // * * * *  _  * * * *
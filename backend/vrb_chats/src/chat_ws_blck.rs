@@ -1,6 +1,7 @@
 use actix::prelude::*;
 use actix_web::http::StatusCode;
 use actix_web_actors::ws;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
 use log::debug;
 use serde_json::to_string;
 use vrb_common::{
@@ -11,7 +12,7 @@ use vrb_common::{
 use crate::{
     chat_event_ws::{BlockEWS, EWSType, ErrEWS, EventWS, UnblockEWS},
     chat_message::{BlockClient, BlockSsn},
-    chat_message_models::BlockedUserMini,
+    chat_message_models::{BLOCK_DURATION_MAX, BLOCK_DURATION_MIN, BlockedUserMini},
     chat_ws_assistant::AssistantBlockUser,
     chat_ws_async_result::{AsyncResultBlockClient, AsyncResultError},
     chat_ws_server::ChatWsServer,
@@ -25,21 +26,33 @@ pub struct ChatWsBlckInfo {
     user_id: i32,
     user_name: String,
     is_owner: bool,
+    is_moderator: bool,
+    owner_id: i32,
+    is_blocked: bool,
+    block_until: Option<DateTime<Utc>>,
 }
 
 impl ChatWsBlckInfo {
     #[rustfmt::skip]
-    pub fn new(room_id: i32, user_id: i32, user_name: String, is_owner: bool) -> ChatWsBlckInfo {
-        ChatWsBlckInfo { room_id, user_id, user_name, is_owner }
+    pub fn new(
+        room_id: i32, user_id: i32, user_name: String, is_owner: bool, is_moderator: bool, owner_id: i32, is_blocked: bool,
+        block_until: Option<DateTime<Utc>>,
+    ) -> ChatWsBlckInfo {
+        ChatWsBlckInfo { room_id, user_id, user_name, is_owner, is_moderator, owner_id, is_blocked, block_until }
     }
 }
 
+/** Get the date and time in the format used in websocket events. */
+fn to_ews_date(date: &DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
 // ** Functionality for handling commands to block/unblock chat members. **
 
 pub trait ChatWsBlck {
     fn get_blck_info(&self) -> ChatWsBlckInfo;
 
-    fn set_is_blocked(&mut self, is_blocked: bool);
+    fn set_is_blocked(&mut self, is_blocked: bool, block_until: Option<DateTime<Utc>>);
 
     fn handle_event_ews_blck(
         &self,
@@ -53,14 +66,16 @@ pub trait ChatWsBlck {
         match event.ews_type() {
             EWSType::Block => {
                 // {"block": "User2"}
+                // {"block": "User2", "duration": 600}
                 let block = event.get_string("block").unwrap_or("".to_owned());
-                self.handle_ews_block_add_task(&block, true, fn_block_user, ctx)?;
+                let duration = event.get_i32("duration");
+                self.handle_ews_block_add_task(&block, true, duration, fn_block_user, ctx)?;
                 Ok(true)
             }
             EWSType::Unblock => {
                 // {"unblock": "User2"}
                 let block = event.get_string("unblock").unwrap_or("".to_owned());
-                self.handle_ews_block_add_task(&block, false, fn_block_user, ctx)?;
+                self.handle_ews_block_add_task(&block, false, None, fn_block_user, ctx)?;
                 Ok(true)
             }
             _ => Ok(false),
@@ -68,10 +83,12 @@ pub trait ChatWsBlck {
     }
 
     // ** Blocking clients in a room by name. (Session -> Server) **
+    // ("duration" - the duration of the block in seconds, if it is absent, then the block is permanent.)
     fn handle_ews_block_add_task(
         &self,
         user_name: &str,
         is_block: bool,
+        duration: Option<i32>,
         fn_block_user: impl AssistantBlockUser + 'static,
         ctx: &mut ws::WebsocketContext<ChatWsSession>,
     ) -> Result<(), ErrEWS>
@@ -80,23 +97,32 @@ pub trait ChatWsBlck {
     {
         let blck_info = self.get_blck_info();
         let room_id = blck_info.room_id;
-        debug!("handle_ews_block_add_task() room_id: {room_id}, user_name: {user_name}, is_block: {is_block}");
+        let user_id = blck_info.user_id;
+        debug!("handle_ews_block_add_task() room_id: {room_id}, user_id: {user_id}, user_name: {user_name}, is_block: {is_block}");
         let tag_name = if is_block { "block" } else { "unblock" };
         // Check if this field is not empty
         chat_ws_tools::check_is_not_empty(user_name, tag_name)?;
         // Check if there is an joined room
         chat_ws_tools::check_is_joined_room(room_id)?;
-        // Check if the user is the owner of the stream.
-        chat_ws_tools::check_is_owner_room(blck_info.is_owner)?;
+        // Check if the user is the owner of the stream or a chat moderator.
+        chat_ws_tools::check_is_owner_or_moderator(blck_info.is_owner, blck_info.is_moderator)?;
+        if let Some(duration) = duration {
+            // Check if the block duration is within the allowed range.
+            let (min, max) = (BLOCK_DURATION_MIN as i32, BLOCK_DURATION_MAX as i32);
+            chat_ws_tools::check_is_in_range(duration, min, max, "duration")?;
+        }
+        // Get the date and time the block ends (if the block is timed).
+        let block_until = duration.map(|v| Utc::now() + Duration::seconds(v.into()));
 
-        let user_id = blck_info.user_id;
+        // The block list belongs to the stream owner, even if the moderator performs the blocking.
+        let owner_id = blck_info.owner_id;
         let block_name = user_name.to_string();
         let addr = ctx.address();
         // Start an additional asynchronous task.
         actix_web::rt::spawn(async move {
             let blocked_nickname = block_name.clone();
             // Perform blocking/unblocking of a user.
-            let result = execute_block_user(is_block, user_id, None, Some(block_name), fn_block_user).await;
+            let result = execute_block_user(is_block, owner_id, None, Some(block_name), block_until, fn_block_user).await;
             if let Err(err) = result {
                 return addr.do_send(AsyncResultError(err.status, err.code.to_string(), err.message.to_string()));
             }
@@ -107,25 +133,53 @@ pub trait ChatWsBlck {
             }
             let blocked_user = opt_blocked_user.unwrap();
             let blocked_name = blocked_user.nickname.clone();
-            addr.do_send(AsyncResultBlockClient(room_id, is_block, blocked_name));
+            let block_until = if is_block { blocked_user.block_until } else { None };
+            addr.do_send(AsyncResultBlockClient(room_id, is_block, blocked_name, block_until));
         });
         Ok(())
     }
 
     // Handler for "CommandSrv::Block(BlockSsn)".
-    fn handle_commandsrv_block(&mut self, block: BlockSsn, ctx: &mut ws::WebsocketContext<ChatWsSession>) {
-        let BlockSsn(is_block, is_in_chat) = block;
-        self.set_is_blocked(is_block);
+    fn handle_commandsrv_block(&mut self, block: BlockSsn, ctx: &mut ws::WebsocketContext<ChatWsSession>)
+    where
+        ChatWsSession: actix::Actor<Context = ws::WebsocketContext<ChatWsSession>>,
+    {
+        let BlockSsn(is_block, is_in_chat, block_until) = block;
+        let block_until = if is_block { block_until } else { None };
+        self.set_is_blocked(is_block, block_until.clone());
         let blck_info = self.get_blck_info();
         let user_name = blck_info.user_name.clone();
         #[rustfmt::skip]
         let str = if is_block {
-            to_string(&BlockEWS { block: user_name, is_in_chat }).unwrap()
+            to_string(&BlockEWS { block: user_name, is_in_chat, block_until: block_until.as_ref().map(to_ews_date) }).unwrap()
         } else {
             to_string(&UnblockEWS { unblock: user_name, is_in_chat }).unwrap()
         };
         debug!("handler<CommandSrv::Block>() is_block: {is_block}, str: {str}");
         ctx.text(str);
+        if let Some(block_until) = block_until {
+            self.schedule_unblock(block_until, ctx);
+        }
+    }
+
+    // Schedule automatic unblocking when the timed block expires.
+    fn schedule_unblock(&self, block_until: DateTime<Utc>, ctx: &mut ws::WebsocketContext<ChatWsSession>)
+    where
+        ChatWsSession: actix::Actor<Context = ws::WebsocketContext<ChatWsSession>>,
+    {
+        let delay = (block_until - Utc::now()).to_std().unwrap_or_default();
+        ctx.run_later(delay, move |act, ctx| {
+            let blck_info = act.get_blck_info();
+            // While waiting, the block could be removed or replaced with another one.
+            if blck_info.is_blocked && blck_info.block_until == Some(block_until) {
+                debug!(
+                    "schedule_unblock() user_name: {}, block_until: {}",
+                    &blck_info.user_name,
+                    to_ews_date(&block_until)
+                );
+                act.handle_commandsrv_block(BlockSsn(false, true, None), ctx);
+            }
+        });
     }
 }
 
@@ -134,9 +188,10 @@ async fn execute_block_user(
     user_id: i32,
     blocked_id: Option<i32>,
     blocked_nickname: Option<String>,
+    block_until: Option<DateTime<Utc>>,
     fn_block_user: impl AssistantBlockUser + 'static,
 ) -> Result<Option<BlockedUserMini>, ApiError> {
-    fn_block_user.execute_block_user(is_block, user_id, blocked_id, blocked_nickname)
+    fn_block_user.execute_block_user(is_block, user_id, blocked_id, blocked_nickname, block_until)
 }
 
 // * * * * Handler for asynchronous response to the "BlockClient" event * * * *
@@ -149,8 +204,8 @@ impl Handler<AsyncResultBlockClient> for ChatWsSession {
     type Result = ();
 
     fn handle(&mut self, info: AsyncResultBlockClient, ctx: &mut Self::Context) {
-        let AsyncResultBlockClient(room_id, is_block, blocked_name) = info;
-        let block_client = BlockClient(room_id, blocked_name.clone(), is_block);
+        let AsyncResultBlockClient(room_id, is_block, blocked_name, block_until) = info;
+        let block_client = BlockClient(room_id, blocked_name.clone(), is_block, block_until.clone());
 
         ChatWsServer::from_registry()
             .send(block_client)
@@ -159,7 +214,7 @@ impl Handler<AsyncResultBlockClient> for ChatWsSession {
                 if let Ok(is_in_chat) = res {
                     #[rustfmt::skip]
                     let str = if is_block {
-                        to_string(&BlockEWS { block: blocked_name, is_in_chat }).unwrap()
+                        to_string(&BlockEWS { block: blocked_name, is_in_chat, block_until: block_until.as_ref().map(to_ews_date) }).unwrap()
                     } else {
                         to_string(&UnblockEWS { unblock: blocked_name, is_in_chat }).unwrap()
                     };
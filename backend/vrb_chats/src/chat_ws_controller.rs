@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use actix_web::{HttpResponse, get, web};
 use actix_web_actors::ws;
 use utoipa;
@@ -11,7 +13,7 @@ use vrb_authent::user_orm::tests::UserOrmApp;
 use crate::chat_message_orm::impls::ChatMessageOrmApp;
 #[cfg(all(test, feature = "mockdata"))]
 use crate::chat_message_orm::tests::ChatMessageOrmApp;
-use crate::{chat_ws_assistant::ChatWsAssistant, chat_ws_session::ChatWsSession};
+use crate::{chat_ws_assistant::ChatWsAssistant, chat_ws_session::ChatWsSession, config_chat::ConfigChat};
 
 pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
     |config: &mut web::ServiceConfig| {
@@ -31,6 +33,11 @@ pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
 ///
 /// Successful response status: 101 Switching Protocols.
 ///
+/// The server periodically sends a "ping" frame to the client (the "CHAT_HEARTBEAT" interval, in seconds).
+/// If there was no activity from the client ("pong" frame, "ping" frame or messages) during the "CHAT_CLIENT_TIMEOUT"
+/// (in seconds), then the connection is closed, and the client leaves the chat room.
+/// The other members of the room receive the "leave" event with the updated number of members.
+///
 /// The following commands are processed:
 ///
 /// - ## The "echo" command.
@@ -65,10 +72,11 @@ pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
 /// {
 ///   "join": number,        // Stream ID.
 ///   "access": string,      // Token received after authorization.
+///   "backlog"?: number,    // Number of recent messages to receive after joining (0 - 100). Optional.
 /// }
 /// ```
 /// *Server* (Reply to the initiator):<br/>
-/// `{ "join": 1, "member": "oliver_taylor", "count": 1, "isOwner": false, "isBlocked": false }`<br/>
+/// `{ "join": 1, "member": "oliver_taylor", "count": 1, "isOwner": false, "isBlocked": false, "isModerator": false }`<br/>
 ///
 /// *Server* (Reply to everyone else):<br/>
 /// `{ "join": 1, "member": "oliver_taylor", "count": 1 }`<br/>
@@ -80,9 +88,37 @@ pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
 ///   "count": number,       // Number of connected users.
 ///   "isOwner": boolean,    // The user is the owner of the chat.
 ///   "isBlocked": boolean,  // The user has been blocked.
+///   "isModerator": boolean, // The user is a chat moderator (by role or appointed by the stream owner).
+///   "pins"?: [             // Pinned messages and announcements of the chat (see the "pin" command). Optional.
+///     { "pin": string, "id": number, "member": string, "date": string, "msgId"?: number }
+///   ],
+/// }
+/// ```
+/// If the "backlog" parameter is specified, then after the reply the initiator receives the recent messages
+/// of the chat as a single batch (in ascending order of date) before any new "msg" events.
+///
+/// *Client* :<br/>
+/// `{ "join": 1, "access":"BP3Y6aQTyguP2Q0Jzm9rQ1wdyZpODpz2H3QwCKT...", "backlog": 2 }`<br/>
+/// *Server* (Reply to the initiator):<br/>
+/// `{ "join": 1, "member": "oliver_taylor", "count": 1, "isOwner": false, "isBlocked": false, "isModerator": false }`<br/>
+/// `{ "backlog": [`<br/>
+/// `  { "msg": "message 1", "id":1, "member": "ethan_brown", "date": "2020-03-11T09:00:00.000Z", "isEdt": false, "isRmv": false },`<br/>
+/// `  { "msg": "", "id":2, "member": "ethan_brown", "date": "2020-03-11T09:05:00.000Z", "isEdt": false, "isRmv": true }`<br/>
+/// `] }`<br/>
+///
+/// ```text
+/// {
+///   "backlog": [           // Recent messages (in the format of the "msg" event). The text of deleted messages is empty.
+///     { "msg": string, "id": number, "member": string, "date": string, "isEdt": boolean, "isRmv": boolean, ... }
+///   ],
 /// }
 /// ```
 /// *Client* :<br/>
+/// `{ "join": 1, "access":"BP3Y6aQTyguP2Q0Jzm9rQ1wdyZpODpz2H3QwCKT...", "backlog": 101 }`<br/>
+/// *Server* :<br/>
+/// `{ "err": 406, "code": "NotAcceptable", "message": "parameter_unacceptable; name: 'backlog', value: 101" }`<br/>
+///
+/// *Client* :<br/>
 /// `{ "join": 0, "access":"BP3Y6aQTyguP2Q0Jzm9rQ1wdyZpODpz2H3QwCKT..." }`<br/>
 /// *Server* :<br/>
 /// `{ "err": 400, "code": "BadRequest", "message": "parameter_not_defined; name: 'join'" }`<br/>
@@ -217,6 +253,7 @@ pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
 /// ```text
 /// {
 ///   "msg": string,         // Message test.
+///   "replyTo"?: number,    // ID of the message being replied to (optional).
 /// }
 /// ```
 /// *Server* :<br/>
@@ -230,9 +267,23 @@ pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
 ///   "date": string,        // Date string in ISO 8601 format: YYYY-MM-DDTHH:mm:ss.sssZ
 ///   "isEdt": boolean,      // Message editing indicator.
 ///   "isRmv": boolean,      // Message deletion indicator.
+///   "replyTo"?: number,    // ID of the message being replied to.
+///   "replyMember"?: string, // The nickname of the author of the message being replied to.
+///   "replyMsg"?: string,   // Fragment of the text of the message being replied to (absent if it was deleted).
 /// }
 /// ```
 /// *Client* :<br/>
+/// `{ "msg": "message 3", "replyTo": 1 }`<br/>
+/// *Server* :<br/>
+/// `{ "msg": "message 3", "id":2, "member": "oliver_taylor", "date": "2020-03-11T09:05:00.000Z", "isEdt": false, "isRmv": false,
+///   "replyTo": 1, "replyMember": "ethan_brown", "replyMsg": "message 1" }`<br/>
+///
+/// *Client* :<br/>
+/// `{ "msg": "message 4", "replyTo": 9999 }`<br/>
+/// *Server* :<br/>
+/// `{ "err": 404, "code": "NotFound", "message": "chat_message_not_found; reply_to: 9999" }`<br/>
+///
+/// *Client* :<br/>
 /// `{ "msg": "" }`<br/>
 /// *Server* :<br/>
 /// `{ "err": 400, "code": "BadRequest", "message": "parameter_not_defined; name: 'msg'" }`<br/>
@@ -247,6 +298,27 @@ pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
 /// *Server* :<br/>
 /// `{ "err": 403, "code": "Forbidden", "message": "block_on_sending_messages" }`<br/>
 ///
+/// - ## The "mention" event.
+/// If the message contains mentions of users ("@nickname"), then each mentioned user receives a notification
+/// in all of their open sessions (including sessions in other chat rooms). The event is sent only by the server.
+/// A user who has blocked the author of the message does not receive the notification.
+/// Unread mentions are available via "GET /api/chat_mentions".
+///
+/// *Client* (user "oliver_taylor") :<br/>
+/// `{ "msg": "Hi @ethan_brown" }`<br/>
+/// *Server* (to all sessions of the user "ethan_brown") :<br/>
+/// `{ "mention": 3, "streamId": 1, "member": "oliver_taylor", "msg": "Hi @ethan_brown", "date": "2020-03-11T09:10:00.000Z" }`<br/>
+///
+/// ```text
+/// {
+///   "mention": number,     // ID of the message with the mention.
+///   "streamId": number,    // ID of the chat (stream) in which the message was sent.
+///   "member": string,      // The nickname of the author of the message.
+///   "msg": string,         // Message test.
+///   "date": string,        // Date string in ISO 8601 format: YYYY-MM-DDTHH:mm:ss.sssZ
+/// }
+/// ```
+///
 /// - ## The "msgPut" command.
 /// Correcting a message in a chat room. Available only to authorized users.
 ///
@@ -381,6 +453,178 @@ pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
 /// *Server* :<br/>
 /// `{ "err": 404, "code": "NotFound", "message": "chat_message_not_found; id: 999999, user_id: 1" }`<br/>
 ///
+/// - ## The "reaction" command.
+/// Adding or removing a reaction (emoji) to a message in a chat. Available only to authorized users.
+/// Each user can add each emoji to a message only once.
+///
+/// *Client* :<br/>
+/// `{ "reaction": "👍", "id": 1 }`<br/>
+///
+/// ```text
+/// {
+///   "reaction": string,    // Emoji of the reaction (maximum length 32 bytes).
+///   "id": number,          // Message ID.
+///   "isRmv"?: boolean,     // Reaction removal indicator (default false).
+/// }
+/// ```
+/// *Server* :<br/>
+/// `{ "reaction": "👍", "id": 1, "count": 3, "delta": 1, "member": "oliver_taylor" }`<br/>
+///
+/// ```text
+/// {
+///   "reaction": string,    // Emoji of the reaction.
+///   "id": number,          // Message ID.
+///   "count": number,       // The number of reactions with this emoji to the message.
+///   "delta": number,       // 1 - the reaction has been added, -1 - the reaction has been removed.
+///   "member": string,      // The nickname of the user who changed the reaction.
+/// }
+/// ```
+/// The server sends the change to all members of the chat room.
+/// If the reaction has already been added (or has already been removed), then nothing is sent.
+///
+/// *Client* :<br/>
+/// `{ "reaction": "👍", "id": 1, "isRmv": true }`<br/>
+/// *Server* :<br/>
+/// `{ "reaction": "👍", "id": 1, "count": 2, "delta": -1, "member": "oliver_taylor" }`<br/>
+///
+/// *Client* :<br/>
+/// `{ "reaction": "", "id": 1 }`<br/>
+/// *Server* :<br/>
+/// `{ "err": 400, "code": "BadRequest", "message": "parameter_not_defined; name: 'reaction'" }`<br/>
+///
+/// *Client* :<br/>
+/// `{ "reaction": "👍", "id": 0 }`<br/>
+/// *Server* :<br/>
+/// `{ "err": 400, "code": "BadRequest", "message": "parameter_not_defined; name: 'id'" }`<br/>
+///
+/// *Client* :<br/>
+/// `{ "reaction": "👍", "id": 1 }`<br/>
+/// *Server* :<br/>
+/// `{ "err": 406, "code": "NotAcceptable", "message": "was_no_join_command" }`<br/>
+///
+/// *Client* :<br/>
+/// `{ "reaction": "👍", "id": 1 }`<br/>
+/// *Server* :<br/>
+/// `{ "err": 403, "code": "Forbidden", "message": "block_on_sending_messages" }`<br/>
+///
+/// *Client* :<br/>
+/// `{ "reaction": "👍", "id": 999999 }`<br/>
+/// *Server* :<br/>
+/// `{ "err": 404, "code": "NotFound", "message": "chat_message_not_found; id: 999999" }`<br/>
+///
+/// - ## The "pin" command.
+/// Pinning a message or an announcement at the top of the chat.
+/// Available only to the stream owner and chat moderators.
+/// Pins are stored and sent to each new member in the reply to the "join" command.
+///
+/// *Client* :<br/>
+/// `{ "pin": "Announcement text" }`<br/>
+/// `{ "pin": "", "msgId": 1 }`<br/>
+///
+/// ```text
+/// {
+///   "pin": string,         // Text of the announcement (maximum length 255 characters).
+///   "msgId"?: number,      // ID of the message to be pinned (then the "pin" text is ignored).
+/// }
+/// ```
+/// *Server* :<br/>
+/// `{ "pin": "Announcement text", "id": 1, "member": "oliver_taylor", "date": "2020-01-20T20:10:57.000Z" }`<br/>
+/// `{ "pin": "text msg", "id": 2, "member": "robert_brown", "date": "2020-01-20T20:12:35.000Z", "msgId": 1 }`<br/>
+///
+/// ```text
+/// {
+///   "pin": string,         // Text of the message (or of the announcement).
+///   "id": number,          // Pin ID.
+///   "member": string,      // The nickname of the author of the message (or of the announcement).
+///   "date": string,        // Date and time the pin was made.
+///   "msgId"?: number,      // ID of the pinned message (absent for an announcement).
+/// }
+/// ```
+/// The server sends the pin to all members of the chat room.
+/// If the message has already been pinned, then the existing pin is sent.
+///
+/// *Client* :<br/>
+/// `{ "pin": "" }`<br/>
+/// *Server* :<br/>
+/// `{ "err": 400, "code": "BadRequest", "message": "parameter_not_defined; name: 'pin'" }`<br/>
+///
+/// *Client* :<br/>
+/// `{ "pin": "", "msgId": 0 }`<br/>
+/// *Server* :<br/>
+/// `{ "err": 400, "code": "BadRequest", "message": "parameter_not_defined; name: 'msgId'" }`<br/>
+///
+/// *Client* :<br/>
+/// `{ "pin": "Announcement text" }`<br/>
+/// *Server* :<br/>
+/// `{ "err": 406, "code": "NotAcceptable", "message": "was_no_join_command" }`<br/>
+///
+/// *Client* :<br/>
+/// `{ "pin": "Announcement text" }`<br/>
+/// *Server* :<br/>
+/// `{ "err": 403, "code": "Forbidden", "message": "stream_owner_rights_missing" }`<br/>
+///
+/// *Client* :<br/>
+/// `{ "pin": "", "msgId": 999999 }`<br/>
+/// *Server* :<br/>
+/// `{ "err": 404, "code": "NotFound", "message": "chat_message_not_found; id: 999999" }`<br/>
+///
+/// - ## The "unpin" command.
+/// Unpinning a message or an announcement. Available only to the stream owner and chat moderators.
+///
+/// *Client* :<br/>
+/// `{ "unpin": 1 }`<br/>
+///
+/// ```text
+/// {
+///   "unpin": number,       // Pin ID.
+/// }
+/// ```
+/// *Server* :<br/>
+/// `{ "unpin": 1 }`<br/>
+///
+/// The server sends the message to all members of the chat room.
+///
+/// *Client* :<br/>
+/// `{ "unpin": 0 }`<br/>
+/// *Server* :<br/>
+/// `{ "err": 400, "code": "BadRequest", "message": "parameter_not_defined; name: 'unpin'" }`<br/>
+///
+/// *Client* :<br/>
+/// `{ "unpin": 1 }`<br/>
+/// *Server* :<br/>
+/// `{ "err": 406, "code": "NotAcceptable", "message": "was_no_join_command" }`<br/>
+///
+/// *Client* :<br/>
+/// `{ "unpin": 1 }`<br/>
+/// *Server* :<br/>
+/// `{ "err": 403, "code": "Forbidden", "message": "stream_owner_rights_missing" }`<br/>
+///
+/// *Client* :<br/>
+/// `{ "unpin": 999999 }`<br/>
+/// *Server* :<br/>
+/// `{ "err": 404, "code": "NotFound", "message": "chat_pin_not_found; id: 999999" }`<br/>
+///
+/// - ## The "stream" event.
+/// When the stream owner changes the state of the stream (`PUT /api/streams/toggle/{id}`) or its data
+/// (`PUT /api/streams/{id}`), all members of the chat room of the stream receive its new state and times.
+/// The rest of the stream data (title, description, logo, tags) is not sent; to get it, the client can call
+/// `GET /api/streams/{id}`. The event is sent only by the server.
+///
+/// *Server* :<br/>
+/// `{ "stream": "started", "id": 1, "live": true, "starttime": "2020-03-11T09:00:00.000Z", "started": "2020-03-11T09:05:00.000Z" }`<br/>
+///
+/// ```text
+/// {
+///   "stream": string,      // The state of the stream: "waiting", "preparing", "started", "paused", "stopped".
+///   "id": number,          // Stream ID (chat room ID).
+///   "live": boolean,       // The stream is "live" ("preparing", "started", "paused").
+///   "starttime": string,   // Date string in ISO 8601 format: YYYY-MM-DDTHH:mm:ss.sssZ
+///   "started"?: string,    // The time the stream began. Optional.
+///   "paused"?: string,     // The time the stream began pausing. Optional.
+///   "stopped"?: string,    // The time the stream stopped. Optional.
+/// }
+/// ```
+///
 /// - ## The "block" command.
 /// The stream owner can block a user. Available only to authorized users.
 ///
@@ -596,6 +840,7 @@ pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
 #[get("/ws")]
 pub async fn get_ws_chat(
     config_jwt: web::Data<config_jwt::ConfigJwt>,
+    config_chat: web::Data<ConfigChat>,
     chat_message_orm: web::Data<ChatMessageOrmApp>,
     user_orm: web::Data<UserOrmApp>,
     request: actix_web::HttpRequest,
@@ -607,6 +852,8 @@ pub async fn get_ws_chat(
     #[rustfmt::skip]
     let assistant = ChatWsAssistant::new(
         config_jwt, chat_message_orm_app, user_orm_app);
+    let heartbeat = Duration::from_secs(config_chat.chat_heartbeat);
+    let client_timeout = Duration::from_secs(config_chat.chat_client_timeout);
 
     let chat_ws_session = ChatWsSession::new(
         u32::default(),    // id: u32 (client_id),
@@ -616,6 +863,8 @@ pub async fn get_ws_chat(
         bool::default(),   // is_owner: bool,
         bool::default(),   // is_blocked: bool,
         assistant,         // assistant: ChatWsAssistant
+        heartbeat,         // heartbeat: Duration
+        client_timeout,    // client_timeout: Duration
     );
     ws::start(chat_ws_session, &request, stream)
 }
@@ -0,0 +1,422 @@
+use std::{
+    io,
+    sync::mpsc::{Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use actix::Recipient;
+use chrono::{DateTime, Utc};
+use log::{debug, error};
+use serde::{Deserialize, Serialize};
+
+use crate::chat_message::ReceiveFanOut;
+
+// ** Events of the chat server that are passed to other processes (nodes). **
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", tag = "ev")]
+pub enum FanOutEvent {
+    // Blocking a client in the room by name.
+    BlockClient {
+        room_id: i32,
+        name: String,
+        is_block: bool,
+        block_until: Option<DateTime<Utc>>,
+    },
+    // Blocking a user by nickname in all rooms of the owner.
+    BlockUser {
+        owner_id: i32,
+        name: String,
+        is_block: bool,
+        block_until: Option<DateTime<Utc>>,
+    },
+    // The number of clients of the node in the room.
+    Count {
+        room_id: i32,
+        count: usize,
+    },
+    // The number of clients of the node in all its rooms. Vec<(room_id, count)>
+    Counts {
+        counts: Vec<(i32, usize)>,
+    },
+    // Notification about the mention for the sessions of the mentioned users.
+    Mention {
+        names: Vec<String>,
+        msg: String,
+    },
+    // A text message for all clients in the room.
+    Msg {
+        room_id: i32,
+        msg: String,
+    },
+    // Slow mode of the room.
+    SlowMode {
+        room_id: i32,
+        slow_mode: u32,
+    },
+}
+
+// ** The package in which the event is passed between nodes. **
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FanOutPacket {
+    pub node: String,
+    #[serde(flatten)]
+    pub event: FanOutEvent,
+}
+
+/** Create a new random node identifier. */
+pub fn new_node_id() -> String {
+    format!("{:016x}", rand::random::<u64>())
+}
+/** Encode the event of the specified node into a string. */
+pub fn encode_packet(node: &str, event: &FanOutEvent) -> String {
+    let packet = FanOutPacket {
+        node: node.to_owned(),
+        event: event.clone(),
+    };
+    serde_json::to_string(&packet).unwrap()
+}
+/** Decode the event from a string. Events of the specified (current) node are skipped. -> (node, event) */
+pub fn decode_packet(payload: &str, current_node: &str) -> Result<Option<(String, FanOutEvent)>, String> {
+    let packet: FanOutPacket = serde_json::from_str(payload).map_err(|e| format!("decode_packet: {}", e.to_string()))?;
+    if packet.node == current_node {
+        return Ok(None);
+    }
+    Ok(Some((packet.node, packet.event)))
+}
+
+// ** Fan-out of chat server events between processes (nodes). **
+pub trait ChatFanOut: Send {
+    /** Get the identifier of the current node. */
+    fn node_id(&self) -> &str;
+    /** Send the event to all other nodes. */
+    fn publish(&mut self, event: &FanOutEvent) -> Result<(), String>;
+    /** Receive (without waiting) the events of other nodes. -> Vec<(node, event)> */
+    fn receive(&mut self) -> Result<Vec<(String, FanOutEvent)>, String>;
+}
+
+/** Start a thread that sends the events from `events_rx` to other nodes and passes the events of other nodes to `recipient`. */
+// The events of other nodes are received every `poll` interval. The thread stops when the server is stopped.
+pub fn spawn_fan_out_thread(
+    mut fan_out: Box<dyn ChatFanOut>,
+    events_rx: Receiver<FanOutEvent>,
+    recipient: Recipient<ReceiveFanOut>,
+    poll: Duration,
+) -> io::Result<thread::JoinHandle<()>> {
+    thread::Builder::new().name("chat-fan-out".to_owned()).spawn(move || {
+        let mut next_receive = Instant::now() + poll;
+        while recipient.connected() {
+            // Send the events of the current node until it is time to receive the events of other nodes.
+            let wait = next_receive.saturating_duration_since(Instant::now());
+            if !wait.is_zero() {
+                match events_rx.recv_timeout(wait) {
+                    Ok(event) => {
+                        if let Err(err) = fan_out.publish(&event) {
+                            error!("{}", err);
+                        }
+                        continue;
+                    }
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+            next_receive = Instant::now() + poll;
+            match fan_out.receive() {
+                Ok(list) if list.is_empty() => {}
+                Ok(list) => recipient.do_send(ReceiveFanOut(list)),
+                Err(err) => error!("{}", err),
+            }
+        }
+        debug!("spawn_fan_out_thread() stopped, node: {}", fan_out.node_id());
+    })
+}
+
+pub mod impls {
+    use std::time::Instant as tm;
+
+    use diesel::{self, Connection, PgConnection, RunQueryDsl, sql_types};
+    use log::{Level::Info, info, log_enabled};
+
+    use super::*;
+
+    // The maximum length of the payload of a PostgreSQL notification (in bytes).
+    pub const NOTIFY_PAYLOAD_MAX: usize = 7999;
+
+    // ** Fan-out via PostgreSQL LISTEN/NOTIFY. **
+    // A dedicated connection (not from the pool) is used to listen to the channel and to send notifications.
+    pub struct ChatFanOutPg {
+        node_id: String,
+        channel: String,
+        db_url: String,
+        conn: Option<PgConnection>,
+    }
+
+    impl ChatFanOutPg {
+        pub fn new(db_url: &str, channel: &str) -> Self {
+            ChatFanOutPg {
+                node_id: new_node_id(),
+                channel: channel.to_owned(),
+                db_url: db_url.to_owned(),
+                conn: None,
+            }
+        }
+        /** Get a connection listening to the channel (establish it, if necessary). */
+        fn get_conn(&mut self) -> Result<&mut PgConnection, String> {
+            if self.conn.is_none() {
+                let mut conn = PgConnection::establish(&self.db_url).map_err(|e| format!("ChatFanOutPg.get_conn: {}", e.to_string()))?;
+                let query = format!("LISTEN \"{}\";", self.channel);
+                diesel::sql_query(query)
+                    .execute(&mut conn)
+                    .map_err(|e| format!("ChatFanOutPg.listen: {}", e.to_string()))?;
+                info!("ChatFanOutPg.get_conn() listen channel: {}, node: {}", &self.channel, &self.node_id);
+                self.conn = Some(conn);
+            }
+            Ok(self.conn.as_mut().unwrap())
+        }
+        /** Close the connection after an error. It will be established again at the next call. */
+        fn release_conn(&mut self) {
+            self.conn = None;
+        }
+    }
+
+    impl ChatFanOut for ChatFanOutPg {
+        /** Get the identifier of the current node. */
+        fn node_id(&self) -> &str {
+            &self.node_id
+        }
+        /** Send the event to all other nodes. */
+        fn publish(&mut self, event: &FanOutEvent) -> Result<(), String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+            let payload = encode_packet(&self.node_id, event);
+            if NOTIFY_PAYLOAD_MAX < payload.len() {
+                return Err(format!("ChatFanOutPg.publish: payload too large ({} bytes)", payload.len()));
+            }
+            let channel = self.channel.clone();
+            let conn = self.get_conn()?;
+            let query = diesel::sql_query("SELECT pg_notify($1, $2);")
+                .bind::<sql_types::Text, _>(channel)
+                .bind::<sql_types::Text, _>(payload);
+            let result = query.execute(conn).map_err(|e| format!("ChatFanOutPg.publish: {}", e.to_string()));
+            if result.is_err() {
+                self.release_conn();
+            }
+            if let Some(timer) = timer {
+                info!("ChatFanOutPg.publish() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            result.map(|_| ())
+        }
+        /** Receive (without waiting) the events of other nodes. -> Vec<(node, event)> */
+        fn receive(&mut self) -> Result<Vec<(String, FanOutEvent)>, String> {
+            let node_id = self.node_id.clone();
+            let conn = self.get_conn()?;
+            let mut payloads: Vec<String> = Vec::new();
+            let mut opt_err: Option<String> = None;
+            for result in conn.notifications_iter() {
+                match result {
+                    Ok(notification) => payloads.push(notification.payload),
+                    Err(e) => {
+                        opt_err = Some(format!("ChatFanOutPg.receive: {}", e.to_string()));
+                        break;
+                    }
+                }
+            }
+            if let Some(err) = opt_err {
+                self.release_conn();
+                return Err(err);
+            }
+            let mut list: Vec<(String, FanOutEvent)> = Vec::new();
+            for payload in payloads {
+                if let Some(value) = decode_packet(&payload, &node_id)? {
+                    list.push(value);
+                }
+            }
+            Ok(list)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "mockdata"))]
+pub mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use actix::prelude::*;
+    use actix_web::rt::time::sleep;
+    use serde_json::to_string;
+
+    use crate::{
+        chat_event_ws::{JoinEWS, LeaveEWS},
+        chat_message::{BlockClient, BlockSsn, ChatMsgSsn, CommandSrv, CountMembers, JoinRoom, LeaveRoom, SendMessage, SetFanOut},
+        chat_ws_server::ChatWsServer,
+    };
+
+    use super::*;
+
+    const POLL: Duration = Duration::from_millis(10);
+    const SYNC: Duration = Duration::from_secs(5);
+    const WAIT: Duration = Duration::from_millis(100);
+
+    // ** Fan-out via a common list of packets in memory (nodes are within the same process). **
+    pub struct ChatFanOutTest {
+        node_id: String,
+        packets: Arc<Mutex<Vec<String>>>,
+        position: usize,
+    }
+
+    impl ChatFanOutTest {
+        pub fn new(packets: Arc<Mutex<Vec<String>>>) -> Self {
+            let position = packets.lock().unwrap().len();
+            ChatFanOutTest {
+                node_id: new_node_id(),
+                packets,
+                position,
+            }
+        }
+    }
+
+    impl ChatFanOut for ChatFanOutTest {
+        /** Get the identifier of the current node. */
+        fn node_id(&self) -> &str {
+            &self.node_id
+        }
+        /** Send the event to all other nodes. */
+        fn publish(&mut self, event: &FanOutEvent) -> Result<(), String> {
+            self.packets.lock().unwrap().push(encode_packet(&self.node_id, event));
+            Ok(())
+        }
+        /** Receive (without waiting) the events of other nodes. -> Vec<(node, event)> */
+        fn receive(&mut self) -> Result<Vec<(String, FanOutEvent)>, String> {
+            let packets = self.packets.lock().unwrap();
+            let mut list: Vec<(String, FanOutEvent)> = Vec::new();
+            for payload in packets[self.position..].iter() {
+                if let Some(value) = decode_packet(payload, &self.node_id)? {
+                    list.push(value);
+                }
+            }
+            self.position = packets.len();
+            Ok(list)
+        }
+    }
+
+    // ** A client (session) that saves all received commands. **
+    struct ClientTest {
+        list: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Actor for ClientTest {
+        type Context = Context<Self>;
+    }
+
+    impl Handler<CommandSrv> for ClientTest {
+        type Result = ();
+
+        fn handle(&mut self, msg: CommandSrv, _ctx: &mut Self::Context) {
+            let value = match msg {
+                CommandSrv::Block(BlockSsn(is_block, _is_in_chat, _block_until)) => format!("block: {}", is_block),
+                CommandSrv::Chat(ChatMsgSsn(msg_str)) => msg_str,
+                CommandSrv::CloseAndStop(_) => "close".to_owned(),
+            };
+            self.list.lock().unwrap().push(value);
+        }
+    }
+
+    fn start_client() -> (Recipient<CommandSrv>, Arc<Mutex<Vec<String>>>) {
+        let list: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+        let client = ClientTest { list: list.clone() }.start();
+        (client.recipient(), list)
+    }
+    fn start_server(packets: Arc<Mutex<Vec<String>>>) -> Addr<ChatWsServer> {
+        let server = ChatWsServer::default().start();
+        server.do_send(SetFanOut(Box::new(ChatFanOutTest::new(packets)), POLL, SYNC));
+        server
+    }
+
+    // ** encode_packet, decode_packet **
+
+    #[test]
+    fn test_decode_packet() {
+        let event = FanOutEvent::Msg {
+            room_id: 1,
+            msg: "text1".to_owned(),
+        };
+        let payload = encode_packet("node1", &event);
+        // The event of another node.
+        let result = decode_packet(&payload, "node2");
+        assert_eq!(result, Ok(Some(("node1".to_owned(), event))));
+        // The event of the current node is skipped.
+        let result = decode_packet(&payload, "node1");
+        assert_eq!(result, Ok(None));
+        // Invalid packet.
+        let result = decode_packet("{ \"node\": \"node1\" }", "node2");
+        assert!(result.is_err());
+    }
+
+    // ** ChatWsServer with fan-out **
+
+    #[actix_web::test]
+    async fn test_chat_ws_server_fan_out_count_msg() {
+        let packets: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+        let server1 = start_server(packets.clone());
+        let server2 = start_server(packets.clone());
+        let room_id = 1;
+
+        // Join client1 to the room on node1.
+        let (client1, list1) = start_client();
+        let (_id1, count) = server1.send(JoinRoom(room_id, 10, "member1".to_owned(), client1)).await.unwrap();
+        assert_eq!(count, 1);
+        sleep(WAIT).await;
+        // The number of clients is aggregated across nodes.
+        let count = server2.send(CountMembers(room_id)).await.unwrap();
+        assert_eq!(count, 1);
+
+        // Join client2 to the room on node2.
+        let (client2, _list2) = start_client();
+        let (id2, count) = server2.send(JoinRoom(room_id, 10, "member2".to_owned(), client2)).await.unwrap();
+        assert_eq!(count, 2);
+        sleep(WAIT).await;
+        #[rustfmt::skip]
+        let join_str = to_string(&JoinEWS {
+            join: room_id, member: "member2".to_owned(), count: 2, is_owner: None, is_blocked: None, is_moderator: None, pins: None }).unwrap();
+        assert_eq!(list1.lock().unwrap().clone(), vec![join_str]);
+        let count = server1.send(CountMembers(room_id)).await.unwrap();
+        assert_eq!(count, 2);
+
+        // The message sent on node2 is received by client1 on node1.
+        server2.send(SendMessage(room_id, "text2".to_owned())).await.unwrap();
+        sleep(WAIT).await;
+        assert_eq!(list1.lock().unwrap().last().unwrap(), "text2");
+
+        // Leave client2 from the room on node2.
+        server2.send(LeaveRoom(room_id, id2, "member2".to_owned())).await.unwrap();
+        sleep(WAIT).await;
+        let leave_str = to_string(&LeaveEWS {
+            leave: room_id,
+            member: "member2".to_owned(),
+            count: 1,
+        })
+        .unwrap();
+        assert_eq!(list1.lock().unwrap().last().unwrap(), &leave_str);
+        let count = server1.send(CountMembers(room_id)).await.unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[actix_web::test]
+    async fn test_chat_ws_server_fan_out_block() {
+        let packets: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+        let server1 = start_server(packets.clone());
+        let server2 = start_server(packets.clone());
+        let room_id = 1;
+
+        // Join client1 to the room on node1.
+        let (client1, list1) = start_client();
+        server1.send(JoinRoom(room_id, 10, "member1".to_owned(), client1)).await.unwrap();
+
+        // The block is sent on node2, and client1 is on node1.
+        let is_in_chat = server2.send(BlockClient(room_id, "member1".to_owned(), true, None)).await.unwrap();
+        assert_eq!(is_in_chat, false);
+        sleep(WAIT).await;
+        assert_eq!(list1.lock().unwrap().clone(), vec!["block: true".to_owned()]);
+    }
+}
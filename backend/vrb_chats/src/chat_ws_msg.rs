@@ -8,29 +8,42 @@ use vrb_common::{
     api_error::{ApiError, code_to_str},
     err,
 };
+use vrb_webhooks::{
+    webhook_dispatcher::{FireWebhookEvent, WebhookDispatcher},
+    webhook_models::WebhookEvent,
+};
 
 use crate::{
-    chat_event_ws::{EWSType, ErrEWS, EventWS, MsgEWS, MsgRmvEWS},
-    chat_message::SendMessage,
-    chat_message_models::ChatMessage,
+    chat_event_ws::{EWSType, ErrEWS, EventWS, MentionEWS, MsgEWS, MsgRmvEWS, PinEWS, ReactionEWS, UnpinEWS},
+    chat_message::{CancelSlowMode, CheckSlowMode, SendMention, SendMessage, SlowMode},
+    chat_message_models::{
+        self, ChatMessage, ChatMessageDto, ChatMessageReactionDelta, ChatPin, MESSAGE_MAX, MentionedUser, REACTION_MAX,
+        REACTIONS_PER_USER_MAX,
+    },
     chat_ws_assistant::AssistantChatMsg,
     chat_ws_async_result::AsyncResultError,
+    chat_ws_server::ChatWsServer,
     chat_ws_session::ChatWsSession,
     chat_ws_tools,
 };
 
+// The maximum interval between messages of one user in slow mode (in seconds).
+pub const SLOW_MODE_MAX: i32 = 3600;
+
 #[derive(Debug, Clone)]
 pub struct ChatWsMsgInfo {
     room_id: i32,
     user_id: i32,
     user_name: String,
     is_blocked: bool,
+    is_moderator: bool,
+    is_owner: bool,
 }
 
 impl ChatWsMsgInfo {
     #[rustfmt::skip]
-    pub fn new(room_id: i32, user_id: i32, user_name: String, is_blocked: bool) -> ChatWsMsgInfo {
-        ChatWsMsgInfo { room_id, user_id, user_name, is_blocked }
+    pub fn new(room_id: i32, user_id: i32, user_name: String, is_blocked: bool, is_moderator: bool, is_owner: bool) -> ChatWsMsgInfo {
+        ChatWsMsgInfo { room_id, user_id, user_name, is_blocked, is_moderator, is_owner }
     }
 }
 
@@ -42,7 +55,7 @@ pub trait ChatWsMsg {
     fn handle_event_ews_msg(
         &self,
         event: EventWS,
-        fn_chat_msg: impl AssistantChatMsg + 'static,
+        fn_chat_msg: impl AssistantChatMsg + Clone + 'static,
         ctx: &mut ws::WebsocketContext<ChatWsSession>,
     ) -> Result<bool, ErrEWS>
     where
@@ -50,9 +63,10 @@ pub trait ChatWsMsg {
     {
         match event.ews_type() {
             EWSType::Msg => {
-                // {"msg":"text msg"}
+                // {"msg":"text msg"} or {"msg":"text msg", "replyTo": 1}
                 let msg = event.get_string("msg").unwrap_or_default();
-                self.handle_ews_msg_add_task(&msg, fn_chat_msg, ctx)?;
+                let reply_to = event.get_i32("replyTo");
+                self.handle_ews_msg_add_task(&msg, reply_to, fn_chat_msg, ctx)?;
                 Ok(true)
             }
             EWSType::MsgCut => {
@@ -74,6 +88,33 @@ pub trait ChatWsMsg {
                 self.handle_ews_msg_rmv_add_task(msg_rmv, fn_chat_msg, ctx)?;
                 Ok(true)
             }
+            EWSType::Pin => {
+                // {"pin": "announcement text"} or {"pin": "", "msgId": 1}
+                let pin = event.get_string("pin").unwrap_or_default();
+                let msg_id = event.get_i32("msgId");
+                self.handle_ews_pin_add_task(&pin, msg_id, fn_chat_msg, ctx)?;
+                Ok(true)
+            }
+            EWSType::Reaction => {
+                // {"reaction": "👍", "id": 1} or {"reaction": "👍", "id": 1, "isRmv": true}
+                let reaction = event.get_string("reaction").unwrap_or_default();
+                let id = event.get_i32("id").unwrap_or_default();
+                let is_rmv = event.get_bool("isRmv").unwrap_or(false);
+                self.handle_ews_reaction_add_task(&reaction, id, is_rmv, fn_chat_msg, ctx)?;
+                Ok(true)
+            }
+            EWSType::SlowMode => {
+                // {"slowMode": 30}
+                let slow_mode = event.get_i32("slowMode");
+                self.handle_ews_slow_mode(slow_mode)?;
+                Ok(true)
+            }
+            EWSType::Unpin => {
+                // {"unpin": 1}
+                let unpin = event.get_i32("unpin").unwrap_or_default();
+                self.handle_ews_unpin_add_task(unpin, fn_chat_msg, ctx)?;
+                Ok(true)
+            }
             _ => Ok(false),
         }
     }
@@ -82,7 +123,8 @@ pub trait ChatWsMsg {
     fn handle_ews_msg_add_task(
         &self,
         msg: &str,
-        fn_chat_msg: impl AssistantChatMsg + 'static,
+        reply_to: Option<i32>,
+        fn_chat_msg: impl AssistantChatMsg + Clone + 'static,
         ctx: &mut ws::WebsocketContext<ChatWsSession>,
     ) -> Result<(), ErrEWS>
     where
@@ -92,7 +134,7 @@ pub trait ChatWsMsg {
         let room_id = msg_info.room_id;
         let user_name = msg_info.user_name.clone();
         let msg = msg.to_owned();
-        debug!("handle_ews_msg_add_task() room_id: {room_id}, user_name: {user_name}, msg: {msg}");
+        debug!("handle_ews_msg_add_task() room_id: {room_id}, user_name: {user_name}, msg: {msg}, reply_to: {reply_to:?}");
         // Check if this field is not empty
         chat_ws_tools::check_is_not_empty(&msg, "msg")?;
         // Check if there is an joined room
@@ -101,25 +143,65 @@ pub trait ChatWsMsg {
         chat_ws_tools::check_is_blocked(msg_info.is_blocked)?;
 
         let user_id = msg_info.user_id;
+        // Slow mode does not apply to the owner of the stream and chat moderators.
+        let is_slow_mode_check = !msg_info.is_owner && !msg_info.is_moderator;
         // Get room (stream) ID and user ID.
         let stream_id = room_id;
+        // Get the nicknames of the users mentioned in the message ("@nickname").
+        let nicknames = chat_message_models::parse_mentions(&msg);
         // Spawn an async task.
         let addr = ctx.address();
         // Start an additional asynchronous task.
         actix_web::rt::spawn(async move {
+            if is_slow_mode_check {
+                // Check the slow mode of the room (the number of seconds to wait before sending).
+                let wait = ChatWsServer::from_registry()
+                    .send(CheckSlowMode(room_id, user_id))
+                    .await
+                    .unwrap_or_default();
+                if wait > 0 {
+                    let message = format!("{}; wait: {} s", err::MSG_SLOW_MODE_ENABLED, wait);
+                    return addr.do_send(AsyncResultError(429, code_to_str(StatusCode::TOO_MANY_REQUESTS), message));
+                }
+            }
             // Create a new user message in the chat.
-            let result = execute_create_chat_message(stream_id, user_id, &msg, fn_chat_msg).await;
+            let result = execute_create_chat_message(stream_id, user_id, &msg, reply_to, fn_chat_msg.clone()).await;
+            if is_slow_mode_check && !matches!(result, Ok(Some(_))) {
+                // The message was not created, so it does not count towards the slow mode.
+                ChatWsServer::from_registry().do_send(CancelSlowMode(room_id, user_id));
+            }
             if let Err(err) = result {
                 return addr.do_send(AsyncResultError(err.status, err.code.to_string(), err.message.to_string()));
             }
             let opt_chat_message = result.unwrap();
+            if let (None, Some(reply_to)) = (&opt_chat_message, reply_to) {
+                // The parent message was not found in this chat (or it was deleted).
+                let message = format!("{}; reply_to: {}", err::MSG_CHAT_MESSAGE_NOT_FOUND, reply_to);
+                return addr.do_send(AsyncResultError(404, code_to_str(StatusCode::NOT_FOUND), message.to_string()));
+            }
             if opt_chat_message.is_none() {
                 let message = format!("{}; stream_id: {}", err::MSG_STREAM_NOT_FOUND, stream_id);
                 return addr.do_send(AsyncResultError(404, code_to_str(StatusCode::NOT_FOUND), message.to_string()));
             }
             let ch_msg = opt_chat_message.unwrap();
+            let mention_ews = MentionEWS::from(ch_msg.clone());
+            // Notify the webhooks of the owner of the stream (if they are subscribed to chat messages).
+            let data = serde_json::to_value(ChatMessageDto::from(ch_msg.clone())).unwrap();
+            WebhookDispatcher::from_registry().do_send(FireWebhookEvent::for_stream(stream_id, WebhookEvent::ChatMessage, data));
             // Send the "AsyncResultSendText" command for execution.
             addr.do_send(AsyncResultSendText(room_id, to_string(&MsgEWS::from(ch_msg)).unwrap()));
+
+            if nicknames.is_empty() {
+                return;
+            }
+            // Add mentions of users to the message (blocked users cannot mention the user).
+            let result = execute_create_chat_message_mentions(mention_ews.mention, user_id, &nicknames, fn_chat_msg).await;
+            if let Err(err) = result {
+                return addr.do_send(AsyncResultError(err.status, err.code.to_string(), err.message.to_string()));
+            }
+            let user_names: Vec<String> = result.unwrap().into_iter().map(|v| v.nickname).collect();
+            // Send a notification to all sessions of the mentioned users (in any room).
+            ChatWsServer::from_registry().do_send(SendMention(user_names, to_string(&mention_ews).unwrap()));
         });
         Ok(())
     }
@@ -147,12 +229,14 @@ pub trait ChatWsMsg {
         chat_ws_tools::check_is_blocked(msg_info.is_blocked)?;
 
         let user_id = msg_info.user_id;
+        // A chat moderator can remove the message of any user.
+        let opt_user_id = if msg_info.is_moderator { None } else { Some(user_id) };
         // Spawn an async task.
         let addr = ctx.address();
         // Start an additional asynchronous task.
         actix_web::rt::spawn(async move {
             // Change a user's message in a chat.
-            let result = execute_modify_chat_message(id, user_id, &msg_cut, fn_chat_msg).await;
+            let result = execute_modify_chat_message(id, opt_user_id, &msg_cut, fn_chat_msg).await;
             if let Err(err) = result {
                 return addr.do_send(AsyncResultError(err.status, err.code.to_string(), err.message.to_string()));
             }
@@ -199,7 +283,7 @@ pub trait ChatWsMsg {
         // Start an additional asynchronous task.
         actix_web::rt::spawn(async move {
             // Change a user's message in a chat.
-            let result = execute_modify_chat_message(id, user_id, &msg_put, fn_chat_msg).await;
+            let result = execute_modify_chat_message(id, Some(user_id), &msg_put, fn_chat_msg).await;
             if let Err(err) = result {
                 return addr.do_send(AsyncResultError(err.status, err.code.to_string(), err.message.to_string()));
             }
@@ -237,12 +321,14 @@ pub trait ChatWsMsg {
         chat_ws_tools::check_is_blocked(msg_info.is_blocked)?;
 
         let user_id = msg_info.user_id;
+        // A chat moderator can remove the message of any user.
+        let opt_user_id = if msg_info.is_moderator { None } else { Some(user_id) };
         // Spawn an async task.
         let addr = ctx.address();
         // Start an additional asynchronous task.
         actix_web::rt::spawn(async move {
             // Delete a user's message in a chat.
-            let result = execute_delete_chat_message(msg_rmv, user_id, fn_chat_msg).await;
+            let result = execute_delete_chat_message(msg_rmv, opt_user_id, fn_chat_msg).await;
             if let Err(err) = result {
                 return addr.do_send(AsyncResultError(err.status, err.code.to_string(), err.message.to_string()));
             }
@@ -256,32 +342,258 @@ pub trait ChatWsMsg {
         });
         Ok(())
     }
+
+    // * Send a pinned message (or an announcement) to all chat members. (Server -> Session) *
+    fn handle_ews_pin_add_task(
+        &self,
+        pin: &str,
+        msg_id: Option<i32>,
+        fn_chat_msg: impl AssistantChatMsg + 'static,
+        ctx: &mut ws::WebsocketContext<ChatWsSession>,
+    ) -> Result<(), ErrEWS>
+    where
+        ChatWsSession: actix::Actor<Context = ws::WebsocketContext<ChatWsSession>>,
+    {
+        let msg_info = self.get_msg_info();
+        let room_id = msg_info.room_id;
+        let user_name = msg_info.user_name.clone();
+        debug!("handle_ews_pin_add_task() room_id: {room_id}, user_name: {user_name}, pin: {pin}, msg_id: {msg_id:?}");
+        let pin = pin.to_owned();
+        if let Some(msg_id) = msg_id {
+            // Check if this field is required
+            chat_ws_tools::check_is_greater_than(msg_id, 0, "msgId")?;
+        } else {
+            // Check if this field is not empty
+            chat_ws_tools::check_is_not_empty(&pin, "pin")?;
+            // Check if the length of the field does not exceed the maximum
+            chat_ws_tools::check_is_max_length(&pin, MESSAGE_MAX.into(), "pin")?;
+        }
+        // Check if there is an joined room
+        chat_ws_tools::check_is_joined_room(room_id)?;
+        // Check if the user is the owner of the stream or a chat moderator.
+        chat_ws_tools::check_is_owner_or_moderator(msg_info.is_owner, msg_info.is_moderator)?;
+
+        let user_id = msg_info.user_id;
+        // Get room (stream) ID.
+        let stream_id = room_id;
+        // The text of the announcement is used only if no message is specified.
+        let opt_pin = if msg_id.is_none() { Some(pin) } else { None };
+        // Spawn an async task.
+        let addr = ctx.address();
+        // Start an additional asynchronous task.
+        actix_web::rt::spawn(async move {
+            // Pin a message (or an announcement) in the chat.
+            let result = execute_create_chat_pin(stream_id, user_id, msg_id, opt_pin, fn_chat_msg).await;
+            if let Err(err) = result {
+                return addr.do_send(AsyncResultError(err.status, err.code.to_string(), err.message.to_string()));
+            }
+            let opt_chat_pin = result.unwrap();
+            if let (None, Some(msg_id)) = (&opt_chat_pin, msg_id) {
+                // The message was not found in this chat (or it was deleted).
+                let message = format!("{}; id: {}", err::MSG_CHAT_MESSAGE_NOT_FOUND, msg_id);
+                return addr.do_send(AsyncResultError(404, code_to_str(StatusCode::NOT_FOUND), message.to_string()));
+            }
+            if opt_chat_pin.is_none() {
+                let message = format!("{}; stream_id: {}", err::MSG_STREAM_NOT_FOUND, stream_id);
+                return addr.do_send(AsyncResultError(404, code_to_str(StatusCode::NOT_FOUND), message.to_string()));
+            }
+            let chat_pin = opt_chat_pin.unwrap();
+            // Send the "AsyncResultSendText" command for execution.
+            addr.do_send(AsyncResultSendText(room_id, to_string(&PinEWS::from(chat_pin)).unwrap()));
+        });
+        Ok(())
+    }
+
+    // * Send a message about unpinning to all chat members. (Server -> Session) *
+    fn handle_ews_unpin_add_task(
+        &self,
+        unpin: i32,
+        fn_chat_msg: impl AssistantChatMsg + 'static,
+        ctx: &mut ws::WebsocketContext<ChatWsSession>,
+    ) -> Result<(), ErrEWS>
+    where
+        ChatWsSession: actix::Actor<Context = ws::WebsocketContext<ChatWsSession>>,
+    {
+        let msg_info = self.get_msg_info();
+        let room_id = msg_info.room_id;
+        let user_name = msg_info.user_name.clone();
+        debug!("handle_ews_unpin_add_task() room_id: {room_id}, user_name: {user_name}, unpin: {unpin}");
+        // Check if this field is required
+        chat_ws_tools::check_is_greater_than(unpin, 0, "unpin")?;
+        // Check if there is an joined room
+        chat_ws_tools::check_is_joined_room(room_id)?;
+        // Check if the user is the owner of the stream or a chat moderator.
+        chat_ws_tools::check_is_owner_or_moderator(msg_info.is_owner, msg_info.is_moderator)?;
+
+        // Get room (stream) ID.
+        let stream_id = room_id;
+        // Spawn an async task.
+        let addr = ctx.address();
+        // Start an additional asynchronous task.
+        actix_web::rt::spawn(async move {
+            // Unpin a message (or an announcement) in the chat.
+            let result = execute_delete_chat_pin(unpin, stream_id, fn_chat_msg).await;
+            if let Err(err) = result {
+                return addr.do_send(AsyncResultError(err.status, err.code.to_string(), err.message.to_string()));
+            }
+            if result.unwrap().is_none() {
+                let message = format!("{}; id: {}", err::MSG_CHAT_PIN_NOT_FOUND, unpin);
+                return addr.do_send(AsyncResultError(404, code_to_str(StatusCode::NOT_FOUND), message.to_string()));
+            }
+            // Send the "AsyncResultSendText" command for execution.
+            addr.do_send(AsyncResultSendText(room_id, to_string(&UnpinEWS { unpin }).unwrap()));
+        });
+        Ok(())
+    }
+
+    // * Send the change in the number of reactions to the message to all chat members. (Server -> Session) *
+    fn handle_ews_reaction_add_task(
+        &self,
+        reaction: &str,
+        id: i32,
+        is_rmv: bool,
+        fn_chat_msg: impl AssistantChatMsg + 'static,
+        ctx: &mut ws::WebsocketContext<ChatWsSession>,
+    ) -> Result<(), ErrEWS>
+    where
+        ChatWsSession: actix::Actor<Context = ws::WebsocketContext<ChatWsSession>>,
+    {
+        let msg_info = self.get_msg_info();
+        let room_id = msg_info.room_id;
+        let user_name = msg_info.user_name.clone();
+        #[rustfmt::skip]
+        debug!("handle_ews_reaction_add_task() room_id: {room_id}, user_name: {user_name}, msg_id: {id}, reaction: {reaction}, is_rmv: {is_rmv}");
+        let reaction = reaction.to_owned();
+        // Check if this field is not empty
+        chat_ws_tools::check_is_not_empty(&reaction, "reaction")?;
+        // Check if the length of the field does not exceed the maximum
+        chat_ws_tools::check_is_max_length(&reaction, REACTION_MAX.into(), "reaction")?;
+        // Check if the field is a single emoji
+        chat_ws_tools::check_is_emoji(&reaction, "reaction")?;
+        // Check if this field is required
+        chat_ws_tools::check_is_greater_than(id, 0, "id")?;
+        // Check if there is an joined room
+        chat_ws_tools::check_is_joined_room(room_id)?;
+        // Check if there is a block on sending messages
+        chat_ws_tools::check_is_blocked(msg_info.is_blocked)?;
+
+        let user_id = msg_info.user_id;
+        // Get room (stream) ID.
+        let stream_id = room_id;
+        // Spawn an async task.
+        let addr = ctx.address();
+        // Start an additional asynchronous task.
+        actix_web::rt::spawn(async move {
+            // Add or remove a user's reaction to the message.
+            let result = execute_modify_chat_message_reaction(id, stream_id, user_id, &reaction, !is_rmv, fn_chat_msg).await;
+            if let Err(err) = result {
+                return addr.do_send(AsyncResultError(err.status, err.code.to_string(), err.message.to_string()));
+            }
+            let opt_reaction_delta = result.unwrap();
+            if opt_reaction_delta.is_none() {
+                let message = format!("{}; id: {}", err::MSG_CHAT_MESSAGE_NOT_FOUND, id);
+                return addr.do_send(AsyncResultError(404, code_to_str(StatusCode::NOT_FOUND), message.to_string()));
+            }
+            let reaction_delta = opt_reaction_delta.unwrap();
+            // The user already has the maximum number of reactions to the message.
+            if reaction_delta.is_limit {
+                let message = format!("{}; max: {}", err::MSG_REACTION_LIMIT_REACHED, REACTIONS_PER_USER_MAX);
+                return addr.do_send(AsyncResultError(406, code_to_str(StatusCode::NOT_ACCEPTABLE), message));
+            }
+            // If the number of reactions has not changed, then there is nothing to send.
+            if reaction_delta.delta == 0 {
+                return;
+            }
+            // Send the "AsyncResultSendText" command for execution.
+            addr.do_send(AsyncResultSendText(room_id, to_string(&ReactionEWS::new(reaction_delta, user_name)).unwrap()));
+        });
+        Ok(())
+    }
+
+    // * Set the slow mode of the room (the minimum interval between messages of one user). (Session -> Server) *
+    fn handle_ews_slow_mode(&self, slow_mode: Option<i32>) -> Result<(), ErrEWS> {
+        let msg_info = self.get_msg_info();
+        let room_id = msg_info.room_id;
+        debug!("handle_ews_slow_mode() room_id: {room_id}, slow_mode: {slow_mode:?}");
+        // Check if this field is required
+        chat_ws_tools::check_is_required(slow_mode, "slowMode")?;
+        let slow_mode = slow_mode.unwrap();
+        // Check if there is an joined room
+        chat_ws_tools::check_is_joined_room(room_id)?;
+        // Check if the user is the owner of the stream.
+        chat_ws_tools::check_is_owner_room(msg_info.is_owner)?;
+        // Check if the interval is within the allowed range. (0 - slow mode is disabled.)
+        chat_ws_tools::check_is_in_range(slow_mode, 0, SLOW_MODE_MAX, "slowMode")?;
+
+        // The server sends the new value to all members of the room.
+        ChatWsServer::from_registry().do_send(SlowMode(room_id, slow_mode.unsigned_abs()));
+        Ok(())
+    }
 }
 
 async fn execute_create_chat_message(
     stream_id: i32,
     user_id: i32,
     msg: &str,
+    reply_to: Option<i32>,
     fn_chat_msg: impl AssistantChatMsg + 'static,
 ) -> Result<Option<ChatMessage>, ApiError> {
-    fn_chat_msg.execute_create_chat_message(stream_id, user_id, &msg)
+    fn_chat_msg.execute_create_chat_message(stream_id, user_id, &msg, reply_to)
 }
 
 async fn execute_modify_chat_message(
     id: i32,
-    user_id: i32,
+    opt_user_id: Option<i32>,
     new_msg: &str,
     fn_chat_msg: impl AssistantChatMsg + 'static,
 ) -> Result<Option<ChatMessage>, ApiError> {
-    fn_chat_msg.execute_modify_chat_message(id, user_id, new_msg)
+    fn_chat_msg.execute_modify_chat_message(id, opt_user_id, new_msg)
 }
 
 async fn execute_delete_chat_message(
     id: i32,
-    user_id: i32,
+    opt_user_id: Option<i32>,
     fn_chat_msg: impl AssistantChatMsg + 'static,
 ) -> Result<Option<ChatMessage>, ApiError> {
-    fn_chat_msg.execute_delete_chat_message(id, user_id)
+    fn_chat_msg.execute_delete_chat_message(id, opt_user_id)
+}
+
+async fn execute_modify_chat_message_reaction(
+    id: i32,
+    stream_id: i32,
+    user_id: i32,
+    emoji: &str,
+    is_add: bool,
+    fn_chat_msg: impl AssistantChatMsg + 'static,
+) -> Result<Option<ChatMessageReactionDelta>, ApiError> {
+    fn_chat_msg.execute_modify_chat_message_reaction(id, stream_id, user_id, emoji, is_add)
+}
+
+async fn execute_create_chat_message_mentions(
+    id: i32,
+    user_id: i32,
+    nicknames: &[String],
+    fn_chat_msg: impl AssistantChatMsg + 'static,
+) -> Result<Vec<MentionedUser>, ApiError> {
+    fn_chat_msg.execute_create_chat_message_mentions(id, user_id, nicknames)
+}
+
+async fn execute_create_chat_pin(
+    stream_id: i32,
+    user_id: i32,
+    chat_message_id: Option<i32>,
+    msg: Option<String>,
+    fn_chat_msg: impl AssistantChatMsg + 'static,
+) -> Result<Option<ChatPin>, ApiError> {
+    fn_chat_msg.execute_create_chat_pin(stream_id, user_id, chat_message_id, msg)
+}
+
+async fn execute_delete_chat_pin(
+    id: i32,
+    stream_id: i32,
+    fn_chat_msg: impl AssistantChatMsg + 'static,
+) -> Result<Option<ChatPin>, ApiError> {
+    fn_chat_msg.execute_delete_chat_pin(id, stream_id)
 }
 
 // * * * * Handler for asynchronous response to the "SendText" event * * * *
@@ -1,16 +1,28 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::mpsc,
+    time::{Duration, Instant},
+};
 
 use actix::prelude::*;
 use actix_broker::BrokerSubscribe;
-use log::debug;
+use chrono::{DateTime, Utc};
+use log::{debug, error};
 use rand;
 use serde_json::to_string;
 
 use crate::{
-    chat_event_ws::{JoinEWS, LeaveEWS},
-    chat_message::{BlockClient, BlockSsn, BlockUser, ChatMsgSsn, CommandSrv, CountMembers, JoinRoom, LeaveRoom, SendMessage},
+    chat_event_ws::{JoinEWS, LeaveEWS, SlowModeEWS},
+    chat_message::{
+        BlockClient, BlockSsn, BlockUser, CancelSlowMode, ChatMsgSsn, CheckSlowMode, CommandSrv, CountMembers, JoinRoom, LeaveRoom,
+        ReceiveFanOut, SendMention, SendMessage, SetFanOut, SlowMode,
+    },
+    chat_ws_fan_out::{self, FanOutEvent},
 };
 
+// A node that has not sent the number of members during this number of sync intervals is considered stopped.
+pub const NODE_SYNC_MISSED_MAX: u32 = 3;
+
 type Client = Recipient<CommandSrv>;
 
 #[derive(Debug)]
@@ -22,7 +34,9 @@ pub struct ClientInfo {
 #[derive(Debug, Default)]
 pub struct RoomInfo {
     owner_id: i32,
-    map: HashMap<u32, ClientInfo>, // Map<client_id: u32, ClientInfo> u32::MAX = 4_294_967_295
+    map: HashMap<u32, ClientInfo>,       // Map<client_id: u32, ClientInfo> u32::MAX = 4_294_967_295
+    slow_mode: u32,                      // The minimum interval between messages of one user in seconds (0 - disabled).
+    last_msg_map: HashMap<i32, Instant>, // Map<user_id: i32, Instant> The time of the user's last message (in slow mode).
 }
 
 /** Add a new client to the map of all clients. -> ("id" - new client ID, "count" - number of clients) */
@@ -44,12 +58,21 @@ fn remove_client_from_map(client_map: &mut HashMap<u32, ClientInfo>, client_id:
     client_map.remove(&client_id)
 }
 
+// Information about another process (node) of the chat server.
+#[derive(Debug)]
+pub struct NodeInfo {
+    counts_map: HashMap<i32, usize>, // Map<room_id: i32, count: usize> The number of clients of the node in the room.
+    last_seen: Instant,              // The time the last event was received from the node.
+}
+
 // ** ChatWsServer **
 
 #[derive(Default)]
 pub struct ChatWsServer {
     rooms_map: HashMap<i32, RoomInfo>,
-    owners_map: HashMap<i32, HashSet<i32>>, // Map<owner_id: i32, Set<room_id: i32>>
+    owners_map: HashMap<i32, HashSet<i32>>,        // Map<owner_id: i32, Set<room_id: i32>>
+    fan_out_tx: Option<mpsc::Sender<FanOutEvent>>, // Queue of events for the fan-out to other processes (nodes) of the server.
+    nodes_map: HashMap<String, NodeInfo>,          // Map<node_id: String, NodeInfo> Other nodes of the server.
 }
 
 /** Get a room by ID (or create a new room) from the map of all rooms. */
@@ -116,12 +139,40 @@ impl ChatWsServer {
         (count, id)
     }*/
 
-    /** Get the number of clients in the room. */
+    /** Get the number of clients in the room (on all nodes). */
     fn count_clients_in_room(&self, room_id: i32) -> usize {
+        let count_on_nodes: usize = self.nodes_map.values().filter_map(|node| node.counts_map.get(&room_id)).sum();
+        self.count_local_clients_in_room(room_id) + count_on_nodes
+    }
+    /** Get the number of clients in the room (on the current node). */
+    fn count_local_clients_in_room(&self, room_id: i32) -> usize {
         self.rooms_map.get(&room_id).map(|room| room.map.len()).unwrap_or(0)
     }
-    /** Send a message to all participants. (exclude - IDs of members to whom the message should not be sent.) */
+    /** Send the event to other nodes (if fan-out is set). The event is sent by the fan-out thread. */
+    fn publish(&mut self, event: FanOutEvent) {
+        if let Some(fan_out_tx) = self.fan_out_tx.as_ref() {
+            if fan_out_tx.send(event).is_err() {
+                error!("ChatWsServer.publish: the fan-out thread has stopped.");
+            }
+        }
+    }
+    /** Send the number of clients in the room of the current node to other nodes. */
+    fn publish_count(&mut self, room_id: i32) {
+        if self.fan_out_tx.is_some() {
+            let count = self.count_local_clients_in_room(room_id);
+            self.publish(FanOutEvent::Count { room_id, count });
+        }
+    }
+    /** Send a message to all participants (on all nodes). (exclude - IDs of members to whom the message should not be sent.) */
     fn send_message_to_clients(&mut self, room_id: i32, msg: &str, exclude: &[u32]) {
+        self.publish(FanOutEvent::Msg {
+            room_id,
+            msg: msg.to_owned(),
+        });
+        self.send_message_to_local_clients(room_id, msg, exclude);
+    }
+    /** Send a message to participants on the current node. (exclude - IDs of members to whom the message should not be sent.) */
+    fn send_message_to_local_clients(&mut self, room_id: i32, msg: &str, exclude: &[u32]) {
         let opt_room_info = self.rooms_map.get_mut(&room_id);
         if opt_room_info.is_none() {
             return;
@@ -148,6 +199,128 @@ impl ChatWsServer {
             remove_client_from_map(&mut room_info.map, client_id);
         }
     }
+    /** Send a block to the client in the room on the current node. -> is_in_chat */
+    fn block_local_client(&self, room_id: i32, user_name: &str, is_block: bool, block_until: Option<DateTime<Utc>>) -> bool {
+        // Get a chat room by its name.
+        if let Some(room_info) = self.rooms_map.get(&room_id) {
+            // Loop through all chat participants.
+            for (_id, client_info) in &room_info.map {
+                // Checking the chat participant name with the name to block.
+                if client_info.name.eq(user_name) {
+                    client_info.client.do_send(CommandSrv::Block(BlockSsn(is_block, true, block_until)));
+                    return true;
+                }
+            }
+        }
+        false
+    }
+    /** Send a block to the user in the rooms of the owner on the current node. -> is_in_chat */
+    fn block_local_user(&self, owner_id: i32, user_name: &str, is_block: bool, block_until: Option<DateTime<Utc>>) -> bool {
+        let room_id_set: HashSet<i32> = match self.owners_map.get(&owner_id) {
+            Some(room_ids) => room_ids.clone(),
+            None => HashSet::new(),
+        };
+        for room_id in room_id_set {
+            if let Some(room_info) = self.rooms_map.get(&room_id) {
+                for (_client_id, client_info) in &room_info.map {
+                    // Checking the chat participant's name against the name to be unblocked/blocked.
+                    if client_info.name.eq(user_name) {
+                        // The session itself removes the timed block when it expires.
+                        client_info.client.do_send(CommandSrv::Block(BlockSsn(is_block, true, block_until)));
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+    /** Send a notification about the mention to the sessions of the mentioned users on the current node. -> count */
+    fn send_mention_to_local_clients(&self, user_names: &[String], msg_str: &str) -> usize {
+        let command_srv = CommandSrv::Chat(ChatMsgSsn(msg_str.to_owned()));
+        let mut count = 0;
+        // The mentioned user can be in several rooms at the same time (in different sessions).
+        for (_room_id, room_info) in &self.rooms_map {
+            for (_client_id, client_info) in &room_info.map {
+                if user_names.contains(&client_info.name) && client_info.client.connected() {
+                    client_info.client.do_send(command_srv.clone());
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+    /** Set the slow mode of the room on the current node. */
+    fn set_local_slow_mode(&mut self, room_id: i32, slow_mode: u32) -> bool {
+        if let Some(room_info) = self.rooms_map.get_mut(&room_id) {
+            room_info.slow_mode = slow_mode;
+            room_info.last_msg_map.clear();
+            true
+        } else {
+            false
+        }
+    }
+    /** Apply the event of another node on the current node. */
+    fn apply_event_of_node(&mut self, node_id: String, event: FanOutEvent) {
+        let node_info = self.nodes_map.entry(node_id).or_insert_with(|| NodeInfo {
+            counts_map: HashMap::new(),
+            last_seen: Instant::now(),
+        });
+        node_info.last_seen = Instant::now();
+        match event {
+            FanOutEvent::BlockClient {
+                room_id,
+                name,
+                is_block,
+                block_until,
+            } => {
+                self.block_local_client(room_id, &name, is_block, block_until);
+            }
+            FanOutEvent::BlockUser {
+                owner_id,
+                name,
+                is_block,
+                block_until,
+            } => {
+                self.block_local_user(owner_id, &name, is_block, block_until);
+            }
+            FanOutEvent::Count { room_id, count } => {
+                if count > 0 {
+                    node_info.counts_map.insert(room_id, count);
+                } else {
+                    node_info.counts_map.remove(&room_id);
+                }
+            }
+            FanOutEvent::Counts { counts } => {
+                node_info.counts_map = counts.into_iter().filter(|(_room_id, count)| *count > 0).collect();
+            }
+            FanOutEvent::Mention { names, msg } => {
+                self.send_mention_to_local_clients(&names, &msg);
+            }
+            FanOutEvent::Msg { room_id, msg } => {
+                self.send_message_to_local_clients(room_id, &msg, &[]);
+            }
+            FanOutEvent::SlowMode { room_id, slow_mode } => {
+                self.set_local_slow_mode(room_id, slow_mode);
+            }
+        }
+    }
+    /** Send the number of clients in all rooms to other nodes and remove the nodes that have stopped. */
+    fn sync_nodes(&mut self, sync: Duration) {
+        let counts: Vec<(i32, usize)> = self
+            .rooms_map
+            .iter()
+            .map(|(room_id, room_info)| (*room_id, room_info.map.len()))
+            .collect();
+        self.publish(FanOutEvent::Counts { counts });
+        let timeout = sync * NODE_SYNC_MISSED_MAX;
+        self.nodes_map.retain(|node_id, node_info| {
+            let is_alive = node_info.last_seen.elapsed() < timeout;
+            if !is_alive {
+                debug!("sync_nodes() node: {node_id} is stopped.");
+            }
+            is_alive
+        });
+    }
 }
 
 impl SystemService for ChatWsServer {}
@@ -172,23 +345,20 @@ impl Handler<BlockClient> for ChatWsServer {
     type Result = MessageResult<BlockClient>;
 
     fn handle(&mut self, msg: BlockClient, _ctx: &mut Self::Context) -> Self::Result {
-        let mut is_in_chat = false;
-        let BlockClient(room_id, user_name, is_block) = msg;
+        let BlockClient(room_id, user_name, is_block, block_until) = msg;
         if room_id <= i32::default() || user_name.len() == 0 {
-            return MessageResult(is_in_chat);
-        }
-        // Get a chat room by its name.
-        if let Some(room_info) = self.rooms_map.get(&room_id) {
-            // Loop through all chat participants.
-            for (_id, client_info) in &room_info.map {
-                // Checking the chat participant name with the name to block.
-                if client_info.name.eq(&user_name) {
-                    is_in_chat = true;
-                    client_info.client.do_send(CommandSrv::Block(BlockSsn(is_block, is_in_chat)));
-                    break;
-                }
-            }
+            return MessageResult(false);
         }
+        // The client can be connected to another node.
+        let name = user_name.clone();
+        self.publish(FanOutEvent::BlockClient {
+            room_id,
+            name,
+            is_block,
+            block_until,
+        });
+        // Send a block to the client (if the client is in the room on the current node).
+        let is_in_chat = self.block_local_client(room_id, &user_name, is_block, block_until);
         debug!("handler<BlockClient>() room_id:{room_id}, user_name: {user_name}, is_block:{is_block}, is_in_chat:{is_in_chat}");
         MessageResult(is_in_chat)
     }
@@ -200,39 +370,67 @@ impl Handler<BlockUser> for ChatWsServer {
     type Result = MessageResult<BlockUser>;
 
     fn handle(&mut self, msg: BlockUser, _ctx: &mut Self::Context) -> Self::Result {
-        let mut is_in_chat = false;
-        let BlockUser(owner_id, user_name, is_block) = msg;
+        let BlockUser(owner_id, user_name, is_block, block_until) = msg;
         if user_name.len() == 0 {
-            return MessageResult(is_in_chat);
+            return MessageResult(false);
         }
-        let room_id_set: HashSet<i32> = match self.owners_map.get(&owner_id) {
-            Some(room_ids) => room_ids.clone(),
-            None => HashSet::new(),
-        };
-        is_in_chat = true;
-        let mut is_exit = false;
+        // The user can be connected to another node.
+        let name = user_name.clone();
+        self.publish(FanOutEvent::BlockUser {
+            owner_id,
+            name,
+            is_block,
+            block_until,
+        });
+        // Send a block to the user (if the user is in the rooms of the owner on the current node).
+        let is_in_chat = self.block_local_user(owner_id, &user_name, is_block, block_until);
+        debug!("handler<BlockUser>() owner_id: {owner_id}, user_name: {user_name}, is_block:{is_block}, is_in_chat:{is_in_chat}");
+        MessageResult(is_in_chat)
+    }
+}
 
-        for room_id in room_id_set {
-            if let Some(room_info) = self.rooms_map.get(&room_id) {
-                for (_client_id, client_info) in &room_info.map {
-                    // Checking the chat participant's name against the name to be unblocked/blocked.
-                    if client_info.name.eq(&user_name) {
-                        client_info.client.do_send(CommandSrv::Block(BlockSsn(is_block, is_in_chat)));
-                        is_exit = true;
-                        break;
-                    }
+// ** Check the slow mode of the room before sending a message. (Session -> Server) **
+
+impl Handler<CheckSlowMode> for ChatWsServer {
+    type Result = MessageResult<CheckSlowMode>;
+
+    fn handle(&mut self, msg: CheckSlowMode, _ctx: &mut Self::Context) -> Self::Result {
+        let CheckSlowMode(room_id, user_id) = msg;
+        let mut wait: u32 = 0;
+        if let Some(room_info) = self.rooms_map.get_mut(&room_id) {
+            if room_info.slow_mode > 0 {
+                let interval = Duration::from_secs(room_info.slow_mode.into());
+                let now = Instant::now();
+                let elapsed = room_info.last_msg_map.get(&user_id).map(|last| now.duration_since(*last));
+                if let Some(elapsed) = elapsed.filter(|elapsed| *elapsed < interval) {
+                    // Round up the remaining time to whole seconds.
+                    wait = u32::try_from((interval - elapsed).as_millis().div_ceil(1000)).unwrap_or(u32::MAX);
+                } else {
+                    room_info.last_msg_map.insert(user_id, now);
                 }
             }
-            if is_exit {
-                break;
-            }
         }
-        debug!("handler<BlockUser>() owner_id: {owner_id}, user_name: {user_name}, is_block:{is_block}, is_in_chat:{is_in_chat}");
-        MessageResult(is_in_chat)
+        debug!("handler<CheckSlowMode>() room_id: {room_id}, user_id: {user_id}, wait: {wait}");
+        MessageResult(wait)
+    }
+}
+
+// ** Cancel the slow mode check if the message was not created. (Session -> Server) **
+
+impl Handler<CancelSlowMode> for ChatWsServer {
+    type Result = ();
+
+    fn handle(&mut self, msg: CancelSlowMode, _ctx: &mut Self::Context) {
+        let CancelSlowMode(room_id, user_id) = msg;
+        // The time of the previous message is no longer within the interval, so the record can be removed.
+        if let Some(room_info) = self.rooms_map.get_mut(&room_id) {
+            room_info.last_msg_map.remove(&user_id);
+        }
+        debug!("handler<CancelSlowMode>() room_id: {room_id}, user_id: {user_id}");
     }
 }
 
-// ** Count of clients in the room. (Session -> Server) **
+// ** Count of clients in the room (on all nodes). (Session -> Server) **
 
 impl Handler<CountMembers> for ChatWsServer {
     type Result = MessageResult<CountMembers>;
@@ -259,9 +457,12 @@ impl Handler<JoinRoom> for ChatWsServer {
         // Add a new room for the specified owner.
         add_room_to_owner(&mut self.owners_map, owner_id, room_id);
         // Add a new client to the room.
-        let (id, count) = add_client_to_map(&mut room_info.map, ClientInfo { name, client });
+        let (id, _local_count) = add_client_to_map(&mut room_info.map, ClientInfo { name, client });
+        // Send the number of clients in the room of the current node to other nodes.
+        self.publish_count(room_id);
+        let count = self.count_clients_in_room(room_id);
         #[rustfmt::skip]
-        let join_str = to_string(&JoinEWS { join: room_id, member, count, is_owner: None, is_blocked: None }).unwrap();
+        let join_str = to_string(&JoinEWS { join: room_id, member, count, is_owner: None, is_blocked: None, is_moderator: None, pins: None }).unwrap();
         debug!("handler<JoinRoom>() room_id: {room_id}, user_name: {user_name}, room.len(): {count} Ok!");
         // Send a chat message to all members.
         self.send_message_to_clients(room_id, &join_str, &[id]);
@@ -283,18 +484,21 @@ impl Handler<LeaveRoom> for ChatWsServer {
         if let Some(room_info) = self.rooms_map.get_mut(&room_id) {
             // Remove a client from the map of all clients of this room.
             let opt_recipient = remove_client_from_map(&mut room_info.map, client_id);
-            // Get the number of clients in the room.
-            let count = room_info.map.len();
+            // If there are no clients left for a given room, it must be removed from the map of all rooms.
+            if room_info.map.is_empty() {
+                opt_owner_id = Some(room_info.owner_id);
+            }
+            // Send the number of clients in the room of the current node to other nodes.
+            self.publish_count(room_id);
+            // Get the number of clients in the room (on all nodes).
+            let count = self.count_clients_in_room(room_id);
             let member = user_name.clone();
             #[rustfmt::skip]
             debug!("handler<LeaveRoom>() room_id: {room_id}, user_name: {user_name}, room.len(): {count}, client_id: {client_id}");
             #[rustfmt::skip]
             let leave_str = to_string(&LeaveEWS { leave: room_id, member, count }).unwrap();
 
-            // If there are no clients left for a given room, it must be removed from the map of all rooms.
-            if count == 0 {
-                opt_owner_id = Some(room_info.owner_id);
-            } else {
+            if count > 0 {
                 // If count > 0 then send a chat message to all members.
                 self.send_message_to_clients(room_id, &leave_str, &[]);
             }
@@ -314,6 +518,39 @@ impl Handler<LeaveRoom> for ChatWsServer {
     }
 }
 
+// ** Events of other processes of the server received by the fan-out. (Fan-out -> Server) **
+
+impl Handler<ReceiveFanOut> for ChatWsServer {
+    type Result = ();
+
+    fn handle(&mut self, msg: ReceiveFanOut, _ctx: &mut Self::Context) {
+        let ReceiveFanOut(list) = msg;
+        for (node_id, event) in list {
+            self.apply_event_of_node(node_id, event);
+        }
+    }
+}
+
+// ** Send a notification about the mention to all sessions of the mentioned users. (Server -> Session) **
+
+impl Handler<SendMention> for ChatWsServer {
+    type Result = ();
+
+    fn handle(&mut self, msg: SendMention, _ctx: &mut Self::Context) {
+        let SendMention(user_names, msg_str) = msg;
+        if user_names.is_empty() {
+            return;
+        }
+        // The mentioned user can be connected to another node.
+        self.publish(FanOutEvent::Mention {
+            names: user_names.clone(),
+            msg: msg_str.clone(),
+        });
+        let count = self.send_mention_to_local_clients(&user_names, &msg_str);
+        debug!("handler<SendMention>() user_names: {user_names:?}, sessions: {count}");
+    }
+}
+
 // ** Send a text message to all clients in the room. (Server -> Session) **
 
 impl Handler<SendMessage> for ChatWsServer {
@@ -326,4 +563,44 @@ impl Handler<SendMessage> for ChatWsServer {
     }
 }
 
+// ** Set the fan-out of events to other processes of the server. (App -> Server) **
+
+impl Handler<SetFanOut> for ChatWsServer {
+    type Result = ();
+
+    fn handle(&mut self, msg: SetFanOut, ctx: &mut Self::Context) {
+        let SetFanOut(fan_out, poll, sync) = msg;
+        debug!("handler<SetFanOut>() node: {}, poll: {poll:?}, sync: {sync:?}", fan_out.node_id());
+        // The fan-out works in a separate thread, so that its database calls do not block the server.
+        let (fan_out_tx, events_rx) = mpsc::channel();
+        let recipient = ctx.address().recipient();
+        if let Err(err) = chat_ws_fan_out::spawn_fan_out_thread(fan_out, events_rx, recipient, poll) {
+            error!("handler<SetFanOut>() spawn_fan_out_thread: {}", err);
+            return;
+        }
+        self.fan_out_tx = Some(fan_out_tx);
+        // Periodically send the number of room members to other nodes.
+        self.sync_nodes(sync);
+        ctx.run_interval(sync, move |act, _ctx| act.sync_nodes(sync));
+    }
+}
+
+// ** Set the slow mode of the room. (Session -> Server) **
+
+impl Handler<SlowMode> for ChatWsServer {
+    type Result = ();
+
+    fn handle(&mut self, msg: SlowMode, _ctx: &mut Self::Context) {
+        let SlowMode(room_id, slow_mode) = msg;
+        if !self.set_local_slow_mode(room_id, slow_mode) {
+            return;
+        }
+        self.publish(FanOutEvent::SlowMode { room_id, slow_mode });
+        debug!("handler<SlowMode>() room_id: {room_id}, slow_mode: {slow_mode}");
+        let slow_mode_str = to_string(&SlowModeEWS { slow_mode }).unwrap();
+        // Send a chat message to all members.
+        self.send_message_to_clients(room_id, &slow_mode_str, &[]);
+    }
+}
+
 // ** -- **
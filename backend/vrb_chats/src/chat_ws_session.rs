@@ -1,13 +1,16 @@
+use std::time::{Duration, Instant};
+
 use actix::prelude::*;
 use actix_broker::{BrokerIssue, BrokerMsg};
 use actix_web::http::StatusCode;
 use actix_web_actors::ws::{self, CloseReason};
+use chrono::{DateTime, Utc};
 use log::{Level::Debug, debug, log_enabled};
 use serde_json::to_string;
 use vrb_common::{api_error::code_to_str, err};
 
 use crate::{
-    chat_event_ws::{CountEWS, EWSType, EchoEWS, ErrEWS, EventWS, JoinEWS, NameEWS},
+    chat_event_ws::{BacklogEWS, CountEWS, EWSType, EchoEWS, ErrEWS, EventWS, JoinEWS, MsgEWS, NameEWS, PinEWS},
     chat_message::{ChatMsgSsn, CommandSrv, CountMembers, JoinRoom, LeaveRoom},
     chat_ws_assistant::ChatWsAssistant,
     chat_ws_async_result::AsyncResultError,
@@ -19,6 +22,8 @@ use crate::{
 };
 
 pub const SPECIFIED_USER_NOT_FOUND: &str = "The specified user was not found.";
+// The maximum number of recent messages sent to the client when joining the room.
+pub const BACKLOG_MAX: i32 = 100;
 
 // ** ChatWsSession **
 
@@ -29,7 +34,14 @@ pub struct ChatWsSession {
     user_name: String,
     is_owner: bool,
     is_blocked: bool,
+    block_until: Option<DateTime<Utc>>, // The end of a timed block (None - the block is permanent).
+    is_moderator: bool,                 // Moderator of the current room (by role or by appointment of the stream owner).
+    owner_id: i32,
+    backlog_max_id: Option<i32>, // The maximum message ID in the sent backlog (the live "msg" events up to it are duplicates).
     assistant: ChatWsAssistant,
+    hb: Instant,              // The time of the last activity of the client ("pong", "ping" or message).
+    heartbeat: Duration,      // Interval for sending a "ping" to the client (zero - the heartbeat is disabled).
+    client_timeout: Duration, // Time without activity of the client, after which the client is disconnected.
 }
 
 // ** ChatWsSession implementation "Actor" **
@@ -37,11 +49,13 @@ pub struct ChatWsSession {
 impl Actor for ChatWsSession {
     type Context = ws::WebsocketContext<Self>;
     // Called when an actor gets polled the first time.
-    fn started(&mut self, _ctx: &mut Self::Context) {
+    fn started(&mut self, ctx: &mut Self::Context) {
         if log_enabled!(Debug) {
             let user_str = format!("user_id: {}, user_name: \"{}\", id: {}", self.user_id, &self.user_name, self.id);
             debug!("ChatWsSession.started() room_id {}, {}", self.room_id, user_str);
         }
+        // Start the heartbeat process for this connection.
+        self.start_heartbeat(ctx);
     }
     // Called after an actor is stopped.
     fn stopped(&mut self, _ctx: &mut Self::Context) {
@@ -64,40 +78,35 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for ChatWsSession {
             Ok(msg) => msg,
         };
         debug!("StreamHandler<Message>: {msg:?}");
+        // Any message from the client confirms that the connection is alive.
+        self.hb = Instant::now();
         match msg {
+            ws::Message::Ping(msg) => {
+                ctx.pong(&msg);
+            }
             ws::Message::Text(text) => {
                 // Handle socket text messages.
                 self.handle_text_messages(text.trim(), ctx);
             }
             ws::Message::Close(reason) => {
-                // Send a message about leaving the room.
-                let leave_room_srv = LeaveRoom(self.room_id, self.id, self.user_name.clone());
-                debug!("StreamHandler<Message::Close> issue_system_sync(leave_room_srv, ctx)");
-                // issue_sync comes from having the `BrokerIssue` trait in scope.
-                self.issue_system_sync(leave_room_srv, ctx);
-
-                debug!("StreamHandler<Message::Close> actix_web::rt::spawn();");
-                let addr = ctx.address();
-                // Start an additional asynchronous task.
-                actix_web::rt::spawn(async move {
-                    // Spawns a future on the current thread as a new task.
-                    // This is required so that two concurrent events arrive in this actor's event queue.
-                    // One thread processed the LeaveRoom command.
-                    // Another thread closed the socket.
-                    // If these events are executed in the same thread, closing the socket clears the event queue.
-                    // As a result, the LeaveRoom command is not processed.
-                    debug!("StreamHandler<Message::Close> addr.do_send(CommandSrv::CloseAndStop(reason))");
-                    addr.do_send(CommandSrv::CloseAndStop(reason));
-                });
+                debug!("StreamHandler<Message::Close> handle_leave_and_close(reason, ctx)");
+                self.handle_leave_and_close(reason, ctx);
             }
             _ => {}
         }
     }
+    // Called when the stream of messages is finished (including when the connection was lost without a "close" frame).
+    fn finished(&mut self, ctx: &mut Self::Context) {
+        debug!("StreamHandler<Message>.finished() room_id: {}, user_name: {}", self.room_id, self.user_name);
+        // Send a message about leaving the room (if it has not been sent yet).
+        self.handle_leave_and_close(None, ctx);
+    }
 }
 
 // ** ChatWsSession implementation **
 
 impl ChatWsSession {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: u32,
         room_id: i32,
@@ -106,6 +115,8 @@ impl ChatWsSession {
         is_owner: bool,
         is_blocked: bool,
         assistant: ChatWsAssistant,
+        heartbeat: Duration,
+        client_timeout: Duration,
     ) -> Self {
         ChatWsSession {
             id,
@@ -114,9 +125,52 @@ impl ChatWsSession {
             user_name,
             is_owner,
             is_blocked,
+            block_until: None,
+            is_moderator: false,
+            owner_id: i32::default(),
+            backlog_max_id: None,
             assistant,
+            hb: Instant::now(),
+            heartbeat,
+            client_timeout,
         }
     }
+    /** Send a "ping" to the client and check the client's activity (if the heartbeat is enabled). */
+    fn start_heartbeat(&self, ctx: &mut ws::WebsocketContext<Self>) {
+        if self.heartbeat.is_zero() {
+            return;
+        }
+        ctx.run_interval(self.heartbeat, |act, ctx| {
+            if Instant::now().duration_since(act.hb) > act.client_timeout {
+                // The client has not responded for a long time, so the connection is considered lost.
+                #[rustfmt::skip]
+                debug!("ChatWsSession.heartbeat() timeout, room_id: {}, user_name: {}, id: {}", act.room_id, act.user_name, act.id);
+                // Leave the room, and the server sends the updated number of members to the remaining clients.
+                act.handle_leave_and_close(None, ctx);
+                return;
+            }
+            ctx.ping(b"");
+        });
+    }
+    /** Leave the room (if there was a join to the room), then close the connection and stop the session. */
+    fn handle_leave_and_close(&mut self, reason: Option<CloseReason>, ctx: &mut ws::WebsocketContext<Self>) {
+        // Send a message about leaving the room (if there was a join to the room).
+        let _ = self.handle_ews_leave(ctx);
+
+        debug!("handle_leave_and_close() actix_web::rt::spawn();");
+        let addr = ctx.address();
+        // Start an additional asynchronous task.
+        actix_web::rt::spawn(async move {
+            // Spawns a future on the current thread as a new task.
+            // This is required so that two concurrent events arrive in this actor's event queue.
+            // One thread processed the LeaveRoom command.
+            // Another thread closed the socket.
+            // If these events are executed in the same thread, closing the socket clears the event queue.
+            // As a result, the LeaveRoom command is not processed.
+            debug!("handle_leave_and_close() addr.do_send(CommandSrv::CloseAndStop(reason))");
+            addr.do_send(CommandSrv::CloseAndStop(reason));
+        });
+    }
     /** Handle socket text messages. */
     fn handle_text_messages(&mut self, msg: &str, ctx: &mut ws::WebsocketContext<Self>) {
         // Parse input data of ws event.
@@ -154,10 +208,11 @@ impl ChatWsSession {
                 }
             }
             EWSType::Join => {
-                // {"join": 1}
+                // {"join": 1} or {"join": 1, "access": "token", "backlog": 20}
                 let room_id = event.get_i32("join").unwrap_or_default(); // (0);
                 let access = event.get_string("access").unwrap_or("".to_owned());
-                if let Err(err) = self.handle_ews_join_add_task(room_id, &access, ctx) {
+                let backlog = event.get_i32("backlog");
+                if let Err(err) = self.handle_ews_join_add_task(room_id, &access, backlog, ctx) {
                     ctx.text(to_string(&err).unwrap());
                 }
             }
@@ -167,11 +222,22 @@ impl ChatWsSession {
                     ctx.text(to_string(&err).unwrap());
                 }
             }
-            EWSType::Msg | EWSType::MsgCut | EWSType::MsgPut | EWSType::MsgRmv => {
-                // EWSType::Msg     {"msg":"text msg"}
-                // EWSType::MsgCut  {"msgCut": "", "id": 1}
-                // EWSType::MsgPut  {"msgPut": "modify msg", "id": 1}
-                // EWSType::MsgRmv  {"msgRmv": 1}
+            EWSType::Msg
+            | EWSType::MsgCut
+            | EWSType::MsgPut
+            | EWSType::MsgRmv
+            | EWSType::Pin
+            | EWSType::Reaction
+            | EWSType::SlowMode
+            | EWSType::Unpin => {
+                // EWSType::Msg       {"msg":"text msg"}
+                // EWSType::MsgCut    {"msgCut": "", "id": 1}
+                // EWSType::MsgPut    {"msgPut": "modify msg", "id": 1}
+                // EWSType::MsgRmv    {"msgRmv": 1}
+                // EWSType::Pin       {"pin": "announcement text"} or {"pin": "", "msgId": 1}
+                // EWSType::Reaction  {"reaction": "👍", "id": 1, "isRmv": false}
+                // EWSType::SlowMode  {"slowMode": 30}
+                // EWSType::Unpin     {"unpin": 1}
                 let assistant = self.assistant.clone();
                 if let Err(err) = self.handle_event_ews_msg(event, assistant, ctx) {
                     ctx.text(to_string(&err).unwrap());
@@ -216,10 +282,21 @@ impl ChatWsSession {
     }
 
     // * Join the client to the chat room. (Session -> Server) *
-    pub fn handle_ews_join_add_task(&mut self, room_id: i32, access: &str, ctx: &mut ws::WebsocketContext<Self>) -> Result<(), ErrEWS> {
-        debug!("handle_ews_join_add_task() room_id: {room_id}, access.len(): {}", access.len());
+    pub fn handle_ews_join_add_task(
+        &mut self,
+        room_id: i32,
+        access: &str,
+        backlog: Option<i32>,
+        ctx: &mut ws::WebsocketContext<Self>,
+    ) -> Result<(), ErrEWS> {
+        let access_len = access.len();
+        debug!("handle_ews_join_add_task() room_id: {room_id}, access.len(): {access_len}, backlog: {backlog:?}");
         // Check if this field is required
         chat_ws_tools::check_is_greater_than(room_id, 0, "join")?;
+        // Check if the number of recent messages is within the allowed range. (0 - do not send recent messages.)
+        let backlog = backlog.unwrap_or_default();
+        chat_ws_tools::check_is_in_range(backlog, 0, BACKLOG_MAX, "backlog")?;
+        let backlog = backlog.unsigned_abs() as usize;
         // Check if there was a join to this room.
         if self.room_id == room_id {
             return Err(chat_ws_tools::get_err409(err::MSG_THERE_WAS_ALREADY_JOIN_TO_ROOM));
@@ -244,13 +321,17 @@ impl ChatWsSession {
         let mut user_name = self.user_name.clone();
         // Start an additional asynchronous task.
         actix_web::rt::spawn(async move {
+            let mut is_moderator = false;
             if let Some(user_id) = opt_user_id {
                 // Check the correctness of the numeric token and get the user data.
                 let result = assistant.check_num_token_and_get_user(user_id, num_token).await;
                 if let Err(err) = result {
                     return addr.do_send(AsyncResultError(err.status, err.code.to_string(), err.message.to_string()));
                 }
-                user_name = result.unwrap().nickname.clone();
+                let user = result.unwrap();
+                user_name = user.nickname.clone();
+                // Determine if the user has chat moderation rights.
+                is_moderator = ChatWsAssistant::is_moderator(&user.role);
             }
 
             // Get chat access information.
@@ -276,16 +357,48 @@ impl ChatWsSession {
             let is_owner = if opt_user_id.is_some() { opt_user_id.unwrap() == chat_access.stream_owner } else { false };
             // Get the "block" value for the given user.
             let is_blocked = if opt_user_id.is_some() { chat_access.is_blocked } else { true };
+            // Get the end of a timed block for the given user.
+            let block_until = if is_blocked { chat_access.block_until } else { None };
             let owner_id = chat_access.stream_owner;
+            // The user may also be appointed as a moderator of this particular room.
+            let is_moderator = is_moderator || (opt_user_id.is_some() && chat_access.is_moderator);
+
+            // Get the pinned messages and announcements of the room.
+            let result = assistant.get_chat_pins(room_id).await;
+            if let Err(err) = result {
+                return addr.do_send(AsyncResultError(err.status, err.code.to_string(), err.message.to_string()));
+            }
+            let pins: Vec<PinEWS> = result.unwrap().into_iter().map(PinEWS::from).collect();
 
             let user_id = opt_user_id.unwrap_or(i32::default());
-            debug!("handle_ews_join_add_task() room_id:{room_id}, user_name:{user_name}, is_owner:{is_owner}, is_blocked:{is_blocked}");
+            #[rustfmt::skip]
+            debug!("handle_ews_join_add_task() room_id:{room_id}, user_name:{user_name}, is_owner:{is_owner}, is_blocked:{is_blocked}, is_moderator:{is_moderator}");
             // Send the "AsyncResultEwsJoin" command for execution.
-            addr.do_send(AsyncResultEwsJoin(room_id, user_id, user_name, is_owner, is_blocked, owner_id));
+            addr.do_send(AsyncResultEwsJoin(
+                room_id, user_id, user_name, is_owner, is_blocked, owner_id, is_moderator, block_until, pins, backlog,
+            ));
         });
         Ok(())
     }
 
+    // * Send the last messages of the room to the client as a single batch. *
+    fn handle_ews_backlog(&mut self, room_id: i32, backlog: usize, ctx: &mut ws::WebsocketContext<Self>) {
+        debug!("handle_ews_backlog() room_id: {room_id}, backlog: {backlog}");
+        let assistant = self.assistant.clone();
+        // The session is already in the room, so the live messages are queued in its mailbox.
+        // "wait" does not process the mailbox until the recent messages are sent, so they always come first.
+        // A message created after joining can also get into the backlog, so its live "msg" event is then skipped.
+        fut::wrap_future::<_, Self>(async move { assistant.get_chat_backlog(room_id, backlog).await })
+            .map(|result, act_self, ctx| match result {
+                Ok(chat_messages) => {
+                    act_self.backlog_max_id = chat_messages.iter().map(|v| v.id).max();
+                    ctx.text(to_string(&BacklogEWS::new(chat_messages)).unwrap());
+                }
+                Err(err) => ctx.text(to_string(&ErrEWS::new(err.status, err.code.to_string(), err.message.to_string())).unwrap()),
+            })
+            .wait(ctx);
+    }
+
     // * Leave the client from the chat room. (Session -> Server) *
     pub fn handle_ews_leave(&mut self, ctx: &mut ws::WebsocketContext<Self>) -> Result<(), ErrEWS> {
         let is_connected = ctx.address().connected();
@@ -302,6 +415,9 @@ impl ChatWsSession {
         self.user_name = "".into();
         self.is_owner = false;
         self.is_blocked = false;
+        self.block_until = None;
+        self.is_moderator = false;
+        self.owner_id = i32::default();
 
         // issue_sync comes from having the `BrokerIssue` trait in scope.
         self.issue_system_sync(leave_room_srv, ctx);
@@ -332,19 +448,23 @@ impl ChatWsSession {
 // ** Added functionality for handling commands to block/unblock chat members. **
 
 impl ChatWsBlck for ChatWsSession {
+    #[rustfmt::skip]
     fn get_blck_info(&self) -> ChatWsBlckInfo {
-        ChatWsBlckInfo::new(self.room_id, self.user_id, self.user_name.clone(), self.is_owner)
+        ChatWsBlckInfo::new(self.room_id, self.user_id, self.user_name.clone(), self.is_owner, self.is_moderator, self.owner_id,
+            self.is_blocked, self.block_until.clone())
     }
-    fn set_is_blocked(&mut self, is_blocked: bool) {
+    fn set_is_blocked(&mut self, is_blocked: bool, block_until: Option<DateTime<Utc>>) {
         self.is_blocked = is_blocked;
+        self.block_until = block_until;
     }
 }
 
 // ** Adding functionality for processing "chat message transfer" commands. **
 
 impl ChatWsMsg for ChatWsSession {
+    #[rustfmt::skip]
     fn get_msg_info(&self) -> ChatWsMsgInfo {
-        ChatWsMsgInfo::new(self.room_id, self.user_id, self.user_name.clone(), self.is_blocked)
+        ChatWsMsgInfo::new(self.room_id, self.user_id, self.user_name.clone(), self.is_blocked, self.is_moderator, self.is_owner)
     }
 }
 
@@ -378,12 +498,16 @@ impl Handler<AsyncResultError> for ChatWsSession {
 // * * * * Handler for asynchronous response to the "JoinEWS" event * * * *
 
 struct AsyncResultEwsJoin(
-    i32,    // room_id
-    i32,    // user_id
-    String, // user_name
-    bool,   // is_owner
-    bool,   // is_blocked
-    i32,    // owner_id
+    i32,                   // room_id
+    i32,                   // user_id
+    String,                // user_name
+    bool,                  // is_owner
+    bool,                  // is_blocked
+    i32,                   // owner_id
+    bool,                  // is_moderator
+    Option<DateTime<Utc>>, // block_until
+    Vec<PinEWS>,           // pins
+    usize,                 // backlog
 );
 
 impl Message for AsyncResultEwsJoin {
@@ -399,12 +523,21 @@ impl Handler<AsyncResultEwsJoin> for ChatWsSession {
             // Send message about "leave"
             let _ = self.handle_ews_leave(ctx);
         }
-        let AsyncResultEwsJoin(room_id, user_id, user_name, is_owner, is_blocked, owner_id) = msg;
+        #[rustfmt::skip]
+        let AsyncResultEwsJoin(room_id, user_id, user_name, is_owner, is_blocked, owner_id, is_moderator, block_until, pins, backlog) = msg;
 
         self.user_id = user_id;
         self.user_name = user_name.clone();
         self.is_owner = is_owner;
         self.is_blocked = is_blocked;
+        self.block_until = block_until.clone();
+        self.is_moderator = is_moderator;
+        self.owner_id = owner_id;
+        self.backlog_max_id = None;
+        if let Some(block_until) = block_until {
+            // Schedule automatic unblocking when the timed block expires.
+            self.schedule_unblock(block_until, ctx);
+        }
 
         // Then send a join message for the new room
         let join_room_srv = JoinRoom(room_id, owner_id, self.user_name.clone(), ctx.address().recipient());
@@ -417,13 +550,19 @@ impl Handler<AsyncResultEwsJoin> for ChatWsSession {
                     act_self.id = id;
                     act_self.room_id = room_id;
                     if log_enabled!(Debug) {
-                        let s1 = format!("is_owner: {is_owner}, is_blocked: {is_blocked}");
+                        let s1 = format!("is_owner: {is_owner}, is_blocked: {is_blocked}, is_moderator: {is_moderator}");
                         debug!("handler<AsyncResultEwsJoin>() room_id:{room_id}, user_name: {user_name}, count:{count}, {s1}");
                     }
                     let is_owner = Some(is_owner);
                     let is_blocked = Some(is_blocked);
+                    let is_moderator = Some(is_moderator);
+                    let pins = if !pins.is_empty() { Some(pins) } else { None };
                     #[rustfmt::skip]
-                    ctx.text(to_string(&JoinEWS { join: room_id, member: user_name, count, is_owner, is_blocked }).unwrap());
+                    ctx.text(to_string(&JoinEWS { join: room_id, member: user_name, count, is_owner, is_blocked, is_moderator, pins }).unwrap());
+                    if backlog > 0 {
+                        // Send the recent messages of the room before any live "msg" events.
+                        act_self.handle_ews_backlog(room_id, backlog, ctx);
+                    }
                 }
                 fut::ready(())
             })
@@ -453,6 +592,12 @@ impl Handler<CommandSrv> for ChatWsSession {
 impl ChatWsSession {
     // Handler for "CommandSrv::Chat(ChatMessageSsn)".
     fn handle_chat_message_ssn(&mut self, chat_msg: ChatMsgSsn, ctx: &mut ws::WebsocketContext<Self>) {
+        if let Some(backlog_max_id) = self.backlog_max_id {
+            // The new message has already been sent to the client in the backlog.
+            if MsgEWS::get_id(&chat_msg.0).is_some_and(|id| id <= backlog_max_id) {
+                return;
+            }
+        }
         ctx.text(chat_msg.0);
     }
 
@@ -1,7 +1,12 @@
 #[cfg(all(test, feature = "mockdata"))]
 mod tests {
-    use actix_web::{App, web::Bytes};
-    use actix_web_actors::ws::{Frame::Text as FrameText, Message::Text as MessageText};
+    use std::time::Duration;
+
+    use actix_web::{App, rt::time::timeout, web::Bytes};
+    use actix_web_actors::ws::{
+        Frame::{Ping as FramePing, Text as FrameText},
+        Message::{Pong as MessagePong, Text as MessageText},
+    };
     use futures_util::{SinkExt, StreamExt}; // this is needed for "send" method in Framed.
     use serde_json::to_string;
     use vrb_authent::{
@@ -17,6 +22,7 @@ mod tests {
         chat_message_orm::tests::ChatMessageOrmTest,
         chat_ws_controller::get_ws_chat,
         chat_ws_tools::{get_err400, get_err401, get_err404, get_err406, get_err409},
+        config_chat,
     };
 
     const URL_WS: &str = "/ws";
@@ -34,6 +40,7 @@ mod tests {
             App::new()
                 .service(get_ws_chat)
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(config_chat::tests::cfg_config_chat(config_chat::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
         });
@@ -78,12 +85,13 @@ mod tests {
             let mut data_u = UserOrmTest::users(&[USER, USER]);
             let session1 = data_u.1.get(0).unwrap().clone();
             let user3_id = USER1_ID + 2;
-            let session3 = Session::new(user3_id, Some(config_jwt::tests::get_num_token(user3_id + 1)));
+            let session3 = Session::new(2, user3_id, config_jwt::tests::get_num_token(user3_id + 1));
             data_u.1 = vec![session1, session3];
             let data_cm = ChatMessageOrmTest::chat_messages(0);
             App::new()
                 .service(get_ws_chat)
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(config_chat::tests::cfg_config_chat(config_chat::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
         });
@@ -210,14 +218,15 @@ mod tests {
             let mut data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
             let user2_id = data_u.0.get(1).unwrap().id;
             // Add session (num_token) for user2.
-            data_u.1.get_mut(1).unwrap().num_token = Some(config_jwt::tests::get_num_token(user2_id));
+            data_u.1.push(Session::new(2, user2_id, config_jwt::tests::get_num_token(user2_id)));
             let user4_id = data_u.0.get(3).unwrap().id;
             // Add session (num_token) for user4.
-            data_u.1.get_mut(3).unwrap().num_token = Some(config_jwt::tests::get_num_token(user4_id));
+            data_u.1.push(Session::new(4, user4_id, config_jwt::tests::get_num_token(user4_id)));
             let data_cm = ChatMessageOrmTest::chat_messages(0);
             App::new()
                 .service(get_ws_chat)
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(config_chat::tests::cfg_config_chat(config_chat::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
         });
@@ -237,7 +246,7 @@ mod tests {
         let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS {
-            join: stream1_id, member: member1.clone(), count: 1, is_owner: Some(true), is_blocked: Some(false) }).unwrap();
+            join: stream1_id, member: member1.clone(), count: 1, is_owner: Some(true), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value)));
 
         // -- Test: 2. "There was already a 'join' to the room.". Trying to connect again. --
@@ -257,13 +266,13 @@ mod tests {
         let item = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS {
-            join: stream1_id, member: "".into(), count: 2, is_owner: Some(false), is_blocked: Some(true) }).unwrap();
+            join: stream1_id, member: "".into(), count: 2, is_owner: Some(false), is_blocked: Some(true), is_moderator: Some(false), pins: None }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value)));
         // Message to user1 about user2 joining.
         let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS {
-            join: stream1_id, member: "".into(), count: 2, is_owner: None, is_blocked: None }).unwrap();
+            join: stream1_id, member: "".into(), count: 2, is_owner: None, is_blocked: None, is_moderator: None, pins: None }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value)));
 
         // Leave user2. (Test: Leave unauthorized.)
@@ -291,13 +300,13 @@ mod tests {
         let item = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS {
-            join: stream1_id, member: member2.clone(), count: 2, is_owner: Some(false), is_blocked: Some(false) }).unwrap();
+            join: stream1_id, member: member2.clone(), count: 2, is_owner: Some(false), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value)));
         // Message to user1 about user2 joining.
         let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS {
-            join: stream1_id, member: member2.clone(), count: 2, is_owner: None, is_blocked: None }).unwrap();
+            join: stream1_id, member: member2.clone(), count: 2, is_owner: None, is_blocked: None, is_moderator: None, pins: None }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value)));
 
         // Leave user2. (Test: Leave authorized.)
@@ -322,13 +331,13 @@ mod tests {
         let item = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS {
-            join: stream1_id, member: member4.clone(), count: 2, is_owner: Some(false), is_blocked: Some(true) }).unwrap();
+            join: stream1_id, member: member4.clone(), count: 2, is_owner: Some(false), is_blocked: Some(true), is_moderator: Some(false), pins: None }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value)));
         // Message to user1 about user2 joining.
         let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS {
-            join: stream1_id, member: member4.clone(), count: 2, is_owner: None, is_blocked: None }).unwrap();
+            join: stream1_id, member: member4.clone(), count: 2, is_owner: None, is_blocked: None, is_moderator: None, pins: None }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value)));
         // Leave user2.
         #[rustfmt::skip]
@@ -359,11 +368,12 @@ mod tests {
             let mut data_u = UserOrmTest::users(&[USER, USER]);
             let user2_id = data_u.0.get(1).unwrap().id;
             // Add session (num_token) for user2.
-            data_u.1.get_mut(1).unwrap().num_token = Some(config_jwt::tests::get_num_token(user2_id));
+            data_u.1.push(Session::new(2, user2_id, config_jwt::tests::get_num_token(user2_id)));
             let data_cm = ChatMessageOrmTest::chat_messages(0);
             App::new()
                 .service(get_ws_chat)
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(config_chat::tests::cfg_config_chat(config_chat::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
         });
@@ -390,7 +400,7 @@ mod tests {
         let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS {
-            join: stream1_id, member: member1.clone(), count: 1, is_owner: Some(true), is_blocked: Some(false) }).unwrap();
+            join: stream1_id, member: member1.clone(), count: 1, is_owner: Some(true), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value)));
 
         // -- Test: 2. "Number of connected users."" --
@@ -413,13 +423,13 @@ mod tests {
         let item = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS {
-            join: stream1_id, member: member2.clone(), count: 2, is_owner: Some(false), is_blocked: Some(false) }).unwrap();
+            join: stream1_id, member: member2.clone(), count: 2, is_owner: Some(false), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value)));
         // Message to user1 about user2 joining.
         let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS {
-            join: stream1_id, member: member2.clone(), count: 2, is_owner: None, is_blocked: None }).unwrap();
+            join: stream1_id, member: member2.clone(), count: 2, is_owner: None, is_blocked: None, is_moderator: None, pins: None }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value)));
 
         // -- Test: 3. "Number of connected users."" --
@@ -448,4 +458,90 @@ mod tests {
         let value = to_string(&LeaveEWS {leave: stream1_id, member: member1.clone(), count: 0 }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value)));
     }
+
+    // ** heartbeat **
+
+    #[actix_web::test]
+    async fn test_get_ws_chat_heartbeat_timeout() {
+        // Create a test server without listening on a port.
+        let mut srv = actix_test::start(|| {
+            let mut data_u = UserOrmTest::users(&[USER, USER]);
+            let user2_id = data_u.0.get(1).unwrap().id;
+            // Add session (num_token) for user2.
+            data_u.1.push(Session::new(2, user2_id, config_jwt::tests::get_num_token(user2_id)));
+            let data_cm = ChatMessageOrmTest::chat_messages(0);
+            // The "ping" is sent every second, and the client is disconnected after two seconds without activity.
+            #[rustfmt::skip]
+            let config_chat = config_chat::ConfigChat { chat_heartbeat: 1, chat_client_timeout: 2, ..config_chat::tests::get_config() };
+            App::new()
+                .service(get_ws_chat)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(config_chat::tests::cfg_config_chat(config_chat))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        });
+        let stream1_id = ChatMessageOrmTest::stream_ids().get(0).unwrap().clone(); // live: true
+        let (profile_vec, _session_vec) = UserOrmTest::users(&[USER, USER]);
+
+        // Open a websocket connection to the test server. (This client does not respond to "ping".)
+        let mut framed1 = srv.ws_at(URL_WS).await.unwrap();
+        let user1_id = profile_vec.get(0).unwrap().id;
+        let member1 = profile_vec.get(0).unwrap().nickname.clone();
+        let token1 = config_jwt::tests::get_token(user1_id);
+        // Join user1.
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"join\": {}, \"access\": \"{}\" }}", stream1_id, token1).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&JoinEWS {
+            join: stream1_id, member: member1.clone(), count: 1, is_owner: Some(true), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
+        assert_eq!(item, FrameText(Bytes::from(value)));
+
+        // Open a websocket connection to the test server. (This client responds to "ping".)
+        let mut framed2 = srv.ws_at(URL_WS).await.unwrap();
+        let user2_id = profile_vec.get(1).unwrap().id;
+        let member2 = profile_vec.get(1).unwrap().nickname.clone();
+        let token2 = config_jwt::tests::get_token(user2_id);
+        // Join user2.
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"join\": {}, \"access\": \"{}\" }}", stream1_id, token2).into());
+        framed2.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&JoinEWS {
+            join: stream1_id, member: member2.clone(), count: 2, is_owner: Some(false), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
+        assert_eq!(item, FrameText(Bytes::from(value)));
+
+        // -- Test: 1. "The client that does not respond to 'ping' leaves the room after the timeout." --
+        let mut count_ping = 0;
+        let item = loop {
+            // Receive a message from a websocket (no longer than the timeout with a margin).
+            let item = timeout(Duration::from_secs(6), framed2.next()).await.unwrap().unwrap().unwrap();
+            if let FramePing(bytes) = item {
+                count_ping += 1;
+                framed2.send(MessagePong(bytes)).await.unwrap(); // Send a "pong" to a websocket.
+            } else {
+                break item;
+            }
+        };
+        assert!(count_ping > 0);
+        // Message to user2 about user1 leaving.
+        #[rustfmt::skip]
+        let value = to_string(&LeaveEWS { leave: stream1_id, member: member1.clone(), count: 1 }).unwrap();
+        assert_eq!(item, FrameText(Bytes::from(value)));
+
+        // -- Test: 2. "The client that responds to 'ping' remains in the room." --
+        framed2.send(MessageText("{ \"count\": 0 }".into())).await.unwrap(); // Send a message to a websocket.
+        let item = loop {
+            let item = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+            if let FramePing(bytes) = item {
+                framed2.send(MessagePong(bytes)).await.unwrap(); // Send a "pong" to a websocket.
+            } else {
+                break item;
+            }
+        };
+        let value = to_string(&CountEWS { count: 1 }).unwrap();
+        assert_eq!(item, FrameText(Bytes::from(value)));
+    }
 }
@@ -6,6 +6,7 @@ mod tests {
     use serde_json::to_string;
     use vrb_authent::{
         config_jwt,
+        user_models::Session,
         user_orm::tests::{USER, UserOrmTest},
     };
     use vrb_common::err;
@@ -14,7 +15,7 @@ mod tests {
         chat_event_ws::{BlockEWS, JoinEWS, LeaveEWS, UnblockEWS},
         chat_message_controller::{delete_blocked_user, tests as ChatMessageCtrlTest},
         chat_message_models::DeleteBlockedUserDto, chat_message_orm::tests::ChatMessageOrmTest, chat_ws_controller::get_ws_chat, 
-        chat_ws_tools::{get_err400, get_err403, get_err404, get_err406}
+        chat_ws_tools::{get_err400, get_err403, get_err404, get_err406}, config_chat,
     };
 
     const URL_WS: &str = "/ws";
@@ -28,11 +29,12 @@ mod tests {
             let mut data_u = UserOrmTest::users(&[USER, USER, USER]);
             let user2_id = data_u.0.get(1).unwrap().id;
             // Add session (num_token) for user2.
-            data_u.1.get_mut(1).unwrap().num_token = Some(config_jwt::tests::get_num_token(user2_id));
+            data_u.1.push(Session::new(2, user2_id, config_jwt::tests::get_num_token(user2_id)));
             let data_cm = ChatMessageOrmTest::chat_messages(2);
             App::new()
                 .service(get_ws_chat)
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(config_chat::tests::cfg_config_chat(config_chat::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
         });
@@ -80,7 +82,7 @@ mod tests {
         let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS { 
-            join: stream1_id, member: member2.clone(), count: 1, is_owner: Some(false), is_blocked: Some(false) }).unwrap();
+            join: stream1_id, member: member2.clone(), count: 1, is_owner: Some(false), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value)));
 
         // Block user3.
@@ -108,12 +110,13 @@ mod tests {
             let user2_id = data_u.0.get(1).unwrap().id;
             let user4_id = data_u.0.get(3).unwrap().id;
             // Add session (num_token) for user2, user4.
-            data_u.1.get_mut(1).unwrap().num_token = Some(config_jwt::tests::get_num_token(user2_id));
-            data_u.1.get_mut(3).unwrap().num_token = Some(config_jwt::tests::get_num_token(user4_id));
+            data_u.1.push(Session::new(2, user2_id, config_jwt::tests::get_num_token(user2_id)));
+            data_u.1.push(Session::new(4, user4_id, config_jwt::tests::get_num_token(user4_id)));
             let data_cm = ChatMessageOrmTest::chat_messages(2);
             App::new()
                 .service(get_ws_chat)
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(config_chat::tests::cfg_config_chat(config_chat::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
         });
@@ -134,7 +137,7 @@ mod tests {
         let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS { 
-            join: stream1_id, member: member1.clone(), count: 1, is_owner: Some(true), is_blocked: Some(false) }).unwrap();
+            join: stream1_id, member: member1.clone(), count: 1, is_owner: Some(true), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value)));
 
         // -- Test: 1. Unblocking user2 who has not blocked and is not in the chat. --
@@ -157,7 +160,7 @@ mod tests {
         // Message to user1.
         let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
-        let value = to_string(&BlockEWS { block: member2.clone(), is_in_chat: false }).unwrap();
+        let value = to_string(&BlockEWS { block: member2.clone(), is_in_chat: false, block_until: None }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value.clone())));
 
         // -- Test: 3. Unblocking user4 who was blocked and is not in the chat. --
@@ -180,7 +183,7 @@ mod tests {
         // Message to user1.
         let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
-        let value = to_string(&BlockEWS { block: member4.clone(), is_in_chat: false }).unwrap();
+        let value = to_string(&BlockEWS { block: member4.clone(), is_in_chat: false, block_until: None }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value.clone())));
 
         // Open a websocket connection to the test server.
@@ -197,13 +200,13 @@ mod tests {
         let item = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS { 
-            join: stream1_id, member: member2.clone(), count: 2, is_owner: Some(false), is_blocked: Some(false) }).unwrap();
+            join: stream1_id, member: member2.clone(), count: 2, is_owner: Some(false), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value)));
         // Message about join user2.
         let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS {
-            join: stream1_id, member: member2.clone(), count: 2, is_owner: None, is_blocked: None }).unwrap();
+            join: stream1_id, member: member2.clone(), count: 2, is_owner: None, is_blocked: None, is_moderator: None, pins: None }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value)));
 
         // -- Test: 5. Unblocking user2 who has not blocked and is in the chat. --
@@ -225,7 +228,7 @@ mod tests {
         // Message to user1.
         let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
-        let value = to_string(&BlockEWS { block: member2.clone(), is_in_chat: true }).unwrap();
+        let value = to_string(&BlockEWS { block: member2.clone(), is_in_chat: true, block_until: None }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value.clone())));
         // Message to user2.
         let item = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
@@ -253,13 +256,13 @@ mod tests {
         let item = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS { 
-            join: stream1_id, member: member4.clone(), count: 2, is_owner: Some(false), is_blocked: Some(true) }).unwrap();
+            join: stream1_id, member: member4.clone(), count: 2, is_owner: Some(false), is_blocked: Some(true), is_moderator: Some(false), pins: None }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value)));
         // Message to user1 about user4 joining.
         let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS { 
-            join: stream1_id, member: member4.clone(), count: 2, is_owner: None, is_blocked: None }).unwrap();
+            join: stream1_id, member: member4.clone(), count: 2, is_owner: None, is_blocked: None, is_moderator: None, pins: None }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value)));
 
         // -- Test: 7. Unblocking user4 who was blocked and is in the chat. --
@@ -284,7 +287,7 @@ mod tests {
         // Message to user1.
         let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
-        let value = to_string(&BlockEWS { block: member4.clone(), is_in_chat: true }).unwrap();
+        let value = to_string(&BlockEWS { block: member4.clone(), is_in_chat: true, block_until: None }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value.clone())));
         // Message to user4.
         let item = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
@@ -298,11 +301,12 @@ mod tests {
             let mut data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
             let user4_id = data_u.0.get(3).unwrap().id;
             // Add session (num_token) for user4.
-            data_u.1.get_mut(3).unwrap().num_token = Some(config_jwt::tests::get_num_token(user4_id));
+            data_u.1.push(Session::new(4, user4_id, config_jwt::tests::get_num_token(user4_id)));
             let data_cm = ChatMessageOrmTest::chat_messages(2);
             App::new()
                 .service(get_ws_chat)
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(config_chat::tests::cfg_config_chat(config_chat::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
                 .service(delete_blocked_user)
@@ -325,7 +329,7 @@ mod tests {
         let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS { 
-            join: stream1_id, member: member1.clone(), count: 1, is_owner: Some(true), is_blocked: Some(false) }).unwrap();
+            join: stream1_id, member: member1.clone(), count: 1, is_owner: Some(true), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value)));
 
         // Open a websocket connection to the test server.
@@ -341,13 +345,13 @@ mod tests {
         let item = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS { 
-            join: stream1_id, member: member4.clone(), count: 2, is_owner: Some(false), is_blocked: Some(true) }).unwrap();
+            join: stream1_id, member: member4.clone(), count: 2, is_owner: Some(false), is_blocked: Some(true), is_moderator: Some(false), pins: None }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value)));
         // Message to user1 about user4 joining.
         let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS { 
-            join: stream1_id, member: member4.clone(), count: 2, is_owner: None, is_blocked: None }).unwrap();
+            join: stream1_id, member: member4.clone(), count: 2, is_owner: None, is_blocked: None, is_moderator: None, pins: None }).unwrap();
         assert_eq!(item, FrameText(Bytes::from(value)));
 
         // Call the unblock method for the user (user4_id) who is in the chat.
@@ -365,4 +369,83 @@ mod tests {
         let item = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         assert_eq!(item, FrameText(Bytes::from(value)));
     }
+    #[actix_web::test]
+    async fn test_get_ws_chat_ews_block_ews_unblock_by_room_moderator() {
+        // Create a test server without listening on a port.
+        let mut srv = actix_test::start(move || {
+            let mut data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
+            let user3_id = data_u.0.get(2).unwrap().id;
+            // Add session (num_token) for user3.
+            data_u.1.push(Session::new(3, user3_id, config_jwt::tests::get_num_token(user3_id)));
+            let data_cm = ChatMessageOrmTest::chat_messages(2);
+            App::new()
+                .service(get_ws_chat)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(config_chat::tests::cfg_config_chat(config_chat::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        });
+        let (profile_vec, _session_vec) = UserOrmTest::users(&[USER, USER, USER, USER]);
+        let stream1_id = ChatMessageOrmTest::stream_ids().get(0).unwrap().clone(); // live: true
+        // User3 is the moderator of stream2.
+        let stream2_id = ChatMessageOrmTest::stream_ids().get(1).unwrap().clone(); // live: true
+
+        let user3_id = profile_vec.get(2).unwrap().id;
+        let member3 = profile_vec.get(2).unwrap().nickname.clone();
+        let token3 = config_jwt::tests::get_token(user3_id);
+        let member1 = profile_vec.get(0).unwrap().nickname.clone();
+
+        // Open a websocket connection to the test server.
+        let mut framed1 = srv.ws_at(URL_WS).await.unwrap();
+
+        // Join user3 to stream1, where user3 is not a moderator.
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"join\": {}, \"access\": \"{}\" }}", stream1_id, token3).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&JoinEWS {
+            join: stream1_id, member: member3.clone(), count: 1, is_owner: Some(false), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
+        assert_eq!(item, FrameText(Bytes::from(value)));
+
+        // -- Test: 1. Blocking user1 in a room where user3 is not a moderator. --
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"block\": \"{}\" }}", member1.clone()).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let err403 = get_err403(err::MSG_STREAM_OWNER_RIGHTS_MISSING);
+        assert_eq!(item, FrameText(Bytes::from(to_string(&err403).unwrap()))); // 403:Forbidden
+
+        // Open a websocket connection to the test server.
+        let mut framed2 = srv.ws_at(URL_WS).await.unwrap();
+
+        // Join user3 to stream2, where user3 is a moderator.
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"join\": {}, \"access\": \"{}\" }}", stream2_id, token3).into());
+        framed2.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&JoinEWS {
+            join: stream2_id, member: member3.clone(), count: 1, is_owner: Some(false), is_blocked: Some(false), is_moderator: Some(true), pins: None }).unwrap();
+        assert_eq!(item, FrameText(Bytes::from(value)));
+
+        // -- Test: 2. Blocking user1 in a room where user3 is a moderator. --
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"block\": \"{}\" }}", member1.clone()).into());
+        framed2.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&BlockEWS { block: member1.clone(), is_in_chat: false, block_until: None }).unwrap();
+        assert_eq!(item, FrameText(Bytes::from(value)));
+
+        // -- Test: 3. Unblocking user4 (blocked by the owner of stream2) in a room where user3 is a moderator. --
+        let member4 = profile_vec.get(3).unwrap().nickname.clone();
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"unblock\": \"{}\" }}", member4.clone()).into());
+        framed2.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&UnblockEWS { unblock: member4.clone(), is_in_chat: false }).unwrap();
+        assert_eq!(item, FrameText(Bytes::from(value)));
+    }
 }
\ No newline at end of file
@@ -1,26 +1,42 @@
 #[cfg(all(test, feature = "mockdata"))]
 mod tests {
-    use actix_web::{App, web::Bytes};
+    use std::{sync::Arc, time};
+
+    use actix::SystemService;
+    use actix_web::{App, rt, web::Bytes};
     use actix_web_actors::ws::{Frame::Text as FrameText, Message::Text as MessageText};
-    use chrono::{SecondsFormat, Utc};
+    use chrono::{Duration, SecondsFormat, Utc};
     use futures_util::{SinkExt, StreamExt}; // this is needed for "send" method in Framed
     use serde_json::{from_slice, to_string};
     use vrb_authent::{
         config_jwt,
+        user_models::Session,
         user_orm::tests::{USER, UserOrmTest},
     };
     use vrb_common::err;
+    use vrb_webhooks::{
+        config_hook,
+        webhook_dispatcher::{SetWebhookOrm, WebhookDispatcher},
+        webhook_models::{Webhook, WebhookEvent, WebhookPayload},
+        webhook_orm::tests::{WEBHOOK_ID, WebhookOrmApp},
+        webhook_sender::tests::ReceiverTest,
+    };
 
     use crate::{
-        chat_event_ws::{JoinEWS, LeaveEWS, MsgEWS, MsgRmvEWS },
-        chat_message_orm::tests::ChatMessageOrmTest,
+        chat_event_ws::{BacklogEWS, JoinEWS, LeaveEWS, MentionEWS, MsgEWS, MsgRmvEWS, PinEWS, ReactionEWS, SlowModeEWS, UnpinEWS},
+        chat_message_models::{ChatMessageDto, ChatPin, MESSAGE_MAX, REACTION_MAX, REACTIONS_PER_USER_MAX},
+        chat_message_orm::tests::{CHAT_PIN_ID, ChatMessageOrmTest, ReactionData},
         chat_ws_controller::get_ws_chat,
-        chat_ws_tools::{get_err400, get_err403, get_err404, get_err406},
+        chat_ws_msg::SLOW_MODE_MAX,
+        chat_ws_session::BACKLOG_MAX,
+        chat_ws_tools::{get_err400, get_err403, get_err404, get_err406, get_err429},
+        config_chat,
     };
 
     const URL_WS: &str = "/ws";
     const ERROR_PROCESSING_WS_FRAME_TEXT: &str = "Error processing websocket message Frame::Text(Bytes)";
     const MSG_FAILED_DESER: &str = "Failed to deserialize response from JSON.";
+    const WAIT_WEBHOOK: time::Duration = time::Duration::from_millis(300);
 
     // ** ews_msg, ews_msg_cut, ews_msg_put, ews_msg_rmv **
 
@@ -32,12 +48,13 @@ mod tests {
             let user2_id = data_u.0.get(1).unwrap().id;
             let user4_id = data_u.0.get(3).unwrap().id;
             // Add session (num_token) for user2, user4.
-            data_u.1.get_mut(1).unwrap().num_token = Some(config_jwt::tests::get_num_token(user2_id));
-            data_u.1.get_mut(3).unwrap().num_token = Some(config_jwt::tests::get_num_token(user4_id));
+            data_u.1.push(Session::new(2, user2_id, config_jwt::tests::get_num_token(user2_id)));
+            data_u.1.push(Session::new(4, user4_id, config_jwt::tests::get_num_token(user4_id)));
             let data_cm = ChatMessageOrmTest::chat_messages(2);
             App::new()
                 .service(get_ws_chat)
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(config_chat::tests::cfg_config_chat(config_chat::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
         });
@@ -162,7 +179,7 @@ mod tests {
         let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS { 
-            join: stream1_id, member: member4.clone(), count: 1, is_owner: Some(false), is_blocked: Some(true) }).unwrap();
+            join: stream1_id, member: member4.clone(), count: 1, is_owner: Some(false), is_blocked: Some(true), is_moderator: Some(false), pins: None }).unwrap();
         assert_eq!(item1, FrameText(Bytes::from(value)));
         
         // -- Test: 1. ews_msg --
@@ -221,7 +238,7 @@ mod tests {
         let item3 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS {
-            join: stream1_id, member: "".into(), count: 1, is_owner: Some(false), is_blocked: Some(true) }).unwrap();
+            join: stream1_id, member: "".into(), count: 1, is_owner: Some(false), is_blocked: Some(true), is_moderator: Some(false), pins: None }).unwrap();
         assert_eq!(item3, FrameText(Bytes::from(value)));
 
         // -- Test: 1. ews_msg --
@@ -282,7 +299,7 @@ mod tests {
         let item2 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS { 
-            join: stream1_id, member: member2.clone(), count: 1, is_owner: Some(false), is_blocked: Some(false) }).unwrap();
+            join: stream1_id, member: member2.clone(), count: 1, is_owner: Some(false), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
         assert_eq!(item2, FrameText(Bytes::from(value)));
 
         // -- Test: 2. ews_msg_put --
@@ -325,11 +342,12 @@ mod tests {
             let mut data_u = UserOrmTest::users(&[USER, USER]);
             let user2_id = data_u.0.get(1).unwrap().id;
             // Add session (num_token) for user2.
-            data_u.1.get_mut(1).unwrap().num_token = Some(config_jwt::tests::get_num_token(user2_id));
+            data_u.1.push(Session::new(2, user2_id, config_jwt::tests::get_num_token(user2_id)));
             let data_cm = ChatMessageOrmTest::chat_messages(2);
             App::new()
                 .service(get_ws_chat)
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(config_chat::tests::cfg_config_chat(config_chat::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
         });
@@ -355,7 +373,7 @@ mod tests {
         let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS { 
-            join: stream1_id, member: member1.clone(), count: 1, is_owner: Some(true), is_blocked: Some(false) }).unwrap();
+            join: stream1_id, member: member1.clone(), count: 1, is_owner: Some(true), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
         assert_eq!(item1, FrameText(Bytes::from(value)));
 
         // Open a websocket connection to the test server.
@@ -375,13 +393,13 @@ mod tests {
         let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS { 
-            join: stream1_id, member: member2.clone(), count: 2, is_owner: Some(false), is_blocked: Some(false) }).unwrap();
+            join: stream1_id, member: member2.clone(), count: 2, is_owner: Some(false), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
         assert_eq!(item2, FrameText(Bytes::from(value)));
         // Message about join user 2.
         let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS {
-            join: stream1_id, member: member2.clone(), count: 2, is_owner: None, is_blocked: None }).unwrap();
+            join: stream1_id, member: member2.clone(), count: 2, is_owner: None, is_blocked: None, is_moderator: None, pins: None }).unwrap();
         assert_eq!(item1, FrameText(Bytes::from(value)));
 
         // -- Test: 1.1. ews_msg: Send a message of type "msg". (authorized)  --
@@ -400,7 +418,8 @@ mod tests {
         // DateTime.to_rfc3339_opts(SecondsFormat::Secs, true)   => "2018-01-26T18:30:09Z"
         let date = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
         #[rustfmt::skip]
-        let msg_ews1 = MsgEWS { msg, id: ch_msg_id1, member: member1.clone(), date, date_edt: None, date_rmv: None };
+        let msg_ews1 = MsgEWS { msg, id: ch_msg_id1, member: member1.clone(), date, date_edt: None, date_rmv: None, cnt_edt: 0,
+            reply_to: None, reply_member: None, reply_msg: None };
 
         let msg_ews2 = msg_ews1.clone();
         if let FrameText(buf) = item1 {
@@ -411,6 +430,7 @@ mod tests {
             assert_eq!(msg_ews_res.date[..19], msg_ews1.date[..19]);
             assert_eq!(msg_ews_res.date_edt.is_none(), msg_ews1.date_edt.is_none());
             assert_eq!(msg_ews_res.date_rmv.is_none(), msg_ews1.date_rmv.is_none());
+            assert_eq!(msg_ews_res.cnt_edt, msg_ews1.cnt_edt);
         } else {
             panic!("{}", ERROR_PROCESSING_WS_FRAME_TEXT);
         }
@@ -424,6 +444,7 @@ mod tests {
             assert_eq!(msg_ews_res.date[0..19], msg_ews2.date[0..19]);
             assert_eq!(msg_ews_res.date_edt.is_none(), msg_ews2.date_edt.is_none());
             assert_eq!(msg_ews_res.date_rmv.is_none(), msg_ews2.date_rmv.is_none());
+            assert_eq!(msg_ews_res.cnt_edt, msg_ews2.cnt_edt);
         } else {
             panic!("{}", ERROR_PROCESSING_WS_FRAME_TEXT);
         }
@@ -445,7 +466,8 @@ mod tests {
         let date = ch_msg1.date_created.to_rfc3339_opts(SecondsFormat::Secs, true);
         let date_edt = Some(Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true));
         #[rustfmt::skip]
-        let msg_ews = MsgEWS { msg, id: ch_msg1.id, member: member1.clone(), date, date_edt, date_rmv: None };
+        let msg_ews = MsgEWS { msg, id: ch_msg1.id, member: member1.clone(), date, date_edt, date_rmv: None, cnt_edt: ch_msg1.cnt_logs + 1,
+            reply_to: None, reply_member: None, reply_msg: None };
         let msg_ews2 = msg_ews.clone();
         if let FrameText(buf) = item {
             let msg_ews_res: MsgEWS = from_slice(&buf).expect(MSG_FAILED_DESER);
@@ -456,6 +478,7 @@ mod tests {
             assert_eq!(msg_ews_res.date_edt.is_some(), msg_ews.date_edt.is_some());
             assert_eq!(msg_ews_res.date_edt.unwrap()[..19], msg_ews.date_edt.unwrap()[..19]);
             assert_eq!(msg_ews_res.date_rmv.is_none(), msg_ews.date_rmv.is_none());
+            assert_eq!(msg_ews_res.cnt_edt, msg_ews.cnt_edt);
         } else {
             panic!("{}", ERROR_PROCESSING_WS_FRAME_TEXT);
         }
@@ -470,6 +493,7 @@ mod tests {
             assert_eq!(msg_ews_res.date_edt.is_some(), msg_ews2.date_edt.is_some());
             assert_eq!(msg_ews_res.date_edt.unwrap()[0..19], msg_ews2.date_edt.unwrap()[0..19]);
             assert_eq!(msg_ews_res.date_rmv.is_none(), msg_ews2.date_rmv.is_none());
+            assert_eq!(msg_ews_res.cnt_edt, msg_ews2.cnt_edt);
         } else {
             panic!("{}", ERROR_PROCESSING_WS_FRAME_TEXT);
         }
@@ -491,7 +515,8 @@ mod tests {
         let date = ch_msg2.date_created.to_rfc3339_opts(SecondsFormat::Secs, true);
         let date_rmv = Some(Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true));
         #[rustfmt::skip]
-        let msg_ews = MsgEWS { msg, id: ch_msg2.id, member: member1.clone(), date, date_edt: None, date_rmv };
+        let msg_ews = MsgEWS { msg, id: ch_msg2.id, member: member1.clone(), date, date_edt: None, date_rmv, cnt_edt: ch_msg2.cnt_logs + 1,
+            reply_to: None, reply_member: None, reply_msg: None };
         let msg_ews2 = msg_ews.clone();
         if let FrameText(buf) = item {
             let msg_ews_res: MsgEWS = from_slice(&buf).expect(MSG_FAILED_DESER);
@@ -502,6 +527,7 @@ mod tests {
             assert_eq!(msg_ews_res.date_edt.is_none(), msg_ews.date_edt.is_none());
             assert_eq!(msg_ews_res.date_rmv.is_some(), msg_ews.date_rmv.is_some());
             assert_eq!(msg_ews_res.date_rmv.unwrap()[..19], msg_ews.date_rmv.unwrap()[..19]);
+            assert_eq!(msg_ews_res.cnt_edt, msg_ews.cnt_edt);
         } else {
             panic!("{}", ERROR_PROCESSING_WS_FRAME_TEXT);
         }
@@ -516,6 +542,7 @@ mod tests {
             assert_eq!(msg_ews_res.date_edt.is_none(), msg_ews2.date_edt.is_none());
             assert_eq!(msg_ews_res.date_rmv.is_some(), msg_ews2.date_rmv.is_some());
             assert_eq!(msg_ews_res.date_rmv.unwrap()[..19], msg_ews2.date_rmv.unwrap()[..19]);
+            assert_eq!(msg_ews_res.cnt_edt, msg_ews2.cnt_edt);
         } else {
             panic!("{}", ERROR_PROCESSING_WS_FRAME_TEXT);
         }
@@ -558,13 +585,13 @@ mod tests {
         let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS {
-            join: stream1_id, member: "".into(), count: 2, is_owner: Some(false), is_blocked: Some(true) }).unwrap();
+            join: stream1_id, member: "".into(), count: 2, is_owner: Some(false), is_blocked: Some(true), is_moderator: Some(false), pins: None }).unwrap();
         assert_eq!(item2, FrameText(Bytes::from(value)));
         // Message to user1 about user2 joining.
         let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS {
-            join: stream1_id, member: "".into(), count: 2, is_owner: None, is_blocked: None }).unwrap();
+            join: stream1_id, member: "".into(), count: 2, is_owner: None, is_blocked: None, is_moderator: None, pins: None }).unwrap();
         assert_eq!(item1, FrameText(Bytes::from(value.clone())));
 
         // -- Test: 1.1. ews_msg: Send a message of type "msg". (unauthorized)  --
@@ -583,7 +610,8 @@ mod tests {
         // DateTime.to_rfc3339_opts(SecondsFormat::Secs, true)   => "2018-01-26T18:30:09Z"
         let date = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
         #[rustfmt::skip]
-        let msg_ews1 = MsgEWS { msg, id: ch_msg_id3, member: member1.clone(), date, date_edt: None, date_rmv: None };
+        let msg_ews1 = MsgEWS { msg, id: ch_msg_id3, member: member1.clone(), date, date_edt: None, date_rmv: None, cnt_edt: 0,
+            reply_to: None, reply_member: None, reply_msg: None };
         let msg_ews2 = msg_ews1.clone();
         if let FrameText(buf) = item1 {
             let msg_ews_res: MsgEWS = from_slice(&buf).expect(MSG_FAILED_DESER);
@@ -593,6 +621,7 @@ mod tests {
             assert_eq!(msg_ews_res.date[..19], msg_ews1.date[..19]);
             assert_eq!(msg_ews_res.date_edt.is_none(), msg_ews1.date_edt.is_none());
             assert_eq!(msg_ews_res.date_rmv.is_none(), msg_ews1.date_rmv.is_none());
+            assert_eq!(msg_ews_res.cnt_edt, msg_ews1.cnt_edt);
         } else {
             panic!("{}", ERROR_PROCESSING_WS_FRAME_TEXT);
         }
@@ -606,6 +635,7 @@ mod tests {
             assert_eq!(msg_ews_res.date[0..19], msg_ews2.date[0..19]);
             assert_eq!(msg_ews_res.date_edt.is_none(), msg_ews2.date_edt.is_none());
             assert_eq!(msg_ews_res.date_rmv.is_none(), msg_ews2.date_rmv.is_none());
+            assert_eq!(msg_ews_res.cnt_edt, msg_ews2.cnt_edt);
         } else {
             panic!("{}", ERROR_PROCESSING_WS_FRAME_TEXT);
         }
@@ -627,7 +657,8 @@ mod tests {
         let date = ch_msg3.date_created.to_rfc3339_opts(SecondsFormat::Secs, true);
         let date_edt = Some(Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true));
         #[rustfmt::skip]
-        let msg_ews = MsgEWS { msg, id: ch_msg3.id, member: member1.clone(), date, date_edt, date_rmv: None };
+        let msg_ews = MsgEWS { msg, id: ch_msg3.id, member: member1.clone(), date, date_edt, date_rmv: None, cnt_edt: ch_msg3.cnt_logs + 1,
+            reply_to: None, reply_member: None, reply_msg: None };
         let msg_ews2 = msg_ews.clone();
         if let FrameText(buf) = item {
             let msg_ews_res: MsgEWS = from_slice(&buf).expect(MSG_FAILED_DESER);
@@ -638,6 +669,7 @@ mod tests {
             assert_eq!(msg_ews_res.date_edt.is_some(), msg_ews.date_edt.is_some());
             assert_eq!(msg_ews_res.date_edt.unwrap()[..19], msg_ews.date_edt.unwrap()[..19]);
             assert_eq!(msg_ews_res.date_rmv.is_none(), msg_ews.date_rmv.is_none());
+            assert_eq!(msg_ews_res.cnt_edt, msg_ews.cnt_edt);
         } else {
             panic!("{}", ERROR_PROCESSING_WS_FRAME_TEXT);
         }
@@ -652,6 +684,7 @@ mod tests {
             assert_eq!(msg_ews_res.date_edt.is_some(), msg_ews2.date_edt.is_some());
             assert_eq!(msg_ews_res.date_edt.unwrap()[0..19], msg_ews2.date_edt.unwrap()[0..19]);
             assert_eq!(msg_ews_res.date_rmv.is_none(), msg_ews2.date_rmv.is_none());
+            assert_eq!(msg_ews_res.cnt_edt, msg_ews2.cnt_edt);
         } else {
             panic!("{}", ERROR_PROCESSING_WS_FRAME_TEXT);
         }
@@ -673,7 +706,8 @@ mod tests {
         let date = ch_msg4.date_created.to_rfc3339_opts(SecondsFormat::Secs, true);
         let date_rmv = Some(Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true));
         #[rustfmt::skip]
-        let msg_ews = MsgEWS { msg, id: ch_msg4.id, member: member1.clone(), date, date_edt: None, date_rmv };
+        let msg_ews = MsgEWS { msg, id: ch_msg4.id, member: member1.clone(), date, date_edt: None, date_rmv, cnt_edt: ch_msg4.cnt_logs + 1,
+            reply_to: None, reply_member: None, reply_msg: None };
         let msg_ews2 = msg_ews.clone();
         if let FrameText(buf) = item {
             let msg_ews_res: MsgEWS = from_slice(&buf).expect(MSG_FAILED_DESER);
@@ -684,6 +718,7 @@ mod tests {
             assert_eq!(msg_ews_res.date_edt.is_none(), msg_ews.date_edt.is_none());
             assert_eq!(msg_ews_res.date_rmv.is_some(), msg_ews.date_rmv.is_some());
             assert_eq!(msg_ews_res.date_rmv.unwrap()[..19], msg_ews.date_rmv.unwrap()[..19]);
+            assert_eq!(msg_ews_res.cnt_edt, msg_ews.cnt_edt);
         } else {
             panic!("{}", ERROR_PROCESSING_WS_FRAME_TEXT);
         }
@@ -698,6 +733,7 @@ mod tests {
             assert_eq!(msg_ews_res.date_edt.is_none(), msg_ews2.date_edt.is_none());
             assert_eq!(msg_ews_res.date_rmv.is_some(), msg_ews2.date_rmv.is_some());
             assert_eq!(msg_ews_res.date_rmv.unwrap()[..19], msg_ews2.date_rmv.unwrap()[..19]);
+            assert_eq!(msg_ews_res.cnt_edt, msg_ews2.cnt_edt);
         } else {
             panic!("{}", ERROR_PROCESSING_WS_FRAME_TEXT);
         }
@@ -719,4 +755,876 @@ mod tests {
         let item = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         assert_eq!(item, FrameText(Bytes::from(value)));
     }
-}
\ No newline at end of file
+
+    // ** ews_slow_mode **
+
+    #[actix_web::test]
+    async fn test_get_ws_chat_ews_slow_mode() {
+        // Create a test server without listening on a port.
+        let mut srv = actix_test::start(move || {
+            let mut data_u = UserOrmTest::users(&[USER, USER]);
+            let user2_id = data_u.0.get(1).unwrap().id;
+            // Add session (num_token) for user2.
+            data_u.1.push(Session::new(2, user2_id, config_jwt::tests::get_num_token(user2_id)));
+            let data_cm = ChatMessageOrmTest::chat_messages(2);
+            App::new()
+                .service(get_ws_chat)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(config_chat::tests::cfg_config_chat(config_chat::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        });
+
+        // Open a websocket connection to the test server.
+        let mut framed1 = srv.ws_at(URL_WS).await.unwrap();
+
+        let (profile_vec, _session_vec) = UserOrmTest::users(&[USER, USER]);
+        let stream1_id = ChatMessageOrmTest::stream_ids().get(0).unwrap().clone(); // live: true
+
+        // -- Test: 1. "'slowMode' parameter not defined" --
+        let msg_text = MessageText("{ \"slowMode\": null }".into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let err400 = get_err400(&format!("{}; name: '{}'", err::MSG_PARAMETER_NOT_DEFINED, "slowMode"));
+        assert_eq!(item1, FrameText(Bytes::from(to_string(&err400).unwrap()))); // 400:BadRequest
+
+        // -- Test: 2. "There was no 'join' command." --
+        let msg_text = MessageText("{ \"slowMode\": 30 }".into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let err406 = get_err406(err::MSG_THERE_WAS_NO_JOIN);
+        assert_eq!(item1, FrameText(Bytes::from(to_string(&err406).unwrap()))); // 406:NotAcceptable
+
+
+        // == Join user1 authorized. (is the owner) ==
+
+        let user1_id = profile_vec.get(0).unwrap().id;
+        let member1 = profile_vec.get(0).unwrap().nickname.clone();
+        let token1 = config_jwt::tests::get_token(user1_id);
+
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"join\": {}, \"access\": \"{}\" }}", stream1_id, token1).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&JoinEWS { 
+            join: stream1_id, member: member1.clone(), count: 1, is_owner: Some(true), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
+        assert_eq!(item1, FrameText(Bytes::from(value)));
+
+        // -- Test: 3. "The value is outside the allowed range." --
+        let slow_mode = SLOW_MODE_MAX + 1;
+        let msg_text = MessageText(format!("{{ \"slowMode\": {} }}", slow_mode).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let err406 = get_err406(&format!("{}; name: '{}', value: {}", err::MSG_PARAMETER_UNACCEPTABLE, "slowMode", slow_mode));
+        assert_eq!(item1, FrameText(Bytes::from(to_string(&err406).unwrap()))); // 406:NotAcceptable
+
+        // Open a websocket connection to the test server.
+        let mut framed2 = srv.ws_at(URL_WS).await.unwrap();
+
+
+        // == Join user2 authorized. (is not the owner) ==
+
+        let user2_id = profile_vec.get(1).unwrap().id;
+        let member2 = profile_vec.get(1).unwrap().nickname.clone();
+        let token2 = config_jwt::tests::get_token(user2_id);
+
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"join\": {}, \"access\": \"{}\" }}", stream1_id, token2).into());
+        framed2.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&JoinEWS { 
+            join: stream1_id, member: member2.clone(), count: 2, is_owner: Some(false), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
+        assert_eq!(item2, FrameText(Bytes::from(value)));
+        // Message about join user 2.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&JoinEWS {
+            join: stream1_id, member: member2.clone(), count: 2, is_owner: None, is_blocked: None, is_moderator: None, pins: None }).unwrap();
+        assert_eq!(item1, FrameText(Bytes::from(value)));
+
+        // -- Test: 4. "stream_owner_rights_missing" --
+        let msg_text = MessageText("{ \"slowMode\": 30 }".into());
+        framed2.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let err403 = get_err403(err::MSG_STREAM_OWNER_RIGHTS_MISSING);
+        assert_eq!(item2, FrameText(Bytes::from(to_string(&err403).unwrap()))); // 403:Forbidden
+
+        // -- Test: 5. The owner enables slow mode. --
+        let msg_text = MessageText("{ \"slowMode\": 30 }".into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let value = to_string(&SlowModeEWS { slow_mode: 30 }).unwrap();
+        // Message to user1.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        assert_eq!(item1, FrameText(Bytes::from(value.clone())));
+        // Message to user2.
+        let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        assert_eq!(item2, FrameText(Bytes::from(value.clone())));
+
+        // -- Test: 6. A message of user2 that was not created does not count towards the slow mode. --
+        let ch_msg_id_wrong = ChatMessageOrmTest::chat_messages(2).0.last().unwrap().id + 1;
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"msg\": \"text_6\", \"replyTo\": {} }}", ch_msg_id_wrong).into());
+        framed2.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let err404 = get_err404(&format!("{}; reply_to: {}", err::MSG_CHAT_MESSAGE_NOT_FOUND, ch_msg_id_wrong));
+        assert_eq!(item2, FrameText(Bytes::from(to_string(&err404).unwrap()))); // 404:NotFound
+
+        // -- Test: 7. User2 sends the first message. --
+        let msg_text = MessageText("{ \"msg\": \"text_7\" }".into());
+        framed2.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        // Message to user2.
+        let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        if let FrameText(buf) = item2 {
+            let msg_ews_res: MsgEWS = from_slice(&buf).expect(MSG_FAILED_DESER);
+            assert_eq!(msg_ews_res.msg, "text_7");
+            assert_eq!(msg_ews_res.member, member2);
+        } else {
+            panic!("{}", ERROR_PROCESSING_WS_FRAME_TEXT);
+        }
+        // Message to user1.
+        let _item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+
+        // -- Test: 8. User2 sends the second message too early. --
+        let msg_text = MessageText("{ \"msg\": \"text_8\" }".into());
+        framed2.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let err429 = get_err429(&format!("{}; wait: {} s", err::MSG_SLOW_MODE_ENABLED, 30));
+        assert_eq!(item2, FrameText(Bytes::from(to_string(&err429).unwrap()))); // 429:TooManyRequests
+
+        // -- Test: 9. The owner is not limited by slow mode. --
+        for msg in ["text_9_1", "text_9_2"] {
+            let msg_text = MessageText(format!("{{ \"msg\": \"{}\" }}", msg).into());
+            framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+            let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+            if let FrameText(buf) = item1 {
+                let msg_ews_res: MsgEWS = from_slice(&buf).expect(MSG_FAILED_DESER);
+                assert_eq!(msg_ews_res.msg, msg);
+                assert_eq!(msg_ews_res.member, member1);
+            } else {
+                panic!("{}", ERROR_PROCESSING_WS_FRAME_TEXT);
+            }
+            let _item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        }
+
+        // -- Test: 10. The owner disables slow mode. --
+        let msg_text = MessageText("{ \"slowMode\": 0 }".into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let value = to_string(&SlowModeEWS { slow_mode: 0 }).unwrap();
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        assert_eq!(item1, FrameText(Bytes::from(value.clone())));
+        let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        assert_eq!(item2, FrameText(Bytes::from(value.clone())));
+
+        // -- Test: 11. User2 can send messages again. --
+        let msg_text = MessageText("{ \"msg\": \"text_11\" }".into());
+        framed2.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        if let FrameText(buf) = item2 {
+            let msg_ews_res: MsgEWS = from_slice(&buf).expect(MSG_FAILED_DESER);
+            assert_eq!(msg_ews_res.msg, "text_11");
+        } else {
+            panic!("{}", ERROR_PROCESSING_WS_FRAME_TEXT);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_get_ws_chat_ews_msg_reply_to() {
+        // Create a test server without listening on a port.
+        let mut srv = actix_test::start(move || {
+            let data_u = UserOrmTest::users(&[USER]);
+            let data_cm = ChatMessageOrmTest::chat_messages(2);
+            App::new()
+                .service(get_ws_chat)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(config_chat::tests::cfg_config_chat(config_chat::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        });
+
+        // Open a websocket connection to the test server.
+        let mut framed1 = srv.ws_at(URL_WS).await.unwrap();
+
+        let (profile_vec, _session_vec) = UserOrmTest::users(&[USER]);
+        let stream1_id = ChatMessageOrmTest::stream_ids().get(0).unwrap().clone(); // live: true
+        let data_cm = ChatMessageOrmTest::chat_messages(2);
+        let ch_msg1 = data_cm.0.get(0).unwrap().clone();
+        let ch_msg_id_wrong = data_cm.0.last().unwrap().id + 1;
+
+        // == Join user1 authorized. ==
+
+        let user1_id = profile_vec.get(0).unwrap().id;
+        let member1 = profile_vec.get(0).unwrap().nickname.clone();
+        let token1 = config_jwt::tests::get_token(user1_id);
+
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"join\": {}, \"access\": \"{}\" }}", stream1_id, token1).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&JoinEWS { 
+            join: stream1_id, member: member1.clone(), count: 1, is_owner: Some(true), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
+        assert_eq!(item1, FrameText(Bytes::from(value)));
+
+        // -- Test: 1. "The message being replied to was not found." --
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"msg\": \"text_1\", \"replyTo\": {} }}", ch_msg_id_wrong).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let err404 = get_err404(&format!("{}; reply_to: {}", err::MSG_CHAT_MESSAGE_NOT_FOUND, ch_msg_id_wrong));
+        assert_eq!(item1, FrameText(Bytes::from(to_string(&err404).unwrap()))); // 404:NotFound
+
+        // -- Test: 2. Reply to the message. --
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"msg\": \"text_2\", \"replyTo\": {} }}", ch_msg1.id).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        if let FrameText(buf) = item1 {
+            let msg_ews_res: MsgEWS = from_slice(&buf).expect(MSG_FAILED_DESER);
+            assert_eq!(msg_ews_res.msg, "text_2");
+            assert_eq!(msg_ews_res.member, member1);
+            assert_eq!(msg_ews_res.reply_to, Some(ch_msg1.id));
+            assert_eq!(msg_ews_res.reply_member, Some(ch_msg1.user_name.clone()));
+            assert_eq!(msg_ews_res.reply_msg, ch_msg1.msg.clone());
+        } else {
+            panic!("{}", ERROR_PROCESSING_WS_FRAME_TEXT);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_get_ws_chat_ews_msg_webhook() {
+        let (profile_vec, _session_vec) = UserOrmTest::users(&[USER]);
+        let stream1_id = ChatMessageOrmTest::stream_ids().get(0).unwrap().clone(); // live: true
+        let user1_id = profile_vec.get(0).unwrap().id;
+        // The webhook of the owner of the stream is sent to the local HTTP receiver.
+        let receiver = ReceiverTest::start(&[200]);
+        let webhook = Webhook::new(WEBHOOK_ID, user1_id, &receiver.url, "secret1", &[WebhookEvent::ChatMessage]);
+        let webhook_orm = WebhookOrmApp::create(&[webhook]).with_stream_owner(stream1_id, user1_id);
+        // Create a test server without listening on a port.
+        let mut srv = actix_test::start(move || {
+            // The events are sent to the webhook dispatcher of the system of the test server.
+            let webhook_orm = Arc::new(webhook_orm.clone());
+            WebhookDispatcher::from_registry().do_send(SetWebhookOrm(webhook_orm, config_hook::tests::get_config()));
+            let data_u = UserOrmTest::users(&[USER]);
+            let data_cm = ChatMessageOrmTest::chat_messages(2);
+            App::new()
+                .service(get_ws_chat)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(config_chat::tests::cfg_config_chat(config_chat::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
+        });
+
+        // Open a websocket connection to the test server.
+        let mut framed1 = srv.ws_at(URL_WS).await.unwrap();
+
+        let member1 = profile_vec.get(0).unwrap().nickname.clone();
+        let token1 = config_jwt::tests::get_token(user1_id);
+
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"join\": {}, \"access\": \"{}\" }}", stream1_id, token1).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&JoinEWS { 
+            join: stream1_id, member: member1.clone(), count: 1, is_owner: Some(true), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
+        assert_eq!(item1, FrameText(Bytes::from(value)));
+
+        // -- Test: 1. The message of the chat is sent to the webhook of the owner of the stream. --
+        let msg_text = MessageText("{ \"msg\": \"text_1\" }".into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let FrameText(buf) = item1 else {
+            panic!("{}", ERROR_PROCESSING_WS_FRAME_TEXT);
+        };
+        let msg_ews_res: MsgEWS = from_slice(&buf).expect(MSG_FAILED_DESER);
+        // The delivery is saved and sent in the background.
+        rt::time::sleep(WAIT_WEBHOOK).await;
+
+        let requests = receiver.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].headers.get("x-verbena-event").unwrap(), "chat.message");
+        let payload: WebhookPayload = serde_json::from_str(&requests[0].body).expect(MSG_FAILED_DESER);
+        assert_eq!(payload.event, WebhookEvent::ChatMessage);
+        let chat_message_dto: ChatMessageDto = serde_json::from_value(payload.data).expect(MSG_FAILED_DESER);
+        assert_eq!(chat_message_dto.id, msg_ews_res.id);
+        assert_eq!(chat_message_dto.member, member1);
+        assert_eq!(chat_message_dto.msg, "text_1");
+    }
+
+    // ** ews_reaction **
+
+    #[actix_web::test]
+    async fn test_get_ws_chat_ews_reaction() {
+        let (profile_vec, _session_vec) = UserOrmTest::users(&[USER, USER, USER, USER]);
+        let user1_id = profile_vec.get(0).unwrap().id;
+        let user2_id = profile_vec.get(1).unwrap().id;
+        let user4_id = profile_vec.get(3).unwrap().id;
+        let mut data_cm = ChatMessageOrmTest::chat_messages(2);
+        let ch_msg1_id = data_cm.0.get(0).unwrap().id;
+        let ch_msg_id_wrong = data_cm.0.last().unwrap().id + 1;
+        // User2 has added the reaction "👍" to message 1, and user1 has added the reaction "🔥".
+        data_cm.4.push(ReactionData::new(ch_msg1_id, user2_id, "👍"));
+        data_cm.4.push(ReactionData::new(ch_msg1_id, user1_id, "🔥"));
+        let data_cm2 = data_cm.clone();
+        // Create a test server without listening on a port.
+        let mut srv = actix_test::start(move || {
+            let mut data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
+            // Add session (num_token) for user4.
+            data_u.1.push(Session::new(4, user4_id, config_jwt::tests::get_num_token(user4_id)));
+            App::new()
+                .service(get_ws_chat)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(config_chat::tests::cfg_config_chat(config_chat::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm2.clone()))
+        });
+
+        let stream1_id = ChatMessageOrmTest::stream_ids().get(0).unwrap().clone(); // live: true
+
+        // Open a websocket connection to the test server.
+        let mut framed1 = srv.ws_at(URL_WS).await.unwrap();
+
+        // -- Test: 1. "'reaction' parameter not defined" --
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"reaction\": \"\", \"id\": {} }}", ch_msg1_id).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let err400 = get_err400(&format!("{}; name: '{}'", err::MSG_PARAMETER_NOT_DEFINED, "reaction"));
+        assert_eq!(item, FrameText(Bytes::from(to_string(&err400).unwrap()))); // 400:BadRequest
+
+        // -- Test: 2. "'reaction' parameter is too long" --
+        let reaction = "a".repeat((REACTION_MAX + 1).into());
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"reaction\": \"{}\", \"id\": {} }}", reaction, ch_msg1_id).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let err406 = get_err406(&format!("{}; name: '{}', max_length: {}", err::MSG_PARAMETER_UNACCEPTABLE, "reaction", REACTION_MAX));
+        assert_eq!(item, FrameText(Bytes::from(to_string(&err406).unwrap()))); // 406:NotAcceptable
+
+        // -- Test: 3. "'id' parameter not defined" --
+        let msg_text = MessageText("{ \"reaction\": \"👍\", \"id\": 0 }".into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let err400 = get_err400(&format!("{}; name: '{}'", err::MSG_PARAMETER_NOT_DEFINED, "id"));
+        assert_eq!(item, FrameText(Bytes::from(to_string(&err400).unwrap()))); // 400:BadRequest
+
+        // -- Test: 4. "There was no 'join' command." --
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"reaction\": \"👍\", \"id\": {} }}", ch_msg1_id).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let err406 = get_err406(err::MSG_THERE_WAS_NO_JOIN);
+        assert_eq!(item, FrameText(Bytes::from(to_string(&err406).unwrap()))); // 406:NotAcceptable
+
+        // == Join user4 authorized (is blocked). ==
+
+        let member4 = profile_vec.get(3).unwrap().nickname.clone();
+        let token4 = config_jwt::tests::get_token(user4_id);
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"join\": {}, \"access\": \"{}\" }}", stream1_id, token4).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&JoinEWS {
+            join: stream1_id, member: member4.clone(), count: 1, is_owner: Some(false), is_blocked: Some(true), is_moderator: Some(false), pins: None }).unwrap();
+        assert_eq!(item1, FrameText(Bytes::from(value)));
+
+        // -- Test: 5. "Sending "reaction" to authorized users (but blocked)." --
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"reaction\": \"👍\", \"id\": {} }}", ch_msg1_id).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let err403 = get_err403(err::MSG_BLOCK_ON_SEND_MESSAGES);
+        assert_eq!(item1, FrameText(Bytes::from(to_string(&err403).unwrap()))); // 403:Forbidden
+
+        // Open a websocket connection to the test server.
+        let mut framed2 = srv.ws_at(URL_WS).await.unwrap();
+
+        // == Join user1 authorized. ==
+
+        let member1 = profile_vec.get(0).unwrap().nickname.clone();
+        let token1 = config_jwt::tests::get_token(user1_id);
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"join\": {}, \"access\": \"{}\" }}", stream1_id, token1).into());
+        framed2.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&JoinEWS {
+            join: stream1_id, member: member1.clone(), count: 2, is_owner: Some(true), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
+        assert_eq!(item2, FrameText(Bytes::from(value)));
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&JoinEWS {
+            join: stream1_id, member: member1.clone(), count: 2, is_owner: None, is_blocked: None, is_moderator: None, pins: None }).unwrap();
+        assert_eq!(item1, FrameText(Bytes::from(value)));
+
+        // -- Test: 6. "The message was not found." --
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"reaction\": \"👍\", \"id\": {} }}", ch_msg_id_wrong).into());
+        framed2.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let err404 = get_err404(&format!("{}; id: {}", err::MSG_CHAT_MESSAGE_NOT_FOUND, ch_msg_id_wrong));
+        assert_eq!(item2, FrameText(Bytes::from(to_string(&err404).unwrap()))); // 404:NotFound
+
+        // -- Test: 7. Add a reaction to the message (all chat members receive the change). --
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"reaction\": \"👍\", \"id\": {} }}", ch_msg1_id).into());
+        framed2.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&ReactionEWS {
+            reaction: "👍".into(), id: ch_msg1_id, count: 2, delta: 1, member: member1.clone() }).unwrap();
+        let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        assert_eq!(item2, FrameText(Bytes::from(value.clone())));
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        assert_eq!(item1, FrameText(Bytes::from(value)));
+
+        // -- Test: 8. Remove a reaction that the user did not add (nothing is sent). --
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"reaction\": \"🎉\", \"id\": {}, \"isRmv\": true }}", ch_msg1_id).into());
+        framed2.send(msg_text).await.unwrap(); // Send a message to a websocket.
+
+        // -- Test: 9. Remove the user's reaction from the message (all chat members receive the change). --
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"reaction\": \"🔥\", \"id\": {}, \"isRmv\": true }}", ch_msg1_id).into());
+        framed2.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&ReactionEWS {
+            reaction: "🔥".into(), id: ch_msg1_id, count: 0, delta: -1, member: member1.clone() }).unwrap();
+        let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        assert_eq!(item2, FrameText(Bytes::from(value.clone())));
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        assert_eq!(item1, FrameText(Bytes::from(value)));
+    }
+
+    #[actix_web::test]
+    async fn test_get_ws_chat_ews_reaction_emoji_and_limit() {
+        let (profile_vec, _session_vec) = UserOrmTest::users(&[USER]);
+        let user1_id = profile_vec.get(0).unwrap().id;
+        let mut data_cm = ChatMessageOrmTest::chat_messages(2);
+        let ch_msg1_id = data_cm.0.get(0).unwrap().id;
+        // User1 has already added the maximum number of reactions to message 1.
+        let reactions = ["🔥", "👍", "🎉"];
+        assert_eq!(reactions.len(), usize::from(REACTIONS_PER_USER_MAX));
+        for reaction in reactions {
+            data_cm.4.push(ReactionData::new(ch_msg1_id, user1_id, reaction));
+        }
+        let data_cm2 = data_cm.clone();
+        // Create a test server without listening on a port.
+        let mut srv = actix_test::start(move || {
+            let data_u = UserOrmTest::users(&[USER]);
+            App::new()
+                .service(get_ws_chat)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(config_chat::tests::cfg_config_chat(config_chat::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm2.clone()))
+        });
+
+        let stream1_id = ChatMessageOrmTest::stream_ids().get(0).unwrap().clone(); // live: true
+
+        // Open a websocket connection to the test server.
+        let mut framed1 = srv.ws_at(URL_WS).await.unwrap();
+
+        // == Join user1 authorized. ==
+
+        let member1 = profile_vec.get(0).unwrap().nickname.clone();
+        let token1 = config_jwt::tests::get_token(user1_id);
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"join\": {}, \"access\": \"{}\" }}", stream1_id, token1).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&JoinEWS {
+            join: stream1_id, member: member1.clone(), count: 1, is_owner: Some(true), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
+        assert_eq!(item1, FrameText(Bytes::from(value)));
+
+        // -- Test: 1. "'reaction' parameter is not a single emoji" --
+        let err406 = get_err406(&format!("{}; name: '{}'", err::MSG_PARAMETER_UNACCEPTABLE, "reaction"));
+        for reaction in ["a", "<b>", "👍👍", "👍 ok"] {
+            #[rustfmt::skip]
+            let msg_text = MessageText(format!("{{ \"reaction\": \"{}\", \"id\": {} }}", reaction, ch_msg1_id).into());
+            framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+            let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+            assert_eq!(item1, FrameText(Bytes::from(to_string(&err406).unwrap()))); // 406:NotAcceptable
+        }
+
+        // -- Test: 2. "The user already has the maximum number of reactions to the message." --
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"reaction\": \"😀\", \"id\": {} }}", ch_msg1_id).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let err406 = get_err406(&format!("{}; max: {}", err::MSG_REACTION_LIMIT_REACHED, REACTIONS_PER_USER_MAX));
+        assert_eq!(item1, FrameText(Bytes::from(to_string(&err406).unwrap()))); // 406:NotAcceptable
+
+        // -- Test: 3. At the limit, the user can still remove their reaction. --
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"reaction\": \"🔥\", \"id\": {}, \"isRmv\": true }}", ch_msg1_id).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&ReactionEWS {
+            reaction: "🔥".into(), id: ch_msg1_id, count: 0, delta: -1, member: member1.clone() }).unwrap();
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        assert_eq!(item1, FrameText(Bytes::from(value)));
+
+        // -- Test: 4. At the limit, re-adding an existing reaction is not an error. --
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"reaction\": \"👍\", \"id\": {} }}", ch_msg1_id).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        // The number of reactions has not changed, so nothing is sent.
+        let opt_item1 = rt::time::timeout(WAIT_WEBHOOK, framed1.next()).await;
+        assert!(opt_item1.is_err());
+    }
+
+    // ** ews_mention **
+
+    #[actix_web::test]
+    async fn test_get_ws_chat_ews_mention() {
+        let (profile_vec, _session_vec) = UserOrmTest::users(&[USER, USER, USER, USER]);
+        let user1_id = profile_vec.get(0).unwrap().id;
+        let user2_id = profile_vec.get(1).unwrap().id;
+        let data_cm = ChatMessageOrmTest::chat_messages(2);
+        let ch_msg_id = data_cm.0.last().unwrap().id + 1;
+        // Create a test server without listening on a port.
+        let mut srv = actix_test::start(move || {
+            let mut data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
+            // Add session (num_token) for user2.
+            data_u.1.push(Session::new(2, user2_id, config_jwt::tests::get_num_token(user2_id)));
+            App::new()
+                .service(get_ws_chat)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(config_chat::tests::cfg_config_chat(config_chat::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm.clone()))
+        });
+
+        let stream1_id = ChatMessageOrmTest::stream_ids().get(0).unwrap().clone(); // live: true
+        let stream2_id = ChatMessageOrmTest::stream_ids().get(1).unwrap().clone(); // live: true
+
+        // Open a websocket connection to the test server.
+        let mut framed1 = srv.ws_at(URL_WS).await.unwrap();
+
+        // == Join user1 authorized (stream1). ==
+
+        let member1 = profile_vec.get(0).unwrap().nickname.clone();
+        let token1 = config_jwt::tests::get_token(user1_id);
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"join\": {}, \"access\": \"{}\" }}", stream1_id, token1).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&JoinEWS {
+            join: stream1_id, member: member1.clone(), count: 1, is_owner: Some(true), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
+        assert_eq!(item1, FrameText(Bytes::from(value)));
+
+        // Open a websocket connection to the test server.
+        let mut framed2 = srv.ws_at(URL_WS).await.unwrap();
+
+        // == Join user2 authorized (stream2 - another room). ==
+
+        let member2 = profile_vec.get(1).unwrap().nickname.clone();
+        let token2 = config_jwt::tests::get_token(user2_id);
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"join\": {}, \"access\": \"{}\" }}", stream2_id, token2).into());
+        framed2.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&JoinEWS {
+            join: stream2_id, member: member2.clone(), count: 1, is_owner: Some(true), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
+        assert_eq!(item2, FrameText(Bytes::from(value)));
+
+        // -- Test: 1. The mentioned user receives a notification in a session of another room. --
+        let msg = format!("Hi @{}", member2);
+        let msg_text = MessageText(format!("{{ \"msg\": \"{}\" }}", msg).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        if let FrameText(buf) = item1 {
+            let msg_ews_res: MsgEWS = from_slice(&buf).expect(MSG_FAILED_DESER);
+            assert_eq!(msg_ews_res.msg, msg);
+            assert_eq!(msg_ews_res.id, ch_msg_id);
+            assert_eq!(msg_ews_res.member, member1);
+        } else {
+            panic!("{}", ERROR_PROCESSING_WS_FRAME_TEXT);
+        }
+        let date = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+        let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        if let FrameText(buf) = item2 {
+            let mention_ews_res: MentionEWS = from_slice(&buf).expect(MSG_FAILED_DESER);
+            assert_eq!(mention_ews_res.mention, ch_msg_id);
+            assert_eq!(mention_ews_res.stream_id, stream1_id);
+            assert_eq!(mention_ews_res.member, member1);
+            assert_eq!(mention_ews_res.msg, msg);
+            assert_eq!(mention_ews_res.date[..19], date[..19]);
+        } else {
+            panic!("{}", ERROR_PROCESSING_WS_FRAME_TEXT);
+        }
+    }
+    // ** ews_pin, ews_unpin **
+
+    #[actix_web::test]
+    async fn test_get_ws_chat_ews_pin() {
+        let (profile_vec, _session_vec) = UserOrmTest::users(&[USER, USER, USER, USER]);
+        let user1_id = profile_vec.get(0).unwrap().id;
+        let user4_id = profile_vec.get(3).unwrap().id;
+        let member1 = profile_vec.get(0).unwrap().nickname.clone();
+        let stream1_id = ChatMessageOrmTest::stream_ids().get(0).unwrap().clone(); // live: true
+        let mut data_cm = ChatMessageOrmTest::chat_messages(2);
+        let ch_msg1 = data_cm.0.get(0).unwrap().clone();
+        let ch_msg_id_wrong = data_cm.0.last().unwrap().id + 1;
+        // User1 (the owner of the stream) has pinned the announcement.
+        let chat_pin1 = ChatPin::new(CHAT_PIN_ID, stream1_id, None, &member1, "announcement_1", Utc::now());
+        data_cm.6.push(chat_pin1.clone());
+        let pins = Some(vec![PinEWS::from(chat_pin1.clone())]);
+        let data_cm2 = data_cm.clone();
+        // Create a test server without listening on a port.
+        let mut srv = actix_test::start(move || {
+            let mut data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
+            // Add session (num_token) for user4.
+            data_u.1.push(Session::new(4, user4_id, config_jwt::tests::get_num_token(user4_id)));
+            App::new()
+                .service(get_ws_chat)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(config_chat::tests::cfg_config_chat(config_chat::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm2.clone()))
+        });
+
+        // Open a websocket connection to the test server.
+        let mut framed1 = srv.ws_at(URL_WS).await.unwrap();
+
+        // -- Test: 1. "'pin' parameter not defined" --
+        let msg_text = MessageText("{ \"pin\": \"\" }".into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let err400 = get_err400(&format!("{}; name: '{}'", err::MSG_PARAMETER_NOT_DEFINED, "pin"));
+        assert_eq!(item, FrameText(Bytes::from(to_string(&err400).unwrap()))); // 400:BadRequest
+
+        // -- Test: 2. "'pin' parameter is too long" --
+        let pin = "a".repeat((MESSAGE_MAX + 1).into());
+        let msg_text = MessageText(format!("{{ \"pin\": \"{}\" }}", pin).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let err406 = get_err406(&format!("{}; name: '{}', max_length: {}", err::MSG_PARAMETER_UNACCEPTABLE, "pin", MESSAGE_MAX));
+        assert_eq!(item, FrameText(Bytes::from(to_string(&err406).unwrap()))); // 406:NotAcceptable
+
+        // -- Test: 3. "'msgId' parameter not defined" --
+        let msg_text = MessageText("{ \"pin\": \"\", \"msgId\": 0 }".into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let err400 = get_err400(&format!("{}; name: '{}'", err::MSG_PARAMETER_NOT_DEFINED, "msgId"));
+        assert_eq!(item, FrameText(Bytes::from(to_string(&err400).unwrap()))); // 400:BadRequest
+
+        // -- Test: 4. "'unpin' parameter not defined" --
+        let msg_text = MessageText("{ \"unpin\": 0 }".into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let err400 = get_err400(&format!("{}; name: '{}'", err::MSG_PARAMETER_NOT_DEFINED, "unpin"));
+        assert_eq!(item, FrameText(Bytes::from(to_string(&err400).unwrap()))); // 400:BadRequest
+
+        // -- Test: 5. "There was no 'join' command." --
+        let msg_text = MessageText("{ \"pin\": \"announcement_5\" }".into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let err406 = get_err406(err::MSG_THERE_WAS_NO_JOIN);
+        assert_eq!(item, FrameText(Bytes::from(to_string(&err406).unwrap()))); // 406:NotAcceptable
+
+        let msg_text = MessageText(format!("{{ \"unpin\": {} }}", chat_pin1.id).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let err406 = get_err406(err::MSG_THERE_WAS_NO_JOIN);
+        assert_eq!(item, FrameText(Bytes::from(to_string(&err406).unwrap()))); // 406:NotAcceptable
+
+        // == Join user4 authorized (not the owner, is blocked). The reply contains the pins of the chat. ==
+
+        let member4 = profile_vec.get(3).unwrap().nickname.clone();
+        let token4 = config_jwt::tests::get_token(user4_id);
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"join\": {}, \"access\": \"{}\" }}", stream1_id, token4).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&JoinEWS {
+            join: stream1_id, member: member4.clone(), count: 1, is_owner: Some(false), is_blocked: Some(true), is_moderator: Some(false), pins: pins.clone() }).unwrap();
+        assert_eq!(item1, FrameText(Bytes::from(value)));
+
+        // -- Test: 6. "Pinning and unpinning is not available to a user who is not the owner or moderator." --
+        let msg_text = MessageText("{ \"pin\": \"announcement_6\" }".into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let err403 = get_err403(err::MSG_STREAM_OWNER_RIGHTS_MISSING);
+        assert_eq!(item1, FrameText(Bytes::from(to_string(&err403).unwrap()))); // 403:Forbidden
+
+        let msg_text = MessageText(format!("{{ \"unpin\": {} }}", chat_pin1.id).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let err403 = get_err403(err::MSG_STREAM_OWNER_RIGHTS_MISSING);
+        assert_eq!(item1, FrameText(Bytes::from(to_string(&err403).unwrap()))); // 403:Forbidden
+
+        // Open a websocket connection to the test server.
+        let mut framed2 = srv.ws_at(URL_WS).await.unwrap();
+
+        // == Join user1 authorized (the owner of the stream). ==
+
+        let token1 = config_jwt::tests::get_token(user1_id);
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"join\": {}, \"access\": \"{}\" }}", stream1_id, token1).into());
+        framed2.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&JoinEWS {
+            join: stream1_id, member: member1.clone(), count: 2, is_owner: Some(true), is_blocked: Some(false), is_moderator: Some(false), pins: pins.clone() }).unwrap();
+        assert_eq!(item2, FrameText(Bytes::from(value)));
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&JoinEWS {
+            join: stream1_id, member: member1.clone(), count: 2, is_owner: None, is_blocked: None, is_moderator: None, pins: None }).unwrap();
+        assert_eq!(item1, FrameText(Bytes::from(value)));
+
+        // -- Test: 7. "The message was not found." --
+        let msg_text = MessageText(format!("{{ \"pin\": \"\", \"msgId\": {} }}", ch_msg_id_wrong).into());
+        framed2.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let err404 = get_err404(&format!("{}; id: {}", err::MSG_CHAT_MESSAGE_NOT_FOUND, ch_msg_id_wrong));
+        assert_eq!(item2, FrameText(Bytes::from(to_string(&err404).unwrap()))); // 404:NotFound
+
+        // -- Test: 8. "The pin was not found." --
+        let pin_id_wrong = chat_pin1.id + 1;
+        let msg_text = MessageText(format!("{{ \"unpin\": {} }}", pin_id_wrong).into());
+        framed2.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let err404 = get_err404(&format!("{}; id: {}", err::MSG_CHAT_PIN_NOT_FOUND, pin_id_wrong));
+        assert_eq!(item2, FrameText(Bytes::from(to_string(&err404).unwrap()))); // 404:NotFound
+
+        // -- Test: 9. Pin the announcement (all chat members receive the pin). --
+        let msg_text = MessageText("{ \"pin\": \"announcement_9\" }".into());
+        framed2.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        #[rustfmt::skip]
+        let pin_ews = PinEWS {
+            pin: "announcement_9".into(), id: CHAT_PIN_ID + 1, member: member1.clone(), date: "".into(), msg_id: None };
+        let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        for item in [item2, item1] {
+            if let FrameText(buf) = item {
+                let pin_ews_res: PinEWS = from_slice(&buf).expect(MSG_FAILED_DESER);
+                assert_eq!(pin_ews_res.pin, pin_ews.pin);
+                assert_eq!(pin_ews_res.id, pin_ews.id);
+                assert_eq!(pin_ews_res.member, pin_ews.member);
+                assert_eq!(pin_ews_res.msg_id, pin_ews.msg_id);
+            } else {
+                panic!("{}", ERROR_PROCESSING_WS_FRAME_TEXT);
+            }
+        }
+
+        // -- Test: 10. Pin the message (all chat members receive the pin with the author and text of the message). --
+        let msg_text = MessageText(format!("{{ \"pin\": \"\", \"msgId\": {} }}", ch_msg1.id).into());
+        framed2.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        #[rustfmt::skip]
+        let pin_ews = PinEWS {
+            pin: ch_msg1.msg.clone().unwrap(), id: CHAT_PIN_ID + 1, member: ch_msg1.user_name.clone(), date: "".into(), msg_id: Some(ch_msg1.id) };
+        let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        for item in [item2, item1] {
+            if let FrameText(buf) = item {
+                let pin_ews_res: PinEWS = from_slice(&buf).expect(MSG_FAILED_DESER);
+                assert_eq!(pin_ews_res.pin, pin_ews.pin);
+                assert_eq!(pin_ews_res.id, pin_ews.id);
+                assert_eq!(pin_ews_res.member, pin_ews.member);
+                assert_eq!(pin_ews_res.msg_id, pin_ews.msg_id);
+            } else {
+                panic!("{}", ERROR_PROCESSING_WS_FRAME_TEXT);
+            }
+        }
+
+        // -- Test: 11. Unpin the announcement (all chat members receive the message). --
+        let msg_text = MessageText(format!("{{ \"unpin\": {} }}", chat_pin1.id).into());
+        framed2.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let value = to_string(&UnpinEWS { unpin: chat_pin1.id }).unwrap();
+        let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        assert_eq!(item2, FrameText(Bytes::from(value.clone())));
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        assert_eq!(item1, FrameText(Bytes::from(value)));
+    }
+
+    // ** ews_join with backlog **
+
+    #[actix_web::test]
+    async fn test_get_ws_chat_ews_join_backlog() {
+        let (profile_vec, _session_vec) = UserOrmTest::users(&[USER, USER, USER, USER]);
+        let user1_id = profile_vec.get(0).unwrap().id;
+        let member1 = profile_vec.get(0).unwrap().nickname.clone();
+        let stream1_id = ChatMessageOrmTest::stream_ids().get(0).unwrap().clone(); // live: true
+        let mut data_cm = ChatMessageOrmTest::chat_messages(3);
+        // The second message was removed.
+        let ch_msg2 = data_cm.0.get_mut(1).unwrap();
+        ch_msg2.date_removed = Some(ch_msg2.date_created + Duration::seconds(30));
+        let data_cm2 = data_cm.clone();
+        // Create a test server without listening on a port.
+        let mut srv = actix_test::start(move || {
+            let data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
+            App::new()
+                .service(get_ws_chat)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(config_chat::tests::cfg_config_chat(config_chat::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm2.clone()))
+        });
+
+        // Open a websocket connection to the test server.
+        let mut framed1 = srv.ws_at(URL_WS).await.unwrap();
+        let token1 = config_jwt::tests::get_token(user1_id);
+
+        // -- Test: 1. "'backlog' parameter is out of range" --
+        for backlog in [-1, BACKLOG_MAX + 1] {
+            #[rustfmt::skip]
+            let msg_text = MessageText(format!("{{ \"join\": {}, \"access\": \"{}\", \"backlog\": {} }}", stream1_id, token1, backlog).into());
+            framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+            let item = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+            #[rustfmt::skip]
+            let err406 = get_err406(&format!("{}; name: '{}', value: {}", err::MSG_PARAMETER_UNACCEPTABLE, "backlog", backlog));
+            assert_eq!(item, FrameText(Bytes::from(to_string(&err406).unwrap()))); // 406:NotAcceptable
+        }
+
+        // == Join user1 authorized with a backlog of 2 messages. ==
+
+        #[rustfmt::skip]
+        let msg_text = MessageText(format!("{{ \"join\": {}, \"access\": \"{}\", \"backlog\": 2 }}", stream1_id, token1).into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        #[rustfmt::skip]
+        let value = to_string(&JoinEWS {
+            join: stream1_id, member: member1.clone(), count: 1, is_owner: Some(true), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
+        assert_eq!(item1, FrameText(Bytes::from(value)));
+        // The recent messages follow the reply to the "join" command (in ascending order of date).
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        if let FrameText(buf) = item1 {
+            let backlog_ews: BacklogEWS = from_slice(&buf).expect(MSG_FAILED_DESER);
+            assert_eq!(backlog_ews.backlog.len(), 2);
+            let msg_ews2 = backlog_ews.backlog.get(0).unwrap();
+            assert_eq!(msg_ews2.id, data_cm.0.get(1).unwrap().id);
+            assert_eq!(msg_ews2.msg, ""); // The text of the removed message is not sent.
+            assert!(msg_ews2.date_rmv.is_some());
+            let msg_ews3 = backlog_ews.backlog.get(1).unwrap();
+            let ch_msg3 = data_cm.0.get(2).unwrap();
+            assert_eq!(msg_ews3.id, ch_msg3.id);
+            assert_eq!(msg_ews3.msg, ch_msg3.msg.clone().unwrap());
+            assert_eq!(msg_ews3.date_rmv, None);
+        } else {
+            panic!("{}", ERROR_PROCESSING_WS_FRAME_TEXT);
+        }
+
+        // -- Test: 2. "The live messages come after the backlog." --
+        let msg_text = MessageText("{ \"msg\": \"message 4\" }".into());
+        framed1.send(msg_text).await.unwrap(); // Send a message to a websocket.
+        let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
+        if let FrameText(buf) = item1 {
+            let msg_ews: MsgEWS = from_slice(&buf).expect(MSG_FAILED_DESER);
+            assert_eq!(msg_ews.msg, "message 4");
+        } else {
+            panic!("{}", ERROR_PROCESSING_WS_FRAME_TEXT);
+        }
+    }
+}
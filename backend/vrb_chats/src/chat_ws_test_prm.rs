@@ -6,6 +6,7 @@ mod tests {
     use serde_json::to_string;
     use vrb_authent::{
         config_jwt,
+        user_models::Session,
         user_orm::tests::{USER, UserOrmTest},
     };
     use vrb_common::err;
@@ -15,6 +16,7 @@ mod tests {
         chat_message_orm::tests::ChatMessageOrmTest,
         chat_ws_controller::get_ws_chat,
         chat_ws_tools::{get_err400, get_err403, get_err406},
+        config_chat,
     };
 
     const URL_WS: &str = "/ws";
@@ -29,11 +31,12 @@ mod tests {
             let mut data_u = UserOrmTest::users(&[USER, USER, USER, USER]);
             let user4_id = data_u.0.get(3).unwrap().id;
             // Add session (num_token) for user4.
-            data_u.1.get_mut(3).unwrap().num_token = Some(config_jwt::tests::get_num_token(user4_id));
+            data_u.1.push(Session::new(4, user4_id, config_jwt::tests::get_num_token(user4_id)));
             let data_cm = ChatMessageOrmTest::chat_messages(0);
             App::new()
                 .service(get_ws_chat)
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(config_chat::tests::cfg_config_chat(config_chat::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
         });
@@ -132,7 +135,7 @@ mod tests {
         let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS { 
-            join: stream1_id, member: member4.clone(), count: 1, is_owner: Some(false), is_blocked: Some(true) }).unwrap();
+            join: stream1_id, member: member4.clone(), count: 1, is_owner: Some(false), is_blocked: Some(true), is_moderator: Some(false), pins: None }).unwrap();
         assert_eq!(item1, FrameText(Bytes::from(value)));
         
         // Test: 1.5. ews_prm_bool: "There is a block on sending messages."
@@ -163,11 +166,12 @@ mod tests {
             let mut data_u = UserOrmTest::users(&[USER, USER]);
             let user2_id = data_u.0.get(1).unwrap().id;
             // Add session (num_token) for user2.
-            data_u.1.get_mut(1).unwrap().num_token = Some(config_jwt::tests::get_num_token(user2_id));
+            data_u.1.push(Session::new(2, user2_id, config_jwt::tests::get_num_token(user2_id)));
             let data_cm = ChatMessageOrmTest::chat_messages(0);
             App::new()
                 .service(get_ws_chat)
                 .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(config_chat::tests::cfg_config_chat(config_chat::tests::get_config()))
                 .configure(UserOrmTest::cfg_user_orm(data_u))
                 .configure(ChatMessageOrmTest::cfg_chat_message_orm(data_cm))
         });
@@ -190,7 +194,7 @@ mod tests {
         let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS { 
-            join: stream1_id, member: member1.clone(), count: 1, is_owner: Some(true), is_blocked: Some(false) }).unwrap();
+            join: stream1_id, member: member1.clone(), count: 1, is_owner: Some(true), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
         assert_eq!(item1, FrameText(Bytes::from(value)));
 
         // Open a websocket connection to the test server.
@@ -207,13 +211,13 @@ mod tests {
         let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS { 
-            join: stream1_id, member: member2.clone(), count: 2, is_owner: Some(false), is_blocked: Some(false) }).unwrap();
+            join: stream1_id, member: member2.clone(), count: 2, is_owner: Some(false), is_blocked: Some(false), is_moderator: Some(false), pins: None }).unwrap();
         assert_eq!(item2, FrameText(Bytes::from(value)));
         // Message about join user 2.
         let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS {
-            join: stream1_id, member: member2.clone(), count: 2, is_owner: None, is_blocked: None }).unwrap();
+            join: stream1_id, member: member2.clone(), count: 2, is_owner: None, is_blocked: None, is_moderator: None, pins: None }).unwrap();
         assert_eq!(item1, FrameText(Bytes::from(value)));
         
         // Open a websocket connection to the test server.
@@ -226,13 +230,13 @@ mod tests {
         let item3 = framed3.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS {
-            join: stream1_id, member: "".into(), count: 3, is_owner: Some(false), is_blocked: Some(true) }).unwrap();
+            join: stream1_id, member: "".into(), count: 3, is_owner: Some(false), is_blocked: Some(true), is_moderator: Some(false), pins: None }).unwrap();
         assert_eq!(item3, FrameText(Bytes::from(value)));
         // Message to user1 about user3 joining.
         let item1 = framed1.next().await.unwrap().unwrap(); // Receive a message from a websocket.
         #[rustfmt::skip]
         let value = to_string(&JoinEWS {
-            join: stream1_id, member: "".into(), count: 3, is_owner: None, is_blocked: None }).unwrap();
+            join: stream1_id, member: "".into(), count: 3, is_owner: None, is_blocked: None, is_moderator: None, pins: None }).unwrap();
         assert_eq!(item1, FrameText(Bytes::from(value.clone())));
         // Message to user2 about user3 joining.
         let item2 = framed2.next().await.unwrap().unwrap(); // Receive a message from a websocket.
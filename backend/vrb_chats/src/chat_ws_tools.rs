@@ -1,7 +1,7 @@
 use actix_web::http::StatusCode;
 use vrb_common::{api_error::code_to_str, err};
 
-use crate::chat_event_ws::ErrEWS;
+use crate::{chat_event_ws::ErrEWS, chat_message_models::is_emoji};
 
 #[rustfmt::skip]
 pub fn get_err400(message: &str) -> ErrEWS {
@@ -28,6 +28,10 @@ pub fn get_err409(message: &str) -> ErrEWS {
     ErrEWS { err: 409, code: code_to_str(StatusCode::CONFLICT), message: message.to_owned() }
 }
 #[rustfmt::skip]
+pub fn get_err429(message: &str) -> ErrEWS {
+    ErrEWS { err: 429, code: code_to_str(StatusCode::TOO_MANY_REQUESTS), message: message.to_owned() }
+}
+#[rustfmt::skip]
 pub fn get_err500(message: &str) -> ErrEWS {
     ErrEWS { err: 500, code: code_to_str(StatusCode::INTERNAL_SERVER_ERROR), message: message.to_owned() }
 }
@@ -44,6 +48,27 @@ pub fn check_is_required<T>(value: Option<T>, name: &str) -> Result<(), ErrEWS>
 pub fn check_is_greater_than(value: i32, limit: i32, name: &str) -> Result<(), ErrEWS> {
     if value <= limit { Err(get_err400(&format!("{}; name: '{}'", err::MSG_PARAMETER_NOT_DEFINED, name))) } else { Ok(()) }
 }
+// Check if the value is within the allowed range.
+pub fn check_is_in_range(value: i32, min: i32, max: i32, name: &str) -> Result<(), ErrEWS> {
+    if value < min || max < value {
+        Err(get_err406(&format!("{}; name: '{}', value: {}", err::MSG_PARAMETER_UNACCEPTABLE, name, value)))
+    } else {
+        Ok(())
+    }
+}
+// Check if the length of the value does not exceed the maximum.
+pub fn check_is_max_length(value: &str, max: usize, name: &str) -> Result<(), ErrEWS> {
+    if max < value.len() {
+        let message = format!("{}; name: '{}', max_length: {}", err::MSG_PARAMETER_UNACCEPTABLE, name, max);
+        Err(get_err406(&message))
+    } else {
+        Ok(())
+    }
+}
+// Check if the value is a single emoji.
+pub fn check_is_emoji(value: &str, name: &str) -> Result<(), ErrEWS> {
+    if !is_emoji(value) { Err(get_err406(&format!("{}; name: '{}'", err::MSG_PARAMETER_UNACCEPTABLE, name))) } else { Ok(()) }
+}
 // Check if there is an joined room
 pub fn check_is_joined_room(room_id: i32) -> Result<(), ErrEWS> {
     if room_id <= i32::default() { Err(get_err406(err::MSG_THERE_WAS_NO_JOIN)) } else { Ok(()) }
@@ -56,3 +81,7 @@ pub fn check_is_blocked(is_blocked: bool) -> Result<(), ErrEWS> {
 pub fn check_is_owner_room(is_owner: bool) -> Result<(), ErrEWS> {
     if !is_owner { Err(get_err403(err::MSG_STREAM_OWNER_RIGHTS_MISSING)) } else { Ok(()) }
 }
+// Check if the user is the owner of the stream or a chat moderator.
+pub fn check_is_owner_or_moderator(is_owner: bool, is_moderator: bool) -> Result<(), ErrEWS> {
+    if !is_owner && !is_moderator { Err(get_err403(err::MSG_STREAM_OWNER_RIGHTS_MISSING)) } else { Ok(()) }
+}
@@ -0,0 +1,96 @@
+use std::env;
+
+use vrb_common::consts;
+
+// Fan-out of chat events through PostgreSQL LISTEN/NOTIFY.
+pub const FAN_OUT_PG: &str = "pg";
+pub const FAN_OUT_CHANNEL: &str = "verbena_chat";
+pub const FAN_OUT_POLL: &str = "100";
+pub const FAN_OUT_SYNC: &str = "10";
+// Heartbeat of the websocket session.
+pub const HEARTBEAT: &str = "5";
+pub const CLIENT_TIMEOUT: &str = "10";
+
+// Chat Server Properties
+#[derive(Debug, Clone)]
+pub struct ConfigChat {
+    // Fan-out of chat events between several processes of the server. ("" - disabled, "pg" - PostgreSQL LISTEN/NOTIFY)
+    pub chat_fan_out: String,
+    // The name of the notification channel (characters: a-z, 0-9, _).
+    pub chat_fan_out_channel: String,
+    // Interval (in milliseconds) for receiving events from other processes.
+    pub chat_fan_out_poll: u64,
+    // Interval (in seconds) for sending the number of room members to other processes.
+    pub chat_fan_out_sync: u64,
+    // Interval (in seconds) for sending a "ping" to the websocket client (0 - the heartbeat is disabled).
+    pub chat_heartbeat: u64,
+    // Time (in seconds) without any activity of the client, after which the client is disconnected.
+    pub chat_client_timeout: u64,
+}
+
+impl ConfigChat {
+    pub fn init_by_env() -> Self {
+        let fan_out = env::var(consts::CHAT_FAN_OUT).unwrap_or("".to_string()).trim().to_lowercase();
+        #[rustfmt::skip]
+        let chat_fan_out = if fan_out == FAN_OUT_PG { FAN_OUT_PG.to_string() } else { "".to_string() };
+
+        let channel = env::var(consts::CHAT_FAN_OUT_CHANNEL)
+            .unwrap_or(FAN_OUT_CHANNEL.to_string())
+            .trim()
+            .to_lowercase();
+        #[rustfmt::skip]
+        let chat_fan_out_channel = if Self::channel_validate(&channel) { channel } else { FAN_OUT_CHANNEL.to_string() };
+
+        let poll = FAN_OUT_POLL.to_string();
+        let chat_fan_out_poll: u64 = env::var(consts::CHAT_FAN_OUT_POLL).unwrap_or(poll).trim().parse().unwrap();
+
+        let sync = FAN_OUT_SYNC.to_string();
+        let chat_fan_out_sync: u64 = env::var(consts::CHAT_FAN_OUT_SYNC).unwrap_or(sync).trim().parse().unwrap();
+
+        let heartbeat = HEARTBEAT.to_string();
+        let chat_heartbeat: u64 = env::var(consts::CHAT_HEARTBEAT).unwrap_or(heartbeat).trim().parse().unwrap();
+
+        let client_timeout = CLIENT_TIMEOUT.to_string();
+        #[rustfmt::skip]
+        let chat_client_timeout: u64 = env::var(consts::CHAT_CLIENT_TIMEOUT).unwrap_or(client_timeout).trim().parse().unwrap();
+
+        ConfigChat {
+            chat_fan_out,
+            chat_fan_out_channel,
+            chat_fan_out_poll: chat_fan_out_poll.max(1),
+            chat_fan_out_sync: chat_fan_out_sync.max(1),
+            chat_heartbeat,
+            // The client must have time to respond to at least one "ping".
+            chat_client_timeout: chat_client_timeout.max(chat_heartbeat + 1),
+        }
+    }
+
+    fn channel_validate(value: &str) -> bool {
+        !value.is_empty() && value.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    }
+}
+
+#[cfg(all(test, feature = "mockdata"))]
+pub mod tests {
+    use actix_web::web;
+
+    use super::*;
+
+    pub fn get_config() -> ConfigChat {
+        ConfigChat {
+            chat_fan_out: "".to_string(),
+            chat_fan_out_channel: FAN_OUT_CHANNEL.to_string(),
+            chat_fan_out_poll: FAN_OUT_POLL.parse().unwrap(),
+            chat_fan_out_sync: FAN_OUT_SYNC.parse().unwrap(),
+            // The heartbeat is disabled so that "ping" does not interfere with the tests.
+            chat_heartbeat: 0,
+            chat_client_timeout: CLIENT_TIMEOUT.parse().unwrap(),
+        }
+    }
+    pub fn cfg_config_chat(config_chat: ConfigChat) -> impl FnOnce(&mut web::ServiceConfig) {
+        move |config: &mut web::ServiceConfig| {
+            let data_config_chat = web::Data::new(config_chat);
+            config.app_data(web::Data::clone(&data_config_chat));
+        }
+    }
+}
@@ -5,12 +5,16 @@ pub mod chat_message_models;
 pub mod chat_message_orm;
 pub mod chat_msg_blocked_test;
 pub mod chat_msg_test_delete;
+pub mod chat_msg_test_export;
 pub mod chat_msg_test_get;
+pub mod chat_msg_test_mention;
 pub mod chat_msg_test_post_put;
+pub mod chat_msg_test_search;
 pub mod chat_ws_assistant;
 pub mod chat_ws_async_result;
 pub mod chat_ws_blck;
 pub mod chat_ws_controller;
+pub mod chat_ws_fan_out;
 pub mod chat_ws_msg;
 pub mod chat_ws_prm;
 pub mod chat_ws_server;
@@ -19,4 +23,5 @@ pub mod chat_ws_test_base;
 pub mod chat_ws_test_blck;
 pub mod chat_ws_test_msg;
 pub mod chat_ws_test_prm;
-pub mod chat_ws_tools;
\ No newline at end of file
+pub mod chat_ws_tools;
+pub mod config_chat;
\ No newline at end of file
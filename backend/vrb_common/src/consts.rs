@@ -1,3 +1,13 @@
+// ** Section: "Chats" **
+
+// Config CHAT
+pub const CHAT_FAN_OUT: &str = "CHAT_FAN_OUT";
+pub const CHAT_FAN_OUT_CHANNEL: &str = "CHAT_FAN_OUT_CHANNEL";
+pub const CHAT_FAN_OUT_POLL: &str = "CHAT_FAN_OUT_POLL";
+pub const CHAT_FAN_OUT_SYNC: &str = "CHAT_FAN_OUT_SYNC";
+pub const CHAT_HEARTBEAT: &str = "CHAT_HEARTBEAT";
+pub const CHAT_CLIENT_TIMEOUT: &str = "CHAT_CLIENT_TIMEOUT";
+
 // ** Section: "Profiles" **
 
 pub const ALIAS_AVATAR_FILES_DIR: &str = "/avatar";
@@ -28,5 +38,17 @@ pub const STRM_LOGO_VALID_TYPES: &str = "STRM_LOGO_VALID_TYPES";
 pub const STRM_LOGO_EXT: &str = "STRM_LOGO_EXT";
 pub const STRM_LOGO_MAX_WIDTH: &str = "STRM_LOGO_MAX_WIDTH";
 pub const STRM_LOGO_MAX_HEIGHT: &str = "STRM_LOGO_MAX_HEIGHT";
+pub const STRM_SERIES_HORIZON: &str = "STRM_SERIES_HORIZON";
+pub const STRM_SERIES_POLL: &str = "STRM_SERIES_POLL";
+
+// ** Section: "Webhooks" **
+
+// Config HOOK
+pub const HOOK_POLL: &str = "HOOK_POLL";
+pub const HOOK_BATCH: &str = "HOOK_BATCH";
+pub const HOOK_TIMEOUT: &str = "HOOK_TIMEOUT";
+pub const HOOK_MAX_ATTEMPTS: &str = "HOOK_MAX_ATTEMPTS";
+pub const HOOK_BACKOFF: &str = "HOOK_BACKOFF";
+pub const HOOK_ALLOW_PRIVATE: &str = "HOOK_ALLOW_PRIVATE";
 
 // **  **
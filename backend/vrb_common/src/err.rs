@@ -17,6 +17,10 @@ pub const MSG_INVALID_OR_EXPIRED_TOKEN: &str = "invalid_or_expired_token";
 pub const MSG_UNACCEPTABLE_TOKEN_NUM: &str = "unacceptable_token_num";
 // 401(d) Unauthorized - According to "user_id" in the token, the user was not found.
 pub const MSG_UNACCEPTABLE_TOKEN_ID: &str = "unacceptable_token_id";
+// 401(e) Unauthorized - The refresh token has already been used. The session is revoked. (user_authent_controller)
+pub const MSG_REFRESH_TOKEN_REUSED: &str = "refresh_token_reused";
+// 401 Unauthorized - The one-time code (TOTP) or the recovery code is incorrect. (user_authent_controller, user_totp_controller)
+pub const MSG_TOTP_CODE_INCORRECT: &str = "totp_code_incorrect";
 
 // 403 Forbidden - Access denied - insufficient rights (authentication)
 pub const MSG_ACCESS_DENIED: &str = "access_denied";
@@ -24,6 +28,8 @@ pub const MSG_ACCESS_DENIED: &str = "access_denied";
 pub const MSG_BLOCK_ON_SEND_MESSAGES: &str = "block_on_sending_messages";
 // 403 Forbidden - Stream owner rights are missing
 pub const MSG_STREAM_OWNER_RIGHTS_MISSING: &str = "stream_owner_rights_missing";
+// 403 Forbidden - Chat moderator rights are missing
+pub const MSG_MODERATOR_RIGHTS_MISSING: &str = "moderator_rights_missing";
 
 // 404 Not Found - Stream not found.
 pub const MSG_STREAM_NOT_FOUND: &str = "stream_not_found";
@@ -31,6 +37,10 @@ pub const MSG_STREAM_NOT_FOUND: &str = "stream_not_found";
 pub const MSG_USER_NOT_FOUND: &str = "user_not_found";
 // 404 Not Found - ChatMessage not found
 pub const MSG_CHAT_MESSAGE_NOT_FOUND: &str = "chat_message_not_found";
+// 404 Not Found - ChatPin not found
+pub const MSG_CHAT_PIN_NOT_FOUND: &str = "chat_pin_not_found";
+// 404 Not Found - Two-factor authentication (TOTP) has not been set up. (user_totp_controller)
+pub const MSG_TOTP_NOT_FOUND: &str = "totp_not_found";
 
 // 406 Not Acceptable - There is no session for this user. (authentication, user_authent_controller)
 pub const MSG_SESSION_NOT_FOUND: &str = "session_not_found";
@@ -44,6 +54,8 @@ pub const MSG_PARAMETER_UNACCEPTABLE: &str = "parameter_value_unacceptable";
 pub const MSG_THERE_WAS_NO_JOIN: &str = "was_no_join_command";
 // 406 Not Acceptable - There was no 'name' command
 pub const MSG_THERE_WAS_NO_NAME: &str = "was_no_name_command";
+// 406 Not Acceptable - The user already has the maximum number of reactions to the message. (chat_ws_msg)
+pub const MSG_REACTION_LIMIT_REACHED: &str = "reaction_limit_reached";
 
 // 409 Conflict - Error checking hash value.
 pub const MSG_INVALID_HASH: &str = "invalid_hash";
@@ -55,6 +67,8 @@ pub const MSG_NICKNAME_ALREADY_USE: &str = "nickname_already_use";
 pub const MSG_THERE_WAS_ALREADY_JOIN_TO_ROOM: &str = "was_already_join_to_room";
 // 409 Conflict - This stream is not active
 pub const MSG_STREAM_NOT_ACTIVE: &str = "stream_not_active";
+// 409 Conflict - Two-factor authentication (TOTP) is already enabled. (user_totp_controller)
+pub const MSG_TOTP_ALREADY_ENABLED: &str = "totp_already_enabled";
 // 409 Conflict - Error encoding web token.
 pub const MSG_JSON_WEB_TOKEN_ENCODE: &str = "json_web_token_encode";
 
@@ -73,6 +87,11 @@ pub const MSG_NO_FIELDS_TO_UPDATE: &str = "no_fields_to_update";
 // 417 Expectation Failed - One of the optional fields must be present.
 pub const MSG_ONE_OPTIONAL_FIELDS_MUST_PRESENT: &str = "one_optional_fields_must_present";
 
+// 429 Too Many Requests - Too many failed attempts, the action is temporarily blocked. (user_authent_controller, user_recovery_controller, user_registr_controller)
+pub const MSG_TOO_MANY_ATTEMPTS: &str = "too_many_attempts";
+// 429 Too Many Requests - Slow mode is enabled in the chat room, the next message can be sent later. (chat_ws_msg)
+pub const MSG_SLOW_MODE_ENABLED: &str = "slow_mode_enabled";
+
 // 500 Internal Server Error - Error creating password hash. (user_registr_controller, user_registr_controller)
 pub const MSG_ERROR_HASHING_PASSWORD: &str = "error_hashing_password";
 // 500 Internal Server Error - Error uploading file
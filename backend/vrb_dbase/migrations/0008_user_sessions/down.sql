@@ -0,0 +1,40 @@
+-- **
+
+/* Remove the indexes on the "sessions" table. */
+DROP INDEX IF EXISTS idx_sessions_last_seen_at;
+DROP INDEX IF EXISTS uq_idx_sessions_user_id_num_token;
+DROP INDEX IF EXISTS idx_sessions_user_id;
+
+/* Drop the multi-device "sessions" table. */
+DROP TABLE IF EXISTS sessions;
+
+-- **
+
+/* Restore the single-session "sessions" table. */
+CREATE TABLE sessions (
+   user_id INT REFERENCES users(id) ON DELETE CASCADE,
+   num_token INT,
+   PRIMARY KEY (user_id)
+);
+
+INSERT INTO sessions(user_id)
+SELECT u.id FROM users u;
+
+/* Create a function to add a record to the "sessions" table
+ after adding a record to the "users" table. */
+CREATE OR REPLACE FUNCTION fn_aft_ins_user_ins_session() RETURNS TRIGGER AS $$
+BEGIN
+  -- Add a record to the sessions table for the new user.
+  INSERT INTO sessions(user_id)
+  VALUES(new.id);
+
+  RETURN new;
+END;
+$$ LANGUAGE plpgsql;
+
+/* Create a trigger after adding a record to the "users" tab.
+(Automatically add records to the "sessions" tab.) */
+CREATE TRIGGER trg_aft_ins_user_ins_session
+  AFTER INSERT ON users FOR EACH ROW EXECUTE PROCEDURE fn_aft_ins_user_ins_session();
+
+-- **
@@ -0,0 +1,39 @@
+-- Multi-device sessions: one "sessions" entry per logged-in device.
+
+-- **
+
+/* Drop trigger "trg_aft_ins_user_ins_session". (Sessions are now created at login.) */
+DROP TRIGGER IF EXISTS trg_aft_ins_user_ins_session ON users;
+
+/* Remove function "fn_aft_ins_user_ins_session". */
+DROP FUNCTION IF EXISTS fn_aft_ins_user_ins_session;
+
+/* Drop the single-session "sessions" table. */
+DROP TABLE IF EXISTS sessions;
+
+-- **
+
+/* Create "sessions" table. (One entry per device.) */
+CREATE TABLE sessions (
+    id SERIAL PRIMARY KEY NOT NULL,
+    /* Owner id */
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    /* Numeric value packed into the tokens of this session. */
+    num_token INTEGER NOT NULL,
+    /* Device label specified by the client. */
+    device VARCHAR(255) NULL,
+    /* The "User-Agent" header of the login request. */
+    user_agent VARCHAR(255) NULL,
+    /* The IP address of the login request. */
+    ip_address VARCHAR(64) NULL,
+    /* Date and time the session was opened. */
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    /* Date and time of the last request of the session. */
+    last_seen_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+CREATE INDEX idx_sessions_user_id ON sessions(user_id);
+CREATE UNIQUE INDEX uq_idx_sessions_user_id_num_token ON sessions(user_id, num_token);
+CREATE INDEX idx_sessions_last_seen_at ON sessions(last_seen_at);
+
+-- **
@@ -0,0 +1,6 @@
+-- **
+
+/* Drop the "user_totp" table. */
+DROP TABLE IF EXISTS user_totp;
+
+-- **
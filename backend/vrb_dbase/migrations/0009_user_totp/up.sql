@@ -0,0 +1,25 @@
+-- Two-factor authentication (TOTP, RFC 6238) for user accounts.
+
+-- **
+
+/* Create "user_totp" table. (One entry per user.) */
+CREATE TABLE user_totp (
+    /* Owner id */
+    user_id INTEGER PRIMARY KEY NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    /* Secret key (base32). */
+    secret VARCHAR(64) NOT NULL,
+    /* Sign that the enrolment is confirmed with a one-time code. */
+    is_enabled BOOLEAN DEFAULT FALSE NOT NULL,
+    /* Hashes of one-time recovery codes. */
+    recovery_codes TEXT[] DEFAULT '{}' NOT NULL,
+    /* Number of the time period of the last accepted code. (Protection against reuse of the code.) */
+    last_step BIGINT NULL,
+    /* Date and time of the entry creation. */
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    /* Date and time of the last entry change. */
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+SELECT diesel_manage_updated_at('user_totp');
+
+-- **
@@ -0,0 +1,10 @@
+-- **
+
+/* Drop the "user_email_change" table. */
+DROP INDEX IF EXISTS uq_user_email_change_user_id;
+DROP INDEX IF EXISTS idx_user_email_change_final_date_new_email;
+DROP INDEX IF EXISTS idx_user_email_change_final_date;
+
+DROP TABLE IF EXISTS user_email_change;
+
+-- **
@@ -0,0 +1,21 @@
+-- **
+-- Changing the user's email (with confirmation to the new address).
+
+-- **
+
+/* Create "user_email_change" table. (One request per user.) */
+CREATE TABLE user_email_change (
+    id SERIAL PRIMARY KEY NOT NULL,
+    /* Owner id */
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    /* New email address (waiting for confirmation). */
+    new_email VARCHAR(255) NOT NULL,
+    /* Date and time until which the request can be confirmed. */
+    final_date TIMESTAMPTZ NOT NULL
+);
+
+CREATE UNIQUE INDEX uq_user_email_change_user_id ON user_email_change(user_id);
+CREATE INDEX idx_user_email_change_final_date_new_email ON user_email_change(final_date, new_email);
+CREATE INDEX idx_user_email_change_final_date ON user_email_change(final_date);
+
+-- **
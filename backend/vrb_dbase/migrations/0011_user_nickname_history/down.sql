@@ -0,0 +1,9 @@
+-- **
+
+/* Drop the "user_nickname_history" table. */
+DROP INDEX IF EXISTS idx_user_nickname_history_user_id_changed_at;
+DROP INDEX IF EXISTS idx_user_nickname_history_old_nickname_reserved_until;
+
+DROP TABLE IF EXISTS user_nickname_history;
+
+-- **
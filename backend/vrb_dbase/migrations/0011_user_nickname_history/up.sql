@@ -0,0 +1,24 @@
+-- **
+-- History of changes to the user's nickname (the old nickname remains reserved for a while).
+
+-- **
+
+/* Create "user_nickname_history" table. */
+CREATE TABLE user_nickname_history (
+    id SERIAL PRIMARY KEY NOT NULL,
+    /* Owner id */
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    /* The nickname before the change. */
+    old_nickname VARCHAR(255) NOT NULL,
+    /* The nickname after the change. */
+    new_nickname VARCHAR(255) NOT NULL,
+    /* Date and time of the change. */
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    /* Date and time until which the old nickname is reserved for the owner. */
+    reserved_until TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_user_nickname_history_user_id_changed_at ON user_nickname_history(user_id, changed_at);
+CREATE INDEX idx_user_nickname_history_old_nickname_reserved_until ON user_nickname_history(old_nickname, reserved_until);
+
+-- **
@@ -0,0 +1,74 @@
+-- **
+-- Suspension of user accounts by the administrator.
+
+-- **
+
+/* Add the "suspended_at" column to the "users" table. (If specified, the account is suspended.) */
+ALTER TABLE users ADD COLUMN suspended_at TIMESTAMPTZ NULL;
+
+CREATE INDEX idx_users_created_at ON users(created_at);
+
+-- **
+
+/* Remove the stored function "find_user" (the list of output columns is changing). */
+DROP FUNCTION IF EXISTS find_user;
+
+/* Stored function for retrieving data from the "users" tables by ID or nickname or email. */
+CREATE OR REPLACE FUNCTION find_user(
+  IN _id INTEGER,
+  IN _nickname VARCHAR,
+  IN _email VARCHAR,
+  IN _is_password BOOLEAN,
+  OUT id INTEGER,
+  OUT nickname VARCHAR,
+  OUT email VARCHAR,
+  OUT "password" VARCHAR,
+  OUT "role" user_role,
+  OUT created_at TIMESTAMPTZ,
+  OUT updated_at TIMESTAMPTZ,
+  OUT suspended_at TIMESTAMPTZ
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+  rec1 RECORD;
+BEGIN
+  IF _id IS NOT NULL THEN
+    SELECT u.id, u.nickname, u.email, u."password", u."role", u.created_at, u.updated_at, u.suspended_at
+    FROM "users" u
+    WHERE u.id = _id
+    INTO rec1;
+  END IF;
+
+  IF rec1 IS NULL AND LENGTH(coalesce(_nickname, '')) > 0 THEN
+    SELECT u.id, u.nickname, u.email, u."password", u."role", u.created_at, u.updated_at, u.suspended_at
+    FROM users u
+    WHERE u.nickname = _nickname
+    LIMIT 1
+    INTO rec1;
+  END IF;
+
+  IF rec1 IS NULL AND LENGTH(coalesce(_email, '')) > 0 THEN
+    SELECT u.id, u.nickname, u.email, u."password", u."role", u.created_at, u.updated_at, u.suspended_at
+    FROM "users" u
+    WHERE u.email = _email
+    LIMIT 1
+    INTO rec1;
+  END IF;
+
+  -- "rec1 IS NOT NULL" is false if at least one field is NULL (for example "suspended_at"), so "NOT (rec1 IS NULL)" is used.
+  IF NOT (rec1 IS NULL) AND rec1.id IS NOT NULL THEN
+    RETURN QUERY
+      SELECT
+        rec1.id,
+        rec1.nickname,
+        rec1.email,
+        CASE WHEN _is_password THEN rec1."password" ELSE ''::VARCHAR END AS "password",
+        rec1."role",
+        rec1.created_at,
+        rec1.updated_at,
+        rec1.suspended_at;
+  END IF;
+END;
+$$;
+
+-- **
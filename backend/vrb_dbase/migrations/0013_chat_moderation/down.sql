@@ -0,0 +1,105 @@
+-- **
+
+/* Restore the stored functions "modify_chat_message" and "delete_chat_message" (only the author's messages). */
+/* Create a stored function to modify the entry in "chat_messages". */
+CREATE OR REPLACE FUNCTION modify_chat_message(
+  IN _id INTEGER,
+  IN _user_id INTEGER,
+  IN _msg VARCHAR,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT user_id INTEGER,
+  OUT user_name VARCHAR,
+  OUT msg VARCHAR,
+  OUT date_created TIMESTAMPTZ,
+  OUT date_changed TIMESTAMPTZ,
+  OUT date_removed TIMESTAMPTZ
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+  rec1 RECORD;
+  is_changed BOOLEAN;
+BEGIN
+  IF (_id IS NULL OR _user_id IS NULL) THEN
+    RETURN;
+  END IF;
+
+  IF _msg IS NOT NULL THEN
+    INSERT INTO chat_message_logs (chat_message_id, old_msg, date_update)
+    SELECT chat_messages.id, chat_messages.msg, CURRENT_TIMESTAMP
+    FROM chat_messages
+    WHERE chat_messages.date_removed IS NULL
+      AND chat_messages.id = _id
+      AND chat_messages.user_id = _user_id;
+  END IF;
+
+  is_changed := _msg IS NOT NULL AND LENGTH(_msg) > 0;
+  
+  UPDATE chat_messages SET
+    msg = _msg,
+    date_changed = CASE WHEN is_changed THEN CURRENT_TIMESTAMP ELSE chat_messages.date_changed END,
+    date_removed = CASE WHEN (NOT is_changed) THEN CURRENT_TIMESTAMP ELSE chat_messages.date_removed END
+  WHERE chat_messages.date_removed IS NULL
+    AND chat_messages.id = _id
+    AND chat_messages.user_id = _user_id
+  RETURNING
+    chat_messages.id, chat_messages.stream_id, chat_messages.user_id, chat_messages.msg,
+    chat_messages.date_created, chat_messages.date_changed, chat_messages.date_removed
+  INTO rec1;
+
+  IF rec1.id IS NULL THEN
+    RETURN;
+  END IF;
+
+  SELECT u.nickname FROM users u WHERE u.id = _user_id INTO user_name;
+
+  RETURN QUERY SELECT
+    rec1.id, rec1.stream_id, rec1.user_id, user_name, rec1.msg,
+    rec1.date_created, rec1.date_changed, rec1.date_removed;
+END;
+$$;
+
+-- **
+
+/* Create a stored function to delete the entity in "chat_messages". */
+CREATE OR REPLACE FUNCTION delete_chat_message(
+  IN _id INTEGER,
+  IN _user_id INTEGER,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT user_id INTEGER,
+  OUT user_name VARCHAR,
+  OUT msg VARCHAR,
+  OUT date_created TIMESTAMPTZ,
+  OUT date_changed TIMESTAMPTZ,
+  OUT date_removed TIMESTAMPTZ
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+  rec1 RECORD;
+BEGIN
+  IF _id IS NULL OR _user_id IS NULL THEN
+    RETURN;
+  END IF;
+
+  DELETE FROM chat_messages
+  WHERE chat_messages.id = _id
+    AND chat_messages.user_id = _user_id
+  RETURNING 
+    chat_messages.id, chat_messages.stream_id, chat_messages.user_id, chat_messages.msg,
+    chat_messages.date_created, chat_messages.date_changed, chat_messages.date_removed
+  INTO rec1;
+
+  IF rec1.id IS NULL THEN
+    RETURN;
+  END IF;
+
+  SELECT u.nickname FROM users u WHERE u.id = _user_id INTO user_name;
+
+  RETURN QUERY SELECT
+    rec1.id, rec1.stream_id, rec1.user_id, user_name, rec1.msg,
+    rec1.date_created, rec1.date_changed, rec1.date_removed;
+END;
+$$;
+
+-- **
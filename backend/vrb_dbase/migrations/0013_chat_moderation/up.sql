@@ -0,0 +1,109 @@
+-- **
+-- Moderation of chat messages: changing and deleting messages regardless of their author.
+
+-- **
+
+/* Create a stored function to modify the entry in "chat_messages".
+   If "_user_id" is NULL, the message is changed regardless of its author (moderation). */
+CREATE OR REPLACE FUNCTION modify_chat_message(
+  IN _id INTEGER,
+  IN _user_id INTEGER,
+  IN _msg VARCHAR,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT user_id INTEGER,
+  OUT user_name VARCHAR,
+  OUT msg VARCHAR,
+  OUT date_created TIMESTAMPTZ,
+  OUT date_changed TIMESTAMPTZ,
+  OUT date_removed TIMESTAMPTZ
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+  rec1 RECORD;
+  is_changed BOOLEAN;
+BEGIN
+  IF _id IS NULL THEN
+    RETURN;
+  END IF;
+
+  IF _msg IS NOT NULL THEN
+    INSERT INTO chat_message_logs (chat_message_id, old_msg, date_update)
+    SELECT chat_messages.id, chat_messages.msg, CURRENT_TIMESTAMP
+    FROM chat_messages
+    WHERE chat_messages.date_removed IS NULL
+      AND chat_messages.id = _id
+      AND (_user_id IS NULL OR chat_messages.user_id = _user_id);
+  END IF;
+
+  is_changed := _msg IS NOT NULL AND LENGTH(_msg) > 0;
+  
+  UPDATE chat_messages SET
+    msg = _msg,
+    date_changed = CASE WHEN is_changed THEN CURRENT_TIMESTAMP ELSE chat_messages.date_changed END,
+    date_removed = CASE WHEN (NOT is_changed) THEN CURRENT_TIMESTAMP ELSE chat_messages.date_removed END
+  WHERE chat_messages.date_removed IS NULL
+    AND chat_messages.id = _id
+    AND (_user_id IS NULL OR chat_messages.user_id = _user_id)
+  RETURNING
+    chat_messages.id, chat_messages.stream_id, chat_messages.user_id, chat_messages.msg,
+    chat_messages.date_created, chat_messages.date_changed, chat_messages.date_removed
+  INTO rec1;
+
+  IF rec1.id IS NULL THEN
+    RETURN;
+  END IF;
+
+  SELECT u.nickname FROM users u WHERE u.id = rec1.user_id INTO user_name;
+
+  RETURN QUERY SELECT
+    rec1.id, rec1.stream_id, rec1.user_id, user_name, rec1.msg,
+    rec1.date_created, rec1.date_changed, rec1.date_removed;
+END;
+$$;
+
+-- **
+
+/* Create a stored function to delete the entity in "chat_messages".
+   If "_user_id" is NULL, the message is deleted regardless of its author (moderation). */
+CREATE OR REPLACE FUNCTION delete_chat_message(
+  IN _id INTEGER,
+  IN _user_id INTEGER,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT user_id INTEGER,
+  OUT user_name VARCHAR,
+  OUT msg VARCHAR,
+  OUT date_created TIMESTAMPTZ,
+  OUT date_changed TIMESTAMPTZ,
+  OUT date_removed TIMESTAMPTZ
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+  rec1 RECORD;
+BEGIN
+  IF _id IS NULL THEN
+    RETURN;
+  END IF;
+
+  DELETE FROM chat_messages
+  WHERE chat_messages.id = _id
+    AND (_user_id IS NULL OR chat_messages.user_id = _user_id)
+  RETURNING 
+    chat_messages.id, chat_messages.stream_id, chat_messages.user_id, chat_messages.msg,
+    chat_messages.date_created, chat_messages.date_changed, chat_messages.date_removed
+  INTO rec1;
+
+  IF rec1.id IS NULL THEN
+    RETURN;
+  END IF;
+
+  SELECT u.nickname FROM users u WHERE u.id = rec1.user_id INTO user_name;
+
+  RETURN QUERY SELECT
+    rec1.id, rec1.stream_id, rec1.user_id, user_name, rec1.msg,
+    rec1.date_created, rec1.date_changed, rec1.date_removed;
+END;
+$$;
+
+-- **
@@ -0,0 +1,64 @@
+-- **
+
+/* The set of output parameters changes, so the function must be recreated. */
+DROP FUNCTION IF EXISTS get_chat_access(INTEGER, INTEGER);
+
+/* Create a stored function to get chat access information. (ChatAccess) */
+CREATE OR REPLACE FUNCTION get_chat_access(
+  IN _stream_id INTEGER,
+  IN _user_id INTEGER,
+  OUT stream_id INTEGER,
+  OUT stream_owner INTEGER,
+  OUT stream_state VARCHAR,
+  OUT is_blocked BOOLEAN
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+  rec1 RECORD;
+  blocked_id INTEGER;
+BEGIN
+  IF _stream_id IS NULL THEN
+    RETURN;
+  END IF;
+
+  SELECT s.id AS stream_id, s.user_id AS stream_owner, CAST(s.state AS VARCHAR) AS stream_state
+  FROM streams s 
+  WHERE s.id = _stream_id
+  INTO rec1;
+
+  IF rec1.stream_id IS NULL THEN 
+    RETURN;
+  END IF;
+
+  IF _user_id IS NOT NULL THEN
+    SELECT bu.id
+    FROM blocked_users bu 
+    WHERE bu.owner_id = rec1.stream_owner AND bu.blocked_id = _user_id
+    INTO blocked_id;
+  ELSE
+    blocked_id := -1;
+  END IF;
+
+  RETURN QUERY SELECT
+    rec1.stream_id,
+    rec1.stream_owner,
+    rec1.stream_state,
+    CASE WHEN rec1.stream_owner = _user_id THEN FALSE 
+    ELSE blocked_id IS NOT NULL 
+    END AS is_blocked;
+END;
+$$;
+
+-- **
+
+DROP FUNCTION IF EXISTS delete_stream_moderator;
+DROP FUNCTION IF EXISTS create_stream_moderator;
+DROP FUNCTION IF EXISTS get_stream_moderators;
+
+/* Drop the "stream_moderators" table. */
+DROP INDEX IF EXISTS uq_idx_stream_moderators_stream_id_user_id;
+DROP INDEX IF EXISTS idx_stream_moderators_user_id;
+
+DROP TABLE IF EXISTS stream_moderators;
+
+-- **
@@ -0,0 +1,207 @@
+-- **
+-- Chat moderators of the stream, appointed by the stream owner.
+
+-- **
+
+/* Create "stream_moderators" table. */
+CREATE TABLE stream_moderators (
+    id SERIAL PRIMARY KEY NOT NULL,
+    /* The stream in whose chat the user is a moderator. */
+    stream_id INTEGER NOT NULL REFERENCES streams(id) ON DELETE CASCADE,
+    /* The user who was appointed moderator. */
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    /* Date and time of appointment. */
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX uq_idx_stream_moderators_stream_id_user_id ON stream_moderators(stream_id, user_id);
+CREATE INDEX idx_stream_moderators_user_id ON stream_moderators(user_id);
+
+-- **
+
+/* Create a stored function that will get the list of "stream_moderators" for the specified stream. */
+CREATE OR REPLACE FUNCTION get_stream_moderators(
+  IN _stream_id INTEGER,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT user_id INTEGER,
+  OUT nickname VARCHAR,
+  OUT created_at TIMESTAMPTZ
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+BEGIN
+  IF (_stream_id IS NULL) THEN
+    RETURN;
+  END IF;
+
+  RETURN QUERY
+    SELECT
+      sm.id, sm.stream_id, sm.user_id, u.nickname, sm.created_at
+    FROM
+      stream_moderators sm, users u
+    WHERE
+      sm.stream_id = _stream_id AND sm.user_id = u.id
+    ORDER BY
+      sm.created_at ASC, sm.id ASC;
+END;
+$$;
+
+/* Create a stored function to add a new entry to "stream_moderators".
+   If such an entry already exists, it is returned unchanged. */
+CREATE OR REPLACE FUNCTION create_stream_moderator(
+  IN _stream_id INTEGER,
+  IN _user_id INTEGER,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT user_id INTEGER,
+  OUT nickname VARCHAR,
+  OUT created_at TIMESTAMPTZ
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+  rec1 RECORD;
+  md_nickname VARCHAR;
+BEGIN
+  IF (_stream_id IS NULL OR _user_id IS NULL) THEN
+    RETURN;
+  END IF;
+
+  SELECT u.nickname
+  FROM users u
+  WHERE u.id = _user_id
+  INTO md_nickname;
+
+  IF md_nickname IS NULL THEN
+    RETURN;
+  END IF;
+
+  -- Check for the presence of such a record.
+  SELECT sm.id, sm.stream_id, sm.user_id, sm.created_at
+  FROM stream_moderators sm
+  WHERE sm.stream_id = _stream_id AND sm.user_id = _user_id
+  INTO rec1;
+
+  -- If there is no such entry, add it.
+  IF rec1.id IS NULL THEN
+    INSERT INTO stream_moderators(stream_id, user_id)
+    VALUES (_stream_id, _user_id)
+    RETURNING
+      stream_moderators.id,
+      stream_moderators.stream_id,
+      stream_moderators.user_id,
+      stream_moderators.created_at
+    INTO rec1;
+  END IF;
+
+  RETURN QUERY SELECT
+    rec1.id,
+    rec1.stream_id,
+    rec1.user_id,
+    md_nickname AS nickname,
+    rec1.created_at;
+END;
+$$;
+
+/* Create a stored function to delete an entry from "stream_moderators". */
+CREATE OR REPLACE FUNCTION delete_stream_moderator(
+  IN _stream_id INTEGER,
+  IN _user_id INTEGER,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT user_id INTEGER,
+  OUT nickname VARCHAR,
+  OUT created_at TIMESTAMPTZ
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+  rec1 RECORD;
+BEGIN
+  IF (_stream_id IS NULL OR _user_id IS NULL) THEN
+    RETURN;
+  END IF;
+
+  DELETE FROM stream_moderators
+  WHERE stream_moderators.stream_id = _stream_id
+    AND stream_moderators.user_id = _user_id
+  RETURNING
+    stream_moderators.id,
+    stream_moderators.stream_id,
+    stream_moderators.user_id,
+    stream_moderators.created_at
+  INTO rec1;
+
+  IF rec1.id IS NULL THEN
+    RETURN;
+  END IF;
+
+  RETURN QUERY SELECT
+    rec1.id,
+    rec1.stream_id,
+    rec1.user_id,
+    u.nickname,
+    rec1.created_at
+  FROM users u
+  WHERE u.id = rec1.user_id;
+END;
+$$;
+
+-- **
+
+/* The set of output parameters changes, so the function must be recreated. */
+DROP FUNCTION IF EXISTS get_chat_access(INTEGER, INTEGER);
+
+/* Create a stored function to get chat access information. (ChatAccess) */
+CREATE OR REPLACE FUNCTION get_chat_access(
+  IN _stream_id INTEGER,
+  IN _user_id INTEGER,
+  OUT stream_id INTEGER,
+  OUT stream_owner INTEGER,
+  OUT stream_state VARCHAR,
+  OUT is_blocked BOOLEAN,
+  OUT is_moderator BOOLEAN
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+  rec1 RECORD;
+  blocked_id INTEGER;
+  moderator_id INTEGER;
+BEGIN
+  IF _stream_id IS NULL THEN
+    RETURN;
+  END IF;
+
+  SELECT s.id AS stream_id, s.user_id AS stream_owner, CAST(s.state AS VARCHAR) AS stream_state
+  FROM streams s 
+  WHERE s.id = _stream_id
+  INTO rec1;
+
+  IF rec1.stream_id IS NULL THEN 
+    RETURN;
+  END IF;
+
+  IF _user_id IS NOT NULL THEN
+    SELECT bu.id
+    FROM blocked_users bu 
+    WHERE bu.owner_id = rec1.stream_owner AND bu.blocked_id = _user_id
+    INTO blocked_id;
+
+    SELECT sm.id
+    FROM stream_moderators sm
+    WHERE sm.stream_id = rec1.stream_id AND sm.user_id = _user_id
+    INTO moderator_id;
+  ELSE
+    blocked_id := -1;
+  END IF;
+
+  RETURN QUERY SELECT
+    rec1.stream_id,
+    rec1.stream_owner,
+    rec1.stream_state,
+    CASE WHEN rec1.stream_owner = _user_id THEN FALSE 
+    ELSE blocked_id IS NOT NULL 
+    END AS is_blocked,
+    moderator_id IS NOT NULL AS is_moderator;
+END;
+$$;
+
+-- **
@@ -0,0 +1,300 @@
+-- **
+
+/* Restore the stored functions of "blocked_users" and "get_chat_access" (permanent blocking only). */
+DROP FUNCTION IF EXISTS get_chat_access(INTEGER, INTEGER);
+DROP FUNCTION IF EXISTS get_blocked_users(INTEGER, VARCHAR, BOOLEAN);
+DROP FUNCTION IF EXISTS get_blocked_users_sort(INTEGER, VARCHAR, BOOLEAN);
+DROP FUNCTION IF EXISTS delete_blocked_user(INTEGER, INTEGER, VARCHAR);
+DROP FUNCTION IF EXISTS create_blocked_user(INTEGER, INTEGER, VARCHAR, TIMESTAMPTZ);
+
+/* Create a stored function to add a new entry to "blocked_users". */
+CREATE OR REPLACE FUNCTION create_blocked_user(
+  IN _owner_id INTEGER,
+  IN _blocked_id INTEGER,
+  IN _blocked_nickname VARCHAR,
+  OUT id INTEGER,
+  OUT user_id INTEGER,
+  OUT nickname VARCHAR,
+  OUT block_date TIMESTAMPTZ
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE 
+  rec1 RECORD;
+  bl_user_id INTEGER;
+  bl_nickname VARCHAR;
+BEGIN
+  IF (_owner_id IS NULL) THEN
+    RETURN;
+  END IF;
+
+  IF _blocked_id IS NOT NULL THEN 
+    SELECT u.id, u.nickname
+    FROM users u 
+    WHERE u.id = _blocked_id
+    INTO bl_user_id, bl_nickname;
+  ELSIF _blocked_nickname IS NOT NULL THEN 
+    SELECT u.id, u.nickname 
+    FROM users u 
+    WHERE u.nickname = _blocked_nickname
+    INTO bl_user_id, bl_nickname;
+  END IF;
+
+  IF (bl_user_id IS NULL OR bl_nickname IS NULL) THEN
+    RETURN;
+  END IF;
+
+  -- Check for the presence of such a record.
+  SELECT
+    blocked_users.id,
+    blocked_users.owner_id,
+    blocked_users.blocked_id,
+    blocked_users.block_date
+  FROM blocked_users
+  WHERE blocked_users.owner_id = _owner_id AND blocked_users.blocked_id = bl_user_id
+  INTO rec1;
+
+  -- If there is no such entry, add it.
+  IF rec1.id IS NULL THEN
+    -- Add a new entry to the "blocked_user" table.
+    INSERT INTO blocked_users(owner_id, blocked_id)
+    VALUES (_owner_id, bl_user_id)
+    RETURNING
+      blocked_users.id,
+      blocked_users.blocked_id,
+      blocked_users.block_date
+    INTO rec1;
+  END IF;
+
+  IF rec1.id IS NULL THEN
+    RETURN;
+  END IF;
+
+  RETURN QUERY SELECT
+    rec1.id,
+    rec1.blocked_id AS user_id,
+    bl_nickname as nickname,
+    rec1.block_date;
+END;
+$$;
+
+/* Create a stored function to delete the entity in "blocked_users". */
+CREATE OR REPLACE FUNCTION delete_blocked_user(
+  IN _owner_id INTEGER,
+  IN _blocked_id INTEGER,
+  IN _blocked_nickname VARCHAR,
+  OUT id INTEGER,
+  OUT user_id INTEGER,
+  OUT nickname VARCHAR,
+  OUT block_date TIMESTAMPTZ
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+  rec1 RECORD;
+  bl_user_id INTEGER;
+  bl_nickname VARCHAR;
+BEGIN
+  IF (_owner_id IS NULL) THEN
+    RETURN;
+  END IF;
+
+  IF _blocked_id IS NOT NULL THEN 
+    SELECT u.id, u.nickname
+    FROM users u 
+    WHERE u.id = _blocked_id
+    INTO bl_user_id, bl_nickname;
+  ELSIF _blocked_nickname IS NOT NULL THEN 
+    SELECT u.id, u.nickname 
+    FROM users u 
+    WHERE u.nickname = _blocked_nickname
+    INTO bl_user_id, bl_nickname;
+  END IF;
+
+  IF (bl_user_id IS NULL OR bl_nickname IS NULL)  THEN
+    RETURN;
+  END IF;
+
+  DELETE FROM blocked_users
+  WHERE blocked_users.owner_id = _owner_id
+    AND blocked_users.blocked_id = bl_user_id
+  RETURNING 
+    blocked_users.id,
+    blocked_users.blocked_id,
+    blocked_users.block_date
+  INTO rec1;
+
+  IF rec1.id IS NULL THEN
+    RETURN;
+  END IF;
+
+  RETURN QUERY SELECT
+    rec1.id,
+    rec1.blocked_id AS user_id,
+    bl_nickname as nickname,
+    rec1.block_date;
+END;
+$$;
+
+/* Create a stored function that will get the list of "blocked_user" by the specified parameter. */
+CREATE OR REPLACE FUNCTION get_blocked_nicknames(
+  IN _owner_id INTEGER,
+  OUT id INTEGER,
+  OUT blocked_id INTEGER,
+  OUT nickname VARCHAR
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+BEGIN
+  IF (_owner_id IS NULL) THEN
+    RETURN;
+  END IF;
+
+  RETURN QUERY
+    SELECT
+      bu.id,
+      bu.blocked_id,
+      u.nickname
+    FROM
+      blocked_users bu, users u
+    WHERE
+      bu.owner_id = _owner_id
+      AND bu.blocked_id = u.id;
+END;
+$$;
+
+/* Create a stored function that will get a sorted list of "blocked_user" by the specified parameter. */
+CREATE OR REPLACE FUNCTION get_blocked_users_sort(
+  IN _owner_id INTEGER,
+  IN _sort_column VARCHAR, -- 'nickname','email','block_date'
+  IN _sort_desc BOOLEAN,
+  OUT id INTEGER,
+  OUT user_id INTEGER,
+  OUT nickname VARCHAR,
+  OUT email VARCHAR,
+  OUT block_date TIMESTAMPTZ
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+BEGIN
+  IF (_owner_id IS NULL) THEN
+    RETURN;
+  END IF;
+  IF (_sort_desc IS NULL) THEN
+    _sort_desc := FALSE;
+  END IF;
+
+  IF (_sort_column = 'email') THEN
+    RETURN QUERY
+      SELECT b.id, b.blocked_id AS user_id, u.nickname, u.email, b.block_date
+      FROM blocked_users b, users u
+      WHERE b.owner_id = _owner_id AND b.blocked_id = u.id
+      ORDER BY 
+        CASE WHEN NOT _sort_desc THEN u.email ELSE NULL END ASC,
+        CASE WHEN _sort_desc     THEN u.email ELSE NULL END DESC;
+  ELSIF (_sort_column = 'block_date') THEN
+    RETURN QUERY
+      SELECT b.id, b.blocked_id AS user_id, u.nickname, u.email, b.block_date
+      FROM blocked_users b, users u
+      WHERE b.owner_id = _owner_id AND b.blocked_id = u.id
+      ORDER BY 
+        CASE WHEN NOT _sort_desc THEN b.block_date ELSE NULL END ASC,
+        CASE WHEN _sort_desc     THEN b.block_date ELSE NULL END DESC;
+  ELSE
+    RETURN QUERY
+      SELECT b.id, b.blocked_id AS user_id, u.nickname, u.email, b.block_date
+      FROM blocked_users b, users u
+      WHERE b.owner_id = _owner_id AND b.blocked_id = u.id
+      ORDER BY 
+        CASE WHEN NOT _sort_desc THEN u.nickname ELSE NULL END ASC,
+        CASE WHEN _sort_desc     THEN u.nickname ELSE NULL END DESC;
+  END IF;
+END;
+$$;
+
+/* Create a stored function that will get the list of "blocked_user" by the specified parameter. */
+CREATE OR REPLACE FUNCTION get_blocked_users(
+  IN _owner_id INTEGER,
+  IN _sort_column VARCHAR, -- 'nickname','email','block_date'
+  IN _sort_desc BOOLEAN,
+  OUT id INTEGER,
+  OUT user_id INTEGER,
+  OUT nickname VARCHAR,
+  OUT email VARCHAR,
+  OUT block_date TIMESTAMPTZ,
+  OUT avatar VARCHAR
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+BEGIN
+  IF (_owner_id IS NULL) THEN
+    RETURN;
+  END IF;
+
+  RETURN QUERY
+    SELECT
+      b.id, b.user_id, b.nickname, b.email, b.block_date, p.avatar
+    FROM
+      get_blocked_users_sort(_owner_id, _sort_column, _sort_desc) b, profiles p
+    WHERE
+      b.user_id = p.user_id;
+END;
+$$;
+
+-- **
+
+/* Create a stored function to get chat access information. (ChatAccess) */
+CREATE OR REPLACE FUNCTION get_chat_access(
+  IN _stream_id INTEGER,
+  IN _user_id INTEGER,
+  OUT stream_id INTEGER,
+  OUT stream_owner INTEGER,
+  OUT stream_state VARCHAR,
+  OUT is_blocked BOOLEAN,
+  OUT is_moderator BOOLEAN
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+  rec1 RECORD;
+  blocked_id INTEGER;
+  moderator_id INTEGER;
+BEGIN
+  IF _stream_id IS NULL THEN
+    RETURN;
+  END IF;
+
+  SELECT s.id AS stream_id, s.user_id AS stream_owner, CAST(s.state AS VARCHAR) AS stream_state
+  FROM streams s 
+  WHERE s.id = _stream_id
+  INTO rec1;
+
+  IF rec1.stream_id IS NULL THEN 
+    RETURN;
+  END IF;
+
+  IF _user_id IS NOT NULL THEN
+    SELECT bu.id
+    FROM blocked_users bu 
+    WHERE bu.owner_id = rec1.stream_owner AND bu.blocked_id = _user_id
+    INTO blocked_id;
+
+    SELECT sm.id
+    FROM stream_moderators sm
+    WHERE sm.stream_id = rec1.stream_id AND sm.user_id = _user_id
+    INTO moderator_id;
+  ELSE
+    blocked_id := -1;
+  END IF;
+
+  RETURN QUERY SELECT
+    rec1.stream_id,
+    rec1.stream_owner,
+    rec1.stream_state,
+    CASE WHEN rec1.stream_owner = _user_id THEN FALSE 
+    ELSE blocked_id IS NOT NULL 
+    END AS is_blocked,
+    moderator_id IS NOT NULL AS is_moderator;
+END;
+$$;
+
+-- **
+
+ALTER TABLE blocked_users DROP COLUMN IF EXISTS block_until;
+
+-- **
@@ -0,0 +1,245 @@
+-- **
+
+/* Restore the stored functions of "chat_messages" (without replies). */
+DROP FUNCTION IF EXISTS delete_chat_message(INTEGER, INTEGER);
+DROP FUNCTION IF EXISTS modify_chat_message(INTEGER, INTEGER, VARCHAR);
+DROP FUNCTION IF EXISTS create_chat_message(INTEGER, INTEGER, VARCHAR, INTEGER);
+DROP FUNCTION IF EXISTS get_chat_message(INTEGER);
+DROP FUNCTION IF EXISTS filter_chat_messages(INTEGER, BOOLEAN, TIMESTAMPTZ, TIMESTAMPTZ, INTEGER, INTEGER);
+
+/* Create a stored function that will filter "chat_message" entities by the specified parameters. */
+CREATE OR REPLACE FUNCTION filter_chat_messages(
+  IN _stream_id INTEGER,
+  IN _sort_des BOOLEAN,
+  IN _min_date_created TIMESTAMPTZ,
+  IN _max_date_created TIMESTAMPTZ,
+  IN _rec_limit INTEGER,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT user_id INTEGER,
+  OUT user_name VARCHAR,
+  OUT msg VARCHAR,
+  OUT date_created TIMESTAMPTZ,
+  OUT date_changed TIMESTAMPTZ,
+  OUT date_removed TIMESTAMPTZ,
+  OUT cnt_logs INTEGER
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+BEGIN
+  IF _min_date_created IS NULL THEN
+    _min_date_created := TO_TIMESTAMP(0);
+  END IF;
+  IF _max_date_created IS NULL THEN
+    _max_date_created := CURRENT_TIMESTAMP;
+  END IF;
+  IF _rec_limit IS NULL THEN
+    _rec_limit := 20;
+  END IF;
+
+  IF _sort_des THEN
+    RETURN QUERY
+      SELECT cm.id, cm.stream_id, cm.user_id, u.nickname as user_name, cm.msg,
+        cm.date_created, cm.date_changed, cm.date_removed,
+        (SELECT CAST(COUNT(*) AS INTEGER) FROM chat_message_logs cml WHERE cml.chat_message_id = cm.id) AS cnt_logs
+      FROM chat_messages cm, users u
+      WHERE cm.stream_id = _stream_id
+        AND u.id = cm.user_id
+        AND _min_date_created < cm.date_created
+        AND cm.date_created < _max_date_created
+      ORDER BY cm.date_created DESC
+      LIMIT _rec_limit;
+  ELSE
+    RETURN QUERY
+      SELECT cm.id, cm.stream_id, cm.user_id, u.nickname as user_name, cm.msg,
+        cm.date_created, cm.date_changed, cm.date_removed,
+        (SELECT CAST(COUNT(*) AS INTEGER) FROM chat_message_logs cml WHERE cml.chat_message_id = cm.id) AS cnt_logs
+      FROM chat_messages cm, users u
+      WHERE cm.stream_id = _stream_id
+        AND u.id = cm.user_id
+        AND _min_date_created < cm.date_created
+        AND cm.date_created < _max_date_created
+      ORDER BY cm.date_created ASC
+      LIMIT _rec_limit;
+  END IF;
+END;
+$$;
+
+/* Create a stored function to get the entity "chat_message" by its ID. */
+CREATE OR REPLACE FUNCTION get_chat_message(
+  IN _id INTEGER,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT user_id INTEGER,
+  OUT user_name VARCHAR,
+  OUT msg VARCHAR,
+  OUT date_created TIMESTAMPTZ,
+  OUT date_changed TIMESTAMPTZ,
+  OUT date_removed TIMESTAMPTZ,
+  OUT cnt_logs INTEGER
+) RETURNS SETOF record LANGUAGE sql
+AS $$
+  SELECT cm.id, cm.stream_id, cm.user_id, u.nickname as user_name, cm.msg,
+    cm.date_created, cm.date_changed, cm.date_removed,
+    (SELECT CAST(COUNT(*) AS INTEGER) FROM chat_message_logs cml WHERE cml.chat_message_id = cm.id) AS cnt_logs
+  FROM chat_messages cm, users u
+  WHERE cm.id = _id
+    AND u.id = cm.user_id;
+$$;
+
+/* Create a stored function to add a new entry to "chat_messages". */
+CREATE OR REPLACE FUNCTION create_chat_message(
+  IN _stream_id INTEGER,
+  IN _user_id INTEGER,
+  IN _msg VARCHAR,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT user_id INTEGER,
+  OUT user_name VARCHAR,
+  OUT msg VARCHAR,
+  OUT date_created TIMESTAMPTZ,
+  OUT date_changed TIMESTAMPTZ,
+  OUT date_removed TIMESTAMPTZ,
+  OUT cnt_logs INTEGER
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+  rec1 RECORD;
+BEGIN
+  -- Add a new entry to the "chat_messages" table.
+  INSERT INTO chat_messages(stream_id, user_id, msg)
+  VALUES (_stream_id, _user_id, _msg)
+  RETURNING
+    chat_messages.id, chat_messages.stream_id, chat_messages.user_id, chat_messages.msg,
+    chat_messages.date_created, chat_messages.date_changed, chat_messages.date_removed
+  INTO rec1;
+
+  IF rec1.id IS NULL THEN
+    RETURN;
+  END IF;
+
+  SELECT u.nickname FROM users u WHERE u.id = _user_id INTO user_name;
+
+  -- A new message has no edit history.
+  RETURN QUERY SELECT
+    rec1.id, rec1.stream_id, rec1.user_id, user_name, rec1.msg,
+    rec1.date_created, rec1.date_changed, rec1.date_removed, 0 AS cnt_logs;
+END;
+$$;
+
+/* Create a stored function to modify the entry in "chat_messages".
+   If "_user_id" is NULL, the message is changed regardless of its author (moderation). */
+CREATE OR REPLACE FUNCTION modify_chat_message(
+  IN _id INTEGER,
+  IN _user_id INTEGER,
+  IN _msg VARCHAR,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT user_id INTEGER,
+  OUT user_name VARCHAR,
+  OUT msg VARCHAR,
+  OUT date_created TIMESTAMPTZ,
+  OUT date_changed TIMESTAMPTZ,
+  OUT date_removed TIMESTAMPTZ,
+  OUT cnt_logs INTEGER
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+  rec1 RECORD;
+  is_changed BOOLEAN;
+BEGIN
+  IF _id IS NULL THEN
+    RETURN;
+  END IF;
+
+  IF _msg IS NOT NULL THEN
+    INSERT INTO chat_message_logs (chat_message_id, old_msg, date_update)
+    SELECT chat_messages.id, chat_messages.msg, CURRENT_TIMESTAMP
+    FROM chat_messages
+    WHERE chat_messages.date_removed IS NULL
+      AND chat_messages.id = _id
+      AND (_user_id IS NULL OR chat_messages.user_id = _user_id);
+  END IF;
+
+  is_changed := _msg IS NOT NULL AND LENGTH(_msg) > 0;
+
+  UPDATE chat_messages SET
+    msg = _msg,
+    date_changed = CASE WHEN is_changed THEN CURRENT_TIMESTAMP ELSE chat_messages.date_changed END,
+    date_removed = CASE WHEN (NOT is_changed) THEN CURRENT_TIMESTAMP ELSE chat_messages.date_removed END
+  WHERE chat_messages.date_removed IS NULL
+    AND chat_messages.id = _id
+    AND (_user_id IS NULL OR chat_messages.user_id = _user_id)
+  RETURNING
+    chat_messages.id, chat_messages.stream_id, chat_messages.user_id, chat_messages.msg,
+    chat_messages.date_created, chat_messages.date_changed, chat_messages.date_removed
+  INTO rec1;
+
+  IF rec1.id IS NULL THEN
+    RETURN;
+  END IF;
+
+  SELECT u.nickname FROM users u WHERE u.id = rec1.user_id INTO user_name;
+
+  SELECT CAST(COUNT(*) AS INTEGER) FROM chat_message_logs cml WHERE cml.chat_message_id = rec1.id INTO cnt_logs;
+
+  RETURN QUERY SELECT
+    rec1.id, rec1.stream_id, rec1.user_id, user_name, rec1.msg,
+    rec1.date_created, rec1.date_changed, rec1.date_removed, cnt_logs;
+END;
+$$;
+
+/* Create a stored function to delete the entity in "chat_messages".
+   If "_user_id" is NULL, the message is deleted regardless of its author (moderation). */
+CREATE OR REPLACE FUNCTION delete_chat_message(
+  IN _id INTEGER,
+  IN _user_id INTEGER,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT user_id INTEGER,
+  OUT user_name VARCHAR,
+  OUT msg VARCHAR,
+  OUT date_created TIMESTAMPTZ,
+  OUT date_changed TIMESTAMPTZ,
+  OUT date_removed TIMESTAMPTZ,
+  OUT cnt_logs INTEGER
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+  rec1 RECORD;
+BEGIN
+  IF _id IS NULL THEN
+    RETURN;
+  END IF;
+
+  -- The edit history is deleted along with the message, so the number of entries is obtained in advance.
+  SELECT CAST(COUNT(*) AS INTEGER) FROM chat_message_logs cml WHERE cml.chat_message_id = _id INTO cnt_logs;
+
+  DELETE FROM chat_messages
+  WHERE chat_messages.id = _id
+    AND (_user_id IS NULL OR chat_messages.user_id = _user_id)
+  RETURNING
+    chat_messages.id, chat_messages.stream_id, chat_messages.user_id, chat_messages.msg,
+    chat_messages.date_created, chat_messages.date_changed, chat_messages.date_removed
+  INTO rec1;
+
+  IF rec1.id IS NULL THEN
+    RETURN;
+  END IF;
+
+  SELECT u.nickname FROM users u WHERE u.id = rec1.user_id INTO user_name;
+
+  RETURN QUERY SELECT
+    rec1.id, rec1.stream_id, rec1.user_id, user_name, rec1.msg,
+    rec1.date_created, rec1.date_changed, rec1.date_removed, cnt_logs;
+END;
+$$;
+
+-- **
+
+/* Remove the index on the "chat_messages" table. */
+DROP INDEX IF EXISTS idx_chat_messages_reply_to;
+
+ALTER TABLE chat_messages DROP COLUMN IF EXISTS reply_to;
+
+-- **
@@ -0,0 +1,305 @@
+-- **
+-- Replies to chat messages. Each message is returned together with the author and the text of the parent message.
+
+-- **
+
+/* The parent message to which this message is a reply. (NULL - the message is not a reply.) */
+ALTER TABLE chat_messages ADD COLUMN reply_to INTEGER NULL REFERENCES chat_messages(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_chat_messages_reply_to ON chat_messages(reply_to);
+
+-- **
+
+/* The set of parameters changes, so the functions must be recreated. */
+DROP FUNCTION IF EXISTS filter_chat_messages(INTEGER, BOOLEAN, TIMESTAMPTZ, TIMESTAMPTZ, INTEGER);
+DROP FUNCTION IF EXISTS get_chat_message(INTEGER);
+DROP FUNCTION IF EXISTS create_chat_message(INTEGER, INTEGER, VARCHAR);
+DROP FUNCTION IF EXISTS modify_chat_message(INTEGER, INTEGER, VARCHAR);
+DROP FUNCTION IF EXISTS delete_chat_message(INTEGER, INTEGER);
+
+-- **
+
+/* Create a stored function that will filter "chat_message" entities by the specified parameters.
+   If "_thread_id" is specified, only the message with this ID and all replies to it (at any depth) are selected.
+   The text of the parent message is not returned if it has been removed. */
+CREATE OR REPLACE FUNCTION filter_chat_messages(
+  IN _stream_id INTEGER,
+  IN _sort_des BOOLEAN,
+  IN _min_date_created TIMESTAMPTZ,
+  IN _max_date_created TIMESTAMPTZ,
+  IN _rec_limit INTEGER,
+  IN _thread_id INTEGER,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT user_id INTEGER,
+  OUT user_name VARCHAR,
+  OUT msg VARCHAR,
+  OUT date_created TIMESTAMPTZ,
+  OUT date_changed TIMESTAMPTZ,
+  OUT date_removed TIMESTAMPTZ,
+  OUT cnt_logs INTEGER,
+  OUT reply_to INTEGER,
+  OUT reply_user_name VARCHAR,
+  OUT reply_msg VARCHAR
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+BEGIN
+  IF _min_date_created IS NULL THEN
+    _min_date_created := TO_TIMESTAMP(0);
+  END IF;
+  IF _max_date_created IS NULL THEN
+    _max_date_created := CURRENT_TIMESTAMP;
+  END IF;
+  IF _rec_limit IS NULL THEN
+    _rec_limit := 20;
+  END IF;
+
+  RETURN QUERY
+    WITH RECURSIVE thread AS (
+      SELECT t1.id FROM chat_messages t1
+      WHERE t1.id = _thread_id AND t1.stream_id = _stream_id
+      UNION
+      SELECT t2.id FROM chat_messages t2, thread t
+      WHERE t2.reply_to = t.id
+    )
+    SELECT cm.id, cm.stream_id, cm.user_id, u.nickname as user_name, cm.msg,
+      cm.date_created, cm.date_changed, cm.date_removed,
+      (SELECT CAST(COUNT(*) AS INTEGER) FROM chat_message_logs cml WHERE cml.chat_message_id = cm.id) AS cnt_logs,
+      cm.reply_to,
+      pu.nickname AS reply_user_name,
+      CASE WHEN pm.date_removed IS NULL THEN pm.msg ELSE NULL END AS reply_msg
+    FROM chat_messages cm
+      JOIN users u ON u.id = cm.user_id
+      LEFT JOIN chat_messages pm ON pm.id = cm.reply_to
+      LEFT JOIN users pu ON pu.id = pm.user_id
+    WHERE cm.stream_id = _stream_id
+      AND _min_date_created < cm.date_created
+      AND cm.date_created < _max_date_created
+      AND (_thread_id IS NULL OR cm.id IN (SELECT thread.id FROM thread))
+    ORDER BY
+      CASE WHEN _sort_des THEN cm.date_created ELSE NULL END DESC,
+      CASE WHEN NOT _sort_des OR _sort_des IS NULL THEN cm.date_created ELSE NULL END ASC
+    LIMIT _rec_limit;
+END;
+$$;
+
+/* Create a stored function to get the entity "chat_message" by its ID. */
+CREATE OR REPLACE FUNCTION get_chat_message(
+  IN _id INTEGER,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT user_id INTEGER,
+  OUT user_name VARCHAR,
+  OUT msg VARCHAR,
+  OUT date_created TIMESTAMPTZ,
+  OUT date_changed TIMESTAMPTZ,
+  OUT date_removed TIMESTAMPTZ,
+  OUT cnt_logs INTEGER,
+  OUT reply_to INTEGER,
+  OUT reply_user_name VARCHAR,
+  OUT reply_msg VARCHAR
+) RETURNS SETOF record LANGUAGE sql
+AS $$
+  SELECT cm.id, cm.stream_id, cm.user_id, u.nickname as user_name, cm.msg,
+    cm.date_created, cm.date_changed, cm.date_removed,
+    (SELECT CAST(COUNT(*) AS INTEGER) FROM chat_message_logs cml WHERE cml.chat_message_id = cm.id) AS cnt_logs,
+    cm.reply_to,
+    pu.nickname AS reply_user_name,
+    CASE WHEN pm.date_removed IS NULL THEN pm.msg ELSE NULL END AS reply_msg
+  FROM chat_messages cm
+    JOIN users u ON u.id = cm.user_id
+    LEFT JOIN chat_messages pm ON pm.id = cm.reply_to
+    LEFT JOIN users pu ON pu.id = pm.user_id
+  WHERE cm.id = _id;
+$$;
+
+/* Create a stored function to add a new entry to "chat_messages".
+   If "_reply_to" is specified, the parent message must be in the same stream and must not be removed. */
+CREATE OR REPLACE FUNCTION create_chat_message(
+  IN _stream_id INTEGER,
+  IN _user_id INTEGER,
+  IN _msg VARCHAR,
+  IN _reply_to INTEGER,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT user_id INTEGER,
+  OUT user_name VARCHAR,
+  OUT msg VARCHAR,
+  OUT date_created TIMESTAMPTZ,
+  OUT date_changed TIMESTAMPTZ,
+  OUT date_removed TIMESTAMPTZ,
+  OUT cnt_logs INTEGER,
+  OUT reply_to INTEGER,
+  OUT reply_user_name VARCHAR,
+  OUT reply_msg VARCHAR
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+  rec1 RECORD;
+  parent_id INTEGER;
+BEGIN
+  IF _reply_to IS NOT NULL THEN
+    SELECT pm.id, pu.nickname, pm.msg
+    FROM chat_messages pm, users pu
+    WHERE pm.id = _reply_to
+      AND pm.stream_id = _stream_id
+      AND pm.date_removed IS NULL
+      AND pu.id = pm.user_id
+    INTO parent_id, reply_user_name, reply_msg;
+
+    IF parent_id IS NULL THEN
+      RETURN;
+    END IF;
+  END IF;
+
+  -- Add a new entry to the "chat_messages" table.
+  INSERT INTO chat_messages(stream_id, user_id, msg, reply_to)
+  VALUES (_stream_id, _user_id, _msg, parent_id)
+  RETURNING
+    chat_messages.id, chat_messages.stream_id, chat_messages.user_id, chat_messages.msg,
+    chat_messages.date_created, chat_messages.date_changed, chat_messages.date_removed, chat_messages.reply_to
+  INTO rec1;
+
+  IF rec1.id IS NULL THEN
+    RETURN;
+  END IF;
+
+  SELECT u.nickname FROM users u WHERE u.id = _user_id INTO user_name;
+
+  -- A new message has no edit history.
+  RETURN QUERY SELECT
+    rec1.id, rec1.stream_id, rec1.user_id, user_name, rec1.msg,
+    rec1.date_created, rec1.date_changed, rec1.date_removed, 0 AS cnt_logs,
+    rec1.reply_to, reply_user_name, reply_msg;
+END;
+$$;
+
+/* Create a stored function to modify the entry in "chat_messages".
+   If "_user_id" is NULL, the message is changed regardless of its author (moderation). */
+CREATE OR REPLACE FUNCTION modify_chat_message(
+  IN _id INTEGER,
+  IN _user_id INTEGER,
+  IN _msg VARCHAR,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT user_id INTEGER,
+  OUT user_name VARCHAR,
+  OUT msg VARCHAR,
+  OUT date_created TIMESTAMPTZ,
+  OUT date_changed TIMESTAMPTZ,
+  OUT date_removed TIMESTAMPTZ,
+  OUT cnt_logs INTEGER,
+  OUT reply_to INTEGER,
+  OUT reply_user_name VARCHAR,
+  OUT reply_msg VARCHAR
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+  rec1 RECORD;
+  is_changed BOOLEAN;
+BEGIN
+  IF _id IS NULL THEN
+    RETURN;
+  END IF;
+
+  IF _msg IS NOT NULL THEN
+    INSERT INTO chat_message_logs (chat_message_id, old_msg, date_update)
+    SELECT chat_messages.id, chat_messages.msg, CURRENT_TIMESTAMP
+    FROM chat_messages
+    WHERE chat_messages.date_removed IS NULL
+      AND chat_messages.id = _id
+      AND (_user_id IS NULL OR chat_messages.user_id = _user_id);
+  END IF;
+
+  is_changed := _msg IS NOT NULL AND LENGTH(_msg) > 0;
+
+  UPDATE chat_messages SET
+    msg = _msg,
+    date_changed = CASE WHEN is_changed THEN CURRENT_TIMESTAMP ELSE chat_messages.date_changed END,
+    date_removed = CASE WHEN (NOT is_changed) THEN CURRENT_TIMESTAMP ELSE chat_messages.date_removed END
+  WHERE chat_messages.date_removed IS NULL
+    AND chat_messages.id = _id
+    AND (_user_id IS NULL OR chat_messages.user_id = _user_id)
+  RETURNING
+    chat_messages.id, chat_messages.stream_id, chat_messages.user_id, chat_messages.msg,
+    chat_messages.date_created, chat_messages.date_changed, chat_messages.date_removed, chat_messages.reply_to
+  INTO rec1;
+
+  IF rec1.id IS NULL THEN
+    RETURN;
+  END IF;
+
+  SELECT u.nickname FROM users u WHERE u.id = rec1.user_id INTO user_name;
+
+  SELECT CAST(COUNT(*) AS INTEGER) FROM chat_message_logs cml WHERE cml.chat_message_id = rec1.id INTO cnt_logs;
+
+  SELECT pu.nickname, CASE WHEN pm.date_removed IS NULL THEN pm.msg ELSE NULL END
+  FROM chat_messages pm, users pu
+  WHERE pm.id = rec1.reply_to AND pu.id = pm.user_id
+  INTO reply_user_name, reply_msg;
+
+  RETURN QUERY SELECT
+    rec1.id, rec1.stream_id, rec1.user_id, user_name, rec1.msg,
+    rec1.date_created, rec1.date_changed, rec1.date_removed, cnt_logs,
+    rec1.reply_to, reply_user_name, reply_msg;
+END;
+$$;
+
+/* Create a stored function to delete the entity in "chat_messages".
+   If "_user_id" is NULL, the message is deleted regardless of its author (moderation).
+   Replies to the deleted message remain, but lose the link to it. */
+CREATE OR REPLACE FUNCTION delete_chat_message(
+  IN _id INTEGER,
+  IN _user_id INTEGER,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT user_id INTEGER,
+  OUT user_name VARCHAR,
+  OUT msg VARCHAR,
+  OUT date_created TIMESTAMPTZ,
+  OUT date_changed TIMESTAMPTZ,
+  OUT date_removed TIMESTAMPTZ,
+  OUT cnt_logs INTEGER,
+  OUT reply_to INTEGER,
+  OUT reply_user_name VARCHAR,
+  OUT reply_msg VARCHAR
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+  rec1 RECORD;
+BEGIN
+  IF _id IS NULL THEN
+    RETURN;
+  END IF;
+
+  -- The edit history is deleted along with the message, so the number of entries is obtained in advance.
+  SELECT CAST(COUNT(*) AS INTEGER) FROM chat_message_logs cml WHERE cml.chat_message_id = _id INTO cnt_logs;
+
+  DELETE FROM chat_messages
+  WHERE chat_messages.id = _id
+    AND (_user_id IS NULL OR chat_messages.user_id = _user_id)
+  RETURNING
+    chat_messages.id, chat_messages.stream_id, chat_messages.user_id, chat_messages.msg,
+    chat_messages.date_created, chat_messages.date_changed, chat_messages.date_removed, chat_messages.reply_to
+  INTO rec1;
+
+  IF rec1.id IS NULL THEN
+    RETURN;
+  END IF;
+
+  SELECT u.nickname FROM users u WHERE u.id = rec1.user_id INTO user_name;
+
+  SELECT pu.nickname, CASE WHEN pm.date_removed IS NULL THEN pm.msg ELSE NULL END
+  FROM chat_messages pm, users pu
+  WHERE pm.id = rec1.reply_to AND pu.id = pm.user_id
+  INTO reply_user_name, reply_msg;
+
+  RETURN QUERY SELECT
+    rec1.id, rec1.stream_id, rec1.user_id, user_name, rec1.msg,
+    rec1.date_created, rec1.date_changed, rec1.date_removed, cnt_logs,
+    rec1.reply_to, reply_user_name, reply_msg;
+END;
+$$;
+
+-- **
@@ -0,0 +1,12 @@
+-- **
+
+DROP FUNCTION IF EXISTS modify_chat_message_reaction(INTEGER, INTEGER, INTEGER, VARCHAR, BOOLEAN);
+DROP FUNCTION IF EXISTS filter_chat_message_reactions(INTEGER[]);
+
+-- **
+
+DROP INDEX IF EXISTS idx_chat_message_reactions_user_id;
+
+DROP TABLE IF EXISTS chat_message_reactions;
+
+-- **
@@ -0,0 +1,90 @@
+-- **
+-- Emoji reactions of users to chat messages.
+
+-- **
+
+/* Create "chat_message_reactions" table. */
+CREATE TABLE chat_message_reactions (
+    /* The message to which the reaction was added. */
+    chat_message_id INTEGER NOT NULL REFERENCES chat_messages(id) ON DELETE CASCADE,
+    /* The user who added the reaction. */
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    /* Reaction (emoji). */
+    emoji VARCHAR(32) NOT NULL,
+    /* Date and time the reaction was added. */
+    date_created TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    PRIMARY KEY (chat_message_id, user_id, emoji)
+);
+
+CREATE INDEX idx_chat_message_reactions_user_id ON chat_message_reactions(user_id);
+
+-- **
+
+/* Create a stored function to get the number of reactions (of each emoji) for the specified messages. */
+CREATE OR REPLACE FUNCTION filter_chat_message_reactions(
+  IN _chat_message_ids INTEGER[],
+  OUT chat_message_id INTEGER,
+  OUT emoji VARCHAR,
+  OUT cnt INTEGER
+) RETURNS SETOF record LANGUAGE sql
+AS $$
+  SELECT cmr.chat_message_id, cmr.emoji, CAST(COUNT(*) AS INTEGER) AS cnt
+  FROM chat_message_reactions cmr
+  WHERE cmr.chat_message_id = ANY(_chat_message_ids)
+  GROUP BY cmr.chat_message_id, cmr.emoji
+  ORDER BY cmr.chat_message_id ASC, MIN(cmr.date_created) ASC, cmr.emoji ASC;
+$$;
+
+/* Create a stored function to add (_is_add = TRUE) or remove (_is_add = FALSE) the user's reaction to the message.
+   The message must be in the specified stream and must not be deleted.
+   Returns the number of reactions (of this emoji) and the change in this number ("delta": 1, 0, -1). */
+CREATE OR REPLACE FUNCTION modify_chat_message_reaction(
+  IN _chat_message_id INTEGER,
+  IN _stream_id INTEGER,
+  IN _user_id INTEGER,
+  IN _emoji VARCHAR,
+  IN _is_add BOOLEAN,
+  OUT chat_message_id INTEGER,
+  OUT emoji VARCHAR,
+  OUT cnt INTEGER,
+  OUT delta INTEGER
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+  rec_cnt INTEGER;
+BEGIN
+  IF _chat_message_id IS NULL OR _stream_id IS NULL OR _user_id IS NULL OR LENGTH(COALESCE(_emoji, '')) = 0 THEN
+    RETURN;
+  END IF;
+
+  IF NOT EXISTS (
+    SELECT 1 FROM chat_messages cm
+    WHERE cm.id = _chat_message_id AND cm.stream_id = _stream_id AND cm.date_removed IS NULL
+  ) THEN
+    RETURN;
+  END IF;
+
+  IF _is_add THEN
+    INSERT INTO chat_message_reactions(chat_message_id, user_id, emoji)
+    VALUES (_chat_message_id, _user_id, _emoji)
+    ON CONFLICT DO NOTHING;
+    GET DIAGNOSTICS rec_cnt = ROW_COUNT;
+    delta := rec_cnt;
+  ELSE
+    DELETE FROM chat_message_reactions
+    WHERE chat_message_reactions.chat_message_id = _chat_message_id
+      AND chat_message_reactions.user_id = _user_id
+      AND chat_message_reactions.emoji = _emoji;
+    GET DIAGNOSTICS rec_cnt = ROW_COUNT;
+    delta := -rec_cnt;
+  END IF;
+
+  SELECT CAST(COUNT(*) AS INTEGER) FROM chat_message_reactions cmr
+  WHERE cmr.chat_message_id = _chat_message_id AND cmr.emoji = _emoji
+  INTO cnt;
+
+  RETURN QUERY SELECT _chat_message_id, _emoji, cnt, delta;
+END;
+$$;
+
+-- **
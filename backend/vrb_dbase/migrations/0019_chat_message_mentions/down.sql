@@ -0,0 +1,14 @@
+-- **
+
+DROP FUNCTION IF EXISTS read_chat_message_mentions(INTEGER, INTEGER[]);
+DROP FUNCTION IF EXISTS filter_chat_message_mentions(INTEGER);
+DROP FUNCTION IF EXISTS create_chat_message_mentions(INTEGER, INTEGER, VARCHAR[]);
+
+-- **
+
+DROP INDEX IF EXISTS idx_chat_message_mentions_user_id;
+DROP INDEX IF EXISTS uq_idx_chat_message_mentions_chat_message_id_user_id;
+
+DROP TABLE IF EXISTS chat_message_mentions;
+
+-- **
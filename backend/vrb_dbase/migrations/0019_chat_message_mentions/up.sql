@@ -0,0 +1,105 @@
+-- **
+-- Mentions of users (@nickname) in chat messages.
+
+-- **
+
+/* Create "chat_message_mentions" table. */
+CREATE TABLE chat_message_mentions (
+    id SERIAL PRIMARY KEY NOT NULL,
+    /* The message in which the user was mentioned. */
+    chat_message_id INTEGER NOT NULL REFERENCES chat_messages(id) ON DELETE CASCADE,
+    /* The mentioned user. */
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    /* Date and time the mention was created. */
+    date_created TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    /* Date and time the mention was read (NULL - the mention has not been read). */
+    date_read TIMESTAMPTZ NULL
+);
+
+CREATE UNIQUE INDEX uq_idx_chat_message_mentions_chat_message_id_user_id ON chat_message_mentions(chat_message_id, user_id);
+CREATE INDEX idx_chat_message_mentions_user_id ON chat_message_mentions(user_id);
+
+-- **
+
+/* Create a stored function to add mentions of users (by nickname) to the message of the specified author.
+   The author is not mentioned, and users who have blocked the author are not mentioned either.
+   Returns the list of mentioned users (only new mentions). */
+CREATE OR REPLACE FUNCTION create_chat_message_mentions(
+  IN _chat_message_id INTEGER,
+  IN _user_id INTEGER,
+  IN _nicknames VARCHAR[],
+  OUT user_id INTEGER,
+  OUT nickname VARCHAR
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+BEGIN
+  RETURN QUERY
+    WITH ins AS (
+      INSERT INTO chat_message_mentions(chat_message_id, user_id)
+      SELECT cm.id, u.id
+      FROM chat_messages cm, users u
+      WHERE cm.id = _chat_message_id AND cm.user_id = _user_id AND cm.date_removed IS NULL
+        AND u.nickname = ANY(_nicknames) AND u.id != _user_id
+        AND NOT EXISTS (
+          SELECT 1 FROM blocked_users bu
+          WHERE bu.owner_id = u.id AND bu.blocked_id = _user_id
+            AND (bu.block_until IS NULL OR bu.block_until > CURRENT_TIMESTAMP)
+        )
+      ON CONFLICT DO NOTHING
+      RETURNING chat_message_mentions.user_id
+    )
+    SELECT u.id, u.nickname
+    FROM ins, users u
+    WHERE u.id = ins.user_id
+    ORDER BY u.nickname ASC;
+END;
+$$;
+
+/* Create a stored function to get a list of unread mentions of the user (in messages that are not deleted). */
+CREATE OR REPLACE FUNCTION filter_chat_message_mentions(
+  IN _user_id INTEGER,
+  OUT id INTEGER,
+  OUT chat_message_id INTEGER,
+  OUT stream_id INTEGER,
+  OUT user_name VARCHAR,
+  OUT msg VARCHAR,
+  OUT date_created TIMESTAMPTZ
+) RETURNS SETOF record LANGUAGE sql
+AS $$
+  SELECT cmm.id, cm.id, cm.stream_id, u.nickname AS user_name, cm.msg, cm.date_created
+  FROM chat_message_mentions cmm, chat_messages cm, users u
+  WHERE cmm.user_id = _user_id AND cmm.date_read IS NULL
+    AND cm.id = cmm.chat_message_id AND cm.date_removed IS NULL
+    AND u.id = cm.user_id
+  ORDER BY cmm.id ASC;
+$$;
+
+/* Create a stored function to mark the user's mentions as read (if _ids is NULL, then all unread mentions).
+   Returns the list of mentions that have been marked as read. */
+CREATE OR REPLACE FUNCTION read_chat_message_mentions(
+  IN _user_id INTEGER,
+  IN _ids INTEGER[],
+  OUT id INTEGER,
+  OUT chat_message_id INTEGER,
+  OUT stream_id INTEGER,
+  OUT user_name VARCHAR,
+  OUT msg VARCHAR,
+  OUT date_created TIMESTAMPTZ
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+BEGIN
+  RETURN QUERY
+    WITH upd AS (
+      UPDATE chat_message_mentions SET date_read = CURRENT_TIMESTAMP
+      WHERE chat_message_mentions.user_id = _user_id AND chat_message_mentions.date_read IS NULL
+        AND (_ids IS NULL OR chat_message_mentions.id = ANY(_ids))
+      RETURNING chat_message_mentions.id, chat_message_mentions.chat_message_id
+    )
+    SELECT upd.id, cm.id, cm.stream_id, u.nickname AS user_name, cm.msg, cm.date_created
+    FROM upd, chat_messages cm, users u
+    WHERE cm.id = upd.chat_message_id AND u.id = cm.user_id
+    ORDER BY upd.id ASC;
+END;
+$$;
+
+-- **
@@ -0,0 +1,9 @@
+-- **
+
+DROP FUNCTION IF EXISTS search_chat_messages(VARCHAR, VARCHAR, INTEGER, INTEGER, INTEGER, TIMESTAMPTZ, TIMESTAMPTZ, INTEGER, INTEGER);
+
+-- **
+
+DROP INDEX IF EXISTS idx_chat_messages_msg_tsv;
+
+-- **
@@ -0,0 +1,62 @@
+-- **
+-- Full-text search over chat messages (by text and by author).
+
+-- **
+
+/* The "simple" configuration does not depend on the language of the message (words are not reduced to stems). */
+CREATE INDEX idx_chat_messages_msg_tsv ON chat_messages USING GIN (to_tsvector('simple', msg));
+
+-- **
+
+/* Create a stored function to search for "chat_message" entities by text and (or) by author.
+   The search is carried out in one stream ("_stream_id") or in all streams of the owner ("_owner_id").
+   If "_viewer_id" is specified, only the streams that this user owns or whose chat this user moderates are searched.
+   Removed messages are not searched. Matches in the text are highlighted with the "<mark>" tag.
+   Each row contains the total number of found messages ("cnt_total"), regardless of "_rec_offset" and "_rec_limit".
+   The function is "STABLE" so that it can be inlined into the query and the "GIN" index can be used. */
+CREATE OR REPLACE FUNCTION search_chat_messages(
+  IN _text VARCHAR,
+  IN _user_name VARCHAR,
+  IN _stream_id INTEGER,
+  IN _owner_id INTEGER,
+  IN _viewer_id INTEGER,
+  IN _min_date_created TIMESTAMPTZ,
+  IN _max_date_created TIMESTAMPTZ,
+  IN _rec_offset INTEGER,
+  IN _rec_limit INTEGER,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT user_id INTEGER,
+  OUT user_name VARCHAR,
+  OUT msg VARCHAR,
+  OUT highlight TEXT,
+  OUT date_created TIMESTAMPTZ,
+  OUT date_changed TIMESTAMPTZ,
+  OUT cnt_total INTEGER
+) RETURNS SETOF record LANGUAGE sql STABLE
+AS $$
+  SELECT cm.id, cm.stream_id, cm.user_id, u.nickname AS user_name, cm.msg,
+    CASE WHEN _text IS NULL THEN cm.msg
+      ELSE ts_headline('simple', cm.msg, websearch_to_tsquery('simple', _text),
+        'StartSel=<mark>, StopSel=</mark>, HighlightAll=true')
+    END AS highlight,
+    cm.date_created, cm.date_changed,
+    CAST(COUNT(*) OVER() AS INTEGER) AS cnt_total
+  FROM chat_messages cm
+    JOIN users u ON u.id = cm.user_id
+    JOIN streams s ON s.id = cm.stream_id
+  WHERE cm.date_removed IS NULL
+    AND (_stream_id IS NULL OR cm.stream_id = _stream_id)
+    AND (_owner_id IS NULL OR s.user_id = _owner_id)
+    AND (_viewer_id IS NULL OR s.user_id = _viewer_id
+      OR EXISTS (SELECT 1 FROM stream_moderators sm WHERE sm.stream_id = s.id AND sm.user_id = _viewer_id))
+    AND (_text IS NULL OR to_tsvector('simple', cm.msg) @@ websearch_to_tsquery('simple', _text))
+    AND (_user_name IS NULL OR u.nickname = _user_name)
+    AND (_min_date_created IS NULL OR _min_date_created <= cm.date_created)
+    AND (_max_date_created IS NULL OR cm.date_created <= _max_date_created)
+  ORDER BY cm.date_created DESC, cm.id DESC
+  OFFSET COALESCE(_rec_offset, 0)
+  LIMIT _rec_limit;
+$$;
+
+-- **
@@ -0,0 +1,5 @@
+-- **
+
+DROP FUNCTION IF EXISTS get_chat_stream_times(INTEGER, INTEGER);
+
+-- **
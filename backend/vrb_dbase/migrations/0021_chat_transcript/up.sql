@@ -0,0 +1,34 @@
+-- **
+-- Chat transcript export (the times of the stream to calculate the time of messages relative to its beginning).
+
+-- **
+
+/* Create a stored function to get the times of the stream (the chat) for the transcript. (ChatStreamTimes)
+   "is_moderator" - whether the user "_user_id" is the chat moderator of this stream. */
+CREATE OR REPLACE FUNCTION get_chat_stream_times(
+  IN _stream_id INTEGER,
+  IN _user_id INTEGER,
+  OUT stream_id INTEGER,
+  OUT stream_owner INTEGER,
+  OUT stream_state VARCHAR,
+  OUT is_moderator BOOLEAN,
+  OUT starttime TIMESTAMPTZ,
+  OUT started TIMESTAMPTZ,
+  OUT paused TIMESTAMPTZ,
+  OUT stopped TIMESTAMPTZ
+) RETURNS SETOF record LANGUAGE sql
+AS $$
+  SELECT
+    s.id AS stream_id,
+    s.user_id AS stream_owner,
+    CAST(s.state AS VARCHAR) AS stream_state,
+    EXISTS (SELECT 1 FROM stream_moderators sm WHERE sm.stream_id = s.id AND sm.user_id = _user_id) AS is_moderator,
+    s.starttime,
+    s.started,
+    s.paused,
+    s.stopped
+  FROM streams s
+  WHERE s.id = _stream_id;
+$$;
+
+-- **
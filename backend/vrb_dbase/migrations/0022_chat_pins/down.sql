@@ -0,0 +1,14 @@
+-- **
+
+DROP FUNCTION IF EXISTS delete_chat_pin(INTEGER, INTEGER);
+DROP FUNCTION IF EXISTS create_chat_pin(INTEGER, INTEGER, INTEGER, VARCHAR);
+DROP FUNCTION IF EXISTS filter_chat_pins(INTEGER);
+
+-- **
+
+DROP INDEX IF EXISTS uq_idx_chat_pins_chat_message_id;
+DROP INDEX IF EXISTS idx_chat_pins_stream_id;
+
+DROP TABLE IF EXISTS chat_pins;
+
+-- **
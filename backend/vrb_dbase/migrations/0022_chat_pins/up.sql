@@ -0,0 +1,137 @@
+-- **
+-- Pinned messages and announcements of the chat (stream). They are shown at the top of the chat.
+
+-- **
+
+/* Create "chat_pins" table. */
+CREATE TABLE chat_pins (
+    id SERIAL PRIMARY KEY NOT NULL,
+    /* The chat (stream) in which the pin was made. */
+    stream_id INTEGER NOT NULL REFERENCES streams(id) ON DELETE CASCADE,
+    /* The pinned message. (NULL - the pin is an announcement.) */
+    chat_message_id INTEGER NULL REFERENCES chat_messages(id) ON DELETE CASCADE,
+    /* The text of the announcement. (NULL - the pin is a message.) */
+    msg VARCHAR(255) NULL,
+    /* The user who made the pin. */
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    /* Date and time the pin was made. */
+    date_created TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    CONSTRAINT chk_chat_pins_message_or_msg CHECK (chat_message_id IS NOT NULL OR msg IS NOT NULL)
+);
+
+CREATE INDEX idx_chat_pins_stream_id ON chat_pins(stream_id);
+CREATE UNIQUE INDEX uq_idx_chat_pins_chat_message_id ON chat_pins(chat_message_id);
+
+-- **
+
+/* Create a stored function to get the pins of the chat (stream).
+   For a pinned message, its author and text are returned. Pins of removed messages are not returned. */
+CREATE OR REPLACE FUNCTION filter_chat_pins(
+  IN _stream_id INTEGER,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT chat_message_id INTEGER,
+  OUT user_name VARCHAR,
+  OUT msg VARCHAR,
+  OUT date_created TIMESTAMPTZ
+) RETURNS SETOF record LANGUAGE sql STABLE
+AS $$
+  SELECT cp.id, cp.stream_id, cp.chat_message_id,
+    COALESCE(mu.nickname, u.nickname) AS user_name,
+    COALESCE(cm.msg, cp.msg) AS msg,
+    cp.date_created
+  FROM chat_pins cp
+    JOIN users u ON u.id = cp.user_id
+    LEFT JOIN chat_messages cm ON cm.id = cp.chat_message_id
+    LEFT JOIN users mu ON mu.id = cm.user_id
+  WHERE cp.stream_id = _stream_id
+    AND cm.date_removed IS NULL
+  ORDER BY cp.date_created ASC, cp.id ASC;
+$$;
+
+/* Create a stored function to pin a message ("_chat_message_id") or an announcement ("_msg") in the chat.
+   The pinned message must be in the specified stream and must not be removed.
+   If the message is already pinned, the existing pin is returned. */
+CREATE OR REPLACE FUNCTION create_chat_pin(
+  IN _stream_id INTEGER,
+  IN _user_id INTEGER,
+  IN _chat_message_id INTEGER,
+  IN _msg VARCHAR,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT chat_message_id INTEGER,
+  OUT user_name VARCHAR,
+  OUT msg VARCHAR,
+  OUT date_created TIMESTAMPTZ
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+  pin_id INTEGER;
+BEGIN
+  IF _stream_id IS NULL OR _user_id IS NULL OR (_chat_message_id IS NULL AND LENGTH(COALESCE(_msg, '')) = 0) THEN
+    RETURN;
+  END IF;
+
+  IF _chat_message_id IS NOT NULL THEN
+    IF NOT EXISTS (
+      SELECT 1 FROM chat_messages cm
+      WHERE cm.id = _chat_message_id AND cm.stream_id = _stream_id AND cm.date_removed IS NULL
+    ) THEN
+      RETURN;
+    END IF;
+    _msg := NULL;
+
+    SELECT cp.id FROM chat_pins cp WHERE cp.chat_message_id = _chat_message_id INTO pin_id;
+  END IF;
+
+  IF pin_id IS NULL THEN
+    INSERT INTO chat_pins(stream_id, chat_message_id, msg, user_id)
+    VALUES (_stream_id, _chat_message_id, _msg, _user_id)
+    RETURNING chat_pins.id INTO pin_id;
+  END IF;
+
+  RETURN QUERY
+    SELECT fcp.id, fcp.stream_id, fcp.chat_message_id, fcp.user_name, fcp.msg, fcp.date_created
+    FROM filter_chat_pins(_stream_id) fcp
+    WHERE fcp.id = pin_id;
+END;
+$$;
+
+/* Create a stored function to unpin (delete) the pin of the chat (stream). */
+CREATE OR REPLACE FUNCTION delete_chat_pin(
+  IN _id INTEGER,
+  IN _stream_id INTEGER,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT chat_message_id INTEGER,
+  OUT user_name VARCHAR,
+  OUT msg VARCHAR,
+  OUT date_created TIMESTAMPTZ
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+BEGIN
+  IF _id IS NULL OR _stream_id IS NULL THEN
+    RETURN;
+  END IF;
+
+  SELECT cp.id, cp.stream_id, cp.chat_message_id,
+    COALESCE(mu.nickname, u.nickname), COALESCE(cm.msg, cp.msg), cp.date_created
+  FROM chat_pins cp
+    JOIN users u ON u.id = cp.user_id
+    LEFT JOIN chat_messages cm ON cm.id = cp.chat_message_id
+    LEFT JOIN users mu ON mu.id = cm.user_id
+  WHERE cp.id = _id AND cp.stream_id = _stream_id
+  INTO id, stream_id, chat_message_id, user_name, msg, date_created;
+
+  IF id IS NULL THEN
+    RETURN;
+  END IF;
+
+  DELETE FROM chat_pins WHERE chat_pins.id = _id;
+
+  RETURN NEXT;
+END;
+$$;
+
+-- **
@@ -0,0 +1,97 @@
+-- **
+-- The history of the stream state transitions. The "streams" table keeps only the latest "started", "paused" and
+-- "stopped" times, so every transition (with the user who made it) is saved to a separate table.
+
+-- **
+
+/* Create "stream_state_log" table. */
+CREATE TABLE stream_state_log (
+    id SERIAL PRIMARY KEY NOT NULL,
+    /* The stream whose state was changed. */
+    stream_id INTEGER NOT NULL REFERENCES streams(id) ON DELETE CASCADE,
+    /* The user who changed the state. (NULL - the user has been deleted.) */
+    user_id INTEGER NULL REFERENCES users(id) ON DELETE SET NULL,
+    /* The state of the stream before the transition. */
+    old_state stream_state NOT NULL,
+    /* The state of the stream after the transition. */
+    new_state stream_state NOT NULL,
+    /* Date and time of the transition. */
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+CREATE INDEX idx_stream_state_log_stream_id_created_at ON stream_state_log(stream_id, created_at);
+
+-- **
+
+/* Create a stored function to get the history of the state transitions of the stream. (StreamStateLog) */
+CREATE OR REPLACE FUNCTION get_stream_state_log(
+  IN _stream_id INTEGER,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT user_id INTEGER,
+  OUT nickname VARCHAR,
+  OUT old_state stream_state,
+  OUT new_state stream_state,
+  OUT created_at TIMESTAMPTZ
+) RETURNS SETOF record LANGUAGE sql STABLE
+AS $$
+  SELECT sl.id, sl.stream_id, sl.user_id, u.nickname, sl.old_state, sl.new_state, sl.created_at
+  FROM stream_state_log sl
+    LEFT JOIN users u ON u.id = sl.user_id
+  WHERE sl.stream_id = _stream_id
+  ORDER BY sl.created_at ASC, sl.id ASC;
+$$;
+
+-- **
+
+/* The set of output parameters changes, so the function must be recreated. */
+DROP FUNCTION IF EXISTS get_chat_stream_times(INTEGER, INTEGER);
+
+/* Create a stored function to get the times of the stream (the chat) for the transcript. (ChatStreamTimes)
+   "is_moderator" - whether the user "_user_id" is the chat moderator of this stream.
+   "paused_starts", "paused_ends" - the periods when the stream was paused (according to the history of the states).
+   The end of the current pause is NULL. */
+CREATE OR REPLACE FUNCTION get_chat_stream_times(
+  IN _stream_id INTEGER,
+  IN _user_id INTEGER,
+  OUT stream_id INTEGER,
+  OUT stream_owner INTEGER,
+  OUT stream_state VARCHAR,
+  OUT is_moderator BOOLEAN,
+  OUT starttime TIMESTAMPTZ,
+  OUT started TIMESTAMPTZ,
+  OUT paused TIMESTAMPTZ,
+  OUT stopped TIMESTAMPTZ,
+  OUT paused_starts TIMESTAMPTZ[],
+  OUT paused_ends TIMESTAMPTZ[]
+) RETURNS SETOF record LANGUAGE sql STABLE
+AS $$
+  SELECT
+    s.id AS stream_id,
+    s.user_id AS stream_owner,
+    CAST(s.state AS VARCHAR) AS stream_state,
+    EXISTS (SELECT 1 FROM stream_moderators sm WHERE sm.stream_id = s.id AND sm.user_id = _user_id) AS is_moderator,
+    s.starttime,
+    s.started,
+    s.paused,
+    s.stopped,
+    COALESCE(p.paused_starts, '{}') AS paused_starts,
+    COALESCE(p.paused_ends, '{}') AS paused_ends
+  FROM streams s
+    LEFT JOIN LATERAL (
+      -- The pause lasts until the next transition of the stream state.
+      SELECT
+        ARRAY_AGG(l.created_at ORDER BY l.created_at, l.id) AS paused_starts,
+        ARRAY_AGG(l.next_created_at ORDER BY l.created_at, l.id) AS paused_ends
+      FROM (
+        SELECT sl.id, sl.new_state, sl.created_at,
+          LEAD(sl.created_at) OVER (ORDER BY sl.created_at, sl.id) AS next_created_at
+        FROM stream_state_log sl
+        WHERE sl.stream_id = s.id
+      ) l
+      WHERE l.new_state = 'paused'
+    ) p ON TRUE
+  WHERE s.id = _stream_id;
+$$;
+
+-- **
@@ -0,0 +1,22 @@
+/* Restore the trigger function for table "streams". */
+CREATE OR REPLACE FUNCTION modify_stream_set_live()
+RETURNS TRIGGER 
+LANGUAGE plpgsql 
+AS $$
+BEGIN
+  NEW.live := NEW."state" IN ('preparing', 'started', 'paused');
+  IF NEW."state" = 'started' AND NEW.started IS NULL THEN
+    NEW.started := CURRENT_TIMESTAMP;
+  END IF;
+  IF NEW."state" = 'paused' THEN
+    NEW.paused := CURRENT_TIMESTAMP;
+  END IF;
+  IF NEW."state" = 'stopped' THEN
+    NEW.stopped := CURRENT_TIMESTAMP;
+  END IF;
+  RETURN NEW;
+END;
+$$;
+
+/* Drop the stored function to check the transition of the stream state. */
+DROP FUNCTION IF EXISTS stream_state_can_change;
@@ -0,0 +1,64 @@
+-- **
+-- The stream state machine. The legal transitions and their side effects are defined in the Rust module
+-- "vrb_dbase::enm_stream_state" (STREAM_STATE_TRANSITIONS, StreamStateFields), the same rules are checked here
+-- for any change of the "streams" table.
+
+-- **
+
+/* Create a stored function to check whether the transition from the old stream state to the new one is legal.
+   (see vrb_dbase::enm_stream_state::STREAM_STATE_TRANSITIONS) */
+CREATE OR REPLACE FUNCTION stream_state_can_change(
+  IN _old_state stream_state,
+  IN _new_state stream_state
+) RETURNS BOOLEAN
+LANGUAGE sql IMMUTABLE
+AS $$
+  SELECT CASE _old_state
+    WHEN 'waiting' THEN _new_state IN ('preparing')
+    WHEN 'preparing' THEN _new_state IN ('waiting', 'started', 'stopped')
+    WHEN 'started' THEN _new_state IN ('waiting', 'paused', 'stopped')
+    WHEN 'paused' THEN _new_state IN ('waiting', 'started', 'stopped')
+    WHEN 'stopped' THEN _new_state IN ('waiting', 'preparing')
+    ELSE FALSE
+  END;
+$$;
+
+-- **
+
+/* Update the trigger function for table "streams".
+   The side effects are applied only at the transition to a new state (see vrb_dbase::enm_stream_state::StreamStateFields).
+   The timestamps set by the request itself are kept. */
+CREATE OR REPLACE FUNCTION modify_stream_set_live()
+RETURNS TRIGGER 
+LANGUAGE plpgsql 
+AS $$
+BEGIN
+  NEW.live := NEW."state" IN ('preparing', 'started', 'paused');
+  IF TG_OP = 'UPDATE' THEN
+    IF NEW."state" = OLD."state" THEN
+      RETURN NEW;
+    END IF;
+    IF NOT stream_state_can_change(OLD."state", NEW."state") THEN
+      RAISE EXCEPTION 'transition from `%` to `%` is not allowed', OLD."state", NEW."state"
+        USING ERRCODE = 'check_violation';
+    END IF;
+    /* The timestamps not set by the request are cleared, so that they are set at the time of the transition. */
+    IF NEW.paused IS NOT DISTINCT FROM OLD.paused AND NEW."state" = 'paused' THEN
+      NEW.paused := NULL;
+    END IF;
+    IF NEW.stopped IS NOT DISTINCT FROM OLD.stopped AND NEW."state" = 'stopped' THEN
+      NEW.stopped := NULL;
+    END IF;
+  END IF;
+  IF NEW."state" = 'started' AND NEW.started IS NULL THEN
+    NEW.started := CURRENT_TIMESTAMP;
+  END IF;
+  IF NEW."state" = 'paused' AND NEW.paused IS NULL THEN
+    NEW.paused := CURRENT_TIMESTAMP;
+  END IF;
+  IF NEW."state" = 'stopped' AND NEW.stopped IS NULL THEN
+    NEW.stopped := CURRENT_TIMESTAMP;
+  END IF;
+  RETURN NEW;
+END;
+$$;
@@ -0,0 +1,22 @@
+-- **
+
+DROP FUNCTION IF EXISTS claim_webhook_deliveries(INTEGER, INTEGER);
+
+DROP FUNCTION IF EXISTS create_webhook_deliveries(INTEGER, INTEGER, VARCHAR, TEXT);
+
+-- **
+
+DROP INDEX IF EXISTS idx_webhook_deliveries_pending_next_attempt_at;
+DROP INDEX IF EXISTS idx_webhook_deliveries_webhook_id_created_at;
+
+DROP TABLE IF EXISTS webhook_deliveries;
+
+DROP TYPE IF EXISTS webhook_delivery_status;
+
+-- **
+
+DROP INDEX IF EXISTS idx_webhooks_user_id;
+
+DROP TABLE IF EXISTS webhooks;
+
+-- **
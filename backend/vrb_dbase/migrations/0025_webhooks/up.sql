@@ -0,0 +1,120 @@
+-- **
+-- Outgoing webhooks. The owner of streams subscribes a URL to the events of the streams and their chats.
+-- Each event is saved as a delivery, which is sent (signed with the secret of the webhook) and retried with backoff.
+
+-- **
+
+/* Create "webhooks" table. */
+CREATE TABLE webhooks (
+    id SERIAL PRIMARY KEY NOT NULL,
+    /* The owner of the webhook (and of the streams whose events are sent). */
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    /* The URL to which the events are sent (http or https). */
+    url VARCHAR(255) NOT NULL,
+    /* The secret for the HMAC signature of the payload. */
+    secret VARCHAR(255) NOT NULL,
+    /* The list of events the webhook is subscribed to ("stream.created", "chat.message", ...). */
+    events VARCHAR[] DEFAULT '{}' NOT NULL,
+    /* Inactive webhooks do not receive new events. */
+    is_active BOOLEAN DEFAULT TRUE NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+SELECT diesel_manage_updated_at('webhooks');
+
+CREATE INDEX idx_webhooks_user_id ON webhooks(user_id);
+
+-- **
+
+/* Create a type "webhook_delivery_status".
+  Accepts the following values:
+    pending - the delivery is waiting to be sent (default),
+    delivered - the delivery was received by the webhook URL,
+    failed - all attempts to send the delivery have failed
+ */
+CREATE TYPE webhook_delivery_status AS ENUM ('pending', 'delivered', 'failed');
+
+/* Create "webhook_deliveries" table. */
+CREATE TABLE webhook_deliveries (
+    id SERIAL PRIMARY KEY NOT NULL,
+    /* The webhook to which the event is sent. */
+    webhook_id INTEGER NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
+    /* The name of the event. */
+    event VARCHAR(255) NOT NULL,
+    /* The body (JSON) of the request. */
+    payload TEXT NOT NULL,
+    "status" webhook_delivery_status DEFAULT 'pending' NOT NULL,
+    /* The number of attempts made to send. */
+    attempts INTEGER DEFAULT 0 NOT NULL,
+    /* The time of the next attempt (for a "pending" delivery). */
+    next_attempt_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    /* The HTTP status of the response to the last attempt. */
+    response_status INTEGER NULL,
+    /* The error of the last attempt. */
+    error TEXT NULL,
+    /* The time of the successful delivery. */
+    delivered_at TIMESTAMPTZ NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+CREATE INDEX idx_webhook_deliveries_webhook_id_created_at ON webhook_deliveries(webhook_id, created_at);
+CREATE INDEX idx_webhook_deliveries_pending_next_attempt_at ON webhook_deliveries(next_attempt_at)
+  WHERE "status" = 'pending';
+
+-- **
+
+/* Create a stored function to add the deliveries of the event to all active webhooks of the user subscribed to it.
+   If "_user_id" is NULL, then the owner of the stream "_stream_id" is used. (WebhookDelivery) */
+CREATE OR REPLACE FUNCTION create_webhook_deliveries(
+  IN _user_id INTEGER,
+  IN _stream_id INTEGER,
+  IN _event VARCHAR,
+  IN _payload TEXT
+) RETURNS SETOF webhook_deliveries LANGUAGE sql
+AS $$
+  INSERT INTO webhook_deliveries(webhook_id, event, payload)
+  SELECT w.id, _event, _payload
+  FROM webhooks w
+  WHERE w.user_id = COALESCE(_user_id, (SELECT s.user_id FROM streams s WHERE s.id = _stream_id))
+    AND w.is_active AND _event = ANY(w.events)
+  ORDER BY w.id ASC
+  RETURNING *;
+$$;
+
+/* Create a stored function to take the "pending" deliveries whose time has come to be sent. (WebhookTask)
+   The next attempt of the taken deliveries is postponed for "_lock_secs" seconds, so that other processes
+   of the server do not send them at the same time. */
+CREATE OR REPLACE FUNCTION claim_webhook_deliveries(
+  IN _limit INTEGER,
+  IN _lock_secs INTEGER,
+  OUT id INTEGER,
+  OUT webhook_id INTEGER,
+  OUT url VARCHAR,
+  OUT secret VARCHAR,
+  OUT event VARCHAR,
+  OUT payload TEXT,
+  OUT attempts INTEGER
+) RETURNS SETOF record LANGUAGE sql
+AS $$
+  WITH due AS (
+    SELECT d.id
+    FROM webhook_deliveries d
+    WHERE d.status = 'pending' AND d.next_attempt_at <= CURRENT_TIMESTAMP
+    ORDER BY d.next_attempt_at ASC, d.id ASC
+    LIMIT _limit
+    FOR UPDATE SKIP LOCKED
+  ), claimed AS (
+    UPDATE webhook_deliveries d
+    SET next_attempt_at = CURRENT_TIMESTAMP + make_interval(secs => _lock_secs)
+    FROM due
+    WHERE d.id = due.id
+    RETURNING d.id, d.webhook_id, d.event, d.payload, d.attempts
+  )
+  SELECT c.id, c.webhook_id, w.url, w.secret, c.event, c.payload, c.attempts
+  FROM claimed c
+    INNER JOIN webhooks w ON w.id = c.webhook_id
+  ORDER BY c.id ASC;
+$$;
+
+-- **
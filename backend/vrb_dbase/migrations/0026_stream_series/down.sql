@@ -0,0 +1,14 @@
+-- **
+
+DROP INDEX IF EXISTS uq_idx_link_stream_series_to_streams_series_id_occurrence_time;
+DROP INDEX IF EXISTS uq_idx_link_stream_series_to_streams_stream_id;
+
+DROP TABLE IF EXISTS link_stream_series_to_streams;
+
+-- **
+
+DROP INDEX IF EXISTS idx_stream_series_user_id;
+
+DROP TABLE IF EXISTS stream_series;
+
+-- **
@@ -0,0 +1,48 @@
+-- **
+-- Recurring series of streams. A series holds a template of the stream and a recurrence rule (RRULE-style).
+-- Its occurrences are ordinary "streams" rows, which are created ahead of time within a horizon.
+
+-- **
+
+/* Create "stream_series" table. */
+CREATE TABLE stream_series (
+    id SERIAL PRIMARY KEY NOT NULL,
+    /* The owner of the series (and of its streams). */
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    /* The template of the streams of the series. */
+    title VARCHAR(255) NOT NULL,
+    descript TEXT DEFAULT '' NOT NULL,
+    "source" VARCHAR(255) DEFAULT 'obs' NOT NULL,
+    tags VARCHAR[] DEFAULT '{}' NOT NULL,
+    /* The start of the first occurrence (the anchor of the recurrence rule). */
+    starttime TIMESTAMPTZ NOT NULL,
+    /* The recurrence rule ("FREQ=WEEKLY;INTERVAL=1;BYDAY=MO,TH", ...). */
+    rrule VARCHAR(255) NOT NULL,
+    /* The time up to which the occurrences have already been created. */
+    materialized_until TIMESTAMPTZ NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+SELECT diesel_manage_updated_at('stream_series');
+
+CREATE INDEX idx_stream_series_user_id ON stream_series(user_id);
+
+-- **
+
+/* Create "link_stream_series_to_streams" table. */
+CREATE TABLE link_stream_series_to_streams (
+    id SERIAL PRIMARY KEY NOT NULL,
+    /* Stream Series id */
+    stream_series_id INTEGER NOT NULL REFERENCES stream_series(id) ON DELETE CASCADE,
+    /* Stream id */
+    stream_id INTEGER NOT NULL REFERENCES streams(id) ON DELETE CASCADE,
+    /* The time of the occurrence according to the rule (it does not change when the stream is moved). */
+    occurrence_time TIMESTAMPTZ NOT NULL
+);
+
+CREATE UNIQUE INDEX uq_idx_link_stream_series_to_streams_stream_id ON link_stream_series_to_streams(stream_id);
+CREATE UNIQUE INDEX uq_idx_link_stream_series_to_streams_series_id_occurrence_time
+  ON link_stream_series_to_streams(stream_series_id, occurrence_time);
+
+-- **
@@ -0,0 +1,6 @@
+-- **
+
+/* Remove the "num_token" column from the "user_email_change" table. */
+ALTER TABLE user_email_change DROP COLUMN IF EXISTS num_token;
+
+-- **
@@ -0,0 +1,12 @@
+-- **
+-- The number of the confirmation token is stored in the request to change the user's email.
+
+-- **
+
+/* The tokens of the existing requests do not contain this number, so these requests can no longer be confirmed. */
+DELETE FROM user_email_change;
+
+/* Add the "num_token" column to the "user_email_change" table. (The token is valid only for this request.) */
+ALTER TABLE user_email_change ADD COLUMN num_token INTEGER NOT NULL;
+
+-- **
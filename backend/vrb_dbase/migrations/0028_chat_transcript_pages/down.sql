@@ -0,0 +1,5 @@
+-- **
+
+DROP FUNCTION IF EXISTS filter_chat_transcript(INTEGER, TIMESTAMPTZ, INTEGER, TIMESTAMPTZ, INTEGER);
+
+-- **
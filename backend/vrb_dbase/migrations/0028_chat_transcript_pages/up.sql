@@ -0,0 +1,56 @@
+-- **
+-- Chat transcript export page by page (messages with the same date are paged by their ID).
+
+-- **
+
+/* Create a stored function to get the next page of "chat_message" entities for the transcript.
+   The messages are sorted by ("date_created", "id"), the page starts after the message ("_after_date", "_after_id"). */
+CREATE OR REPLACE FUNCTION filter_chat_transcript(
+  IN _stream_id INTEGER,
+  IN _after_date TIMESTAMPTZ,
+  IN _after_id INTEGER,
+  IN _max_date_created TIMESTAMPTZ,
+  IN _rec_limit INTEGER,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT user_id INTEGER,
+  OUT user_name VARCHAR,
+  OUT msg VARCHAR,
+  OUT date_created TIMESTAMPTZ,
+  OUT date_changed TIMESTAMPTZ,
+  OUT date_removed TIMESTAMPTZ,
+  OUT cnt_logs INTEGER,
+  OUT reply_to INTEGER,
+  OUT reply_user_name VARCHAR,
+  OUT reply_msg VARCHAR
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+BEGIN
+  IF _max_date_created IS NULL THEN
+    _max_date_created := CURRENT_TIMESTAMP;
+  END IF;
+  IF _rec_limit IS NULL THEN
+    _rec_limit := 20;
+  END IF;
+
+  RETURN QUERY
+    SELECT cm.id, cm.stream_id, cm.user_id, u.nickname as user_name, cm.msg,
+      cm.date_created, cm.date_changed, cm.date_removed,
+      (SELECT CAST(COUNT(*) AS INTEGER) FROM chat_message_logs cml WHERE cml.chat_message_id = cm.id) AS cnt_logs,
+      cm.reply_to,
+      pu.nickname AS reply_user_name,
+      CASE WHEN pm.date_removed IS NULL THEN pm.msg ELSE NULL END AS reply_msg
+    FROM chat_messages cm
+      JOIN users u ON u.id = cm.user_id
+      LEFT JOIN chat_messages pm ON pm.id = cm.reply_to
+      LEFT JOIN users pu ON pu.id = pm.user_id
+    WHERE cm.stream_id = _stream_id
+      AND (_after_date IS NULL OR (cm.date_created, cm.id) > (_after_date, COALESCE(_after_id, 0)))
+      AND cm.date_created < _max_date_created
+    ORDER BY cm.date_created ASC, cm.id ASC
+    LIMIT _rec_limit;
+END;
+$$;
+
+-- **
@@ -0,0 +1,88 @@
+-- **
+-- The text of the found chat messages is escaped before the matches are highlighted (the highlight is HTML markup).
+
+-- **
+
+/* Create a stored function to replace the HTML special characters with their entities. */
+CREATE OR REPLACE FUNCTION html_escape(
+  IN _text TEXT
+) RETURNS TEXT LANGUAGE sql IMMUTABLE
+AS $$
+  SELECT replace(replace(replace(replace(replace(_text, '&', '&amp;'), '<', '&lt;'), '>', '&gt;'), '"', '&quot;'), '''', '&#39;');
+$$;
+
+/* Create a stored function to search for "chat_message" entities by text and (or) by author.
+   The search is carried out in one stream ("_stream_id") or in all streams of the owner ("_owner_id").
+   If "_viewer_id" is specified, only the streams that this user owns or whose chat this user moderates are searched.
+   Removed messages are not searched. The text is HTML-escaped and the matches in it are highlighted with the "<mark>" tag.
+   Each row contains the total number of found messages ("cnt_total"), regardless of "_rec_offset" and "_rec_limit".
+   The search by text is a separate query, so that the "GIN" index can be used for it. */
+CREATE OR REPLACE FUNCTION search_chat_messages(
+  IN _text VARCHAR,
+  IN _user_name VARCHAR,
+  IN _stream_id INTEGER,
+  IN _owner_id INTEGER,
+  IN _viewer_id INTEGER,
+  IN _min_date_created TIMESTAMPTZ,
+  IN _max_date_created TIMESTAMPTZ,
+  IN _rec_offset INTEGER,
+  IN _rec_limit INTEGER,
+  OUT id INTEGER,
+  OUT stream_id INTEGER,
+  OUT user_id INTEGER,
+  OUT user_name VARCHAR,
+  OUT msg VARCHAR,
+  OUT highlight TEXT,
+  OUT date_created TIMESTAMPTZ,
+  OUT date_changed TIMESTAMPTZ,
+  OUT cnt_total INTEGER
+) RETURNS SETOF record LANGUAGE plpgsql STABLE
+AS $$
+BEGIN
+  IF _text IS NULL THEN
+    RETURN QUERY
+      SELECT cm.id, cm.stream_id, cm.user_id, u.nickname AS user_name, cm.msg,
+        html_escape(cm.msg) AS highlight,
+        cm.date_created, cm.date_changed,
+        CAST(COUNT(*) OVER() AS INTEGER) AS cnt_total
+      FROM chat_messages cm
+        JOIN users u ON u.id = cm.user_id
+        JOIN streams s ON s.id = cm.stream_id
+      WHERE cm.date_removed IS NULL
+        AND (_stream_id IS NULL OR cm.stream_id = _stream_id)
+        AND (_owner_id IS NULL OR s.user_id = _owner_id)
+        AND (_viewer_id IS NULL OR s.user_id = _viewer_id
+          OR EXISTS (SELECT 1 FROM stream_moderators sm WHERE sm.stream_id = s.id AND sm.user_id = _viewer_id))
+        AND (_user_name IS NULL OR u.nickname = _user_name)
+        AND (_min_date_created IS NULL OR _min_date_created <= cm.date_created)
+        AND (_max_date_created IS NULL OR cm.date_created <= _max_date_created)
+      ORDER BY cm.date_created DESC, cm.id DESC
+      OFFSET COALESCE(_rec_offset, 0)
+      LIMIT _rec_limit;
+  ELSE
+    RETURN QUERY
+      SELECT cm.id, cm.stream_id, cm.user_id, u.nickname AS user_name, cm.msg,
+        ts_headline('simple', html_escape(cm.msg), websearch_to_tsquery('simple', _text),
+          'StartSel=<mark>, StopSel=</mark>, HighlightAll=true') AS highlight,
+        cm.date_created, cm.date_changed,
+        CAST(COUNT(*) OVER() AS INTEGER) AS cnt_total
+      FROM chat_messages cm
+        JOIN users u ON u.id = cm.user_id
+        JOIN streams s ON s.id = cm.stream_id
+      WHERE cm.date_removed IS NULL
+        AND to_tsvector('simple', cm.msg) @@ websearch_to_tsquery('simple', _text)
+        AND (_stream_id IS NULL OR cm.stream_id = _stream_id)
+        AND (_owner_id IS NULL OR s.user_id = _owner_id)
+        AND (_viewer_id IS NULL OR s.user_id = _viewer_id
+          OR EXISTS (SELECT 1 FROM stream_moderators sm WHERE sm.stream_id = s.id AND sm.user_id = _viewer_id))
+        AND (_user_name IS NULL OR u.nickname = _user_name)
+        AND (_min_date_created IS NULL OR _min_date_created <= cm.date_created)
+        AND (_max_date_created IS NULL OR cm.date_created <= _max_date_created)
+      ORDER BY cm.date_created DESC, cm.id DESC
+      OFFSET COALESCE(_rec_offset, 0)
+      LIMIT _rec_limit;
+  END IF;
+END;
+$$;
+
+-- **
@@ -0,0 +1,6 @@
+-- **
+
+/* Remove the "time_zone" column from the "stream_series" table. */
+ALTER TABLE stream_series DROP COLUMN IF EXISTS time_zone;
+
+-- **
@@ -0,0 +1,9 @@
+-- **
+-- The time zone of the series of streams. The occurrences keep the local time of day of the first stream in this time zone.
+
+-- **
+
+/* Add the "time_zone" column to the "stream_series" table. (The name of the time zone: "Europe/Berlin", ...) */
+ALTER TABLE stream_series ADD COLUMN time_zone VARCHAR(64) DEFAULT 'UTC' NOT NULL;
+
+-- **
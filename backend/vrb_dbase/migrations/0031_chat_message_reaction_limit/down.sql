@@ -0,0 +1,57 @@
+-- **
+
+DROP FUNCTION IF EXISTS modify_chat_message_reaction(INTEGER, INTEGER, INTEGER, VARCHAR, BOOLEAN, INTEGER);
+
+/* Restore the stored function to add (_is_add = TRUE) or remove (_is_add = FALSE) the user's reaction to the message.
+   The message must be in the specified stream and must not be deleted.
+   Returns the number of reactions (of this emoji) and the change in this number ("delta": 1, 0, -1). */
+CREATE OR REPLACE FUNCTION modify_chat_message_reaction(
+  IN _chat_message_id INTEGER,
+  IN _stream_id INTEGER,
+  IN _user_id INTEGER,
+  IN _emoji VARCHAR,
+  IN _is_add BOOLEAN,
+  OUT chat_message_id INTEGER,
+  OUT emoji VARCHAR,
+  OUT cnt INTEGER,
+  OUT delta INTEGER
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+  rec_cnt INTEGER;
+BEGIN
+  IF _chat_message_id IS NULL OR _stream_id IS NULL OR _user_id IS NULL OR LENGTH(COALESCE(_emoji, '')) = 0 THEN
+    RETURN;
+  END IF;
+
+  IF NOT EXISTS (
+    SELECT 1 FROM chat_messages cm
+    WHERE cm.id = _chat_message_id AND cm.stream_id = _stream_id AND cm.date_removed IS NULL
+  ) THEN
+    RETURN;
+  END IF;
+
+  IF _is_add THEN
+    INSERT INTO chat_message_reactions(chat_message_id, user_id, emoji)
+    VALUES (_chat_message_id, _user_id, _emoji)
+    ON CONFLICT DO NOTHING;
+    GET DIAGNOSTICS rec_cnt = ROW_COUNT;
+    delta := rec_cnt;
+  ELSE
+    DELETE FROM chat_message_reactions
+    WHERE chat_message_reactions.chat_message_id = _chat_message_id
+      AND chat_message_reactions.user_id = _user_id
+      AND chat_message_reactions.emoji = _emoji;
+    GET DIAGNOSTICS rec_cnt = ROW_COUNT;
+    delta := -rec_cnt;
+  END IF;
+
+  SELECT CAST(COUNT(*) AS INTEGER) FROM chat_message_reactions cmr
+  WHERE cmr.chat_message_id = _chat_message_id AND cmr.emoji = _emoji
+  INTO cnt;
+
+  RETURN QUERY SELECT _chat_message_id, _emoji, cnt, delta;
+END;
+$$;
+
+-- **
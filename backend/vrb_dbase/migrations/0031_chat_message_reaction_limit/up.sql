@@ -0,0 +1,82 @@
+-- **
+-- The number of different reactions of one user to one message is limited.
+
+-- **
+
+/* The function gets the maximum number of reactions and the result field "is_limit". */
+DROP FUNCTION IF EXISTS modify_chat_message_reaction(INTEGER, INTEGER, INTEGER, VARCHAR, BOOLEAN);
+
+/* Create a stored function to add (_is_add = TRUE) or remove (_is_add = FALSE) the user's reaction to the message.
+   The message must be in the specified stream and must not be deleted.
+   The user can add no more than "_max_count" different reactions to the message, otherwise "is_limit" is TRUE.
+   Returns the number of reactions (of this emoji) and the change in this number ("delta": 1, 0, -1). */
+CREATE OR REPLACE FUNCTION modify_chat_message_reaction(
+  IN _chat_message_id INTEGER,
+  IN _stream_id INTEGER,
+  IN _user_id INTEGER,
+  IN _emoji VARCHAR,
+  IN _is_add BOOLEAN,
+  IN _max_count INTEGER,
+  OUT chat_message_id INTEGER,
+  OUT emoji VARCHAR,
+  OUT cnt INTEGER,
+  OUT delta INTEGER,
+  OUT is_limit BOOLEAN
+) RETURNS SETOF record LANGUAGE plpgsql
+AS $$
+DECLARE
+  rec_cnt INTEGER;
+BEGIN
+  IF _chat_message_id IS NULL OR _stream_id IS NULL OR _user_id IS NULL OR LENGTH(COALESCE(_emoji, '')) = 0 THEN
+    RETURN;
+  END IF;
+
+  IF NOT EXISTS (
+    SELECT 1 FROM chat_messages cm
+    WHERE cm.id = _chat_message_id AND cm.stream_id = _stream_id AND cm.date_removed IS NULL
+  ) THEN
+    RETURN;
+  END IF;
+
+  is_limit := FALSE;
+  delta := 0;
+  IF _is_add THEN
+    -- The reactions of the user to the message are added one at a time, so that the limit is not exceeded.
+    PERFORM pg_advisory_xact_lock(_chat_message_id, _user_id);
+
+    IF NOT EXISTS (
+      SELECT 1 FROM chat_message_reactions cmr
+      WHERE cmr.chat_message_id = _chat_message_id AND cmr.user_id = _user_id AND cmr.emoji = _emoji
+    ) THEN
+      SELECT CAST(COUNT(*) AS INTEGER) FROM chat_message_reactions cmr
+      WHERE cmr.chat_message_id = _chat_message_id AND cmr.user_id = _user_id
+      INTO rec_cnt;
+
+      IF _max_count IS NOT NULL AND _max_count <= rec_cnt THEN
+        is_limit := TRUE;
+      ELSE
+        INSERT INTO chat_message_reactions(chat_message_id, user_id, emoji)
+        VALUES (_chat_message_id, _user_id, _emoji)
+        ON CONFLICT DO NOTHING;
+        GET DIAGNOSTICS rec_cnt = ROW_COUNT;
+        delta := rec_cnt;
+      END IF;
+    END IF;
+  ELSE
+    DELETE FROM chat_message_reactions
+    WHERE chat_message_reactions.chat_message_id = _chat_message_id
+      AND chat_message_reactions.user_id = _user_id
+      AND chat_message_reactions.emoji = _emoji;
+    GET DIAGNOSTICS rec_cnt = ROW_COUNT;
+    delta := -rec_cnt;
+  END IF;
+
+  SELECT CAST(COUNT(*) AS INTEGER) FROM chat_message_reactions cmr
+  WHERE cmr.chat_message_id = _chat_message_id AND cmr.emoji = _emoji
+  INTO cnt;
+
+  RETURN QUERY SELECT _chat_message_id, _emoji, cnt, delta, is_limit;
+END;
+$$;
+
+-- **
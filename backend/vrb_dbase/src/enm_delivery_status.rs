@@ -0,0 +1,20 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, diesel_derive_enum::DbEnum, ToSchema)]
+#[ExistingTypePath = "crate::schema::sql_types::WebhookDeliveryStatus"]
+#[DbValueStyle = "snake_case"] // BazQuxx => "baz_quxx"
+#[serde(rename_all = "lowercase")]
+pub enum DeliveryStatus {
+    Pending, // (default)
+    Delivered,
+    Failed,
+}
+
+impl fmt::Display for DeliveryStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string(&self).unwrap().replace("\"", ""))
+    }
+}
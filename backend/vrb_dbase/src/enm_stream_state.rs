@@ -1,5 +1,6 @@
 use std::fmt;
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -21,8 +22,134 @@ impl fmt::Display for StreamState {
     }
 }
 
+// ** Section: Stream state machine **
+// The same rules are checked by the SQL function "stream_state_can_change" (migration "0024_stream_state_machine").
+
+/// List of legal transitions: the old state and the states into which it can be switched.
+pub const STREAM_STATE_TRANSITIONS: [(StreamState, &[StreamState]); 5] = [
+    (StreamState::Waiting, &[StreamState::Preparing]),
+    (StreamState::Preparing, &[StreamState::Waiting, StreamState::Started, StreamState::Stopped]),
+    (StreamState::Started, &[StreamState::Waiting, StreamState::Paused, StreamState::Stopped]),
+    (StreamState::Paused, &[StreamState::Waiting, StreamState::Started, StreamState::Stopped]),
+    (StreamState::Stopped, &[StreamState::Waiting, StreamState::Preparing]),
+];
+
 impl StreamState {
+    /// Checks whether the state is "live" ("preparing", "started", "paused").
+    /// A user can have only one stream in the "live" state.
     pub fn is_live(stream_state: StreamState) -> bool {
         stream_state == StreamState::Preparing || stream_state == StreamState::Started || stream_state == StreamState::Paused
     }
+    /// Get a list of states into which the current state can be switched.
+    pub fn next_states(&self) -> &'static [StreamState] {
+        STREAM_STATE_TRANSITIONS
+            .iter()
+            .find(|(state, _)| state == self)
+            .map(|(_, next)| *next)
+            .unwrap_or(&[])
+    }
+    /// Checks whether the transition from the current state to the new one is legal.
+    /// The new state must be different from the current one.
+    pub fn can_change_to(&self, new_state: StreamState) -> bool {
+        self.next_states().contains(&new_state)
+    }
+}
+
+/// The fields of the stream that depend on its state.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StreamStateFields {
+    pub state: StreamState,
+    pub live: bool,
+    pub started: Option<DateTime<Utc>>,
+    pub paused: Option<DateTime<Utc>>,
+    pub stopped: Option<DateTime<Utc>>,
+}
+
+impl StreamStateFields {
+    #[rustfmt::skip]
+    pub fn new(
+        state: StreamState, started: Option<DateTime<Utc>>, paused: Option<DateTime<Utc>>, stopped: Option<DateTime<Utc>>,
+    ) -> Self {
+        let live = StreamState::is_live(state);
+        StreamStateFields { state, live, started, paused, stopped }
+    }
+    /// Get the values of the fields after the transition to the new state (the side effects of the transition):
+    /// - "live" is set for the states "preparing", "started", "paused";
+    /// - "started" is set at the first transition to the "started" state;
+    /// - "paused" is set at each transition to the "paused" state;
+    /// - "stopped" is set at each transition to the "stopped" state.
+    pub fn change_to(&self, new_state: StreamState, now: DateTime<Utc>) -> Self {
+        if self.state == new_state {
+            return *self;
+        }
+        StreamStateFields {
+            state: new_state,
+            live: StreamState::is_live(new_state),
+            started: if new_state == StreamState::Started {
+                self.started.or(Some(now))
+            } else {
+                self.started
+            },
+            paused: if new_state == StreamState::Paused { Some(now) } else { self.paused },
+            stopped: if new_state == StreamState::Stopped { Some(now) } else { self.stopped },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    const ALL_STATES: [StreamState; 5] = [
+        StreamState::Waiting,
+        StreamState::Preparing,
+        StreamState::Started,
+        StreamState::Paused,
+        StreamState::Stopped,
+    ];
+
+    #[test]
+    fn test_stream_state_can_change_to() {
+        #[rustfmt::skip]
+        let legal = [
+            (StreamState::Waiting, StreamState::Preparing),
+            (StreamState::Preparing, StreamState::Waiting), (StreamState::Preparing, StreamState::Started),
+            (StreamState::Preparing, StreamState::Stopped),
+            (StreamState::Started, StreamState::Waiting), (StreamState::Started, StreamState::Paused),
+            (StreamState::Started, StreamState::Stopped),
+            (StreamState::Paused, StreamState::Waiting), (StreamState::Paused, StreamState::Started),
+            (StreamState::Paused, StreamState::Stopped),
+            (StreamState::Stopped, StreamState::Waiting), (StreamState::Stopped, StreamState::Preparing),
+        ];
+        for old_state in ALL_STATES {
+            for new_state in ALL_STATES {
+                let expected = legal.contains(&(old_state, new_state));
+                assert_eq!(old_state.can_change_to(new_state), expected, "{} -> {}", old_state, new_state);
+            }
+        }
+    }
+    #[test]
+    fn test_stream_state_fields_change_to() {
+        let now = Utc::now();
+        let before = now - Duration::minutes(10);
+        let fields = StreamStateFields::new(StreamState::Preparing, None, None, None);
+        assert_eq!(fields.live, true);
+
+        let started = fields.change_to(StreamState::Started, before);
+        assert_eq!(started, StreamStateFields::new(StreamState::Started, Some(before), None, None));
+
+        let paused = started.change_to(StreamState::Paused, now);
+        assert_eq!(paused, StreamStateFields::new(StreamState::Paused, Some(before), Some(now), None));
+        // The start time of the stream does not change after the pause.
+        let resumed = paused.change_to(StreamState::Started, now);
+        assert_eq!(resumed, StreamStateFields::new(StreamState::Started, Some(before), Some(now), None));
+
+        let stopped = resumed.change_to(StreamState::Stopped, now);
+        assert_eq!(stopped, StreamStateFields::new(StreamState::Stopped, Some(before), Some(now), Some(now)));
+        assert_eq!(stopped.live, false);
+        // Without a transition, the fields do not change.
+        assert_eq!(stopped.change_to(StreamState::Stopped, now + Duration::minutes(1)), stopped);
+    }
 }
@@ -1,4 +1,5 @@
 pub mod dbase;
+pub mod enm_delivery_status;
 pub mod enm_stream_state;
 pub mod enm_user_role;
 #[rustfmt::skip]
@@ -8,6 +8,10 @@ pub mod sql_types {
     #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "user_role"))]
     pub struct UserRole;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "webhook_delivery_status"))]
+    pub struct WebhookDeliveryStatus;
 }
 
 diesel::table! {
@@ -16,6 +20,7 @@ diesel::table! {
         owner_id -> Int4,
         blocked_id -> Int4,
         block_date -> Timestamptz,
+        block_until -> Nullable<Timestamptz>,
     }
 }
 
@@ -29,6 +34,26 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    chat_message_mentions (id) {
+        id -> Int4,
+        chat_message_id -> Int4,
+        user_id -> Int4,
+        date_created -> Timestamptz,
+        date_read -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    chat_message_reactions (chat_message_id, user_id, emoji) {
+        chat_message_id -> Int4,
+        user_id -> Int4,
+        #[max_length = 32]
+        emoji -> Varchar,
+        date_created -> Timestamptz,
+    }
+}
+
 diesel::table! {
     chat_messages (id) {
         id -> Int4,
@@ -39,6 +64,19 @@ diesel::table! {
         date_created -> Timestamptz,
         date_changed -> Nullable<Timestamptz>,
         date_removed -> Nullable<Timestamptz>,
+        reply_to -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    chat_pins (id) {
+        id -> Int4,
+        stream_id -> Int4,
+        chat_message_id -> Nullable<Int4>,
+        #[max_length = 255]
+        msg -> Nullable<Varchar>,
+        user_id -> Int4,
+        date_created -> Timestamptz,
     }
 }
 
@@ -50,6 +88,15 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    link_stream_series_to_streams (id) {
+        id -> Int4,
+        stream_series_id -> Int4,
+        stream_id -> Int4,
+        occurrence_time -> Timestamptz,
+    }
+}
+
 diesel::table! {
     profiles (user_id) {
         user_id -> Int4,
@@ -66,9 +113,62 @@ diesel::table! {
 }
 
 diesel::table! {
-    sessions (user_id) {
+    sessions (id) {
+        id -> Int4,
+        user_id -> Int4,
+        num_token -> Int4,
+        #[max_length = 255]
+        device -> Nullable<Varchar>,
+        #[max_length = 255]
+        user_agent -> Nullable<Varchar>,
+        #[max_length = 64]
+        ip_address -> Nullable<Varchar>,
+        created_at -> Timestamptz,
+        last_seen_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    stream_moderators (id) {
+        id -> Int4,
+        stream_id -> Int4,
         user_id -> Int4,
-        num_token -> Nullable<Int4>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    stream_series (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 255]
+        title -> Varchar,
+        descript -> Text,
+        #[max_length = 255]
+        source -> Varchar,
+        tags -> Array<Varchar>,
+        starttime -> Timestamptz,
+        #[max_length = 255]
+        rrule -> Varchar,
+        materialized_until -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        #[max_length = 64]
+        time_zone -> Varchar,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::StreamState;
+
+    stream_state_log (id) {
+        id -> Int4,
+        stream_id -> Int4,
+        user_id -> Nullable<Int4>,
+        old_state -> StreamState,
+        new_state -> StreamState,
+        created_at -> Timestamptz,
     }
 }
 
@@ -106,6 +206,30 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    user_email_change (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 255]
+        new_email -> Varchar,
+        final_date -> Timestamptz,
+        num_token -> Int4,
+    }
+}
+
+diesel::table! {
+    user_nickname_history (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 255]
+        old_nickname -> Varchar,
+        #[max_length = 255]
+        new_nickname -> Varchar,
+        changed_at -> Timestamptz,
+        reserved_until -> Timestamptz,
+    }
+}
+
 diesel::table! {
     user_recovery (id) {
         id -> Int4,
@@ -127,6 +251,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    user_totp (user_id) {
+        user_id -> Int4,
+        #[max_length = 64]
+        secret -> Varchar,
+        is_enabled -> Bool,
+        recovery_codes -> Array<Text>,
+        last_step -> Nullable<Int8>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::UserRole;
@@ -142,30 +279,97 @@ diesel::table! {
         role -> UserRole,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        suspended_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::WebhookDeliveryStatus;
+
+    webhook_deliveries (id) {
+        id -> Int4,
+        webhook_id -> Int4,
+        #[max_length = 255]
+        event -> Varchar,
+        payload -> Text,
+        status -> WebhookDeliveryStatus,
+        attempts -> Int4,
+        next_attempt_at -> Timestamptz,
+        response_status -> Nullable<Int4>,
+        error -> Nullable<Text>,
+        delivered_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    webhooks (id) {
+        id -> Int4,
+        user_id -> Int4,
+        #[max_length = 255]
+        url -> Varchar,
+        #[max_length = 255]
+        secret -> Varchar,
+        events -> Array<Varchar>,
+        is_active -> Bool,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
     }
 }
 
 diesel::joinable!(chat_message_logs -> chat_messages (chat_message_id));
+diesel::joinable!(chat_message_mentions -> chat_messages (chat_message_id));
+diesel::joinable!(chat_message_mentions -> users (user_id));
+diesel::joinable!(chat_message_reactions -> chat_messages (chat_message_id));
+diesel::joinable!(chat_message_reactions -> users (user_id));
 diesel::joinable!(chat_messages -> streams (stream_id));
 diesel::joinable!(chat_messages -> users (user_id));
+diesel::joinable!(chat_pins -> chat_messages (chat_message_id));
+diesel::joinable!(chat_pins -> streams (stream_id));
+diesel::joinable!(chat_pins -> users (user_id));
+diesel::joinable!(link_stream_series_to_streams -> stream_series (stream_series_id));
+diesel::joinable!(link_stream_series_to_streams -> streams (stream_id));
 diesel::joinable!(link_stream_tags_to_streams -> stream_tags (stream_tag_id));
 diesel::joinable!(link_stream_tags_to_streams -> streams (stream_id));
 diesel::joinable!(profiles -> users (user_id));
 diesel::joinable!(sessions -> users (user_id));
+diesel::joinable!(stream_moderators -> streams (stream_id));
+diesel::joinable!(stream_moderators -> users (user_id));
+diesel::joinable!(stream_series -> users (user_id));
+diesel::joinable!(stream_state_log -> streams (stream_id));
+diesel::joinable!(stream_state_log -> users (user_id));
 diesel::joinable!(stream_tags -> users (user_id));
 diesel::joinable!(streams -> users (user_id));
+diesel::joinable!(user_email_change -> users (user_id));
+diesel::joinable!(user_nickname_history -> users (user_id));
 diesel::joinable!(user_recovery -> users (user_id));
+diesel::joinable!(user_totp -> users (user_id));
+diesel::joinable!(webhook_deliveries -> webhooks (webhook_id));
+diesel::joinable!(webhooks -> users (user_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     blocked_users,
     chat_message_logs,
+    chat_message_mentions,
+    chat_message_reactions,
     chat_messages,
+    chat_pins,
+    link_stream_series_to_streams,
     link_stream_tags_to_streams,
     profiles,
     sessions,
+    stream_moderators,
+    stream_series,
+    stream_state_log,
     stream_tags,
     streams,
+    user_email_change,
+    user_nickname_history,
     user_recovery,
     user_registration,
+    user_totp,
     users,
+    webhook_deliveries,
+    webhooks,
 );
@@ -7,6 +7,8 @@ pub const LOGO_MAX_SIZE: &str = "0";
 pub const LOGO_VALID_TYPES: &str = "image/jpeg,image/gif,image/png,image/bmp";
 pub const LOGO_MAX_WIDTH: &str = "0";
 pub const LOGO_MAX_HEIGHT: &str = "0";
+pub const SERIES_HORIZON: &str = "28";
+pub const SERIES_POLL: &str = "3600";
 
 // Stream Logo Properties
 #[derive(Debug, Clone)]
@@ -25,6 +27,10 @@ pub struct ConfigStrm {
     pub strm_logo_max_width: u32,
     // Maximum height for a logo file.
     pub strm_logo_max_height: u32,
+    // The horizon (in days) within which the streams of a recurring series are created in advance.
+    pub strm_series_horizon: u32,
+    // Interval (in seconds) for creating the streams of recurring series that have come within the horizon.
+    pub strm_series_poll: u64,
 }
 
 impl ConfigStrm {
@@ -52,6 +58,14 @@ impl ConfigStrm {
         #[rustfmt::skip]
         let logo_max_height: u32 = env::var(consts::STRM_LOGO_MAX_HEIGHT).unwrap_or(max_height).trim().parse().unwrap();
 
+        let series_horizon = SERIES_HORIZON.to_string();
+        #[rustfmt::skip]
+        let strm_series_horizon: u32 = env::var(consts::STRM_SERIES_HORIZON).unwrap_or(series_horizon).trim().parse().unwrap();
+
+        let series_poll = SERIES_POLL.to_string();
+        #[rustfmt::skip]
+        let strm_series_poll: u64 = env::var(consts::STRM_SERIES_POLL).unwrap_or(series_poll).trim().parse().unwrap();
+
         ConfigStrm {
             strm_logo_files_dir,
             strm_logo_max_size: logo_max_size,
@@ -59,6 +73,8 @@ impl ConfigStrm {
             strm_logo_ext,
             strm_logo_max_width: logo_max_width,
             strm_logo_max_height: logo_max_height,
+            strm_series_horizon: strm_series_horizon.max(1),
+            strm_series_poll: strm_series_poll.max(1),
         }
     }
 
@@ -109,5 +125,7 @@ pub fn get_test_config() -> ConfigStrm {
         strm_logo_ext: None,
         strm_logo_max_width: LOGO_MAX_WIDTH.parse().unwrap(),
         strm_logo_max_height: LOGO_MAX_HEIGHT.parse().unwrap(),
+        strm_series_horizon: SERIES_HORIZON.parse().unwrap(),
+        strm_series_poll: SERIES_POLL.parse().unwrap(),
     }
 }
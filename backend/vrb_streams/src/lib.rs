@@ -2,6 +2,13 @@ pub mod config_strm;
 pub mod stream_controller;
 pub mod stream_models;
 pub mod stream_orm;
+pub mod stream_series_controller;
+pub mod stream_series_models;
+pub mod stream_series_orm;
+pub mod stream_series_scheduler;
+pub mod stream_series_test;
 pub mod stream_test_get;
+pub mod stream_test_moderators;
 pub mod stream_test_post_delete;
 pub mod stream_test_put;
+pub mod stream_test_timeline;
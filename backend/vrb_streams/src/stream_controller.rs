@@ -1,5 +1,6 @@
 use std::{borrow::Cow, fs, ops::Deref, path};
 
+use actix::SystemService;
 use actix_multipart::form::{MultipartForm, tempfile::TempFile, text::Text};
 use actix_web::{HttpResponse, delete, get, http::StatusCode, post, put, web};
 use chrono::{DateTime, Duration, SecondsFormat::Millis, Utc};
@@ -8,6 +9,7 @@ use mime::IMAGE;
 use serde_json::{self, json};
 use utoipa;
 use vrb_authent::authentication::{Authenticated, RequireAuth};
+use vrb_chats::{chat_event_ws::StreamEWS, chat_message::SendMessage, chat_ws_server::ChatWsServer};
 use vrb_common::{
     alias_path::alias_path_stream,
     api_error::{ApiError, code_to_str},
@@ -16,6 +18,10 @@ use vrb_common::{
 };
 use vrb_dbase::{enm_stream_state::StreamState, enm_user_role::UserRole};
 use vrb_tools::{cdis::coding, loading::dynamic_image};
+use vrb_webhooks::{
+    webhook_dispatcher::{FireWebhookEvent, WebhookDispatcher},
+    webhook_models::WebhookEvent,
+};
 
 #[cfg(not(all(test, feature = "mockdata")))]
 use crate::stream_orm::impls::StreamOrmApp;
@@ -24,8 +30,9 @@ use crate::stream_orm::tests::StreamOrmApp;
 use crate::{
     config_strm::{self, ConfigStrm},
     stream_models::{
-        self, CreateStreamInfoDto, ModifyStream, ModifyStreamInfoDto, SearchStreamEventDto, SearchStreamInfoDto, SearchStreamPeriodDto,
-        StreamConfigDto, StreamEventPageDto, StreamInfoDto, StreamInfoPageDto, ToggleStreamStateDto,
+        self, CreateStreamInfoDto, CreateStreamModeratorDto, ModifyStream, ModifyStreamInfoDto, SearchStreamEventDto, SearchStreamInfoDto,
+        SearchStreamPeriodDto, StreamConfigDto, StreamEventPageDto, StreamInfoDto, StreamInfoPageDto, StreamModeratorDto, StreamStateError,
+        StreamTimelineDto, ToggleStreamStateDto,
     },
     stream_orm::StreamOrm,
 };
@@ -57,6 +64,8 @@ pub const MSG_INVALID_STREAM_STATE: &str = "invalid_stream_state";
 pub const MSG_EXIST_IS_ACTIVE_STREAM: &str = "exist_is_active_stream";
 
 // ** Section: Stream Delete **
+// ** Section: Stream Moderators **
+// ** Section: Stream Timeline **
 // ** **
 
 pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
@@ -79,7 +88,15 @@ pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
             // PUT /api/streams/{id}
             .service(put_stream)
             // DELETE /api/streams/{id}
-            .service(delete_stream);
+            .service(delete_stream)
+            // GET /api/streams/{id}/moderators
+            .service(get_stream_moderators)
+            // POST /api/streams/{id}/moderators
+            .service(post_stream_moderator)
+            // DELETE /api/streams/{id}/moderators/{user_id}
+            .service(delete_stream_moderator)
+            // GET /api/streams/{id}/timeline
+            .service(get_stream_timeline);
     }
 }
 
@@ -900,6 +917,8 @@ pub async fn post_stream(
     // Merge a "stream" and a corresponding list of "tags".
     let list = StreamInfoDto::merge_streams_and_tags(&[stream], &stream_tags);
     let stream_info_dto = list[0].clone();
+    // Notify the webhooks of the owner of the stream.
+    fire_stream_webhook(WebhookEvent::StreamCreated, &stream_info_dto);
 
     Ok(HttpResponse::Created().json(stream_info_dto)) // 201
 }
@@ -993,6 +1012,8 @@ impl ModifyStreamForm {
 /// ```
 ///  
 /// Return the stream with updated data (`StreamInfoDto`) with status 200 or 204 (no content) if the stream is not found.
+/// 
+/// The new data of the stream is sent to all members of its chat room (the "stream" event of the websocket).
 ///
 #[utoipa::path(
     responses(
@@ -1200,6 +1221,10 @@ pub async fn put_stream(
         // Merge a "stream" and a corresponding list of "tags".
         let list = StreamInfoDto::merge_streams_and_tags(&[stream], &stream_tags);
         let stream_info_dto: StreamInfoDto = list[0].clone();
+        // Notify all members of its chat room about the change of the stream.
+        send_stream_ews(&stream_info_dto);
+        // Notify the webhooks of the owner of the stream.
+        fire_stream_webhook(WebhookEvent::StreamUpdated, &stream_info_dto);
 
         // If the file path starts with alice, then the file corresponds to the entity type.
         // And only then can the file be deleted.
@@ -1260,6 +1285,17 @@ pub async fn put_stream(
 /// 
 /// From "started" a stream can be moved to the "stopped" state.
 /// 
+/// From "stopped", the stream can be switched to the "preparing" state.
+/// 
+/// From any other state, the stream can be returned to the "waiting" state.
+/// 
+/// The legal transitions are defined by the stream state machine (`vrb_dbase::enm_stream_state`).
+/// 
+/// Each transition is saved to the history of the stream states along with the user who made it
+/// (see `GET /api/streams/{id}/timeline`).
+/// 
+/// The new state of the stream is sent to all members of its chat room (the "stream" event of the websocket).
+/// 
 /// One could call with following curl.
 /// ```text
 /// curl -i -X PUT http://localhost:8080/api/streams/toggle/1  -d '{"state": "started"}'
@@ -1351,67 +1387,37 @@ pub async fn put_toggle_state(
             .add_param(Cow::Borrowed("invalidState"), &json));
     }
 
-    let is_not_acceptable = match new_state {
-        StreamState::Preparing => vec![StreamState::Started, StreamState::Paused].contains(&stream.state),
-        StreamState::Started => vec![StreamState::Waiting, StreamState::Stopped].contains(&stream.state),
-        StreamState::Paused => vec![StreamState::Waiting, StreamState::Stopped, StreamState::Preparing].contains(&stream.state),
-        StreamState::Stopped => vec![StreamState::Waiting].contains(&stream.state),
-        _ => false,
-    };
-    if is_not_acceptable {
+    // Check the transition against the stream state machine.
+    if !stream.state.can_change_to(new_state) {
         let json = json!({ "oldState": &stream.state.to_string(), "newState": &new_state });
         error!("{}-{}; {}", code_to_str(StatusCode::NOT_ACCEPTABLE), MSG_INVALID_STREAM_STATE, json.to_string());
         return Err(ApiError::new(406, MSG_INVALID_STREAM_STATE) // 406
             .add_param(Cow::Borrowed("invalidState"), &json));
     }
-    // If the stream goes into active state, then
-    if vec![StreamState::Preparing, StreamState::Started, StreamState::Paused].contains(&new_state) {
-        let stream_orm2 = stream_orm.clone();
-        // find any stream in active state.
-        let res_stream2_tags = web::block(move || {
-            let res_stream2_tags = stream_orm2
-                .find_stream_by_params(None, opt_user_id, Some(true), false, &[id])
-                .map_err(|e| {
-                    error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
-                    ApiError::create(507, err::MSG_DATABASE, &e)
-                });
-            res_stream2_tags
-        })
-        .await
-        .map_err(|e| {
-            #[rustfmt::skip]
-            error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
-            ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
-        })?;
-        
-        let opt_stream2_tags = match res_stream2_tags { Ok(v) => v, Err(e) => return Err(e) };
-
-        if let Some((stream2, _tags)) = opt_stream2_tags {
-            let json = json!({ "id": stream2.id, "title": &stream2.title });
-            error!("{}-{}; {}", code_to_str(StatusCode::CONFLICT), MSG_EXIST_IS_ACTIVE_STREAM, json.to_string());
-            return Err(ApiError::new(409, MSG_EXIST_IS_ACTIVE_STREAM) // 409
-                .add_param(Cow::Borrowed("activeStream"), &json));
-        }
-    }
-
-    let modify_stream: ModifyStream = ModifyStream {
-        title: None,
-        descript: None,
-        logo: None,
-        starttime: None,
-        state: Some(new_state),
-        started: None,
-        paused: None,
-        stopped: None,
-        source: None,
-    };
+    let changed_by = user.id;
 
     let res_stream_tags = web::block(move || {
-        // Modify an entity (stream).
-        let res_stream_tags = stream_orm.modify_stream(id, opt_user_id, modify_stream, None)
-        .map_err(|e| {
-            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
-            ApiError::create(507, err::MSG_DATABASE, &e)
+        // Modify the state of an entity (stream) and add the transition to the history of states.
+        // The transition and the rule of a single live stream are checked with the streams of the owner locked.
+        let res_stream_tags = stream_orm.modify_stream_state(id, opt_user_id, new_state, changed_by)
+        .map_err(|e| match e {
+            StreamStateError::NotAllowed(old_state, new_state) => {
+                let json = json!({ "oldState": &old_state, "newState": &new_state });
+                error!("{}-{}; {}", code_to_str(StatusCode::NOT_ACCEPTABLE), MSG_INVALID_STREAM_STATE, json);
+                ApiError::new(406, MSG_INVALID_STREAM_STATE) // 406
+                    .add_param(Cow::Borrowed("invalidState"), &json)
+            }
+            StreamStateError::LiveExists(stream2) => {
+                // A user can have only one live stream.
+                let json = json!({ "id": stream2.id, "title": &stream2.title });
+                error!("{}-{}; {}", code_to_str(StatusCode::CONFLICT), MSG_EXIST_IS_ACTIVE_STREAM, json);
+                ApiError::new(409, MSG_EXIST_IS_ACTIVE_STREAM) // 409
+                    .add_param(Cow::Borrowed("activeStream"), &json)
+            }
+            StreamStateError::Database(e) => {
+                error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+                ApiError::create(507, err::MSG_DATABASE, &e) // 507
+            }
         });
         res_stream_tags
     })
@@ -1433,9 +1439,36 @@ pub async fn put_toggle_state(
     // Merge a "stream" and a corresponding list of "tags".
     let list = StreamInfoDto::merge_streams_and_tags(&[stream], &tags);
     let stream_info_dto: StreamInfoDto = list[0].clone();
+    // Send the new state of the stream to all members of its chat room.
+    send_stream_ews(&stream_info_dto);
+    // Notify the webhooks of the owner of the stream.
+    fire_stream_webhook(WebhookEvent::StreamState, &stream_info_dto);
+
     Ok(HttpResponse::Ok().json(stream_info_dto)) // 200
 }
 
+/** Send the state of the stream to all members of its chat room (the room ID is the stream ID). */
+pub fn send_stream_ews(stream_info_dto: &StreamInfoDto) {
+    let date_str = |value: DateTime<Utc>| value.to_rfc3339_opts(Millis, true);
+    let stream_ews = StreamEWS {
+        stream: stream_info_dto.state,
+        id: stream_info_dto.id,
+        live: stream_info_dto.live,
+        starttime: date_str(stream_info_dto.starttime),
+        started: stream_info_dto.started.map(date_str),
+        paused: stream_info_dto.paused.map(date_str),
+        stopped: stream_info_dto.stopped.map(date_str),
+    };
+    let stream_ews_str = serde_json::to_string(&stream_ews).unwrap();
+    ChatWsServer::from_registry().do_send(SendMessage(stream_info_dto.id, stream_ews_str));
+}
+
+/** Send the event of the stream to the webhooks of the owner of the stream. */
+pub fn fire_stream_webhook(event: WebhookEvent, stream_info_dto: &StreamInfoDto) {
+    let data = serde_json::to_value(stream_info_dto).unwrap();
+    WebhookDispatcher::from_registry().do_send(FireWebhookEvent::for_user(stream_info_dto.user_id, event, data));
+}
+
 // ** Section: Stream Delete **
 
 /// delete_stream
@@ -1522,16 +1555,388 @@ pub async fn delete_stream(
         // Merge a "stream" and a corresponding list of "tags".
         let list = StreamInfoDto::merge_streams_and_tags(&[stream], &stream_tags);
         let stream_info_dto = list[0].clone();
+        // Notify the webhooks of the owner of the stream.
+        fire_stream_webhook(WebhookEvent::StreamDeleted, &stream_info_dto);
         Ok(HttpResponse::Ok().json(stream_info_dto)) // 200
     } else {
         Ok(HttpResponse::NoContent().finish()) // 204
     }
 }
 
+// ** Section: Stream Moderators **
+
+/// Find the stream with the specified ID that belongs to the user (for the administrator, any stream).
+async fn find_own_stream(
+    stream_orm: web::Data<StreamOrmApp>,
+    id: i32,
+    opt_user_id: Option<i32>,
+) -> Result<Option<stream_models::Stream>, ApiError> {
+    let res_stream_tags = web::block(move || {
+        // Find a stream by ID.
+        let res_stream_tags = stream_orm
+            .find_stream_by_params(Some(id), opt_user_id, None, false, &[])
+            .map_err(|e| {
+                error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+                ApiError::create(507, err::MSG_DATABASE, &e) // 507
+            });
+        res_stream_tags
+    })
+    .await
+    .map_err(|e| {
+        #[rustfmt::skip]
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })?;
+
+    Ok(res_stream_tags?.map(|(stream, _tags)| stream))
+}
+
+/// get_stream_moderators
+///
+/// Get a list of chat moderators of the specified stream.
+/// Only the owner of the stream or the administrator can view the list.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X GET http://localhost:8080/api/streams/1/moderators
+/// ```
+///
+/// Return the list of moderators (`Vec<StreamModeratorDto>`) with status 200 or 204 (no content) if the stream is not found.
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "List of chat moderators of the stream.", body = Vec<StreamModeratorDto>),
+        (status = 204, description = "The stream with the specified ID was not found."),
+        (status = 416, description = "Error parsing input parameter. `curl -i -X GET http://localhost:8080/api/streams/2a/moderators`",
+            body = ApiError, example = json!(ApiError::create(416,
+                err::MSG_PARSING_TYPE_NOT_SUPPORTED, "`id` - invalid digit found in string (2a)"))),
+        (status = 506, description = "Blocking error.", body = ApiError, 
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError, 
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    params(("id", description = "Unique stream ID.")),
+    security(("bearer_auth" = [])),
+)]
+// GET /api/streams/{id}/moderators
+#[rustfmt::skip]
+#[get("/api/streams/{id}/moderators", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn get_stream_moderators(
+    authenticated: Authenticated,
+    stream_orm: web::Data<StreamOrmApp>,
+    request: actix_web::HttpRequest,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    // Get current user details.
+    let user = authenticated.deref();
+
+    // Get data from request.
+    let id_str = request.match_info().query("id").to_string();
+    let id = parser::parse_i32(&id_str).map_err(|e| {
+        let msg = format!("`{}` - {}", "id", &e);
+        error!("{}-{}; {}", code_to_str(StatusCode::RANGE_NOT_SATISFIABLE), err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg);
+        ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg) // 416
+    })?;
+
+    let opt_user_id: Option<i32> = if user.role == UserRole::Admin { None } else { Some(user.id) };
+    // Only the owner of the stream (or the administrator) can manage its moderators.
+    let opt_stream = find_own_stream(stream_orm.clone(), id, opt_user_id).await?;
+    if opt_stream.is_none() {
+        return Ok(HttpResponse::NoContent().finish()); // 204
+    }
+
+    let res_stream_moderators = web::block(move || {
+        // Get a list of chat moderators of the stream.
+        let res_data = stream_orm.get_stream_moderators(id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_data
+    })
+    .await
+    .map_err(|e| {
+        #[rustfmt::skip]
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })?;
+
+    let stream_moderators = res_stream_moderators?;
+    let stream_moderator_dto_list: Vec<StreamModeratorDto> = stream_moderators.into_iter().map(|v| StreamModeratorDto::from(v)).collect();
+
+    Ok(HttpResponse::Ok().json(stream_moderator_dto_list)) // 200
+}
+
+/// post_stream_moderator
+///
+/// Appoint a user as a chat moderator of the specified stream.
+/// Only the owner of the stream or the administrator can appoint moderators.
+/// The moderator can block and unblock users and remove messages in the chat of this stream.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X POST http://localhost:8080/api/streams/1/moderators -d '{"userId": 2}'
+/// ```
+///
+/// Return the new moderator (`StreamModeratorDto`) with status 201 or 204 (no content) if the stream or user is not found.
+/// If the user is already a moderator of the stream, the existing entry is returned.
+///
+#[utoipa::path(
+    responses(
+        (status = 201, description = "The user has been appointed as the stream's chat moderator.", body = StreamModeratorDto),
+        (status = 204, description = "The stream or user with the specified ID was not found."),
+        (status = 416, description = "Error parsing input parameter. `curl -i -X POST http://localhost:8080/api/streams/2a/moderators
+            -d '{\"userId\": 2}'`", body = ApiError, example = json!(ApiError::create(416,
+                err::MSG_PARSING_TYPE_NOT_SUPPORTED, "`id` - invalid digit found in string (2a)"))),
+        (status = 506, description = "Blocking error.", body = ApiError, 
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError, 
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    params(("id", description = "Unique stream ID.")),
+    request_body(content = CreateStreamModeratorDto, description = "The ID of the user being appointed as moderator.",
+        example = json!({ "userId": 2 })),
+    security(("bearer_auth" = [])),
+)]
+// POST /api/streams/{id}/moderators
+#[rustfmt::skip]
+#[post("/api/streams/{id}/moderators", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn post_stream_moderator(
+    authenticated: Authenticated,
+    stream_orm: web::Data<StreamOrmApp>,
+    request: actix_web::HttpRequest,
+    json_body: web::Json<CreateStreamModeratorDto>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    // Get current user details.
+    let user = authenticated.deref();
+
+    // Get data from request.
+    let id_str = request.match_info().query("id").to_string();
+    let id = parser::parse_i32(&id_str).map_err(|e| {
+        let msg = format!("`{}` - {}", "id", &e);
+        error!("{}-{}; {}", code_to_str(StatusCode::RANGE_NOT_SATISFIABLE), err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg);
+        ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg) // 416
+    })?;
+    let moderator_id = json_body.into_inner().user_id;
+
+    let opt_user_id: Option<i32> = if user.role == UserRole::Admin { None } else { Some(user.id) };
+    // Only the owner of the stream (or the administrator) can manage its moderators.
+    let opt_stream = find_own_stream(stream_orm.clone(), id, opt_user_id).await?;
+    if opt_stream.is_none() {
+        return Ok(HttpResponse::NoContent().finish()); // 204
+    }
+
+    let res_stream_moderator = web::block(move || {
+        // Add a new entity (stream_moderator).
+        let res_data = stream_orm.create_stream_moderator(id, moderator_id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_data
+    })
+    .await
+    .map_err(|e| {
+        #[rustfmt::skip]
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })?;
+
+    if let Some(stream_moderator) = res_stream_moderator? {
+        Ok(HttpResponse::Created().json(StreamModeratorDto::from(stream_moderator))) // 201
+    } else {
+        Ok(HttpResponse::NoContent().finish()) // 204
+    }
+}
+
+/// delete_stream_moderator
+///
+/// Remove a user from the chat moderators of the specified stream.
+/// Only the owner of the stream or the administrator can remove moderators.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X DELETE http://localhost:8080/api/streams/1/moderators/2
+/// ```
+///
+/// Return the removed moderator (`StreamModeratorDto`) with status 200 or 204 (no content) if the stream or moderator is not found.
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "The user has been removed from the stream's chat moderators.", body = StreamModeratorDto),
+        (status = 204, description = "The stream or moderator with the specified ID was not found."),
+        (status = 416, description = "Error parsing input parameter. `curl -i -X DELETE http://localhost:8080/api/streams/1/moderators/2a`",
+            body = ApiError, example = json!(ApiError::create(416,
+                err::MSG_PARSING_TYPE_NOT_SUPPORTED, "`user_id` - invalid digit found in string (2a)"))),
+        (status = 506, description = "Blocking error.", body = ApiError, 
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError, 
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    params(("id", description = "Unique stream ID."), ("user_id", description = "Unique ID of the moderator user.")),
+    security(("bearer_auth" = [])),
+)]
+// DELETE /api/streams/{id}/moderators/{user_id}
+#[rustfmt::skip]
+#[delete("/api/streams/{id}/moderators/{user_id}", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn delete_stream_moderator(
+    authenticated: Authenticated,
+    stream_orm: web::Data<StreamOrmApp>,
+    request: actix_web::HttpRequest,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    // Get current user details.
+    let user = authenticated.deref();
+
+    // Get data from request.
+    let id_str = request.match_info().query("id").to_string();
+    let id = parser::parse_i32(&id_str).map_err(|e| {
+        let msg = format!("`{}` - {}", "id", &e);
+        error!("{}-{}; {}", code_to_str(StatusCode::RANGE_NOT_SATISFIABLE), err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg);
+        ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg) // 416
+    })?;
+    let user_id_str = request.match_info().query("user_id").to_string();
+    let moderator_id = parser::parse_i32(&user_id_str).map_err(|e| {
+        let msg = format!("`{}` - {}", "user_id", &e);
+        error!("{}-{}; {}", code_to_str(StatusCode::RANGE_NOT_SATISFIABLE), err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg);
+        ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg) // 416
+    })?;
+
+    let opt_user_id: Option<i32> = if user.role == UserRole::Admin { None } else { Some(user.id) };
+    // Only the owner of the stream (or the administrator) can manage its moderators.
+    let opt_stream = find_own_stream(stream_orm.clone(), id, opt_user_id).await?;
+    if opt_stream.is_none() {
+        return Ok(HttpResponse::NoContent().finish()); // 204
+    }
+
+    let res_stream_moderator = web::block(move || {
+        // Delete an entity (stream_moderator).
+        let res_data = stream_orm.delete_stream_moderator(id, moderator_id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_data
+    })
+    .await
+    .map_err(|e| {
+        #[rustfmt::skip]
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })?;
+
+    if let Some(stream_moderator) = res_stream_moderator? {
+        Ok(HttpResponse::Ok().json(StreamModeratorDto::from(stream_moderator))) // 200
+    } else {
+        Ok(HttpResponse::NoContent().finish()) // 204
+    }
+}
+
+// ** Section: Stream Timeline **
+
+/// get_stream_timeline
+///
+/// Get the timeline of the specified stream: the history of its state transitions (with the users who made them).
+/// Only the owner of the stream or the administrator can view the timeline.
+///
+/// The net live duration (`liveDuration`, in seconds) is the total time in the "started" state, excluding paused time.
+/// The total paused time (`pausedDuration`, in seconds) is the time in the "paused" state.
+/// The current state lasts until the moment of the request.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X GET http://localhost:8080/api/streams/1/timeline
+/// ```
+///
+/// Return the timeline of the stream (`StreamTimelineDto`) with status 200 or 204 (no content) if the stream is not found.
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "The timeline of the stream.", body = StreamTimelineDto,
+            example = json!(StreamTimelineDto {
+                stream_id: 1,
+                state: StreamState::Stopped,
+                live_duration: 2400,
+                paused_duration: 300,
+                timeline: vec![
+                    stream_models::StreamStateLogDto::from(stream_models::StreamStateLog::new(1, 1, 1, "james_smith",
+                        StreamState::Waiting, StreamState::Preparing, Utc::now() + Duration::minutes(-60))),
+                    stream_models::StreamStateLogDto::from(stream_models::StreamStateLog::new(2, 1, 1, "james_smith",
+                        StreamState::Preparing, StreamState::Started, Utc::now() + Duration::minutes(-55))),
+                    stream_models::StreamStateLogDto::from(stream_models::StreamStateLog::new(3, 1, 1, "james_smith",
+                        StreamState::Started, StreamState::Paused, Utc::now() + Duration::minutes(-35))),
+                    stream_models::StreamStateLogDto::from(stream_models::StreamStateLog::new(4, 1, 1, "james_smith",
+                        StreamState::Paused, StreamState::Started, Utc::now() + Duration::minutes(-30))),
+                    stream_models::StreamStateLogDto::from(stream_models::StreamStateLog::new(5, 1, 1, "james_smith",
+                        StreamState::Started, StreamState::Stopped, Utc::now() + Duration::minutes(-10))),
+                ]
+            })
+        ),
+        (status = 204, description = "The stream with the specified ID was not found."),
+        (status = 416, description = "Error parsing input parameter. `curl -i -X GET http://localhost:8080/api/streams/2a/timeline`",
+            body = ApiError, example = json!(ApiError::create(416,
+                err::MSG_PARSING_TYPE_NOT_SUPPORTED, "`id` - invalid digit found in string (2a)"))),
+        (status = 506, description = "Blocking error.", body = ApiError, 
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError, 
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    params(("id", description = "Unique stream ID.")),
+    security(("bearer_auth" = [])),
+)]
+// GET /api/streams/{id}/timeline
+#[rustfmt::skip]
+#[get("/api/streams/{id}/timeline", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn get_stream_timeline(
+    authenticated: Authenticated,
+    stream_orm: web::Data<StreamOrmApp>,
+    request: actix_web::HttpRequest,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    // Get current user details.
+    let user = authenticated.deref();
+
+    // Get data from request.
+    let id_str = request.match_info().query("id").to_string();
+    let id = parser::parse_i32(&id_str).map_err(|e| {
+        let msg = format!("`{}` - {}", "id", &e);
+        error!("{}-{}; {}", code_to_str(StatusCode::RANGE_NOT_SATISFIABLE), err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg);
+        ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg) // 416
+    })?;
+
+    let opt_user_id: Option<i32> = if user.role == UserRole::Admin { None } else { Some(user.id) };
+    // Only the owner of the stream (or the administrator) can view its timeline.
+    let opt_stream = find_own_stream(stream_orm.clone(), id, opt_user_id).await?;
+    let Some(stream) = opt_stream else {
+        return Ok(HttpResponse::NoContent().finish()); // 204
+    };
+
+    let res_stream_state_log = web::block(move || {
+        // Get the history of the state transitions of the stream.
+        let res_data = stream_orm.get_stream_state_log(id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_data
+    })
+    .await
+    .map_err(|e| {
+        #[rustfmt::skip]
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })?;
+
+    let stream_state_log = res_stream_state_log?;
+    let stream_timeline_dto = StreamTimelineDto::new(&stream, stream_state_log, Utc::now());
+
+    Ok(HttpResponse::Ok().json(stream_timeline_dto)) // 200
+}
+
 #[cfg(all(test, feature = "mockdata"))]
 pub mod tests {
+    use std::sync::{Arc, Mutex};
 
+    use actix::{Actor, Context, Handler, SystemService};
     use actix_web::http;
+    use vrb_authent::user_orm::tests::USER2_NAME;
+    use vrb_chats::{
+        chat_message::{ChatMsgSsn, CommandSrv, JoinRoom},
+        chat_ws_server::ChatWsServer,
+    };
     use vrb_common::api_error::ApiError;
     use vrb_tools::token_data::BEARER;
 
@@ -1547,4 +1952,32 @@ pub mod tests {
             assert_eq!(app_err.message, msg.to_string());
         }
     }
+
+    // ** A chat client (session) that saves all received messages. **
+    pub struct ChatClientTest {
+        list: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Actor for ChatClientTest {
+        type Context = Context<Self>;
+    }
+
+    impl Handler<CommandSrv> for ChatClientTest {
+        type Result = ();
+
+        fn handle(&mut self, msg: CommandSrv, _ctx: &mut Self::Context) {
+            if let CommandSrv::Chat(ChatMsgSsn(msg_str)) = msg {
+                self.list.lock().unwrap().push(msg_str);
+            }
+        }
+    }
+
+    // Join the chat client to the room of the stream.
+    pub async fn join_chat_client(stream_id: i32, owner_id: i32) -> Arc<Mutex<Vec<String>>> {
+        let list: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+        let client = ChatClientTest { list: list.clone() }.start();
+        let join_room = JoinRoom(stream_id, owner_id, USER2_NAME.to_owned(), client.recipient());
+        ChatWsServer::from_registry().send(join_room).await.unwrap();
+        list
+    }
 }
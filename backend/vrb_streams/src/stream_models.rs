@@ -479,6 +479,35 @@ pub struct ToggleStreamStateDto {
     pub state: StreamState,
 }
 
+/// The reason why the state of the stream was not changed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamStateError {
+    /// The transition is not allowed by the stream state machine (old state, new state).
+    NotAllowed(StreamState, StreamState),
+    /// The owner of the stream already has a live stream.
+    LiveExists(Stream),
+    /// Error while querying the database.
+    Database(String),
+}
+
+impl fmt::Display for StreamStateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamStateError::NotAllowed(old_state, new_state) => {
+                write!(f, "stream_state: transition from `{}` to `{}` is not allowed", old_state, new_state)
+            }
+            StreamStateError::LiveExists(stream) => write!(f, "stream_state: there is already a live stream (id: {})", stream.id),
+            StreamStateError::Database(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl From<String> for StreamStateError {
+    fn from(err: String) -> Self {
+        StreamStateError::Database(err)
+    }
+}
+
 // **  Section: table "stream_tags" receiving data **
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Queryable, Selectable, QueryableByName)]
@@ -759,6 +788,183 @@ pub struct SearchStreamPeriodDto {
     pub finish: DateTime<Utc>,
 }
 
+// **  Section: table "stream_moderators" receiving data **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, QueryableByName)]
+#[diesel(table_name = schema::stream_moderators)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct StreamModerator {
+    pub id: i32,
+    pub stream_id: i32,
+    pub user_id: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    #[diesel(column_name = "nickname")]
+    pub nickname: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl StreamModerator {
+    pub fn new(id: i32, stream_id: i32, user_id: i32, nickname: &str, created_at: DateTime<Utc>) -> Self {
+        StreamModerator {
+            id,
+            stream_id,
+            user_id,
+            nickname: nickname.to_owned(),
+            created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamModeratorDto {
+    pub id: i32,
+    pub stream_id: i32,
+    pub user_id: i32,
+    pub nickname: String,
+    #[serde(with = "serial_datetime")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<StreamModerator> for StreamModeratorDto {
+    fn from(stream_moderator: StreamModerator) -> Self {
+        StreamModeratorDto {
+            id: stream_moderator.id,
+            stream_id: stream_moderator.stream_id,
+            user_id: stream_moderator.user_id,
+            nickname: stream_moderator.nickname.to_owned(),
+            created_at: stream_moderator.created_at.to_owned(),
+        }
+    }
+}
+
+// **  Section: table "stream_moderators" data creation **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateStreamModeratorDto {
+    pub user_id: i32,
+}
+
+// **  Section: table "stream_state_log" receiving data **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, QueryableByName)]
+#[diesel(table_name = schema::stream_state_log)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct StreamStateLog {
+    pub id: i32,
+    pub stream_id: i32,
+    pub user_id: Option<i32>, // The user who changed the state. (None - the user has been deleted.)
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    #[diesel(column_name = "nickname")]
+    pub nickname: Option<String>,
+    pub old_state: StreamState,
+    pub new_state: StreamState,
+    pub created_at: DateTime<Utc>,
+}
+
+impl StreamStateLog {
+    #[rustfmt::skip]
+    pub fn new(
+        id: i32, stream_id: i32, user_id: i32, nickname: &str, old_state: StreamState, new_state: StreamState, created_at: DateTime<Utc>,
+    ) -> Self {
+        StreamStateLog {
+            id,
+            stream_id,
+            user_id: Some(user_id),
+            nickname: Some(nickname.to_owned()),
+            old_state,
+            new_state,
+            created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamStateLogDto {
+    pub id: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
+    pub old_state: StreamState,
+    pub new_state: StreamState,
+    #[serde(with = "serial_datetime")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<StreamStateLog> for StreamStateLogDto {
+    fn from(stream_state_log: StreamStateLog) -> Self {
+        StreamStateLogDto {
+            id: stream_state_log.id,
+            user_id: stream_state_log.user_id,
+            nickname: stream_state_log.nickname,
+            old_state: stream_state_log.old_state,
+            new_state: stream_state_log.new_state,
+            created_at: stream_state_log.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamTimelineDto {
+    pub stream_id: i32,
+    pub state: StreamState,
+    pub live_duration: i64,   // The net time (in seconds) of the broadcast, excluding the time of pauses.
+    pub paused_duration: i64, // The total time (in seconds) of pauses.
+    pub timeline: Vec<StreamStateLogDto>,
+}
+
+impl StreamTimelineDto {
+    /// Create a timeline of the stream from the history of its states (sorted by time).
+    /// The current state lasts until the specified time "now".
+    pub fn new(stream: &Stream, stream_state_logs: Vec<StreamStateLog>, now: DateTime<Utc>) -> Self {
+        let mut live_duration = Duration::zero();
+        let mut paused_duration = Duration::zero();
+        for (idx, state_log) in stream_state_logs.iter().enumerate() {
+            // The state lasts until the next transition.
+            let end = stream_state_logs.get(idx + 1).map(|v| v.created_at).unwrap_or(now);
+            let period = (end - state_log.created_at).max(Duration::zero());
+            match state_log.new_state {
+                StreamState::Started => live_duration += period,
+                StreamState::Paused => paused_duration += period,
+                _ => {}
+            }
+        }
+        StreamTimelineDto {
+            stream_id: stream.id,
+            state: stream.state,
+            live_duration: live_duration.num_seconds(),
+            paused_duration: paused_duration.num_seconds(),
+            timeline: stream_state_logs.into_iter().map(|v| StreamStateLogDto::from(v)).collect(),
+        }
+    }
+}
+
+// **  Section: table "stream_state_log" data creation **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Insertable)]
+#[diesel(table_name = schema::stream_state_log)]
+pub struct CreateStreamStateLog {
+    pub stream_id: i32,
+    pub user_id: Option<i32>,
+    pub old_state: StreamState,
+    pub new_state: StreamState,
+}
+
+impl CreateStreamStateLog {
+    pub fn new(stream_id: i32, user_id: Option<i32>, old_state: StreamState, new_state: StreamState) -> Self {
+        CreateStreamStateLog {
+            stream_id,
+            user_id,
+            old_state,
+            new_state,
+        }
+    }
+}
+
 // ** **
 
 #[cfg(test)]
@@ -871,6 +1077,71 @@ mod tests {
         assert_eq!(result.len(), 3);
         assert_eq!(result, streams_info);
     }
+
+    // ** StreamTimelineDto **
+
+    #[test]
+    fn test_stream_timeline_without_state_log() {
+        let stream = Stream::new(1, 123, "title1", Utc::now());
+
+        let result = StreamTimelineDto::new(&stream, vec![], Utc::now());
+
+        assert_eq!(result.stream_id, stream.id);
+        assert_eq!(result.state, StreamState::Waiting);
+        assert_eq!(result.live_duration, 0);
+        assert_eq!(result.paused_duration, 0);
+        assert_eq!(result.timeline.len(), 0);
+    }
+
+    #[test]
+    fn test_stream_timeline_with_several_pauses() {
+        let (user_id, nickname) = (123, "user_123");
+        let now = Utc::now();
+        let mut stream = Stream::new(1, user_id, "title1", now);
+        stream.state = StreamState::Stopped;
+        let minutes = |value: i64| now - Duration::minutes(value);
+        #[rustfmt::skip]
+        let stream_state_logs = vec![
+            StreamStateLog::new(1, stream.id, user_id, nickname, StreamState::Waiting, StreamState::Preparing, minutes(60)),
+            StreamStateLog::new(2, stream.id, user_id, nickname, StreamState::Preparing, StreamState::Started, minutes(55)),
+            StreamStateLog::new(3, stream.id, user_id, nickname, StreamState::Started, StreamState::Paused, minutes(45)),
+            StreamStateLog::new(4, stream.id, user_id, nickname, StreamState::Paused, StreamState::Started, minutes(40)),
+            StreamStateLog::new(5, stream.id, user_id, nickname, StreamState::Started, StreamState::Paused, minutes(30)),
+            StreamStateLog::new(6, stream.id, user_id, nickname, StreamState::Paused, StreamState::Started, minutes(20)),
+            StreamStateLog::new(7, stream.id, user_id, nickname, StreamState::Started, StreamState::Stopped, minutes(5)),
+        ];
+
+        let result = StreamTimelineDto::new(&stream, stream_state_logs.clone(), now);
+
+        assert_eq!(result.state, StreamState::Stopped);
+        // Broadcast: 10 + 10 + 15 minutes, pauses: 5 + 10 minutes.
+        assert_eq!(result.live_duration, 35 * 60);
+        assert_eq!(result.paused_duration, 15 * 60);
+        let timeline: Vec<StreamStateLogDto> = stream_state_logs.into_iter().map(|v| StreamStateLogDto::from(v)).collect();
+        assert_eq!(result.timeline, timeline);
+    }
+
+    #[test]
+    fn test_stream_timeline_with_current_pause() {
+        let (user_id, nickname) = (123, "user_123");
+        let now = Utc::now();
+        let mut stream = Stream::new(1, user_id, "title1", now);
+        stream.state = StreamState::Paused;
+        let minutes = |value: i64| now - Duration::minutes(value);
+        #[rustfmt::skip]
+        let stream_state_logs = vec![
+            StreamStateLog::new(1, stream.id, user_id, nickname, StreamState::Waiting, StreamState::Preparing, minutes(30)),
+            StreamStateLog::new(2, stream.id, user_id, nickname, StreamState::Preparing, StreamState::Started, minutes(20)),
+            StreamStateLog::new(3, stream.id, user_id, nickname, StreamState::Started, StreamState::Paused, minutes(8)),
+        ];
+
+        let result = StreamTimelineDto::new(&stream, stream_state_logs, now);
+
+        // The current pause lasts until "now".
+        assert_eq!(result.live_duration, 12 * 60);
+        assert_eq!(result.paused_duration, 8 * 60);
+        assert_eq!(result.timeline.len(), 3);
+    }
 }
 
 #[cfg(all(test, feature = "mockdata"))]
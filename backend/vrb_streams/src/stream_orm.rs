@@ -1,7 +1,12 @@
 use chrono::{DateTime, Utc};
 use vrb_dbase::dbase::DbPool;
 
-use super::stream_models::{CreateStream, ModifyStream, SearchStream, SearchStreamEvent, SearchStreamPeriod, Stream, StreamTagStreamId};
+use vrb_dbase::enm_stream_state::StreamState;
+
+use super::stream_models::{
+    CreateStream, ModifyStream, SearchStream, SearchStreamEvent, SearchStreamPeriod, Stream, StreamModerator, StreamStateError,
+    StreamStateLog, StreamTagStreamId,
+};
 
 pub trait StreamOrm {
     /// Find an entity (stream) by parameters.
@@ -42,9 +47,30 @@ pub trait StreamOrm {
         &self, id: i32, opt_user_id: Option<i32>, modify_stream: ModifyStream, opt_tags: Option<Vec<String>>,
     ) -> Result<Option<(Stream, Vec<StreamTagStreamId>)>, String>;
 
+    /// Modify the state of an entity (stream) and add the transition to the history of states (stream_state_log).
+    /// The transition and its side effects (live flag, timestamps, a single live stream per user)
+    /// follow the stream state machine (`vrb_dbase::enm_stream_state`); an illegal transition or a second live stream
+    /// returns the corresponding `StreamStateError`.
+    #[rustfmt::skip]
+    fn modify_stream_state(
+        &self, id: i32, opt_user_id: Option<i32>, new_state: StreamState, changed_by: i32,
+    ) -> Result<Option<(Stream, Vec<StreamTagStreamId>)>, StreamStateError>;
+
     /// Delete an entity (stream).
     #[rustfmt::skip]
     fn delete_stream(&self, id: i32, opt_user_id: Option<i32>) -> Result<Option<(Stream, Vec<StreamTagStreamId>)>, String>;
+
+    /// Get a list of chat moderators of the stream.
+    fn get_stream_moderators(&self, stream_id: i32) -> Result<Vec<StreamModerator>, String>;
+
+    /// Add a new entity (stream_moderator). If the user is not found, then nothing is added.
+    fn create_stream_moderator(&self, stream_id: i32, user_id: i32) -> Result<Option<StreamModerator>, String>;
+
+    /// Delete an entity (stream_moderator).
+    fn delete_stream_moderator(&self, stream_id: i32, user_id: i32) -> Result<Option<StreamModerator>, String>;
+
+    /// Get the history of the state transitions of the stream.
+    fn get_stream_state_log(&self, stream_id: i32) -> Result<Vec<StreamStateLog>, String>;
 }
 
 #[cfg(not(all(test, feature = "mockdata")))]
@@ -64,9 +90,10 @@ pub mod impls {
     use diesel::{self, prelude::*, sql_types};
     use log::{Level::Info, info, log_enabled};
     use vrb_dbase::dbase;
+    use vrb_dbase::enm_stream_state::StreamStateFields;
     use vrb_dbase::schema::{self, streams::dsl as streams_dsl};
 
-    use crate::stream_models::{self, CreateStream, SearchStreamPeriod};
+    use crate::stream_models::{self, CreateStream, CreateStreamStateLog, SearchStreamPeriod};
 
     use super::*;
 
@@ -559,6 +586,104 @@ pub mod impls {
             }
         }
 
+        /// Modify the state of an entity (stream) and add the transition to the history of states (stream_state_log).
+        #[rustfmt::skip]
+        fn modify_stream_state(
+            &self, id: i32, opt_user_id: Option<i32>, new_state: StreamState, changed_by: i32,
+        ) -> Result<Option<(Stream, Vec<StreamTagStreamId>)>, StreamStateError> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            let mut err_table = "modify_stream_state";
+            let mut opt_state_err: Option<StreamStateError> = None;
+            let res_data = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                // Run query using Diesel to get the owner of the entry (stream).
+                let opt_owner_id = schema::streams::table
+                    .filter(streams_dsl::id.eq(id))
+                    .select(streams_dsl::user_id)
+                    .first::<i32>(conn)
+                    .optional()?;
+                // Check the owner of the stream (if a user ID is specified).
+                let Some(owner_id) = opt_owner_id.filter(|v| opt_user_id.is_none_or(|user_id| user_id == *v)) else {
+                    return Ok(None);
+                };
+                // Lock all streams of the owner until the end of the transaction (in ID order to avoid deadlocks),
+                // so that the rule of a single live stream is checked against the actual data.
+                let owner_streams = schema::streams::table
+                    .filter(streams_dsl::user_id.eq(owner_id))
+                    .order(streams_dsl::id.asc())
+                    .for_update()
+                    .load::<Stream>(conn)?;
+                let Some(old_stream) = owner_streams.iter().find(|v| v.id == id) else {
+                    return Ok(None);
+                };
+
+                if old_stream.state != new_state {
+                    // Check the transition against the stream state machine.
+                    if !old_stream.state.can_change_to(new_state) {
+                        opt_state_err = Some(StreamStateError::NotAllowed(old_stream.state, new_state));
+                        return Err(diesel::result::Error::RollbackTransaction);
+                    }
+                    // A user can have only one live stream.
+                    let opt_live_stream = owner_streams.iter().find(|v| v.id != id && v.live);
+                    if let Some(live_stream) = opt_live_stream.filter(|_| StreamState::is_live(new_state)) {
+                        opt_state_err = Some(StreamStateError::LiveExists(live_stream.clone()));
+                        return Err(diesel::result::Error::RollbackTransaction);
+                    }
+                }
+                // Get the values of the fields after the transition to the new state.
+                #[rustfmt::skip]
+                let old_fields = StreamStateFields::new(old_stream.state, old_stream.started, old_stream.paused, old_stream.stopped);
+                let new_fields = old_fields.change_to(new_state, Utc::now());
+
+                // Run query using Diesel to update the state of the entry (stream).
+                let stream = diesel::update(schema::streams::table.filter(streams_dsl::id.eq(id)))
+                    .set((
+                        streams_dsl::state.eq(new_fields.state),
+                        streams_dsl::live.eq(new_fields.live),
+                        streams_dsl::started.eq(new_fields.started),
+                        streams_dsl::paused.eq(new_fields.paused),
+                        streams_dsl::stopped.eq(new_fields.stopped),
+                    ))
+                    .returning(Stream::as_returning())
+                    .get_result(conn)?;
+
+                if old_stream.state != stream.state {
+                    // Add the transition to the history of the stream states.
+                    let create_state_log = CreateStreamStateLog::new(id, Some(changed_by), old_stream.state, stream.state);
+                    let res_state_log = diesel::insert_into(schema::stream_state_log::table)
+                        .values(create_state_log)
+                        .execute(conn);
+
+                    if let Err(err) = res_state_log {
+                        err_table = "stream_state_log";
+                        return Err(err);
+                    }
+                }
+
+                // Get a list of "tags" for the specified "stream".
+                let stream_tags = match self.get_stream_tags(conn, &[id]) {
+                    Ok(v) => v,
+                    Err(err) => {
+                        err_table = "get_stream_tags_names";
+                        return Err(err);
+                    }
+                };
+                Ok(Some((stream, stream_tags)))
+            });
+
+            if let Some(timer) = timer {
+                info!("modify_stream_state() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            match (res_data, opt_state_err) {
+                (Ok(value), _) => Ok(value),
+                (Err(_), Some(state_err)) => Err(state_err),
+                (Err(err), None) => Err(StreamStateError::Database(format!("{}: {}", err_table, err.to_string()))),
+            }
+        }
+
         /// Delete an entity (stream).
         #[rustfmt::skip]
         fn delete_stream(&self, id: i32, opt_user_id: Option<i32>) -> Result<Option<(Stream, Vec<StreamTagStreamId>)>, String> {
@@ -593,6 +718,92 @@ pub mod impls {
             }
             Ok(result)
         }
+
+        /// Get a list of chat moderators of the stream.
+        fn get_stream_moderators(&self, stream_id: i32) -> Result<Vec<StreamModerator>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            let query = diesel::sql_query("select * from get_stream_moderators($1);")
+                .bind::<sql_types::Integer, _>(stream_id); // $1
+
+            // Run a query using Diesel to get a list of "stream_moderators".
+            let stream_moderator_list: Vec<StreamModerator> = query
+                .load(&mut conn)
+                .map_err(|e| format!("get_stream_moderators: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("get_stream_moderators() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(stream_moderator_list)
+        }
+
+        /// Add a new entity (stream_moderator). If the user is not found, then nothing is added.
+        fn create_stream_moderator(&self, stream_id: i32, user_id: i32) -> Result<Option<StreamModerator>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            let query = diesel::sql_query("select * from create_stream_moderator($1,$2);")
+                .bind::<sql_types::Integer, _>(stream_id) // $1
+                .bind::<sql_types::Integer, _>(user_id); // $2
+
+            // Run a query using Diesel to add a new entry and return it.
+            let opt_stream_moderator = query
+                .get_result::<StreamModerator>(&mut conn)
+                .optional()
+                .map_err(|e| format!("create_stream_moderator: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("create_stream_moderator() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(opt_stream_moderator)
+        }
+
+        /// Delete an entity (stream_moderator).
+        fn delete_stream_moderator(&self, stream_id: i32, user_id: i32) -> Result<Option<StreamModerator>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            let query = diesel::sql_query("select * from delete_stream_moderator($1,$2);")
+                .bind::<sql_types::Integer, _>(stream_id) // $1
+                .bind::<sql_types::Integer, _>(user_id); // $2
+
+            // Run a query using Diesel to delete the entry and return it.
+            let opt_stream_moderator = query
+                .get_result::<StreamModerator>(&mut conn)
+                .optional()
+                .map_err(|e| format!("delete_stream_moderator: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("delete_stream_moderator() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(opt_stream_moderator)
+        }
+
+        /// Get the history of the state transitions of the stream.
+        fn get_stream_state_log(&self, stream_id: i32) -> Result<Vec<StreamStateLog>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            let query = diesel::sql_query("select * from get_stream_state_log($1);").bind::<sql_types::Integer, _>(stream_id); // $1
+
+            // Run a query using Diesel to get a list of "stream_state_log".
+            let stream_state_log_list: Vec<StreamStateLog> =
+                query.load(&mut conn).map_err(|e| format!("get_stream_state_log: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("get_stream_state_log() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(stream_state_log_list)
+        }
     }
 }
 
@@ -602,21 +813,26 @@ pub mod tests {
 
     use actix_web::web;
     use chrono::{DateTime, Duration, Timelike, Utc};
-    use vrb_authent::user_orm::tests::USER_IDS;
-    use vrb_dbase::enm_stream_state::StreamState;
+    use vrb_authent::user_orm::tests::{USER_IDS, USER_NAMES};
+    use vrb_dbase::enm_stream_state::{StreamState, StreamStateFields};
 
     use crate::config_strm;
     use crate::stream_models::{
-        self, CreateStream, ModifyStream, SearchStream, SearchStreamEvent, SearchStreamPeriod, Stream, StreamInfoDto, StreamTagStreamId,
+        self, CreateStream, ModifyStream, SearchStream, SearchStreamEvent, SearchStreamPeriod, Stream, StreamInfoDto, StreamModerator,
+        StreamStateError, StreamStateLog, StreamTagStreamId,
     };
 
     use crate::stream_orm::StreamOrm;
 
     pub const STREAM_ID: i32 = 1400;
+    pub const STREAM_MODERATOR_ID: i32 = 1500;
+    pub const STREAM_STATE_LOG_ID: i32 = 1600;
 
     #[derive(Debug, Clone)]
     pub struct StreamOrmApp {
         pub stream_info_vec: Vec<StreamInfoDto>,
+        pub stream_moderator_vec: Vec<StreamModerator>,
+        pub stream_state_log_vec: Vec<StreamStateLog>,
     }
 
     impl StreamOrmApp {
@@ -624,6 +840,8 @@ pub mod tests {
         pub fn new() -> Self {
             StreamOrmApp {
                 stream_info_vec: Vec::new(),
+                stream_moderator_vec: Vec::new(),
+                stream_state_log_vec: Vec::new(),
             }
         }
         /// Create a new instance with the specified `stream` list.
@@ -637,7 +855,35 @@ pub mod tests {
                 stream2.id = STREAM_ID + delta;
                 stream_info_vec.push(stream2);
             }
-            StreamOrmApp { stream_info_vec }
+            StreamOrmApp {
+                stream_info_vec,
+                stream_moderator_vec: Vec::new(),
+                stream_state_log_vec: Vec::new(),
+            }
+        }
+        /// Create a new instance with the specified `stream` list and `stream_moderator` list.
+        #[cfg(test)]
+        pub fn create_with_moderators(stream_vec: &[StreamInfoDto], stream_moderator_vec: &[StreamModerator]) -> Self {
+            let mut stream_orm_app = Self::create(stream_vec);
+            for (idx, stream_moderator) in stream_moderator_vec.iter().enumerate() {
+                let mut stream_moderator2 = stream_moderator.clone();
+                let delta: i32 = idx.try_into().unwrap();
+                stream_moderator2.id = STREAM_MODERATOR_ID + delta;
+                stream_orm_app.stream_moderator_vec.push(stream_moderator2);
+            }
+            stream_orm_app
+        }
+        /// Create a new instance with the specified `stream` list and `stream_state_log` list.
+        #[cfg(test)]
+        pub fn create_with_state_log(stream_vec: &[StreamInfoDto], stream_state_log_vec: &[StreamStateLog]) -> Self {
+            let mut stream_orm_app = Self::create(stream_vec);
+            for (idx, stream_state_log) in stream_state_log_vec.iter().enumerate() {
+                let mut stream_state_log2 = stream_state_log.clone();
+                let delta: i32 = idx.try_into().unwrap();
+                stream_state_log2.id = STREAM_STATE_LOG_ID + delta;
+                stream_orm_app.stream_state_log_vec.push(stream_state_log2);
+            }
+            stream_orm_app
         }
         /// Create entity "Stream" from "StreamInfoDto".
         fn to_stream(stream_info: &StreamInfoDto) -> Stream {
@@ -986,7 +1232,7 @@ pub mod tests {
                     None => stream_info.logo
                 };
                 let new_state = modify_stream.state.unwrap_or(stream_info.state.clone());
-                let new_live = vec![StreamState::Preparing, StreamState::Started, StreamState::Paused].contains(&new_state);
+                let new_live = StreamState::is_live(new_state);
 
                 let stream_saved = Stream {
                     id: stream_info.id,
@@ -1016,6 +1262,46 @@ pub mod tests {
             }
         }
 
+        /// Modify the state of an entity (stream) and add the transition to the history of states (stream_state_log).
+        #[rustfmt::skip]
+        fn modify_stream_state(
+            &self, id: i32, opt_user_id: Option<i32>, new_state: StreamState, _changed_by: i32,
+        ) -> Result<Option<(Stream, Vec<StreamTagStreamId>)>, StreamStateError> {
+            #[rustfmt::skip]
+            let opt_stream_info = self.stream_info_vec.iter()
+                .find(|stream| stream.id == id && opt_user_id.is_none_or(|user_id| user_id == stream.user_id));
+            let Some(stream_info) = opt_stream_info else {
+                return Ok(None);
+            };
+            if stream_info.state != new_state {
+                // Check the transition against the stream state machine.
+                if !stream_info.state.can_change_to(new_state) {
+                    return Err(StreamStateError::NotAllowed(stream_info.state, new_state));
+                }
+                // A user can have only one live stream.
+                #[rustfmt::skip]
+                let opt_live_stream = self.stream_info_vec.iter()
+                    .find(|stream| stream.user_id == stream_info.user_id && stream.id != id && stream.live);
+                if let Some(live_stream) = opt_live_stream.filter(|_| StreamState::is_live(new_state)) {
+                    return Err(StreamStateError::LiveExists(Self::to_stream(live_stream)));
+                }
+            }
+            // Get the values of the fields after the transition to the new state.
+            #[rustfmt::skip]
+            let old_fields = StreamStateFields::new(stream_info.state, stream_info.started, stream_info.paused, stream_info.stopped);
+            let new_fields = old_fields.change_to(new_state, Utc::now());
+
+            let mut stream_saved = Self::to_stream(stream_info);
+            stream_saved.state = new_fields.state;
+            stream_saved.live = new_fields.live;
+            stream_saved.started = new_fields.started;
+            stream_saved.paused = new_fields.paused;
+            stream_saved.stopped = new_fields.stopped;
+            stream_saved.updated_at = Utc::now();
+
+            Ok(Some((stream_saved, self.get_tags(stream_info))))
+        }
+
         /// Delete an entity (stream).
         #[rustfmt::skip]
         fn delete_stream(&self, id: i32, opt_user_id: Option<i32>) -> Result<Option<(Stream, Vec<StreamTagStreamId>)>, String> {
@@ -1032,6 +1318,54 @@ pub mod tests {
                 None => Ok(None),
             }
         }
+
+        /// Get a list of chat moderators of the stream.
+        fn get_stream_moderators(&self, stream_id: i32) -> Result<Vec<StreamModerator>, String> {
+            let result: Vec<StreamModerator> = self
+                .stream_moderator_vec
+                .iter()
+                .filter(|v| v.stream_id == stream_id)
+                .map(|v| v.clone())
+                .collect();
+            Ok(result)
+        }
+
+        /// Add a new entity (stream_moderator). If the user is not found, then nothing is added.
+        fn create_stream_moderator(&self, stream_id: i32, user_id: i32) -> Result<Option<StreamModerator>, String> {
+            let opt_idx_user = USER_IDS.iter().position(|v| *v == user_id);
+            if opt_idx_user.is_none() {
+                return Ok(None);
+            }
+            #[rustfmt::skip]
+            let opt_stream_moderator = self.stream_moderator_vec.iter()
+                .find(|v| v.stream_id == stream_id && v.user_id == user_id).map(|v| v.clone());
+            if opt_stream_moderator.is_some() {
+                return Ok(opt_stream_moderator);
+            }
+            let id = self.stream_moderator_vec.last().map(|v| v.id + 1).unwrap_or(STREAM_MODERATOR_ID);
+            let nickname = USER_NAMES.get(opt_idx_user.unwrap()).unwrap().to_lowercase();
+            Ok(Some(StreamModerator::new(id, stream_id, user_id, &nickname, Utc::now())))
+        }
+
+        /// Delete an entity (stream_moderator).
+        fn delete_stream_moderator(&self, stream_id: i32, user_id: i32) -> Result<Option<StreamModerator>, String> {
+            #[rustfmt::skip]
+            let opt_stream_moderator = self.stream_moderator_vec.iter()
+                .find(|v| v.stream_id == stream_id && v.user_id == user_id).map(|v| v.clone());
+            Ok(opt_stream_moderator)
+        }
+
+        /// Get the history of the state transitions of the stream.
+        fn get_stream_state_log(&self, stream_id: i32) -> Result<Vec<StreamStateLog>, String> {
+            let mut result: Vec<StreamStateLog> = self
+                .stream_state_log_vec
+                .iter()
+                .filter(|v| v.stream_id == stream_id)
+                .map(|v| v.clone())
+                .collect();
+            result.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+            Ok(result)
+        }
     }
 
     pub struct StreamOrmTest {}
@@ -1079,5 +1413,19 @@ pub mod tests {
                 config.app_data(web::Data::clone(&data_stream_orm));
             }
         }
+        #[rustfmt::skip]
+        pub fn cfg_stream_moderator_orm(data_s: Vec<StreamInfoDto>, data_sm: Vec<StreamModerator>) -> impl FnOnce(&mut web::ServiceConfig) {
+            move |config: &mut web::ServiceConfig| {
+                let data_stream_orm = web::Data::new(StreamOrmApp::create_with_moderators(&data_s, &data_sm));
+                config.app_data(web::Data::clone(&data_stream_orm));
+            }
+        }
+        #[rustfmt::skip]
+        pub fn cfg_stream_state_log_orm(data_s: Vec<StreamInfoDto>, data_ssl: Vec<StreamStateLog>) -> impl FnOnce(&mut web::ServiceConfig) {
+            move |config: &mut web::ServiceConfig| {
+                let data_stream_orm = web::Data::new(StreamOrmApp::create_with_state_log(&data_s, &data_ssl));
+                config.app_data(web::Data::clone(&data_stream_orm));
+            }
+        }
     }
 }
@@ -0,0 +1,568 @@
+use std::{fs, ops::Deref};
+
+use actix_web::{HttpResponse, delete, get, http::StatusCode, post, put, web};
+use chrono::{DateTime, Duration, Utc};
+use log::error;
+use utoipa;
+use vrb_authent::authentication::{Authenticated, RequireAuth};
+use vrb_common::{
+    alias_path::alias_path_stream,
+    api_error::{ApiError, code_to_str},
+    err, parser,
+    validators::{Validator, msg_validation},
+};
+use vrb_dbase::enm_user_role::UserRole;
+use vrb_webhooks::webhook_models::WebhookEvent;
+
+#[cfg(not(all(test, feature = "mockdata")))]
+use crate::stream_series_orm::impls::StreamSeriesOrmApp;
+#[cfg(all(test, feature = "mockdata"))]
+use crate::stream_series_orm::tests::StreamSeriesOrmApp;
+use crate::{
+    config_strm::ConfigStrm,
+    stream_controller::{fire_stream_webhook, send_stream_ews},
+    stream_models::{Stream, StreamInfoDto, StreamTagStreamId},
+    stream_series_models::{
+        self, CreateStreamSeries, CreateStreamSeriesDto, ModifyStreamSeries, ModifyStreamSeriesDto, SeriesScope, StreamSeriesDto,
+    },
+    stream_series_orm::StreamSeriesOrm,
+};
+
+pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
+    |config: &mut web::ServiceConfig| {
+        //     GET /api/stream_series
+        config
+            .service(get_stream_series)
+            // POST /api/stream_series
+            .service(post_stream_series)
+            // GET /api/stream_series/{id}/streams
+            .service(get_stream_series_streams)
+            // PUT /api/stream_series/{id}/streams/{stream_id}
+            .service(put_stream_series_stream)
+            // DELETE /api/stream_series/{id}
+            .service(delete_stream_series);
+    }
+}
+
+/// Get the parameter (ID) from the request path.
+fn get_param_from_request(request: &actix_web::HttpRequest, name: &str) -> Result<i32, ApiError> {
+    let value_str = request.match_info().query(name).to_string();
+    parser::parse_i32(&value_str).map_err(|e| {
+        let msg = format!("`{}` - {}", name, &e);
+        error!(
+            "{}-{}; {}",
+            code_to_str(StatusCode::RANGE_NOT_SATISFIABLE),
+            err::MSG_PARSING_TYPE_NOT_SUPPORTED,
+            &msg
+        );
+        ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg) // 416
+    })
+}
+
+/// Get the time up to which the streams of the series are created.
+fn get_horizon(config_strm: &ConfigStrm) -> DateTime<Utc> {
+    Utc::now() + Duration::days(config_strm.strm_series_horizon.into())
+}
+
+/// Check that the time zone is known. If not, return the validation error response (417).
+async fn check_time_zone(
+    stream_series_orm: web::Data<StreamSeriesOrmApp>,
+    opt_time_zone: Option<String>,
+) -> Result<Option<HttpResponse>, ApiError> {
+    let Some(time_zone) = opt_time_zone else {
+        return Ok(None);
+    };
+    let time_zone2 = time_zone.clone();
+    let res_is_known = web::block(move || {
+        // Check that the time zone with the specified name is known.
+        let res_data = stream_series_orm.is_time_zone_known(&time_zone2).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_data
+    })
+    .await
+    .map_err(|e| {
+        #[rustfmt::skip]
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })?;
+
+    if res_is_known? {
+        return Ok(None);
+    }
+    let validation_errors = vec![stream_series_models::time_zone_invalid(&time_zone)];
+    error!("{}-{}", code_to_str(StatusCode::EXPECTATION_FAILED), msg_validation(&validation_errors));
+    Ok(Some(ApiError::to_response(&ApiError::validations(validation_errors)))) // 417
+}
+
+/// Delete the logo files of the deleted streams.
+fn remove_logo_files(config_strm: &ConfigStrm, streams: &[Stream]) {
+    let alias_path_strm = alias_path_stream::AliasStrm::new(&config_strm.strm_logo_files_dir);
+    let alias_strm = alias_path_strm.as_ref();
+
+    for path_file_img in streams.iter().filter_map(|stream| stream.logo.clone()) {
+        // If the file path starts with alice, then the file corresponds to the entity type.
+        // And only then can the file be deleted.
+        if alias_strm.starts_with_alias(&path_file_img) {
+            // Return file path prefix instead of alias.
+            let full_path_file_img = alias_strm.alias_to_path(&path_file_img);
+            if let Err(err) = fs::remove_file(&full_path_file_img) {
+                error!("remove_logo_files() remove_file({}): error: {:?}", &full_path_file_img, err);
+            }
+        }
+    }
+}
+
+/// Notify the webhooks of the owner of the streams about the event of each stream.
+pub fn fire_streams_webhook(event: WebhookEvent, streams: &[Stream], stream_tags: &[StreamTagStreamId]) {
+    // Merge a "stream" and a corresponding list of "tags".
+    for stream_info_dto in StreamInfoDto::merge_streams_and_tags(streams, stream_tags) {
+        fire_stream_webhook(event, &stream_info_dto);
+    }
+}
+
+// ** Section: Stream Series Get **
+
+/// get_stream_series
+///
+/// Get a list of recurring series of streams of the current user.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X GET http://localhost:8080/api/stream_series
+/// ```
+///
+/// Return the list of series (`Vec<StreamSeriesDto>`) with status 200.
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "List of series of streams of the current user.", body = Vec<StreamSeriesDto>),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    security(("bearer_auth" = [])),
+)]
+// GET /api/stream_series
+#[rustfmt::skip]
+#[get("/api/stream_series", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn get_stream_series(
+    authenticated: Authenticated,
+    stream_series_orm: web::Data<StreamSeriesOrmApp>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    // Get current user details.
+    let user = authenticated.deref();
+    let user_id = user.id;
+
+    let res_stream_series = web::block(move || {
+        // Get a list of series of the user.
+        let res_data = stream_series_orm.get_stream_series(user_id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_data
+    })
+    .await
+    .map_err(|e| {
+        #[rustfmt::skip]
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })?;
+
+    let stream_series_list = res_stream_series?;
+    let stream_series_dto_list: Vec<StreamSeriesDto> = stream_series_list.into_iter().map(StreamSeriesDto::from).collect();
+
+    Ok(HttpResponse::Ok().json(stream_series_dto_list)) // 200
+}
+
+/// get_stream_series_streams
+///
+/// Get a list of the streams (occurrences) of the specified series.
+/// Only the owner of the series or the administrator can view them.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X GET http://localhost:8080/api/stream_series/1/streams
+/// ```
+///
+/// Return the list of streams (`Vec<StreamInfoDto>`) ordered by start time with status 200
+/// or 204 (no content) if the series is not found.
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "List of the streams of the series.", body = Vec<StreamInfoDto>),
+        (status = 204, description = "The series with the specified ID was not found."),
+        (status = 416, description = "Error parsing input parameter. `curl -i -X GET http://localhost:8080/api/stream_series/2a/streams`",
+            body = ApiError, example = json!(ApiError::create(416,
+                err::MSG_PARSING_TYPE_NOT_SUPPORTED, "`id` - invalid digit found in string (2a)"))),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    params(("id", description = "Unique series ID.")),
+    security(("bearer_auth" = [])),
+)]
+// GET /api/stream_series/{id}/streams
+#[rustfmt::skip]
+#[get("/api/stream_series/{id}/streams", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn get_stream_series_streams(
+    authenticated: Authenticated,
+    stream_series_orm: web::Data<StreamSeriesOrmApp>,
+    request: actix_web::HttpRequest,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    // Get current user details.
+    let user = authenticated.deref();
+
+    // Get data from request.
+    let id = get_param_from_request(&request, "id")?;
+
+    let opt_user_id: Option<i32> = if user.role == UserRole::Admin { None } else { Some(user.id) };
+
+    let res_streams = web::block(move || {
+        // Find the series (only the owner or the administrator can view it).
+        let opt_stream_series = stream_series_orm.find_stream_series_by_id(id, opt_user_id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        })?;
+        if opt_stream_series.is_none() {
+            return Ok(None);
+        }
+        // Get a list of the streams of the series.
+        let res_data = stream_series_orm.get_stream_series_streams(id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_data.map(Some)
+    })
+    .await
+    .map_err(|e| {
+        #[rustfmt::skip]
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })?;
+
+    if let Some((streams, stream_tags)) = res_streams? {
+        // Merge a "stream" and a corresponding list of "tags".
+        let list = StreamInfoDto::merge_streams_and_tags(&streams, &stream_tags);
+        Ok(HttpResponse::Ok().json(list)) // 200
+    } else {
+        Ok(HttpResponse::NoContent().finish()) // 204
+    }
+}
+
+// ** Section: Stream Series Post **
+
+/// post_stream_series
+///
+/// Add a new recurring series of streams for the current user.
+/// The streams of the series are created in advance, up to the horizon (`STRM_SERIES_HORIZON` days from now).
+/// Later streams are created as they come within the horizon. They are ordinary streams:
+/// they are shown in the calendar (`/api/streams_events`, `/api/streams_period`) and can be started as usual.
+///
+/// The recurrence rule (a subset of RFC 5545 RRULE) takes the values:
+/// - "daily" or "weekly";
+/// - "FREQ=DAILY|WEEKLY[;INTERVAL=n][;BYDAY=MO,TU,WE,TH,FR,SA,SU][;COUNT=n|;UNTIL=YYYYMMDD[THHMMSSZ]]".
+///
+/// The streams keep the local time of day of `starttime` in the time zone of the series (also after the change to DST).
+///
+/// Request structure:
+/// ```text
+/// {
+///   title: String,             // required - the title of the streams;
+///   descript?: String,         // optional - the description of the streams;
+///   starttime: DateTime<Utc>,  // required - the start of the first stream;
+///   source?: String,           // optional - the source of the streams ("obs" by default);
+///   tags: Vec<String>,         // required - the tags of the streams;
+///   rrule: String,             // required - the recurrence rule;
+///   timeZone?: String,         // optional - the name of the time zone ("Europe/Berlin", "UTC" by default);
+/// }
+/// ```
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X POST http://localhost:8080/api/stream_series -d '{"title": "Weekly show",
+///   "starttime": "2030-01-03T18:00:00.000Z", "tags": ["show"], "rrule": "FREQ=WEEKLY;BYDAY=TH"}'
+/// ```
+///
+/// Return the new series (`StreamSeriesDto`) with status 201.
+///
+#[utoipa::path(
+    responses(
+        (status = 201, description = "The series was created together with its streams up to the horizon.",
+            body = StreamSeriesDto),
+        (status = 417, description = "Validation error. `curl -i -X POST http://localhost:8080/api/stream_series
+            -d '{\"title\": \"Weekly show\", \"starttime\": \"2030-01-03T18:00:00.000Z\", \"tags\": [\"show\"], \"rrule\": \"monthly\"}'`",
+            body = [ApiError], example = json!(ApiError::validations((CreateStreamSeriesDto {
+                title: "Weekly show".to_owned(), descript: None, starttime: Utc::now() + Duration::days(1), source: None,
+                tags: vec!["show".to_owned()], rrule: "monthly".to_owned(), time_zone: None
+            }).validate().err().unwrap()))),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    request_body(content = CreateStreamSeriesDto, description = "The template of the streams and the recurrence rule.",
+        example = json!({ "title": "Weekly show", "starttime": "2030-01-03T18:00:00.000Z", "tags": ["show"],
+            "rrule": "FREQ=WEEKLY;BYDAY=TH", "timeZone": "Europe/Berlin" })),
+    security(("bearer_auth" = [])),
+)]
+// POST /api/stream_series
+#[rustfmt::skip]
+#[post("/api/stream_series", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn post_stream_series(
+    authenticated: Authenticated,
+    config_strm: web::Data<ConfigStrm>,
+    stream_series_orm: web::Data<StreamSeriesOrmApp>,
+    json_body: web::Json<CreateStreamSeriesDto>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    // Get current user details.
+    let user = authenticated.deref();
+
+    // Checking the validity of the data model.
+    let validation_res = json_body.validate();
+    if let Err(validation_errors) = validation_res {
+        error!("{}-{}", code_to_str(StatusCode::EXPECTATION_FAILED), msg_validation(&validation_errors));
+        return Ok(ApiError::to_response(&ApiError::validations(validation_errors))); // 417
+    }
+    // Check that the time zone is known.
+    if let Some(response) = check_time_zone(stream_series_orm.clone(), json_body.time_zone.clone()).await? {
+        return Ok(response); // 417
+    }
+
+    let create_stream_series = CreateStreamSeries::convert(json_body.into_inner(), user.id);
+    let horizon = get_horizon(config_strm.get_ref());
+
+    let res_stream_series = web::block(move || {
+        // Add a new entity (stream_series) and create its streams.
+        let res_data = stream_series_orm.create_stream_series(create_stream_series, horizon).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_data
+    })
+    .await
+    .map_err(|e| {
+        #[rustfmt::skip]
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })?;
+
+    let (stream_series, streams, stream_tags) = res_stream_series?;
+    // Notify the webhooks of the owner about the created streams of the series.
+    fire_streams_webhook(WebhookEvent::StreamCreated, &streams, &stream_tags);
+
+    Ok(HttpResponse::Created().json(StreamSeriesDto::from(stream_series))) // 201
+}
+
+// ** Section: Stream Series Put **
+
+/// put_stream_series_stream
+///
+/// Change the specified stream (occurrence) of the series.
+/// Only the owner of the series or the administrator can change it.
+///
+/// The "scope" field specifies what is changed:
+/// - "this" - only the specified stream (the recurrence rule cannot be changed);
+/// - "future" - the series itself and its waiting streams, starting with the specified one.
+///   If "starttime", "rrule" or "timeZone" is changed, the schedule of the series starts again from the specified stream:
+///   its waiting streams are deleted and created again according to the new schedule.
+///   If "starttime" is not specified, the new schedule starts at the time of the specified occurrence.
+///
+/// Request structure:
+/// ```text
+/// {
+///   scope: String,             // required - "this" | "future";
+///   title?: String,            // optional - the title of the streams;
+///   descript?: String,         // optional - the description of the streams;
+///   starttime?: DateTime<Utc>, // optional - the new start of the stream (the new start of the schedule for "future");
+///   source?: String,           // optional - the source of the streams;
+///   tags?: Vec<String>,        // optional - the tags of the streams;
+///   rrule?: String,            // optional - the new recurrence rule (only for "future");
+///   timeZone?: String,         // optional - the new time zone (only for "future");
+/// }
+/// ```
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X PUT http://localhost:8080/api/stream_series/1/streams/2 -d '{"scope": "future", "title": "New title"}'
+/// ```
+///
+/// Return the changed (or created again) streams (`Vec<StreamInfoDto>`) with status 200
+/// or 204 (no content) if the series or its stream is not found.
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "The streams of the series were changed.", body = Vec<StreamInfoDto>),
+        (status = 204, description = "The series or its stream with the specified ID was not found."),
+        (status = 416, description = "Error parsing input parameter. `curl -i -X PUT http://localhost:8080/api/stream_series/1/streams/2a
+            -d '{\"scope\": \"this\", \"title\": \"New title\"}'`", body = ApiError, example = json!(ApiError::create(416,
+                err::MSG_PARSING_TYPE_NOT_SUPPORTED, "`stream_id` - invalid digit found in string (2a)"))),
+        (status = 417, description = "Validation error. `curl -i -X PUT http://localhost:8080/api/stream_series/1/streams/2
+            -d '{\"scope\": \"this\", \"rrule\": \"daily\"}'`", body = [ApiError],
+            example = json!(ApiError::validations((ModifyStreamSeriesDto {
+                scope: SeriesScope::This, title: None, descript: None, starttime: None, source: None, tags: None,
+                rrule: Some("daily".to_owned()), time_zone: None
+            }).validate().err().unwrap()))),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    params(("id", description = "Unique series ID."), ("stream_id", description = "Unique ID of the stream of the series.")),
+    request_body(content = ModifyStreamSeriesDto, description = "The scope of the change and the new data of the streams.",
+        example = json!({ "scope": SeriesScope::Future, "title": "New title", "rrule": "FREQ=WEEKLY;BYDAY=FR" })),
+    security(("bearer_auth" = [])),
+)]
+// PUT /api/stream_series/{id}/streams/{stream_id}
+#[rustfmt::skip]
+#[put("/api/stream_series/{id}/streams/{stream_id}", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn put_stream_series_stream(
+    authenticated: Authenticated,
+    config_strm: web::Data<ConfigStrm>,
+    stream_series_orm: web::Data<StreamSeriesOrmApp>,
+    request: actix_web::HttpRequest,
+    json_body: web::Json<ModifyStreamSeriesDto>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    // Get current user details.
+    let user = authenticated.deref();
+
+    // Get data from request.
+    let id = get_param_from_request(&request, "id")?;
+    let stream_id = get_param_from_request(&request, "stream_id")?;
+
+    // Checking the validity of the data model.
+    let validation_res = json_body.validate();
+    if let Err(validation_errors) = validation_res {
+        error!("{}-{}", code_to_str(StatusCode::EXPECTATION_FAILED), msg_validation(&validation_errors));
+        return Ok(ApiError::to_response(&ApiError::validations(validation_errors))); // 417
+    }
+    // Check that the time zone is known.
+    if let Some(response) = check_time_zone(stream_series_orm.clone(), json_body.time_zone.clone()).await? {
+        return Ok(response); // 417
+    }
+
+    let modify_stream_series_dto = json_body.into_inner();
+    let scope = modify_stream_series_dto.scope;
+    let modify_stream_series = ModifyStreamSeries::from(modify_stream_series_dto);
+    // For "future", a change of the schedule recreates the waiting streams.
+    let is_recreated = scope == SeriesScope::Future && modify_stream_series.is_schedule_changed();
+    let opt_user_id: Option<i32> = if user.role == UserRole::Admin { None } else { Some(user.id) };
+    let config_strm = config_strm.get_ref().clone();
+    let horizon = get_horizon(&config_strm);
+
+    let res_streams = web::block(move || {
+        // Modify the stream of the series or the series from this stream on.
+        let res_data = stream_series_orm
+            .modify_stream_series(id, stream_id, opt_user_id, scope, modify_stream_series, horizon)
+            .map_err(|e| {
+                error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+                ApiError::create(507, err::MSG_DATABASE, &e) // 507
+            });
+        res_data
+    })
+    .await
+    .map_err(|e| {
+        #[rustfmt::skip]
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })?;
+
+    if let Some((streams, deleted_streams, stream_tags)) = res_streams? {
+        // Delete the logo files of the streams that were deleted when the schedule was changed.
+        remove_logo_files(&config_strm, &deleted_streams);
+        // Notify the webhooks of the owner about the deleted streams of the series.
+        fire_streams_webhook(WebhookEvent::StreamDeleted, &deleted_streams, &stream_tags);
+        // Merge a "stream" and a corresponding list of "tags".
+        let list = StreamInfoDto::merge_streams_and_tags(&streams, &stream_tags);
+        for stream_info_dto in list.iter() {
+            if is_recreated {
+                // Notify the webhooks of the owner of the stream.
+                fire_stream_webhook(WebhookEvent::StreamCreated, stream_info_dto);
+            } else {
+                // Send the new data of the stream to all members of its chat room.
+                send_stream_ews(stream_info_dto);
+                // Notify the webhooks of the owner of the stream.
+                fire_stream_webhook(WebhookEvent::StreamUpdated, stream_info_dto);
+            }
+        }
+        Ok(HttpResponse::Ok().json(list)) // 200
+    } else {
+        Ok(HttpResponse::NoContent().finish()) // 204
+    }
+}
+
+// ** Section: Stream Series Delete **
+
+/// delete_stream_series
+///
+/// Delete the specified series together with its future streams that have not yet started.
+/// The other streams of the series (past, started or stopped) remain as ordinary streams.
+/// Only the owner of the series or the administrator can delete it.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X DELETE http://localhost:8080/api/stream_series/1
+/// ```
+///
+/// Return the deleted series (`StreamSeriesDto`) with status 200 or 204 (no content) if the series is not found.
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "The specified series was deleted successfully.", body = StreamSeriesDto),
+        (status = 204, description = "The series with the specified ID was not found."),
+        (status = 416, description = "Error parsing input parameter. `curl -i -X DELETE http://localhost:8080/api/stream_series/2a`",
+            body = ApiError, example = json!(ApiError::create(416,
+                err::MSG_PARSING_TYPE_NOT_SUPPORTED, "`id` - invalid digit found in string (2a)"))),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    params(("id", description = "Unique series ID.")),
+    security(("bearer_auth" = [])),
+)]
+// DELETE /api/stream_series/{id}
+#[rustfmt::skip]
+#[delete("/api/stream_series/{id}", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn delete_stream_series(
+    authenticated: Authenticated,
+    config_strm: web::Data<ConfigStrm>,
+    stream_series_orm: web::Data<StreamSeriesOrmApp>,
+    request: actix_web::HttpRequest,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    // Get current user details.
+    let user = authenticated.deref();
+
+    // Get data from request.
+    let id = get_param_from_request(&request, "id")?;
+
+    let opt_user_id: Option<i32> = if user.role == UserRole::Admin { None } else { Some(user.id) };
+
+    let res_stream_series = web::block(move || {
+        // Delete an entity (stream_series) and its future streams.
+        let res_data = stream_series_orm.delete_stream_series(id, opt_user_id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_data
+    })
+    .await
+    .map_err(|e| {
+        #[rustfmt::skip]
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })?;
+
+    if let Some((stream_series, deleted_streams, stream_tags)) = res_stream_series? {
+        // Delete the logo files of the deleted streams.
+        remove_logo_files(config_strm.get_ref(), &deleted_streams);
+        // Notify the webhooks of the owner about the deleted streams of the series.
+        fire_streams_webhook(WebhookEvent::StreamDeleted, &deleted_streams, &stream_tags);
+        Ok(HttpResponse::Ok().json(StreamSeriesDto::from(stream_series))) // 200
+    } else {
+        Ok(HttpResponse::NoContent().finish()) // 204
+    }
+}
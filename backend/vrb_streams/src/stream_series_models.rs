@@ -0,0 +1,881 @@
+use std::{borrow::Cow, convert::Infallible, fmt};
+
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{self, json};
+use utoipa::ToSchema;
+use vrb_common::{
+    err, serial_datetime, serial_datetime_option,
+    validators::{ValidationChecks, ValidationError, Validator},
+};
+use vrb_dbase::schema;
+
+use crate::stream_models::{MSG_TAG_REQUIRED, validate_descript, validate_source, validate_starttime, validate_tag, validate_title};
+
+pub const MSG_RRULE_REQUIRED: &str = "rrule:required";
+pub const RRULE_MAX: u16 = 255;
+pub const MSG_RRULE_MAX_LENGTH: &str = "rrule:max_length";
+pub const MSG_RRULE_INVALID: &str = "rrule:invalid";
+// The recurrence rule can only be changed for all future occurrences.
+pub const MSG_RRULE_ONLY_FOR_FUTURE: &str = "rrule:only_for_future";
+// The maximum "INTERVAL" of the recurrence rule (one year) for "FREQ=DAILY" and "FREQ=WEEKLY".
+pub const RRULE_INTERVAL_DAILY_MAX: u32 = 366;
+pub const RRULE_INTERVAL_WEEKLY_MAX: u32 = 52;
+
+pub const TIME_ZONE_DEFAULT: &str = "UTC";
+pub const TIME_ZONE_MAX: u8 = 64;
+pub const MSG_TIME_ZONE_MAX_LENGTH: &str = "time_zone:max_length";
+pub const MSG_TIME_ZONE_INVALID: &str = "time_zone:invalid";
+// The time zone can only be changed for all future occurrences.
+pub const MSG_TIME_ZONE_ONLY_FOR_FUTURE: &str = "time_zone:only_for_future";
+
+// MAX=255, the value must be a valid recurrence rule.
+pub fn validate_rrule(value: &str) -> Result<(), ValidationError> {
+    ValidationChecks::required(value, MSG_RRULE_REQUIRED)?;
+    ValidationChecks::max_length(value, RRULE_MAX.into(), MSG_RRULE_MAX_LENGTH)?;
+    if let Err(e) = SeriesRule::parse(value) {
+        let mut err = ValidationError::new(MSG_RRULE_INVALID);
+        let json = json!({ "actualValue": value, "error": e });
+        err.add_param(Cow::Borrowed("invalid"), &json);
+        return Err(err);
+    }
+    Ok(())
+}
+
+// MAX=64, the value must look like the name of a time zone ("Europe/Berlin"). Whether it is known is checked by the database.
+pub fn validate_time_zone(value: &str) -> Result<(), ValidationError> {
+    ValidationChecks::max_length(value, TIME_ZONE_MAX.into(), MSG_TIME_ZONE_MAX_LENGTH)?;
+    let is_valid_char = |c: char| c.is_ascii_alphanumeric() || "/_+-".contains(c);
+    if value.is_empty() || !value.chars().all(is_valid_char) {
+        return Err(time_zone_invalid(value));
+    }
+    Ok(())
+}
+
+// Get the error of the invalid (or unknown) time zone.
+pub fn time_zone_invalid(value: &str) -> ValidationError {
+    let mut err = ValidationError::new(MSG_TIME_ZONE_INVALID);
+    let json = json!({ "actualValue": value });
+    err.add_param(Cow::Borrowed("invalid"), &json)
+}
+
+// **  Section: time zone of the series **
+
+/// Conversion of the times of the occurrences between UTC and the local time of the series.
+pub trait SeriesTimeZone {
+    type Error;
+    /// Convert the UTC times into the local time.
+    fn to_local(&mut self, values: &[DateTime<Utc>]) -> Result<Vec<NaiveDateTime>, Self::Error>;
+    /// Convert the local times into UTC (in the same order).
+    fn to_utc(&mut self, values: &[NaiveDateTime]) -> Result<Vec<DateTime<Utc>>, Self::Error>;
+}
+
+impl SeriesTimeZone for FixedOffset {
+    type Error = Infallible;
+
+    fn to_local(&mut self, values: &[DateTime<Utc>]) -> Result<Vec<NaiveDateTime>, Self::Error> {
+        #[rustfmt::skip]
+        let result = values.iter().map(|v| v.naive_utc().checked_add_offset(*self).unwrap_or(NaiveDateTime::MAX)).collect();
+        Ok(result)
+    }
+    fn to_utc(&mut self, values: &[NaiveDateTime]) -> Result<Vec<DateTime<Utc>>, Self::Error> {
+        #[rustfmt::skip]
+        let result = values.iter().map(|v| v.checked_sub_offset(*self).unwrap_or(NaiveDateTime::MAX).and_utc()).collect();
+        Ok(result)
+    }
+}
+
+// **  Section: recurrence rule **
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum SeriesFreq {
+    Daily,
+    Weekly,
+}
+
+/// The recurrence rule of a series of streams (a subset of RFC 5545 RRULE).
+///
+/// Accepted values: "daily", "weekly" or "[RRULE:]FREQ=DAILY|WEEKLY[;INTERVAL=n][;BYDAY=MO,TU,..][;COUNT=n|;UNTIL=date]".
+/// The "INTERVAL" is no more than one year (366 days or 52 weeks).
+/// The date of "UNTIL" is "YYYYMMDD" (the whole day in UTC is included) or "YYYYMMDDTHHMMSSZ".
+/// Occurrences keep the local time of day of the first stream (in the time zone of the series).
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeriesRule {
+    pub freq: SeriesFreq,
+    pub interval: u32,
+    pub by_day: Vec<Weekday>,
+    pub count: Option<u32>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+const WEEKDAY_NAMES: [(&str, Weekday); 7] = [
+    ("MO", Weekday::Mon),
+    ("TU", Weekday::Tue),
+    ("WE", Weekday::Wed),
+    ("TH", Weekday::Thu),
+    ("FR", Weekday::Fri),
+    ("SA", Weekday::Sat),
+    ("SU", Weekday::Sun),
+];
+
+impl SeriesRule {
+    pub fn new(freq: SeriesFreq) -> Self {
+        SeriesRule {
+            freq,
+            interval: 1,
+            by_day: vec![],
+            count: None,
+            until: None,
+        }
+    }
+    /// Parse the recurrence rule.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let mut value = value.trim().to_uppercase();
+        if let Some(rule) = value.strip_prefix("RRULE:") {
+            value = rule.to_string();
+        }
+        match value.as_str() {
+            "DAILY" => return Ok(SeriesRule::new(SeriesFreq::Daily)),
+            "WEEKLY" => return Ok(SeriesRule::new(SeriesFreq::Weekly)),
+            _ => {}
+        }
+        let mut opt_freq: Option<SeriesFreq> = None;
+        let mut interval: u32 = 1;
+        let mut by_day: Vec<Weekday> = vec![];
+        let mut count: Option<u32> = None;
+        let mut until: Option<DateTime<Utc>> = None;
+
+        for part in value.split(';').map(|v| v.trim()).filter(|v| !v.is_empty()) {
+            let (name, val) = part.split_once('=').ok_or(format!("invalid part \"{}\"", part))?;
+            match name.trim() {
+                "FREQ" => {
+                    opt_freq = match val.trim() {
+                        "DAILY" => Some(SeriesFreq::Daily),
+                        "WEEKLY" => Some(SeriesFreq::Weekly),
+                        _ => return Err(format!("unsupported FREQ \"{}\"", val)),
+                    }
+                }
+                "INTERVAL" => {
+                    interval = val.trim().parse().map_err(|_| format!("invalid INTERVAL \"{}\"", val))?;
+                    if interval == 0 {
+                        return Err("INTERVAL must be greater than 0".to_string());
+                    }
+                }
+                "BYDAY" => {
+                    for day_name in val.split(',').map(|v| v.trim()) {
+                        let day = WEEKDAY_NAMES.iter().find(|(name, _)| *name == day_name);
+                        let (_, weekday) = day.ok_or(format!("invalid BYDAY \"{}\"", day_name))?;
+                        if !by_day.contains(weekday) {
+                            by_day.push(*weekday);
+                        }
+                    }
+                    by_day.sort_by_key(|v| v.num_days_from_monday());
+                }
+                "COUNT" => {
+                    let value: u32 = val.trim().parse().map_err(|_| format!("invalid COUNT \"{}\"", val))?;
+                    if value == 0 {
+                        return Err("COUNT must be greater than 0".to_string());
+                    }
+                    count = Some(value);
+                }
+                "UNTIL" => {
+                    until = Some(Self::parse_until(val.trim()).ok_or(format!("invalid UNTIL \"{}\"", val))?);
+                }
+                _ => return Err(format!("unsupported part \"{}\"", name)),
+            }
+        }
+        let freq = opt_freq.ok_or("FREQ is required".to_string())?;
+        #[rustfmt::skip]
+        let interval_max = if freq == SeriesFreq::Daily { RRULE_INTERVAL_DAILY_MAX } else { RRULE_INTERVAL_WEEKLY_MAX };
+        if interval > interval_max {
+            return Err(format!("INTERVAL must be no more than {}", interval_max));
+        }
+        if count.is_some() && until.is_some() {
+            return Err("COUNT and UNTIL cannot be used together".to_string());
+        }
+        Ok(SeriesRule {
+            freq,
+            interval,
+            by_day,
+            count,
+            until,
+        })
+    }
+    fn parse_until(value: &str) -> Option<DateTime<Utc>> {
+        if let Ok(date_time) = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ") {
+            return Some(date_time.and_utc());
+        }
+        // The date includes the whole day.
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        Some(date.and_time(NaiveTime::from_hms_opt(23, 59, 59).unwrap()).and_utc())
+    }
+
+    /// Get the times of the occurrences (in ascending order) that start no later than "until".
+    /// The first occurrence is "dtstart" (if it matches the "BYDAY" part of the rule).
+    /// The occurrences are calculated in the local time, so they keep the local time of day when the offset changes (DST).
+    #[rustfmt::skip]
+    pub fn occurrences<Z: SeriesTimeZone>(
+        &self, dtstart: DateTime<Utc>, until: DateTime<Utc>, time_zone: &mut Z,
+    ) -> Result<Vec<DateTime<Utc>>, Z::Error> {
+        let until = self.until.map(|v| v.min(until)).unwrap_or(until);
+        if until < dtstart {
+            return Ok(vec![]);
+        }
+        let local_times = time_zone.to_local(&[dtstart, until])?;
+        // The local "until" is taken with a margin of a day, the later occurrences are dropped after the conversion.
+        let local_until = local_times[1].checked_add_signed(Duration::days(1)).unwrap_or(NaiveDateTime::MAX);
+        let occurrences = time_zone.to_utc(&self.local_occurrences(local_times[0], local_until))?;
+        Ok(occurrences.into_iter().filter(|v| *v <= until).collect())
+    }
+    /// Get the local times of the occurrences (in ascending order) that start no later than "until" (in the local time).
+    fn local_occurrences(&self, dtstart: NaiveDateTime, until: NaiveDateTime) -> Vec<NaiveDateTime> {
+        let mut result: Vec<NaiveDateTime> = Vec::new();
+        let max_count = self.count.map(|v| v as usize).unwrap_or(usize::MAX);
+        let start_date = dtstart.date();
+        let time = dtstart.time();
+        let step = Duration::days(self.interval.into());
+
+        // Get the dates of the occurrences in the period (day or week) that starts with the specified date.
+        let period_dates = |period: NaiveDate| -> Vec<NaiveDate> {
+            match self.freq {
+                SeriesFreq::Daily if self.by_day.is_empty() || self.by_day.contains(&period.weekday()) => vec![period],
+                SeriesFreq::Daily => vec![],
+                SeriesFreq::Weekly => {
+                    #[rustfmt::skip]
+                    let days = if !self.by_day.is_empty() { self.by_day.clone() } else { vec![start_date.weekday()] };
+                    days.iter()
+                        .filter_map(|day| period.checked_add_signed(Duration::days(day.num_days_from_monday().into())))
+                        .filter(|date| *date >= start_date)
+                        .collect()
+                }
+            }
+        };
+        let mut period = match self.freq {
+            SeriesFreq::Daily => start_date,
+            SeriesFreq::Weekly => start_date - Duration::days(start_date.weekday().num_days_from_monday().into()),
+        };
+        let step = if self.freq == SeriesFreq::Weekly { step * 7 } else { step };
+
+        while result.len() < max_count && period.and_time(time) <= until {
+            for date in period_dates(period) {
+                let date_time = date.and_time(time);
+                if date_time > until || result.len() >= max_count {
+                    break;
+                }
+                result.push(date_time);
+            }
+            period = match period.checked_add_signed(step) {
+                Some(next_period) => next_period,
+                None => break,
+            };
+        }
+        result
+    }
+    /// Get the number of occurrences that start before the specified time.
+    #[rustfmt::skip]
+    pub fn count_before<Z: SeriesTimeZone>(
+        &self, dtstart: DateTime<Utc>, time: DateTime<Utc>, time_zone: &mut Z,
+    ) -> Result<u32, Z::Error> {
+        let occurrences = self.occurrences(dtstart, time, time_zone)?;
+        Ok(occurrences.iter().filter(|v| **v < time).count() as u32)
+    }
+}
+
+impl fmt::Display for SeriesRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let freq = if self.freq == SeriesFreq::Daily { "DAILY" } else { "WEEKLY" };
+        let mut parts: Vec<String> = vec![format!("FREQ={}", freq)];
+        if self.interval != 1 {
+            parts.push(format!("INTERVAL={}", self.interval));
+        }
+        if !self.by_day.is_empty() {
+            let names: Vec<&str> = self
+                .by_day
+                .iter()
+                .filter_map(|day| WEEKDAY_NAMES.iter().find(|(_, weekday)| weekday == day).map(|(name, _)| *name))
+                .collect();
+            parts.push(format!("BYDAY={}", names.join(",")));
+        }
+        if let Some(count) = self.count {
+            parts.push(format!("COUNT={}", count));
+        }
+        if let Some(until) = self.until {
+            parts.push(format!("UNTIL={}", until.format("%Y%m%dT%H%M%SZ")));
+        }
+        write!(f, "{}", parts.join(";"))
+    }
+}
+
+// **  Section: table "stream_series" receiving data **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Queryable, Selectable)]
+#[diesel(table_name = schema::stream_series)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct StreamSeries {
+    pub id: i32,
+    pub user_id: i32,
+    pub title: String,                             // min_len=2 max_len=255
+    pub descript: String,                          // min_len=2,max_len=2048 default ""
+    pub source: String,                            // min_len=2 max_len=255 default "obs"
+    pub tags: Vec<String>,                         // min_amount=1 max_amount=4
+    pub starttime: DateTime<Utc>,                  // the start of the first occurrence
+    pub rrule: String,                             // max_len=255
+    pub materialized_until: Option<DateTime<Utc>>, // Nullable
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub time_zone: String, // max_len=64 default "UTC" (the name of the time zone of the occurrences)
+}
+
+impl StreamSeries {
+    pub fn create(create_stream_series: CreateStreamSeries, id: i32) -> StreamSeries {
+        let now = Utc::now();
+        StreamSeries {
+            id,
+            user_id: create_stream_series.user_id,
+            title: create_stream_series.title,
+            descript: create_stream_series.descript.unwrap_or_default(),
+            source: create_stream_series.source.unwrap_or("obs".to_string()),
+            tags: create_stream_series.tags,
+            starttime: create_stream_series.starttime,
+            rrule: create_stream_series.rrule,
+            materialized_until: None,
+            created_at: now,
+            updated_at: now,
+            time_zone: create_stream_series.time_zone.unwrap_or(TIME_ZONE_DEFAULT.to_string()),
+        }
+    }
+    /// Get the recurrence rule of the series.
+    pub fn rule(&self) -> Result<SeriesRule, String> {
+        SeriesRule::parse(&self.rrule)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamSeriesDto {
+    pub id: i32,
+    pub user_id: i32,
+    pub title: String,
+    pub descript: String,
+    pub source: String,
+    pub tags: Vec<String>,
+    #[serde(with = "serial_datetime")]
+    pub starttime: DateTime<Utc>,
+    pub rrule: String,
+    // The time zone in which the occurrences keep the time of day of the first stream.
+    pub time_zone: String,
+    // The time up to which the streams of the series have already been created.
+    #[rustfmt::skip]
+    #[serde(default, with = "serial_datetime_option", skip_serializing_if = "Option::is_none")]
+    pub materialized_until: Option<DateTime<Utc>>,
+    #[serde(with = "serial_datetime")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "serial_datetime")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<StreamSeries> for StreamSeriesDto {
+    fn from(stream_series: StreamSeries) -> Self {
+        StreamSeriesDto {
+            id: stream_series.id,
+            user_id: stream_series.user_id,
+            title: stream_series.title,
+            descript: stream_series.descript,
+            source: stream_series.source,
+            tags: stream_series.tags,
+            starttime: stream_series.starttime,
+            rrule: stream_series.rrule,
+            time_zone: stream_series.time_zone,
+            materialized_until: stream_series.materialized_until,
+            created_at: stream_series.created_at,
+            updated_at: stream_series.updated_at,
+        }
+    }
+}
+
+// **  Section: table "stream_series" data creation **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateStreamSeriesDto {
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub descript: Option<String>,
+    // The start of the first occurrence.
+    #[serde(with = "serial_datetime")]
+    pub starttime: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    pub tags: Vec<String>,
+    // The recurrence rule ("daily", "weekly", "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH;COUNT=10").
+    pub rrule: String,
+    // The name of the time zone of the occurrences ("Europe/Berlin"), "UTC" by default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_zone: Option<String>,
+}
+
+impl Validator for CreateStreamSeriesDto {
+    // Check the model against the required conditions.
+    fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors: Vec<Option<ValidationError>> = vec![];
+
+        errors.push(validate_title(&self.title).err());
+
+        if let Some(value) = &self.descript {
+            errors.push(validate_descript(value).err());
+        }
+        errors.push(validate_starttime(&self.starttime).err());
+
+        if let Some(value) = &self.source {
+            errors.push(validate_source(value).err());
+        }
+        if self.tags.is_empty() {
+            errors.push(ValidationChecks::required(&self.tags.join(","), MSG_TAG_REQUIRED).err());
+        } else {
+            errors.push(validate_tag(&self.tags).err());
+        }
+        errors.push(validate_rrule(&self.rrule).err());
+
+        if let Some(value) = &self.time_zone {
+            errors.push(validate_time_zone(value).err());
+        }
+
+        self.filter_errors(errors)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Insertable)]
+#[diesel(table_name = schema::stream_series)]
+pub struct CreateStreamSeries {
+    pub user_id: i32,
+    pub title: String,
+    pub descript: Option<String>, // default ""
+    pub source: Option<String>,   // default "obs"
+    pub tags: Vec<String>,
+    pub starttime: DateTime<Utc>,
+    pub rrule: String,
+    pub time_zone: Option<String>, // default "UTC"
+}
+
+impl CreateStreamSeries {
+    /// Convert the data into a new series (the recurrence rule is saved in the normalized form).
+    pub fn convert(create_stream_series_dto: CreateStreamSeriesDto, user_id: i32) -> Self {
+        #[rustfmt::skip]
+        let rrule = SeriesRule::parse(&create_stream_series_dto.rrule).map(|v| v.to_string())
+            .unwrap_or(create_stream_series_dto.rrule);
+        CreateStreamSeries {
+            user_id,
+            title: create_stream_series_dto.title,
+            descript: create_stream_series_dto.descript,
+            source: create_stream_series_dto.source,
+            tags: create_stream_series_dto.tags,
+            starttime: create_stream_series_dto.starttime,
+            rrule,
+            time_zone: create_stream_series_dto.time_zone,
+        }
+    }
+}
+
+// **  Section: table "stream_series" data editing **
+
+/// Which occurrences of the series are changed.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SeriesScope {
+    // Only the specified occurrence (the stream).
+    This,
+    // The specified occurrence and all the following ones (the series itself is also changed).
+    Future,
+}
+
+impl fmt::Display for SeriesScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string(&self).unwrap().replace("\"", ""))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ModifyStreamSeriesDto {
+    pub scope: SeriesScope,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub descript: Option<String>,
+    // For the "future" scope, this is the start of the new schedule (from the specified occurrence).
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "serial_datetime_option")]
+    pub starttime: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    // The new recurrence rule (only for the "future" scope).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rrule: Option<String>,
+    // The new time zone of the occurrences (only for the "future" scope).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_zone: Option<String>,
+}
+
+impl ModifyStreamSeriesDto {
+    pub fn valid_names<'a>() -> Vec<&'a str> {
+        vec!["title", "descript", "starttime", "source", "tags", "rrule", "timeZone"]
+    }
+}
+
+impl Validator for ModifyStreamSeriesDto {
+    // Check the model against the required conditions.
+    fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors: Vec<Option<ValidationError>> = vec![];
+
+        if let Some(value) = &self.title {
+            errors.push(validate_title(value).err());
+        }
+        if let Some(value) = &self.descript {
+            // The field is optional and we check if there is a value.
+            if !value.is_empty() {
+                errors.push(validate_descript(value).err());
+            }
+        }
+        if let Some(value) = &self.starttime {
+            errors.push(validate_starttime(value).err());
+        }
+        if let Some(value) = &self.source {
+            // The field is optional and we check if there is a value.
+            if !value.is_empty() {
+                errors.push(validate_source(value).err());
+            }
+        }
+        if let Some(value) = &self.tags {
+            errors.push(validate_tag(value).err());
+        }
+        if let Some(value) = &self.rrule {
+            if self.scope == SeriesScope::This {
+                let mut err = ValidationError::new(MSG_RRULE_ONLY_FOR_FUTURE);
+                let json = json!({ "actualValue": self.scope.to_string(), "validValue": SeriesScope::Future.to_string() });
+                errors.push(Some(err.add_param(Cow::Borrowed("scope"), &json)));
+            } else {
+                errors.push(validate_rrule(value).err());
+            }
+        }
+        if let Some(value) = &self.time_zone {
+            if self.scope == SeriesScope::This {
+                let mut err = ValidationError::new(MSG_TIME_ZONE_ONLY_FOR_FUTURE);
+                let json = json!({ "actualValue": self.scope.to_string(), "validValue": SeriesScope::Future.to_string() });
+                errors.push(Some(err.add_param(Cow::Borrowed("scope"), &json)));
+            } else {
+                errors.push(validate_time_zone(value).err());
+            }
+        }
+
+        let list_is_some = vec![
+            self.title.is_some(),
+            self.descript.is_some(),
+            self.starttime.is_some(),
+            self.source.is_some(),
+            self.tags.is_some(),
+            self.rrule.is_some(),
+            self.time_zone.is_some(),
+        ];
+        let valid_names = ModifyStreamSeriesDto::valid_names().join(",");
+        errors.push(ValidationChecks::no_fields_to_update(&list_is_some, &valid_names, err::MSG_NO_FIELDS_TO_UPDATE).err());
+
+        self.filter_errors(errors)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, AsChangeset)]
+#[diesel(table_name = schema::stream_series)]
+pub struct ModifyStreamSeries {
+    pub title: Option<String>,
+    pub descript: Option<String>,
+    pub source: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub starttime: Option<DateTime<Utc>>,
+    pub rrule: Option<String>,
+    pub materialized_until: Option<Option<DateTime<Utc>>>,
+    pub time_zone: Option<String>,
+}
+
+impl ModifyStreamSeries {
+    /// Whether the schedule of the series (the start, the recurrence rule or the time zone) is changed.
+    pub fn is_schedule_changed(&self) -> bool {
+        self.starttime.is_some() || self.rrule.is_some() || self.time_zone.is_some()
+    }
+}
+
+impl From<ModifyStreamSeriesDto> for ModifyStreamSeries {
+    fn from(modify_stream_series_dto: ModifyStreamSeriesDto) -> Self {
+        // The recurrence rule is saved in the normalized form.
+        #[rustfmt::skip]
+        let rrule = modify_stream_series_dto.rrule
+            .map(|rrule| SeriesRule::parse(&rrule).map(|v| v.to_string()).unwrap_or(rrule));
+        ModifyStreamSeries {
+            title: modify_stream_series_dto.title,
+            descript: modify_stream_series_dto.descript,
+            source: modify_stream_series_dto.source,
+            tags: modify_stream_series_dto.tags,
+            starttime: modify_stream_series_dto.starttime,
+            rrule,
+            materialized_until: None,
+            time_zone: modify_stream_series_dto.time_zone,
+        }
+    }
+}
+
+// **  Section: table "link_stream_series_to_streams" **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Queryable, Selectable)]
+#[diesel(table_name = schema::link_stream_series_to_streams)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct LinkStreamSeriesToStreams {
+    pub id: i32,
+    pub stream_series_id: i32,
+    pub stream_id: i32,
+    pub occurrence_time: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Insertable)]
+#[diesel(table_name = schema::link_stream_series_to_streams)]
+pub struct CreateLinkStreamSeriesToStreams {
+    pub stream_series_id: i32,
+    pub stream_id: i32,
+    pub occurrence_time: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Offset, TimeZone};
+
+    use super::*;
+
+    fn date_time(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap()
+    }
+    fn utc() -> FixedOffset {
+        Utc.fix()
+    }
+
+    // ** The time zone with the daylight saving time from 2030-03-31 01:00 UTC (the offset changes from +1 to +2). **
+    struct DstZoneTest {}
+
+    impl DstZoneTest {
+        fn offset_secs(is_summer: bool) -> i64 {
+            if is_summer { 7200 } else { 3600 }
+        }
+    }
+
+    impl SeriesTimeZone for DstZoneTest {
+        type Error = Infallible;
+
+        fn to_local(&mut self, values: &[DateTime<Utc>]) -> Result<Vec<NaiveDateTime>, Self::Error> {
+            let transition = date_time(2030, 3, 31, 1);
+            #[rustfmt::skip]
+            let result = values.iter().map(|v| v.naive_utc() + Duration::seconds(Self::offset_secs(*v >= transition))).collect();
+            Ok(result)
+        }
+        fn to_utc(&mut self, values: &[NaiveDateTime]) -> Result<Vec<DateTime<Utc>>, Self::Error> {
+            let transition = date_time(2030, 3, 31, 1).naive_utc() + Duration::seconds(Self::offset_secs(true));
+            #[rustfmt::skip]
+            let result = values.iter().map(|v| (*v - Duration::seconds(Self::offset_secs(*v >= transition))).and_utc()).collect();
+            Ok(result)
+        }
+    }
+
+    // ** SeriesRule::parse **
+
+    #[test]
+    fn test_series_rule_parse_keywords() {
+        assert_eq!(SeriesRule::parse("daily"), Ok(SeriesRule::new(SeriesFreq::Daily)));
+        assert_eq!(SeriesRule::parse(" Weekly "), Ok(SeriesRule::new(SeriesFreq::Weekly)));
+    }
+    #[test]
+    fn test_series_rule_parse_rrule() {
+        let rule = SeriesRule::parse("RRULE:freq=weekly;interval=2;byday=TH,MO,TH;count=10").unwrap();
+        assert_eq!(rule.freq, SeriesFreq::Weekly);
+        assert_eq!(rule.interval, 2);
+        assert_eq!(rule.by_day, vec![Weekday::Mon, Weekday::Thu]);
+        assert_eq!(rule.count, Some(10));
+        assert_eq!(rule.until, None);
+        assert_eq!(rule.to_string(), "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH;COUNT=10");
+    }
+    #[test]
+    fn test_series_rule_parse_until() {
+        let rule = SeriesRule::parse("FREQ=DAILY;UNTIL=20300105").unwrap();
+        assert_eq!(rule.until, Some(Utc.with_ymd_and_hms(2030, 1, 5, 23, 59, 59).unwrap()));
+        let rule = SeriesRule::parse("FREQ=DAILY;UNTIL=20300105T100000Z").unwrap();
+        assert_eq!(rule.until, Some(date_time(2030, 1, 5, 10)));
+        assert_eq!(rule.to_string(), "FREQ=DAILY;UNTIL=20300105T100000Z");
+    }
+    #[test]
+    fn test_series_rule_parse_invalid() {
+        let values = [
+            "", "monthly", "FREQ=MONTHLY", "INTERVAL=2", "FREQ=DAILY;INTERVAL=0", "FREQ=DAILY;INTERVAL=a", "FREQ=WEEKLY;BYDAY=XX",
+            "FREQ=DAILY;COUNT=0", "FREQ=DAILY;UNTIL=2030", "FREQ=DAILY;COUNT=2;UNTIL=20300105", "FREQ=DAILY;BYMONTH=1", "FREQ=DAILY;COUNT",
+            "FREQ=DAILY;INTERVAL=367", "INTERVAL=53;FREQ=WEEKLY", "FREQ=WEEKLY;INTERVAL=100000000",
+        ];
+        for value in values {
+            assert!(SeriesRule::parse(value).is_err(), "value: \"{}\"", value);
+        }
+    }
+
+    #[test]
+    fn test_series_rule_parse_interval_max() {
+        let rule = SeriesRule::parse("FREQ=DAILY;INTERVAL=366").unwrap();
+        assert_eq!(rule.interval, RRULE_INTERVAL_DAILY_MAX);
+        let rule = SeriesRule::parse("FREQ=WEEKLY;INTERVAL=52").unwrap();
+        assert_eq!(rule.interval, RRULE_INTERVAL_WEEKLY_MAX);
+    }
+
+    // ** SeriesRule::occurrences **
+
+    #[test]
+    fn test_series_rule_occurrences_daily() {
+        let rule = SeriesRule::parse("FREQ=DAILY;INTERVAL=2").unwrap();
+        let dtstart = date_time(2030, 1, 1, 18);
+        let result = rule.occurrences(dtstart, date_time(2030, 1, 7, 18), &mut utc()).unwrap();
+        #[rustfmt::skip]
+        let expected = vec![date_time(2030, 1, 1, 18), date_time(2030, 1, 3, 18), date_time(2030, 1, 5, 18), date_time(2030, 1, 7, 18)];
+        assert_eq!(result, expected);
+    }
+    #[test]
+    fn test_series_rule_occurrences_daily_by_day() {
+        // 2030-01-04 is a Friday.
+        let rule = SeriesRule::parse("FREQ=DAILY;BYDAY=SA,SU").unwrap();
+        let result = rule.occurrences(date_time(2030, 1, 4, 9), date_time(2030, 1, 13, 0), &mut utc()).unwrap();
+        #[rustfmt::skip]
+        let expected = vec![date_time(2030, 1, 5, 9), date_time(2030, 1, 6, 9), date_time(2030, 1, 12, 9)];
+        assert_eq!(result, expected);
+    }
+    #[test]
+    fn test_series_rule_occurrences_weekly() {
+        // 2030-01-02 is a Wednesday.
+        let rule = SeriesRule::parse("weekly").unwrap();
+        let result = rule
+            .occurrences(date_time(2030, 1, 2, 20), date_time(2030, 1, 23, 20), &mut utc())
+            .unwrap();
+        #[rustfmt::skip]
+        let expected = vec![date_time(2030, 1, 2, 20), date_time(2030, 1, 9, 20), date_time(2030, 1, 16, 20), date_time(2030, 1, 23, 20)];
+        assert_eq!(result, expected);
+    }
+    #[test]
+    fn test_series_rule_occurrences_weekly_by_day_and_interval() {
+        // 2030-01-02 is a Wednesday. The Monday of its week (2029-12-31) is before the start and is skipped.
+        let rule = SeriesRule::parse("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH").unwrap();
+        let result = rule
+            .occurrences(date_time(2030, 1, 2, 20), date_time(2030, 1, 31, 0), &mut utc())
+            .unwrap();
+        #[rustfmt::skip]
+        let expected = vec![date_time(2030, 1, 3, 20), date_time(2030, 1, 14, 20), date_time(2030, 1, 17, 20), date_time(2030, 1, 28, 20)];
+        assert_eq!(result, expected);
+    }
+    #[test]
+    fn test_series_rule_occurrences_count_and_until() {
+        let dtstart = date_time(2030, 1, 1, 18);
+        let rule = SeriesRule::parse("FREQ=DAILY;COUNT=3").unwrap();
+        let result = rule.occurrences(dtstart, date_time(2030, 2, 1, 0), &mut utc()).unwrap();
+        assert_eq!(result, vec![date_time(2030, 1, 1, 18), date_time(2030, 1, 2, 18), date_time(2030, 1, 3, 18)]);
+
+        let rule = SeriesRule::parse("FREQ=DAILY;UNTIL=20300102").unwrap();
+        let result = rule.occurrences(dtstart, date_time(2030, 2, 1, 0), &mut utc()).unwrap();
+        assert_eq!(result, vec![date_time(2030, 1, 1, 18), date_time(2030, 1, 2, 18)]);
+    }
+    #[test]
+    fn test_series_rule_occurrences_until_max_date() {
+        // The next period is beyond the range of dates.
+        let rule = SeriesRule::parse("FREQ=WEEKLY;INTERVAL=52").unwrap();
+        let dtstart = date_time(2030, 1, 2, 20);
+        let result = rule.occurrences(dtstart, DateTime::<Utc>::MAX_UTC, &mut utc()).unwrap();
+        assert_eq!(result.first(), Some(&dtstart));
+        assert!(result.windows(2).all(|v| v[0] < v[1]));
+    }
+    #[test]
+    fn test_series_rule_occurrences_in_time_zone() {
+        // 2030-01-02 is a Wednesday. In the time zone +09:00 the first stream starts on Thursday at 08:00.
+        let rule = SeriesRule::parse("FREQ=WEEKLY;BYDAY=TH").unwrap();
+        let mut time_zone = FixedOffset::east_opt(9 * 3600).unwrap();
+        let result = rule
+            .occurrences(date_time(2030, 1, 2, 23), date_time(2030, 1, 17, 0), &mut time_zone)
+            .unwrap();
+        assert_eq!(result, vec![date_time(2030, 1, 2, 23), date_time(2030, 1, 9, 23), date_time(2030, 1, 16, 23)]);
+    }
+    #[test]
+    fn test_series_rule_occurrences_keep_local_time_after_dst() {
+        // The streams start at 19:00 of the local time (18:00 UTC in winter and 17:00 UTC in summer).
+        let rule = SeriesRule::parse("FREQ=DAILY").unwrap();
+        let result = rule
+            .occurrences(date_time(2030, 3, 29, 18), date_time(2030, 4, 1, 18), &mut DstZoneTest {})
+            .unwrap();
+        #[rustfmt::skip]
+        let expected = vec![date_time(2030, 3, 29, 18), date_time(2030, 3, 30, 18), date_time(2030, 3, 31, 17), date_time(2030, 4, 1, 17)];
+        assert_eq!(result, expected);
+        let result = rule
+            .count_before(date_time(2030, 3, 29, 18), date_time(2030, 4, 1, 17), &mut DstZoneTest {})
+            .unwrap();
+        assert_eq!(result, 3);
+    }
+    #[test]
+    fn test_series_rule_count_before() {
+        let dtstart = date_time(2030, 1, 1, 18);
+        let rule = SeriesRule::parse("FREQ=DAILY;COUNT=5").unwrap();
+        assert_eq!(rule.count_before(dtstart, date_time(2030, 1, 1, 18), &mut utc()).unwrap(), 0);
+        assert_eq!(rule.count_before(dtstart, date_time(2030, 1, 3, 18), &mut utc()).unwrap(), 2);
+        assert_eq!(rule.count_before(dtstart, date_time(2030, 2, 1, 0), &mut utc()).unwrap(), 5);
+    }
+
+    // ** validate_time_zone **
+
+    #[test]
+    fn test_validate_time_zone() {
+        for value in ["UTC", "Europe/Berlin", "America/Argentina/Buenos_Aires", "Etc/GMT+5"] {
+            assert!(validate_time_zone(value).is_ok(), "value: \"{}\"", value);
+        }
+        let value = "Europe/Berlin'; --";
+        assert_eq!(validate_time_zone(value).unwrap_err().message, MSG_TIME_ZONE_INVALID);
+        assert_eq!(validate_time_zone("").unwrap_err().message, MSG_TIME_ZONE_INVALID);
+        let value = "a".repeat((TIME_ZONE_MAX + 1).into());
+        assert_eq!(validate_time_zone(&value).unwrap_err().message, MSG_TIME_ZONE_MAX_LENGTH);
+    }
+
+    // ** ModifyStreamSeriesDto **
+
+    #[test]
+    fn test_modify_stream_series_dto_rrule_only_for_future() {
+        let mut modify_stream_series_dto = ModifyStreamSeriesDto {
+            scope: SeriesScope::This,
+            title: None,
+            descript: None,
+            starttime: None,
+            source: None,
+            tags: None,
+            rrule: Some("daily".to_string()),
+            time_zone: None,
+        };
+        let errors = modify_stream_series_dto.validate().err().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message, MSG_RRULE_ONLY_FOR_FUTURE);
+
+        modify_stream_series_dto.scope = SeriesScope::Future;
+        assert!(modify_stream_series_dto.validate().is_ok());
+    }
+    #[test]
+    fn test_modify_stream_series_dto_time_zone_only_for_future() {
+        let mut modify_stream_series_dto = ModifyStreamSeriesDto {
+            scope: SeriesScope::This,
+            title: None,
+            descript: None,
+            starttime: None,
+            source: None,
+            tags: None,
+            rrule: None,
+            time_zone: Some("Europe/Berlin".to_string()),
+        };
+        let errors = modify_stream_series_dto.validate().err().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message, MSG_TIME_ZONE_ONLY_FOR_FUTURE);
+
+        modify_stream_series_dto.scope = SeriesScope::Future;
+        assert!(modify_stream_series_dto.validate().is_ok());
+    }
+}
@@ -0,0 +1,1025 @@
+use chrono::{DateTime, Utc};
+use vrb_dbase::dbase::DbPool;
+
+use super::stream_models::{Stream, StreamTagStreamId};
+use super::stream_series_models::{CreateStreamSeries, ModifyStreamSeries, SeriesScope, StreamSeries};
+
+pub trait StreamSeriesOrm {
+    /// Get a list of entities (stream_series) of the user.
+    fn get_stream_series(&self, user_id: i32) -> Result<Vec<StreamSeries>, String>;
+
+    /// Find an entity (stream_series) by ID. If the user is specified, then only among the user's series.
+    fn find_stream_series_by_id(&self, id: i32, opt_user_id: Option<i32>) -> Result<Option<StreamSeries>, String>;
+
+    /// Get a list of the streams (occurrences) of the series.
+    fn get_stream_series_streams(&self, id: i32) -> Result<(Vec<Stream>, Vec<StreamTagStreamId>), String>;
+
+    /// Add a new entity (stream_series) and create its streams up to the horizon.
+    /// Returns the series and the created streams with their tags.
+    #[rustfmt::skip]
+    fn create_stream_series(
+        &self, create_stream_series: CreateStreamSeries, horizon: DateTime<Utc>,
+    ) -> Result<(StreamSeries, Vec<Stream>, Vec<StreamTagStreamId>), String>;
+
+    /// Modify the specified occurrence (stream) of the series ("this") or the series from this occurrence on ("future").
+    /// For "future", a change of the start or the rule recreates the waiting streams from this occurrence on.
+    /// Returns the changed (or recreated) streams, the deleted streams and the tags of both.
+    #[rustfmt::skip]
+    fn modify_stream_series(
+        &self, id: i32, stream_id: i32, opt_user_id: Option<i32>, scope: SeriesScope, modify_stream_series: ModifyStreamSeries,
+        horizon: DateTime<Utc>,
+    ) -> Result<Option<(Vec<Stream>, Vec<Stream>, Vec<StreamTagStreamId>)>, String>;
+
+    /// Delete an entity (stream_series) together with its future waiting streams.
+    /// The other streams of the series remain as ordinary streams.
+    /// Returns the series and the deleted streams with their tags.
+    #[rustfmt::skip]
+    fn delete_stream_series(
+        &self, id: i32, opt_user_id: Option<i32>,
+    ) -> Result<Option<(StreamSeries, Vec<Stream>, Vec<StreamTagStreamId>)>, String>;
+
+    /// Create the streams of the series (all or the specified one) that have come within the horizon.
+    /// Returns the created streams with their tags.
+    #[rustfmt::skip]
+    fn materialize_stream_series(
+        &self, opt_id: Option<i32>, horizon: DateTime<Utc>,
+    ) -> Result<(Vec<Stream>, Vec<StreamTagStreamId>), String>;
+
+    /// Check that the time zone with the specified name is known ("Europe/Berlin").
+    fn is_time_zone_known(&self, name: &str) -> Result<bool, String>;
+}
+
+#[cfg(not(all(test, feature = "mockdata")))]
+pub fn get_stream_series_orm_app(pool: DbPool) -> impls::StreamSeriesOrmApp {
+    impls::StreamSeriesOrmApp::new(pool)
+}
+#[cfg(all(test, feature = "mockdata"))]
+pub fn get_stream_series_orm_app(_: DbPool) -> tests::StreamSeriesOrmApp {
+    tests::StreamSeriesOrmApp::new()
+}
+
+#[cfg(not(all(test, feature = "mockdata")))]
+pub mod impls {
+    use std::time::Instant as tm;
+
+    use chrono::NaiveDateTime;
+    use diesel::{self, prelude::*, sql_types};
+    use log::{Level::Info, info, log_enabled};
+    use vrb_dbase::dbase;
+    use vrb_dbase::enm_stream_state::StreamState;
+    use vrb_dbase::schema::{
+        self, link_stream_series_to_streams::dsl as links_dsl, stream_series::dsl as series_dsl, streams::dsl as streams_dsl,
+    };
+
+    use crate::stream_models::{CreateStream, ModifyStream};
+    use crate::stream_series_models::{CreateLinkStreamSeriesToStreams, LinkStreamSeriesToStreams, SeriesTimeZone};
+
+    use super::*;
+
+    pub const CONN_POOL: &str = "ConnectionPool";
+
+    #[derive(Debug, Clone, QueryableByName)]
+    struct UtcTime {
+        #[diesel(sql_type = sql_types::Timestamptz)]
+        value: DateTime<Utc>,
+    }
+
+    #[derive(Debug, Clone, QueryableByName)]
+    struct LocalTime {
+        #[diesel(sql_type = sql_types::Timestamp)]
+        value: NaiveDateTime,
+    }
+
+    #[derive(Debug, Clone, QueryableByName)]
+    struct IsExist {
+        #[diesel(sql_type = sql_types::Bool)]
+        value: bool,
+    }
+
+    // ** The time zone of the series, the times are converted by the database (by the name of the time zone). **
+    struct PgTimeZone<'a> {
+        conn: &'a mut dbase::DbPooledConnection,
+        name: &'a str,
+    }
+
+    impl SeriesTimeZone for PgTimeZone<'_> {
+        type Error = diesel::result::Error;
+
+        fn to_local(&mut self, values: &[DateTime<Utc>]) -> Result<Vec<NaiveDateTime>, Self::Error> {
+            #[rustfmt::skip]
+            let query = diesel::sql_query(
+                "SELECT v.t AT TIME ZONE $2 AS value FROM unnest($1::TIMESTAMPTZ[]) WITH ORDINALITY AS v(t, n) ORDER BY v.n;")
+                .bind::<sql_types::Array<sql_types::Timestamptz>, _>(values)
+                .bind::<sql_types::Text, _>(self.name);
+
+            let local_times = query.get_results::<LocalTime>(self.conn)?;
+            Ok(local_times.into_iter().map(|v| v.value).collect())
+        }
+        fn to_utc(&mut self, values: &[NaiveDateTime]) -> Result<Vec<DateTime<Utc>>, Self::Error> {
+            // A nonexistent local time (in the gap of the daylight saving time) is shifted forward by the gap.
+            #[rustfmt::skip]
+            let query = diesel::sql_query(
+                "SELECT v.t AT TIME ZONE $2 AS value FROM unnest($1::TIMESTAMP[]) WITH ORDINALITY AS v(t, n) ORDER BY v.n;")
+                .bind::<sql_types::Array<sql_types::Timestamp>, _>(values)
+                .bind::<sql_types::Text, _>(self.name);
+
+            let utc_times = query.get_results::<UtcTime>(self.conn)?;
+            Ok(utc_times.into_iter().map(|v| v.value).collect())
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct StreamSeriesOrmApp {
+        pub pool: dbase::DbPool,
+    }
+
+    impl StreamSeriesOrmApp {
+        pub fn new(pool: dbase::DbPool) -> Self {
+            StreamSeriesOrmApp { pool }
+        }
+        pub fn get_conn(&self) -> Result<dbase::DbPooledConnection, String> {
+            (&self.pool).get().map_err(|e| format!("{}: {}", CONN_POOL, e.to_string()))
+        }
+        /// Get a list of "tags" for the specified "streams".
+        fn get_stream_tags(
+            &self,
+            conn: &mut dbase::DbPooledConnection,
+            ids: &[i32],
+        ) -> Result<Vec<StreamTagStreamId>, diesel::result::Error> {
+            #[rustfmt::skip]
+            let query = diesel::sql_query("select * from get_stream_tags_names($1);")
+                .bind::<sql_types::Array<sql_types::Integer>, _>(ids);
+
+            query.get_results::<StreamTagStreamId>(conn)
+        }
+        /// Update the list of "tags" for the specified "stream".
+        fn update_list_stream_tags(
+            &self,
+            conn: &mut dbase::DbPooledConnection,
+            stream_id: i32,
+            user_id: i32,
+            tags: &[String],
+        ) -> Result<usize, diesel::result::Error> {
+            let stream_tags: Vec<String> = tags.iter().map(|tag| tag.to_lowercase().trim().to_string()).collect();
+            // Run query using Diesel to add a list of "stream_tags" for the entity (stream).
+            let query = diesel::sql_query("CALL update_list_stream_tags($1, $2, $3);")
+                .bind::<sql_types::Integer, _>(stream_id)
+                .bind::<sql_types::Integer, _>(user_id)
+                .bind::<sql_types::Array<sql_types::Text>, _>(stream_tags);
+
+            query.execute(conn)
+        }
+        /// Update the "stream_tags" data for user.
+        fn update_stream_tags_for_user(&self, conn: &mut dbase::DbPooledConnection, user_id: i32) -> Result<(), diesel::result::Error> {
+            // Run query using Diesel to update the list of "stream_tags" for the user.
+            #[rustfmt::skip]
+            let query =
+                diesel::sql_query("CALL update_stream_tags_for_user($1);")
+                    .bind::<sql_types::Integer, _>(user_id);
+
+            query.execute(conn)?;
+            Ok(())
+        }
+        /// Get the series by ID and lock it until the end of the transaction.
+        /// If the user is specified, then only among the user's series.
+        fn lock_stream_series(
+            &self,
+            conn: &mut dbase::DbPooledConnection,
+            id: i32,
+            opt_user_id: Option<i32>,
+        ) -> Result<Option<StreamSeries>, diesel::result::Error> {
+            let opt_stream_series = schema::stream_series::table
+                .filter(series_dsl::id.eq(id))
+                .for_update()
+                .select(StreamSeries::as_select())
+                .first::<StreamSeries>(conn)
+                .optional()?;
+
+            Ok(opt_stream_series.filter(|v| opt_user_id.map(|user_id| v.user_id == user_id).unwrap_or(true)))
+        }
+        /// Get the waiting streams of the series whose occurrence time is not earlier than the specified one.
+        fn get_waiting_streams(
+            &self,
+            conn: &mut dbase::DbPooledConnection,
+            id: i32,
+            from: DateTime<Utc>,
+        ) -> Result<Vec<Stream>, diesel::result::Error> {
+            schema::streams::table
+                .inner_join(schema::link_stream_series_to_streams::table)
+                .filter(links_dsl::stream_series_id.eq(id))
+                .filter(links_dsl::occurrence_time.ge(from))
+                .filter(streams_dsl::state.eq(StreamState::Waiting))
+                .order_by(streams_dsl::starttime.asc())
+                .select(Stream::as_select())
+                .load::<Stream>(conn)
+        }
+        /// Create the streams of the series whose start has come within the horizon (and has not yet passed).
+        fn materialize(
+            &self,
+            conn: &mut dbase::DbPooledConnection,
+            stream_series: &StreamSeries,
+            horizon: DateTime<Utc>,
+        ) -> Result<Vec<Stream>, diesel::result::Error> {
+            let mut result: Vec<Stream> = Vec::new();
+            let Ok(rule) = stream_series.rule() else {
+                return Ok(result);
+            };
+            let now = Utc::now();
+            let materialized_until = stream_series.materialized_until;
+
+            // Get the occurrence times that already have streams.
+            let occurrence_times: Vec<DateTime<Utc>> = schema::link_stream_series_to_streams::table
+                .filter(links_dsl::stream_series_id.eq(stream_series.id))
+                .select(links_dsl::occurrence_time)
+                .load(conn)?;
+            let mut time_zone = PgTimeZone {
+                conn,
+                name: &stream_series.time_zone,
+            };
+            let occurrences = rule.occurrences(stream_series.starttime, horizon, &mut time_zone)?;
+
+            for occurrence_time in occurrences {
+                let is_new = materialized_until.map(|v| occurrence_time > v).unwrap_or(true);
+                if occurrence_time < now || !is_new || occurrence_times.contains(&occurrence_time) {
+                    continue;
+                }
+                let create_stream = CreateStream {
+                    user_id: stream_series.user_id,
+                    title: stream_series.title.clone(),
+                    descript: Some(stream_series.descript.clone()),
+                    logo: None,
+                    starttime: occurrence_time,
+                    state: None,
+                    started: None,
+                    paused: None,
+                    stopped: None,
+                    source: Some(stream_series.source.clone()),
+                };
+                // Run query using Diesel to add a new entry (stream).
+                let stream = diesel::insert_into(schema::streams::table)
+                    .values(create_stream)
+                    .returning(Stream::as_returning())
+                    .get_result(conn)?;
+                // Update the list of "tags" for the specified "stream".
+                self.update_list_stream_tags(conn, stream.id, stream.user_id, &stream_series.tags)?;
+
+                let create_link = CreateLinkStreamSeriesToStreams {
+                    stream_series_id: stream_series.id,
+                    stream_id: stream.id,
+                    occurrence_time,
+                };
+                diesel::insert_into(schema::link_stream_series_to_streams::table)
+                    .values(create_link)
+                    .execute(conn)?;
+                result.push(stream);
+            }
+            if materialized_until.map(|v| v < horizon).unwrap_or(true) {
+                diesel::update(schema::stream_series::table.filter(series_dsl::id.eq(stream_series.id)))
+                    .set(series_dsl::materialized_until.eq(Some(horizon)))
+                    .execute(conn)?;
+            }
+            Ok(result)
+        }
+    }
+
+    impl StreamSeriesOrm for StreamSeriesOrmApp {
+        /// Get a list of entities (stream_series) of the user.
+        fn get_stream_series(&self, user_id: i32) -> Result<Vec<StreamSeries>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            // Run query using Diesel to get the series of the user.
+            let stream_series_list: Vec<StreamSeries> = schema::stream_series::table
+                .filter(series_dsl::user_id.eq(user_id))
+                .order_by(series_dsl::id.asc())
+                .select(StreamSeries::as_select())
+                .load(&mut conn)
+                .map_err(|e| format!("get_stream_series: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("get_stream_series() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(stream_series_list)
+        }
+
+        /// Find an entity (stream_series) by ID. If the user is specified, then only among the user's series.
+        fn find_stream_series_by_id(&self, id: i32, opt_user_id: Option<i32>) -> Result<Option<StreamSeries>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            let mut query = schema::stream_series::table.into_boxed();
+            // Add a filter by unique series identifier.
+            query = query.filter(series_dsl::id.eq(id));
+            if let Some(user_id) = opt_user_id {
+                // Add an additional filter by user ID.
+                query = query.filter(series_dsl::user_id.eq(user_id));
+            }
+            // Run query using Diesel to find the series.
+            let opt_stream_series = query
+                .select(StreamSeries::as_select())
+                .first::<StreamSeries>(&mut conn)
+                .optional()
+                .map_err(|e| format!("find_stream_series_by_id: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("find_stream_series_by_id() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(opt_stream_series)
+        }
+
+        /// Get a list of the streams (occurrences) of the series.
+        fn get_stream_series_streams(&self, id: i32) -> Result<(Vec<Stream>, Vec<StreamTagStreamId>), String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            // Run query using Diesel to get the streams of the series.
+            let streams: Vec<Stream> = schema::streams::table
+                .inner_join(schema::link_stream_series_to_streams::table)
+                .filter(links_dsl::stream_series_id.eq(id))
+                .order_by(streams_dsl::starttime.asc())
+                .select(Stream::as_select())
+                .load(&mut conn)
+                .map_err(|e| format!("get_stream_series_streams: {}", e.to_string()))?;
+
+            let ids: Vec<i32> = streams.iter().map(|stream| stream.id).collect();
+            // Get a list of "tags" for the specified "streams".
+            let stream_tags = self
+                .get_stream_tags(&mut conn, &ids)
+                .map_err(|e| format!("get_stream_tags_names: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("get_stream_series_streams() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok((streams, stream_tags))
+        }
+
+        /// Add a new entity (stream_series) and create its streams up to the horizon.
+        /// Returns the series and the created streams with their tags.
+        #[rustfmt::skip]
+        fn create_stream_series(
+            &self, create_stream_series: CreateStreamSeries, horizon: DateTime<Utc>,
+        ) -> Result<(StreamSeries, Vec<Stream>, Vec<StreamTagStreamId>), String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            let res_data = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                // Run query using Diesel to add a new entry (stream_series).
+                let stream_series = diesel::insert_into(schema::stream_series::table)
+                    .values(create_stream_series)
+                    .returning(StreamSeries::as_returning())
+                    .get_result(conn)?;
+
+                // Create the streams of the series up to the horizon.
+                let streams = self.materialize(conn, &stream_series, horizon)?;
+
+                let ids: Vec<i32> = streams.iter().map(|stream| stream.id).collect();
+                // Get a list of "tags" for the specified "streams".
+                let stream_tags = self.get_stream_tags(conn, &ids)?;
+
+                // Get the series with the updated time of materialization.
+                let stream_series = schema::stream_series::table
+                    .filter(series_dsl::id.eq(stream_series.id))
+                    .select(StreamSeries::as_select())
+                    .first::<StreamSeries>(conn)?;
+
+                Ok((stream_series, streams, stream_tags))
+            });
+
+            if let Some(timer) = timer {
+                info!("create_stream_series() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            res_data.map_err(|e| format!("create_stream_series: {}", e.to_string()))
+        }
+
+        /// Modify the specified occurrence (stream) of the series ("this") or the series from this occurrence on ("future").
+        /// For "future", a change of the start or the rule recreates the waiting streams from this occurrence on.
+        /// Returns the changed (or recreated) streams, the deleted streams and the tags of both.
+        #[rustfmt::skip]
+        fn modify_stream_series(
+            &self, id: i32, stream_id: i32, opt_user_id: Option<i32>, scope: SeriesScope, modify_stream_series: ModifyStreamSeries,
+            horizon: DateTime<Utc>,
+        ) -> Result<Option<(Vec<Stream>, Vec<Stream>, Vec<StreamTagStreamId>)>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            let res_data = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let Some(stream_series) = self.lock_stream_series(conn, id, opt_user_id)? else {
+                    return Ok(None);
+                };
+                // Find the occurrence of the series.
+                let opt_link = schema::link_stream_series_to_streams::table
+                    .filter(links_dsl::stream_series_id.eq(id))
+                    .filter(links_dsl::stream_id.eq(stream_id))
+                    .select(LinkStreamSeriesToStreams::as_select())
+                    .first::<LinkStreamSeriesToStreams>(conn)
+                    .optional()?;
+                let Some(link) = opt_link else {
+                    return Ok(None);
+                };
+                let mut modify_stream_series = modify_stream_series;
+                let opt_tags = modify_stream_series.tags.clone();
+                let mut deleted_streams: Vec<Stream> = Vec::new();
+                let mut deleted_stream_tags: Vec<StreamTagStreamId> = Vec::new();
+
+                let streams: Vec<Stream> = if scope == SeriesScope::This {
+                    let modify_stream = ModifyStream {
+                        title: modify_stream_series.title.clone(),
+                        descript: modify_stream_series.descript.clone(),
+                        logo: None,
+                        starttime: modify_stream_series.starttime,
+                        state: None,
+                        started: None,
+                        paused: None,
+                        stopped: None,
+                        source: modify_stream_series.source.clone(),
+                    };
+                    let query = schema::streams::table.filter(streams_dsl::id.eq(stream_id));
+                    // Run query using Diesel to update the entry (stream).
+                    let stream = if modify_stream.is_empty() {
+                        query.first::<Stream>(conn)?
+                    } else {
+                        diesel::update(query).set(&modify_stream).returning(Stream::as_returning()).get_result(conn)?
+                    };
+                    if let Some(tags) = opt_tags {
+                        // Update the list of "tags" for the specified "stream".
+                        self.update_list_stream_tags(conn, stream.id, stream.user_id, &tags)?;
+                    }
+                    vec![stream]
+                } else {
+                    let from = link.occurrence_time;
+                    if modify_stream_series.is_schedule_changed() {
+                        let stream_ids: Vec<i32> = schema::link_stream_series_to_streams::table
+                            .filter(links_dsl::stream_series_id.eq(id))
+                            .filter(links_dsl::occurrence_time.ge(from))
+                            .select(links_dsl::stream_id)
+                            .load(conn)?;
+                        // Get a list of "tags" for the streams before they are deleted.
+                        deleted_stream_tags = self.get_stream_tags(conn, &stream_ids)?;
+                        // Delete the waiting streams, they will be created again according to the new schedule.
+                        deleted_streams = diesel::delete(
+                            schema::streams::table
+                                .filter(streams_dsl::id.eq_any(stream_ids))
+                                .filter(streams_dsl::state.eq(StreamState::Waiting)),
+                        )
+                        .returning(Stream::as_returning())
+                        .get_results(conn)?;
+
+                        // The schedule now starts with the specified occurrence.
+                        if modify_stream_series.rrule.is_none() {
+                            // The occurrences before it are no longer counted by the rule.
+                            if let Ok(mut rule) = stream_series.rule() {
+                                if let Some(count) = rule.count {
+                                    let mut time_zone = PgTimeZone { conn, name: &stream_series.time_zone };
+                                    let count_before = rule.count_before(stream_series.starttime, from, &mut time_zone)?;
+                                    rule.count = Some(count.saturating_sub(count_before).max(1));
+                                    modify_stream_series.rrule = Some(rule.to_string());
+                                }
+                            }
+                        }
+                        if modify_stream_series.starttime.is_none() {
+                            modify_stream_series.starttime = Some(from);
+                        }
+                        modify_stream_series.materialized_until = Some(None);
+
+                        // Run query using Diesel to update the entry (stream_series).
+                        let stream_series = diesel::update(schema::stream_series::table.filter(series_dsl::id.eq(id)))
+                            .set(&modify_stream_series)
+                            .returning(StreamSeries::as_returning())
+                            .get_result(conn)?;
+                        if !deleted_streams.is_empty() {
+                            // Update the "stream_tags" data for user.
+                            self.update_stream_tags_for_user(conn, stream_series.user_id)?;
+                        }
+                        // Create the streams of the series according to the new schedule.
+                        self.materialize(conn, &stream_series, horizon)?;
+                    } else {
+                        // Run query using Diesel to update the entry (stream_series).
+                        diesel::update(schema::stream_series::table.filter(series_dsl::id.eq(id)))
+                            .set(&modify_stream_series)
+                            .execute(conn)?;
+
+                        let modify_stream = ModifyStream {
+                            title: modify_stream_series.title.clone(),
+                            descript: modify_stream_series.descript.clone(),
+                            logo: None,
+                            starttime: None,
+                            state: None,
+                            started: None,
+                            paused: None,
+                            stopped: None,
+                            source: modify_stream_series.source.clone(),
+                        };
+                        for stream in self.get_waiting_streams(conn, id, from)? {
+                            if !modify_stream.is_empty() {
+                                // Run query using Diesel to update the entry (stream).
+                                diesel::update(schema::streams::table.filter(streams_dsl::id.eq(stream.id)))
+                                    .set(&modify_stream)
+                                    .execute(conn)?;
+                            }
+                            if let Some(tags) = &opt_tags {
+                                // Update the list of "tags" for the specified "stream".
+                                self.update_list_stream_tags(conn, stream.id, stream.user_id, tags)?;
+                            }
+                        }
+                    }
+                    self.get_waiting_streams(conn, id, from)?
+                };
+
+                let ids: Vec<i32> = streams.iter().map(|stream| stream.id).collect();
+                // Get a list of "tags" for the specified "streams".
+                let mut stream_tags = self.get_stream_tags(conn, &ids)?;
+                // Add the "tags" of the deleted streams.
+                let deleted_ids: Vec<i32> = deleted_streams.iter().map(|stream| stream.id).collect();
+                stream_tags.extend(deleted_stream_tags.into_iter().filter(|v| deleted_ids.contains(&v.stream_id)));
+
+                Ok(Some((streams, deleted_streams, stream_tags)))
+            });
+
+            if let Some(timer) = timer {
+                info!("modify_stream_series() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            res_data.map_err(|e| format!("modify_stream_series: {}", e.to_string()))
+        }
+
+        /// Delete an entity (stream_series) together with its future waiting streams.
+        /// The other streams of the series remain as ordinary streams.
+        /// Returns the series and the deleted streams with their tags.
+        #[rustfmt::skip]
+        fn delete_stream_series(
+            &self, id: i32, opt_user_id: Option<i32>,
+        ) -> Result<Option<(StreamSeries, Vec<Stream>, Vec<StreamTagStreamId>)>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            let res_data = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let Some(stream_series) = self.lock_stream_series(conn, id, opt_user_id)? else {
+                    return Ok(None);
+                };
+                let stream_ids: Vec<i32> = schema::link_stream_series_to_streams::table
+                    .filter(links_dsl::stream_series_id.eq(id))
+                    .select(links_dsl::stream_id)
+                    .load(conn)?;
+                // Get a list of "tags" for the streams before they are deleted.
+                let stream_tags = self.get_stream_tags(conn, &stream_ids)?;
+                // Delete the future streams of the series that have not yet started.
+                let deleted_streams: Vec<Stream> = diesel::delete(
+                    schema::streams::table
+                        .filter(streams_dsl::id.eq_any(stream_ids))
+                        .filter(streams_dsl::state.eq(StreamState::Waiting))
+                        .filter(streams_dsl::starttime.ge(Utc::now())),
+                )
+                .returning(Stream::as_returning())
+                .get_results(conn)?;
+
+                // Run query using Diesel to delete the entry (stream_series) and its links to the streams.
+                diesel::delete(schema::stream_series::table.filter(series_dsl::id.eq(id))).execute(conn)?;
+
+                if !deleted_streams.is_empty() {
+                    // Update the "stream_tags" data for user.
+                    self.update_stream_tags_for_user(conn, stream_series.user_id)?;
+                }
+                let deleted_ids: Vec<i32> = deleted_streams.iter().map(|stream| stream.id).collect();
+                let stream_tags = stream_tags.into_iter().filter(|v| deleted_ids.contains(&v.stream_id)).collect();
+
+                Ok(Some((stream_series, deleted_streams, stream_tags)))
+            });
+
+            if let Some(timer) = timer {
+                info!("delete_stream_series() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            res_data.map_err(|e| format!("delete_stream_series: {}", e.to_string()))
+        }
+
+        /// Create the streams of the series (all or the specified one) that have come within the horizon.
+        /// Returns the created streams with their tags.
+        #[rustfmt::skip]
+        fn materialize_stream_series(
+            &self, opt_id: Option<i32>, horizon: DateTime<Utc>,
+        ) -> Result<(Vec<Stream>, Vec<StreamTagStreamId>), String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            let mut query = schema::stream_series::table.select(series_dsl::id).into_boxed();
+            if let Some(id) = opt_id {
+                query = query.filter(series_dsl::id.eq(id));
+            }
+            // Run query using Diesel to get the series whose streams are not created up to the horizon.
+            let ids: Vec<i32> = query
+                .filter(series_dsl::materialized_until.is_null().or(series_dsl::materialized_until.lt(horizon)))
+                .order_by(series_dsl::id.asc())
+                .load(&mut conn)
+                .map_err(|e| format!("materialize_stream_series: {}", e.to_string()))?;
+
+            let mut streams: Vec<Stream> = Vec::new();
+            let mut stream_tags: Vec<StreamTagStreamId> = Vec::new();
+            for id in ids {
+                let res_data = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                    // The series that is being changed by another process is skipped until the next pass.
+                    let opt_stream_series = schema::stream_series::table
+                        .filter(series_dsl::id.eq(id))
+                        .for_update()
+                        .skip_locked()
+                        .select(StreamSeries::as_select())
+                        .first::<StreamSeries>(conn)
+                        .optional()?;
+                    let Some(stream_series) = opt_stream_series else {
+                        return Ok((Vec::new(), Vec::new()));
+                    };
+                    let streams = self.materialize(conn, &stream_series, horizon)?;
+                    let ids: Vec<i32> = streams.iter().map(|stream| stream.id).collect();
+                    // Get a list of "tags" for the specified "streams".
+                    let stream_tags = self.get_stream_tags(conn, &ids)?;
+                    Ok((streams, stream_tags))
+                });
+                let (streams2, stream_tags2) = res_data.map_err(|e| format!("materialize_stream_series: {}", e.to_string()))?;
+                streams.extend(streams2);
+                stream_tags.extend(stream_tags2);
+            }
+
+            if let Some(timer) = timer {
+                info!("materialize_stream_series() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok((streams, stream_tags))
+        }
+
+        /// Check that the time zone with the specified name is known ("Europe/Berlin").
+        fn is_time_zone_known(&self, name: &str) -> Result<bool, String> {
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            #[rustfmt::skip]
+            let query = diesel::sql_query("SELECT EXISTS (SELECT 1 FROM pg_timezone_names WHERE name = $1) AS value;")
+                .bind::<sql_types::Text, _>(name);
+
+            let is_exist = query
+                .get_result::<IsExist>(&mut conn)
+                .map_err(|e| format!("is_time_zone_known: {}", e.to_string()))?;
+
+            Ok(is_exist.value)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "mockdata"))]
+pub mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use actix_web::web;
+    use chrono::{Duration, FixedOffset, Offset, Utc};
+    use vrb_dbase::enm_stream_state::StreamState;
+
+    use crate::stream_models::{Stream, StreamTagStreamId};
+    use crate::stream_orm::tests::STREAM_ID;
+    use crate::stream_series_models::{CreateStreamSeries, LinkStreamSeriesToStreams};
+
+    use super::*;
+
+    pub const STREAM_SERIES_ID: i32 = 1900;
+    // The known time zones (without the daylight saving time) and their offsets (in seconds).
+    pub const TIME_ZONES: [(&str, i32); 3] = [("UTC", 0), ("Asia/Tokyo", 9 * 3600), ("Asia/Kolkata", 5 * 3600 + 1800)];
+
+    /// Get the time zone by its name.
+    fn get_time_zone(name: &str) -> Option<FixedOffset> {
+        let (_, offset) = TIME_ZONES.iter().find(|(zone_name, _)| *zone_name == name)?;
+        FixedOffset::east_opt(*offset)
+    }
+
+    /// The data of the series, their streams and the links between them.
+    #[derive(Debug, Clone, Default)]
+    pub struct StreamSeriesData {
+        pub stream_series_vec: Vec<StreamSeries>,
+        pub stream_vec: Vec<Stream>,
+        pub stream_tags_vec: Vec<StreamTagStreamId>,
+        pub link_vec: Vec<LinkStreamSeriesToStreams>,
+    }
+
+    impl StreamSeriesData {
+        /// Get the streams with the specified IDs and their tags.
+        fn get_streams(&self, ids: &[i32]) -> (Vec<Stream>, Vec<StreamTagStreamId>) {
+            let mut streams: Vec<Stream> = self.stream_vec.iter().filter(|v| ids.contains(&v.id)).cloned().collect();
+            streams.sort_by_key(|v| v.starttime);
+            let stream_tags = self.stream_tags_vec.iter().filter(|v| ids.contains(&v.stream_id)).cloned().collect();
+            (streams, stream_tags)
+        }
+        /// Get the IDs of the waiting streams of the series whose occurrence time is not earlier than the specified one.
+        fn get_waiting_stream_ids(&self, id: i32, from: DateTime<Utc>) -> Vec<i32> {
+            self.link_vec
+                .iter()
+                .filter(|v| v.stream_series_id == id && v.occurrence_time >= from)
+                .filter(|v| self.stream_vec.iter().any(|s| s.id == v.stream_id && s.state == StreamState::Waiting))
+                .map(|v| v.stream_id)
+                .collect()
+        }
+        /// Update the list of "tags" for the specified "stream".
+        fn set_stream_tags(&mut self, stream_id: i32, user_id: i32, tags: &[String]) {
+            self.stream_tags_vec.retain(|v| v.stream_id != stream_id);
+            for (idx, tag) in tags.iter().enumerate() {
+                let id: i32 = idx.try_into().unwrap();
+                let name = tag.to_lowercase().trim().to_string();
+                self.stream_tags_vec.push(StreamTagStreamId {
+                    stream_id,
+                    id,
+                    user_id,
+                    name,
+                });
+            }
+        }
+        /// Delete the specified streams (with their tags and links). Returns the deleted streams and their tags.
+        fn remove_streams(&mut self, ids: &[i32]) -> (Vec<Stream>, Vec<StreamTagStreamId>) {
+            let removed = self.get_streams(ids);
+            self.stream_vec.retain(|v| !ids.contains(&v.id));
+            self.stream_tags_vec.retain(|v| !ids.contains(&v.stream_id));
+            self.link_vec.retain(|v| !ids.contains(&v.stream_id));
+            removed
+        }
+        /// Create the streams of the series whose start has come within the horizon (and has not yet passed).
+        fn materialize(&mut self, idx: usize, horizon: DateTime<Utc>) -> Vec<Stream> {
+            let mut result: Vec<Stream> = Vec::new();
+            let stream_series = self.stream_series_vec[idx].clone();
+            let Ok(rule) = stream_series.rule() else {
+                return result;
+            };
+            let now = Utc::now();
+            let materialized_until = stream_series.materialized_until;
+            let mut time_zone = get_time_zone(&stream_series.time_zone).unwrap_or(Utc.fix());
+            let occurrences = rule.occurrences(stream_series.starttime, horizon, &mut time_zone).unwrap();
+            for occurrence_time in occurrences {
+                let is_new = materialized_until.map(|v| occurrence_time > v).unwrap_or(true);
+                #[rustfmt::skip]
+                let is_exist = self.link_vec.iter().any(|v| v.stream_series_id == stream_series.id && v.occurrence_time == occurrence_time);
+                if occurrence_time < now || !is_new || is_exist {
+                    continue;
+                }
+                let stream_id = self.stream_vec.iter().map(|v| v.id + 1).max().unwrap_or(STREAM_ID);
+                let mut stream = Stream::new(stream_id, stream_series.user_id, &stream_series.title, occurrence_time);
+                stream.descript = stream_series.descript.clone();
+                stream.source = stream_series.source.clone();
+                self.stream_vec.push(stream.clone());
+                self.set_stream_tags(stream_id, stream_series.user_id, &stream_series.tags);
+
+                let link_id = self.link_vec.iter().map(|v| v.id + 1).max().unwrap_or(1);
+                #[rustfmt::skip]
+                self.link_vec.push(LinkStreamSeriesToStreams { id: link_id, stream_series_id: stream_series.id, stream_id, occurrence_time });
+                result.push(stream);
+            }
+            if materialized_until.map(|v| v < horizon).unwrap_or(true) {
+                self.stream_series_vec[idx].materialized_until = Some(horizon);
+            }
+            result
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct StreamSeriesOrmApp {
+        // The data is shared, so that the created streams of the series can be checked in the tests.
+        pub data: Arc<Mutex<StreamSeriesData>>,
+    }
+
+    impl StreamSeriesOrmApp {
+        /// Create a new instance.
+        pub fn new() -> Self {
+            StreamSeriesOrmApp {
+                data: Arc::new(Mutex::new(StreamSeriesData::default())),
+            }
+        }
+        /// Create a new instance with the specified `stream_series` list (their streams are created up to the horizon).
+        pub fn create(stream_series_vec: &[StreamSeries], horizon: DateTime<Utc>) -> Self {
+            let stream_series_orm_app = Self::new();
+            {
+                let mut data = stream_series_orm_app.data.lock().unwrap();
+                for (idx, stream_series) in stream_series_vec.iter().enumerate() {
+                    let mut stream_series2 = stream_series.clone();
+                    let delta: i32 = idx.try_into().unwrap();
+                    stream_series2.id = STREAM_SERIES_ID + delta;
+                    data.stream_series_vec.push(stream_series2);
+                    data.materialize(idx, horizon);
+                }
+            }
+            stream_series_orm_app
+        }
+        /// Get a copy of the data.
+        pub fn data(&self) -> StreamSeriesData {
+            self.data.lock().unwrap().clone()
+        }
+    }
+
+    impl StreamSeriesOrm for StreamSeriesOrmApp {
+        /// Get a list of entities (stream_series) of the user.
+        fn get_stream_series(&self, user_id: i32) -> Result<Vec<StreamSeries>, String> {
+            let data = self.data.lock().unwrap();
+            Ok(data.stream_series_vec.iter().filter(|v| v.user_id == user_id).cloned().collect())
+        }
+
+        /// Find an entity (stream_series) by ID. If the user is specified, then only among the user's series.
+        fn find_stream_series_by_id(&self, id: i32, opt_user_id: Option<i32>) -> Result<Option<StreamSeries>, String> {
+            let data = self.data.lock().unwrap();
+            let opt_stream_series = data
+                .stream_series_vec
+                .iter()
+                .find(|v| v.id == id && opt_user_id.map(|user_id| v.user_id == user_id).unwrap_or(true))
+                .cloned();
+            Ok(opt_stream_series)
+        }
+
+        /// Get a list of the streams (occurrences) of the series.
+        fn get_stream_series_streams(&self, id: i32) -> Result<(Vec<Stream>, Vec<StreamTagStreamId>), String> {
+            let data = self.data.lock().unwrap();
+            let ids: Vec<i32> = data.link_vec.iter().filter(|v| v.stream_series_id == id).map(|v| v.stream_id).collect();
+            Ok(data.get_streams(&ids))
+        }
+
+        /// Add a new entity (stream_series) and create its streams up to the horizon.
+        /// Returns the series and the created streams with their tags.
+        #[rustfmt::skip]
+        fn create_stream_series(
+            &self, create_stream_series: CreateStreamSeries, horizon: DateTime<Utc>,
+        ) -> Result<(StreamSeries, Vec<Stream>, Vec<StreamTagStreamId>), String> {
+            let mut data = self.data.lock().unwrap();
+            let id = data.stream_series_vec.iter().map(|v| v.id + 1).max().unwrap_or(STREAM_SERIES_ID);
+            data.stream_series_vec.push(StreamSeries::create(create_stream_series, id));
+            let idx = data.stream_series_vec.len() - 1;
+            let ids: Vec<i32> = data.materialize(idx, horizon).iter().map(|v| v.id).collect();
+            let (streams, stream_tags) = data.get_streams(&ids);
+            Ok((data.stream_series_vec[idx].clone(), streams, stream_tags))
+        }
+
+        /// Modify the specified occurrence (stream) of the series ("this") or the series from this occurrence on ("future").
+        /// For "future", a change of the start or the rule recreates the waiting streams from this occurrence on.
+        /// Returns the changed (or recreated) streams, the deleted streams and the tags of both.
+        #[rustfmt::skip]
+        fn modify_stream_series(
+            &self, id: i32, stream_id: i32, opt_user_id: Option<i32>, scope: SeriesScope, modify_stream_series: ModifyStreamSeries,
+            horizon: DateTime<Utc>,
+        ) -> Result<Option<(Vec<Stream>, Vec<Stream>, Vec<StreamTagStreamId>)>, String> {
+            let mut data = self.data.lock().unwrap();
+            let opt_idx = data
+                .stream_series_vec
+                .iter()
+                .position(|v| v.id == id && opt_user_id.map(|user_id| v.user_id == user_id).unwrap_or(true));
+            let Some(idx) = opt_idx else {
+                return Ok(None);
+            };
+            let opt_link = data.link_vec.iter().find(|v| v.stream_series_id == id && v.stream_id == stream_id).cloned();
+            let Some(link) = opt_link else {
+                return Ok(None);
+            };
+            let modify = modify_stream_series;
+            let mut deleted_streams: Vec<Stream> = Vec::new();
+            let mut deleted_stream_tags: Vec<StreamTagStreamId> = Vec::new();
+
+            let ids: Vec<i32> = if scope == SeriesScope::This {
+                let now = Utc::now();
+                let opt_stream = data.stream_vec.iter_mut().find(|v| v.id == stream_id).map(|stream| {
+                    stream.title = modify.title.clone().unwrap_or(stream.title.clone());
+                    stream.descript = modify.descript.clone().unwrap_or(stream.descript.clone());
+                    stream.starttime = modify.starttime.unwrap_or(stream.starttime);
+                    stream.source = modify.source.clone().unwrap_or(stream.source.clone());
+                    stream.updated_at = now;
+                    stream.user_id
+                });
+                if let (Some(user_id), Some(tags)) = (opt_stream, &modify.tags) {
+                    data.set_stream_tags(stream_id, user_id, tags);
+                }
+                vec![stream_id]
+            } else {
+                let from = link.occurrence_time;
+                let stream_series = &mut data.stream_series_vec[idx];
+                stream_series.title = modify.title.clone().unwrap_or(stream_series.title.clone());
+                stream_series.descript = modify.descript.clone().unwrap_or(stream_series.descript.clone());
+                stream_series.source = modify.source.clone().unwrap_or(stream_series.source.clone());
+                stream_series.tags = modify.tags.clone().unwrap_or(stream_series.tags.clone());
+                stream_series.updated_at = Utc::now();
+                let stream_series = stream_series.clone();
+
+                if modify.is_schedule_changed() {
+                    let waiting_ids = data.get_waiting_stream_ids(id, from);
+                    (deleted_streams, deleted_stream_tags) = data.remove_streams(&waiting_ids);
+
+                    let mut rrule = modify.rrule.clone().unwrap_or(stream_series.rrule.clone());
+                    if modify.rrule.is_none() {
+                        if let Ok(mut rule) = stream_series.rule() {
+                            if let Some(count) = rule.count {
+                                let mut time_zone = get_time_zone(&stream_series.time_zone).unwrap_or(Utc.fix());
+                                let count_before = rule.count_before(stream_series.starttime, from, &mut time_zone).unwrap();
+                                rule.count = Some(count.saturating_sub(count_before).max(1));
+                                rrule = rule.to_string();
+                            }
+                        }
+                    }
+                    let stream_series = &mut data.stream_series_vec[idx];
+                    stream_series.starttime = modify.starttime.unwrap_or(from);
+                    stream_series.rrule = rrule;
+                    stream_series.time_zone = modify.time_zone.clone().unwrap_or(stream_series.time_zone.clone());
+                    stream_series.materialized_until = None;
+                    data.materialize(idx, horizon);
+                } else {
+                    for stream_id in data.get_waiting_stream_ids(id, from) {
+                        if let Some(stream) = data.stream_vec.iter_mut().find(|v| v.id == stream_id) {
+                            stream.title = stream_series.title.clone();
+                            stream.descript = stream_series.descript.clone();
+                            stream.source = stream_series.source.clone();
+                        }
+                        if modify.tags.is_some() {
+                            data.set_stream_tags(stream_id, stream_series.user_id, &stream_series.tags);
+                        }
+                    }
+                }
+                data.get_waiting_stream_ids(id, from)
+            };
+            let (streams, mut stream_tags) = data.get_streams(&ids);
+            stream_tags.extend(deleted_stream_tags);
+            Ok(Some((streams, deleted_streams, stream_tags)))
+        }
+
+        /// Delete an entity (stream_series) together with its future waiting streams.
+        /// The other streams of the series remain as ordinary streams.
+        /// Returns the series and the deleted streams with their tags.
+        #[rustfmt::skip]
+        fn delete_stream_series(
+            &self, id: i32, opt_user_id: Option<i32>,
+        ) -> Result<Option<(StreamSeries, Vec<Stream>, Vec<StreamTagStreamId>)>, String> {
+            let mut data = self.data.lock().unwrap();
+            let opt_idx = data
+                .stream_series_vec
+                .iter()
+                .position(|v| v.id == id && opt_user_id.map(|user_id| v.user_id == user_id).unwrap_or(true));
+            let Some(idx) = opt_idx else {
+                return Ok(None);
+            };
+            let now = Utc::now();
+            // Get the future streams of the series that have not yet started.
+            let ids: Vec<i32> = data
+                .stream_vec
+                .iter()
+                .filter(|v| v.state == StreamState::Waiting && v.starttime >= now)
+                .filter(|v| data.link_vec.iter().any(|link| link.stream_series_id == id && link.stream_id == v.id))
+                .map(|v| v.id)
+                .collect();
+            let (deleted_streams, stream_tags) = data.remove_streams(&ids);
+            data.link_vec.retain(|v| v.stream_series_id != id);
+            let stream_series = data.stream_series_vec.remove(idx);
+            Ok(Some((stream_series, deleted_streams, stream_tags)))
+        }
+
+        /// Create the streams of the series (all or the specified one) that have come within the horizon.
+        /// Returns the created streams with their tags.
+        #[rustfmt::skip]
+        fn materialize_stream_series(
+            &self, opt_id: Option<i32>, horizon: DateTime<Utc>,
+        ) -> Result<(Vec<Stream>, Vec<StreamTagStreamId>), String> {
+            let mut data = self.data.lock().unwrap();
+            let mut ids: Vec<i32> = Vec::new();
+            for idx in 0..data.stream_series_vec.len() {
+                if opt_id.map(|id| data.stream_series_vec[idx].id == id).unwrap_or(true) {
+                    ids.extend(data.materialize(idx, horizon).iter().map(|v| v.id));
+                }
+            }
+            Ok(data.get_streams(&ids))
+        }
+
+        /// Check that the time zone with the specified name is known ("Europe/Berlin").
+        fn is_time_zone_known(&self, name: &str) -> Result<bool, String> {
+            Ok(get_time_zone(name).is_some())
+        }
+    }
+
+    pub struct StreamSeriesOrmTest {}
+
+    impl StreamSeriesOrmTest {
+        /// Create a series with the specified rule, the first stream of which starts in an hour.
+        pub fn stream_series(user_id: i32, rrule: &str, tags: &str) -> StreamSeries {
+            let tags1: Vec<String> = tags.split(',').map(|val| val.to_string()).collect();
+            let create_stream_series = CreateStreamSeries {
+                user_id,
+                title: format!("series_{}", user_id),
+                descript: None,
+                source: None,
+                tags: tags1,
+                starttime: Utc::now() + Duration::hours(1),
+                rrule: rrule.to_string(),
+                time_zone: None,
+            };
+            StreamSeries::create(create_stream_series, STREAM_SERIES_ID)
+        }
+        pub fn cfg_stream_series_orm(data_ss: Vec<StreamSeries>, horizon: DateTime<Utc>) -> impl FnOnce(&mut web::ServiceConfig) {
+            move |config: &mut web::ServiceConfig| {
+                let data_stream_series_orm = web::Data::new(StreamSeriesOrmApp::create(&data_ss, horizon));
+                config.app_data(web::Data::clone(&data_stream_series_orm));
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::{sync::Arc, time::Duration};
+
+use actix::prelude::*;
+use actix_web::rt::task;
+use chrono::Utc;
+use log::{error, info};
+use vrb_webhooks::webhook_models::WebhookEvent;
+
+use crate::{config_strm::ConfigStrm, stream_series_controller::fire_streams_webhook, stream_series_orm::StreamSeriesOrm};
+
+pub type StreamSeriesOrmArc = Arc<dyn StreamSeriesOrm + Send + Sync>;
+
+// ** Messages of the stream series scheduler. **
+
+/// Set the storage of the series (and the settings) and start creating their streams.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct SetStreamSeriesOrm(pub StreamSeriesOrmArc, pub ConfigStrm);
+
+/// Create the streams of the series that have come within the horizon.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct MaterializeStreamSeries;
+
+// ** The scheduler of the series of streams. **
+// Periodically creates (in a separate thread) the streams of the series that have come within the horizon.
+#[derive(Default)]
+pub struct StreamSeriesScheduler {
+    stream_series_orm: Option<StreamSeriesOrmArc>,
+    series_horizon: u32, // The horizon (in days).
+    is_running: bool,    // The streams are being created.
+}
+
+impl SystemService for StreamSeriesScheduler {}
+impl Supervised for StreamSeriesScheduler {}
+
+impl Actor for StreamSeriesScheduler {
+    type Context = Context<Self>;
+}
+
+impl Handler<SetStreamSeriesOrm> for StreamSeriesScheduler {
+    type Result = ();
+
+    fn handle(&mut self, msg: SetStreamSeriesOrm, ctx: &mut Self::Context) {
+        let SetStreamSeriesOrm(stream_series_orm, config_strm) = msg;
+        let poll = Duration::from_secs(config_strm.strm_series_poll);
+        self.stream_series_orm = Some(stream_series_orm);
+        self.series_horizon = config_strm.strm_series_horizon;
+        // Periodically create the streams that have come within the horizon.
+        ctx.run_interval(poll, |_act, ctx| ctx.notify(MaterializeStreamSeries));
+        // Create the streams that came within the horizon while the server was stopped.
+        ctx.notify(MaterializeStreamSeries);
+    }
+}
+
+impl Handler<MaterializeStreamSeries> for StreamSeriesScheduler {
+    type Result = ();
+
+    fn handle(&mut self, _msg: MaterializeStreamSeries, ctx: &mut Self::Context) {
+        let Some(stream_series_orm) = self.stream_series_orm.clone() else {
+            return;
+        };
+        if self.is_running {
+            return;
+        }
+        self.is_running = true;
+        let horizon = Utc::now() + chrono::Duration::days(self.series_horizon.into());
+        let fut = task::spawn_blocking(move || stream_series_orm.materialize_stream_series(None, horizon));
+        let fut = fut::wrap_future::<_, Self>(fut).map(move |res, act, _ctx| {
+            act.is_running = false;
+            match res {
+                Ok(Ok((streams, stream_tags))) => {
+                    if !streams.is_empty() {
+                        info!("StreamSeriesScheduler: created streams: {}", streams.len());
+                    }
+                    // Notify the webhooks of the owners about the created streams.
+                    fire_streams_webhook(WebhookEvent::StreamCreated, &streams, &stream_tags);
+                }
+                Ok(Err(err)) => error!("StreamSeriesScheduler: creating streams, error: {}", err),
+                Err(err) => error!("StreamSeriesScheduler: creating streams, error: {}", err),
+            }
+        });
+        ctx.spawn(fut);
+    }
+}
+
+#[cfg(all(test, feature = "mockdata"))]
+pub mod tests {
+    use actix_web::rt::time::sleep;
+    use vrb_authent::user_orm::tests::USER1_ID;
+
+    use crate::{
+        config_strm,
+        stream_series_orm::tests::{StreamSeriesOrmApp, StreamSeriesOrmTest},
+    };
+
+    use super::*;
+
+    const WAIT: Duration = Duration::from_millis(300);
+
+    // ** MaterializeStreamSeries **
+
+    #[actix_web::test]
+    async fn test_materialize_stream_series_up_to_horizon() {
+        // The streams of the series were created only for the next two days.
+        let stream_series = StreamSeriesOrmTest::stream_series(USER1_ID, "daily", "tag1");
+        let stream_series_orm = StreamSeriesOrmApp::create(&[stream_series], Utc::now() + chrono::Duration::days(2));
+        assert_eq!(stream_series_orm.data().stream_vec.len(), 2);
+
+        let config_strm = config_strm::get_test_config();
+        let series_horizon = config_strm.strm_series_horizon;
+        let scheduler = StreamSeriesScheduler::default().start();
+        scheduler.do_send(SetStreamSeriesOrm(Arc::new(stream_series_orm.clone()), config_strm));
+        sleep(WAIT).await;
+
+        // The first stream starts in an hour, so all the days of the horizon have their streams.
+        let data = stream_series_orm.data();
+        assert_eq!(data.stream_vec.len(), usize::try_from(series_horizon).unwrap());
+        assert_eq!(data.link_vec.len(), data.stream_vec.len());
+        let materialized_until = data.stream_series_vec[0].materialized_until.unwrap();
+        assert!(materialized_until > Utc::now() + chrono::Duration::days((series_horizon - 1).into()));
+    }
+}
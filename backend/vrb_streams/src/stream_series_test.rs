@@ -0,0 +1,818 @@
+#[cfg(all(test, feature = "mockdata"))]
+mod tests {
+    use std::{sync::Arc, time};
+
+    use actix::SystemService;
+    use actix_web::{
+        self, App, body, dev,
+        http::StatusCode,
+        http::header::{CONTENT_TYPE, HeaderValue},
+        rt, test, web,
+    };
+    use chrono::{DateTime, Duration, SecondsFormat, Utc};
+    use serde_json::{self, json};
+    use vrb_authent::{
+        config_jwt,
+        user_orm::tests::{ADMIN, USER, USER1_ID, USER2_ID, UserOrmTest},
+    };
+    use vrb_chats::chat_event_ws::StreamEWS;
+    use vrb_common::{
+        api_error::{ApiError, code_to_str},
+        err,
+    };
+    use vrb_dbase::enm_stream_state::StreamState;
+    use vrb_webhooks::{
+        config_hook,
+        webhook_dispatcher::{SetWebhookOrm, WebhookDispatcher},
+        webhook_models::{Webhook, WebhookEvent, WebhookPayload},
+        webhook_orm::tests::{WEBHOOK_ID, WebhookOrmApp},
+        webhook_sender::tests::ReceiverTest,
+    };
+
+    use crate::{
+        config_strm,
+        stream_controller::{get_streams_events, get_streams_period, tests as StreamCtrlTest},
+        stream_models::{self, StreamEventPageDto, StreamInfoDto},
+        stream_orm::tests::StreamOrmTest,
+        stream_series_controller::{
+            delete_stream_series, get_stream_series, get_stream_series_streams, post_stream_series, put_stream_series_stream,
+        },
+        stream_series_models::{self, StreamSeriesDto},
+        stream_series_orm::tests::{STREAM_SERIES_ID, StreamSeriesOrmApp, StreamSeriesOrmTest},
+    };
+
+    const MSG_FAILED_DESER: &str = "Failed to deserialize response from JSON.";
+    const MSG_CASTING_TO_TYPE: &str = "invalid digit found in string";
+    const WAIT: time::Duration = time::Duration::from_millis(50);
+    const WAIT_WEBHOOK: time::Duration = time::Duration::from_millis(300);
+
+    fn horizon() -> DateTime<Utc> {
+        Utc::now() + Duration::days(config_strm::get_test_config().strm_series_horizon.into())
+    }
+    fn to_str(value: DateTime<Utc>) -> String {
+        value.to_rfc3339_opts(SecondsFormat::Millis, true)
+    }
+    // Send the specified events of the user to the local HTTP receiver of the webhook.
+    fn start_webhook_receiver(user_id: i32, events: &[WebhookEvent]) -> ReceiverTest {
+        let receiver = ReceiverTest::start(&[200]);
+        let webhook = Webhook::new(WEBHOOK_ID, user_id, &receiver.url, "secret1", events);
+        let webhook_orm = Arc::new(WebhookOrmApp::create(&[webhook]));
+        WebhookDispatcher::from_registry().do_send(SetWebhookOrm(webhook_orm, config_hook::tests::get_config()));
+        receiver
+    }
+    // Get the events and the streams received by the webhook receiver (ordered by the stream ID).
+    fn get_webhook_streams(receiver: &ReceiverTest) -> Vec<(WebhookEvent, StreamInfoDto)> {
+        let mut result: Vec<(WebhookEvent, StreamInfoDto)> = Vec::new();
+        for request in receiver.requests() {
+            let payload: WebhookPayload = serde_json::from_str(&request.body).expect(MSG_FAILED_DESER);
+            let stream_info_dto: StreamInfoDto = serde_json::from_value(payload.data).expect(MSG_FAILED_DESER);
+            result.push((payload.event, stream_info_dto));
+        }
+        result.sort_by_key(|(_, stream_info_dto)| stream_info_dto.id);
+        result
+    }
+
+    // ** get_stream_series **
+
+    #[actix_web::test]
+    async fn test_get_stream_series_only_own() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER, USER]);
+        let stream_series1 = StreamSeriesOrmTest::stream_series(USER1_ID, "weekly", "tag1");
+        let stream_series2 = StreamSeriesOrmTest::stream_series(USER2_ID, "weekly", "tag2");
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_stream_series)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamSeriesOrmTest::cfg_stream_series_orm(vec![stream_series1, stream_series2], horizon()))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri("/api/stream_series")
+            .insert_header(StreamCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let stream_series_dto_res: Vec<StreamSeriesDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(stream_series_dto_res.len(), 1);
+        assert_eq!(stream_series_dto_res[0].id, STREAM_SERIES_ID);
+        assert_eq!(stream_series_dto_res[0].user_id, USER1_ID);
+    }
+
+    // ** get_stream_series_streams **
+
+    #[actix_web::test]
+    async fn test_get_stream_series_streams_invalid_id() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let stream_series1 = StreamSeriesOrmTest::stream_series(USER1_ID, "weekly", "tag1");
+        let stream_series_id_bad = format!("{}a", STREAM_SERIES_ID);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_stream_series_streams)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamSeriesOrmTest::cfg_stream_series_orm(vec![stream_series1], horizon()))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/stream_series/{}/streams", stream_series_id_bad))
+            .insert_header(StreamCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::RANGE_NOT_SATISFIABLE); // 416
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::RANGE_NOT_SATISFIABLE));
+        #[rustfmt::skip]
+        let msg = format!("{}; `{}` - {} ({})", err::MSG_PARSING_TYPE_NOT_SUPPORTED, "id", MSG_CASTING_TO_TYPE, stream_series_id_bad);
+        assert_eq!(app_err.message, msg);
+    }
+    #[actix_web::test]
+    async fn test_get_stream_series_streams_another_user() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER, USER]);
+        let stream_series2 = StreamSeriesOrmTest::stream_series(USER2_ID, "weekly", "tag2");
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_stream_series_streams)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamSeriesOrmTest::cfg_stream_series_orm(vec![stream_series2], horizon()))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/stream_series/{}/streams", STREAM_SERIES_ID))
+            .insert_header(StreamCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT); // 204
+    }
+    #[actix_web::test]
+    async fn test_get_stream_series_streams_another_user_with_role_admin() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN, USER]);
+        let stream_series2 = StreamSeriesOrmTest::stream_series(USER2_ID, "weekly", "tag2");
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_stream_series_streams)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamSeriesOrmTest::cfg_stream_series_orm(vec![stream_series2], horizon()))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/stream_series/{}/streams", STREAM_SERIES_ID))
+            .insert_header(StreamCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let stream_info_dto_res: Vec<StreamInfoDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(stream_info_dto_res.len(), 4);
+        assert!(stream_info_dto_res.iter().all(|v| v.user_id == USER2_ID));
+    }
+    #[actix_web::test]
+    async fn test_get_stream_series_streams_valid() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        // The first stream starts in an hour, then every week within the horizon (28 days).
+        let stream_series1 = StreamSeriesOrmTest::stream_series(USER1_ID, "weekly", "tag1,tag2");
+        let starttime = stream_series1.starttime;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_stream_series_streams)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamSeriesOrmTest::cfg_stream_series_orm(vec![stream_series1.clone()], horizon()))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/stream_series/{}/streams", STREAM_SERIES_ID))
+            .insert_header(StreamCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let stream_info_dto_res: Vec<StreamInfoDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(stream_info_dto_res.len(), 4);
+        for (idx, stream_info_dto) in stream_info_dto_res.iter().enumerate() {
+            let week: i64 = idx.try_into().unwrap();
+            assert_eq!(to_str(stream_info_dto.starttime), to_str(starttime + Duration::weeks(week)));
+            assert_eq!(stream_info_dto.title, stream_series1.title);
+            assert_eq!(stream_info_dto.tags, stream_series1.tags);
+        }
+    }
+
+    // ** post_stream_series **
+
+    #[actix_web::test]
+    async fn test_post_stream_series_invalid_rrule() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_stream_series)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_config_strm(config_strm::get_test_config()))
+                .configure(StreamSeriesOrmTest::cfg_stream_series_orm(vec![], horizon()))
+        ).await;
+        let starttime = to_str(Utc::now() + Duration::days(1));
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/stream_series")
+            .insert_header(StreamCtrlTest::header_auth(&token1))
+            .set_json(json!({ "title": "title1", "starttime": starttime, "tags": ["tag1"], "rrule": "FREQ=MONTHLY" }))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err_vec: Vec<ApiError> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        #[rustfmt::skip]
+        StreamCtrlTest::check_app_err(app_err_vec, &code_to_str(StatusCode::EXPECTATION_FAILED), &[stream_series_models::MSG_RRULE_INVALID]);
+    }
+    #[actix_web::test]
+    async fn test_post_stream_series_valid() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_stream_series)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_config_strm(config_strm::get_test_config()))
+                .configure(StreamSeriesOrmTest::cfg_stream_series_orm(vec![], horizon()))
+        ).await;
+        let starttime = to_str(Utc::now() + Duration::days(1));
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/stream_series")
+            .insert_header(StreamCtrlTest::header_auth(&token1))
+            .set_json(json!({ "title": "title1", "starttime": starttime, "tags": ["tag1"], "rrule": "rrule:freq=weekly;byday=th,mo" }))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED); // 201
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let stream_series_dto_res: StreamSeriesDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(stream_series_dto_res.id, STREAM_SERIES_ID);
+        assert_eq!(stream_series_dto_res.user_id, USER1_ID);
+        assert_eq!(stream_series_dto_res.title, "title1");
+        assert_eq!(stream_series_dto_res.descript, stream_models::STREAM_DESCRIPT_DEF);
+        assert_eq!(stream_series_dto_res.source, stream_models::STREAM_SOURCE_DEF);
+        assert_eq!(to_str(stream_series_dto_res.starttime), starttime);
+        // The recurrence rule is saved in the normalized form.
+        assert_eq!(stream_series_dto_res.rrule, "FREQ=WEEKLY;BYDAY=MO,TH");
+        assert_eq!(stream_series_dto_res.time_zone, stream_series_models::TIME_ZONE_DEFAULT);
+        // The streams of the series were created up to the horizon.
+        assert!(stream_series_dto_res.materialized_until.is_some());
+    }
+    #[actix_web::test]
+    async fn test_post_stream_series_unknown_time_zone() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_stream_series)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_config_strm(config_strm::get_test_config()))
+                .configure(StreamSeriesOrmTest::cfg_stream_series_orm(vec![], horizon()))
+        ).await;
+        let starttime = to_str(Utc::now() + Duration::days(1));
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/stream_series")
+            .insert_header(StreamCtrlTest::header_auth(&token1))
+            .set_json(json!({ "title": "title1", "starttime": starttime, "tags": ["tag1"], "rrule": "daily", "timeZone": "Mars/Olympus" }))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err_vec: Vec<ApiError> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        #[rustfmt::skip]
+        StreamCtrlTest::check_app_err(app_err_vec, &code_to_str(StatusCode::EXPECTATION_FAILED), &[stream_series_models::MSG_TIME_ZONE_INVALID]);
+    }
+    #[actix_web::test]
+    async fn test_post_stream_series_time_zone() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let stream_series_orm = StreamSeriesOrmApp::new();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_stream_series)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_config_strm(config_strm::get_test_config()))
+                .app_data(web::Data::new(stream_series_orm.clone()))
+        ).await;
+        // The first stream starts at 23:00 UTC, it is 08:00 of the next day in Tokyo.
+        let starttime = (Utc::now() + Duration::days(1)).date_naive().and_hms_opt(23, 0, 0).unwrap().and_utc();
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/stream_series")
+            .insert_header(StreamCtrlTest::header_auth(&token1))
+            .set_json(json!({ "title": "title1", "starttime": to_str(starttime), "tags": ["tag1"], "rrule": "daily",
+                "timeZone": "Asia/Tokyo" }))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED); // 201
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let stream_series_dto_res: StreamSeriesDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(stream_series_dto_res.time_zone, "Asia/Tokyo");
+        // The streams of the series start at the same time of day.
+        let data = stream_series_orm.data();
+        assert!(data.stream_vec.len() > 1);
+        for (idx, stream) in data.stream_vec.iter().enumerate() {
+            let delta: i64 = idx.try_into().unwrap();
+            assert_eq!(stream.starttime, starttime + Duration::days(delta));
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_post_stream_series_fires_webhook() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let stream_series_orm = StreamSeriesOrmApp::new();
+        let receiver = start_webhook_receiver(USER1_ID, &[WebhookEvent::StreamCreated]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_stream_series)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_config_strm(config_strm::get_test_config()))
+                .app_data(web::Data::new(stream_series_orm.clone()))
+        ).await;
+        let starttime = to_str(Utc::now() + Duration::days(1));
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/stream_series")
+            .insert_header(StreamCtrlTest::header_auth(&token1))
+            .set_json(json!({ "title": "title1", "starttime": starttime, "tags": ["tag1"], "rrule": "FREQ=DAILY;COUNT=2" }))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED); // 201
+        // The deliveries are saved and sent in the background.
+        rt::time::sleep(WAIT_WEBHOOK).await;
+
+        // The webhook received the event of each created stream.
+        let stream_ids: Vec<i32> = stream_series_orm.data().stream_vec.iter().map(|v| v.id).collect();
+        let list = get_webhook_streams(&receiver);
+        assert_eq!(list.iter().map(|(_, v)| v.id).collect::<Vec<i32>>(), stream_ids);
+        for (event, stream_info_dto) in list {
+            assert_eq!(event, WebhookEvent::StreamCreated);
+            assert_eq!(stream_info_dto.title, "title1");
+            assert_eq!(stream_info_dto.tags, vec!["tag1".to_string()]);
+        }
+    }
+
+    // ** put_stream_series_stream **
+
+    #[actix_web::test]
+    async fn test_put_stream_series_stream_invalid_stream_id() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let stream_series1 = StreamSeriesOrmTest::stream_series(USER1_ID, "daily", "tag1");
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_stream_series_stream)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_config_strm(config_strm::get_test_config()))
+                .configure(StreamSeriesOrmTest::cfg_stream_series_orm(vec![stream_series1], horizon()))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/stream_series/{}/streams/{}", STREAM_SERIES_ID, "2a"))
+            .insert_header(StreamCtrlTest::header_auth(&token1))
+            .set_json(json!({ "scope": "this", "title": "title2" }))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::RANGE_NOT_SATISFIABLE); // 416
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::RANGE_NOT_SATISFIABLE));
+        #[rustfmt::skip]
+        let msg = format!("{}; `{}` - {} ({})", err::MSG_PARSING_TYPE_NOT_SUPPORTED, "stream_id", MSG_CASTING_TO_TYPE, "2a");
+        assert_eq!(app_err.message, msg);
+    }
+    #[actix_web::test]
+    async fn test_put_stream_series_stream_rrule_for_this() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let stream_series1 = StreamSeriesOrmTest::stream_series(USER1_ID, "daily", "tag1");
+        let stream_series_orm = StreamSeriesOrmApp::create(&[stream_series1], horizon());
+        let stream_id = stream_series_orm.data().stream_vec[0].id;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_stream_series_stream)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_config_strm(config_strm::get_test_config()))
+                .app_data(web::Data::new(stream_series_orm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/stream_series/{}/streams/{}", STREAM_SERIES_ID, stream_id))
+            .insert_header(StreamCtrlTest::header_auth(&token1))
+            .set_json(json!({ "scope": "this", "rrule": "weekly" }))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err_vec: Vec<ApiError> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        #[rustfmt::skip]
+        StreamCtrlTest::check_app_err(app_err_vec, &code_to_str(StatusCode::EXPECTATION_FAILED), &[stream_series_models::MSG_RRULE_ONLY_FOR_FUTURE]);
+    }
+    #[actix_web::test]
+    async fn test_put_stream_series_stream_not_in_series() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let stream_series1 = StreamSeriesOrmTest::stream_series(USER1_ID, "daily", "tag1");
+        let stream_series_orm = StreamSeriesOrmApp::create(&[stream_series1], horizon());
+        let stream_id = stream_series_orm.data().stream_vec.iter().map(|v| v.id).max().unwrap() + 1;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_stream_series_stream)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_config_strm(config_strm::get_test_config()))
+                .app_data(web::Data::new(stream_series_orm))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/stream_series/{}/streams/{}", STREAM_SERIES_ID, stream_id))
+            .insert_header(StreamCtrlTest::header_auth(&token1))
+            .set_json(json!({ "scope": "this", "title": "title2" }))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT); // 204
+    }
+    #[actix_web::test]
+    async fn test_put_stream_series_stream_this() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let stream_series1 = StreamSeriesOrmTest::stream_series(USER1_ID, "daily", "tag1");
+        let stream_series_orm = StreamSeriesOrmApp::create(&[stream_series1.clone()], horizon());
+        let stream_id = stream_series_orm.data().stream_vec[1].id;
+        let starttime = stream_series_orm.data().stream_vec[1].starttime + Duration::hours(2);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_stream_series_stream)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_config_strm(config_strm::get_test_config()))
+                .app_data(web::Data::new(stream_series_orm.clone()))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/stream_series/{}/streams/{}", STREAM_SERIES_ID, stream_id))
+            .insert_header(StreamCtrlTest::header_auth(&token1))
+            .set_json(json!({ "scope": "this", "title": "title2", "starttime": to_str(starttime), "tags": ["tag2"] }))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let stream_info_dto_res: Vec<StreamInfoDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(stream_info_dto_res.len(), 1);
+        assert_eq!(stream_info_dto_res[0].id, stream_id);
+        assert_eq!(stream_info_dto_res[0].title, "title2");
+        assert_eq!(to_str(stream_info_dto_res[0].starttime), to_str(starttime));
+        assert_eq!(stream_info_dto_res[0].tags, vec!["tag2".to_string()]);
+        // Only this stream was changed, the series and its other streams are the same.
+        let data = stream_series_orm.data();
+        assert_eq!(data.stream_series_vec[0].title, stream_series1.title);
+        let changed: Vec<i32> = data.stream_vec.iter().filter(|v| v.title != stream_series1.title).map(|v| v.id).collect();
+        assert_eq!(changed, vec![stream_id]);
+    }
+    #[actix_web::test]
+    async fn test_put_stream_series_stream_future_title() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let stream_series1 = StreamSeriesOrmTest::stream_series(USER1_ID, "weekly", "tag1");
+        let stream_series_orm = StreamSeriesOrmApp::create(&[stream_series1.clone()], horizon());
+        let stream_vec = stream_series_orm.data().stream_vec;
+        let stream_id = stream_vec[1].id;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_stream_series_stream)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_config_strm(config_strm::get_test_config()))
+                .app_data(web::Data::new(stream_series_orm.clone()))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/stream_series/{}/streams/{}", STREAM_SERIES_ID, stream_id))
+            .insert_header(StreamCtrlTest::header_auth(&token1))
+            .set_json(json!({ "scope": "future", "title": "title2" }))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let stream_info_dto_res: Vec<StreamInfoDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        // The streams keep their IDs and times, only the data is changed.
+        let ids: Vec<i32> = stream_info_dto_res.iter().map(|v| v.id).collect();
+        assert_eq!(ids, stream_vec[1..].iter().map(|v| v.id).collect::<Vec<i32>>());
+        assert!(stream_info_dto_res.iter().all(|v| v.title == "title2"));
+        // The previous stream was not changed, the series was changed.
+        let data = stream_series_orm.data();
+        assert_eq!(data.stream_vec[0].title, stream_series1.title);
+        assert_eq!(data.stream_series_vec[0].title, "title2");
+        assert_eq!(data.stream_series_vec[0].starttime, stream_series1.starttime);
+    }
+    #[actix_web::test]
+    async fn test_put_stream_series_stream_future_rrule() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        // Daily streams within the horizon (28 days).
+        let stream_series1 = StreamSeriesOrmTest::stream_series(USER1_ID, "FREQ=DAILY;COUNT=20", "tag1");
+        let stream_series_orm = StreamSeriesOrmApp::create(&[stream_series1.clone()], horizon());
+        let stream_vec = stream_series_orm.data().stream_vec;
+        assert_eq!(stream_vec.len(), 20);
+        let stream_id = stream_vec[2].id;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_stream_series_stream)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_config_strm(config_strm::get_test_config()))
+                .app_data(web::Data::new(stream_series_orm.clone()))
+        ).await;
+        // From the third stream on, the streams are held every two days.
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/stream_series/{}/streams/{}", STREAM_SERIES_ID, stream_id))
+            .insert_header(StreamCtrlTest::header_auth(&token1))
+            .set_json(json!({ "scope": "future", "rrule": "FREQ=DAILY;INTERVAL=2" }))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let stream_info_dto_res: Vec<StreamInfoDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        // The days 2, 4, ..., 26 are within the horizon.
+        assert_eq!(stream_info_dto_res.len(), 13);
+        for (idx, stream_info_dto) in stream_info_dto_res.iter().enumerate() {
+            let day: i64 = (2 + 2 * idx).try_into().unwrap();
+            assert_eq!(to_str(stream_info_dto.starttime), to_str(stream_series1.starttime + Duration::days(day)));
+        }
+        // The first two streams remain, the others were created again.
+        let data = stream_series_orm.data();
+        assert_eq!(data.stream_vec.len(), 2 + 13);
+        assert_eq!(data.stream_vec[0].id, stream_vec[0].id);
+        assert_eq!(data.stream_vec[1].id, stream_vec[1].id);
+        assert_eq!(data.stream_series_vec[0].starttime, stream_vec[2].starttime);
+        assert_eq!(data.stream_series_vec[0].rrule, "FREQ=DAILY;INTERVAL=2");
+    }
+    #[actix_web::test]
+    async fn test_put_stream_series_stream_future_starttime_keeps_count() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let stream_series1 = StreamSeriesOrmTest::stream_series(USER1_ID, "FREQ=DAILY;COUNT=5", "tag1");
+        let stream_series_orm = StreamSeriesOrmApp::create(&[stream_series1.clone()], horizon());
+        let stream_vec = stream_series_orm.data().stream_vec;
+        let stream_id = stream_vec[2].id;
+        let starttime = stream_vec[2].starttime + Duration::hours(3);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_stream_series_stream)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_config_strm(config_strm::get_test_config()))
+                .app_data(web::Data::new(stream_series_orm.clone()))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/stream_series/{}/streams/{}", STREAM_SERIES_ID, stream_id))
+            .insert_header(StreamCtrlTest::header_auth(&token1))
+            .set_json(json!({ "scope": "future", "starttime": to_str(starttime) }))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let stream_info_dto_res: Vec<StreamInfoDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        // The remaining three streams of the series are moved by three hours.
+        assert_eq!(stream_info_dto_res.len(), 3);
+        assert_eq!(to_str(stream_info_dto_res[0].starttime), to_str(starttime));
+        let data = stream_series_orm.data();
+        assert_eq!(data.stream_vec.len(), 5);
+        assert_eq!(data.stream_series_vec[0].rrule, "FREQ=DAILY;COUNT=3");
+    }
+
+    #[actix_web::test]
+    async fn test_put_stream_series_stream_this_sends_stream_event() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let stream_series1 = StreamSeriesOrmTest::stream_series(USER1_ID, "FREQ=DAILY;COUNT=3", "tag1");
+        let stream_series_orm = StreamSeriesOrmApp::create(&[stream_series1], horizon());
+        let stream_id = stream_series_orm.data().stream_vec[1].id;
+        let list = StreamCtrlTest::join_chat_client(stream_id, USER1_ID).await;
+        let receiver = start_webhook_receiver(USER1_ID, &[WebhookEvent::StreamUpdated]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_stream_series_stream)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_config_strm(config_strm::get_test_config()))
+                .app_data(web::Data::new(stream_series_orm.clone()))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/stream_series/{}/streams/{}", STREAM_SERIES_ID, stream_id))
+            .insert_header(StreamCtrlTest::header_auth(&token1))
+            .set_json(json!({ "scope": "this", "title": "title2" }))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+        rt::time::sleep(WAIT).await;
+
+        // The members of the chat room of the stream received its new data.
+        let messages = list.lock().unwrap().clone();
+        assert_eq!(messages.len(), 1);
+        let stream_ews: StreamEWS = serde_json::from_str(&messages[0]).expect(MSG_FAILED_DESER);
+        assert_eq!(stream_ews.id, stream_id);
+        assert_eq!(stream_ews.stream, StreamState::Waiting);
+        // The deliveries are saved and sent in the background.
+        rt::time::sleep(WAIT_WEBHOOK).await;
+
+        let list = get_webhook_streams(&receiver);
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].0, WebhookEvent::StreamUpdated);
+        assert_eq!(list[0].1.id, stream_id);
+        assert_eq!(list[0].1.title, "title2");
+    }
+    #[actix_web::test]
+    async fn test_put_stream_series_stream_future_rrule_fires_webhook() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let stream_series1 = StreamSeriesOrmTest::stream_series(USER1_ID, "FREQ=DAILY;COUNT=3", "tag1");
+        let stream_series_orm = StreamSeriesOrmApp::create(&[stream_series1], horizon());
+        let stream_vec = stream_series_orm.data().stream_vec;
+        let stream_id = stream_vec[1].id;
+        let receiver = start_webhook_receiver(USER1_ID, &[WebhookEvent::StreamCreated, WebhookEvent::StreamDeleted]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_stream_series_stream)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_config_strm(config_strm::get_test_config()))
+                .app_data(web::Data::new(stream_series_orm.clone()))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/stream_series/{}/streams/{}", STREAM_SERIES_ID, stream_id))
+            .insert_header(StreamCtrlTest::header_auth(&token1))
+            .set_json(json!({ "scope": "future", "rrule": "FREQ=DAILY;INTERVAL=2;COUNT=2" }))
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let stream_info_dto_res: Vec<StreamInfoDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(stream_info_dto_res.len(), 2);
+        // The deliveries are saved and sent in the background.
+        rt::time::sleep(WAIT_WEBHOOK).await;
+
+        // The waiting streams were deleted and created again according to the new schedule.
+        let list = get_webhook_streams(&receiver);
+        let deleted: Vec<&StreamInfoDto> = list.iter().filter(|(e, _)| *e == WebhookEvent::StreamDeleted).map(|(_, v)| v).collect();
+        let created: Vec<&StreamInfoDto> = list.iter().filter(|(e, _)| *e == WebhookEvent::StreamCreated).map(|(_, v)| v).collect();
+        assert_eq!(list.len(), 4);
+        assert_eq!(deleted.iter().map(|v| v.id).collect::<Vec<i32>>(), vec![stream_vec[1].id, stream_vec[2].id]);
+        assert!(deleted.iter().all(|v| v.tags == vec!["tag1".to_string()]));
+        let created_ids: Vec<i32> = stream_info_dto_res.iter().map(|v| v.id).collect();
+        assert_eq!(created.iter().map(|v| v.id).collect::<Vec<i32>>(), created_ids);
+    }
+
+    // ** delete_stream_series **
+
+    #[actix_web::test]
+    async fn test_delete_stream_series_another_user() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER, USER]);
+        let stream_series2 = StreamSeriesOrmTest::stream_series(USER2_ID, "weekly", "tag2");
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(delete_stream_series)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_config_strm(config_strm::get_test_config()))
+                .configure(StreamSeriesOrmTest::cfg_stream_series_orm(vec![stream_series2], horizon()))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::delete().uri(&format!("/api/stream_series/{}", STREAM_SERIES_ID))
+            .insert_header(StreamCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT); // 204
+    }
+    #[actix_web::test]
+    async fn test_delete_stream_series_valid() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let stream_series1 = StreamSeriesOrmTest::stream_series(USER1_ID, "weekly", "tag1");
+        let stream_series_orm = StreamSeriesOrmApp::create(&[stream_series1], horizon());
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(delete_stream_series)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_config_strm(config_strm::get_test_config()))
+                .app_data(web::Data::new(stream_series_orm.clone()))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::delete().uri(&format!("/api/stream_series/{}", STREAM_SERIES_ID))
+            .insert_header(StreamCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let stream_series_dto_res: StreamSeriesDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(stream_series_dto_res.id, STREAM_SERIES_ID);
+        // The future streams of the series were deleted.
+        let data = stream_series_orm.data();
+        assert_eq!(data.stream_series_vec.len(), 0);
+        assert_eq!(data.stream_vec.len(), 0);
+        assert_eq!(data.link_vec.len(), 0);
+    }
+    #[actix_web::test]
+    async fn test_delete_stream_series_fires_webhook() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let stream_series1 = StreamSeriesOrmTest::stream_series(USER1_ID, "FREQ=DAILY;COUNT=2", "tag1");
+        let stream_series_orm = StreamSeriesOrmApp::create(&[stream_series1], horizon());
+        let stream_ids: Vec<i32> = stream_series_orm.data().stream_vec.iter().map(|v| v.id).collect();
+        let receiver = start_webhook_receiver(USER1_ID, &[WebhookEvent::StreamDeleted]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(delete_stream_series)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_config_strm(config_strm::get_test_config()))
+                .app_data(web::Data::new(stream_series_orm.clone()))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::delete().uri(&format!("/api/stream_series/{}", STREAM_SERIES_ID))
+            .insert_header(StreamCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+        // The deliveries are saved and sent in the background.
+        rt::time::sleep(WAIT_WEBHOOK).await;
+
+        // The webhook received the event of each deleted stream.
+        let list = get_webhook_streams(&receiver);
+        assert_eq!(list.iter().map(|(_, v)| v.id).collect::<Vec<i32>>(), stream_ids);
+        for (event, stream_info_dto) in list {
+            assert_eq!(event, WebhookEvent::StreamDeleted);
+            assert_eq!(stream_info_dto.tags, vec!["tag1".to_string()]);
+        }
+    }
+
+    // ** The streams of the series in the calendar **
+
+    #[actix_web::test]
+    async fn test_stream_series_streams_in_streams_events_and_period() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let stream_series1 = StreamSeriesOrmTest::stream_series(USER1_ID, "daily", "tag1");
+        let stream_series_orm = StreamSeriesOrmApp::create(&[stream_series1.clone()], horizon());
+        let data = stream_series_orm.data();
+        // The streams of the series are ordinary streams.
+        let streams = StreamInfoDto::merge_streams_and_tags(&data.stream_vec, &data.stream_tags_vec);
+        let period: Vec<DateTime<Utc>> = streams.iter().map(|v| v.starttime).collect();
+        assert_eq!(period.len(), 28);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_streams_events).service(get_streams_period)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_stream_orm(streams))
+        ).await;
+        // The events of the day in a week.
+        let starttime = to_str(Utc::now() + Duration::days(7));
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/streams_events?starttime={}", starttime))
+            .insert_header(StreamCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: StreamEventPageDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.count, 1);
+        assert_eq!(response.list[0].title, stream_series1.title);
+        assert_eq!(to_str(response.list[0].starttime), to_str(stream_series1.starttime + Duration::days(7)));
+
+        // The days with the streams within the horizon.
+        let (start, finish) = (to_str(Utc::now()), to_str(horizon()));
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/streams_period?start={}&finish={}", start, finish))
+            .insert_header(StreamCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: Vec<DateTime<Utc>> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        let response_s: Vec<String> = response.into_iter().map(to_str).collect();
+        assert_eq!(response_s, period.into_iter().map(to_str).collect::<Vec<String>>());
+    }
+}
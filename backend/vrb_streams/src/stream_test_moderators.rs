@@ -0,0 +1,356 @@
+#[cfg(all(test, feature = "mockdata"))]
+mod tests {
+    use actix_web::{
+        self, App, body, dev,
+        http::StatusCode,
+        http::header::{CONTENT_TYPE, HeaderValue},
+        test,
+    };
+    use chrono::{SecondsFormat, Utc};
+    use serde_json;
+    use vrb_authent::{
+        config_jwt,
+        user_orm::tests::{ADMIN, USER, USER1, USER1_ID, USER2, USER2_ID, USER2_NAME, USER3_ID, USER3_NAME, UserOrmTest},
+    };
+    use vrb_common::{
+        api_error::{ApiError, code_to_str},
+        err,
+    };
+
+    use crate::{
+        stream_controller::{delete_stream_moderator, get_stream_moderators, post_stream_moderator, tests as StreamCtrlTest},
+        stream_models::{CreateStreamModeratorDto, StreamModerator, StreamModeratorDto},
+        stream_orm::tests::{STREAM_MODERATOR_ID, StreamOrmTest},
+    };
+
+    const MSG_FAILED_DESER: &str = "Failed to deserialize response from JSON.";
+    const MSG_CASTING_TO_TYPE: &str = "invalid digit found in string";
+
+    // ** get_stream_moderators **
+
+    #[actix_web::test]
+    async fn test_get_stream_moderators_invalid_id() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let streams = StreamOrmTest::streams(&[USER1]);
+        let stream_id_bad = format!("{}a", streams.get(0).unwrap().id);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_stream_moderators)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_stream_orm(streams))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/streams/{}/moderators", stream_id_bad))
+            .insert_header(StreamCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::RANGE_NOT_SATISFIABLE); // 416
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::RANGE_NOT_SATISFIABLE));
+        #[rustfmt::skip]
+        let msg = format!("{}; `{}` - {}", err::MSG_PARSING_TYPE_NOT_SUPPORTED, "id", MSG_CASTING_TO_TYPE);
+        assert!(app_err.message.starts_with(&msg));
+    }
+    #[actix_web::test]
+    async fn test_get_stream_moderators_stream_of_another_user() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let streams = StreamOrmTest::streams(&[USER2]);
+        let stream2_id = streams.get(0).unwrap().id;
+        let moderators = vec![StreamModerator::new(0, stream2_id, USER3_ID, USER3_NAME, Utc::now())];
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_stream_moderators)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_stream_moderator_orm(streams, moderators))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/streams/{}/moderators", stream2_id))
+            .insert_header(StreamCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT); // 204
+    }
+    #[actix_web::test]
+    async fn test_get_stream_moderators_own_stream() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let streams = StreamOrmTest::streams(&[USER1, USER2]);
+        let stream1_id = streams.get(0).unwrap().id;
+        let stream2_id = streams.get(1).unwrap().id;
+        #[rustfmt::skip]
+        let moderators = vec![
+            StreamModerator::new(0, stream1_id, USER2_ID, USER2_NAME, Utc::now()),
+            StreamModerator::new(0, stream2_id, USER3_ID, USER3_NAME, Utc::now()),
+            StreamModerator::new(0, stream1_id, USER3_ID, USER3_NAME, Utc::now()),
+        ];
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_stream_moderators)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_stream_moderator_orm(streams, moderators))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/streams/{}/moderators", stream1_id))
+            .insert_header(StreamCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: Vec<StreamModeratorDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.len(), 2);
+        assert_eq!(response[0].id, STREAM_MODERATOR_ID);
+        assert_eq!(response[0].stream_id, stream1_id);
+        assert_eq!(response[0].user_id, USER2_ID);
+        assert_eq!(response[0].nickname, USER2_NAME);
+        assert_eq!(response[1].id, STREAM_MODERATOR_ID + 2);
+        assert_eq!(response[1].stream_id, stream1_id);
+        assert_eq!(response[1].user_id, USER3_ID);
+        assert_eq!(response[1].nickname, USER3_NAME);
+    }
+    #[actix_web::test]
+    async fn test_get_stream_moderators_admin_stream_of_another_user() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN]);
+        let streams = StreamOrmTest::streams(&[USER2]);
+        let stream2_id = streams.get(0).unwrap().id;
+        let moderators = vec![StreamModerator::new(0, stream2_id, USER3_ID, USER3_NAME, Utc::now())];
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_stream_moderators)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_stream_moderator_orm(streams, moderators))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/streams/{}/moderators", stream2_id))
+            .insert_header(StreamCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: Vec<StreamModeratorDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.len(), 1);
+        assert_eq!(response[0].user_id, USER3_ID);
+    }
+
+    // ** post_stream_moderator **
+
+    #[actix_web::test]
+    async fn test_post_stream_moderator_stream_of_another_user() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let streams = StreamOrmTest::streams(&[USER2]);
+        let stream2_id = streams.get(0).unwrap().id;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_stream_moderator)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_stream_orm(streams))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri(&format!("/api/streams/{}/moderators", stream2_id))
+            .insert_header(StreamCtrlTest::header_auth(&token1))
+            .set_json(CreateStreamModeratorDto { user_id: USER3_ID })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT); // 204
+    }
+    #[actix_web::test]
+    async fn test_post_stream_moderator_non_existent_user() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let streams = StreamOrmTest::streams(&[USER1]);
+        let stream1_id = streams.get(0).unwrap().id;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_stream_moderator)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_stream_orm(streams))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri(&format!("/api/streams/{}/moderators", stream1_id))
+            .insert_header(StreamCtrlTest::header_auth(&token1))
+            .set_json(CreateStreamModeratorDto { user_id: USER1_ID - 1 })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT); // 204
+    }
+    #[actix_web::test]
+    async fn test_post_stream_moderator_new_moderator() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let streams = StreamOrmTest::streams(&[USER1]);
+        let stream1_id = streams.get(0).unwrap().id;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_stream_moderator)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_stream_orm(streams))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri(&format!("/api/streams/{}/moderators", stream1_id))
+            .insert_header(StreamCtrlTest::header_auth(&token1))
+            .set_json(CreateStreamModeratorDto { user_id: USER3_ID })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED); // 201
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: StreamModeratorDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.id, STREAM_MODERATOR_ID);
+        assert_eq!(response.stream_id, stream1_id);
+        assert_eq!(response.user_id, USER3_ID);
+        assert_eq!(response.nickname, USER3_NAME);
+        let now_date = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+        assert_eq!(response.created_at.to_rfc3339_opts(SecondsFormat::Secs, true), now_date);
+    }
+    #[actix_web::test]
+    async fn test_post_stream_moderator_old_moderator() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let streams = StreamOrmTest::streams(&[USER1]);
+        let stream1_id = streams.get(0).unwrap().id;
+        #[rustfmt::skip]
+        let moderators = vec![
+            StreamModerator::new(0, stream1_id, USER2_ID, USER2_NAME, Utc::now()),
+            StreamModerator::new(0, stream1_id, USER3_ID, USER3_NAME, Utc::now()),
+        ];
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_stream_moderator)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_stream_moderator_orm(streams, moderators))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri(&format!("/api/streams/{}/moderators", stream1_id))
+            .insert_header(StreamCtrlTest::header_auth(&token1))
+            .set_json(CreateStreamModeratorDto { user_id: USER2_ID })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED); // 201
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: StreamModeratorDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.id, STREAM_MODERATOR_ID);
+        assert_eq!(response.stream_id, stream1_id);
+        assert_eq!(response.user_id, USER2_ID);
+        assert_eq!(response.nickname, USER2_NAME);
+    }
+
+    // ** delete_stream_moderator **
+
+    #[actix_web::test]
+    async fn test_delete_stream_moderator_invalid_user_id() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let streams = StreamOrmTest::streams(&[USER1]);
+        let stream1_id = streams.get(0).unwrap().id;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(delete_stream_moderator)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_stream_orm(streams))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::delete().uri(&format!("/api/streams/{}/moderators/{}a", stream1_id, USER2_ID))
+            .insert_header(StreamCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::RANGE_NOT_SATISFIABLE); // 416
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::RANGE_NOT_SATISFIABLE));
+        #[rustfmt::skip]
+        let msg = format!("{}; `{}` - {}", err::MSG_PARSING_TYPE_NOT_SUPPORTED, "user_id", MSG_CASTING_TO_TYPE);
+        assert!(app_err.message.starts_with(&msg));
+    }
+    #[actix_web::test]
+    async fn test_delete_stream_moderator_not_moderator() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let streams = StreamOrmTest::streams(&[USER1]);
+        let stream1_id = streams.get(0).unwrap().id;
+        let moderators = vec![StreamModerator::new(0, stream1_id, USER2_ID, USER2_NAME, Utc::now())];
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(delete_stream_moderator)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_stream_moderator_orm(streams, moderators))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::delete().uri(&format!("/api/streams/{}/moderators/{}", stream1_id, USER3_ID))
+            .insert_header(StreamCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT); // 204
+    }
+    #[actix_web::test]
+    async fn test_delete_stream_moderator_stream_of_another_user() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let streams = StreamOrmTest::streams(&[USER2]);
+        let stream2_id = streams.get(0).unwrap().id;
+        let moderators = vec![StreamModerator::new(0, stream2_id, USER3_ID, USER3_NAME, Utc::now())];
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(delete_stream_moderator)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_stream_moderator_orm(streams, moderators))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::delete().uri(&format!("/api/streams/{}/moderators/{}", stream2_id, USER3_ID))
+            .insert_header(StreamCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT); // 204
+    }
+    #[actix_web::test]
+    async fn test_delete_stream_moderator_existent_moderator() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let streams = StreamOrmTest::streams(&[USER1]);
+        let stream1_id = streams.get(0).unwrap().id;
+        #[rustfmt::skip]
+        let moderators = vec![
+            StreamModerator::new(0, stream1_id, USER2_ID, USER2_NAME, Utc::now()),
+            StreamModerator::new(0, stream1_id, USER3_ID, USER3_NAME, Utc::now()),
+        ];
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(delete_stream_moderator)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_stream_moderator_orm(streams, moderators))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::delete().uri(&format!("/api/streams/{}/moderators/{}", stream1_id, USER3_ID))
+            .insert_header(StreamCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: StreamModeratorDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.id, STREAM_MODERATOR_ID + 1);
+        assert_eq!(response.stream_id, stream1_id);
+        assert_eq!(response.user_id, USER3_ID);
+        assert_eq!(response.nickname, USER3_NAME);
+    }
+}
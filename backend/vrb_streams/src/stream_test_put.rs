@@ -1,19 +1,24 @@
 #[cfg(all(test, feature = "mockdata"))]
 mod tests {
-    use std::{borrow::Cow, fs, path};
+    use std::{borrow::Cow, fs, path, sync::Arc, time};
 
+    use actix::SystemService;
     use actix_multipart_test::MultiPartFormDataBuilder;
     use actix_web::{
         self, App, body, dev,
         http::StatusCode,
         http::header::{CONTENT_TYPE, HeaderValue},
-        test,
+        rt, test,
     };
     use chrono::{Duration, SecondsFormat, Utc};
     use serde_json;
     use vrb_authent::{
         config_jwt,
-        user_orm::tests::{ADMIN, USER, USER1, USER1_ID, USER2, UserOrmTest},
+        user_orm::tests::{ADMIN, USER, USER1, USER1_ID, USER2, USER2_ID, UserOrmTest},
+    };
+    use vrb_chats::{
+        chat_event_ws::StreamEWS,
+        chat_ws_fan_out::{FanOutEvent, encode_packet, impls::NOTIFY_PAYLOAD_MAX, new_node_id},
     };
     use vrb_common::{
         api_error::{ApiError, code_to_str},
@@ -21,6 +26,13 @@ mod tests {
     };
     use vrb_dbase::enm_stream_state::StreamState;
     use vrb_tools::{cdis::coding, png_files};
+    use vrb_webhooks::{
+        config_hook,
+        webhook_dispatcher::{SetWebhookOrm, WebhookDispatcher},
+        webhook_models::{Webhook, WebhookEvent, WebhookPayload},
+        webhook_orm::tests::{WEBHOOK_ID, WebhookOrmApp},
+        webhook_sender::tests::ReceiverTest,
+    };
 
     use crate::{
         config_strm,
@@ -36,6 +48,8 @@ mod tests {
     const MSG_CASTING_TO_TYPE: &str = "invalid digit found in string";
     const MSG_MULTIPART_STREAM_INCOMPLETE: &str = "Multipart stream is incomplete";
     const MSG_CONTENT_TYPE_NOT_FOUND: &str = "Could not find Content-Type header";
+    const WAIT: time::Duration = time::Duration::from_millis(50);
+    const WAIT_WEBHOOK: time::Duration = time::Duration::from_millis(300);
 
     // ** put_stream **
 
@@ -1102,6 +1116,51 @@ mod tests {
         assert!(stream_dto_res.logo.is_none());
     }
 
+    #[actix_web::test]
+    async fn test_put_stream_sends_stream_event() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let streams = StreamOrmTest::streams(&[USER1]);
+        let stream = streams.get(0).unwrap().clone();
+        let title_s = format!("{}_a", stream.title.clone());
+        // A description of the maximum length, which is doubled by escaping in the event of the fan-out.
+        let descript_s = "\"".repeat(stream_models::DESCRIPT_MAX.into());
+        #[rustfmt::skip]
+        let (header, body) = MultiPartFormDataBuilder::new()
+            .with_text("title", title_s.clone())
+            .with_text("descript", descript_s.clone())
+            .build();
+        // The chat client (user2) is in the room of the stream.
+        let list = StreamCtrlTest::join_chat_client(stream.id, stream.user_id).await;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_stream)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_config_strm(config_strm::get_test_config()))
+                .configure(StreamOrmTest::cfg_stream_orm(streams))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/streams/{}", stream.id))
+            .insert_header(StreamCtrlTest::header_auth(&token1))
+            .insert_header(header).set_payload(body).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+        rt::time::sleep(WAIT).await;
+
+        let messages = list.lock().unwrap().clone();
+        assert_eq!(messages.len(), 1);
+        let stream_ews: StreamEWS = serde_json::from_str(&messages[0]).expect(MSG_FAILED_DESER);
+        assert_eq!(stream_ews.id, stream.id);
+        assert_eq!(stream_ews.stream, stream.state);
+        assert_eq!(stream_ews.live, stream.live);
+        assert_eq!(stream_ews.starttime, stream.starttime.to_rfc3339_opts(SecondsFormat::Millis, true));
+        // The event fits into the payload of the fan-out to other nodes.
+        #[rustfmt::skip]
+        let event = FanOutEvent::Msg { room_id: stream.id, msg: messages[0].clone() };
+        assert!(encode_packet(&new_node_id(), &event).len() <= NOTIFY_PAYLOAD_MAX);
+    }
+
     // ** put_toggle_state **
 
     #[actix_web::test]
@@ -1334,6 +1393,75 @@ mod tests {
         assert_eq!(*app_err.params.get("activeStream").unwrap(), json);
     }
     #[actix_web::test]
+    async fn test_put_toggle_state_admin_conflict() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN, USER]);
+        let mut streams = StreamOrmTest::streams(&[USER2, USER2]);
+        let stream1 = streams.get_mut(0).unwrap();
+        stream1.state = StreamState::Waiting;
+        let stream1_id = stream1.id;
+        let stream2 = streams.get_mut(1).unwrap();
+        stream2.state = StreamState::Started;
+        stream2.live = true;
+        let (stream2_id, stream2_title) = (stream2.id, stream2.title.clone());
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_toggle_state)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_config_strm(config_strm::get_test_config()))
+                .configure(StreamOrmTest::cfg_stream_orm(streams))
+        ).await;
+        // The administrator changes the stream of user2, who already has a live stream.
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/streams/toggle/{}", stream1_id))
+            .insert_header(StreamCtrlTest::header_auth(&token1))
+            .set_json(ToggleStreamStateDto{ state: StreamState::Preparing })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT); // 409
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::CONFLICT));
+        assert_eq!(&app_err.message, MSG_EXIST_IS_ACTIVE_STREAM);
+        #[rustfmt::skip]
+        let json = serde_json::json!({ "id": stream2_id, "title": &stream2_title });
+        assert_eq!(*app_err.params.get("activeStream").unwrap(), json);
+    }
+    #[actix_web::test]
+    async fn test_put_toggle_state_admin_live_stream_of_other_user() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN, USER]);
+        let mut streams = StreamOrmTest::streams(&[USER1, USER2]);
+        let stream1 = streams.get_mut(0).unwrap();
+        stream1.state = StreamState::Started;
+        stream1.live = true;
+        let stream2 = streams.get_mut(1).unwrap();
+        stream2.state = StreamState::Waiting;
+        let stream2_id = stream2.id;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_toggle_state)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_config_strm(config_strm::get_test_config()))
+                .configure(StreamOrmTest::cfg_stream_orm(streams))
+        ).await;
+        // The live stream of another user does not prevent the stream of user2 from becoming live.
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/streams/toggle/{}", stream2_id))
+            .insert_header(StreamCtrlTest::header_auth(&token1))
+            .set_json(ToggleStreamStateDto{ state: StreamState::Preparing })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let stream_dto_res: StreamInfoDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(stream_dto_res.id, stream2_id);
+        assert_eq!(stream_dto_res.state, StreamState::Preparing);
+        assert!(stream_dto_res.live);
+    }
+    #[actix_web::test]
     async fn test_put_toggle_state_ok() {
         let buff = [
             (StreamState::Preparing, StreamState::Started),
@@ -1341,6 +1469,9 @@ mod tests {
             (StreamState::Paused, StreamState::Started),
             (StreamState::Started, StreamState::Stopped),
             (StreamState::Paused, StreamState::Stopped),
+            (StreamState::Waiting, StreamState::Preparing),
+            (StreamState::Stopped, StreamState::Preparing),
+            (StreamState::Paused, StreamState::Waiting),
         ];
         for (old_state, new_state) in buff {
             let token1 = config_jwt::tests::get_token(USER1_ID);
@@ -1374,6 +1505,97 @@ mod tests {
             assert_eq!(stream_dto_res.user_id, stream_user_id);
             assert_eq!(stream_dto_res.state, new_state);
             assert_eq!(stream_dto_res.live, new_live);
+            // The side effects of the transition.
+            assert_eq!(stream_dto_res.started.is_some(), new_state == StreamState::Started);
+            assert_eq!(stream_dto_res.paused.is_some(), new_state == StreamState::Paused);
+            assert_eq!(stream_dto_res.stopped.is_some(), new_state == StreamState::Stopped);
         }
     }
+    #[actix_web::test]
+    async fn test_put_toggle_state_sends_stream_event() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let mut streams = StreamOrmTest::streams(&[USER1, USER2]);
+        let stream = streams.get_mut(0).unwrap();
+        stream.state = StreamState::Preparing;
+        let stream_id = stream.id;
+        let stream_user_id = stream.user_id;
+        let stream2_id = streams.get(1).unwrap().id;
+        // The chat client (user2) is in the room of the stream.
+        let list = StreamCtrlTest::join_chat_client(stream_id, stream_user_id).await;
+        // Another chat client is in the room of another stream.
+        let list2 = StreamCtrlTest::join_chat_client(stream2_id, USER2_ID).await;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_toggle_state)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_config_strm(config_strm::get_test_config()))
+                .configure(StreamOrmTest::cfg_stream_orm(streams))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/streams/toggle/{}", stream_id))
+            .insert_header(StreamCtrlTest::header_auth(&token1))
+            .set_json(ToggleStreamStateDto{ state: StreamState::Started })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let stream_dto_res: StreamInfoDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        rt::time::sleep(WAIT).await;
+
+        let messages = list.lock().unwrap().clone();
+        assert_eq!(messages.len(), 1);
+        let stream_ews: StreamEWS = serde_json::from_str(&messages[0]).expect(MSG_FAILED_DESER);
+        assert_eq!(stream_ews.id, stream_id);
+        assert_eq!(stream_ews.stream, StreamState::Started);
+        assert_eq!(stream_ews.live, true);
+        let started = stream_dto_res.started.map(|v| v.to_rfc3339_opts(SecondsFormat::Millis, true));
+        assert!(started.is_some());
+        assert_eq!(stream_ews.started, started);
+        assert_eq!(stream_ews.paused, None);
+        assert_eq!(stream_ews.stopped, None);
+        // The members of other rooms do not receive the event.
+        assert_eq!(list2.lock().unwrap().len(), 0);
+    }
+    #[actix_web::test]
+    async fn test_put_toggle_state_fires_webhook() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let mut streams = StreamOrmTest::streams(&[USER1]);
+        let stream = streams.get_mut(0).unwrap();
+        stream.state = StreamState::Preparing;
+        let stream_id = stream.id;
+        // The webhook of the owner of the stream is sent to the local HTTP receiver.
+        let receiver = ReceiverTest::start(&[200]);
+        let webhook = Webhook::new(WEBHOOK_ID, USER1_ID, &receiver.url, "secret1", &[WebhookEvent::StreamState]);
+        let webhook_orm = Arc::new(WebhookOrmApp::create(&[webhook]));
+        WebhookDispatcher::from_registry().do_send(SetWebhookOrm(webhook_orm, config_hook::tests::get_config()));
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_toggle_state)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_config_strm(config_strm::get_test_config()))
+                .configure(StreamOrmTest::cfg_stream_orm(streams))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/streams/toggle/{}", stream_id))
+            .insert_header(StreamCtrlTest::header_auth(&token1))
+            .set_json(ToggleStreamStateDto{ state: StreamState::Started })
+            .to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+        // The delivery is saved and sent in the background.
+        rt::time::sleep(WAIT_WEBHOOK).await;
+
+        let requests = receiver.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].headers.get("x-verbena-event").unwrap(), "stream.state");
+        let payload: WebhookPayload = serde_json::from_str(&requests[0].body).expect(MSG_FAILED_DESER);
+        assert_eq!(payload.event, WebhookEvent::StreamState);
+        let stream_dto: StreamInfoDto = serde_json::from_value(payload.data).expect(MSG_FAILED_DESER);
+        assert_eq!(stream_dto.id, stream_id);
+        assert_eq!(stream_dto.state, StreamState::Started);
+    }
 }
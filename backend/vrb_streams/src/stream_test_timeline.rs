@@ -0,0 +1,193 @@
+#[cfg(all(test, feature = "mockdata"))]
+mod tests {
+    use actix_web::{
+        self, App, body, dev,
+        http::StatusCode,
+        http::header::{CONTENT_TYPE, HeaderValue},
+        test,
+    };
+    use chrono::{Duration, Utc};
+    use serde_json;
+    use vrb_authent::{
+        config_jwt,
+        user_orm::tests::{ADMIN, USER, USER1, USER1_ID, USER1_NAME, USER2, USER2_ID, USER2_NAME, UserOrmTest},
+    };
+    use vrb_common::{
+        api_error::{ApiError, code_to_str},
+        err,
+    };
+    use vrb_dbase::enm_stream_state::StreamState;
+
+    use crate::{
+        stream_controller::{get_stream_timeline, tests as StreamCtrlTest},
+        stream_models::{StreamStateLog, StreamTimelineDto},
+        stream_orm::tests::{STREAM_STATE_LOG_ID, StreamOrmTest},
+    };
+
+    const MSG_FAILED_DESER: &str = "Failed to deserialize response from JSON.";
+    const MSG_CASTING_TO_TYPE: &str = "invalid digit found in string";
+
+    // Create a history of the stream states: the broadcast lasted 35 minutes, and the pauses lasted 15 minutes.
+    fn create_state_logs(stream_id: i32, user_id: i32, nickname: &str) -> Vec<StreamStateLog> {
+        let now = Utc::now();
+        let minutes = |value: i64| now - Duration::minutes(value);
+        #[rustfmt::skip]
+        let state_logs = vec![
+            StreamStateLog::new(0, stream_id, user_id, nickname, StreamState::Waiting, StreamState::Preparing, minutes(60)),
+            StreamStateLog::new(0, stream_id, user_id, nickname, StreamState::Preparing, StreamState::Started, minutes(55)),
+            StreamStateLog::new(0, stream_id, user_id, nickname, StreamState::Started, StreamState::Paused, minutes(45)),
+            StreamStateLog::new(0, stream_id, user_id, nickname, StreamState::Paused, StreamState::Started, minutes(40)),
+            StreamStateLog::new(0, stream_id, user_id, nickname, StreamState::Started, StreamState::Paused, minutes(30)),
+            StreamStateLog::new(0, stream_id, user_id, nickname, StreamState::Paused, StreamState::Started, minutes(20)),
+            StreamStateLog::new(0, stream_id, user_id, nickname, StreamState::Started, StreamState::Stopped, minutes(5)),
+        ];
+        state_logs
+    }
+
+    // ** get_stream_timeline **
+
+    #[actix_web::test]
+    async fn test_get_stream_timeline_invalid_id() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let streams = StreamOrmTest::streams(&[USER1]);
+        let stream_id_bad = format!("{}a", streams.get(0).unwrap().id);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_stream_timeline)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_stream_orm(streams))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/streams/{}/timeline", stream_id_bad))
+            .insert_header(StreamCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::RANGE_NOT_SATISFIABLE); // 416
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::RANGE_NOT_SATISFIABLE));
+        #[rustfmt::skip]
+        let msg = format!("{}; `{}` - {}", err::MSG_PARSING_TYPE_NOT_SUPPORTED, "id", MSG_CASTING_TO_TYPE);
+        assert!(app_err.message.starts_with(&msg));
+    }
+    #[actix_web::test]
+    async fn test_get_stream_timeline_stream_of_another_user() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let streams = StreamOrmTest::streams(&[USER2]);
+        let stream2_id = streams.get(0).unwrap().id;
+        let state_logs = create_state_logs(stream2_id, USER2_ID, USER2_NAME);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_stream_timeline)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_stream_state_log_orm(streams, state_logs))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/streams/{}/timeline", stream2_id))
+            .insert_header(StreamCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT); // 204
+    }
+    #[actix_web::test]
+    async fn test_get_stream_timeline_without_state_log() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let streams = StreamOrmTest::streams(&[USER1]);
+        let stream1_id = streams.get(0).unwrap().id;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_stream_timeline)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_stream_state_log_orm(streams, vec![]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/streams/{}/timeline", stream1_id))
+            .insert_header(StreamCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: StreamTimelineDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.stream_id, stream1_id);
+        assert_eq!(response.state, StreamState::Waiting);
+        assert_eq!(response.live_duration, 0);
+        assert_eq!(response.paused_duration, 0);
+        assert_eq!(response.timeline.len(), 0);
+    }
+    #[actix_web::test]
+    async fn test_get_stream_timeline_own_stream() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let mut streams = StreamOrmTest::streams(&[USER1, USER2]);
+        streams[0].state = StreamState::Stopped;
+        let stream1_id = streams.get(0).unwrap().id;
+        let stream2_id = streams.get(1).unwrap().id;
+        let mut state_logs = create_state_logs(stream1_id, USER1_ID, USER1_NAME);
+        state_logs.extend(create_state_logs(stream2_id, USER2_ID, USER2_NAME));
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_stream_timeline)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_stream_state_log_orm(streams, state_logs))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/streams/{}/timeline", stream1_id))
+            .insert_header(StreamCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: StreamTimelineDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.stream_id, stream1_id);
+        assert_eq!(response.state, StreamState::Stopped);
+        assert_eq!(response.live_duration, 35 * 60);
+        assert_eq!(response.paused_duration, 15 * 60);
+        assert_eq!(response.timeline.len(), 7);
+        assert_eq!(response.timeline[0].id, STREAM_STATE_LOG_ID);
+        assert_eq!(response.timeline[0].user_id, Some(USER1_ID));
+        assert_eq!(response.timeline[0].nickname, Some(USER1_NAME.to_string()));
+        assert_eq!(response.timeline[0].old_state, StreamState::Waiting);
+        assert_eq!(response.timeline[0].new_state, StreamState::Preparing);
+        assert_eq!(response.timeline[6].old_state, StreamState::Started);
+        assert_eq!(response.timeline[6].new_state, StreamState::Stopped);
+    }
+    #[actix_web::test]
+    async fn test_get_stream_timeline_admin_stream_of_another_user() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN]);
+        let mut streams = StreamOrmTest::streams(&[USER2]);
+        streams[0].state = StreamState::Stopped;
+        let stream2_id = streams.get(0).unwrap().id;
+        let state_logs = create_state_logs(stream2_id, USER2_ID, USER2_NAME);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_stream_timeline)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(StreamOrmTest::cfg_stream_state_log_orm(streams, state_logs))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/streams/{}/timeline", stream2_id))
+            .insert_header(StreamCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let response: StreamTimelineDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(response.stream_id, stream2_id);
+        assert_eq!(response.live_duration, 35 * 60);
+        assert_eq!(response.paused_duration, 15 * 60);
+        assert_eq!(response.timeline.len(), 7);
+        assert_eq!(response.timeline[0].user_id, Some(USER2_ID));
+    }
+}
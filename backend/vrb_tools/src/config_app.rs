@@ -13,6 +13,12 @@ pub const CERTIFICATE: &str = "ssl.crt.pem";
 pub const PRIVATE_KEY: &str = "ssl.key.pem";
 pub const ALLOWED_ORIGIN: &str = "http://localhost:4250,http://127.0.0.1:4250";
 pub const DIR_TMP: &str = "./tmp";
+pub const ATTEMPT_MAX: &str = "5";
+pub const ATTEMPT_WINDOW: &str = "900";
+pub const ATTEMPT_LOCKOUT: &str = "60";
+pub const ATTEMPT_LOCKOUT_MAX: &str = "3600";
+pub const NICKNAME_COOLDOWN: &str = "0";
+pub const NICKNAME_RESERVE: &str = "2592000";
 
 #[derive(Debug, Clone)]
 pub struct ConfigApp {
@@ -29,6 +35,12 @@ pub struct ConfigApp {
     pub app_allowed_origin: String,
     pub app_dir_tmp: String,
     pub app_num_workers: Option<usize>,
+    pub app_attempt_max: u32,
+    pub app_attempt_window: i64,
+    pub app_attempt_lockout: i64,
+    pub app_attempt_lockout_max: i64,
+    pub app_nickname_cooldown: i64,
+    pub app_nickname_reserve: i64,
 }
 
 impl ConfigApp {
@@ -72,6 +84,20 @@ impl ConfigApp {
         #[rustfmt::skip]
         let app_num_workers = if num_workers.len() > 0 { Some(num_workers.parse::<usize>().unwrap()) } else { None };
 
+        // Number of failed attempts (login, recovery, registration) before blocking. The value 0 disables the check.
+        let app_attempt_max = env::var("APP_ATTEMPT_MAX").unwrap_or(ATTEMPT_MAX.to_string());
+        // Period (in seconds) during which failed attempts are counted.
+        let app_attempt_window = env::var("APP_ATTEMPT_WINDOW").unwrap_or(ATTEMPT_WINDOW.to_string());
+        // Initial lock duration (in seconds). Each subsequent failed attempt doubles the duration.
+        let app_attempt_lockout = env::var("APP_ATTEMPT_LOCKOUT").unwrap_or(ATTEMPT_LOCKOUT.to_string());
+        // Maximum lock duration (in seconds).
+        let app_attempt_lockout_max = env::var("APP_ATTEMPT_LOCKOUT_MAX").unwrap_or(ATTEMPT_LOCKOUT_MAX.to_string());
+
+        // Minimum period (in seconds) between changes of the user's nickname. The value 0 disables the check.
+        let app_nickname_cooldown = env::var("APP_NICKNAME_COOLDOWN").unwrap_or(NICKNAME_COOLDOWN.to_string());
+        // Period (in seconds) during which the old nickname remains reserved for its owner after the change.
+        let app_nickname_reserve = env::var("APP_NICKNAME_RESERVE").unwrap_or(NICKNAME_RESERVE.to_string());
+
         ConfigApp {
             app_host,
             app_protocol,
@@ -86,6 +112,12 @@ impl ConfigApp {
             app_allowed_origin,
             app_dir_tmp,
             app_num_workers,
+            app_attempt_max: app_attempt_max.parse::<u32>().unwrap(),
+            app_attempt_window: app_attempt_window.parse::<i64>().unwrap(),
+            app_attempt_lockout: app_attempt_lockout.parse::<i64>().unwrap(),
+            app_attempt_lockout_max: app_attempt_lockout_max.parse::<i64>().unwrap(),
+            app_nickname_cooldown: app_nickname_cooldown.parse::<i64>().unwrap(),
+            app_nickname_reserve: app_nickname_reserve.parse::<i64>().unwrap(),
         }
     }
     fn get_domain(protocol: &str, host: &str, port: &str) -> String {
@@ -113,5 +145,11 @@ pub fn get_test_config() -> ConfigApp {
         app_allowed_origin: "".to_string(),
         app_dir_tmp: "./".to_string(),
         app_num_workers: None,
+        app_attempt_max: 3,
+        app_attempt_window: 600,
+        app_attempt_lockout: 30,
+        app_attempt_lockout_max: 300,
+        app_nickname_cooldown: 3600,
+        app_nickname_reserve: 86400,
     }
 }
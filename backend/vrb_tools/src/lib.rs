@@ -8,3 +8,4 @@ pub mod ssl_acceptor;
 pub mod template_rendering;
 pub mod token_coding;
 pub mod token_data;
+pub mod totp_tools;
@@ -21,6 +21,27 @@ pub trait Mailer {
         target: &str,
         recovery_duration: i64,
     ) -> Result<(), String>;
+    /// Send an email to confirm the new email address.
+    fn send_email_change_code(
+        &self,
+        path_template: &str,
+        receiver: &str,
+        domain: &str,
+        subject: &str,
+        nickname: &str,
+        target: &str,
+        email_change_duration: i64,
+    ) -> Result<(), String>;
+    /// Send a notice to the old email address that the email has been changed.
+    fn send_email_change_notice(
+        &self,
+        path_template: &str,
+        receiver: &str,
+        domain: &str,
+        subject: &str,
+        nickname: &str,
+        new_email: &str,
+    ) -> Result<(), String>;
 }
 
 /* use vrb_tools::send_email::config_smtp;
@@ -196,6 +217,97 @@ pub mod impls {
             // Sending mail (synchronous)
             self.sending(message)
         }
+        /// Send an email to confirm the new email address.
+        fn send_email_change_code(
+            &self,
+            path_template: &str,
+            receiver: &str,
+            domain: &str,
+            subject: &str,
+            nickname: &str,
+            target: &str,
+            email_change_duration: i64,
+        ) -> Result<(), String> {
+            if path_template.len() == 0 {
+                return Err("Path_template not specified.".to_string());
+            }
+            if receiver.len() == 0 {
+                return Err("Recipient not specified.".to_string());
+            }
+            let mut params: HashMap<&str, &str> = HashMap::new();
+            params.insert("subject", subject);
+            params.insert("domain", domain);
+            params.insert("nickname", nickname);
+            params.insert("target", target);
+            let email_change_duration_val = email_change_duration.to_string();
+            params.insert("email_change_duration", &email_change_duration_val);
+
+            let path_email_change_code: PathBuf = [path_template, "email_change_code.hbs"].iter().collect();
+            let path_basic_layout: PathBuf = [path_template, "basic_layout.hbs"].iter().collect();
+
+            let tpl_vec = [
+                ("email_change_code", path_email_change_code.as_path()),
+                ("base", path_basic_layout.as_path()),
+            ];
+            // Create a html_template to send.
+            let html_template = template_rendering::render_template(&tpl_vec, params)?;
+
+            if self.config_smtp.smtp_save_letter {
+                let path = "res_email_change.html";
+                let res_file = File::create(path);
+                if let Ok(mut file) = res_file {
+                    let _ = write!(file, "{}", &html_template);
+                }
+            }
+            // Create a message to send.
+            let message = self.new_message(receiver, subject, &html_template)?;
+            // Sending mail (synchronous)
+            self.sending(message)
+        }
+        /// Send a notice to the old email address that the email has been changed.
+        fn send_email_change_notice(
+            &self,
+            path_template: &str,
+            receiver: &str,
+            domain: &str,
+            subject: &str,
+            nickname: &str,
+            new_email: &str,
+        ) -> Result<(), String> {
+            if path_template.len() == 0 {
+                return Err("Path_template not specified.".to_string());
+            }
+            if receiver.len() == 0 {
+                return Err("Recipient not specified.".to_string());
+            }
+            let mut params: HashMap<&str, &str> = HashMap::new();
+            params.insert("subject", subject);
+            params.insert("domain", domain);
+            params.insert("nickname", nickname);
+            params.insert("new_email", new_email);
+
+            let path_email_change_notice: PathBuf = [path_template, "email_change_notice.hbs"].iter().collect();
+            let path_basic_layout: PathBuf = [path_template, "basic_layout.hbs"].iter().collect();
+
+            let tpl_vec = [
+                ("email_change_notice", path_email_change_notice.as_path()),
+                ("base", path_basic_layout.as_path()),
+            ];
+            // Create a html_template to send.
+            let html_template = template_rendering::render_template(&tpl_vec, params)?;
+
+            if self.config_smtp.smtp_save_letter {
+                let path = "res_email_change_notice.html";
+                let res_file = File::create(path);
+                if let Ok(mut file) = res_file {
+                    let _ = write!(file, "{}", &html_template);
+                }
+            }
+            // Create a message to send.
+            let message = self.new_message(receiver, subject, &html_template)?;
+            // Sending mail (synchronous)
+            self.sending(message)
+        }
     }
 }
 
@@ -333,5 +445,96 @@ pub mod tests {
             */
             Ok(())
         }
+        /// Send an email to confirm the new email address.
+        fn send_email_change_code(
+            &self,
+            path_template: &str,
+            receiver: &str,
+            domain: &str,
+            subject: &str,
+            nickname: &str,
+            target: &str,
+            email_change_duration: i64,
+        ) -> Result<(), String> {
+            if path_template.len() == 0 {
+                return Err("Path_template not specified.".to_string());
+            }
+            if receiver.len() == 0 {
+                return Err("Recipient not specified.".to_string());
+            }
+            if domain.len() == 0 || subject.len() == 0 || nickname.len() == 0 || target.len() == 0 || email_change_duration == -999 {
+                return Err("Recipient params: domain, nickname, target.".to_string());
+            }
+            let mut params: HashMap<&str, &str> = HashMap::new();
+            params.insert("subject", subject);
+            params.insert("domain", domain);
+            params.insert("nickname", nickname);
+            params.insert("target", target);
+            let email_change_duration_val = email_change_duration.to_string();
+            params.insert("email_change_duration", &email_change_duration_val);
+
+            let path_email_change_code: PathBuf = [path_template, "email_change_code.hbs"].iter().collect();
+            let path_basic_layout: PathBuf = [path_template, "basic_layout.hbs"].iter().collect();
+
+            let tpl_vec = [
+                ("email_change_code", path_email_change_code.as_path()),
+                ("base", path_basic_layout.as_path()),
+            ];
+            // Create a html_template to send.
+            let html_template = template_rendering::render_template(&tpl_vec, params)?;
+
+            if self.save_file && self.config_smtp.smtp_save_letter {
+                let path = "res_email_change_test.html";
+                let res_file = File::create(path);
+                if let Ok(mut file) = res_file {
+                    let _ = write!(file, "{}", &html_template);
+                }
+            }
+            Ok(())
+        }
+        /// Send a notice to the old email address that the email has been changed.
+        fn send_email_change_notice(
+            &self,
+            path_template: &str,
+            receiver: &str,
+            domain: &str,
+            subject: &str,
+            nickname: &str,
+            new_email: &str,
+        ) -> Result<(), String> {
+            if path_template.len() == 0 {
+                return Err("Path_template not specified.".to_string());
+            }
+            if receiver.len() == 0 {
+                return Err("Recipient not specified.".to_string());
+            }
+            if domain.len() == 0 || subject.len() == 0 || nickname.len() == 0 || new_email.len() == 0 {
+                return Err("Recipient params: domain, nickname, new_email.".to_string());
+            }
+            let mut params: HashMap<&str, &str> = HashMap::new();
+            params.insert("subject", subject);
+            params.insert("domain", domain);
+            params.insert("nickname", nickname);
+            params.insert("new_email", new_email);
+
+            let path_email_change_notice: PathBuf = [path_template, "email_change_notice.hbs"].iter().collect();
+            let path_basic_layout: PathBuf = [path_template, "basic_layout.hbs"].iter().collect();
+
+            let tpl_vec = [
+                ("email_change_notice", path_email_change_notice.as_path()),
+                ("base", path_basic_layout.as_path()),
+            ];
+            // Create a html_template to send.
+            let html_template = template_rendering::render_template(&tpl_vec, params)?;
+
+            if self.save_file && self.config_smtp.smtp_save_letter {
+                let path = "res_email_change_notice_test.html";
+                let res_file = File::create(path);
+                if let Ok(mut file) = res_file {
+                    let _ = write!(file, "{}", &html_template);
+                }
+            }
+            Ok(())
+        }
     }
 }
@@ -12,6 +12,13 @@ pub const CD_NUM_TOKEN_MIN: usize = 1;
 pub const CD_NUM_TOKEN_MAX: usize = 10000;
 // User_ID from the header does not match the user_ID from the parameters
 pub const CD_UNALLOWABLE_TOKEN: &str = "UnallowableToken";
+// The kind of token does not match the expected one (e.g. a refresh token instead of an access token).
+pub const CD_UNALLOWABLE_TOKEN_KIND: &str = "UnallowableTokenKind";
+
+pub const TOKEN_KIND_ACCESS: &str = "access";
+pub const TOKEN_KIND_REFRESH: &str = "refresh";
+pub const TOKEN_KIND_CHALLENGE: &str = "challenge";
+pub const TOKEN_KIND_EMAIL_CHANGE: &str = "email_change";
 
 #[derive(Debug, Serialize, Deserialize)]
 struct TokenClaims {
@@ -19,15 +26,62 @@ struct TokenClaims {
     pub iat: usize,
     pub iss: String,
     pub sub: String,
+    // Token kind: "access" (default), "refresh", "challenge" or "email_change".
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub typ: String,
+    // Session (token family) ID. Used only in the refresh token.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub sid: String,
 }
 
-/// Pack two parameters into a token.
+/// Pack two parameters into a token (access token).
 pub fn encode_token(
     user_id: i32,
     num_token: i32,
     secret: &[u8],
     // expires in seconds
     expires: i64,
+) -> Result<String, String> {
+    encode_claims(user_id, num_token, TOKEN_KIND_ACCESS, None, secret, expires)
+}
+
+/// Pack three parameters into a one-time refresh token.
+pub fn encode_refresh_token(
+    user_id: i32,
+    num_token: i32,
+    session_id: i32,
+    secret: &[u8],
+    // expires in seconds
+    expires: i64,
+) -> Result<String, String> {
+    encode_claims(user_id, num_token, TOKEN_KIND_REFRESH, Some(session_id), secret, expires)
+}
+
+/// Pack two parameters into a challenge token (the second step of login, confirmation with a one-time code).
+pub fn encode_challenge_token(
+    user_id: i32,
+    num_token: i32,
+    secret: &[u8],
+    // expires in seconds
+    expires: i64,
+) -> Result<String, String> {
+    encode_claims(user_id, num_token, TOKEN_KIND_CHALLENGE, None, secret, expires)
+}
+
+/// Pack two parameters into an email change token (confirmation of the new email address).
+pub fn encode_email_change_token(
+    user_email_change_id: i32,
+    num_token: i32,
+    secret: &[u8],
+    // expires in seconds
+    expires: i64,
+) -> Result<String, String> {
+    encode_claims(user_email_change_id, num_token, TOKEN_KIND_EMAIL_CHANGE, None, secret, expires)
+}
+
+#[rustfmt::skip]
+fn encode_claims(
+    user_id: i32, num_token: i32, typ: &str, opt_session_id: Option<i32>, secret: &[u8], expires: i64,
 ) -> Result<String, String> {
     if num_token == 0 {
         let err = errors::Error::from(errors::ErrorKind::InvalidSubject).to_string();
@@ -45,8 +99,17 @@ pub fn encode_token(
     let exp = (now + Duration::seconds(expires)).timestamp() as usize;
     let iss = num_token.to_string();
     let sub = user_id.to_string();
-
-    let claims = TokenClaims { exp, iat, iss, sub };
+    let typ = typ.to_string();
+    let sid = opt_session_id.map(|v| v.to_string()).unwrap_or_default();
+
+    let claims = TokenClaims {
+        exp,
+        iat,
+        iss,
+        sub,
+        typ,
+        sid,
+    };
     // Encode the header and claims given and sign the payload using the algorithm from the header and the key.
     #[rustfmt::skip]
     let encoded = jwt::encode(
@@ -63,8 +126,57 @@ pub fn encode_token(
     Ok(encrypted)
 }
 
-/// Unpack two parameters from the token.
+/// Unpack two parameters from the token (access token).
 pub fn decode_token<T: Into<String>>(token: T, secret: &[u8]) -> Result<(i32, i32), String> {
+    let claims = decode_claims(token, secret)?;
+    // A refresh token (or a challenge token, an email change token) cannot be used as an access token.
+    if !claims.typ.is_empty() && claims.typ != TOKEN_KIND_ACCESS {
+        error!("{}: typ: {}", CD_UNALLOWABLE_TOKEN_KIND, claims.typ);
+        return Err(CD_UNALLOWABLE_TOKEN_KIND.to_string());
+    }
+    parse_claims(&claims)
+}
+
+/// Unpack three parameters (user_id, num_token, session_id) from the refresh token.
+pub fn decode_refresh_token<T: Into<String>>(token: T, secret: &[u8]) -> Result<(i32, i32, i32), String> {
+    let claims = decode_claims(token, secret)?;
+    // An access token cannot be used as a refresh token.
+    if claims.typ != TOKEN_KIND_REFRESH {
+        error!("{}: typ: {}", CD_UNALLOWABLE_TOKEN_KIND, claims.typ);
+        return Err(CD_UNALLOWABLE_TOKEN_KIND.to_string());
+    }
+    let (user_id, num_token) = parse_claims(&claims)?;
+
+    let session_id = parser::parse_i32(&claims.sid).map_err(|err| {
+        #[rustfmt::skip]
+        error!("{}: session_id: {} - {}", CD_UNALLOWABLE_TOKEN, claims.sid, err);
+        CD_UNALLOWABLE_TOKEN
+    })?;
+
+    Ok((user_id, num_token, session_id))
+}
+
+/// Unpack two parameters (user_id, num_token) from the challenge token.
+pub fn decode_challenge_token<T: Into<String>>(token: T, secret: &[u8]) -> Result<(i32, i32), String> {
+    let claims = decode_claims(token, secret)?;
+    if claims.typ != TOKEN_KIND_CHALLENGE {
+        error!("{}: typ: {}", CD_UNALLOWABLE_TOKEN_KIND, claims.typ);
+        return Err(CD_UNALLOWABLE_TOKEN_KIND.to_string());
+    }
+    parse_claims(&claims)
+}
+
+/// Unpack two parameters (user_email_change_id, num_token) from the email change token.
+pub fn decode_email_change_token<T: Into<String>>(token: T, secret: &[u8]) -> Result<(i32, i32), String> {
+    let claims = decode_claims(token, secret)?;
+    if claims.typ != TOKEN_KIND_EMAIL_CHANGE {
+        error!("{}: typ: {}", CD_UNALLOWABLE_TOKEN_KIND, claims.typ);
+        return Err(CD_UNALLOWABLE_TOKEN_KIND.to_string());
+    }
+    parse_claims(&claims)
+}
+
+fn decode_claims<T: Into<String>>(token: T, secret: &[u8]) -> Result<TokenClaims, String> {
     if secret.len() == 0 {
         let err = errors::Error::from(errors::ErrorKind::InvalidKeyFormat).to_string();
         error!("{:?}", err);
@@ -98,8 +210,12 @@ pub fn decode_token<T: Into<String>>(token: T, secret: &[u8]) -> Result<(i32, i3
         err.to_string()
     })?;
 
-    let user_id_str = token_data.claims.sub.as_str();
-    let num_token_str = token_data.claims.iss.as_str();
+    Ok(token_data.claims)
+}
+
+fn parse_claims(claims: &TokenClaims) -> Result<(i32, i32), String> {
+    let user_id_str = claims.sub.as_str();
+    let num_token_str = claims.iss.as_str();
 
     let user_id = parser::parse_i32(user_id_str).map_err(|err| {
         #[rustfmt::skip]
@@ -214,4 +330,95 @@ mod tests {
         assert_eq!(res_user_id, user_id);
         assert_eq!(res_num_token, num_token);
     }
+
+    // ** encode_refresh_token, decode_refresh_token **
+
+    #[test]
+    fn test_decode_refresh_token_as_access_token() {
+        let secret = b"super-secret-key";
+        let token = encode_refresh_token(123, 567, 89, secret, EXPIRES).unwrap();
+
+        let result = decode_token(&token, secret);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), CD_UNALLOWABLE_TOKEN_KIND);
+    }
+    #[test]
+    fn test_decode_access_token_as_refresh_token() {
+        let secret = b"super-secret-key";
+        let token = encode_token(123, 567, secret, EXPIRES).unwrap();
+
+        let result = decode_refresh_token(&token, secret);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), CD_UNALLOWABLE_TOKEN_KIND);
+    }
+    #[test]
+    fn test_encode_and_decoded_valid_refresh_token() {
+        let (user_id, num_token, session_id) = (123, 567, 89);
+        let secret = b"super-secret-key";
+
+        let token = encode_refresh_token(user_id, num_token, session_id, secret, EXPIRES).unwrap();
+        let result = decode_refresh_token(&token, secret).unwrap();
+
+        assert_eq!(result, (user_id, num_token, session_id));
+    }
+
+    // ** encode_challenge_token, decode_challenge_token **
+
+    #[test]
+    fn test_decode_challenge_token_as_access_token() {
+        let secret = b"super-secret-key";
+        let token = encode_challenge_token(123, 567, secret, EXPIRES).unwrap();
+
+        let result = decode_token(&token, secret);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), CD_UNALLOWABLE_TOKEN_KIND);
+    }
+    #[test]
+    fn test_encode_and_decoded_valid_challenge_token() {
+        let (user_id, num_token) = (123, 567);
+        let secret = b"super-secret-key";
+
+        let token = encode_challenge_token(user_id, num_token, secret, EXPIRES).unwrap();
+        assert_eq!(decode_refresh_token(&token, secret).unwrap_err(), CD_UNALLOWABLE_TOKEN_KIND);
+        let result = decode_challenge_token(&token, secret).unwrap();
+
+        assert_eq!(result, (user_id, num_token));
+    }
+
+    // ** encode_email_change_token, decode_email_change_token **
+
+    #[test]
+    fn test_decode_email_change_token_as_access_token() {
+        let secret = b"super-secret-key";
+        let token = encode_email_change_token(123, 567, secret, EXPIRES).unwrap();
+
+        let result = decode_token(&token, secret);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), CD_UNALLOWABLE_TOKEN_KIND);
+    }
+    #[test]
+    fn test_decode_access_token_as_email_change_token() {
+        let secret = b"super-secret-key";
+        let token = encode_token(123, 567, secret, EXPIRES).unwrap();
+
+        let result = decode_email_change_token(&token, secret);
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), CD_UNALLOWABLE_TOKEN_KIND);
+    }
+    #[test]
+    fn test_encode_and_decoded_valid_email_change_token() {
+        let (user_email_change_id, num_token) = (123, 567);
+        let secret = b"super-secret-key";
+
+        let token = encode_email_change_token(user_email_change_id, num_token, secret, EXPIRES).unwrap();
+        assert_eq!(decode_challenge_token(&token, secret).unwrap_err(), CD_UNALLOWABLE_TOKEN_KIND);
+        let result = decode_email_change_token(&token, secret).unwrap();
+
+        assert_eq!(result, (user_email_change_id, num_token));
+    }
 }
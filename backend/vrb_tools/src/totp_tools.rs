@@ -0,0 +1,237 @@
+use std::io::Cursor;
+
+use image::{GrayImage, ImageFormat, Luma};
+use openssl::{hash::MessageDigest, pkey::PKey, sign::Signer};
+use qrcode::{Color, QrCode};
+use rand::Rng;
+
+// Time-based one-time password (TOTP) according to RFC 6238 (HMAC-SHA1, 6 digits, 30 seconds).
+pub const TOTP_DIGITS: u32 = 6;
+pub const TOTP_PERIOD: i64 = 30;
+// The allowed deviation of the client's clock (in periods).
+pub const TOTP_SKEW: i64 = 1;
+// The length of the secret key (in bytes).
+pub const SECRET_LENGTH: usize = 20;
+// The number and length of recovery codes.
+pub const RECOVERY_CODE_COUNT: usize = 8;
+pub const RECOVERY_CODE_LENGTH: usize = 10;
+// The size of one module (point) of the QR code (in pixels) and the width of the empty border (in modules).
+pub const QR_MODULE_SIZE: u32 = 6;
+pub const QR_QUIET_ZONE: u32 = 4;
+
+pub const ERR_INVALID_SECRET: &str = "Invalid secret key (base32) - ";
+pub const ERR_INVALID_CODE: &str = "The code must contain only digits.";
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const RECOVERY_CODE_ALPHABET: &[u8] = b"abcdefghjkmnpqrstuvwxyz23456789";
+
+/// Encode bytes into a base32 string (RFC 4648, without padding).
+pub fn base32_encode(data: &[u8]) -> String {
+    let mut result = String::new();
+    let mut buffer: u32 = 0;
+    let mut bits: u32 = 0;
+    for byte in data.iter() {
+        buffer = (buffer << 8) | (*byte as u32);
+        bits += 8;
+        while bits >= 5 {
+            let idx = (buffer >> (bits - 5)) & 0x1f;
+            result.push(BASE32_ALPHABET[idx as usize] as char);
+            bits -= 5;
+        }
+    }
+    if bits > 0 {
+        let idx = (buffer << (5 - bits)) & 0x1f;
+        result.push(BASE32_ALPHABET[idx as usize] as char);
+    }
+    result
+}
+
+/// Decode a base32 string (RFC 4648, padding and case are ignored) into bytes.
+pub fn base32_decode(value: &str) -> Result<Vec<u8>, String> {
+    let mut result: Vec<u8> = Vec::new();
+    let mut buffer: u32 = 0;
+    let mut bits: u32 = 0;
+    for ch in value.trim_end_matches('=').chars().filter(|ch| !ch.is_whitespace()) {
+        let ch = ch.to_ascii_uppercase() as u8;
+        let idx = BASE32_ALPHABET
+            .iter()
+            .position(|v| *v == ch)
+            .ok_or_else(|| format!("{}'{}'", ERR_INVALID_SECRET, ch as char))?;
+        buffer = (buffer << 5) | (idx as u32);
+        bits += 5;
+        if bits >= 8 {
+            result.push(((buffer >> (bits - 8)) & 0xff) as u8);
+            bits -= 8;
+        }
+    }
+    Ok(result)
+}
+
+/// Generate a new random secret key (in base32 encoding).
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; SECRET_LENGTH];
+    rand::rng().fill(&mut bytes[..]);
+    base32_encode(&bytes)
+}
+
+/// Get the number of the time period for the specified time (in seconds).
+pub fn get_time_step(timestamp: i64) -> i64 {
+    timestamp.div_euclid(TOTP_PERIOD)
+}
+
+/// Calculate the one-time code (HOTP, RFC 4226) for the secret key and the time period number.
+pub fn generate_code(secret: &str, time_step: i64) -> Result<String, String> {
+    let key = base32_decode(secret)?;
+    if key.is_empty() {
+        return Err(format!("{}empty", ERR_INVALID_SECRET));
+    }
+    let pkey = PKey::hmac(&key).map_err(|err| err.to_string())?;
+    let mut signer = Signer::new(MessageDigest::sha1(), &pkey).map_err(|err| err.to_string())?;
+    signer.update(&time_step.to_be_bytes()).map_err(|err| err.to_string())?;
+    let hmac = signer.sign_to_vec().map_err(|err| err.to_string())?;
+    // Dynamic truncation.
+    let offset = (hmac[hmac.len() - 1] & 0x0f) as usize;
+    let binary = ((hmac[offset] as u32 & 0x7f) << 24)
+        | ((hmac[offset + 1] as u32) << 16)
+        | ((hmac[offset + 2] as u32) << 8)
+        | (hmac[offset + 3] as u32);
+    let code = binary % 10_u32.pow(TOTP_DIGITS);
+    Ok(format!("{:0width$}", code, width = TOTP_DIGITS as usize))
+}
+
+/// Check the one-time code for the specified time (in seconds), taking into account the allowed deviation.
+/// Returns the number of the time period for which the code matched.
+pub fn verify_code(secret: &str, code: &str, timestamp: i64) -> Result<Option<i64>, String> {
+    let code = code.trim();
+    if code.len() != TOTP_DIGITS as usize || !code.chars().all(|ch| ch.is_ascii_digit()) {
+        return Err(ERR_INVALID_CODE.to_string());
+    }
+    let time_step = get_time_step(timestamp);
+    for step in (time_step - TOTP_SKEW)..=(time_step + TOTP_SKEW) {
+        if generate_code(secret, step)? == code {
+            return Ok(Some(step));
+        }
+    }
+    Ok(None)
+}
+
+/// Percent-encode a value for use in a URI.
+fn percent_encode(value: &str) -> String {
+    let mut result = String::new();
+    for byte in value.as_bytes().iter() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => result.push(*byte as char),
+            _ => result.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    result
+}
+
+/// Get the key URI ("otpauth://totp/...") for authenticator applications.
+pub fn get_otpauth_uri(issuer: &str, account: &str, secret: &str) -> String {
+    let issuer = percent_encode(issuer);
+    let account = percent_encode(account);
+    #[rustfmt::skip]
+    let result = format!("otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        issuer, account, secret, issuer, TOTP_DIGITS, TOTP_PERIOD);
+    result
+}
+
+/// Generate a list of random one-time recovery codes.
+pub fn generate_recovery_codes() -> Vec<String> {
+    let mut rng = rand::rng();
+    (0..RECOVERY_CODE_COUNT)
+        .map(|_| {
+            (0..RECOVERY_CODE_LENGTH)
+                .map(|_| RECOVERY_CODE_ALPHABET[rng.random_range(0..RECOVERY_CODE_ALPHABET.len())] as char)
+                .collect::<String>()
+        })
+        .collect()
+}
+
+/// Create a QR code image (in PNG format) for the specified data.
+pub fn encode_qr_code_png(data: &str) -> Result<Vec<u8>, String> {
+    let qr_code = QrCode::new(data.as_bytes()).map_err(|err| err.to_string())?;
+    let width = qr_code.width() as u32;
+    let colors = qr_code.to_colors();
+    let size = (width + QR_QUIET_ZONE * 2) * QR_MODULE_SIZE;
+
+    let image = GrayImage::from_fn(size, size, |x, y| {
+        let (col, row) = (x / QR_MODULE_SIZE, y / QR_MODULE_SIZE);
+        let is_inside = col >= QR_QUIET_ZONE && row >= QR_QUIET_ZONE && col < width + QR_QUIET_ZONE && row < width + QR_QUIET_ZONE;
+        #[rustfmt::skip]
+        let is_dark = is_inside && colors[((row - QR_QUIET_ZONE) * width + (col - QR_QUIET_ZONE)) as usize] == Color::Dark;
+        if is_dark { Luma([0u8]) } else { Luma([255u8]) }
+    });
+
+    let mut buffer: Vec<u8> = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut buffer), ImageFormat::Png)
+        .map_err(|err| err.to_string())?;
+    Ok(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The secret key "12345678901234567890" from RFC 6238 (Appendix B).
+    const SECRET_RFC: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    #[test]
+    fn test_base32_encode_decode() {
+        assert_eq!(base32_encode(b"12345678901234567890"), SECRET_RFC);
+        assert_eq!(base32_encode(b"f"), "MY");
+        assert_eq!(base32_encode(b"foobar"), "MZXW6YTBOI");
+        assert_eq!(base32_decode("MZXW6YTBOI======").unwrap(), b"foobar".to_vec());
+        assert_eq!(base32_decode(&SECRET_RFC.to_lowercase()).unwrap(), b"12345678901234567890".to_vec());
+        assert!(base32_decode("MZXW6YTB01").is_err());
+    }
+    #[test]
+    fn test_generate_secret() {
+        let secret = generate_secret();
+        assert_eq!(base32_decode(&secret).unwrap().len(), SECRET_LENGTH);
+        assert_ne!(secret, generate_secret());
+    }
+    #[test]
+    fn test_generate_code_rfc6238() {
+        // Test vectors from RFC 6238 (the last 6 digits of the SHA1 values).
+        let vectors: [(i64, &str); 4] = [
+            (59, "287082"),
+            (1111111109, "081804"),
+            (1234567890, "005924"),
+            (2000000000, "279037"),
+        ];
+        for (timestamp, code) in vectors.iter() {
+            assert_eq!(generate_code(SECRET_RFC, get_time_step(*timestamp)).unwrap(), code.to_string());
+        }
+    }
+    #[test]
+    fn test_verify_code() {
+        assert_eq!(verify_code(SECRET_RFC, "287082", 59).unwrap(), Some(1));
+        // The code of the previous period is allowed.
+        assert_eq!(verify_code(SECRET_RFC, "287082", 59 + TOTP_PERIOD).unwrap(), Some(1));
+        assert_eq!(verify_code(SECRET_RFC, "287082", 59 + TOTP_PERIOD * 3).unwrap(), None);
+        assert_eq!(verify_code(SECRET_RFC, "28708", 59).unwrap_err(), ERR_INVALID_CODE);
+        assert_eq!(verify_code(SECRET_RFC, "28708a", 59).unwrap_err(), ERR_INVALID_CODE);
+    }
+    #[test]
+    fn test_get_otpauth_uri() {
+        let uri = get_otpauth_uri("Verbéna", "user 1", "ABC");
+        #[rustfmt::skip]
+        assert_eq!(uri, "otpauth://totp/Verb%C3%A9na:user%201?secret=ABC&issuer=Verb%C3%A9na&algorithm=SHA1&digits=6&period=30");
+    }
+    #[test]
+    fn test_generate_recovery_codes() {
+        let codes = generate_recovery_codes();
+        assert_eq!(codes.len(), RECOVERY_CODE_COUNT);
+        assert!(codes.iter().all(|code| code.len() == RECOVERY_CODE_LENGTH));
+    }
+    #[test]
+    fn test_encode_qr_code_png() {
+        let buffer = encode_qr_code_png("otpauth://totp/app:user?secret=ABC").unwrap();
+        let image = image::load_from_memory_with_format(&buffer, ImageFormat::Png).unwrap();
+        assert_eq!(image.width() % QR_MODULE_SIZE, 0);
+        assert_eq!(image.width(), image.height());
+    }
+}
@@ -0,0 +1,90 @@
+use std::env;
+
+use vrb_common::{consts, parser};
+
+pub const HOOK_POLL: &str = "5";
+pub const HOOK_BATCH: &str = "20";
+pub const HOOK_TIMEOUT: &str = "10";
+pub const HOOK_MAX_ATTEMPTS: &str = "6";
+pub const HOOK_BACKOFF: &str = "30";
+pub const HOOK_ALLOW_PRIVATE: &str = "false";
+
+// Webhook Delivery Properties
+#[derive(Debug, Clone)]
+pub struct ConfigHook {
+    // Interval (in seconds) for sending the pending deliveries (and retries).
+    pub hook_poll: u64,
+    // The maximum number of deliveries sent per pass.
+    pub hook_batch: u32,
+    // Timeout (in seconds) for sending one delivery (connecting to the webhook URL and waiting for its response).
+    pub hook_timeout: u64,
+    // The number of attempts to send a delivery, after which it is marked as "failed".
+    pub hook_max_attempts: u32,
+    // Delay (in seconds) before the first retry; each next retry waits twice as long.
+    pub hook_backoff: u64,
+    // Allow sending to loopback and private network addresses (only for local testing of a receiver).
+    pub hook_allow_private: bool,
+}
+
+impl ConfigHook {
+    pub fn init_by_env() -> Self {
+        let poll = HOOK_POLL.to_string();
+        let hook_poll: u64 = env::var(consts::HOOK_POLL).unwrap_or(poll).trim().parse().unwrap();
+
+        let batch = HOOK_BATCH.to_string();
+        let hook_batch: u32 = env::var(consts::HOOK_BATCH).unwrap_or(batch).trim().parse().unwrap();
+
+        let timeout = HOOK_TIMEOUT.to_string();
+        let hook_timeout: u64 = env::var(consts::HOOK_TIMEOUT).unwrap_or(timeout).trim().parse().unwrap();
+
+        let max_attempts = HOOK_MAX_ATTEMPTS.to_string();
+        #[rustfmt::skip]
+        let hook_max_attempts: u32 = env::var(consts::HOOK_MAX_ATTEMPTS).unwrap_or(max_attempts).trim().parse().unwrap();
+
+        let backoff = HOOK_BACKOFF.to_string();
+        let hook_backoff: u64 = env::var(consts::HOOK_BACKOFF).unwrap_or(backoff).trim().parse().unwrap();
+
+        let allow_private = env::var(consts::HOOK_ALLOW_PRIVATE).unwrap_or(HOOK_ALLOW_PRIVATE.to_string());
+        let hook_allow_private = parser::parse_bool(allow_private.trim()).unwrap();
+
+        ConfigHook {
+            hook_poll: hook_poll.max(1),
+            hook_batch: hook_batch.max(1),
+            hook_timeout: hook_timeout.max(1),
+            hook_max_attempts: hook_max_attempts.max(1),
+            hook_backoff,
+            hook_allow_private,
+        }
+    }
+}
+
+impl Default for ConfigHook {
+    fn default() -> Self {
+        ConfigHook {
+            hook_poll: HOOK_POLL.parse().unwrap(),
+            hook_batch: HOOK_BATCH.parse().unwrap(),
+            hook_timeout: HOOK_TIMEOUT.parse().unwrap(),
+            hook_max_attempts: HOOK_MAX_ATTEMPTS.parse().unwrap(),
+            hook_backoff: HOOK_BACKOFF.parse().unwrap(),
+            hook_allow_private: false,
+        }
+    }
+}
+
+#[cfg(any(test, feature = "mockdata"))]
+pub mod tests {
+    use super::*;
+
+    pub fn get_config() -> ConfigHook {
+        ConfigHook {
+            // Deliveries are sent on the event, so the polling does not interfere with the tests.
+            hook_poll: 3600,
+            hook_batch: HOOK_BATCH.parse().unwrap(),
+            hook_timeout: 2,
+            hook_max_attempts: 3,
+            hook_backoff: HOOK_BACKOFF.parse().unwrap(),
+            // The receivers of the tests listen on the loopback address.
+            hook_allow_private: true,
+        }
+    }
+}
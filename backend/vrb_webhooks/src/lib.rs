@@ -0,0 +1,9 @@
+pub mod config_hook;
+pub mod webhook_controller;
+pub mod webhook_dispatcher;
+pub mod webhook_models;
+pub mod webhook_orm;
+pub mod webhook_sender;
+pub mod webhook_test_delete;
+pub mod webhook_test_get;
+pub mod webhook_test_post_put;
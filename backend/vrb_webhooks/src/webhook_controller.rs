@@ -0,0 +1,470 @@
+use std::ops::Deref;
+
+use actix_web::{HttpResponse, delete, get, http::StatusCode, post, put, web};
+use log::error;
+use utoipa;
+use vrb_authent::authentication::{Authenticated, RequireAuth};
+use vrb_common::{
+    api_error::{ApiError, code_to_str},
+    err, parser,
+    validators::{Validator, msg_validation},
+};
+use vrb_dbase::enm_user_role::UserRole;
+
+#[cfg(not(all(test, feature = "mockdata")))]
+use crate::webhook_orm::impls::WebhookOrmApp;
+#[cfg(all(test, feature = "mockdata"))]
+use crate::webhook_orm::tests::WebhookOrmApp;
+use crate::{
+    webhook_models::{CreateWebhook, CreateWebhookDto, ModifyWebhook, ModifyWebhookDto, WebhookDeliveryDto, WebhookDto, WebhookEvent},
+    webhook_orm::WebhookOrm,
+    webhook_sender,
+};
+
+// ** Section: Webhook Deliveries **
+
+// The maximum number of the latest deliveries in the delivery log of the webhook.
+pub const WEBHOOK_DELIVERIES_MAX: i64 = 100;
+
+// ** **
+
+pub fn configure() -> impl FnOnce(&mut web::ServiceConfig) {
+    |config: &mut web::ServiceConfig| {
+        //     GET /api/webhooks
+        config
+            .service(get_webhooks)
+            // POST /api/webhooks
+            .service(post_webhook)
+            // PUT /api/webhooks/{id}
+            .service(put_webhook)
+            // DELETE /api/webhooks/{id}
+            .service(delete_webhook)
+            // GET /api/webhooks/{id}/deliveries
+            .service(get_webhook_deliveries);
+    }
+}
+
+/// Get the ID from the request path.
+fn get_id_from_request(request: &actix_web::HttpRequest) -> Result<i32, ApiError> {
+    let id_str = request.match_info().query("id").to_string();
+    parser::parse_i32(&id_str).map_err(|e| {
+        let msg = format!("`{}` - {}", "id", &e);
+        error!(
+            "{}-{}; {}",
+            code_to_str(StatusCode::RANGE_NOT_SATISFIABLE),
+            err::MSG_PARSING_TYPE_NOT_SUPPORTED,
+            &msg
+        );
+        ApiError::create(416, err::MSG_PARSING_TYPE_NOT_SUPPORTED, &msg) // 416
+    })
+}
+
+// ** Section: Webhook Get **
+
+/// get_webhooks
+///
+/// Get a list of webhooks of the current user.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X GET http://localhost:8080/api/webhooks
+/// ```
+///
+/// Return the list of webhooks (`Vec<WebhookDto>`) with status 200. The secret of the webhook is not returned.
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "List of webhooks of the current user.", body = Vec<WebhookDto>),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    security(("bearer_auth" = [])),
+)]
+// GET /api/webhooks
+#[rustfmt::skip]
+#[get("/api/webhooks", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn get_webhooks(
+    authenticated: Authenticated,
+    webhook_orm: web::Data<WebhookOrmApp>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    // Get current user details.
+    let user = authenticated.deref();
+    let user_id = user.id;
+
+    let res_webhooks = web::block(move || {
+        // Get a list of webhooks of the user.
+        let res_data = webhook_orm.get_webhooks(user_id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_data
+    })
+    .await
+    .map_err(|e| {
+        #[rustfmt::skip]
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })?;
+
+    let webhooks = res_webhooks?;
+    let webhook_dto_list: Vec<WebhookDto> = webhooks.into_iter().map(|v| WebhookDto::from(v)).collect();
+
+    Ok(HttpResponse::Ok().json(webhook_dto_list)) // 200
+}
+
+// ** Section: Webhook Post **
+
+/// post_webhook
+///
+/// Add a new webhook for the current user.
+/// The webhook receives the events of the user's streams and their chats, to which it is subscribed:
+/// - "stream.created" - the stream was created;
+/// - "stream.updated" - the data of the stream was changed;
+/// - "stream.deleted" - the stream was deleted;
+/// - "stream.state" - the state of the stream was changed;
+/// - "chat.message" - a new message was added to the chat of the stream.
+///
+/// Request structure:
+/// ```text
+/// {
+///   url: String,             // required - the URL to which the events are sent (http or https);
+///   events: Vec<String>,     // required - the list of events the webhook is subscribed to;
+///   isActive?: bool,         // optional - whether the webhook receives events (true by default);
+/// }
+/// ```
+///
+/// Each event is sent as a POST request with a JSON body `{ "event": "stream.created", "createdAt": "...", "data": {...} }`.
+/// The request contains the headers:
+/// - "X-Verbena-Event" - the name of the event;
+/// - "X-Verbena-Delivery" - the unique ID of the delivery;
+/// - "X-Verbena-Timestamp" - the time of sending (Unix time in seconds);
+/// - "X-Verbena-Signature" - "sha256=" + hex(HMAC-SHA256(secret, "{timestamp}.{body}")).
+///
+/// If the response status is not 2xx, then the delivery is retried with a growing delay.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X POST http://localhost:8080/api/webhooks -d '{"url": "https://example.com/hook", "events": ["stream.state"]}'
+/// ```
+///
+/// Return the new webhook (`WebhookDto`) with status 201. Only this response contains the secret of the webhook.
+///
+#[utoipa::path(
+    responses(
+        (status = 201, description = "The webhook was created. The response contains the secret of the webhook.", body = WebhookDto),
+        (status = 417, description = "Validation error. `curl -i -X POST http://localhost:8080/api/webhooks
+            -d '{\"url\": \"ftp://example.com\", \"events\": []}'`", body = [ApiError],
+            example = json!(ApiError::validations((CreateWebhookDto {
+                url: "ftp://example.com".to_owned(), events: vec![], is_active: None
+            }).validate().err().unwrap()))),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    request_body(content = CreateWebhookDto, description = "The URL of the webhook and the list of events.",
+        example = json!({ "url": "https://example.com/hook", "events": [WebhookEvent::StreamState, WebhookEvent::ChatMessage] })),
+    security(("bearer_auth" = [])),
+)]
+// POST /api/webhooks
+#[rustfmt::skip]
+#[post("/api/webhooks", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn post_webhook(
+    authenticated: Authenticated,
+    webhook_orm: web::Data<WebhookOrmApp>,
+    json_body: web::Json<CreateWebhookDto>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    // Get current user details.
+    let user = authenticated.deref();
+
+    // Checking the validity of the data model.
+    let validation_res = json_body.validate();
+    if let Err(validation_errors) = validation_res {
+        error!("{}-{}", code_to_str(StatusCode::EXPECTATION_FAILED), msg_validation(&validation_errors));
+        return Ok(ApiError::to_response(&ApiError::validations(validation_errors))); // 417
+    }
+
+    let secret = webhook_sender::generate_secret();
+    let create_webhook = CreateWebhook::convert(json_body.into_inner(), user.id, &secret);
+
+    let res_webhook = web::block(move || {
+        // Add a new entity (webhook).
+        let res_data = webhook_orm.create_webhook(create_webhook).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_data
+    })
+    .await
+    .map_err(|e| {
+        #[rustfmt::skip]
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })?;
+
+    let webhook = res_webhook?;
+
+    Ok(HttpResponse::Created().json(WebhookDto::with_secret(webhook))) // 201
+}
+
+// ** Section: Webhook Put **
+
+/// put_webhook
+///
+/// Update the webhook with new data.
+/// Only the owner of the webhook or the administrator can change it.
+///
+/// Request structure:
+/// ```text
+/// {
+///   url?: String,            // optional - the URL to which the events are sent (http or https);
+///   events?: Vec<String>,    // optional - the list of events the webhook is subscribed to;
+///   isActive?: bool,         // optional - whether the webhook receives events;
+/// }
+/// ```
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X PUT http://localhost:8080/api/webhooks/1 -d '{"isActive": false}'
+/// ```
+///
+/// Return the updated webhook (`WebhookDto`) with status 200 or 204 (no content) if the webhook is not found.
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "The webhook was updated.", body = WebhookDto),
+        (status = 204, description = "The webhook with the specified ID was not found."),
+        (status = 416, description = "Error parsing input parameter. `curl -i -X PUT http://localhost:8080/api/webhooks/2a
+            -d '{\"isActive\": false}'`", body = ApiError, example = json!(ApiError::create(416,
+                err::MSG_PARSING_TYPE_NOT_SUPPORTED, "`id` - invalid digit found in string (2a)"))),
+        (status = 417, description = "Validation error. `curl -i -X PUT http://localhost:8080/api/webhooks/1 -d '{}'`",
+            body = [ApiError], example = json!(ApiError::validations((ModifyWebhookDto {
+                url: None, events: None, is_active: None
+            }).validate().err().unwrap()))),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    params(("id", description = "Unique webhook ID.")),
+    request_body(content = ModifyWebhookDto, description = "The new data of the webhook.",
+        example = json!({ "events": [WebhookEvent::StreamCreated, WebhookEvent::StreamDeleted], "isActive": true })),
+    security(("bearer_auth" = [])),
+)]
+// PUT /api/webhooks/{id}
+#[rustfmt::skip]
+#[put("/api/webhooks/{id}", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn put_webhook(
+    authenticated: Authenticated,
+    webhook_orm: web::Data<WebhookOrmApp>,
+    request: actix_web::HttpRequest,
+    json_body: web::Json<ModifyWebhookDto>,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    // Get current user details.
+    let user = authenticated.deref();
+
+    // Get data from request.
+    let id = get_id_from_request(&request)?;
+
+    // Checking the validity of the data model.
+    let validation_res = json_body.validate();
+    if let Err(validation_errors) = validation_res {
+        error!("{}-{}", code_to_str(StatusCode::EXPECTATION_FAILED), msg_validation(&validation_errors));
+        return Ok(ApiError::to_response(&ApiError::validations(validation_errors))); // 417
+    }
+
+    let modify_webhook = ModifyWebhook::from(json_body.into_inner());
+    let opt_user_id: Option<i32> = if user.role == UserRole::Admin { None } else { Some(user.id) };
+
+    let res_webhook = web::block(move || {
+        // Modify an entity (webhook).
+        let res_data = webhook_orm.modify_webhook(id, opt_user_id, modify_webhook).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_data
+    })
+    .await
+    .map_err(|e| {
+        #[rustfmt::skip]
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })?;
+
+    if let Some(webhook) = res_webhook? {
+        Ok(HttpResponse::Ok().json(WebhookDto::from(webhook))) // 200
+    } else {
+        Ok(HttpResponse::NoContent().finish()) // 204
+    }
+}
+
+// ** Section: Webhook Delete **
+
+/// delete_webhook
+///
+/// Delete the specified webhook together with its delivery log.
+/// Only the owner of the webhook or the administrator can delete it.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X DELETE http://localhost:8080/api/webhooks/1
+/// ```
+///
+/// Return the deleted webhook (`WebhookDto`) with status 200 or 204 (no content) if the webhook is not found.
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "The specified webhook was deleted successfully.", body = WebhookDto),
+        (status = 204, description = "The webhook with the specified ID was not found."),
+        (status = 416, description = "Error parsing input parameter. `curl -i -X DELETE http://localhost:8080/api/webhooks/2a`",
+            body = ApiError, example = json!(ApiError::create(416,
+                err::MSG_PARSING_TYPE_NOT_SUPPORTED, "`id` - invalid digit found in string (2a)"))),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    params(("id", description = "Unique webhook ID.")),
+    security(("bearer_auth" = [])),
+)]
+// DELETE /api/webhooks/{id}
+#[rustfmt::skip]
+#[delete("/api/webhooks/{id}", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn delete_webhook(
+    authenticated: Authenticated,
+    webhook_orm: web::Data<WebhookOrmApp>,
+    request: actix_web::HttpRequest,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    // Get current user details.
+    let user = authenticated.deref();
+
+    // Get data from request.
+    let id = get_id_from_request(&request)?;
+
+    let opt_user_id: Option<i32> = if user.role == UserRole::Admin { None } else { Some(user.id) };
+
+    let res_webhook = web::block(move || {
+        // Delete an entity (webhook).
+        let res_data = webhook_orm.delete_webhook(id, opt_user_id).map_err(|e| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        });
+        res_data
+    })
+    .await
+    .map_err(|e| {
+        #[rustfmt::skip]
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })?;
+
+    if let Some(webhook) = res_webhook? {
+        Ok(HttpResponse::Ok().json(WebhookDto::from(webhook))) // 200
+    } else {
+        Ok(HttpResponse::NoContent().finish()) // 204
+    }
+}
+
+// ** Section: Webhook Deliveries **
+
+/// get_webhook_deliveries
+///
+/// Get the delivery log of the specified webhook: the latest deliveries (new ones first, no more than 100).
+/// Only the owner of the webhook or the administrator can view the log.
+///
+/// The delivery has the status:
+/// - "pending" - waiting to be sent (the time of the next attempt is in "nextAttemptAt");
+/// - "delivered" - the webhook URL responded with the status 2xx;
+/// - "failed" - all attempts to send have failed.
+///
+/// One could call with following curl.
+/// ```text
+/// curl -i -X GET http://localhost:8080/api/webhooks/1/deliveries
+/// ```
+///
+/// Return the list of deliveries (`Vec<WebhookDeliveryDto>`) with status 200 or 204 (no content) if the webhook is not found.
+///
+#[utoipa::path(
+    responses(
+        (status = 200, description = "The delivery log of the webhook.", body = Vec<WebhookDeliveryDto>),
+        (status = 204, description = "The webhook with the specified ID was not found."),
+        (status = 416, description = "Error parsing input parameter. `curl -i -X GET http://localhost:8080/api/webhooks/2a/deliveries`",
+            body = ApiError, example = json!(ApiError::create(416,
+                err::MSG_PARSING_TYPE_NOT_SUPPORTED, "`id` - invalid digit found in string (2a)"))),
+        (status = 506, description = "Blocking error.", body = ApiError,
+            example = json!(ApiError::create(506, err::MSG_BLOCKING, "Error while blocking process."))),
+        (status = 507, description = "Database error.", body = ApiError,
+            example = json!(ApiError::create(507, err::MSG_DATABASE, "Error while querying the database."))),
+    ),
+    params(("id", description = "Unique webhook ID.")),
+    security(("bearer_auth" = [])),
+)]
+// GET /api/webhooks/{id}/deliveries
+#[rustfmt::skip]
+#[get("/api/webhooks/{id}/deliveries", wrap = "RequireAuth::allowed_roles(RequireAuth::all_roles())")]
+pub async fn get_webhook_deliveries(
+    authenticated: Authenticated,
+    webhook_orm: web::Data<WebhookOrmApp>,
+    request: actix_web::HttpRequest,
+) -> actix_web::Result<HttpResponse, ApiError> {
+    // Get current user details.
+    let user = authenticated.deref();
+
+    // Get data from request.
+    let id = get_id_from_request(&request)?;
+
+    let opt_user_id: Option<i32> = if user.role == UserRole::Admin { None } else { Some(user.id) };
+
+    let res_webhook_deliveries = web::block(move || {
+        let err_database = |e: String| {
+            error!("{}-{}; {}", code_to_str(StatusCode::INSUFFICIENT_STORAGE), err::MSG_DATABASE, &e);
+            ApiError::create(507, err::MSG_DATABASE, &e) // 507
+        };
+        // Only the owner of the webhook (or the administrator) can view its delivery log.
+        let opt_webhook = webhook_orm.find_webhook_by_id(id, opt_user_id).map_err(err_database)?;
+        if opt_webhook.is_none() {
+            return Ok(None);
+        }
+        // Get a list of the latest deliveries of the webhook.
+        let webhook_deliveries = webhook_orm.get_webhook_deliveries(id, WEBHOOK_DELIVERIES_MAX).map_err(err_database)?;
+        Ok(Some(webhook_deliveries))
+    })
+    .await
+    .map_err(|e| {
+        #[rustfmt::skip]
+        error!("{}-{}; {}", code_to_str(StatusCode::VARIANT_ALSO_NEGOTIATES), err::MSG_BLOCKING, &e.to_string());
+        ApiError::create(506, err::MSG_BLOCKING, &e.to_string()) // 506
+    })?;
+
+    if let Some(webhook_deliveries) = res_webhook_deliveries? {
+        #[rustfmt::skip]
+        let webhook_delivery_dto_list: Vec<WebhookDeliveryDto> =
+            webhook_deliveries.into_iter().map(|v| WebhookDeliveryDto::from(v)).collect();
+        Ok(HttpResponse::Ok().json(webhook_delivery_dto_list)) // 200
+    } else {
+        Ok(HttpResponse::NoContent().finish()) // 204
+    }
+}
+
+#[cfg(all(test, feature = "mockdata"))]
+pub mod tests {
+
+    use actix_web::http;
+    use vrb_common::api_error::ApiError;
+    use vrb_tools::token_data::BEARER;
+
+    pub fn header_auth(token: &str) -> (http::header::HeaderName, http::header::HeaderValue) {
+        let header_value = http::header::HeaderValue::from_str(&format!("{}{}", BEARER, token)).unwrap();
+        (http::header::AUTHORIZATION, header_value)
+    }
+    pub fn check_app_err(app_err_vec: Vec<ApiError>, code: &str, msgs: &[&str]) {
+        assert_eq!(app_err_vec.len(), msgs.len());
+        for (idx, msg) in msgs.iter().enumerate() {
+            let app_err = app_err_vec.get(idx).unwrap();
+            assert_eq!(app_err.code, code);
+            assert_eq!(app_err.message, msg.to_string());
+        }
+    }
+}
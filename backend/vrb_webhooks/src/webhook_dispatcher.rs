@@ -0,0 +1,355 @@
+use std::{sync::Arc, time::Duration};
+
+use actix::prelude::*;
+use actix_web::rt::task;
+use chrono::Utc;
+use log::{error, info};
+use serde_json;
+
+use crate::{
+    config_hook::ConfigHook,
+    webhook_models::{ModifyWebhookDelivery, WebhookEvent, WebhookPayload, WebhookTask},
+    webhook_orm::WebhookOrm,
+    webhook_sender::{self, HEADER_DELIVERY, HEADER_EVENT, HEADER_SIGNATURE, HEADER_TIMESTAMP},
+};
+
+pub type WebhookOrmArc = Arc<dyn WebhookOrm + Send + Sync>;
+
+// ** Messages of the webhook dispatcher. **
+
+/// Set the storage of webhooks (and the settings) and start sending the deliveries.
+/// Until it is set, the events are ignored.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct SetWebhookOrm(pub WebhookOrmArc, pub ConfigHook);
+
+/// The event for the webhooks of the owner of the stream.
+/// If "opt_user_id" is not specified, then the owner of the stream "opt_stream_id" is used.
+#[derive(Debug, Clone, Message)]
+#[rtype(result = "()")]
+pub struct FireWebhookEvent {
+    pub opt_user_id: Option<i32>,
+    pub opt_stream_id: Option<i32>,
+    pub event: WebhookEvent,
+    pub data: serde_json::Value,
+}
+
+impl FireWebhookEvent {
+    /// Create an event for the webhooks of the specified user.
+    pub fn for_user(user_id: i32, event: WebhookEvent, data: serde_json::Value) -> Self {
+        FireWebhookEvent {
+            opt_user_id: Some(user_id),
+            opt_stream_id: None,
+            event,
+            data,
+        }
+    }
+    /// Create an event for the webhooks of the owner of the specified stream.
+    pub fn for_stream(stream_id: i32, event: WebhookEvent, data: serde_json::Value) -> Self {
+        FireWebhookEvent {
+            opt_user_id: None,
+            opt_stream_id: Some(stream_id),
+            event,
+            data,
+        }
+    }
+}
+
+/// Send the deliveries whose time has come.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct SendWebhookDeliveries;
+
+// ** Sending deliveries. **
+
+/// Get the time (in seconds) for which the taken deliveries are locked for other processes.
+/// It must be enough to send the whole batch: each delivery takes no longer than the timeout (see `post_json`),
+/// and the margin covers resolving the host names and saving the results.
+pub fn get_lock_secs(config_hook: &ConfigHook) -> i32 {
+    let lock_secs = u64::from(config_hook.hook_batch).saturating_mul(config_hook.hook_timeout.saturating_mul(3));
+    i32::try_from(lock_secs).unwrap_or(i32::MAX)
+}
+
+/// Send the delivery to the webhook URL. Return the HTTP status of the response.
+pub fn send_webhook_task(webhook_task: &WebhookTask, timeout: Duration, allow_private: bool) -> Result<u16, String> {
+    let timestamp = Utc::now().timestamp();
+    let signature = webhook_sender::sign_payload(&webhook_task.secret, timestamp, &webhook_task.payload)?;
+    let headers = vec![
+        (HEADER_EVENT, webhook_task.event.clone()),
+        (HEADER_DELIVERY, webhook_task.id.to_string()),
+        (HEADER_TIMESTAMP, timestamp.to_string()),
+        (HEADER_SIGNATURE, signature),
+    ];
+    webhook_sender::post_json(&webhook_task.url, &headers, &webhook_task.payload, timeout, allow_private)
+}
+
+/// Send one batch of the deliveries whose time has come and save the results. Return the number of deliveries taken.
+pub fn send_webhook_deliveries(webhook_orm: &dyn WebhookOrm, config_hook: &ConfigHook) -> Result<usize, String> {
+    let limit = i32::try_from(config_hook.hook_batch).unwrap_or(i32::MAX);
+    let webhook_tasks = webhook_orm.claim_webhook_deliveries(limit, get_lock_secs(config_hook))?;
+    let timeout = Duration::from_secs(config_hook.hook_timeout);
+
+    for webhook_task in webhook_tasks.iter() {
+        let result = send_webhook_task(webhook_task, timeout, config_hook.hook_allow_private);
+        if let Err(err) = &result {
+            #[rustfmt::skip]
+            info!("send_webhook_deliveries() delivery: {}, url: {}, error: {}", webhook_task.id, &webhook_task.url, err);
+        }
+        let (max_attempts, backoff) = (config_hook.hook_max_attempts, config_hook.hook_backoff);
+        let modify_webhook_delivery = ModifyWebhookDelivery::from_attempt(webhook_task.attempts, result, max_attempts, backoff, Utc::now());
+        webhook_orm.modify_webhook_delivery(webhook_task.id, modify_webhook_delivery)?;
+    }
+    Ok(webhook_tasks.len())
+}
+
+// ** The dispatcher of webhook events. **
+// Saves the events as deliveries and sends them (in a separate thread) on the event and periodically (for retries).
+#[derive(Default)]
+pub struct WebhookDispatcher {
+    webhook_orm: Option<WebhookOrmArc>,
+    config_hook: ConfigHook,
+    is_sending: bool, // The deliveries are being sent.
+    is_repeat: bool,  // Sending was requested while the previous one was in progress.
+}
+
+impl SystemService for WebhookDispatcher {}
+impl Supervised for WebhookDispatcher {}
+
+impl Actor for WebhookDispatcher {
+    type Context = Context<Self>;
+}
+
+impl Handler<SetWebhookOrm> for WebhookDispatcher {
+    type Result = ();
+
+    fn handle(&mut self, msg: SetWebhookOrm, ctx: &mut Self::Context) {
+        let SetWebhookOrm(webhook_orm, config_hook) = msg;
+        let poll = Duration::from_secs(config_hook.hook_poll);
+        self.webhook_orm = Some(webhook_orm);
+        self.config_hook = config_hook;
+        // Periodically send the deliveries that are waiting for the next attempt.
+        ctx.run_interval(poll, |_act, ctx| ctx.notify(SendWebhookDeliveries));
+        // Send the deliveries left over from the previous start.
+        ctx.notify(SendWebhookDeliveries);
+    }
+}
+
+impl Handler<FireWebhookEvent> for WebhookDispatcher {
+    type Result = ();
+
+    fn handle(&mut self, msg: FireWebhookEvent, ctx: &mut Self::Context) {
+        let Some(webhook_orm) = self.webhook_orm.clone() else {
+            return;
+        };
+        let webhook_payload = WebhookPayload {
+            event: msg.event,
+            created_at: Utc::now(),
+            data: msg.data,
+        };
+        let payload = serde_json::to_string(&webhook_payload).unwrap();
+        let (opt_user_id, opt_stream_id, event) = (msg.opt_user_id, msg.opt_stream_id, msg.event);
+        #[rustfmt::skip]
+        let fut = task::spawn_blocking(move || {
+            webhook_orm.create_webhook_deliveries(opt_user_id, opt_stream_id, event, &payload)
+        });
+        let fut = fut::wrap_future::<_, Self>(fut).map(move |res, _act, ctx| match res {
+            Ok(Ok(webhook_deliveries)) => {
+                if !webhook_deliveries.is_empty() {
+                    ctx.notify(SendWebhookDeliveries);
+                }
+            }
+            Ok(Err(err)) => error!("WebhookDispatcher: event: {}, error: {}", event, err),
+            Err(err) => error!("WebhookDispatcher: event: {}, error: {}", event, err),
+        });
+        ctx.spawn(fut);
+    }
+}
+
+impl Handler<SendWebhookDeliveries> for WebhookDispatcher {
+    type Result = ();
+
+    fn handle(&mut self, _msg: SendWebhookDeliveries, ctx: &mut Self::Context) {
+        let Some(webhook_orm) = self.webhook_orm.clone() else {
+            return;
+        };
+        if self.is_sending {
+            self.is_repeat = true;
+            return;
+        }
+        self.is_sending = true;
+        let config_hook = self.config_hook.clone();
+        let batch = usize::try_from(config_hook.hook_batch).unwrap_or(usize::MAX);
+        let fut = task::spawn_blocking(move || send_webhook_deliveries(webhook_orm.as_ref(), &config_hook));
+        let fut = fut::wrap_future::<_, Self>(fut).map(move |res, act, ctx| {
+            act.is_sending = false;
+            // A full batch means that there may be more deliveries whose time has come.
+            let is_full_batch = match res {
+                Ok(Ok(count)) => count >= batch,
+                Ok(Err(err)) => {
+                    error!("WebhookDispatcher: sending deliveries, error: {}", err);
+                    false
+                }
+                Err(err) => {
+                    error!("WebhookDispatcher: sending deliveries, error: {}", err);
+                    false
+                }
+            };
+            if act.is_repeat || is_full_batch {
+                act.is_repeat = false;
+                ctx.notify(SendWebhookDeliveries);
+            }
+        });
+        ctx.spawn(fut);
+    }
+}
+
+#[cfg(all(test, feature = "mockdata"))]
+pub mod tests {
+    use actix_web::rt::time::sleep;
+    use serde_json::json;
+    use vrb_dbase::enm_delivery_status::DeliveryStatus;
+
+    use crate::{
+        config_hook,
+        webhook_models::Webhook,
+        webhook_orm::tests::{WEBHOOK_DELIVERY_ID, WEBHOOK_ID, WebhookOrmApp},
+        webhook_sender::{SIGNATURE_PREFIX, hmac_sha256_hex, tests::ReceiverTest},
+    };
+
+    use super::*;
+
+    const WAIT: Duration = Duration::from_millis(300);
+
+    fn start_dispatcher(webhook_orm: WebhookOrmApp) -> Addr<WebhookDispatcher> {
+        let dispatcher = WebhookDispatcher::default().start();
+        dispatcher.do_send(SetWebhookOrm(Arc::new(webhook_orm), config_hook::tests::get_config()));
+        dispatcher
+    }
+
+    // ** FireWebhookEvent **
+
+    #[actix_web::test]
+    async fn test_fire_webhook_event_delivered_with_signature() {
+        let receiver = ReceiverTest::start(&[200]);
+        let events = vec![WebhookEvent::StreamCreated, WebhookEvent::StreamState];
+        let webhook = Webhook::new(WEBHOOK_ID, 1, &receiver.url, "secret1", &events);
+        let webhook_orm = WebhookOrmApp::create(&[webhook]);
+        let dispatcher = start_dispatcher(webhook_orm.clone());
+
+        let data = json!({ "id": 10, "title": "title1" });
+        dispatcher.do_send(FireWebhookEvent::for_user(1, WebhookEvent::StreamCreated, data.clone()));
+        // The webhook is not subscribed to this event.
+        dispatcher.do_send(FireWebhookEvent::for_user(1, WebhookEvent::StreamDeleted, data.clone()));
+        // The event of another user.
+        dispatcher.do_send(FireWebhookEvent::for_user(2, WebhookEvent::StreamCreated, data.clone()));
+        sleep(WAIT).await;
+
+        let requests = receiver.requests();
+        assert_eq!(requests.len(), 1);
+        let request = &requests[0];
+        assert_eq!(request.headers.get("x-verbena-event").unwrap(), "stream.created");
+        assert_eq!(request.headers.get("x-verbena-delivery").unwrap(), &WEBHOOK_DELIVERY_ID.to_string());
+        // The receiver checks the signature with the secret of the webhook.
+        let timestamp = request.headers.get("x-verbena-timestamp").unwrap();
+        let hmac = hmac_sha256_hex(b"secret1", format!("{}.{}", timestamp, &request.body).as_bytes()).unwrap();
+        assert_eq!(request.headers.get("x-verbena-signature").unwrap(), &format!("{}{}", SIGNATURE_PREFIX, hmac));
+        let payload: WebhookPayload = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(payload.event, WebhookEvent::StreamCreated);
+        assert_eq!(payload.data, data);
+
+        let deliveries = webhook_orm.deliveries();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].status, DeliveryStatus::Delivered);
+        assert_eq!(deliveries[0].attempts, 1);
+        assert_eq!(deliveries[0].response_status, Some(200));
+        assert!(deliveries[0].delivered_at.is_some());
+    }
+    #[actix_web::test]
+    async fn test_fire_webhook_event_for_stream_owner() {
+        let receiver = ReceiverTest::start(&[204]);
+        let webhook = Webhook::new(WEBHOOK_ID, 1, &receiver.url, "secret1", &[WebhookEvent::ChatMessage]);
+        let webhook_orm = WebhookOrmApp::create(&[webhook]).with_stream_owner(10, 1);
+        let dispatcher = start_dispatcher(webhook_orm.clone());
+
+        let data = json!({ "streamId": 10, "msg": "msg1" });
+        dispatcher.do_send(FireWebhookEvent::for_stream(10, WebhookEvent::ChatMessage, data.clone()));
+        // The stream of another user.
+        dispatcher.do_send(FireWebhookEvent::for_stream(11, WebhookEvent::ChatMessage, data.clone()));
+        sleep(WAIT).await;
+
+        let requests = receiver.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].headers.get("x-verbena-event").unwrap(), "chat.message");
+        let deliveries = webhook_orm.deliveries();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].status, DeliveryStatus::Delivered);
+    }
+    #[actix_web::test]
+    async fn test_fire_webhook_event_inactive_webhook() {
+        let receiver = ReceiverTest::start(&[200]);
+        let mut webhook = Webhook::new(WEBHOOK_ID, 1, &receiver.url, "secret1", &[WebhookEvent::StreamCreated]);
+        webhook.is_active = false;
+        let webhook_orm = WebhookOrmApp::create(&[webhook]);
+        let dispatcher = start_dispatcher(webhook_orm.clone());
+
+        dispatcher.do_send(FireWebhookEvent::for_user(1, WebhookEvent::StreamCreated, json!({ "id": 10 })));
+        sleep(WAIT).await;
+
+        assert_eq!(receiver.requests().len(), 0);
+        assert_eq!(webhook_orm.deliveries().len(), 0);
+    }
+    #[actix_web::test]
+    async fn test_fire_webhook_event_without_webhook_orm() {
+        let dispatcher = WebhookDispatcher::default().start();
+        // Until the storage is set, the events are ignored.
+        let result = dispatcher
+            .send(FireWebhookEvent::for_user(1, WebhookEvent::StreamCreated, json!({})))
+            .await;
+        assert!(result.is_ok());
+    }
+
+    // ** send_webhook_deliveries **
+
+    #[actix_web::test]
+    async fn test_send_webhook_deliveries_retry_and_fail() {
+        // The receiver responds with an error to each attempt.
+        let receiver = ReceiverTest::start(&[500, 503, 500]);
+        let webhook = Webhook::new(WEBHOOK_ID, 1, &receiver.url, "secret1", &[WebhookEvent::StreamDeleted]);
+        let webhook_orm = WebhookOrmApp::create(&[webhook]);
+        webhook_orm
+            .create_webhook_deliveries(Some(1), None, WebhookEvent::StreamDeleted, "{}")
+            .unwrap();
+        let config_hook = config_hook::tests::get_config();
+
+        // The first attempt.
+        let count = send_webhook_deliveries(&webhook_orm, &config_hook).unwrap();
+        assert_eq!(count, 1);
+        let delivery = webhook_orm.deliveries()[0].clone();
+        assert_eq!(delivery.status, DeliveryStatus::Pending);
+        assert_eq!(delivery.attempts, 1);
+        assert_eq!(delivery.response_status, Some(500));
+        let backoff = i64::try_from(config_hook.hook_backoff).unwrap();
+        assert!(delivery.next_attempt_at > Utc::now() + chrono::Duration::seconds(backoff - 5));
+
+        // The time of the next attempt has not come yet.
+        let count = send_webhook_deliveries(&webhook_orm, &config_hook).unwrap();
+        assert_eq!(count, 0);
+
+        for attempts in 2..=config_hook.hook_max_attempts {
+            // Move the time of the next attempt to the past.
+            webhook_orm.webhook_delivery_vec.lock().unwrap()[0].next_attempt_at = Utc::now();
+            let count = send_webhook_deliveries(&webhook_orm, &config_hook).unwrap();
+            assert_eq!(count, 1);
+            assert_eq!(webhook_orm.deliveries()[0].attempts, i32::try_from(attempts).unwrap());
+        }
+        let delivery = webhook_orm.deliveries()[0].clone();
+        assert_eq!(delivery.status, DeliveryStatus::Failed);
+        assert_eq!(delivery.error, Some("HTTP status 500".to_owned()));
+        assert_eq!(receiver.requests().len(), 3);
+
+        // The failed delivery is no longer sent.
+        webhook_orm.webhook_delivery_vec.lock().unwrap()[0].next_attempt_at = Utc::now();
+        let count = send_webhook_deliveries(&webhook_orm, &config_hook).unwrap();
+        assert_eq!(count, 0);
+    }
+}
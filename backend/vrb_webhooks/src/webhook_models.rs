@@ -0,0 +1,502 @@
+use std::{borrow::Cow, fmt};
+
+use chrono::{DateTime, Duration, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json;
+use utoipa::ToSchema;
+use vrb_common::{
+    err, serial_datetime, serial_datetime_option,
+    validators::{ValidationChecks, ValidationError, Validator},
+};
+use vrb_dbase::{enm_delivery_status::DeliveryStatus, schema};
+
+use crate::webhook_sender::{self, WebhookUrl};
+
+pub const MSG_URL_REQUIRED: &str = "url:required";
+pub const URL_MAX: u16 = 255;
+pub const MSG_URL_MAX_LENGTH: &str = "url:max_length";
+pub const URL_REGEX: &str = r"^https?://[^\s/?#]+[^\s]*$";
+pub const MSG_URL_REGEX: &str = "url:regex";
+pub const MSG_URL_HOST_NOT_ALLOWED: &str = "url:host_not_allowed";
+
+pub const EVENTS_MIN_AMOUNT: u8 = 1;
+pub const MSG_EVENTS_MIN_AMOUNT: &str = "events:min_amount";
+
+pub fn validate_url(value: &str) -> Result<(), ValidationError> {
+    ValidationChecks::required(value, MSG_URL_REQUIRED)?;
+    ValidationChecks::max_length(value, URL_MAX.into(), MSG_URL_MAX_LENGTH)?;
+    ValidationChecks::regexp(value, URL_REGEX, MSG_URL_REGEX)?;
+    // Loopback, private and link-local hosts are not allowed, so that the internal services cannot be reached.
+    let is_public_host = WebhookUrl::parse(value).map(|v| webhook_sender::is_public_host(&v.host)).unwrap_or(false);
+    if !is_public_host {
+        let mut err = ValidationError::new(MSG_URL_HOST_NOT_ALLOWED);
+        err.add_param(Cow::Borrowed("actualValue"), &value);
+        return Err(err);
+    }
+    Ok(())
+}
+
+pub fn validate_events(events: &[WebhookEvent]) -> Result<(), ValidationError> {
+    ValidationChecks::min_amount(events.len(), EVENTS_MIN_AMOUNT.into(), MSG_EVENTS_MIN_AMOUNT)?;
+    Ok(())
+}
+
+// **  Section: webhook events **
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, ToSchema)]
+pub enum WebhookEvent {
+    #[serde(rename = "stream.created")]
+    StreamCreated,
+    #[serde(rename = "stream.updated")]
+    StreamUpdated,
+    #[serde(rename = "stream.deleted")]
+    StreamDeleted,
+    #[serde(rename = "stream.state")]
+    StreamState,
+    #[serde(rename = "chat.message")]
+    ChatMessage, // (only on explicit subscription)
+}
+
+impl WebhookEvent {
+    pub fn all_values() -> Vec<WebhookEvent> {
+        vec![
+            WebhookEvent::StreamCreated,
+            WebhookEvent::StreamUpdated,
+            WebhookEvent::StreamDeleted,
+            WebhookEvent::StreamState,
+            WebhookEvent::ChatMessage,
+        ]
+    }
+    /// Convert the list of event names into a list of events (unknown names are skipped).
+    pub fn from_names(names: &[String]) -> Vec<WebhookEvent> {
+        names.iter().filter_map(|v| WebhookEvent::try_from(v.as_str()).ok()).collect()
+    }
+    /// Convert the list of events into a list of their names (without duplicates).
+    pub fn to_names(events: &[WebhookEvent]) -> Vec<String> {
+        let mut result: Vec<String> = Vec::new();
+        for event in events.iter() {
+            let name = event.to_string();
+            if !result.contains(&name) {
+                result.push(name);
+            }
+        }
+        result
+    }
+}
+
+impl fmt::Display for WebhookEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string(&self).unwrap().replace("\"", ""))
+    }
+}
+
+impl TryFrom<&str> for WebhookEvent {
+    type Error = ();
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        WebhookEvent::all_values().into_iter().find(|v| v.to_string() == value).ok_or(())
+    }
+}
+
+/// The body of the request sent to the webhook URL.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookPayload {
+    pub event: WebhookEvent,
+    #[serde(with = "serial_datetime")]
+    pub created_at: DateTime<Utc>,
+    // The data of the event (stream, chat message).
+    #[schema(value_type = Object)]
+    pub data: serde_json::Value,
+}
+
+// **  Section: table "webhooks" receiving data **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Queryable, Selectable)]
+#[diesel(table_name = schema::webhooks)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Webhook {
+    pub id: i32,
+    pub user_id: i32,
+    pub url: String,         // max_len=255
+    pub secret: String,      // max_len=255
+    pub events: Vec<String>, // min_amount=1
+    pub is_active: bool,     // default true
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Webhook {
+    pub fn new(id: i32, user_id: i32, url: &str, secret: &str, events: &[WebhookEvent]) -> Self {
+        let now = Utc::now();
+        Webhook {
+            id,
+            user_id,
+            url: url.to_owned(),
+            secret: secret.to_owned(),
+            events: WebhookEvent::to_names(events),
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDto {
+    pub id: i32,
+    pub user_id: i32,
+    pub url: String,
+    // The secret is returned only when the webhook is created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    pub events: Vec<WebhookEvent>,
+    pub is_active: bool,
+    #[serde(with = "serial_datetime")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "serial_datetime")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WebhookDto {
+    /// Create a "WebhookDto" together with the secret of the webhook.
+    pub fn with_secret(webhook: Webhook) -> Self {
+        let secret = webhook.secret.clone();
+        let mut webhook_dto = WebhookDto::from(webhook);
+        webhook_dto.secret = Some(secret);
+        webhook_dto
+    }
+}
+
+impl From<Webhook> for WebhookDto {
+    fn from(webhook: Webhook) -> Self {
+        WebhookDto {
+            id: webhook.id,
+            user_id: webhook.user_id,
+            url: webhook.url,
+            secret: None,
+            events: WebhookEvent::from_names(&webhook.events),
+            is_active: webhook.is_active,
+            created_at: webhook.created_at,
+            updated_at: webhook.updated_at,
+        }
+    }
+}
+
+// **  Section: table "webhooks" data creation **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWebhookDto {
+    pub url: String,
+    pub events: Vec<WebhookEvent>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_active: Option<bool>,
+}
+
+impl Validator for CreateWebhookDto {
+    // Check the model against the required conditions.
+    fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors: Vec<Option<ValidationError>> = vec![];
+
+        errors.push(validate_url(&self.url).err());
+        errors.push(validate_events(&self.events).err());
+
+        self.filter_errors(errors)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Insertable)]
+#[diesel(table_name = schema::webhooks)]
+pub struct CreateWebhook {
+    pub user_id: i32,
+    pub url: String,
+    pub secret: String,
+    pub events: Vec<String>,
+    pub is_active: Option<bool>, // default true
+}
+
+impl CreateWebhook {
+    pub fn convert(create_webhook_dto: CreateWebhookDto, user_id: i32, secret: &str) -> Self {
+        CreateWebhook {
+            user_id,
+            url: create_webhook_dto.url,
+            secret: secret.to_owned(),
+            events: WebhookEvent::to_names(&create_webhook_dto.events),
+            is_active: create_webhook_dto.is_active,
+        }
+    }
+}
+
+// **  Section: table "webhooks" data editing **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ModifyWebhookDto {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub events: Option<Vec<WebhookEvent>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_active: Option<bool>,
+}
+
+impl ModifyWebhookDto {
+    pub fn valid_names<'a>() -> Vec<&'a str> {
+        vec!["url", "events", "isActive"]
+    }
+}
+
+impl Validator for ModifyWebhookDto {
+    // Check the model against the required conditions.
+    fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors: Vec<Option<ValidationError>> = vec![];
+
+        if let Some(value) = &self.url {
+            errors.push(validate_url(value).err());
+        }
+        if let Some(value) = &self.events {
+            errors.push(validate_events(value).err());
+        }
+
+        let list_is_some = vec![self.url.is_some(), self.events.is_some(), self.is_active.is_some()];
+        let valid_names = ModifyWebhookDto::valid_names().join(",");
+        errors.push(ValidationChecks::no_fields_to_update(&list_is_some, &valid_names, err::MSG_NO_FIELDS_TO_UPDATE).err());
+
+        self.filter_errors(errors)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, AsChangeset)]
+#[diesel(table_name = schema::webhooks)]
+pub struct ModifyWebhook {
+    pub url: Option<String>,
+    pub events: Option<Vec<String>>,
+    pub is_active: Option<bool>,
+}
+
+impl From<ModifyWebhookDto> for ModifyWebhook {
+    fn from(modify_webhook_dto: ModifyWebhookDto) -> Self {
+        ModifyWebhook {
+            url: modify_webhook_dto.url,
+            events: modify_webhook_dto.events.map(|v| WebhookEvent::to_names(&v)),
+            is_active: modify_webhook_dto.is_active,
+        }
+    }
+}
+
+// **  Section: table "webhook_deliveries" receiving data **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, QueryableByName, Queryable, Selectable)]
+#[diesel(table_name = schema::webhook_deliveries)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct WebhookDelivery {
+    pub id: i32,
+    pub webhook_id: i32,
+    pub event: String,
+    pub payload: String,
+    pub status: DeliveryStatus,              // default Pending
+    pub attempts: i32,                       // default 0
+    pub next_attempt_at: DateTime<Utc>,      //
+    pub response_status: Option<i32>,        // Nullable
+    pub error: Option<String>,               // Nullable
+    pub delivered_at: Option<DateTime<Utc>>, // Nullable
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookDelivery {
+    pub fn new(id: i32, webhook_id: i32, event: WebhookEvent, payload: &str) -> Self {
+        let now = Utc::now();
+        WebhookDelivery {
+            id,
+            webhook_id,
+            event: event.to_string(),
+            payload: payload.to_owned(),
+            status: DeliveryStatus::Pending,
+            attempts: 0,
+            next_attempt_at: now,
+            response_status: None,
+            error: None,
+            delivered_at: None,
+            created_at: now,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDeliveryDto {
+    pub id: i32,
+    pub webhook_id: i32,
+    pub event: String,
+    pub payload: String,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    #[serde(with = "serial_datetime")]
+    pub next_attempt_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_status: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "serial_datetime_option")]
+    pub delivered_at: Option<DateTime<Utc>>,
+    #[serde(with = "serial_datetime")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<WebhookDelivery> for WebhookDeliveryDto {
+    fn from(webhook_delivery: WebhookDelivery) -> Self {
+        WebhookDeliveryDto {
+            id: webhook_delivery.id,
+            webhook_id: webhook_delivery.webhook_id,
+            event: webhook_delivery.event,
+            payload: webhook_delivery.payload,
+            status: webhook_delivery.status,
+            attempts: webhook_delivery.attempts,
+            next_attempt_at: webhook_delivery.next_attempt_at,
+            response_status: webhook_delivery.response_status,
+            error: webhook_delivery.error,
+            delivered_at: webhook_delivery.delivered_at,
+            created_at: webhook_delivery.created_at,
+        }
+    }
+}
+
+// **  Section: the delivery taken for sending **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, QueryableByName)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct WebhookTask {
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub id: i32, // The delivery ID.
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub webhook_id: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub url: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub secret: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub event: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub payload: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub attempts: i32, // The number of attempts made before.
+}
+
+// **  Section: table "webhook_deliveries" data editing **
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, AsChangeset)]
+#[diesel(table_name = schema::webhook_deliveries)]
+#[diesel(treat_none_as_null = true)]
+pub struct ModifyWebhookDelivery {
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub response_status: Option<i32>,
+    pub error: Option<String>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl ModifyWebhookDelivery {
+    /// Get the result of the attempt to send the delivery.
+    /// "result": Ok(status) - the HTTP status of the response, Err(error) - the error of the request.
+    /// If the response status is not 2xx, then the next attempt is made after "backoff * 2^(attempts - 1)" seconds.
+    /// After "max_attempts" unsuccessful attempts, the delivery is marked as "failed".
+    #[rustfmt::skip]
+    pub fn from_attempt(
+        attempts: i32, result: Result<u16, String>, max_attempts: u32, backoff: u64, now: DateTime<Utc>,
+    ) -> Self {
+        let attempts = attempts + 1;
+        let response_status: Option<i32> = result.as_ref().ok().map(|v| i32::from(*v));
+        if let Ok(200..=299) = result {
+            return ModifyWebhookDelivery {
+                status: DeliveryStatus::Delivered,
+                attempts,
+                next_attempt_at: now,
+                response_status,
+                error: None,
+                delivered_at: Some(now),
+            };
+        }
+        let error = match result {
+            Ok(status) => format!("HTTP status {}", status),
+            Err(err) => err,
+        };
+        let is_failed = i64::from(attempts) >= i64::from(max_attempts);
+        // The delay doubles with each attempt (the exponent is limited to avoid overflow).
+        let exponent = u32::try_from(attempts - 1).unwrap_or(0).min(20);
+        let delay = i64::try_from(backoff.saturating_mul(1 << exponent)).unwrap_or(i64::MAX);
+        ModifyWebhookDelivery {
+            status: if is_failed { DeliveryStatus::Failed } else { DeliveryStatus::Pending },
+            attempts,
+            next_attempt_at: if is_failed { now } else { now + Duration::seconds(delay) },
+            response_status,
+            error: Some(error),
+            delivered_at: None,
+        }
+    }
+}
+
+// ** **
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    // ** WebhookEvent **
+
+    #[test]
+    fn test_webhook_event_names() {
+        let names: Vec<String> = WebhookEvent::all_values().iter().map(|v| v.to_string()).collect();
+        #[rustfmt::skip]
+        assert_eq!(names, vec!["stream.created", "stream.updated", "stream.deleted", "stream.state", "chat.message"]);
+        let events = vec![WebhookEvent::ChatMessage, WebhookEvent::StreamState, WebhookEvent::ChatMessage];
+        let names = WebhookEvent::to_names(&events);
+        assert_eq!(names, vec!["chat.message", "stream.state"]);
+        let names2 = vec!["stream.state".to_owned(), "unknown".to_owned()];
+        assert_eq!(WebhookEvent::from_names(&names2), vec![WebhookEvent::StreamState]);
+    }
+
+    // ** ModifyWebhookDelivery **
+
+    #[test]
+    fn test_modify_webhook_delivery_from_attempt_delivered() {
+        let now = Utc::now();
+        let modify = ModifyWebhookDelivery::from_attempt(0, Ok(204), 3, 30, now);
+        assert_eq!(modify.status, DeliveryStatus::Delivered);
+        assert_eq!(modify.attempts, 1);
+        assert_eq!(modify.response_status, Some(204));
+        assert_eq!(modify.error, None);
+        assert_eq!(modify.delivered_at, Some(now));
+    }
+    #[test]
+    fn test_modify_webhook_delivery_from_attempt_retry_with_backoff() {
+        let now = Utc::now();
+        let modify = ModifyWebhookDelivery::from_attempt(0, Ok(500), 3, 30, now);
+        assert_eq!(modify.status, DeliveryStatus::Pending);
+        assert_eq!(modify.attempts, 1);
+        assert_eq!(modify.next_attempt_at, now + Duration::seconds(30));
+        assert_eq!(modify.response_status, Some(500));
+        assert_eq!(modify.error, Some("HTTP status 500".to_owned()));
+        assert_eq!(modify.delivered_at, None);
+
+        let modify = ModifyWebhookDelivery::from_attempt(1, Err("connection refused".to_owned()), 3, 30, now);
+        assert_eq!(modify.status, DeliveryStatus::Pending);
+        assert_eq!(modify.attempts, 2);
+        assert_eq!(modify.next_attempt_at, now + Duration::seconds(60));
+        assert_eq!(modify.response_status, None);
+        assert_eq!(modify.error, Some("connection refused".to_owned()));
+    }
+    #[test]
+    fn test_modify_webhook_delivery_from_attempt_failed() {
+        let now = Utc::now();
+        let modify = ModifyWebhookDelivery::from_attempt(2, Ok(404), 3, 30, now);
+        assert_eq!(modify.status, DeliveryStatus::Failed);
+        assert_eq!(modify.attempts, 3);
+        assert_eq!(modify.next_attempt_at, now);
+        assert_eq!(modify.response_status, Some(404));
+    }
+}
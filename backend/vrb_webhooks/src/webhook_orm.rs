@@ -0,0 +1,537 @@
+use vrb_dbase::dbase::DbPool;
+
+use super::webhook_models::{CreateWebhook, ModifyWebhook, ModifyWebhookDelivery, Webhook, WebhookDelivery, WebhookEvent, WebhookTask};
+
+pub trait WebhookOrm {
+    /// Get a list of entities (webhooks) of the user.
+    fn get_webhooks(&self, user_id: i32) -> Result<Vec<Webhook>, String>;
+
+    /// Find an entity (webhook) by ID. If the user is specified, then only among the user's webhooks.
+    fn find_webhook_by_id(&self, id: i32, opt_user_id: Option<i32>) -> Result<Option<Webhook>, String>;
+
+    /// Add a new entity (webhook).
+    fn create_webhook(&self, create_webhook: CreateWebhook) -> Result<Webhook, String>;
+
+    /// Modify an entity (webhook).
+    #[rustfmt::skip]
+    fn modify_webhook(&self, id: i32, opt_user_id: Option<i32>, modify_webhook: ModifyWebhook) -> Result<Option<Webhook>, String>;
+
+    /// Delete an entity (webhook) together with its deliveries.
+    fn delete_webhook(&self, id: i32, opt_user_id: Option<i32>) -> Result<Option<Webhook>, String>;
+
+    /// Get a list of the latest deliveries of the webhook (new ones first).
+    fn get_webhook_deliveries(&self, webhook_id: i32, limit: i64) -> Result<Vec<WebhookDelivery>, String>;
+
+    /// Add the deliveries of the event to all active webhooks of the user subscribed to it.
+    /// If the user is not specified, then the owner of the stream "opt_stream_id" is used.
+    #[rustfmt::skip]
+    fn create_webhook_deliveries(
+        &self, opt_user_id: Option<i32>, opt_stream_id: Option<i32>, event: WebhookEvent, payload: &str,
+    ) -> Result<Vec<WebhookDelivery>, String>;
+
+    /// Take the "pending" deliveries whose time has come to be sent.
+    /// Their next attempt is postponed for "lock_secs" seconds, so that other processes do not send them at the same time.
+    fn claim_webhook_deliveries(&self, limit: i32, lock_secs: i32) -> Result<Vec<WebhookTask>, String>;
+
+    /// Modify an entity (webhook_delivery) with the result of the attempt to send it.
+    #[rustfmt::skip]
+    fn modify_webhook_delivery(&self, id: i32, modify_webhook_delivery: ModifyWebhookDelivery) -> Result<Option<WebhookDelivery>, String>;
+}
+
+#[cfg(not(all(test, feature = "mockdata")))]
+pub fn get_webhook_orm_app(pool: DbPool) -> impls::WebhookOrmApp {
+    impls::WebhookOrmApp::new(pool)
+}
+#[cfg(all(test, feature = "mockdata"))]
+pub fn get_webhook_orm_app(_: DbPool) -> tests::WebhookOrmApp {
+    tests::WebhookOrmApp::new()
+}
+
+#[cfg(not(all(test, feature = "mockdata")))]
+pub mod impls {
+    use std::time::Instant as tm;
+
+    use diesel::{self, prelude::*, sql_types};
+    use log::{Level::Info, info, log_enabled};
+    use vrb_dbase::dbase;
+    use vrb_dbase::schema::{self, webhook_deliveries::dsl as deliveries_dsl, webhooks::dsl as webhooks_dsl};
+
+    use super::*;
+
+    pub const CONN_POOL: &str = "ConnectionPool";
+
+    #[derive(Debug, Clone)]
+    pub struct WebhookOrmApp {
+        pub pool: dbase::DbPool,
+    }
+
+    impl WebhookOrmApp {
+        pub fn new(pool: dbase::DbPool) -> Self {
+            WebhookOrmApp { pool }
+        }
+        pub fn get_conn(&self) -> Result<dbase::DbPooledConnection, String> {
+            (&self.pool).get().map_err(|e| format!("{}: {}", CONN_POOL, e.to_string()))
+        }
+    }
+
+    impl WebhookOrm for WebhookOrmApp {
+        /// Get a list of entities (webhooks) of the user.
+        fn get_webhooks(&self, user_id: i32) -> Result<Vec<Webhook>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            // Run query using Diesel to get a list of entries (webhooks).
+            let webhook_list: Vec<Webhook> = schema::webhooks::table
+                .filter(webhooks_dsl::user_id.eq(user_id))
+                .order_by(webhooks_dsl::id.asc())
+                .select(Webhook::as_select())
+                .load(&mut conn)
+                .map_err(|e| format!("get_webhooks: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("get_webhooks() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(webhook_list)
+        }
+
+        /// Find an entity (webhook) by ID. If the user is specified, then only among the user's webhooks.
+        fn find_webhook_by_id(&self, id: i32, opt_user_id: Option<i32>) -> Result<Option<Webhook>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            // Prepare an SQL query to get the webhook.
+            let mut query = schema::webhooks::table.into_boxed();
+            // Add a filter by unique webhook identifier.
+            query = query.filter(webhooks_dsl::id.eq(id));
+            if let Some(user_id) = opt_user_id {
+                // Add an additional filter by user ID.
+                query = query.filter(webhooks_dsl::user_id.eq(user_id));
+            }
+            // Run query using Diesel to get the entry (webhook).
+            let opt_webhook = query
+                .select(Webhook::as_select())
+                .first::<Webhook>(&mut conn)
+                .optional()
+                .map_err(|e| format!("find_webhook_by_id: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("find_webhook_by_id() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(opt_webhook)
+        }
+
+        /// Add a new entity (webhook).
+        fn create_webhook(&self, create_webhook: CreateWebhook) -> Result<Webhook, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            // Run query using Diesel to add a new entry (webhook).
+            let webhook = diesel::insert_into(schema::webhooks::table)
+                .values(create_webhook)
+                .returning(Webhook::as_returning())
+                .get_result(&mut conn)
+                .map_err(|e| format!("create_webhook: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("create_webhook() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(webhook)
+        }
+
+        /// Modify an entity (webhook).
+        #[rustfmt::skip]
+        fn modify_webhook(&self, id: i32, opt_user_id: Option<i32>, modify_webhook: ModifyWebhook) -> Result<Option<Webhook>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            // Prepare a SQL-request to update the entry (webhook).
+            let mut query = diesel::update(schema::webhooks::table).into_boxed();
+            // Add a filter by unique webhook identifier.
+            query = query.filter(webhooks_dsl::id.eq(id));
+            if let Some(user_id) = opt_user_id {
+                // Add an additional filter by user ID.
+                query = query.filter(webhooks_dsl::user_id.eq(user_id));
+            }
+            // Run query using Diesel to update the entry (webhook).
+            let opt_webhook = query
+                .set(&modify_webhook)
+                .returning(Webhook::as_returning())
+                .get_result(&mut conn)
+                .optional()
+                .map_err(|e| format!("modify_webhook: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("modify_webhook() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(opt_webhook)
+        }
+
+        /// Delete an entity (webhook) together with its deliveries.
+        fn delete_webhook(&self, id: i32, opt_user_id: Option<i32>) -> Result<Option<Webhook>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            // Prepare a SQL-request to delete the entry (webhook). Its deliveries are deleted by the cascade.
+            let mut query = diesel::delete(schema::webhooks::table).into_boxed();
+            // Add a filter by unique webhook identifier.
+            query = query.filter(webhooks_dsl::id.eq(id));
+            if let Some(user_id) = opt_user_id {
+                // Add an additional filter by user ID.
+                query = query.filter(webhooks_dsl::user_id.eq(user_id));
+            }
+            // Run query using Diesel to delete the entry (webhook).
+            let opt_webhook = query
+                .returning(Webhook::as_returning())
+                .get_result(&mut conn)
+                .optional()
+                .map_err(|e| format!("delete_webhook: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("delete_webhook() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(opt_webhook)
+        }
+
+        /// Get a list of the latest deliveries of the webhook (new ones first).
+        fn get_webhook_deliveries(&self, webhook_id: i32, limit: i64) -> Result<Vec<WebhookDelivery>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            // Run query using Diesel to get a list of entries (webhook_deliveries).
+            let webhook_delivery_list: Vec<WebhookDelivery> = schema::webhook_deliveries::table
+                .filter(deliveries_dsl::webhook_id.eq(webhook_id))
+                .order_by((deliveries_dsl::created_at.desc(), deliveries_dsl::id.desc()))
+                .limit(limit)
+                .select(WebhookDelivery::as_select())
+                .load(&mut conn)
+                .map_err(|e| format!("get_webhook_deliveries: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("get_webhook_deliveries() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(webhook_delivery_list)
+        }
+
+        /// Add the deliveries of the event to all active webhooks of the user subscribed to it.
+        /// If the user is not specified, then the owner of the stream "opt_stream_id" is used.
+        #[rustfmt::skip]
+        fn create_webhook_deliveries(
+            &self, opt_user_id: Option<i32>, opt_stream_id: Option<i32>, event: WebhookEvent, payload: &str,
+        ) -> Result<Vec<WebhookDelivery>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            let query = diesel::sql_query("select * from create_webhook_deliveries($1,$2,$3,$4);")
+                .bind::<sql_types::Nullable<sql_types::Integer>, _>(opt_user_id) // $1
+                .bind::<sql_types::Nullable<sql_types::Integer>, _>(opt_stream_id) // $2
+                .bind::<sql_types::Text, _>(event.to_string()) // $3
+                .bind::<sql_types::Text, _>(payload); // $4
+
+            // Run a query using Diesel to add new entries and return them.
+            let webhook_delivery_list: Vec<WebhookDelivery> = query
+                .load(&mut conn)
+                .map_err(|e| format!("create_webhook_deliveries: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("create_webhook_deliveries() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(webhook_delivery_list)
+        }
+
+        /// Take the "pending" deliveries whose time has come to be sent.
+        /// Their next attempt is postponed for "lock_secs" seconds, so that other processes do not send them at the same time.
+        fn claim_webhook_deliveries(&self, limit: i32, lock_secs: i32) -> Result<Vec<WebhookTask>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            let query = diesel::sql_query("select * from claim_webhook_deliveries($1,$2);")
+                .bind::<sql_types::Integer, _>(limit) // $1
+                .bind::<sql_types::Integer, _>(lock_secs); // $2
+
+            // Run a query using Diesel to get a list of the taken deliveries.
+            let webhook_task_list: Vec<WebhookTask> = query
+                .load(&mut conn)
+                .map_err(|e| format!("claim_webhook_deliveries: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("claim_webhook_deliveries() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(webhook_task_list)
+        }
+
+        /// Modify an entity (webhook_delivery) with the result of the attempt to send it.
+        #[rustfmt::skip]
+        fn modify_webhook_delivery(&self, id: i32, modify_webhook_delivery: ModifyWebhookDelivery) -> Result<Option<WebhookDelivery>, String> {
+            let timer = if log_enabled!(Info) { Some(tm::now()) } else { None };
+
+            // Get a connection from the P2D2 pool.
+            let mut conn = self.get_conn()?;
+
+            // Run query using Diesel to update the entry (webhook_delivery).
+            let opt_webhook_delivery = diesel::update(schema::webhook_deliveries::table)
+                .filter(deliveries_dsl::id.eq(id))
+                .set(&modify_webhook_delivery)
+                .returning(WebhookDelivery::as_returning())
+                .get_result(&mut conn)
+                .optional()
+                .map_err(|e| format!("modify_webhook_delivery: {}", e.to_string()))?;
+
+            if let Some(timer) = timer {
+                info!("modify_webhook_delivery() time: {}", format!("{:.2?}", timer.elapsed()));
+            }
+            Ok(opt_webhook_delivery)
+        }
+    }
+}
+
+#[cfg(any(test, feature = "mockdata"))]
+pub mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    use actix_web::web;
+    use chrono::{Duration, Utc};
+    use vrb_dbase::enm_delivery_status::DeliveryStatus;
+
+    use super::*;
+
+    pub const WEBHOOK_ID: i32 = 1700;
+    pub const WEBHOOK_DELIVERY_ID: i32 = 1800;
+
+    #[derive(Debug, Clone)]
+    pub struct WebhookOrmApp {
+        pub webhook_vec: Vec<Webhook>,
+        // The deliveries are shared, so that the results of sending them can be checked in the tests.
+        pub webhook_delivery_vec: Arc<Mutex<Vec<WebhookDelivery>>>,
+        // Map<stream_id, user_id> - the owners of the streams.
+        pub stream_owner_map: HashMap<i32, i32>,
+    }
+
+    impl WebhookOrmApp {
+        /// Create a new instance.
+        pub fn new() -> Self {
+            WebhookOrmApp {
+                webhook_vec: Vec::new(),
+                webhook_delivery_vec: Arc::new(Mutex::new(Vec::new())),
+                stream_owner_map: HashMap::new(),
+            }
+        }
+        /// Create a new instance with the specified `webhook` list.
+        pub fn create(webhook_vec: &[Webhook]) -> Self {
+            let mut webhook_orm_app = Self::new();
+            for (idx, webhook) in webhook_vec.iter().enumerate() {
+                let mut webhook2 = webhook.clone();
+                let delta: i32 = idx.try_into().unwrap();
+                webhook2.id = WEBHOOK_ID + delta;
+                webhook_orm_app.webhook_vec.push(webhook2);
+            }
+            webhook_orm_app
+        }
+        /// Create a new instance with the specified `webhook` list and `webhook_delivery` list.
+        pub fn create_with_deliveries(webhook_vec: &[Webhook], webhook_delivery_vec: &[WebhookDelivery]) -> Self {
+            let webhook_orm_app = Self::create(webhook_vec);
+            {
+                let mut delivery_list = webhook_orm_app.webhook_delivery_vec.lock().unwrap();
+                for (idx, webhook_delivery) in webhook_delivery_vec.iter().enumerate() {
+                    let mut webhook_delivery2 = webhook_delivery.clone();
+                    let delta: i32 = idx.try_into().unwrap();
+                    webhook_delivery2.id = WEBHOOK_DELIVERY_ID + delta;
+                    delivery_list.push(webhook_delivery2);
+                }
+            }
+            webhook_orm_app
+        }
+        /// Add the owner of the stream (used to find the webhooks of chat events).
+        pub fn with_stream_owner(mut self, stream_id: i32, user_id: i32) -> Self {
+            self.stream_owner_map.insert(stream_id, user_id);
+            self
+        }
+        /// Get a copy of the list of deliveries.
+        pub fn deliveries(&self) -> Vec<WebhookDelivery> {
+            self.webhook_delivery_vec.lock().unwrap().clone()
+        }
+    }
+
+    impl WebhookOrm for WebhookOrmApp {
+        /// Get a list of entities (webhooks) of the user.
+        fn get_webhooks(&self, user_id: i32) -> Result<Vec<Webhook>, String> {
+            let result: Vec<Webhook> = self.webhook_vec.iter().filter(|v| v.user_id == user_id).map(|v| v.clone()).collect();
+            Ok(result)
+        }
+
+        /// Find an entity (webhook) by ID. If the user is specified, then only among the user's webhooks.
+        fn find_webhook_by_id(&self, id: i32, opt_user_id: Option<i32>) -> Result<Option<Webhook>, String> {
+            let opt_webhook = self
+                .webhook_vec
+                .iter()
+                .find(|v| v.id == id && opt_user_id.map(|user_id| v.user_id == user_id).unwrap_or(true))
+                .map(|v| v.clone());
+            Ok(opt_webhook)
+        }
+
+        /// Add a new entity (webhook).
+        fn create_webhook(&self, create_webhook: CreateWebhook) -> Result<Webhook, String> {
+            let id = self.webhook_vec.last().map(|v| v.id + 1).unwrap_or(WEBHOOK_ID);
+            let events = WebhookEvent::from_names(&create_webhook.events);
+            let mut webhook = Webhook::new(id, create_webhook.user_id, &create_webhook.url, &create_webhook.secret, &events);
+            webhook.is_active = create_webhook.is_active.unwrap_or(true);
+            Ok(webhook)
+        }
+
+        /// Modify an entity (webhook).
+        #[rustfmt::skip]
+        fn modify_webhook(&self, id: i32, opt_user_id: Option<i32>, modify_webhook: ModifyWebhook) -> Result<Option<Webhook>, String> {
+            let opt_webhook = self.find_webhook_by_id(id, opt_user_id)?.map(|mut webhook| {
+                if let Some(url) = modify_webhook.url {
+                    webhook.url = url;
+                }
+                if let Some(events) = modify_webhook.events {
+                    webhook.events = events;
+                }
+                if let Some(is_active) = modify_webhook.is_active {
+                    webhook.is_active = is_active;
+                }
+                webhook.updated_at = Utc::now();
+                webhook
+            });
+            Ok(opt_webhook)
+        }
+
+        /// Delete an entity (webhook) together with its deliveries.
+        fn delete_webhook(&self, id: i32, opt_user_id: Option<i32>) -> Result<Option<Webhook>, String> {
+            self.find_webhook_by_id(id, opt_user_id)
+        }
+
+        /// Get a list of the latest deliveries of the webhook (new ones first).
+        fn get_webhook_deliveries(&self, webhook_id: i32, limit: i64) -> Result<Vec<WebhookDelivery>, String> {
+            let mut result: Vec<WebhookDelivery> = self
+                .webhook_delivery_vec
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|v| v.webhook_id == webhook_id)
+                .map(|v| v.clone())
+                .collect();
+            result.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+            result.truncate(usize::try_from(limit).unwrap_or(0));
+            Ok(result)
+        }
+
+        /// Add the deliveries of the event to all active webhooks of the user subscribed to it.
+        /// If the user is not specified, then the owner of the stream "opt_stream_id" is used.
+        #[rustfmt::skip]
+        fn create_webhook_deliveries(
+            &self, opt_user_id: Option<i32>, opt_stream_id: Option<i32>, event: WebhookEvent, payload: &str,
+        ) -> Result<Vec<WebhookDelivery>, String> {
+            let opt_owner_id = opt_user_id.or_else(|| opt_stream_id.and_then(|v| self.stream_owner_map.get(&v).cloned()));
+            let Some(owner_id) = opt_owner_id else {
+                return Ok(vec![]);
+            };
+            let event_name = event.to_string();
+            let mut delivery_list = self.webhook_delivery_vec.lock().unwrap();
+            let mut result: Vec<WebhookDelivery> = Vec::new();
+            for webhook in self.webhook_vec.iter() {
+                if webhook.user_id != owner_id || !webhook.is_active || !webhook.events.contains(&event_name) {
+                    continue;
+                }
+                let id = delivery_list.last().map(|v| v.id + 1).unwrap_or(WEBHOOK_DELIVERY_ID);
+                let webhook_delivery = WebhookDelivery::new(id, webhook.id, event, payload);
+                delivery_list.push(webhook_delivery.clone());
+                result.push(webhook_delivery);
+            }
+            Ok(result)
+        }
+
+        /// Take the "pending" deliveries whose time has come to be sent.
+        /// Their next attempt is postponed for "lock_secs" seconds, so that other processes do not send them at the same time.
+        fn claim_webhook_deliveries(&self, limit: i32, lock_secs: i32) -> Result<Vec<WebhookTask>, String> {
+            let now = Utc::now();
+            let mut delivery_list = self.webhook_delivery_vec.lock().unwrap();
+            let mut result: Vec<WebhookTask> = Vec::new();
+            for delivery in delivery_list.iter_mut() {
+                if result.len() >= usize::try_from(limit).unwrap_or(0) {
+                    break;
+                }
+                if delivery.status != DeliveryStatus::Pending || delivery.next_attempt_at > now {
+                    continue;
+                }
+                let Some(webhook) = self.webhook_vec.iter().find(|v| v.id == delivery.webhook_id) else {
+                    continue;
+                };
+                delivery.next_attempt_at = now + Duration::seconds(lock_secs.into());
+                result.push(WebhookTask {
+                    id: delivery.id,
+                    webhook_id: webhook.id,
+                    url: webhook.url.clone(),
+                    secret: webhook.secret.clone(),
+                    event: delivery.event.clone(),
+                    payload: delivery.payload.clone(),
+                    attempts: delivery.attempts,
+                });
+            }
+            Ok(result)
+        }
+
+        /// Modify an entity (webhook_delivery) with the result of the attempt to send it.
+        #[rustfmt::skip]
+        fn modify_webhook_delivery(&self, id: i32, modify_webhook_delivery: ModifyWebhookDelivery) -> Result<Option<WebhookDelivery>, String> {
+            let mut delivery_list = self.webhook_delivery_vec.lock().unwrap();
+            let opt_webhook_delivery = delivery_list.iter_mut().find(|v| v.id == id).map(|delivery| {
+                delivery.status = modify_webhook_delivery.status;
+                delivery.attempts = modify_webhook_delivery.attempts;
+                delivery.next_attempt_at = modify_webhook_delivery.next_attempt_at;
+                delivery.response_status = modify_webhook_delivery.response_status;
+                delivery.error = modify_webhook_delivery.error;
+                delivery.delivered_at = modify_webhook_delivery.delivered_at;
+                delivery.clone()
+            });
+            Ok(opt_webhook_delivery)
+        }
+    }
+
+    pub struct WebhookOrmTest {}
+
+    impl WebhookOrmTest {
+        pub fn webhooks(user_ids: &[i32], events: &[WebhookEvent]) -> Vec<Webhook> {
+            let mut webhook_vec: Vec<Webhook> = Vec::new();
+            for (index, user_id) in user_ids.iter().enumerate() {
+                let idx: i32 = index.try_into().unwrap();
+                let url = format!("http://127.0.0.1:9/hook_{}_{}", index, user_id);
+                let secret = format!("secret_{}_{}", index, user_id);
+                webhook_vec.push(Webhook::new(WEBHOOK_ID + idx, *user_id, &url, &secret, events));
+            }
+            webhook_vec
+        }
+        pub fn cfg_webhook_orm(data_w: Vec<Webhook>) -> impl FnOnce(&mut web::ServiceConfig) {
+            move |config: &mut web::ServiceConfig| {
+                let data_webhook_orm = web::Data::new(WebhookOrmApp::create(&data_w));
+                config.app_data(web::Data::clone(&data_webhook_orm));
+            }
+        }
+        #[rustfmt::skip]
+        pub fn cfg_webhook_delivery_orm(data_w: Vec<Webhook>, data_wd: Vec<WebhookDelivery>) -> impl FnOnce(&mut web::ServiceConfig) {
+            move |config: &mut web::ServiceConfig| {
+                let data_webhook_orm = web::Data::new(WebhookOrmApp::create_with_deliveries(&data_w, &data_wd));
+                config.app_data(web::Data::clone(&data_webhook_orm));
+            }
+        }
+    }
+}
@@ -0,0 +1,489 @@
+use std::{
+    io::{Read, Write},
+    net::{IpAddr, Ipv4Addr, SocketAddr, TcpStream, ToSocketAddrs},
+    time::{Duration, Instant},
+};
+
+use openssl::{
+    hash::MessageDigest,
+    pkey::PKey,
+    sign::Signer,
+    ssl::{SslConnector, SslMethod},
+};
+use rand::Rng;
+
+// The length of the secret of the webhook (in bytes).
+pub const SECRET_LENGTH: usize = 32;
+// Headers of the request sent to the webhook URL.
+pub const HEADER_EVENT: &str = "X-Verbena-Event";
+pub const HEADER_DELIVERY: &str = "X-Verbena-Delivery";
+pub const HEADER_TIMESTAMP: &str = "X-Verbena-Timestamp";
+pub const HEADER_SIGNATURE: &str = "X-Verbena-Signature";
+pub const SIGNATURE_PREFIX: &str = "sha256=";
+pub const USER_AGENT: &str = "verbena-webhook";
+// The maximum size of the status line and headers of the response that are read.
+const RESPONSE_HEAD_MAX: usize = 8192;
+
+/// Generate a new random secret of the webhook (in hex encoding).
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; SECRET_LENGTH];
+    rand::rng().fill(&mut bytes[..]);
+    to_hex(&bytes)
+}
+
+/// Encode bytes into a hex string (lowercase).
+pub fn to_hex(data: &[u8]) -> String {
+    data.iter().map(|v| format!("{:02x}", v)).collect()
+}
+
+/// Calculate HMAC-SHA256 of the data with the specified key (in hex encoding).
+pub fn hmac_sha256_hex(key: &[u8], data: &[u8]) -> Result<String, String> {
+    let pkey = PKey::hmac(key).map_err(|e| e.to_string())?;
+    let mut signer = Signer::new(MessageDigest::sha256(), &pkey).map_err(|e| e.to_string())?;
+    signer.update(data).map_err(|e| e.to_string())?;
+    let hmac = signer.sign_to_vec().map_err(|e| e.to_string())?;
+    Ok(to_hex(&hmac))
+}
+
+/// Get the signature of the request: "sha256=" + hex(HMAC-SHA256(secret, "{timestamp}.{body}")).
+/// The receiver calculates the same value and compares it with the "X-Verbena-Signature" header.
+pub fn sign_payload(secret: &str, timestamp: i64, body: &str) -> Result<String, String> {
+    let data = format!("{}.{}", timestamp, body);
+    let hmac = hmac_sha256_hex(secret.as_bytes(), data.as_bytes())?;
+    Ok(format!("{}{}", SIGNATURE_PREFIX, hmac))
+}
+
+/// Check that the IPv4 address is public (not loopback, private, link-local, reserved, etc.).
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(a == 0 // "This network" 0.0.0.0/8
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        || (a == 100 && (b & 0xc0) == 64) // Shared address space 100.64.0.0/10
+        || (a == 192 && b == 0 && c == 0) // IETF protocol assignments 192.0.0.0/24
+        || (a == 198 && (b & 0xfe) == 18) // Benchmarking 198.18.0.0/15
+        || a >= 240) // Reserved 240.0.0.0/4
+}
+
+/// Check that the IP address is public.
+/// Requests to loopback, private and link-local addresses would reach the internal services of the server.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip4) => is_public_ipv4(ip4),
+        IpAddr::V6(ip6) => {
+            let segments = ip6.segments();
+            // IPv4-mapped (::ffff:0:0/96) and NAT64 (64:ff9b::/96) addresses lead to the IPv4 address.
+            if let Some(ip4) = ip6.to_ipv4_mapped() {
+                return is_public_ipv4(ip4);
+            }
+            if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+                let [.., a, b, c, d] = ip6.octets();
+                return is_public_ipv4(Ipv4Addr::new(a, b, c, d));
+            }
+            !(ip6.is_unspecified()
+                || ip6.is_loopback()
+                || ip6.is_multicast()
+                || segments[..6] == [0; 6] // IPv4-compatible ::/96
+                || (segments[0] & 0xfe00) == 0xfc00 // Unique local fc00::/7
+                || (segments[0] & 0xffc0) == 0xfe80 // Link-local fe80::/10
+                || (segments[0] & 0xffc0) == 0xfec0 // Site-local fec0::/10
+                || (segments[0] == 0x2001 && segments[1] == 0x0db8)) // Documentation 2001:db8::/32
+        }
+    }
+}
+
+/// Check that the host of the URL is not a local one (an IP address must be public, "localhost" names are not allowed).
+/// The names are checked again by their addresses when sending (see `post_json`).
+pub fn is_public_host(host: &str) -> bool {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        return is_public_ip(ip);
+    }
+    let name = host.trim_end_matches('.').to_lowercase();
+    !(name == "localhost" || name.ends_with(".localhost"))
+}
+
+// ** The parts of the webhook URL. **
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookUrl {
+    pub is_https: bool,
+    pub host: String,
+    pub port: u16,
+    pub path: String,
+}
+
+impl WebhookUrl {
+    /// Parse the URL ("http://host[:port][/path]" or "https://host[:port][/path]").
+    pub fn parse(url: &str) -> Result<Self, String> {
+        let (is_https, rest) = if let Some(rest) = url.strip_prefix("https://") {
+            (true, rest)
+        } else if let Some(rest) = url.strip_prefix("http://") {
+            (false, rest)
+        } else {
+            return Err(format!("unsupported URL scheme: {}", url));
+        };
+        let idx = rest.find(['/', '?']).unwrap_or(rest.len());
+        let (authority, path) = rest.split_at(idx);
+        #[rustfmt::skip]
+        let path = if path.starts_with('/') { path.to_owned() } else { format!("/{}", path) };
+
+        let default_port: u16 = if is_https { 443 } else { 80 };
+        // The host can be an IPv6 address in square brackets ("[::1]:8080").
+        let port_idx = match authority.rfind(']') {
+            Some(idx) => authority[idx..].find(':').map(|v| v + idx),
+            None => authority.rfind(':'),
+        };
+        let (host, port) = match port_idx {
+            Some(idx) => {
+                let port = authority[idx + 1..].parse::<u16>().map_err(|e| format!("invalid URL port: {}", e))?;
+                (&authority[..idx], port)
+            }
+            None => (authority, default_port),
+        };
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        if host.is_empty() {
+            return Err(format!("invalid URL host: {}", url));
+        }
+        Ok(WebhookUrl {
+            is_https,
+            host: host.to_owned(),
+            port,
+            path,
+        })
+    }
+    /// Get the value of the "Host" header.
+    fn host_header(&self) -> String {
+        #[rustfmt::skip]
+        let host = if self.host.contains(':') { format!("[{}]", &self.host) } else { self.host.clone() };
+        let default_port: u16 = if self.is_https { 443 } else { 80 };
+        #[rustfmt::skip]
+        let result = if self.port == default_port { host } else { format!("{}:{}", host, self.port) };
+        result
+    }
+}
+
+/// Send a POST request with a JSON body to the URL (HTTP/1.1, the connection is closed after the response).
+/// The timeout applies to the whole exchange: the connection, the TLS handshake, writing the request and reading the response.
+/// Only public addresses are connected to, unless `allow_private` is set.
+/// Return the HTTP status of the response.
+#[rustfmt::skip]
+pub fn post_json(url: &str, headers: &[(&str, String)], body: &str, timeout: Duration, allow_private: bool) -> Result<u16, String> {
+    let webhook_url = WebhookUrl::parse(url)?;
+    let deadline = Instant::now() + timeout;
+
+    let mut request = format!(
+        "POST {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n",
+        &webhook_url.path,
+        webhook_url.host_header(),
+        USER_AGENT,
+        body.len()
+    );
+    for (name, value) in headers.iter() {
+        request.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    request.push_str("Connection: close\r\n\r\n");
+    request.push_str(body);
+
+    let addrs: Vec<SocketAddr> = (webhook_url.host.as_str(), webhook_url.port)
+        .to_socket_addrs()
+        .map_err(|e| format!("resolve {}: {}", &webhook_url.host, e))?
+        .collect();
+    // The resolved address is checked (and then connected to), so the name cannot be changed to a local address
+    // after the URL was checked (DNS rebinding).
+    let addr = match addrs.iter().find(|v| allow_private || is_public_ip(v.ip())) {
+        Some(addr) => *addr,
+        None if addrs.is_empty() => return Err(format!("resolve {}: no address", &webhook_url.host)),
+        None => return Err(format!("resolve {}: address not allowed: {}", &webhook_url.host, addrs[0].ip())),
+    };
+    let remaining = get_remaining(deadline).ok_or(format!("connect {}: timed out", addr))?;
+    let stream = TcpStream::connect_timeout(&addr, remaining).map_err(|e| format!("connect {}: {}", addr, e))?;
+    // The socket is shared with the stream, it is used to reduce the timeouts as the deadline approaches.
+    let socket = stream.try_clone().map_err(|e| e.to_string())?;
+
+    if webhook_url.is_https {
+        set_remaining_timeouts(&socket, deadline).map_err(|e| format!("tls {}: {}", &webhook_url.host, e))?;
+        let connector = SslConnector::builder(SslMethod::tls()).map_err(|e| e.to_string())?.build();
+        let mut ssl_stream = connector
+            .connect(&webhook_url.host, stream)
+            .map_err(|e| format!("tls {}: {}", &webhook_url.host, e))?;
+        exchange(&mut ssl_stream, request.as_bytes(), &socket, deadline)
+    } else {
+        let mut stream = stream;
+        exchange(&mut stream, request.as_bytes(), &socket, deadline)
+    }
+}
+
+/// Get the time remaining until the deadline (None if the deadline has passed).
+fn get_remaining(deadline: Instant) -> Option<Duration> {
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    #[rustfmt::skip]
+    let result = if remaining.is_zero() { None } else { Some(remaining) };
+    result
+}
+
+/// Set the read and write timeouts of the socket to the time remaining until the deadline.
+fn set_remaining_timeouts(socket: &TcpStream, deadline: Instant) -> Result<(), String> {
+    let remaining = get_remaining(deadline).ok_or("timed out".to_owned())?;
+    socket.set_read_timeout(Some(remaining)).map_err(|e| e.to_string())?;
+    socket.set_write_timeout(Some(remaining)).map_err(|e| e.to_string())
+}
+
+/// Write the request and read the status of the response (the rest of the response is not needed).
+/// Each write and read waits no longer than the time remaining until the deadline.
+fn exchange<S: Read + Write>(stream: &mut S, request: &[u8], socket: &TcpStream, deadline: Instant) -> Result<u16, String> {
+    set_remaining_timeouts(socket, deadline).map_err(|e| format!("write: {}", e))?;
+    stream.write_all(request).map_err(|e| format!("write: {}", e))?;
+    stream.flush().map_err(|e| format!("write: {}", e))?;
+
+    let mut head: Vec<u8> = Vec::new();
+    let mut buffer = [0u8; 1024];
+    while !head.windows(2).any(|v| v == b"\r\n") {
+        if head.len() > RESPONSE_HEAD_MAX {
+            return Err("read: the status line is too long".to_owned());
+        }
+        // The response can come in small parts, so the total time of reading is limited by the deadline.
+        set_remaining_timeouts(socket, deadline).map_err(|e| format!("read: {}", e))?;
+        let len = stream.read(&mut buffer).map_err(|e| format!("read: {}", e))?;
+        if len == 0 {
+            break;
+        }
+        head.extend_from_slice(&buffer[..len]);
+    }
+    parse_status_line(&String::from_utf8_lossy(&head))
+}
+
+/// Get the status from the status line of the response ("HTTP/1.1 200 OK").
+pub fn parse_status_line(head: &str) -> Result<u16, String> {
+    let line = head.lines().next().unwrap_or("");
+    let mut parts = line.split_whitespace();
+    let (version, status) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    if !version.starts_with("HTTP/") {
+        return Err(format!("invalid response: \"{}\"", line));
+    }
+    status.parse::<u16>().map_err(|_| format!("invalid response: \"{}\"", line))
+}
+
+#[cfg(any(test, feature = "mockdata"))]
+pub mod tests {
+    use std::{
+        collections::HashMap,
+        io::{BufRead, BufReader},
+        net::TcpListener,
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    use super::*;
+
+    // ** The request received by the local HTTP stand-in. **
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct RequestTest {
+        pub method: String,
+        pub path: String,
+        pub headers: HashMap<String, String>, // The names of the headers are in lowercase.
+        pub body: String,
+    }
+
+    // ** Local HTTP stand-in of the webhook receiver. **
+    // Responds to each request with the next status from the list (the last status is repeated).
+    pub struct ReceiverTest {
+        pub url: String,
+        pub requests: Arc<Mutex<Vec<RequestTest>>>,
+    }
+
+    impl ReceiverTest {
+        pub fn start(statuses: &[u16]) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}/hook", listener.local_addr().unwrap());
+            let requests: Arc<Mutex<Vec<RequestTest>>> = Arc::new(Mutex::new(Vec::new()));
+            let requests2 = requests.clone();
+            let statuses = statuses.to_vec();
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let Ok(mut stream) = stream else { break };
+                    let Some(request) = Self::read_request(&stream) else { continue };
+                    let mut list = requests2.lock().unwrap();
+                    list.push(request);
+                    let status = statuses.get(list.len() - 1).or(statuses.last()).cloned().unwrap_or(200);
+                    let response = format!("HTTP/1.1 {} Test\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+                    let _ = stream.write_all(response.as_bytes());
+                }
+            });
+            ReceiverTest { url, requests }
+        }
+        fn read_request(stream: &TcpStream) -> Option<RequestTest> {
+            let mut reader = BufReader::new(stream);
+            let mut line = String::new();
+            reader.read_line(&mut line).ok()?;
+            let mut parts = line.split_whitespace();
+            let method = parts.next()?.to_owned();
+            let path = parts.next()?.to_owned();
+            let mut headers: HashMap<String, String> = HashMap::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).ok()?;
+                let line = line.trim_end();
+                if line.is_empty() {
+                    break;
+                }
+                let (name, value) = line.split_once(':')?;
+                headers.insert(name.trim().to_lowercase(), value.trim().to_owned());
+            }
+            let len: usize = headers.get("content-length").and_then(|v| v.parse().ok()).unwrap_or(0);
+            let mut body = vec![0u8; len];
+            reader.read_exact(&mut body).ok()?;
+            let body = String::from_utf8(body).ok()?;
+            Some(RequestTest {
+                method,
+                path,
+                headers,
+                body,
+            })
+        }
+        pub fn requests(&self) -> Vec<RequestTest> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    // ** hmac_sha256_hex, sign_payload **
+
+    #[test]
+    fn test_hmac_sha256_hex() {
+        // RFC 4231, test case 2.
+        let hmac = hmac_sha256_hex(b"Jefe", b"what do ya want for nothing?").unwrap();
+        assert_eq!(hmac, "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+    }
+    #[test]
+    fn test_sign_payload() {
+        let signature = sign_payload("Jefe", 1700000000, "{}").unwrap();
+        let hmac = hmac_sha256_hex(b"Jefe", b"1700000000.{}").unwrap();
+        assert_eq!(signature, format!("{}{}", SIGNATURE_PREFIX, hmac));
+    }
+    #[test]
+    fn test_generate_secret() {
+        let secret1 = generate_secret();
+        let secret2 = generate_secret();
+        assert_eq!(secret1.len(), SECRET_LENGTH * 2);
+        assert!(secret1.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(secret1, secret2);
+    }
+
+    // ** WebhookUrl::parse **
+
+    #[test]
+    fn test_webhook_url_parse() {
+        let url = WebhookUrl::parse("https://example.com/api/hook?a=1").unwrap();
+        #[rustfmt::skip]
+        assert_eq!(url, WebhookUrl { is_https: true, host: "example.com".to_owned(), port: 443, path: "/api/hook?a=1".to_owned() });
+        let url = WebhookUrl::parse("http://127.0.0.1:8080").unwrap();
+        #[rustfmt::skip]
+        assert_eq!(url, WebhookUrl { is_https: false, host: "127.0.0.1".to_owned(), port: 8080, path: "/".to_owned() });
+        assert_eq!(url.host_header(), "127.0.0.1:8080");
+        let url = WebhookUrl::parse("http://[::1]:9000/hook").unwrap();
+        #[rustfmt::skip]
+        assert_eq!(url, WebhookUrl { is_https: false, host: "::1".to_owned(), port: 9000, path: "/hook".to_owned() });
+        assert_eq!(url.host_header(), "[::1]:9000");
+        assert!(WebhookUrl::parse("ftp://example.com").is_err());
+        assert!(WebhookUrl::parse("http://:80/hook").is_err());
+        assert!(WebhookUrl::parse("http://example.com:port/hook").is_err());
+    }
+    #[test]
+    fn test_parse_status_line() {
+        assert_eq!(parse_status_line("HTTP/1.1 204 No Content\r\n"), Ok(204));
+        assert!(parse_status_line("SSH-2.0-OpenSSH\r\n").is_err());
+        assert!(parse_status_line("").is_err());
+    }
+
+    // ** post_json **
+
+    #[test]
+    fn test_post_json_to_receiver() {
+        let receiver = ReceiverTest::start(&[202]);
+        let headers = vec![(HEADER_EVENT, "stream.created".to_owned())];
+        let result = post_json(&receiver.url, &headers, "{\"id\":1}", Duration::from_secs(2), true);
+        assert_eq!(result, Ok(202));
+
+        let requests = receiver.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/hook");
+        assert_eq!(requests[0].headers.get("content-type").unwrap(), "application/json");
+        assert_eq!(requests[0].headers.get("x-verbena-event").unwrap(), "stream.created");
+        assert_eq!(requests[0].body, "{\"id\":1}");
+    }
+    #[test]
+    fn test_post_json_slow_response() {
+        // The receiver sends the response one byte at a time, each within the timeout of a single read.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            let Ok((mut stream, _)) = listener.accept() else { return };
+            let _ = ReceiverTest::read_request(&stream);
+            for byte in b"HTTP/1.1 200 OK\r\n\r\n" {
+                if stream.write_all(&[*byte]).is_err() {
+                    break;
+                }
+                thread::sleep(Duration::from_millis(100));
+            }
+        });
+        let start = Instant::now();
+        let result = post_json(&url, &[], "{}", Duration::from_millis(500), true);
+        assert!(result.unwrap_err().starts_with("read: "));
+        // The whole delivery is limited by the timeout.
+        assert!(start.elapsed() < Duration::from_millis(1500));
+    }
+    #[test]
+    fn test_post_json_connection_refused() {
+        // Take a free port and release it, so that nobody listens to it.
+        let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let url = format!("http://127.0.0.1:{}/hook", port);
+        let result = post_json(&url, &[], "{}", Duration::from_secs(2), true);
+        assert!(result.unwrap_err().starts_with("connect "));
+    }
+    #[test]
+    fn test_post_json_to_private_address() {
+        let receiver = ReceiverTest::start(&[200]);
+        let result = post_json(&receiver.url, &[], "{}", Duration::from_secs(2), false);
+        assert!(result.unwrap_err().contains("address not allowed: 127.0.0.1"));
+        // The name is checked by its address.
+        let url = receiver.url.replace("127.0.0.1", "localhost");
+        let result = post_json(&url, &[], "{}", Duration::from_secs(2), false);
+        assert!(result.unwrap_err().contains("address not allowed"));
+        assert_eq!(receiver.requests().len(), 0);
+    }
+
+    // ** is_public_ip, is_public_host **
+
+    #[test]
+    fn test_is_public_ip() {
+        #[rustfmt::skip]
+        let private_ips = [
+            "0.0.0.0", "127.0.0.1", "10.1.2.3", "172.16.0.1", "192.168.1.1", "169.254.169.254", "100.64.0.1",
+            "255.255.255.255", "224.0.0.1", "::", "::1", "::ffff:127.0.0.1", "::ffff:10.0.0.1", "64:ff9b::a00:1",
+            "fc00::1", "fd12:3456::1", "fe80::1", "::127.0.0.1",
+        ];
+        for ip in private_ips {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+        #[rustfmt::skip]
+        let public_ips = ["8.8.8.8", "93.184.216.34", "2606:2800:220:1::1", "::ffff:8.8.8.8", "64:ff9b::808:808"];
+        for ip in public_ips {
+            assert!(is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+    }
+    #[test]
+    fn test_is_public_host() {
+        assert!(!is_public_host("127.0.0.1"));
+        assert!(!is_public_host("::1"));
+        assert!(!is_public_host("[::1]"));
+        assert!(!is_public_host("localhost"));
+        assert!(!is_public_host("LocalHost."));
+        assert!(!is_public_host("api.localhost"));
+        assert!(is_public_host("example.com"));
+        assert!(is_public_host("8.8.8.8"));
+    }
+}
@@ -0,0 +1,174 @@
+#[cfg(all(test, feature = "mockdata"))]
+mod tests {
+    use actix_web::{
+        self, App, body, dev,
+        http::StatusCode,
+        http::header::{CONTENT_TYPE, HeaderValue},
+        test,
+    };
+    use serde_json;
+    use vrb_authent::{
+        config_jwt,
+        user_orm::tests::{ADMIN, USER, USER1_ID, USER2_ID, UserOrmTest},
+    };
+    use vrb_common::{
+        api_error::{ApiError, code_to_str},
+        err,
+    };
+
+    use crate::{
+        webhook_controller::{get_webhook_deliveries, get_webhooks, tests as WebhookCtrlTest},
+        webhook_models::{WebhookDelivery, WebhookDeliveryDto, WebhookDto, WebhookEvent},
+        webhook_orm::tests::WebhookOrmTest,
+    };
+
+    const MSG_FAILED_DESER: &str = "Failed to deserialize response from JSON.";
+    const MSG_CASTING_TO_TYPE: &str = "invalid digit found in string";
+
+    // ** get_webhooks **
+
+    #[actix_web::test]
+    async fn test_get_webhooks_only_own() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER, USER]);
+        let webhooks = WebhookOrmTest::webhooks(&[USER1_ID, USER2_ID, USER1_ID], &[WebhookEvent::StreamState]);
+        let webhook1_dto = WebhookDto::from(webhooks.get(0).unwrap().clone());
+        let webhook3_dto = WebhookDto::from(webhooks.get(2).unwrap().clone());
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_webhooks)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(WebhookOrmTest::cfg_webhook_orm(webhooks))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri("/api/webhooks")
+            .insert_header(WebhookCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let webhook_dto_res: Vec<WebhookDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(webhook_dto_res.len(), 2);
+        let json1 = serde_json::json!(webhook1_dto).to_string();
+        let webhook1_dto_ser: WebhookDto = serde_json::from_slice(json1.as_bytes()).expect(MSG_FAILED_DESER);
+        assert_eq!(webhook_dto_res.get(0).unwrap(), &webhook1_dto_ser);
+        assert_eq!(webhook_dto_res.get(1).unwrap().id, webhook3_dto.id);
+        // The secret of the webhook is not returned.
+        assert!(webhook_dto_res.iter().all(|v| v.secret.is_none()));
+    }
+
+    // ** get_webhook_deliveries **
+
+    #[actix_web::test]
+    async fn test_get_webhook_deliveries_invalid_id() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let webhooks = WebhookOrmTest::webhooks(&[USER1_ID], &[WebhookEvent::StreamState]);
+        let webhook_id_bad = format!("{}a", webhooks.get(0).unwrap().id);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_webhook_deliveries)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(WebhookOrmTest::cfg_webhook_orm(webhooks))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/webhooks/{}/deliveries", webhook_id_bad))
+            .insert_header(WebhookCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::RANGE_NOT_SATISFIABLE); // 416
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::RANGE_NOT_SATISFIABLE));
+        #[rustfmt::skip]
+        let msg = format!("{}; `{}` - {}", err::MSG_PARSING_TYPE_NOT_SUPPORTED, "id", MSG_CASTING_TO_TYPE);
+        assert!(app_err.message.starts_with(&msg));
+    }
+    #[actix_web::test]
+    async fn test_get_webhook_deliveries_webhook_of_another_user() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER, USER]);
+        let webhooks = WebhookOrmTest::webhooks(&[USER2_ID], &[WebhookEvent::StreamState]);
+        let webhook2_id = webhooks.get(0).unwrap().id;
+        let deliveries = vec![WebhookDelivery::new(0, webhook2_id, WebhookEvent::StreamState, "{}")];
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_webhook_deliveries)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(WebhookOrmTest::cfg_webhook_delivery_orm(webhooks, deliveries))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/webhooks/{}/deliveries", webhook2_id))
+            .insert_header(WebhookCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT); // 204
+    }
+    #[actix_web::test]
+    async fn test_get_webhook_deliveries_own_webhook() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER, USER]);
+        let webhooks = WebhookOrmTest::webhooks(&[USER1_ID, USER2_ID], &[WebhookEvent::StreamState]);
+        let webhook1_id = webhooks.get(0).unwrap().id;
+        let webhook2_id = webhooks.get(1).unwrap().id;
+        #[rustfmt::skip]
+        let deliveries = vec![
+            WebhookDelivery::new(0, webhook1_id, WebhookEvent::StreamState, "{\"n\":1}"),
+            WebhookDelivery::new(0, webhook2_id, WebhookEvent::StreamState, "{\"n\":2}"),
+            WebhookDelivery::new(0, webhook1_id, WebhookEvent::StreamState, "{\"n\":3}"),
+        ];
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_webhook_deliveries)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(WebhookOrmTest::cfg_webhook_delivery_orm(webhooks, deliveries))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/webhooks/{}/deliveries", webhook1_id))
+            .insert_header(WebhookCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let delivery_dto_res: Vec<WebhookDeliveryDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(delivery_dto_res.len(), 2);
+        assert!(delivery_dto_res.iter().all(|v| v.webhook_id == webhook1_id));
+        // New deliveries come first.
+        assert_eq!(delivery_dto_res.get(0).unwrap().payload, "{\"n\":3}");
+        assert_eq!(delivery_dto_res.get(1).unwrap().payload, "{\"n\":1}");
+    }
+    #[actix_web::test]
+    async fn test_get_webhook_deliveries_admin_webhook_of_another_user() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN, USER]);
+        let webhooks = WebhookOrmTest::webhooks(&[USER2_ID], &[WebhookEvent::StreamState]);
+        let webhook2_id = webhooks.get(0).unwrap().id;
+        let deliveries = vec![WebhookDelivery::new(0, webhook2_id, WebhookEvent::StreamState, "{}")];
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(get_webhook_deliveries)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(WebhookOrmTest::cfg_webhook_delivery_orm(webhooks, deliveries))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::get().uri(&format!("/api/webhooks/{}/deliveries", webhook2_id))
+            .insert_header(WebhookCtrlTest::header_auth(&token1)).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let delivery_dto_res: Vec<WebhookDeliveryDto> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(delivery_dto_res.len(), 1);
+        assert_eq!(delivery_dto_res.get(0).unwrap().webhook_id, webhook2_id);
+    }
+}
@@ -0,0 +1,254 @@
+#[cfg(all(test, feature = "mockdata"))]
+mod tests {
+    use std::borrow::Cow;
+
+    use actix_web::{
+        self, App, body, dev,
+        http::StatusCode,
+        http::header::{CONTENT_TYPE, HeaderValue},
+        test,
+    };
+    use serde_json;
+    use vrb_authent::{
+        config_jwt,
+        user_orm::tests::{ADMIN, USER, USER1_ID, USER2_ID, UserOrmTest},
+    };
+    use vrb_common::{
+        api_error::{ApiError, code_to_str},
+        err, validators,
+    };
+
+    use crate::{
+        webhook_controller::{post_webhook, put_webhook, tests as WebhookCtrlTest},
+        webhook_models::{self, CreateWebhookDto, ModifyWebhookDto, WebhookDto, WebhookEvent},
+        webhook_orm::tests::WebhookOrmTest,
+        webhook_sender,
+    };
+
+    const MSG_FAILED_DESER: &str = "Failed to deserialize response from JSON.";
+    const MSG_CASTING_TO_TYPE: &str = "invalid digit found in string";
+
+    // ** post_webhook **
+
+    async fn call_post_webhook(create_webhook_dto: CreateWebhookDto) -> dev::ServiceResponse {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(post_webhook)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(WebhookOrmTest::cfg_webhook_orm(vec![]))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::post().uri("/api/webhooks")
+            .insert_header(WebhookCtrlTest::header_auth(&token1))
+            .set_json(create_webhook_dto).to_request();
+        test::call_service(&app, req).await
+    }
+    async fn check_post_webhook_invalid(url: &str, events: Vec<WebhookEvent>, msgs: &[&str]) {
+        let resp = call_post_webhook(CreateWebhookDto {
+            url: url.to_owned(),
+            events,
+            is_active: None,
+        })
+        .await;
+        assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err_vec: Vec<ApiError> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        WebhookCtrlTest::check_app_err(app_err_vec, &code_to_str(StatusCode::EXPECTATION_FAILED), msgs);
+    }
+    #[actix_web::test]
+    async fn test_post_webhook_url_empty() {
+        let events = vec![WebhookEvent::StreamState];
+        check_post_webhook_invalid("", events, &[webhook_models::MSG_URL_REQUIRED]).await;
+    }
+    #[actix_web::test]
+    async fn test_post_webhook_url_max() {
+        let url = format!("https://example.com/{}", "a".repeat(webhook_models::URL_MAX.into()));
+        let events = vec![WebhookEvent::StreamState];
+        check_post_webhook_invalid(&url, events, &[webhook_models::MSG_URL_MAX_LENGTH]).await;
+    }
+    #[actix_web::test]
+    async fn test_post_webhook_url_wrong() {
+        let events = vec![WebhookEvent::StreamState];
+        check_post_webhook_invalid("ftp://example.com/hook", events, &[webhook_models::MSG_URL_REGEX]).await;
+    }
+    #[actix_web::test]
+    async fn test_post_webhook_url_host_not_allowed() {
+        #[rustfmt::skip]
+        let urls = [
+            "http://127.0.0.1:8080/hook", "http://10.0.0.5/hook", "http://169.254.169.254/latest/meta-data",
+            "http://[::1]:9000/hook", "http://localhost/hook", "https://example.com:port/hook",
+        ];
+        for url in urls {
+            let events = vec![WebhookEvent::StreamState];
+            check_post_webhook_invalid(url, events, &[webhook_models::MSG_URL_HOST_NOT_ALLOWED]).await;
+        }
+    }
+    #[actix_web::test]
+    async fn test_post_webhook_events_empty() {
+        check_post_webhook_invalid("https://example.com/hook", vec![], &[webhook_models::MSG_EVENTS_MIN_AMOUNT]).await;
+    }
+    #[actix_web::test]
+    async fn test_post_webhook_valid_data() {
+        let url = "https://example.com/hook";
+        let events = vec![WebhookEvent::StreamCreated, WebhookEvent::ChatMessage];
+        let resp = call_post_webhook(CreateWebhookDto {
+            url: url.to_owned(),
+            events: events.clone(),
+            is_active: None,
+        })
+        .await;
+        assert_eq!(resp.status(), StatusCode::CREATED); // 201
+
+        #[rustfmt::skip]
+        assert_eq!(resp.headers().get(CONTENT_TYPE).unwrap(), HeaderValue::from_static("application/json"));
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let webhook_dto_res: WebhookDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(webhook_dto_res.user_id, USER1_ID);
+        assert_eq!(webhook_dto_res.url, url);
+        assert_eq!(webhook_dto_res.events, events);
+        assert_eq!(webhook_dto_res.is_active, true);
+        // Only the response to the creation contains the secret of the webhook.
+        let secret = webhook_dto_res.secret.unwrap();
+        assert_eq!(secret.len(), 2 * webhook_sender::SECRET_LENGTH);
+    }
+
+    // ** put_webhook **
+
+    #[actix_web::test]
+    async fn test_put_webhook_invalid_id() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let webhooks = WebhookOrmTest::webhooks(&[USER1_ID], &[WebhookEvent::StreamState]);
+        let webhook_id_bad = format!("{}a", webhooks.get(0).unwrap().id);
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_webhook)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(WebhookOrmTest::cfg_webhook_orm(webhooks))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/webhooks/{}", webhook_id_bad))
+            .insert_header(WebhookCtrlTest::header_auth(&token1))
+            .set_json(ModifyWebhookDto { url: None, events: None, is_active: Some(false) }).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::RANGE_NOT_SATISFIABLE); // 416
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err: ApiError = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err.code, code_to_str(StatusCode::RANGE_NOT_SATISFIABLE));
+        #[rustfmt::skip]
+        let msg = format!("{}; `{}` - {}", err::MSG_PARSING_TYPE_NOT_SUPPORTED, "id", MSG_CASTING_TO_TYPE);
+        assert!(app_err.message.starts_with(&msg));
+    }
+    #[actix_web::test]
+    async fn test_put_webhook_no_fields() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let webhooks = WebhookOrmTest::webhooks(&[USER1_ID], &[WebhookEvent::StreamState]);
+        let webhook1_id = webhooks.get(0).unwrap().id;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_webhook)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(WebhookOrmTest::cfg_webhook_orm(webhooks))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/webhooks/{}", webhook1_id))
+            .insert_header(WebhookCtrlTest::header_auth(&token1))
+            .set_json(ModifyWebhookDto { url: None, events: None, is_active: None }).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::EXPECTATION_FAILED); // 417
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let app_err_vec: Vec<ApiError> = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(app_err_vec.len(), 1);
+        let app_err = app_err_vec.get(0).unwrap();
+        assert_eq!(app_err.message, err::MSG_NO_FIELDS_TO_UPDATE);
+        let key = Cow::Borrowed(validators::NM_NO_FIELDS_TO_UPDATE);
+        #[rustfmt::skip]
+        let names1 = app_err.params.get(&key).unwrap().get("validNames").unwrap().as_str().unwrap();
+        assert_eq!(names1, ModifyWebhookDto::valid_names().join(","));
+    }
+    #[actix_web::test]
+    async fn test_put_webhook_webhook_of_another_user() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER, USER]);
+        let webhooks = WebhookOrmTest::webhooks(&[USER2_ID], &[WebhookEvent::StreamState]);
+        let webhook2_id = webhooks.get(0).unwrap().id;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_webhook)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(WebhookOrmTest::cfg_webhook_orm(webhooks))
+        ).await;
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/webhooks/{}", webhook2_id))
+            .insert_header(WebhookCtrlTest::header_auth(&token1))
+            .set_json(ModifyWebhookDto { url: None, events: None, is_active: Some(false) }).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT); // 204
+    }
+    #[actix_web::test]
+    async fn test_put_webhook_valid_data() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[USER]);
+        let webhooks = WebhookOrmTest::webhooks(&[USER1_ID], &[WebhookEvent::StreamState]);
+        let webhook1 = webhooks.get(0).unwrap().clone();
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_webhook)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(WebhookOrmTest::cfg_webhook_orm(webhooks))
+        ).await;
+        let events = vec![WebhookEvent::StreamCreated, WebhookEvent::StreamDeleted];
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/webhooks/{}", webhook1.id))
+            .insert_header(WebhookCtrlTest::header_auth(&token1))
+            .set_json(ModifyWebhookDto { url: None, events: Some(events.clone()), is_active: Some(false) }).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let webhook_dto_res: WebhookDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(webhook_dto_res.id, webhook1.id);
+        assert_eq!(webhook_dto_res.url, webhook1.url);
+        assert_eq!(webhook_dto_res.events, events);
+        assert_eq!(webhook_dto_res.is_active, false);
+        assert_eq!(webhook_dto_res.secret, None);
+    }
+    #[actix_web::test]
+    async fn test_put_webhook_admin_webhook_of_another_user() {
+        let token1 = config_jwt::tests::get_token(USER1_ID);
+        let data_u = UserOrmTest::users(&[ADMIN, USER]);
+        let webhooks = WebhookOrmTest::webhooks(&[USER2_ID], &[WebhookEvent::StreamState]);
+        let webhook2_id = webhooks.get(0).unwrap().id;
+        #[rustfmt::skip]
+        let app = test::init_service(
+            App::new().service(put_webhook)
+                .configure(config_jwt::tests::cfg_config_jwt(config_jwt::tests::get_config()))
+                .configure(UserOrmTest::cfg_user_orm(data_u))
+                .configure(WebhookOrmTest::cfg_webhook_orm(webhooks))
+        ).await;
+        let url = "https://example.org:8081/hook";
+        #[rustfmt::skip]
+        let req = test::TestRequest::put().uri(&format!("/api/webhooks/{}", webhook2_id))
+            .insert_header(WebhookCtrlTest::header_auth(&token1))
+            .set_json(ModifyWebhookDto { url: Some(url.to_owned()), events: None, is_active: None }).to_request();
+        let resp: dev::ServiceResponse = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK); // 200
+
+        let body = body::to_bytes(resp.into_body()).await.unwrap();
+        let webhook_dto_res: WebhookDto = serde_json::from_slice(&body).expect(MSG_FAILED_DESER);
+        assert_eq!(webhook_dto_res.user_id, USER2_ID);
+        assert_eq!(webhook_dto_res.url, url);
+    }
+}